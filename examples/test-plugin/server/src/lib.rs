@@ -45,8 +45,9 @@ struct PostMessageOutput {
 extern "ExtismHost" {
     fn log_info(msg: String);
 
-    fn store_set(collection: String, key: String, value: String);
-    fn store_get(collection: String, key: String) -> String;
+    fn store_set(input: String);
+    fn store_get(input: String) -> String;
+    fn store_has(input: String) -> String;
 
     fn db_execute(sql: String) -> u64;
     fn db_query(sql: String) -> String;
@@ -67,21 +68,28 @@ pub fn greet(input: String) -> FnResult<String> {
         log_info(format!("Guest is greeting user: {}", args.name))?;
     }
 
-    let collection = "stats".to_string();
-    let key = args.name.clone();
+    let key = format!("stats:{}", args.name);
 
-    let raw_value = unsafe { store_get(collection.clone(), key.clone())? };
-    let mut count: u32 = if raw_value == "null" {
-        0
+    // A stored value may itself be `null`, so ask whether the key exists
+    // instead of inspecting what `store_get` returns.
+    let has_input = serde_json::json!({ "key": key }).to_string();
+    let has: serde_json::Value = serde_json::from_str(&unsafe { store_has(has_input)? })?;
+    let mut count: u32 = if has["exists"].as_bool().unwrap_or(false) {
+        let get_input = serde_json::json!({ "keys": [key] }).to_string();
+        let got: serde_json::Value = serde_json::from_str(&unsafe { store_get(get_input)? })?;
+        serde_json::from_str(got["values"][&key].as_str().unwrap_or("0"))?
     } else {
-        serde_json::from_str(&raw_value)?
+        0
     };
 
     count += 1;
 
-    let new_value = serde_json::to_string(&count)?;
+    let set_input = serde_json::json!({
+        "entries": [{ "key": key, "value": serde_json::to_string(&count)? }]
+    })
+    .to_string();
     unsafe {
-        store_set(collection, key, new_value)?;
+        store_set(set_input)?;
     }
 
     let output = DemoOutput {
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::retry::BackoffJitter;
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DlqConfig {
     #[serde(default = "default_dlq_max_retries")]
@@ -8,6 +10,9 @@ pub struct DlqConfig {
     pub base_delay_ms: u64,
     #[serde(default = "default_dlq_max_delay_ms")]
     pub max_delay_ms: u64,
+    /// Randomization applied to retry delays.
+    #[serde(default)]
+    pub backoff_jitter: BackoffJitter,
     #[serde(default = "default_dlq_stuck_job_timeout_secs")]
     pub stuck_job_timeout_secs: u64,
     #[serde(default = "default_dlq_stuck_job_scan_interval_secs")]
@@ -16,6 +21,10 @@ pub struct DlqConfig {
     pub retry_cleanup_interval_secs: u64,
     #[serde(default = "default_dlq_retry_max_age_secs")]
     pub retry_max_age_secs: u64,
+    /// Most recent attempts kept in a message's retry history; older ones
+    /// are dropped. `0` keeps every attempt.
+    #[serde(default = "default_dlq_max_retry_history")]
+    pub max_retry_history: usize,
 }
 
 fn default_dlq_max_retries() -> u8 {
@@ -39,6 +48,9 @@ fn default_dlq_retry_cleanup_interval_secs() -> u64 {
 fn default_dlq_retry_max_age_secs() -> u64 {
     7200
 }
+fn default_dlq_max_retry_history() -> usize {
+    20
+}
 
 impl Default for DlqConfig {
     fn default() -> Self {
@@ -46,10 +58,12 @@ impl Default for DlqConfig {
             max_retries: default_dlq_max_retries(),
             base_delay_ms: default_dlq_base_delay_ms(),
             max_delay_ms: default_dlq_max_delay_ms(),
+            backoff_jitter: BackoffJitter::default(),
             stuck_job_timeout_secs: default_dlq_stuck_job_timeout_secs(),
             stuck_job_scan_interval_secs: default_dlq_stuck_job_scan_interval_secs(),
             retry_cleanup_interval_secs: default_dlq_retry_cleanup_interval_secs(),
             retry_max_age_secs: default_dlq_retry_max_age_secs(),
+            max_retry_history: default_dlq_max_retry_history(),
         }
     }
 }
@@ -68,6 +82,33 @@ pub struct MqAppConfig {
     pub operation_result_queue_name: String,
     #[serde(default = "default_operation_dlq_queue_name")]
     pub operation_dlq_queue_name: String,
+    /// Operation tasks a worker judges at once, per subscribed queue.
+    #[serde(default = "default_operation_concurrency")]
+    pub operation_concurrency: usize,
+    /// Operation results the server handles at once.
+    #[serde(default = "default_operation_result_concurrency")]
+    pub operation_result_concurrency: usize,
+    /// Dead-lettered operations the server persists at once.
+    #[serde(default = "default_operation_dlq_concurrency")]
+    pub operation_dlq_concurrency: usize,
+    /// Recently delivered operation results remembered so a redelivered
+    /// result is dropped instead of reaching a waiter twice. 0 disables.
+    #[serde(default = "default_operation_result_dedup_window")]
+    pub operation_result_dedup_window: usize,
+    /// Spread contest operation results over this many extra per-replica
+    /// result queues, chosen by contest id, each with its own consumer, so a
+    /// busy contest cannot starve result handling for the rest. Results
+    /// outside any contest stay on `operation_result_queue_name`. 0 keeps a
+    /// single result queue.
+    #[serde(default)]
+    pub operation_result_contest_shards: usize,
+    /// Delay before the first attempt to resume a consumer after the broker
+    /// connection drops; doubled on each further failure.
+    #[serde(default = "default_reconnect_base_delay_ms")]
+    pub reconnect_base_delay_ms: u64,
+    /// Upper bound on the reconnect delay.
+    #[serde(default = "default_reconnect_max_delay_ms")]
+    pub reconnect_max_delay_ms: u64,
     #[serde(default)]
     pub dlq: DlqConfig,
 }
@@ -90,6 +131,24 @@ fn default_operation_result_queue_name() -> String {
 fn default_operation_dlq_queue_name() -> String {
     "operation_tasks_dlq".into()
 }
+fn default_operation_concurrency() -> usize {
+    1
+}
+fn default_operation_result_concurrency() -> usize {
+    8
+}
+fn default_operation_dlq_concurrency() -> usize {
+    1
+}
+fn default_operation_result_dedup_window() -> usize {
+    10_000
+}
+fn default_reconnect_base_delay_ms() -> u64 {
+    1000
+}
+fn default_reconnect_max_delay_ms() -> u64 {
+    30_000
+}
 
 impl Default for MqAppConfig {
     fn default() -> Self {
@@ -100,6 +159,13 @@ impl Default for MqAppConfig {
             operation_queue_name: default_operation_queue_name(),
             operation_result_queue_name: default_operation_result_queue_name(),
             operation_dlq_queue_name: default_operation_dlq_queue_name(),
+            operation_concurrency: default_operation_concurrency(),
+            operation_result_concurrency: default_operation_result_concurrency(),
+            operation_dlq_concurrency: default_operation_dlq_concurrency(),
+            operation_result_dedup_window: default_operation_result_dedup_window(),
+            operation_result_contest_shards: 0,
+            reconnect_base_delay_ms: default_reconnect_base_delay_ms(),
+            reconnect_max_delay_ms: default_reconnect_max_delay_ms(),
             dlq: DlqConfig::default(),
         }
     }
@@ -14,6 +14,8 @@ pub enum DlqErrorCode {
     MaxRetriesExceeded,
     DeserializationError,
     StuckJob,
+    /// The receiving endpoint answered with a non-retryable status.
+    DeliveryRejected,
 }
 
 impl DlqErrorCode {
@@ -22,6 +24,7 @@ impl DlqErrorCode {
             Self::MaxRetriesExceeded => "MAX_RETRIES_EXCEEDED",
             Self::DeserializationError => "DESERIALIZATION_ERROR",
             Self::StuckJob => "STUCK_JOB",
+            Self::DeliveryRejected => "DELIVERY_REJECTED",
         }
     }
 }
@@ -37,6 +40,7 @@ impl std::fmt::Display for DlqErrorCode {
 pub enum DlqMessageType {
     OperationTask,
     StuckSubmission,
+    WebhookDelivery,
 }
 
 impl DlqMessageType {
@@ -44,6 +48,7 @@ impl DlqMessageType {
         match self {
             Self::OperationTask => "operation_task",
             Self::StuckSubmission => "stuck_submission",
+            Self::WebhookDelivery => "webhook_delivery",
         }
     }
 }
@@ -61,8 +66,9 @@ impl std::str::FromStr for DlqMessageType {
         match s {
             "operation_task" => Ok(Self::OperationTask),
             "stuck_submission" => Ok(Self::StuckSubmission),
+            "webhook_delivery" => Ok(Self::WebhookDelivery),
             _ => Err(format!(
-                "Invalid message_type '{}'. Must be 'operation_task' or 'stuck_submission'",
+                "Invalid message_type '{}'. Must be 'operation_task', 'stuck_submission' or 'webhook_delivery'",
                 s
             )),
         }
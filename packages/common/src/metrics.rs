@@ -12,6 +12,7 @@ pub struct Metrics {
     pub dlq_messages_total: Counter<u64>,
 
     pub plugin_call_duration: Histogram<f64>,
+    pub standings_cache_requests: Counter<u64>,
 
     pub mq_queue_depth: UpDownCounter<i64>,
 }
@@ -70,6 +71,10 @@ impl Metrics {
                 .with_description("Duration of WASM plugin calls")
                 .with_boundaries(PLUGIN_BUCKETS_SECONDS.to_vec())
                 .build(),
+            standings_cache_requests: meter
+                .u64_counter("broccoli.standings_cache.requests")
+                .with_description("Standings cache lookups, labelled by hit or miss")
+                .build(),
 
             mq_queue_depth: meter
                 .i64_up_down_counter("broccoli.mq.queue.depth")
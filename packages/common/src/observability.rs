@@ -100,6 +100,11 @@ pub fn inject_trace_context() -> Option<String> {
     ))
 }
 
+/// Returns the trace id segment of a W3C `traceparent` value.
+pub fn trace_id_from_traceparent(traceparent: &str) -> Option<&str> {
+    traceparent.split('-').nth(1).filter(|id| !id.is_empty())
+}
+
 pub fn extract_trace_context(traceparent: &str) -> Option<opentelemetry::Context> {
     use opentelemetry::trace::{
         SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
@@ -58,6 +58,8 @@ impl RetryState {
 pub struct RetryTracker {
     state: HashMap<String, RetryState>,
     max_retries: u8,
+    /// Most recent attempts kept per message; `0` keeps all of them.
+    max_history: usize,
 }
 
 impl RetryTracker {
@@ -65,9 +67,17 @@ impl RetryTracker {
         Self {
             state: HashMap::new(),
             max_retries,
+            max_history: 0,
         }
     }
 
+    /// Bounds each message's retry history to its `max_history` most recent
+    /// attempts. `0` leaves it unbounded.
+    pub fn with_max_history(mut self, max_history: usize) -> Self {
+        self.max_history = max_history;
+        self
+    }
+
     pub fn record_failure(&mut self, id: &str, error: &str) -> RetryDecision {
         let retry_state = self
             .state
@@ -79,6 +89,10 @@ impl RetryTracker {
         retry_state
             .history
             .push(RetryAttempt::new(retry_state.attempt, error));
+        if self.max_history > 0 && retry_state.history.len() > self.max_history {
+            let excess = retry_state.history.len() - self.max_history;
+            retry_state.history.drain(..excess);
+        }
 
         if retry_state.attempt <= self.max_retries {
             RetryDecision::Retry {
@@ -117,7 +131,30 @@ impl RetryTracker {
     }
 }
 
-pub fn calculate_backoff(attempt: u8, base_ms: u64, max_ms: u64) -> Duration {
+/// How [`calculate_backoff`] randomizes the exponential delay so retries of
+/// jobs that failed together do not all fire at once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackoffJitter {
+    /// Exactly the exponential delay. Reproducible, meant for tests.
+    None,
+    /// The delay plus up to a quarter of it.
+    #[default]
+    Partial,
+    /// Anywhere between zero and the delay.
+    Full,
+    /// Half the delay plus up to another half.
+    Equal,
+}
+
+/// `base_ms * 2^(attempt - 1)`, randomized per `jitter` and capped at
+/// `max_ms`. Attempt 0 means no delay.
+pub fn calculate_backoff(
+    attempt: u8,
+    base_ms: u64,
+    max_ms: u64,
+    jitter: BackoffJitter,
+) -> Duration {
     if attempt == 0 {
         return Duration::ZERO;
     }
@@ -125,14 +162,17 @@ pub fn calculate_backoff(attempt: u8, base_ms: u64, max_ms: u64) -> Duration {
     let exp_factor = 2u64.saturating_pow((attempt - 1) as u32);
     let delay_ms = base_ms.saturating_mul(exp_factor);
 
-    let jitter = if delay_ms > 0 {
-        rand::rng().random_range(0..=delay_ms / 4)
-    } else {
-        0
+    let mut rng = rand::rng();
+    let total_delay = match jitter {
+        BackoffJitter::None => delay_ms,
+        BackoffJitter::Partial => delay_ms.saturating_add(rng.random_range(0..=delay_ms / 4)),
+        BackoffJitter::Full => rng.random_range(0..=delay_ms.min(max_ms)),
+        BackoffJitter::Equal => {
+            let capped = delay_ms.min(max_ms);
+            capped / 2 + rng.random_range(0..=capped - capped / 2)
+        }
     };
-
-    let total_delay = delay_ms.saturating_add(jitter).min(max_ms);
-    Duration::from_millis(total_delay)
+    Duration::from_millis(total_delay.min(max_ms))
 }
 
 pub struct RetryCleanupGuard<'a> {
@@ -194,31 +234,62 @@ mod tests {
 
     #[test]
     fn backoff_attempt_1_is_base_delay() {
-        let d = calculate_backoff(1, 1000, 60000);
+        let d = calculate_backoff(1, 1000, 60000, BackoffJitter::Partial);
         assert!(d.as_millis() >= 1000 && d.as_millis() <= 1250, "d={d:?}");
     }
 
     #[test]
     fn backoff_attempt_2_doubles_delay() {
-        let d = calculate_backoff(2, 1000, 60000);
+        let d = calculate_backoff(2, 1000, 60000, BackoffJitter::Partial);
         assert!(d.as_millis() >= 2000 && d.as_millis() <= 2500, "d={d:?}");
     }
 
     #[test]
     fn backoff_attempt_3_quadruples_delay() {
-        let d = calculate_backoff(3, 1000, 60000);
+        let d = calculate_backoff(3, 1000, 60000, BackoffJitter::Partial);
         assert!(d.as_millis() >= 4000 && d.as_millis() <= 5000, "d={d:?}");
     }
 
     #[test]
     fn backoff_is_capped_at_max_ms() {
-        let d = calculate_backoff(10, 10000, 60000);
+        let d = calculate_backoff(10, 10000, 60000, BackoffJitter::Partial);
         assert!(d.as_millis() <= 60000, "d={d:?}");
     }
 
     #[test]
     fn backoff_attempt_zero_returns_zero() {
-        assert_eq!(calculate_backoff(0, 1000, 60000), Duration::ZERO);
+        assert_eq!(
+            calculate_backoff(0, 1000, 60000, BackoffJitter::Partial),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn backoff_without_jitter_is_exact_exponential_delay() {
+        for (attempt, expected) in [(1, 1000), (2, 2000), (3, 4000), (10, 60000)] {
+            let d = calculate_backoff(attempt, 1000, 60000, BackoffJitter::None);
+            assert_eq!(d, Duration::from_millis(expected), "attempt={attempt}");
+        }
+    }
+
+    #[test]
+    fn full_jitter_stays_between_zero_and_delay() {
+        for _ in 0..200 {
+            let d = calculate_backoff(3, 1000, 60000, BackoffJitter::Full);
+            assert!(d.as_millis() <= 4000, "d={d:?}");
+            let d = calculate_backoff(10, 1000, 60000, BackoffJitter::Full);
+            assert!(d.as_millis() <= 60000, "d={d:?}");
+        }
+    }
+
+    #[test]
+    fn equal_jitter_stays_between_half_and_full_delay() {
+        for _ in 0..200 {
+            let d = calculate_backoff(3, 1000, 60000, BackoffJitter::Equal);
+            assert!(d.as_millis() >= 2000 && d.as_millis() <= 4000, "d={d:?}");
+            let d = calculate_backoff(10, 1000, 60000, BackoffJitter::Equal);
+            assert!(d.as_millis() >= 30000 && d.as_millis() <= 60000, "d={d:?}");
+        }
     }
 
     #[test]
@@ -252,6 +323,27 @@ mod tests {
         assert_eq!(tracker.get_attempt("msg1"), 0);
     }
 
+    #[test]
+    fn retry_history_is_capped_to_most_recent_attempts() {
+        let mut tracker = RetryTracker::new(50).with_max_history(5);
+
+        let mut last = None;
+        for i in 1..=30 {
+            last = Some(tracker.record_failure("msg1", &format!("error {i}")));
+        }
+
+        match last.unwrap() {
+            RetryDecision::Retry { attempt, history } => {
+                assert_eq!(attempt, 30);
+                assert_eq!(history.len(), 5);
+                assert_eq!(history[0].attempt, 26);
+                assert_eq!(history[4].attempt, 30);
+                assert_eq!(history[4].error, "error 30");
+            }
+            _ => panic!("expected Retry"),
+        }
+    }
+
     #[test]
     fn retry_tracker_cleared_after_success() {
         let mut tracker = RetryTracker::new(3);
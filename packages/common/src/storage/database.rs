@@ -187,6 +187,17 @@ impl BlobStore for DatabaseBlobStore {
         Ok((bytes, offset + len as u64 >= size as u64))
     }
 
+    async fn get_range_stream(
+        &self,
+        hash: &ContentHash,
+        offset: u64,
+        len: u64,
+    ) -> Result<BoxReader, StorageError> {
+        let len = usize::try_from(len).unwrap_or(usize::MAX);
+        let (bytes, _) = self.get_range(hash, offset, len).await?;
+        Ok(Box::new(Cursor::new(bytes)))
+    }
+
     async fn exists(&self, hash: &ContentHash) -> Result<bool, StorageError> {
         let hash_hex = hash.to_hex();
 
@@ -169,6 +169,24 @@ impl BlobStore for FilesystemBlobStore {
         Ok((bytes, offset + filled as u64 >= size))
     }
 
+    async fn get_range_stream(
+        &self,
+        hash: &ContentHash,
+        offset: u64,
+        len: u64,
+    ) -> Result<BoxReader, StorageError> {
+        let blob_path = self.blob_path(hash);
+        let mut file = match fs::File::open(&blob_path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(StorageError::NotFound(hash.to_hex()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        file.seek(std::io::SeekFrom::Start(offset)).await?;
+        Ok(Box::new(BufReader::new(file).take(len)))
+    }
+
     async fn exists(&self, hash: &ContentHash) -> Result<bool, StorageError> {
         let blob_path = self.blob_path(hash);
         Ok(fs::try_exists(&blob_path).await?)
@@ -216,6 +234,17 @@ mod tests {
         assert_eq!(retrieved, data);
     }
 
+    #[tokio::test]
+    async fn range_stream_reads_only_the_range() {
+        let (store, _dir) = temp_store().await;
+        let hash = store.put(b"hello world").await.unwrap();
+
+        let mut reader = store.get_range_stream(&hash, 6, 3).await.unwrap();
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, b"wor");
+    }
+
     #[tokio::test]
     async fn put_is_deterministic() {
         let (store, _dir) = temp_store().await;
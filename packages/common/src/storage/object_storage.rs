@@ -1,3 +1,4 @@
+use std::io::Cursor;
 use std::path::PathBuf;
 
 use async_trait::async_trait;
@@ -202,6 +203,18 @@ impl BlobStore for ObjectStorageBlobStore {
         Ok((bytes, eof))
     }
 
+    /// Issues one ranged GET; the response body for the range is buffered.
+    async fn get_range_stream(
+        &self,
+        hash: &ContentHash,
+        offset: u64,
+        len: u64,
+    ) -> Result<BoxReader, StorageError> {
+        let len = usize::try_from(len).unwrap_or(usize::MAX);
+        let (bytes, _) = self.get_range(hash, offset, len).await?;
+        Ok(Box::new(Cursor::new(bytes)))
+    }
+
     async fn exists(&self, hash: &ContentHash) -> Result<bool, StorageError> {
         let key = Self::object_key(hash);
 
@@ -57,6 +57,20 @@ pub trait BlobStore: Send + Sync {
         Ok((bytes, filled < len))
     }
 
+    /// Streams at most `len` bytes of the blob starting at `offset`. The
+    /// default reads and discards the leading bytes; backends that can seek
+    /// or issue ranged requests override it.
+    async fn get_range_stream(
+        &self,
+        hash: &ContentHash,
+        offset: u64,
+        len: u64,
+    ) -> Result<BoxReader, StorageError> {
+        let mut reader = self.get_stream(hash).await?;
+        tokio::io::copy(&mut (&mut reader).take(offset), &mut tokio::io::sink()).await?;
+        Ok(Box::new(reader.take(len)))
+    }
+
     async fn exists(&self, hash: &ContentHash) -> Result<bool, StorageError>;
 
     async fn delete(&self, hash: &ContentHash) -> Result<bool, StorageError>;
@@ -1,5 +1,6 @@
 pub use broccoli_server_sdk::types::{
-    BuildEvalOpsInput, CheckerParseInput, CheckerVerdict, FileRef, JudgeFile, OnCodeRunInput,
-    OnCodeRunOutput, OnSubmissionInput, OnSubmissionOutput, RunCheckerInput, SourceFile,
-    StartEvaluateBatchInput, TestCaseBodyRef, TestCaseRow, TestCaseVerdict, Verdict as SdkVerdict,
+    BuildEvalOpsInput, CheckerParseInput, CheckerVerdict, FileRef, JudgeFile, JudgeMode,
+    OnCodeRunInput, OnCodeRunOutput, OnSubmissionInput, OnSubmissionOutput, RunCheckerInput,
+    SourceFile, StartEvaluateBatchInput, TestCaseBodyRef, TestCaseRow, TestCaseVerdict,
+    Verdict as SdkVerdict,
 };
@@ -5,6 +5,7 @@ use std::str::FromStr;
 #[cfg(feature = "sea-orm")]
 use sea_orm::entity::prelude::*;
 
+/// Lifecycle state of a submission or code run.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Default, utoipa::ToSchema)]
 #[cfg_attr(
     feature = "sea-orm",
@@ -13,12 +14,18 @@ use sea_orm::entity::prelude::*;
 )]
 #[serde(rename_all = "PascalCase")]
 pub enum SubmissionStatus {
+    /// Queued and waiting for a judge worker.
     #[default]
     Pending,
+    /// The source is being compiled.
     Compiling,
+    /// Test cases are being executed.
     Running,
+    /// Judging finished; see the verdict for the outcome.
     Judged,
+    /// The source failed to compile; no test cases were run.
     CompilationError,
+    /// Judging could not complete because of an internal error.
     SystemError,
 }
 
@@ -105,21 +112,33 @@ impl FromStr for SubmissionStatus {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, utoipa::ToSchema, Default)]
+/// Outcome of judging a submission or a single test case.
+///
+/// Serialized as a plain string. Plugins may report verdicts outside
+/// [`Verdict::ALL`]; those round-trip through [`Verdict::Other`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
 #[cfg_attr(
     feature = "sea-orm",
     derive(DeriveValueType),
     sea_orm(value_type = "String")
 )]
 pub enum Verdict {
+    /// Output matched the expected answer.
     Accepted,
+    /// Output did not match the expected answer.
     WrongAnswer,
+    /// CPU time limit was exceeded.
     TimeLimitExceeded,
+    /// Memory limit was exceeded.
     MemoryLimitExceeded,
+    /// The program crashed or exited with a non-zero status.
     RuntimeError,
+    /// The judge failed; not the contestant's fault.
     #[default]
     SystemError,
+    /// Not run, e.g. because an earlier test case failed.
     Skipped,
+    /// Plugin-defined verdict, serialized as its raw string.
     Other(String),
 }
 
@@ -231,6 +250,50 @@ impl FromStr for Verdict {
     }
 }
 
+impl Verdict {
+    fn description(&self) -> &'static str {
+        match self {
+            Self::Accepted => "output matched the expected answer",
+            Self::WrongAnswer => "output did not match the expected answer",
+            Self::TimeLimitExceeded => "CPU time limit was exceeded",
+            Self::MemoryLimitExceeded => "memory limit was exceeded",
+            Self::RuntimeError => "the program crashed or exited with a non-zero status",
+            Self::SystemError => "the judge failed; not the contestant's fault",
+            Self::Skipped => "not run, e.g. because an earlier test case failed",
+            Self::Other(_) => "plugin-defined verdict",
+        }
+    }
+}
+
+impl utoipa::PartialSchema for Verdict {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::{ObjectBuilder, OneOfBuilder, SchemaType, Type};
+
+        let mut description = String::from(
+            "Outcome of judging a submission or test case: one of the built-in verdicts, \
+             or any other string a plugin reported.\n",
+        );
+        for verdict in Self::ALL {
+            description.push_str(&format!("\n- `{verdict}`: {}", verdict.description()));
+        }
+        OneOfBuilder::new()
+            .item(
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::Type(Type::String))
+                    .enum_values(Some(Self::ALL.iter().map(Verdict::as_str))),
+            )
+            .item(
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::Type(Type::String))
+                    .description(Some(Self::Other(String::new()).description())),
+            )
+            .description(Some(description))
+            .into()
+    }
+}
+
+impl utoipa::ToSchema for Verdict {}
+
 impl Serialize for Verdict {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
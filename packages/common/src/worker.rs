@@ -16,6 +16,22 @@ pub struct Task {
     pub priority: Option<u8>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub trace_context: Option<String>,
+    /// [`TASK_SCHEMA_VERSION`] of the publisher; `None` for tasks published
+    /// before versioning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+}
+
+/// Schema version of [`Task`] this build publishes and understands.
+///
+/// Bump it on incompatible changes; workers dead-letter tasks stamped with a
+/// newer version instead of misreading them.
+pub const TASK_SCHEMA_VERSION: u32 = 1;
+
+/// Whether a task stamped with `version` can be handled by this build. Tasks
+/// without a version predate versioning and are always accepted.
+pub fn is_supported_task_schema(version: Option<u32>) -> bool {
+    version.is_none_or(|v| v <= TASK_SCHEMA_VERSION)
 }
 
 impl Task {
@@ -24,9 +40,46 @@ impl Task {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskResult {
+    pub task_id: String,
+    pub success: bool,
+    pub output: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// `worker.id` of the worker that produced the result; `None` for
+    /// results synthesized elsewhere or sent by older workers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskEvent {
+    Started { task: Task },
+    Completed { result: TaskResult },
+    Failed { task: Task, error: String },
+}
+
+impl Event for TaskEvent {
+    fn topic(&self) -> &str {
+        match self {
+            TaskEvent::Started { .. } => "task_started",
+            TaskEvent::Completed { .. } => "task_completed",
+            TaskEvent::Failed { .. } => "task_failed",
+        }
+    }
+}
+
+#[async_trait]
+pub trait Executor: Send + Sync {
+    fn if_accept(&self, _task_type: &str) -> bool;
+    async fn execute(&self, task: Task) -> Result<TaskResult>;
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Task;
+    use super::{TASK_SCHEMA_VERSION, Task, is_supported_task_schema};
 
     #[test]
     fn reply_queue_defaults_to_result_queue_for_legacy_envelopes() {
@@ -57,37 +110,35 @@ mod tests {
 
         assert_eq!(task.reply_queue_name(), "operation_results.replica-a");
     }
-}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TaskResult {
-    pub task_id: String,
-    pub success: bool,
-    pub output: serde_json::Value,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub error: Option<String>,
-}
+    fn task_with_schema_version(version: Option<u32>) -> Task {
+        let mut task = serde_json::json!({
+            "id": "task-1",
+            "task_type": "operation",
+            "executor_name": "operation",
+            "payload": {},
+            "result_queue": "operation_results"
+        });
+        if let Some(version) = version {
+            task["schema_version"] = version.into();
+        }
+        serde_json::from_value(task).unwrap()
+    }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "snake_case")]
-pub enum TaskEvent {
-    Started { task: Task },
-    Completed { result: TaskResult },
-    Failed { task: Task, error: String },
-}
+    #[test]
+    fn task_with_matching_or_missing_schema_version_is_supported() {
+        let legacy = task_with_schema_version(None);
+        assert_eq!(legacy.schema_version, None);
+        assert!(is_supported_task_schema(legacy.schema_version));
 
-impl Event for TaskEvent {
-    fn topic(&self) -> &str {
-        match self {
-            TaskEvent::Started { .. } => "task_started",
-            TaskEvent::Completed { .. } => "task_completed",
-            TaskEvent::Failed { .. } => "task_failed",
-        }
+        let current = task_with_schema_version(Some(TASK_SCHEMA_VERSION));
+        assert!(is_supported_task_schema(current.schema_version));
     }
-}
 
-#[async_trait]
-pub trait Executor: Send + Sync {
-    fn if_accept(&self, _task_type: &str) -> bool;
-    async fn execute(&self, task: Task) -> Result<TaskResult>;
+    #[test]
+    fn task_with_future_schema_version_is_unsupported() {
+        let future = task_with_schema_version(Some(TASK_SCHEMA_VERSION + 1));
+        assert_eq!(future.schema_version, Some(TASK_SCHEMA_VERSION + 1));
+        assert!(!is_supported_task_schema(future.schema_version));
+    }
 }
@@ -1,3 +1,42 @@
+use crate::reconnect::ReconnectPolicy;
+
 pub type PublishConfig = broccoli_queue::queue::PublishOptions;
-pub type ConsumeConfig = broccoli_queue::queue::ConsumeOptions;
+pub type ConsumeOptions = broccoli_queue::queue::ConsumeOptions;
 pub type RetryStrategy = broccoli_queue::queue::RetryStrategy;
+
+/// Consumer tuning for a single queue.
+#[derive(Debug, Clone)]
+pub struct ConsumeConfig {
+    /// Messages handled at once. Each slot fetches its next message only
+    /// after finishing the previous one, so this also caps how many messages
+    /// the consumer holds unacknowledged (its prefetch).
+    pub concurrency: usize,
+    pub options: Option<ConsumeOptions>,
+    /// Backoff [`consume_with_reconnect`](crate::consume_with_reconnect)
+    /// waits before reconnecting after the broker connection drops.
+    pub reconnect: ReconnectPolicy,
+}
+
+impl ConsumeConfig {
+    pub fn with_concurrency(concurrency: usize) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_reconnect(mut self, reconnect: ReconnectPolicy) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+}
+
+impl Default for ConsumeConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 1,
+            options: None,
+            reconnect: ReconnectPolicy::default(),
+        }
+    }
+}
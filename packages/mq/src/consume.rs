@@ -0,0 +1,151 @@
+use std::future::Future;
+
+use broccoli_queue::brokers::broker::BrokerMessage;
+use broccoli_queue::error::BroccoliError;
+use futures::stream::{FuturesUnordered, StreamExt};
+
+use crate::config::ConsumeConfig;
+use crate::models::MqQueue;
+use crate::reconnect::with_reconnect;
+
+/// Consumes `queue` with up to `config.concurrency` messages in flight.
+///
+/// Runs that many sequential consumers side by side rather than using
+/// broccoli_queue's own concurrency mode, whose detached tasks swallow
+/// consume errors and outlive the returned future. Here the first consumer
+/// error is returned and dropping the future stops every consumer.
+pub async fn consume<T, F, Fut>(
+    mq: &MqQueue,
+    queue: &str,
+    config: &ConsumeConfig,
+    handler: F,
+) -> Result<(), BroccoliError>
+where
+    T: serde::de::DeserializeOwned + Send + Clone + serde::Serialize + 'static,
+    F: Fn(BrokerMessage<T>) -> Fut + Send + Sync + Clone + 'static,
+    Fut: Future<Output = Result<(), BroccoliError>> + Send + 'static,
+{
+    run_bounded(config.concurrency, || {
+        mq.process_messages(queue, None, config.options.clone(), handler.clone())
+    })
+    .await
+}
+
+/// [`consume`] that outlives broker restarts: after a consumer error it
+/// waits out `config.reconnect`'s backoff and consumes `queue` again.
+/// Returns only once a consumer finishes cleanly.
+pub async fn consume_with_reconnect<T, F, Fut>(
+    mq: &MqQueue,
+    queue: &str,
+    config: &ConsumeConfig,
+    handler: F,
+) where
+    T: serde::de::DeserializeOwned + Send + Clone + serde::Serialize + 'static,
+    F: Fn(BrokerMessage<T>) -> Fut + Send + Sync + Clone + 'static,
+    Fut: Future<Output = Result<(), BroccoliError>> + Send + 'static,
+{
+    with_reconnect(queue, &config.reconnect, || {
+        consume(mq, queue, config, handler.clone())
+    })
+    .await
+}
+
+/// Drives `concurrency` (at least one) futures made by `consumer` together,
+/// returning the first error or `Ok` once all of them finish.
+pub async fn run_bounded<F, Fut>(concurrency: usize, mut consumer: F) -> Result<(), BroccoliError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), BroccoliError>>,
+{
+    let mut consumers: FuturesUnordered<Fut> =
+        (0..concurrency.max(1)).map(|_| consumer()).collect();
+    while let Some(result) = consumers.next().await {
+        result?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    struct FakeQueue {
+        messages: Mutex<VecDeque<u32>>,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+        processed: AtomicUsize,
+    }
+
+    impl FakeQueue {
+        fn with_messages(count: u32) -> Arc<Self> {
+            Arc::new(Self {
+                messages: Mutex::new((0..count).collect()),
+                in_flight: AtomicUsize::new(0),
+                max_in_flight: AtomicUsize::new(0),
+                processed: AtomicUsize::new(0),
+            })
+        }
+
+        /// Sequential consumer: takes one message at a time until empty.
+        async fn drain(self: Arc<Self>) -> Result<(), BroccoliError> {
+            loop {
+                let Some(_message) = self.messages.lock().unwrap().pop_front() else {
+                    return Ok(());
+                };
+                let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                self.processed.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrency_bounds_in_flight_messages() {
+        for concurrency in [1, 3, 8] {
+            let queue = FakeQueue::with_messages(40);
+            run_bounded(concurrency, || Arc::clone(&queue).drain())
+                .await
+                .unwrap();
+
+            assert_eq!(queue.processed.load(Ordering::SeqCst), 40);
+            assert_eq!(
+                queue.max_in_flight.load(Ordering::SeqCst),
+                concurrency,
+                "concurrency {concurrency}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn zero_concurrency_still_runs_one_consumer() {
+        let queue = FakeQueue::with_messages(5);
+        run_bounded(0, || Arc::clone(&queue).drain()).await.unwrap();
+
+        assert_eq!(queue.processed.load(Ordering::SeqCst), 5);
+        assert_eq!(queue.max_in_flight.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn first_consumer_error_is_returned() {
+        let started = AtomicUsize::new(0);
+        let result = run_bounded(3, || {
+            let index = started.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if index == 1 {
+                    Err(BroccoliError::Consume("connection lost".into()))
+                } else {
+                    std::future::pending().await
+                }
+            }
+        })
+        .await;
+
+        assert!(matches!(result, Err(BroccoliError::Consume(_))));
+    }
+}
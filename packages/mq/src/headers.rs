@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+/// Content type stamped on JSON-encoded payloads.
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// Typed headers carried alongside a message payload.
+///
+/// broccoli_queue keeps its own per-message metadata private and never
+/// serializes it, so headers travel inside the message body instead.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageHeaders {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Version of the payload schema, so consumers can refuse payloads newer
+    /// than they understand. `None` for messages from older publishers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+}
+
+impl MessageHeaders {
+    pub fn json() -> Self {
+        Self {
+            content_type: Some(JSON_CONTENT_TYPE.to_string()),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_schema_version(mut self, version: u32) -> Self {
+        self.schema_version = Some(version);
+        self
+    }
+
+    pub fn with_trace_id(mut self, trace_id: Option<String>) -> Self {
+        self.trace_id = trace_id;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A payload published together with its [`MessageHeaders`].
+///
+/// The payload's fields are flattened next to a `headers` object, so a plain
+/// payload without headers still decodes (with empty headers) and consumers
+/// that read the bare payload type simply ignore the extra field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope<T> {
+    #[serde(default, skip_serializing_if = "MessageHeaders::is_empty")]
+    pub headers: MessageHeaders,
+    #[serde(flatten)]
+    pub payload: T,
+}
+
+impl<T> Envelope<T> {
+    pub fn new(headers: MessageHeaders, payload: T) -> Self {
+        Self { headers, payload }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Job {
+        id: String,
+    }
+
+    #[test]
+    fn headers_round_trip_with_payload() {
+        let headers = MessageHeaders::json()
+            .with_schema_version(2)
+            .with_trace_id(Some("4bf92f3577b34da6a3ce929d0e0e4736".into()));
+        let sent = Envelope::new(headers.clone(), Job { id: "job-1".into() });
+
+        let json = serde_json::to_string(&sent).unwrap();
+        let received: Envelope<Job> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(received.headers, headers);
+        assert_eq!(received.payload, sent.payload);
+    }
+
+    #[test]
+    fn bare_payload_decodes_with_empty_headers() {
+        let received: Envelope<Job> = serde_json::from_str(r#"{"id":"job-1"}"#).unwrap();
+
+        assert!(received.headers.is_empty());
+        assert_eq!(received.payload.id, "job-1");
+    }
+
+    #[test]
+    fn enveloped_payload_decodes_as_bare_payload() {
+        let sent = Envelope::new(
+            MessageHeaders::json().with_schema_version(1),
+            Job { id: "job-1".into() },
+        );
+
+        let json = serde_json::to_string(&sent).unwrap();
+        let received: Job = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(received.id, "job-1");
+    }
+}
@@ -1,8 +1,14 @@
 pub mod config;
+pub mod consume;
 pub mod error;
+pub mod headers;
 pub mod models;
+pub mod reconnect;
 
 pub use config::ConsumeConfig;
+pub use consume::{consume, consume_with_reconnect};
+pub use headers::{Envelope, MessageHeaders};
 pub use models::{BroccoliError, BrokerMessage, MqBuilder, MqConfig, MqQueue, init_mq};
+pub use reconnect::{ReconnectPolicy, with_reconnect};
 
 pub type Mq = MqQueue;
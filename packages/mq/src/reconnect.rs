@@ -0,0 +1,149 @@
+use std::future::Future;
+use std::time::Duration;
+
+use broccoli_queue::error::BroccoliError;
+use common::config::MqAppConfig;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+/// Bounded exponential backoff between attempts to resume a consumer after
+/// the broker connection drops.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl ReconnectPolicy {
+    /// Delay before reconnect `attempt` (1-based): `base_delay` doubled per
+    /// previous attempt, capped at `max_delay`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 1u32 << attempt.saturating_sub(1).min(16);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl From<&MqAppConfig> for ReconnectPolicy {
+    fn from(config: &MqAppConfig) -> Self {
+        Self {
+            base_delay: Duration::from_millis(config.reconnect_base_delay_ms),
+            max_delay: Duration::from_millis(config.reconnect_max_delay_ms.max(1)),
+        }
+    }
+}
+
+/// Runs the consumer made by `consume` until it finishes cleanly, starting a
+/// fresh one after each error once the policy's backoff has elapsed.
+///
+/// The backoff resets after a consumer stays up for longer than
+/// `max_delay`, so a broker restart hours later starts again from
+/// `base_delay`.
+pub async fn with_reconnect<F, Fut>(queue: &str, policy: &ReconnectPolicy, mut consume: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), BroccoliError>>,
+{
+    let mut attempt = 0;
+    loop {
+        let started = Instant::now();
+        let error = match consume().await {
+            Ok(()) => return,
+            Err(e) => e,
+        };
+        if started.elapsed() > policy.max_delay {
+            attempt = 0;
+        }
+        attempt += 1;
+        let delay = policy.delay(attempt);
+        warn!(
+            queue,
+            attempt,
+            delay_ms = delay.as_millis() as u64,
+            error = %error,
+            "MQ consumer disconnected, reconnecting"
+        );
+        tokio::time::sleep(delay).await;
+        info!(queue, attempt, "Resuming MQ consumer");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    fn policy() -> ReconnectPolicy {
+        ReconnectPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(800),
+        }
+    }
+
+    #[test]
+    fn delay_doubles_up_to_the_cap() {
+        let delays: Vec<u128> = (1..=6).map(|n| policy().delay(n).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 800, 800, 800]);
+        assert_eq!(policy().delay(u32::MAX), Duration::from_millis(800));
+    }
+
+    /// A queue whose connection can be dropped: consuming it processes
+    /// messages until the next scheduled drop, then fails like a consumer
+    /// whose broker went away.
+    struct FlakyQueue {
+        messages: Mutex<VecDeque<u32>>,
+        drops: Mutex<VecDeque<usize>>,
+        processed: AtomicUsize,
+        connections: AtomicUsize,
+    }
+
+    impl FlakyQueue {
+        async fn consume(&self) -> Result<(), BroccoliError> {
+            self.connections.fetch_add(1, Ordering::SeqCst);
+            let drop_after = self.drops.lock().unwrap().pop_front();
+            let mut handled = 0;
+            loop {
+                if drop_after == Some(handled) {
+                    return Err(BroccoliError::Consume("connection reset by peer".into()));
+                }
+                let Some(_message) = self.messages.lock().unwrap().pop_front() else {
+                    return Ok(());
+                };
+                self.processed.fetch_add(1, Ordering::SeqCst);
+                handled += 1;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn consumer_resumes_after_dropped_connection() {
+        let queue = Arc::new(FlakyQueue {
+            messages: Mutex::new((0..10).collect()),
+            drops: Mutex::new(VecDeque::from([3, 0, 2])),
+            processed: AtomicUsize::new(0),
+            connections: AtomicUsize::new(0),
+        });
+
+        let policy = ReconnectPolicy {
+            base_delay: Duration::from_millis(5),
+            max_delay: Duration::from_secs(1),
+        };
+        let started = Instant::now();
+        with_reconnect("operation_tasks", &policy, || queue.consume()).await;
+
+        assert_eq!(queue.processed.load(Ordering::SeqCst), 10);
+        assert_eq!(queue.connections.load(Ordering::SeqCst), 4);
+        // Three drops in quick succession back off 5 + 10 + 20 ms.
+        assert!(started.elapsed() >= Duration::from_millis(35));
+    }
+}
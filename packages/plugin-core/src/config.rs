@@ -9,6 +9,11 @@ pub struct PluginConfig {
     pub call_timeout_secs: u64,
     #[serde(default = "default_pool_max_instances")]
     pub pool_max_instances: usize,
+    /// Wall-clock limit for a single plugin function call, in milliseconds.
+    /// Calls exceeding it are interrupted and fail with `PluginError::Timeout`.
+    /// `None` (the default) leaves calls unbounded.
+    #[serde(default)]
+    pub exec_timeout_ms: Option<u64>,
 }
 
 fn default_call_timeout() -> u64 {
@@ -26,6 +31,7 @@ impl Default for PluginConfig {
             enable_wasi: true,
             call_timeout_secs: default_call_timeout(),
             pool_max_instances: default_pool_max_instances(),
+            exec_timeout_ms: None,
         }
     }
 }
@@ -30,6 +30,20 @@ pub enum PluginError {
         message: String,
     },
 
+    #[error("Function '{func_name}' on plugin '{plugin_id}' crashed: {message}")]
+    Trapped {
+        plugin_id: String,
+        func_name: String,
+        message: String,
+    },
+
+    #[error("Function '{func_name}' on plugin '{plugin_id}' timed out after {timeout_ms}ms")]
+    Timeout {
+        plugin_id: String,
+        func_name: String,
+        timeout_ms: u64,
+    },
+
     #[error("Extism error: {0}")]
     Extism(#[from] extism::Error),
 
@@ -115,6 +115,20 @@ pub struct ServerConfig {
 
     #[serde(default)]
     pub hooks: Vec<HookDeclaration>,
+
+    /// Optional I/O contracts for exported functions, keyed by function name.
+    #[serde(default)]
+    pub functions: HashMap<String, FunctionSchema>,
+}
+
+/// Input/output schemas declared for a single exported plugin function.
+///
+/// For route handlers the schemas describe the HTTP request body and the
+/// `body` of the plugin's response, not the full envelope.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct FunctionSchema {
+    pub input: Option<SchemaProperty>,
+    pub output: Option<SchemaProperty>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -137,6 +151,12 @@ pub struct ServerRouteConfig {
 
     #[serde(default)]
     pub permission: Option<String>,
+
+    /// Marks a `GET` route that serves contest standings. The server caches
+    /// its responses per `{contest_id}` path parameter and drops them when a
+    /// judged result for that contest is applied.
+    #[serde(default)]
+    pub standings: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -361,6 +381,98 @@ impl SchemaProperty {
 
         schema.into()
     }
+
+    /// Validate `value` against this schema, returning a message naming the
+    /// first offending path. `format` is treated as an annotation only.
+    pub fn validate(&self, value: &serde_json::Value) -> Result<(), String> {
+        self.validate_at(value, "$")
+    }
+
+    fn validate_at(&self, value: &serde_json::Value, path: &str) -> Result<(), String> {
+        use serde_json::Value;
+
+        let type_matches = match self.schema_type.as_str() {
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "boolean" => value.is_boolean(),
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "null" => value.is_null(),
+            other => return Err(format!("{path}: unsupported schema type '{other}'")),
+        };
+        if !type_matches {
+            return Err(format!("{path}: expected {}", self.schema_type));
+        }
+
+        if let Some(ref allowed) = self.enum_values
+            && !allowed.contains(value)
+        {
+            return Err(format!("{path}: value is not one of the allowed values"));
+        }
+
+        match value {
+            Value::Number(n) => {
+                let n = n.as_f64().unwrap_or_default();
+                if let Some(min) = self.min
+                    && n < min
+                {
+                    return Err(format!("{path}: must be >= {min}"));
+                }
+                if let Some(max) = self.max
+                    && n > max
+                {
+                    return Err(format!("{path}: must be <= {max}"));
+                }
+            }
+            Value::String(s) => {
+                let len = s.chars().count();
+                if let Some(min) = self.min_length
+                    && len < min as usize
+                {
+                    return Err(format!("{path}: length must be >= {min}"));
+                }
+                if let Some(max) = self.max_length
+                    && len > max as usize
+                {
+                    return Err(format!("{path}: length must be <= {max}"));
+                }
+                if let Some(ref pattern) = self.pattern {
+                    let re = regex::Regex::new(pattern)
+                        .map_err(|e| format!("{path}: invalid pattern '{pattern}': {e}"))?;
+                    if !re.is_match(s) {
+                        return Err(format!("{path}: does not match pattern '{pattern}'"));
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(ref item_schema) = self.items {
+                    for (i, item) in items.iter().enumerate() {
+                        item_schema.validate_at(item, &format!("{path}[{i}]"))?;
+                    }
+                }
+            }
+            Value::Object(obj) => {
+                for key in self.required.iter().flatten() {
+                    if !obj.contains_key(key) {
+                        return Err(format!("{path}: missing required property '{key}'"));
+                    }
+                }
+                for (key, val) in obj {
+                    match self.properties.get(key) {
+                        Some(prop) => prop.validate_at(val, &format!("{path}.{key}"))?,
+                        None if self.additional_properties == Some(false) => {
+                            return Err(format!("{path}: unexpected property '{key}'"));
+                        }
+                        None => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -876,4 +988,104 @@ mod tests {
         let server = manifest.server.unwrap();
         assert_eq!(server.hooks[0].mode, HookMode::Blocking);
     }
+
+    fn greet_function_schema() -> FunctionSchema {
+        let toml_str = r#"
+            name = "test"
+            version = "1.0.0"
+
+            [server]
+            entry = "test.wasm"
+
+            [server.functions.greet.input]
+            type = "object"
+            required = ["name"]
+            additional_properties = false
+
+            [server.functions.greet.input.properties.name]
+            type = "string"
+            min_length = 1
+            pattern = "^[a-z]+$"
+
+            [server.functions.greet.input.properties.times]
+            type = "integer"
+            min = 1.0
+            max = 3.0
+
+            [server.functions.greet.output]
+            type = "object"
+            required = ["greetings"]
+
+            [server.functions.greet.output.properties.greetings]
+            type = "array"
+            items = { type = "string" }
+        "#;
+
+        let manifest: PluginManifest = toml::from_str(toml_str).unwrap();
+        manifest.server.unwrap().functions["greet"].clone()
+    }
+
+    #[test]
+    fn function_schema_accepts_conforming_input_and_output() {
+        let schema = greet_function_schema();
+
+        let input = schema.input.unwrap();
+        assert!(input.validate(&json!({"name": "alice"})).is_ok());
+        assert!(input.validate(&json!({"name": "bob", "times": 2})).is_ok());
+
+        let output = schema.output.unwrap();
+        assert!(output.validate(&json!({"greetings": ["hi"]})).is_ok());
+    }
+
+    #[test]
+    fn function_schema_rejects_malformed_input() {
+        let input = greet_function_schema().input.unwrap();
+
+        let cases = [
+            (json!("alice"), "$: expected object"),
+            (json!({}), "$: missing required property 'name'"),
+            (json!({"name": 42}), "$.name: expected string"),
+            (json!({"name": ""}), "$.name: length must be >= 1"),
+            (
+                json!({"name": "Alice"}),
+                "$.name: does not match pattern '^[a-z]+$'",
+            ),
+            (json!({"name": "a", "times": 5}), "$.times: must be <= 3"),
+            (
+                json!({"name": "a", "times": 1.5}),
+                "$.times: expected integer",
+            ),
+            (
+                json!({"name": "a", "extra": 1}),
+                "$: unexpected property 'extra'",
+            ),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(input.validate(&value).unwrap_err(), expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn function_schema_reports_array_item_path() {
+        let output = greet_function_schema().output.unwrap();
+
+        let err = output
+            .validate(&json!({"greetings": ["hi", 3]}))
+            .unwrap_err();
+        assert_eq!(err, "$.greetings[1]: expected string");
+    }
+
+    #[test]
+    fn functions_default_to_empty_when_omitted() {
+        let toml_str = r#"
+            name = "test"
+            version = "1.0.0"
+
+            [server]
+            entry = "test.wasm"
+        "#;
+
+        let manifest: PluginManifest = toml::from_str(toml_str).unwrap();
+        assert!(manifest.server.unwrap().functions.is_empty());
+    }
 }
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
-use extism::Pool;
+use extism::{Plugin, Pool, PoolBuilder, PoolPlugin};
 use matchit::Router;
 use serde::Serialize;
 
@@ -25,6 +25,53 @@ pub struct RouteMatchInfo {
 pub struct RouteMetaData {
     pub handler: String,
     pub permission: Option<String>,
+    pub standings: bool,
+}
+
+type InstanceFactory = dyn Fn() -> Result<Plugin, extism::Error>;
+
+/// Serialises access to an instance factory.
+struct SharedFactory(Mutex<Box<InstanceFactory>>);
+
+// SAFETY: mirrors `extism::Pool`, which holds the same non-`Send` factory
+// behind a mutex. The factory is only ever invoked while holding the lock,
+// and the instances it produces are handed straight to the pool.
+unsafe impl Send for SharedFactory {}
+unsafe impl Sync for SharedFactory {}
+
+impl SharedFactory {
+    fn build(&self) -> Result<Plugin, extism::Error> {
+        (self.0.lock().unwrap_or_else(|e| e.into_inner()))()
+    }
+}
+
+/// Instance pool of a loaded plugin, together with the factory that fills it.
+#[derive(Clone)]
+pub struct PluginRuntime {
+    pub pool: Pool,
+    factory: Arc<SharedFactory>,
+}
+
+impl PluginRuntime {
+    pub fn new<F>(max_instances: usize, factory: F) -> Self
+    where
+        F: Fn() -> Result<Plugin, extism::Error> + 'static,
+    {
+        let factory = Arc::new(SharedFactory(Mutex::new(Box::new(factory))));
+        let pool_factory = factory.clone();
+        let pool = PoolBuilder::new()
+            .with_max_instances(max_instances)
+            .build(move || pool_factory.build());
+        Self { pool, factory }
+    }
+
+    /// Replace a pooled instance in place with a freshly built one. Used after
+    /// a call leaves the instance in an unknown state (trap, interrupt).
+    pub fn recycle(&self, instance: &PoolPlugin) -> Result<(), extism::Error> {
+        let fresh = self.factory.build()?;
+        *instance.plugin() = fresh;
+        Ok(())
+    }
 }
 
 pub struct PluginEntry {
@@ -32,7 +79,7 @@ pub struct PluginEntry {
     pub root_dir: PathBuf,
     pub manifest: PluginManifest,
     pub status: PluginStatus,
-    pub runtime: Option<Pool>,
+    pub runtime: Option<PluginRuntime>,
     pub router: Router<RouteMatchInfo>,
 }
 
@@ -80,6 +127,7 @@ impl PluginEntry {
                     RouteMetaData {
                         handler: route.handler.clone(),
                         permission: route.permission.clone(),
+                        standings: route.standings,
                     },
                 );
             }
@@ -150,4 +198,71 @@ impl PluginEntry {
 
         Ok(canonical_asset_path)
     }
+
+    /// Paths of every file under the web root, relative to it with `/`
+    /// separators and sorted. Entries resolving outside the root (e.g.
+    /// through symlinks) are skipped, matching `resolve_web_asset`.
+    pub fn list_web_assets(&self) -> Result<Vec<String>, AssetError> {
+        let web_config = self.manifest.web.as_ref().ok_or(AssetError::NoWebConfig)?;
+        let web_root = self
+            .root_dir
+            .join(&web_config.root)
+            .canonicalize()
+            .map_err(AssetError::Io)?;
+        collect_web_assets(&web_root).map_err(AssetError::Io)
+    }
+}
+
+/// Walks `web_root` (already canonical). Each directory is read once, so a
+/// symlink back to the root or one of its subdirectories cannot loop.
+fn collect_web_assets(web_root: &Path) -> std::io::Result<Vec<String>> {
+    let mut assets = Vec::new();
+    let mut visited = HashSet::from([web_root.to_path_buf()]);
+    let mut pending = vec![web_root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let Ok(path) = entry?.path().canonicalize() else {
+                continue;
+            };
+            if !path.starts_with(web_root) {
+                continue;
+            }
+            if path.is_dir() {
+                if visited.insert(path.clone()) {
+                    pending.push(path);
+                }
+            } else if let Ok(relative) = path.strip_prefix(web_root) {
+                let components: Vec<_> = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect();
+                assets.push(components.join("/"));
+            }
+        }
+    }
+    assets.sort();
+    assets.dedup();
+    Ok(assets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn web_asset_walk_survives_symlink_loops() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join("styles")).unwrap();
+        std::fs::write(root.join("index.js"), "").unwrap();
+        std::fs::write(root.join("styles/theme.css"), "").unwrap();
+        std::os::unix::fs::symlink(&root, root.join("styles/back-to-root")).unwrap();
+        std::os::unix::fs::symlink(root.join("styles"), root.join("again")).unwrap();
+
+        assert_eq!(
+            collect_web_assets(&root).unwrap(),
+            vec!["index.js".to_string(), "styles/theme.css".to_string()]
+        );
+    }
 }
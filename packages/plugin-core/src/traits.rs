@@ -1,7 +1,9 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use async_trait::async_trait;
-use extism::{Manifest, PluginBuilder, PoolBuilder, Wasm};
+use extism::{Manifest, PluginBuilder, Wasm};
 use serde::{Serialize, de::DeserializeOwned};
 use tracing::{debug, error, info, instrument, warn};
 
@@ -10,7 +12,7 @@ use crate::error::{AssetError, PluginError};
 use crate::host::HostFunctionRegistry;
 use crate::i18n::{I18nRegistry, TranslationMap};
 use crate::manifest::PluginManifest;
-use crate::registry::{PluginEntry, PluginInfo, PluginRegistry, PluginStatus};
+use crate::registry::{PluginEntry, PluginInfo, PluginRegistry, PluginRuntime, PluginStatus};
 
 #[async_trait]
 pub trait PluginManager: Send + Sync {
@@ -103,16 +105,12 @@ pub trait PluginManager: Send + Sync {
             let host_functions = self.get_host_functions().resolve(plugin_id, &permissions);
             let wasi = self.get_config().enable_wasi;
             let max_instances = self.get_config().pool_max_instances;
-            let pool = PoolBuilder::new()
-                .with_max_instances(max_instances)
-                .build(move || {
-                    PluginBuilder::new(&manifest)
-                        .with_wasi(wasi)
-                        .with_functions(host_functions.clone())
-                        .build()
-                });
-
-            runtime = Some(pool);
+            runtime = Some(PluginRuntime::new(max_instances, move || {
+                PluginBuilder::new(&manifest)
+                    .with_wasi(wasi)
+                    .with_functions(host_functions.clone())
+                    .build()
+            }));
         }
 
         plugin_entry.runtime = runtime;
@@ -186,6 +184,16 @@ pub trait PluginManager: Send + Sync {
         plugin_entry.resolve_web_asset(asset_path)
     }
 
+    fn list_plugin_assets(&self, plugin_id: &str) -> Result<Vec<String>, AssetError> {
+        let registry = self
+            .get_registry()
+            .read()
+            .map_err(|_| AssetError::Internal("Failed to acquire registry read lock".into()))?;
+
+        let plugin_entry = registry.get(plugin_id).ok_or(AssetError::NotFound)?;
+        plugin_entry.list_web_assets()
+    }
+
     fn update_translations(&self) -> Result<(), PluginError> {
         let registry = self
             .get_registry()
@@ -300,6 +308,7 @@ pub trait PluginManager: Send + Sync {
         let start = std::time::Instant::now();
 
         let timeout = Duration::from_secs(self.get_config().call_timeout_secs);
+        let exec_timeout_ms = self.get_config().exec_timeout_ms;
         let registry = self
             .get_registry()
             .read()
@@ -313,7 +322,7 @@ pub trait PluginManager: Send + Sync {
             return Err(PluginError::NotLoaded(plugin_id.to_string()));
         }
 
-        let pool = plugin_entry
+        let runtime = plugin_entry
             .runtime
             .as_ref()
             .ok_or_else(|| PluginError::NoRuntime(plugin_id.to_string()))?
@@ -322,7 +331,8 @@ pub trait PluginManager: Send + Sync {
         drop(registry);
 
         let result = tokio::task::block_in_place(|| {
-            let plugin = pool
+            let plugin = runtime
+                .pool
                 .get(timeout)
                 .map_err(|e| {
                     PluginError::Internal(format!(
@@ -344,13 +354,58 @@ pub trait PluginManager: Send + Sync {
                 });
             }
 
-            plugin
-                .call(func_name, input)
-                .map_err(|e| PluginError::ExecutionFailed {
-                    plugin_id: plugin_id.to_string(),
-                    func_name: func_name.to_string(),
-                    message: e.to_string(),
+            // The watchdog cancels the in-flight call once the deadline passes;
+            // cancelling an idle instance is a no-op, so a late fire is harmless.
+            let timed_out = Arc::new(AtomicBool::new(false));
+            let watchdog = exec_timeout_ms.map(|ms| {
+                let cancel = plugin.plugin().cancel_handle();
+                let timed_out = timed_out.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(ms)).await;
+                    timed_out.store(true, Ordering::SeqCst);
+                    if let Err(e) = cancel.cancel() {
+                        warn!("Failed to cancel timed out plugin call: {}", e);
+                    }
                 })
+            });
+
+            let result = plugin.call(func_name, input);
+
+            if let Some(watchdog) = watchdog {
+                watchdog.abort();
+            }
+
+            result.map_err(|e| {
+                let timed_out = timed_out.load(Ordering::SeqCst);
+                let trapped = e.chain().any(|cause| cause.is::<wasmtime::Trap>());
+
+                // An interrupted or trapped guest may have left its memory and
+                // globals mid-update; never hand that instance out again.
+                if timed_out || trapped {
+                    warn!("Recycling plugin instance after failed call: {:#}", e);
+                    if let Err(e) = runtime.recycle(&plugin) {
+                        error!("Failed to rebuild plugin instance: {}", e);
+                    }
+                }
+
+                match exec_timeout_ms {
+                    Some(timeout_ms) if timed_out => PluginError::Timeout {
+                        plugin_id: plugin_id.to_string(),
+                        func_name: func_name.to_string(),
+                        timeout_ms,
+                    },
+                    _ if trapped => PluginError::Trapped {
+                        plugin_id: plugin_id.to_string(),
+                        func_name: func_name.to_string(),
+                        message: e.root_cause().to_string(),
+                    },
+                    _ => PluginError::ExecutionFailed {
+                        plugin_id: plugin_id.to_string(),
+                        func_name: func_name.to_string(),
+                        message: e.to_string(),
+                    },
+                }
+            })
         });
 
         let duration = start.elapsed();
@@ -133,6 +133,17 @@ pub fn has_problem(
         .is_some_and(|row| row.exists))
 }
 
+/// SQL predicate on the `submission` rows aliased `alias` whose results
+/// their contest problem's `result_delay_secs` still withholds.
+pub fn results_withheld(alias: &str) -> String {
+    format!(
+        "EXISTS (SELECT 1 FROM contest_problem cp \
+         WHERE cp.contest_id = {alias}.contest_id AND cp.problem_id = {alias}.problem_id \
+         AND cp.result_delay_secs > 0 \
+         AND {alias}.created_at + make_interval(secs => cp.result_delay_secs) > NOW())"
+    )
+}
+
 #[cfg(feature = "guest")]
 pub fn check_problem_access(
     host: &crate::sdk::Host,
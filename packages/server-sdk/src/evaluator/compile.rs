@@ -0,0 +1,249 @@
+use crate::Host;
+use crate::error::SdkError;
+use crate::types::*;
+
+/// Id of the single synthetic case a compile-only batch is started with.
+const COMPILE_CASE_ID: i32 = 0;
+
+/// Compiles `case.solution_source` without evaluating any test case.
+///
+/// Starts a one-case batch in [`JudgeMode::CompileOnly`], so the evaluator
+/// builds just the compile step, and returns its verdict as produced by
+/// [`interpret_compile_result`](super::interpret_compile_result). Batch
+/// failures and timeouts come back as a `SystemError` verdict.
+pub fn compile_only(
+    host: &Host,
+    problem_type: &str,
+    case: StartEvaluateCaseInput,
+) -> Result<TestCaseVerdict, SdkError> {
+    let system_error = |message: String| TestCaseVerdict {
+        test_case_id: COMPILE_CASE_ID,
+        verdict: Verdict::SystemError,
+        score: 0.0,
+        time_used_ms: None,
+        memory_used_kb: None,
+        message: Some(message),
+        stdout: None,
+        stderr: None,
+        worker_id: None,
+    };
+
+    let result_timeout_ms = default_evaluation_result_timeout_ms(case.time_limit_ms);
+    let batch_input = StartEvaluateBatchInput {
+        problem_type: problem_type.to_string(),
+        test_cases: vec![StartEvaluateCaseInput {
+            test_case_id: COMPILE_CASE_ID,
+            input: TestCaseBodyRef::default(),
+            expected_output: TestCaseBodyRef::default(),
+            // Not a stored test case, so the host must not look it up.
+            is_custom: true,
+            judge_mode: JudgeMode::CompileOnly,
+            ..case
+        }],
+    };
+
+    let batch_id = match host.eval.start_batch(&batch_input) {
+        Ok(id) => id,
+        Err(e) => return Ok(system_error(format!("BATCH_START_FAILED: {e:?}"))),
+    };
+
+    match host.eval.next_result(&batch_id, result_timeout_ms) {
+        Ok(Some(verdict)) => Ok(verdict),
+        Ok(None) => {
+            let _ = host.eval.cancel_batch(&batch_id);
+            Ok(system_error("EVALUATION_TIMEOUT".into()))
+        }
+        Err(e) => {
+            let _ = host.eval.cancel_batch(&batch_id);
+            Ok(system_error(format!("Error polling result: {e:?}")))
+        }
+    }
+}
+
+/// Records the outcome of a compile-only submission.
+///
+/// A compile error is stored as `CompilationError` with the compiler output;
+/// a clean compile as `Judged` with no verdict, so it never counts as an
+/// accepted (or rejected) attempt, keeping any warnings as compile output.
+/// Sandbox failures are reported back as an unsuccessful output.
+pub fn compile_submission(
+    host: &Host,
+    req: &OnSubmissionInput,
+) -> Result<OnSubmissionOutput, SdkError> {
+    let affected = host.submission.update(&SubmissionUpdate {
+        status: Some(SubmissionStatus::Running),
+        ..SubmissionUpdate::new(req.submission_id, req.judgement_id, req.judge_epoch)
+    })?;
+    if affected == 0 {
+        return Err(SdkError::StaleEpoch);
+    }
+
+    let verdict = compile_only(
+        host,
+        &req.problem_type,
+        StartEvaluateCaseInput {
+            problem_id: req.problem_id,
+            test_case_id: COMPILE_CASE_ID,
+            solution_source: req.files.clone(),
+            solution_language: req.language.clone(),
+            time_limit_ms: req.time_limit_ms,
+            memory_limit_kb: req.memory_limit_kb,
+            contest_id: req.contest_id,
+            input: TestCaseBodyRef::default(),
+            expected_output: TestCaseBodyRef::default(),
+            is_custom: true,
+            target_worker_id: req.target_worker_id.clone(),
+            judge_mode: JudgeMode::CompileOnly,
+            judge_job: Some(req.judge_job()),
+        },
+    )?;
+
+    if verdict.verdict == Verdict::SystemError {
+        return Ok(OnSubmissionOutput {
+            success: false,
+            error_message: verdict.message,
+        });
+    }
+
+    let update = SubmissionUpdate::new(req.submission_id, req.judgement_id, req.judge_epoch);
+    let update = if verdict.verdict == Verdict::CompileError {
+        SubmissionUpdate {
+            status: Some(SubmissionStatus::CompilationError),
+            verdict: Some(None),
+            score: Some(0.0),
+            compile_output: Some(verdict.message.clone()),
+            ..update
+        }
+    } else {
+        SubmissionUpdate {
+            status: Some(SubmissionStatus::Judged),
+            verdict: Some(None),
+            score: Some(0.0),
+            compile_output: Some(verdict.message.clone()),
+            ..update
+        }
+    };
+    if host.submission.update(&update)? == 0 {
+        return Err(SdkError::StaleEpoch);
+    }
+
+    let _ = host.log.info(&format!(
+        "Submission {} compiled only: {:?}",
+        req.submission_id, verdict.verdict
+    ));
+
+    Ok(OnSubmissionOutput {
+        success: true,
+        error_message: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator::evaluate_run;
+
+    fn run_input() -> OnCodeRunInput {
+        OnCodeRunInput {
+            id: 7,
+            user_id: 1,
+            problem_id: 2,
+            contest_id: None,
+            files: vec![SourceFile {
+                filename: "main.cpp".into(),
+                content: "int main() {}".into(),
+            }],
+            language: "cpp".into(),
+            time_limit_ms: 1000,
+            memory_limit_kb: 262_144,
+            problem_type: "batch".into(),
+            test_cases: vec![],
+            judge_mode: JudgeMode::CompileOnly,
+        }
+    }
+
+    fn submission_input() -> OnSubmissionInput {
+        OnSubmissionInput {
+            submission_id: 3,
+            judgement_id: 4,
+            user_id: 1,
+            problem_id: 2,
+            contest_id: None,
+            files: run_input().files,
+            language: "cpp".into(),
+            time_limit_ms: 1000,
+            memory_limit_kb: 262_144,
+            problem_type: "batch".into(),
+            test_cases: vec![],
+            judge_epoch: 0,
+            target_worker_id: None,
+            judge_mode: JudgeMode::CompileOnly,
+        }
+    }
+
+    fn compiled_with(message: &str) -> TestCaseVerdict {
+        TestCaseVerdict {
+            message: Some(message.into()),
+            ..TestCaseVerdict::accepted(COMPILE_CASE_ID)
+        }
+    }
+
+    #[test]
+    fn compile_only_run_records_compile_output_and_no_results() {
+        let host = Host::mock();
+        host.eval.queue_result(compiled_with("warning: unused"));
+
+        let output = evaluate_run(&host, &run_input()).unwrap();
+
+        assert!(output.success);
+        let batches = host.eval.batch_inputs();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].test_cases.len(), 1);
+        assert!(batches[0].test_cases[0].judge_mode.is_compile_only());
+        assert!(host.code_run.results().is_empty());
+
+        let last = host.code_run.last_update();
+        assert_eq!(last.status, Some(SubmissionStatus::Judged));
+        assert_eq!(last.compile_output, Some(Some("warning: unused".into())));
+    }
+
+    #[test]
+    fn compile_only_run_reports_compile_error() {
+        let host = Host::mock();
+        host.eval
+            .queue_result(TestCaseVerdict::compile_error(COMPILE_CASE_ID));
+
+        evaluate_run(&host, &run_input()).unwrap();
+
+        let last = host.code_run.last_update();
+        assert_eq!(last.status, Some(SubmissionStatus::CompilationError));
+        assert_eq!(last.compile_output, Some(Some("Compilation failed".into())));
+        assert!(host.code_run.results().is_empty());
+    }
+
+    #[test]
+    fn compile_only_submission_has_no_verdict_or_test_results() {
+        let host = Host::mock();
+        host.eval.queue_result(compiled_with("warning: unused"));
+
+        let output = compile_submission(&host, &submission_input()).unwrap();
+
+        assert!(output.success);
+        assert!(host.submission.results().is_empty());
+        let last = host.submission.last_update();
+        assert_eq!(last.status, Some(SubmissionStatus::Judged));
+        assert_eq!(last.verdict, Some(None));
+        assert_eq!(last.compile_output, Some(Some("warning: unused".into())));
+    }
+
+    #[test]
+    fn compile_only_timeout_is_reported_as_failure() {
+        let host = Host::mock();
+
+        let output = compile_submission(&host, &submission_input()).unwrap();
+
+        assert!(!output.success);
+        assert_eq!(output.error_message.as_deref(), Some("EVALUATION_TIMEOUT"));
+        assert!(host.eval.was_cancelled());
+    }
+}
@@ -2,12 +2,41 @@ use crate::Checker;
 use crate::error::SdkError;
 use crate::types::*;
 
+/// Turns a test case's operation result into a verdict. Unless
+/// `require_zero_exit` is set, a run that exits with a non-zero code still
+/// goes to the checker. The program's stderr stays on the verdict only when
+/// `stderr_capture` keeps it for the final verdict.
 pub fn interpret_sandbox_result(
     checker: &Checker,
     test_case_id: i32,
     result: &OperationResult,
     checker_format: &str,
     checker_input: &CheckerParseInput,
+    require_zero_exit: bool,
+    stderr_capture: StderrCapture,
+) -> Result<TestCaseVerdict, SdkError> {
+    let mut verdict = sandbox_verdict(
+        checker,
+        test_case_id,
+        result,
+        checker_format,
+        checker_input,
+        require_zero_exit,
+    )?;
+    verdict.worker_id = result.worker_id.clone();
+    if !stderr_capture.keeps(&verdict.verdict) {
+        verdict.stderr = None;
+    }
+    Ok(verdict)
+}
+
+fn sandbox_verdict(
+    checker: &Checker,
+    test_case_id: i32,
+    result: &OperationResult,
+    checker_format: &str,
+    checker_input: &CheckerParseInput,
+    require_zero_exit: bool,
 ) -> Result<TestCaseVerdict, SdkError> {
     if !result.success && result.task_results.is_empty() {
         return Ok(TestCaseVerdict {
@@ -19,10 +48,24 @@ pub fn interpret_sandbox_result(
             message: result.error.clone(),
             stdout: None,
             stderr: None,
+            worker_id: None,
         });
     }
 
     if let Some(compile_result) = result.task_results.get("compile") {
+        if compile_timed_out(&compile_result.sandbox_result) {
+            return Ok(TestCaseVerdict {
+                test_case_id,
+                verdict: Verdict::CompileError,
+                score: 0.0,
+                time_used_ms: None,
+                memory_used_kb: None,
+                message: Some(compile_timeout_message(&compile_result.sandbox_result)),
+                stdout: None,
+                stderr: None,
+                worker_id: None,
+            });
+        }
         if let Some(exit_code) = compile_result.sandbox_result.exit_code {
             if exit_code != 0 {
                 return Ok(TestCaseVerdict {
@@ -37,6 +80,7 @@ pub fn interpret_sandbox_result(
                     ),
                     stdout: None,
                     stderr: None,
+                    worker_id: None,
                 });
             }
         } else if !compile_result.success {
@@ -52,6 +96,7 @@ pub fn interpret_sandbox_result(
                 ),
                 stdout: None,
                 stderr: None,
+                worker_id: None,
             });
         }
     }
@@ -71,6 +116,7 @@ pub fn interpret_sandbox_result(
                 message: Some("Execution step was skipped".into()),
                 stdout: None,
                 stderr: None,
+                worker_id: None,
             });
         }
 
@@ -87,6 +133,7 @@ pub fn interpret_sandbox_result(
                 )),
                 stdout: exec_stdout,
                 stderr: exec_stderr,
+                worker_id: None,
             });
         }
 
@@ -105,6 +152,20 @@ pub fn interpret_sandbox_result(
                     )),
                     stdout: exec_stdout,
                     stderr: exec_stderr,
+                    worker_id: None,
+                });
+            }
+            DISK_QUOTA_EXCEEDED_STATUS => {
+                return Ok(TestCaseVerdict {
+                    test_case_id,
+                    verdict: Verdict::RuntimeError,
+                    score: 0.0,
+                    time_used_ms: extract_time_used(result),
+                    memory_used_kb: extract_memory_used(result),
+                    message: Some(sandbox.message.clone()),
+                    stdout: exec_stdout,
+                    stderr: exec_stderr,
+                    worker_id: None,
                 });
             }
             "SG" => {
@@ -117,9 +178,10 @@ pub fn interpret_sandbox_result(
                     message: Some(sandbox.message.clone()),
                     stdout: exec_stdout,
                     stderr: exec_stderr,
+                    worker_id: None,
                 });
             }
-            "RE" => {
+            "RE" if require_zero_exit || sandbox.exit_code.is_none() => {
                 return Ok(TestCaseVerdict {
                     test_case_id,
                     verdict: Verdict::RuntimeError,
@@ -129,6 +191,7 @@ pub fn interpret_sandbox_result(
                     message: Some(format!("Exit code: {}", sandbox.exit_code.unwrap_or(-1))),
                     stdout: exec_stdout,
                     stderr: exec_stderr,
+                    worker_id: None,
                 });
             }
             _ => {}
@@ -147,6 +210,7 @@ pub fn interpret_sandbox_result(
                 message: Some("No exec result found".into()),
                 stdout: None,
                 stderr: None,
+                worker_id: None,
             });
         }
     };
@@ -182,6 +246,7 @@ pub fn interpret_sandbox_result(
             message: v.message,
             stdout: exec_stdout,
             stderr: exec_stderr,
+            worker_id: None,
         }),
         Err(e) => Ok(TestCaseVerdict {
             test_case_id,
@@ -192,26 +257,83 @@ pub fn interpret_sandbox_result(
             message: Some(format!("Checker call failed: {:?}", e)),
             stdout: exec_stdout,
             stderr: exec_stderr,
+            worker_id: None,
         }),
     }
 }
 
+/// Interprets the result of a compile-only operation.
+///
+/// A failed or timed-out compilation is a `CompileError` and a sandbox
+/// failure a `SystemError`, exactly as in [`interpret_sandbox_result`]. A successful
+/// one is `Accepted`, carrying the compiler's diagnostics (warnings) in
+/// `message`. Languages without a compile step are trivially accepted.
+pub fn interpret_compile_result(test_case_id: i32, result: &OperationResult) -> TestCaseVerdict {
+    let verdict = |verdict: Verdict, message: Option<String>| TestCaseVerdict {
+        test_case_id,
+        verdict,
+        score: 0.0,
+        time_used_ms: None,
+        memory_used_kb: None,
+        message,
+        stdout: None,
+        stderr: None,
+        worker_id: result.worker_id.clone(),
+    };
+
+    if !result.success && result.task_results.is_empty() {
+        return verdict(Verdict::SystemError, result.error.clone());
+    }
+
+    let Some(compile_result) = result.task_results.get("compile") else {
+        return verdict(Verdict::Accepted, None);
+    };
+    let sandbox = &compile_result.sandbox_result;
+    if compile_timed_out(sandbox) {
+        return verdict(
+            Verdict::CompileError,
+            Some(compile_timeout_message(sandbox)),
+        );
+    }
+    match sandbox.exit_code {
+        Some(code) if code != 0 => verdict(
+            Verdict::CompileError,
+            truncate_stderr(&sandbox.stderr, "Compilation failed"),
+        ),
+        None if !compile_result.success => verdict(
+            Verdict::SystemError,
+            truncate_stderr(&sandbox.stderr, "Compilation step failed (sandbox error)"),
+        ),
+        _ if sandbox.stderr.is_empty() => verdict(Verdict::Accepted, None),
+        _ => verdict(Verdict::Accepted, truncate_stderr(&sandbox.stderr, "")),
+    }
+}
+
+/// A compiler killed by its time limit is the submission's fault (e.g.
+/// template bombs), not the sandbox's.
+fn compile_timed_out(sandbox: &ExecutionResult) -> bool {
+    sandbox.status == "TO"
+}
+
+fn compile_timeout_message(sandbox: &ExecutionResult) -> String {
+    format!(
+        "Compilation time limit exceeded ({:.1}s)",
+        sandbox.time_used.max(sandbox.wall_time_used)
+    )
+}
+
 fn extract_time_used(result: &OperationResult) -> Option<i64> {
-    result.task_results.get("exec").and_then(|exec_result| {
-        let t = exec_result.sandbox_result.time_used;
-        if t > 0.0 && t.is_finite() && t < (i64::MAX as f64 / 1000.0) {
-            Some((t * 1000.0) as i64)
-        } else {
-            None
-        }
-    })
+    result
+        .task_results
+        .get("exec")
+        .and_then(|exec_result| exec_result.sandbox_result.time_used_ms())
 }
 
 fn extract_memory_used(result: &OperationResult) -> Option<i64> {
     result
         .task_results
         .get("exec")
-        .and_then(|exec_result| exec_result.sandbox_result.memory_used.map(|m| m as i64))
+        .and_then(|exec_result| exec_result.sandbox_result.memory_used_kb())
 }
 
 fn opt_nonempty(s: &str) -> Option<String> {
@@ -234,3 +356,216 @@ fn truncate_stderr(stderr: &str, fallback: &str) -> Option<String> {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Host;
+    use std::collections::HashMap;
+
+    fn compile_op(exit_code: Option<i32>, success: bool, stderr: &str) -> OperationResult {
+        let compile = TaskExecutionResult {
+            task_id: "compile".to_string(),
+            success,
+            sandbox_result: ExecutionResult {
+                exit_code,
+                stderr: stderr.to_string(),
+                ..Default::default()
+            },
+            collected_outputs: HashMap::new(),
+        };
+        OperationResult {
+            success,
+            task_results: HashMap::from([("compile".to_string(), compile)]),
+            error: None,
+            worker_id: None,
+        }
+    }
+
+    fn nonzero_exit_op() -> OperationResult {
+        let exec = TaskExecutionResult {
+            task_id: "exec".to_string(),
+            success: false,
+            sandbox_result: ExecutionResult {
+                exit_code: Some(1),
+                status: "RE".to_string(),
+                stdout: "3\n".to_string(),
+                ..Default::default()
+            },
+            collected_outputs: HashMap::new(),
+        };
+        OperationResult {
+            success: false,
+            task_results: HashMap::from([("exec".to_string(), exec)]),
+            error: None,
+            worker_id: None,
+        }
+    }
+
+    fn checker_input() -> CheckerParseInput {
+        CheckerParseInput {
+            stdout: JudgeFile::Missing,
+            stderr: String::new(),
+            exit_code: 0,
+            expected_output: JudgeFile::inline("3\n"),
+            test_input: JudgeFile::inline("1 2\n"),
+            checker_source: None,
+            config: None,
+        }
+    }
+
+    #[test]
+    fn nonzero_exit_is_runtime_error_when_required() {
+        let host = Host::mock();
+
+        let verdict = interpret_sandbox_result(
+            &host.checker,
+            0,
+            &nonzero_exit_op(),
+            "exact",
+            &checker_input(),
+            true,
+            StderrCapture::Always,
+        )
+        .unwrap();
+
+        assert_eq!(verdict.verdict, Verdict::RuntimeError);
+        assert_eq!(verdict.message.as_deref(), Some("Exit code: 1"));
+        assert!(host.checker.calls().is_empty());
+    }
+
+    #[test]
+    fn nonzero_exit_with_correct_output_is_checked_when_not_required() {
+        let host = Host::mock();
+        host.checker.queue_verdict(CheckerVerdict {
+            verdict: Verdict::Accepted,
+            score: 1.0,
+            message: None,
+        });
+
+        let verdict = interpret_sandbox_result(
+            &host.checker,
+            0,
+            &nonzero_exit_op(),
+            "exact",
+            &checker_input(),
+            false,
+            StderrCapture::Always,
+        )
+        .unwrap();
+
+        assert_eq!(verdict.verdict, Verdict::Accepted);
+        let calls = host.checker.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].1.stdout, JudgeFile::inline("3\n"));
+        assert_eq!(calls[0].1.exit_code, 1);
+    }
+
+    fn clean_exit_with_stderr_op() -> OperationResult {
+        let exec = TaskExecutionResult {
+            task_id: "exec".to_string(),
+            success: true,
+            sandbox_result: ExecutionResult {
+                exit_code: Some(0),
+                status: "OK".to_string(),
+                stdout: "4\n".to_string(),
+                stderr: "debug: n=2\n".to_string(),
+                ..Default::default()
+            },
+            collected_outputs: HashMap::new(),
+        };
+        OperationResult {
+            success: true,
+            task_results: HashMap::from([("exec".to_string(), exec)]),
+            error: None,
+            worker_id: None,
+        }
+    }
+
+    #[test]
+    fn on_failure_capture_keeps_stderr_of_wrong_answers_only() {
+        let host = Host::mock();
+        host.checker.queue_verdict(CheckerVerdict {
+            verdict: Verdict::WrongAnswer,
+            score: 0.0,
+            message: None,
+        });
+        host.checker.queue_verdict(CheckerVerdict {
+            verdict: Verdict::Accepted,
+            score: 1.0,
+            message: None,
+        });
+
+        let wrong = interpret_sandbox_result(
+            &host.checker,
+            0,
+            &clean_exit_with_stderr_op(),
+            "exact",
+            &checker_input(),
+            false,
+            StderrCapture::OnFailure,
+        )
+        .unwrap();
+        let accepted = interpret_sandbox_result(
+            &host.checker,
+            0,
+            &clean_exit_with_stderr_op(),
+            "exact",
+            &checker_input(),
+            false,
+            StderrCapture::OnFailure,
+        )
+        .unwrap();
+
+        assert_eq!(wrong.verdict, Verdict::WrongAnswer);
+        assert_eq!(wrong.stderr.as_deref(), Some("debug: n=2\n"));
+        assert_eq!(accepted.verdict, Verdict::Accepted);
+        assert!(accepted.stderr.is_none());
+    }
+
+    #[test]
+    fn failed_compile_is_compile_error_with_diagnostics() {
+        let verdict = interpret_compile_result(0, &compile_op(Some(1), false, "error: x"));
+
+        assert_eq!(verdict.verdict, Verdict::CompileError);
+        assert_eq!(verdict.message.as_deref(), Some("error: x"));
+    }
+
+    #[test]
+    fn clean_compile_is_accepted_with_warnings() {
+        let verdict = interpret_compile_result(0, &compile_op(Some(0), true, "warning: y"));
+
+        assert_eq!(verdict.verdict, Verdict::Accepted);
+        assert_eq!(verdict.message.as_deref(), Some("warning: y"));
+        assert!(verdict.stdout.is_none());
+    }
+
+    #[test]
+    fn compile_timeout_is_compile_error() {
+        let mut result = compile_op(None, false, "");
+        let compile = result.task_results.get_mut("compile").unwrap();
+        compile.sandbox_result.status = "TO".into();
+        compile.sandbox_result.wall_time_used = 10.0;
+
+        let verdict = interpret_compile_result(0, &result);
+
+        assert_eq!(verdict.verdict, Verdict::CompileError);
+        assert_eq!(
+            verdict.message.as_deref(),
+            Some("Compilation time limit exceeded (10.0s)")
+        );
+    }
+
+    #[test]
+    fn language_without_compile_step_is_accepted() {
+        let result = OperationResult {
+            success: true,
+            ..Default::default()
+        };
+
+        let verdict = interpret_compile_result(0, &result);
+
+        assert_eq!(verdict.verdict, Verdict::Accepted);
+        assert!(verdict.message.is_none());
+    }
+}
@@ -1,5 +1,9 @@
+mod compile;
 mod interpret;
+mod output_only;
 mod run;
 
-pub use interpret::interpret_sandbox_result;
+pub use compile::{compile_only, compile_submission};
+pub use interpret::{interpret_compile_result, interpret_sandbox_result};
+pub use output_only::evaluate_output_only;
 pub use run::{evaluate_run, handle_code_run};
@@ -0,0 +1,193 @@
+use std::path::Path;
+
+use crate::Checker;
+use crate::types::*;
+
+/// Judges one test case of an output-only problem, where the contestant
+/// submits output files instead of a program.
+///
+/// Nothing is compiled or run: the submitted file goes straight to the
+/// checker as the program's stdout. A file named after the test case's
+/// label (e.g. `7.out` for the case labelled `7`) answers that case;
+/// otherwise a lone submitted file answers every case. A case without an
+/// answer is a `WrongAnswer`.
+pub fn evaluate_output_only(checker: &Checker, req: &BuildEvalOpsInput) -> TestCaseVerdict {
+    let verdict = |verdict: Verdict, score: f64, message: Option<String>| TestCaseVerdict {
+        test_case_id: req.test_case_id,
+        verdict,
+        score,
+        time_used_ms: None,
+        memory_used_kb: None,
+        message,
+        stdout: None,
+        stderr: None,
+        worker_id: None,
+    };
+
+    let Some(output) = submitted_output(req) else {
+        return verdict(
+            Verdict::WrongAnswer,
+            0.0,
+            Some(format!(
+                "No output submitted for test case {}",
+                req.test_case_label
+                    .clone()
+                    .unwrap_or_else(|| req.test_case_id.to_string())
+            )),
+        );
+    };
+
+    let input = CheckerParseInput {
+        stdout: JudgeFile::inline(output.content.clone()),
+        stderr: String::new(),
+        exit_code: 0,
+        expected_output: req.expected_output.clone(),
+        test_input: req.test_input.clone(),
+        checker_source: req.checker_source.clone(),
+        config: req.checker_config.clone(),
+    };
+    let checker_format = req.checker_format.as_deref().unwrap_or("exact");
+    match checker.run(checker_format, &input) {
+        Ok(v) => verdict(v.verdict, v.score, v.message),
+        Err(e) => verdict(
+            Verdict::SystemError,
+            0.0,
+            Some(format!("Checker call failed: {:?}", e)),
+        ),
+    }
+}
+
+fn submitted_output(req: &BuildEvalOpsInput) -> Option<&SourceFile> {
+    let submitted: Vec<&SourceFile> = req
+        .solution_source
+        .iter()
+        .filter(|f| {
+            !req.additional_file_refs
+                .iter()
+                .any(|extra| extra.filename == f.filename)
+        })
+        .collect();
+    let case_stem = req.test_case_label.as_deref();
+    submitted
+        .iter()
+        .find(|f| {
+            case_stem.is_some_and(|label| {
+                Path::new(&f.filename)
+                    .file_stem()
+                    .is_some_and(|stem| stem.to_str() == Some(label))
+            })
+        })
+        .or(match submitted.as_slice() {
+            [only] => Some(only),
+            _ => None,
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Host;
+
+    /// A request for the stored case labelled `label`, whose database id is
+    /// deliberately unrelated to its label.
+    fn request(label: &str, files: &[(&str, &str)]) -> BuildEvalOpsInput {
+        BuildEvalOpsInput {
+            problem_id: 1,
+            test_case_id: 900,
+            test_case_label: Some(label.into()),
+            solution_source: files
+                .iter()
+                .map(|(filename, content)| SourceFile {
+                    filename: filename.to_string(),
+                    content: content.to_string(),
+                })
+                .collect(),
+            solution_language: "output".into(),
+            time_limit_ms: 1000,
+            memory_limit_kb: 262144,
+            wall_time_multiplier: None,
+            require_zero_exit: true,
+            contest_id: None,
+            test_input: JudgeFile::inline("1 2\n"),
+            expected_output: JudgeFile::inline("3\n"),
+            checker_format: Some("tokens".into()),
+            checker_config: None,
+            checker_source: None,
+            additional_file_refs: vec![],
+            target_worker_id: None,
+            judge_mode: JudgeMode::Full,
+            judge_job: None,
+        }
+    }
+
+    #[test]
+    fn submitted_file_is_checked_without_running_anything() {
+        let host = Host::mock();
+        host.checker.queue_verdict(CheckerVerdict {
+            verdict: Verdict::Accepted,
+            score: 1.0,
+            message: None,
+        });
+
+        let verdict = evaluate_output_only(&host.checker, &request("4", &[("answer.txt", "3\n")]));
+
+        assert_eq!(verdict.verdict, Verdict::Accepted);
+        assert_eq!(verdict.score, 1.0);
+        assert_eq!(verdict.time_used_ms, None);
+        let calls = host.checker.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "tokens");
+        assert_eq!(calls[0].1.stdout, JudgeFile::inline("3\n"));
+        assert_eq!(calls[0].1.expected_output, JudgeFile::inline("3\n"));
+    }
+
+    #[test]
+    fn checker_verdict_is_reported() {
+        let host = Host::mock();
+        host.checker.queue_verdict(CheckerVerdict {
+            verdict: Verdict::WrongAnswer,
+            score: 0.0,
+            message: Some("expected 3, found 4".into()),
+        });
+
+        let verdict = evaluate_output_only(&host.checker, &request("4", &[("answer.txt", "4\n")]));
+
+        assert_eq!(verdict.verdict, Verdict::WrongAnswer);
+        assert_eq!(verdict.message.as_deref(), Some("expected 3, found 4"));
+    }
+
+    #[test]
+    fn file_named_after_the_case_answers_it() {
+        let host = Host::mock();
+        host.checker.queue_verdict(CheckerVerdict {
+            verdict: Verdict::Accepted,
+            score: 1.0,
+            message: None,
+        });
+        let files = [("1.out", "first\n"), ("2.out", "second\n")];
+
+        evaluate_output_only(&host.checker, &request("2", &files));
+
+        assert_eq!(
+            host.checker.calls()[0].1.stdout,
+            JudgeFile::inline("second\n")
+        );
+    }
+
+    #[test]
+    fn case_without_an_answer_is_wrong_without_checking() {
+        let host = Host::mock();
+        let files = [("1.out", "first\n"), ("2.out", "second\n")];
+
+        let verdict = evaluate_output_only(&host.checker, &request("3", &files));
+
+        assert_eq!(verdict.verdict, Verdict::WrongAnswer);
+        assert_eq!(
+            verdict.message.as_deref(),
+            Some("No output submitted for test case 3")
+        );
+        assert_eq!(verdict.score, 0.0);
+        assert!(host.checker.calls().is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use super::compile::compile_only;
 use crate::Host;
 use crate::error::SdkError;
 use crate::types::*;
@@ -13,6 +14,10 @@ pub fn handle_code_run(host: &Host, input: &str) -> Result<String, SdkError> {
 }
 
 pub fn evaluate_run(host: &Host, req: &OnCodeRunInput) -> Result<OnCodeRunOutput, SdkError> {
+    if req.judge_mode.is_compile_only() {
+        return compile_run(host, req);
+    }
+
     let test_cases = &req.test_cases;
 
     if test_cases.is_empty() {
@@ -55,6 +60,8 @@ pub fn evaluate_run(host: &Host, req: &OnCodeRunInput) -> Result<OnCodeRunOutput
                 is_custom: tc.is_custom,
                 // Code-run flow does not currently support worker pinning.
                 target_worker_id: None,
+                judge_mode: JudgeMode::Full,
+                judge_job: None,
             })
             .collect(),
     };
@@ -134,6 +141,76 @@ pub fn evaluate_run(host: &Host, req: &OnCodeRunInput) -> Result<OnCodeRunOutput
     finalize_code_run(host, req.id, &verdicts)
 }
 
+/// Compiles the run's source and records the diagnostics as its compile
+/// output, without inserting any test case result.
+fn compile_run(host: &Host, req: &OnCodeRunInput) -> Result<OnCodeRunOutput, SdkError> {
+    host.code_run.update(&CodeRunUpdate {
+        code_run_id: req.id,
+        status: Some(SubmissionStatus::Running),
+        ..Default::default()
+    })?;
+
+    let verdict = compile_only(
+        host,
+        &req.problem_type,
+        StartEvaluateCaseInput {
+            problem_id: req.problem_id,
+            test_case_id: 0,
+            solution_source: req.files.clone(),
+            solution_language: req.language.clone(),
+            time_limit_ms: req.time_limit_ms,
+            memory_limit_kb: req.memory_limit_kb,
+            contest_id: req.contest_id,
+            input: TestCaseBodyRef::default(),
+            expected_output: TestCaseBodyRef::default(),
+            is_custom: true,
+            target_worker_id: None,
+            judge_mode: JudgeMode::CompileOnly,
+            judge_job: None,
+        },
+    )?;
+
+    if verdict.verdict == Verdict::SystemError {
+        return Ok(OnCodeRunOutput {
+            success: false,
+            error_message: verdict.message,
+        });
+    }
+
+    let update = if verdict.verdict == Verdict::CompileError {
+        CodeRunUpdate {
+            code_run_id: req.id,
+            status: Some(SubmissionStatus::CompilationError),
+            verdict: Some(None),
+            score: Some(0.0),
+            compile_output: Some(verdict.message.clone()),
+            ..Default::default()
+        }
+    } else {
+        CodeRunUpdate {
+            code_run_id: req.id,
+            status: Some(SubmissionStatus::Judged),
+            verdict: Some(Some(Verdict::Accepted)),
+            score: Some(0.0),
+            time_used: Some(None),
+            memory_used: Some(None),
+            compile_output: Some(verdict.message.clone()),
+            ..Default::default()
+        }
+    };
+    host.code_run.update(&update)?;
+
+    let _ = host.log.info(&format!(
+        "Code run {} compiled only: {:?}",
+        req.id, verdict.verdict
+    ));
+
+    Ok(OnCodeRunOutput {
+        success: true,
+        error_message: None,
+    })
+}
+
 struct TcResult {
     test_case_id: i32,
     verdict: Verdict,
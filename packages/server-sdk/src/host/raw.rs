@@ -23,7 +23,11 @@ extern "ExtismHost" {
     pub fn resolve_language(input: String) -> String;
     pub fn register_language_resolver(input: String);
     pub fn store_get(input: String) -> String;
+    pub fn store_has(input: String) -> String;
     pub fn store_set(input: String);
+    pub fn store_get_many(collection: String, keys: String) -> String;
+    pub fn store_set_many(collection: String, entries: String);
+    pub fn store_keys(collection: String, prefix: String) -> String;
     pub fn store_compare_and_set(input: String) -> String;
     pub fn store_delete(input: String);
     pub fn blob_read_range(input: String) -> String;
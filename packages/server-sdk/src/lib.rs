@@ -1,6 +1,7 @@
 pub mod api;
 pub mod contest;
 pub mod error;
+pub mod standings;
 pub mod types;
 
 #[cfg(feature = "guest")]
@@ -29,5 +30,7 @@ pub mod prelude {
     #[cfg(feature = "guest")]
     pub use crate::evaluator;
     #[cfg(feature = "guest")]
+    pub use crate::standings;
+    #[cfg(feature = "guest")]
     pub use crate::sdk::*;
 }
@@ -1,3 +1,8 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::cell::RefCell;
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::VecDeque;
+
 use crate::error::SdkError;
 #[cfg(target_arch = "wasm32")]
 use crate::types::RunCheckerInput;
@@ -22,22 +27,41 @@ impl Checker {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub(super) struct CheckerMock;
+pub(super) struct CheckerMock {
+    verdicts: RefCell<VecDeque<CheckerVerdict>>,
+    calls: RefCell<Vec<(String, CheckerParseInput)>>,
+}
 
 #[cfg(not(target_arch = "wasm32"))]
 impl CheckerMock {
     pub fn new() -> Self {
-        Self
+        Self {
+            verdicts: RefCell::new(VecDeque::new()),
+            calls: RefCell::new(Vec::new()),
+        }
     }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl Checker {
-    pub fn run(
-        &self,
-        _format: &str,
-        _input: &CheckerParseInput,
-    ) -> Result<CheckerVerdict, SdkError> {
-        Err(SdkError::Other("Mock checker not implemented".into()))
+    pub fn run(&self, format: &str, input: &CheckerParseInput) -> Result<CheckerVerdict, SdkError> {
+        self.inner
+            .calls
+            .borrow_mut()
+            .push((format.to_string(), input.clone()));
+        self.inner
+            .verdicts
+            .borrow_mut()
+            .pop_front()
+            .ok_or_else(|| SdkError::Other("Mock checker not implemented".into()))
+    }
+
+    pub fn queue_verdict(&self, verdict: CheckerVerdict) {
+        self.inner.verdicts.borrow_mut().push_back(verdict);
+    }
+
+    /// Format and input of every `run` call, in order.
+    pub fn calls(&self) -> Vec<(String, CheckerParseInput)> {
+        self.inner.calls.borrow().clone()
     }
 }
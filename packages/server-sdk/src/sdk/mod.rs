@@ -20,7 +20,7 @@ pub use language::Language;
 pub use logger::Logger;
 pub use operations::Operations;
 pub use registry::Registry;
-pub use storage::Storage;
+pub use storage::{KeyList, Storage};
 pub use submissions::Submissions;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -42,13 +42,19 @@ impl Operations {
             .get("output")
             .ok_or_else(|| SdkError::HostCall("Missing 'output' in TaskResult".into()))?;
 
-        serde_json::from_value::<OperationResult>(output.clone()).map_err(|_| {
-            let error_msg = output
-                .get("error")
-                .and_then(|e| e.as_str())
-                .unwrap_or("Unknown operation error");
-            SdkError::HostCall(format!("Operation failed at worker: {error_msg}"))
-        })
+        let mut result =
+            serde_json::from_value::<OperationResult>(output.clone()).map_err(|_| {
+                let error_msg = output
+                    .get("error")
+                    .and_then(|e| e.as_str())
+                    .unwrap_or("Unknown operation error");
+                SdkError::HostCall(format!("Operation failed at worker: {error_msg}"))
+            })?;
+        result.worker_id = task_result
+            .get("worker_id")
+            .and_then(|id| id.as_str())
+            .map(String::from);
+        Ok(result)
     }
 
     pub fn cancel_batch(&self, batch_id: &str) -> Result<(), SdkError> {
@@ -38,6 +38,25 @@ impl Registry {
         Ok(())
     }
 
+    /// Registers a contest type that scores a submission as the sum of its
+    /// test case scores, with the most severe test case verdict, so the
+    /// server may recompute its results from the stored rows.
+    pub fn register_summed_contest_type(
+        &self,
+        contest_type: &str,
+        submission_handler: &str,
+        code_run_handler: &str,
+    ) -> Result<(), SdkError> {
+        let input = serde_json::json!({
+            "type": contest_type,
+            "submission_handler": submission_handler,
+            "code_run_handler": code_run_handler,
+            "summed_scores": true,
+        });
+        unsafe { crate::host::raw::register_contest_type(serde_json::to_string(&input)?)? };
+        Ok(())
+    }
+
     pub fn register_evaluator(&self, evaluator_type: &str, handler: &str) -> Result<(), SdkError> {
         let input = serde_json::json!({
             "type": evaluator_type,
@@ -64,6 +83,31 @@ impl Registry {
         default_filename: &str,
         extensions: &[&str],
         template: &str,
+    ) -> Result<(), SdkError> {
+        self.register_language_resolver_with_binary_extensions(
+            language_id,
+            function_name,
+            display_name,
+            default_filename,
+            extensions,
+            &[],
+            template,
+        )
+    }
+
+    /// Like [`Self::register_language_resolver`], but files with one of
+    /// `binary_extensions` are accepted in submissions without being checked
+    /// for valid UTF-8.
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_language_resolver_with_binary_extensions(
+        &self,
+        language_id: &str,
+        function_name: &str,
+        display_name: &str,
+        default_filename: &str,
+        extensions: &[&str],
+        binary_extensions: &[&str],
+        template: &str,
     ) -> Result<(), SdkError> {
         let input = serde_json::json!({
             "language_id": language_id,
@@ -71,11 +115,35 @@ impl Registry {
             "display_name": display_name,
             "default_filename": default_filename,
             "extensions": extensions,
+            "binary_extensions": binary_extensions,
             "template": template,
         });
         unsafe { crate::host::raw::register_language_resolver(serde_json::to_string(&input)?)? };
         Ok(())
     }
+
+    /// Like [`Self::register_language_resolver`], but the language can only
+    /// be submitted to problems of one of `problem_types`.
+    pub fn register_language_resolver_for_problem_types(
+        &self,
+        language_id: &str,
+        function_name: &str,
+        display_name: &str,
+        default_filename: &str,
+        extensions: &[&str],
+        problem_types: &[&str],
+    ) -> Result<(), SdkError> {
+        let input = serde_json::json!({
+            "language_id": language_id,
+            "function_name": function_name,
+            "display_name": display_name,
+            "default_filename": default_filename,
+            "extensions": extensions,
+            "problem_types": problem_types,
+        });
+        unsafe { crate::host::raw::register_language_resolver(serde_json::to_string(&input)?)? };
+        Ok(())
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -109,6 +177,15 @@ impl Registry {
         Ok(())
     }
 
+    pub fn register_summed_contest_type(
+        &self,
+        _contest_type: &str,
+        _submission_handler: &str,
+        _code_run_handler: &str,
+    ) -> Result<(), SdkError> {
+        Ok(())
+    }
+
     pub fn register_evaluator(
         &self,
         _evaluator_type: &str,
@@ -132,4 +209,30 @@ impl Registry {
     ) -> Result<(), SdkError> {
         Ok(())
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_language_resolver_with_binary_extensions(
+        &self,
+        _language_id: &str,
+        _function_name: &str,
+        _display_name: &str,
+        _default_filename: &str,
+        _extensions: &[&str],
+        _binary_extensions: &[&str],
+        _template: &str,
+    ) -> Result<(), SdkError> {
+        Ok(())
+    }
+
+    pub fn register_language_resolver_for_problem_types(
+        &self,
+        _language_id: &str,
+        _function_name: &str,
+        _display_name: &str,
+        _default_filename: &str,
+        _extensions: &[&str],
+        _problem_types: &[&str],
+    ) -> Result<(), SdkError> {
+        Ok(())
+    }
 }
@@ -1,5 +1,5 @@
 use crate::db::Params;
-use crate::types::{SubmissionStatus, Verdict, sanitize_result_text_field};
+use crate::types::{SubmissionStatus, SubtaskScore, Verdict, sanitize_result_text_field};
 
 #[cfg(target_arch = "wasm32")]
 use crate::error::SdkError;
@@ -102,7 +102,7 @@ fn push_double_opt(p: &mut Params, sets: &mut Vec<String>, col: &str, val: &Opti
     }
 }
 
-fn push_double_opt_str(
+pub(super) fn push_double_opt_str(
     p: &mut Params,
     sets: &mut Vec<String>,
     col: &str,
@@ -117,3 +117,14 @@ fn push_double_opt_str(
         None => {}
     }
 }
+
+pub(super) fn push_subtasks(
+    p: &mut Params,
+    sets: &mut Vec<String>,
+    subtasks: &Option<Vec<SubtaskScore>>,
+) {
+    if let Some(subtasks) = subtasks {
+        let json = serde_json::to_string(subtasks).unwrap_or_else(|_| "[]".to_string());
+        sets.push(format!("subtask_results = {}::jsonb", p.bind(json)));
+    }
+}
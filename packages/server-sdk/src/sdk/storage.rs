@@ -16,6 +16,12 @@ pub struct BlobRange {
     pub eof: bool,
 }
 
+pub struct KeyList {
+    pub keys: Vec<String>,
+    /// The host caps each listing; `true` means more keys matched.
+    pub truncated: bool,
+}
+
 #[cfg(target_arch = "wasm32")]
 impl Storage {
     pub fn get(&self, keys: &[&str]) -> Result<HashMap<String, String>, SdkError> {
@@ -42,6 +48,15 @@ impl Storage {
         Ok(map.remove(key))
     }
 
+    /// Whether `key` exists, without reading its value. A stored `"null"`
+    /// counts as present.
+    pub fn has(&self, key: &str) -> Result<bool, SdkError> {
+        let input = serde_json::json!({ "key": key });
+        let result_json = unsafe { crate::host::raw::store_has(serde_json::to_string(&input)?)? };
+        let result: serde_json::Value = serde_json::from_str(&result_json)?;
+        Ok(result["exists"].as_bool().unwrap_or(false))
+    }
+
     pub fn set(&self, entries: &[(&str, &str)]) -> Result<(), SdkError> {
         if entries.is_empty() {
             return Ok(());
@@ -55,6 +70,68 @@ impl Storage {
         Ok(())
     }
 
+    /// Read `keys` from a named collection in one host round-trip. Missing
+    /// keys are absent from the result.
+    pub fn get_many(
+        &self,
+        collection: &str,
+        keys: &[&str],
+    ) -> Result<HashMap<String, String>, SdkError> {
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let result_json = unsafe {
+            crate::host::raw::store_get_many(collection.to_string(), serde_json::to_string(keys)?)?
+        };
+        let result: serde_json::Value = serde_json::from_str(&result_json)?;
+        let values = result
+            .get("values")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(values)
+    }
+
+    /// List keys in a named collection that start with `prefix`, in key order.
+    pub fn keys(&self, collection: &str, prefix: &str) -> Result<KeyList, SdkError> {
+        let result_json =
+            unsafe { crate::host::raw::store_keys(collection.to_string(), prefix.to_string())? };
+        let result: serde_json::Value = serde_json::from_str(&result_json)?;
+        let keys = result
+            .get("keys")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|k| k.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let truncated = result["truncated"].as_bool().unwrap_or(false);
+        Ok(KeyList { keys, truncated })
+    }
+
+    /// Write `entries` into a named collection in one host round-trip.
+    pub fn set_many(&self, collection: &str, entries: &[(&str, &str)]) -> Result<(), SdkError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let entries_json: Vec<serde_json::Value> = entries
+            .iter()
+            .map(|(k, v)| serde_json::json!({ "key": k, "value": v }))
+            .collect();
+        unsafe {
+            crate::host::raw::store_set_many(
+                collection.to_string(),
+                serde_json::to_string(&entries_json)?,
+            )?
+        };
+        Ok(())
+    }
+
     pub fn delete(&self, keys: &[&str]) -> Result<(), SdkError> {
         if keys.is_empty() {
             return Ok(());
@@ -129,6 +206,7 @@ impl Storage {
 #[cfg(not(target_arch = "wasm32"))]
 pub(super) struct StorageMock {
     data: std::cell::RefCell<HashMap<String, String>>,
+    collections: std::cell::RefCell<HashMap<String, HashMap<String, String>>>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -136,6 +214,7 @@ impl StorageMock {
     pub fn new() -> Self {
         Self {
             data: std::cell::RefCell::new(HashMap::new()),
+            collections: std::cell::RefCell::new(HashMap::new()),
         }
     }
 }
@@ -154,6 +233,10 @@ impl Storage {
         Ok(self.inner.data.borrow().get(key).cloned())
     }
 
+    pub fn has(&self, key: &str) -> Result<bool, SdkError> {
+        Ok(self.inner.data.borrow().contains_key(key))
+    }
+
     pub fn set(&self, entries: &[(&str, &str)]) -> Result<(), SdkError> {
         let mut data = self.inner.data.borrow_mut();
         for (k, v) in entries {
@@ -162,6 +245,48 @@ impl Storage {
         Ok(())
     }
 
+    pub fn get_many(
+        &self,
+        collection: &str,
+        keys: &[&str],
+    ) -> Result<HashMap<String, String>, SdkError> {
+        let collections = self.inner.collections.borrow();
+        let Some(data) = collections.get(collection) else {
+            return Ok(HashMap::new());
+        };
+        Ok(keys
+            .iter()
+            .filter_map(|k| data.get(*k).map(|v| (k.to_string(), v.clone())))
+            .collect())
+    }
+
+    pub fn keys(&self, collection: &str, prefix: &str) -> Result<KeyList, SdkError> {
+        let collections = self.inner.collections.borrow();
+        let mut keys: Vec<String> = collections
+            .get(collection)
+            .map(|data| {
+                data.keys()
+                    .filter(|k| k.starts_with(prefix))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        keys.sort();
+        Ok(KeyList {
+            keys,
+            truncated: false,
+        })
+    }
+
+    pub fn set_many(&self, collection: &str, entries: &[(&str, &str)]) -> Result<(), SdkError> {
+        let mut collections = self.inner.collections.borrow_mut();
+        let data = collections.entry(collection.to_string()).or_default();
+        for (k, v) in entries {
+            data.insert(k.to_string(), v.to_string());
+        }
+        Ok(())
+    }
+
     pub fn delete(&self, keys: &[&str]) -> Result<(), SdkError> {
         let mut data = self.inner.data.borrow_mut();
         for key in keys {
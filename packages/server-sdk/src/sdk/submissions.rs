@@ -47,11 +47,25 @@ impl Submissions {
             &update.error_code,
             &update.error_message,
         );
+        super::shared::push_double_opt_str(&mut p, &mut sets, "judged_by", &update.judged_by);
+        super::shared::push_subtasks(&mut p, &mut sets, &update.subtasks);
 
         if sets.is_empty() {
             return Ok(1);
         }
 
+        // A terminal write records which judgement it came from, so a
+        // redelivered result for that judgement is dropped below rather than
+        // applied a second time.
+        let ends_judgement =
+            update.judgement_id > 0 && update.status.as_ref().is_some_and(|s| s.is_terminal());
+        if ends_judgement {
+            sets.push(format!(
+                "applied_judgement_id = {}",
+                p.bind(update.judgement_id)
+            ));
+        }
+
         // Mirror the same column writes onto submission_judgement so the
         // versioned row stays in sync with the denormalized cache. Skipped
         // when judgement_id is unset (legacy caller path); the backfill
@@ -72,6 +86,8 @@ impl Submissions {
                 &update.error_code,
                 &update.error_message,
             );
+            super::shared::push_double_opt_str(&mut jp, &mut jsets, "judged_by", &update.judged_by);
+            super::shared::push_subtasks(&mut jp, &mut jsets, &update.subtasks);
             if !jsets.is_empty() {
                 let mut judgement_sets: Vec<String> = jsets
                     .into_iter()
@@ -86,29 +102,45 @@ impl Submissions {
                 if is_terminal_marker {
                     judgement_sets.push("is_finalized = TRUE".to_string());
                     judgement_sets.push("finalized_at = NOW()".to_string());
+                    judgement_sets.push(
+                        "judging_duration_ms = \
+                         (EXTRACT(EPOCH FROM (NOW() - created_at)) * 1000)::BIGINT"
+                            .to_string(),
+                    );
                 }
                 let jsql = format!(
                     "UPDATE submission_judgement SET {} WHERE id = {} AND judge_epoch = {} \
                      AND is_finalized = FALSE \
                      AND (is_current = TRUE OR version = ( \
                          SELECT MAX(version) FROM submission_judgement WHERE submission_id = {} \
-                     ))",
+                     )) \
+                     AND NOT EXISTS ( \
+                         SELECT 1 FROM submission WHERE id = {} AND applied_judgement_id = {} \
+                     )",
                     judgement_sets.join(", "),
                     jp.bind(update.judgement_id),
                     jp.bind(update.judge_epoch),
                     jp.bind(update.submission_id),
+                    jp.bind(update.submission_id),
+                    jp.bind(update.judgement_id),
                 );
                 judgement_rows = super::shared::raw_execute(&jsql, &jp.into_args())?;
             }
         }
 
-        let sql = format!(
+        let mut sql = format!(
             "UPDATE submission SET {} WHERE id = {} AND judge_epoch = {} \
              AND status NOT IN ('Judged', 'CompilationError', 'SystemError')",
             sets.join(", "),
             p.bind(update.submission_id),
             p.bind(update.judge_epoch),
         );
+        if update.judgement_id > 0 {
+            sql.push_str(&format!(
+                " AND applied_judgement_id IS DISTINCT FROM {}",
+                p.bind(update.judgement_id)
+            ));
+        }
         let submission_rows = super::shared::raw_execute(&sql, &p.into_args())?;
         if update.judgement_id > 0 {
             Ok(judgement_rows)
@@ -119,49 +151,13 @@ impl Submissions {
 
     pub fn insert_results(&self, results: &[TestCaseResultRow]) -> Result<(), SdkError> {
         use crate::db::Params;
-        use crate::types::sanitize_result_text_field;
-        use serde_json::json;
 
         if results.is_empty() {
             return Ok(());
         }
 
         let mut p = Params::new();
-        let mut rows = Vec::with_capacity(results.len());
-
-        for r in results {
-            let score_val = if r.score.is_finite() { r.score } else { 0.0 };
-            let message = r.message.as_deref().map(sanitize_result_text_field);
-            let stdout = r.stdout.as_deref().map(sanitize_result_text_field);
-            let stderr = r.stderr.as_deref().map(sanitize_result_text_field);
-            let judgement_param = if r.judgement_id > 0 {
-                json!(r.judgement_id)
-            } else {
-                json!(null)
-            };
-            rows.push(format!(
-                "({}, {}::int, {}::int, {}::int, {}, {}, {}::int, {}::int, {}::text, {}::text, {}::text, NOW())",
-                p.bind(r.submission_id),
-                p.bind(judgement_param),
-                p.bind(json!(r.test_case_id)),
-                p.bind(json!(r.run_index)),
-                p.bind(r.verdict.to_db_str()),
-                p.bind(score_val),
-                p.bind(json!(r.time_used)),
-                p.bind(json!(r.memory_used)),
-                p.bind(json!(message.as_deref())),
-                p.bind(json!(stdout.as_deref())),
-                p.bind(json!(stderr.as_deref())),
-            ));
-        }
-
-        let sql = format!(
-            "INSERT INTO test_case_result \
-             (submission_id, judgement_id, test_case_id, run_index, verdict, score, \
-              time_used, memory_used, checker_output, stdout, stderr, created_at) \
-             VALUES {}",
-            rows.join(", ")
-        );
+        let sql = insert_results_sql(&mut p, results);
         super::shared::raw_execute(&sql, &p.into_args())?;
         Ok(())
     }
@@ -170,21 +166,89 @@ impl Submissions {
         use crate::db::Params;
 
         let mut p = Params::new();
-        let sql = if judgement_id > 0 {
-            format!(
-                "DELETE FROM test_case_result WHERE submission_id = {} AND judgement_id = {}",
-                p.bind(submission_id),
-                p.bind(judgement_id),
-            )
-        } else {
-            format!(
-                "DELETE FROM test_case_result WHERE submission_id = {} AND judgement_id IS NULL",
-                p.bind(submission_id),
-            )
-        };
+        let sql = delete_results_sql(&mut p, submission_id, judgement_id);
         super::shared::raw_execute(&sql, &p.into_args())?;
         Ok(())
     }
+
+    /// Swaps a judgement's result rows for `results` in one transaction, so
+    /// a failure part-way never leaves the judgement without rows.
+    pub fn replace_results(
+        &self,
+        submission_id: i32,
+        judgement_id: i32,
+        results: &[TestCaseResultRow],
+    ) -> Result<(), SdkError> {
+        use crate::db::Params;
+
+        let tx = super::Db {}.begin()?;
+        let mut p = Params::new();
+        let sql = delete_results_sql(&mut p, submission_id, judgement_id);
+        tx.execute_with_args(&sql, &p.into_args())?;
+        if !results.is_empty() {
+            let mut p = Params::new();
+            let sql = insert_results_sql(&mut p, results);
+            tx.execute_with_args(&sql, &p.into_args())?;
+        }
+        tx.commit()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn insert_results_sql(p: &mut crate::db::Params, results: &[TestCaseResultRow]) -> String {
+    use crate::types::sanitize_result_text_field;
+    use serde_json::json;
+
+    let mut rows = Vec::with_capacity(results.len());
+    for r in results {
+        let score_val = if r.score.is_finite() { r.score } else { 0.0 };
+        let message = r.message.as_deref().map(sanitize_result_text_field);
+        let stdout = r.stdout.as_deref().map(sanitize_result_text_field);
+        let stderr = r.stderr.as_deref().map(sanitize_result_text_field);
+        let judgement_param = if r.judgement_id > 0 {
+            json!(r.judgement_id)
+        } else {
+            json!(null)
+        };
+        rows.push(format!(
+            "({}, {}::int, {}::int, {}::int, {}, {}, {}::int, {}::int, {}::text, {}::text, {}::text, NOW())",
+            p.bind(r.submission_id),
+            p.bind(judgement_param),
+            p.bind(json!(r.test_case_id)),
+            p.bind(json!(r.run_index)),
+            p.bind(r.verdict.to_db_str()),
+            p.bind(score_val),
+            p.bind(json!(r.time_used)),
+            p.bind(json!(r.memory_used)),
+            p.bind(json!(message.as_deref())),
+            p.bind(json!(stdout.as_deref())),
+            p.bind(json!(stderr.as_deref())),
+        ));
+    }
+
+    format!(
+        "INSERT INTO test_case_result \
+         (submission_id, judgement_id, test_case_id, run_index, verdict, score, \
+          time_used, memory_used, checker_output, stdout, stderr, created_at) \
+         VALUES {}",
+        rows.join(", ")
+    )
+}
+
+#[cfg(target_arch = "wasm32")]
+fn delete_results_sql(p: &mut crate::db::Params, submission_id: i32, judgement_id: i32) -> String {
+    if judgement_id > 0 {
+        format!(
+            "DELETE FROM test_case_result WHERE submission_id = {} AND judgement_id = {}",
+            p.bind(submission_id),
+            p.bind(judgement_id),
+        )
+    } else {
+        format!(
+            "DELETE FROM test_case_result WHERE submission_id = {} AND judgement_id IS NULL",
+            p.bind(submission_id),
+        )
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -247,6 +311,23 @@ impl Submissions {
         Ok(())
     }
 
+    pub fn replace_results(
+        &self,
+        submission_id: i32,
+        judgement_id: i32,
+        results: &[TestCaseResultRow],
+    ) -> Result<(), SdkError> {
+        if let Some(err) = self.inner.insert_errors.borrow_mut().pop_front() {
+            return Err(err);
+        }
+        self.delete_results(submission_id, judgement_id)?;
+        self.inner
+            .tc_results
+            .borrow_mut()
+            .extend_from_slice(results);
+        Ok(())
+    }
+
     pub fn set_test_cases(&self, tcs: Vec<TestCaseRow>) {
         *self.inner.test_cases.borrow_mut() = tcs;
     }
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "guest")]
+use crate::error::SdkError;
+use crate::types::PluginHttpRequest;
+
+/// Points for the first solver of a problem under dynamic scoring.
+const DYNAMIC_MAX_POINTS: f64 = 100.0;
+/// Points lost per earlier solver under dynamic scoring.
+const DYNAMIC_DECAY_PER_SOLVE: f64 = 2.0;
+/// Floor for late solvers under dynamic scoring.
+const DYNAMIC_MIN_POINTS: f64 = 30.0;
+
+/// Which of a participant's judged submissions to a problem sets its score
+/// on score-based boards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum ScoreSelection {
+    /// The highest score among the participant's submissions.
+    #[default]
+    Best,
+    /// The score of the participant's most recent submission.
+    Last,
+}
+
+/// A contest's standings options, resolved for one viewer. Every contest
+/// type's board applies them, so the options mean the same thing whichever
+/// plugin serves the standings.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StandingsPolicy {
+    /// The viewer has `contest:manage` permission and sees the live board.
+    #[serde(skip)]
+    pub is_manager: bool,
+    #[serde(skip)]
+    pub viewer_id: Option<i32>,
+    /// The contest sets `anonymize_standings_until_end` and has not ended.
+    pub anonymize: bool,
+    /// How many top rows viewers without `contest:manage` get.
+    pub public_standings_limit: Option<i32>,
+    /// Problems are worth [`dynamic_points`] by solve order instead of
+    /// their score.
+    pub dynamic_scoring: bool,
+    pub score_selection: ScoreSelection,
+    /// Contest problems' `max_points`, by problem id, for those that set it.
+    #[serde(skip)]
+    pub max_points: HashMap<i32, i32>,
+}
+
+impl StandingsPolicy {
+    /// Name shown for a participant. While the board is anonymized, viewers
+    /// without `contest:manage` see everyone but themselves as
+    /// `Participant #k`, where `k` is the participant's 1-based position in
+    /// registration order, so a participant keeps the same label across
+    /// requests.
+    pub fn display_name(&self, user_id: i32, username: &str, registration_number: usize) -> String {
+        if self.anonymize && !self.is_manager && self.viewer_id != Some(user_id) {
+            format!("Participant #{registration_number}")
+        } else {
+            username.to_string()
+        }
+    }
+
+    /// Scales `score`, out of `raw_total`, to `problem_id`'s `max_points`.
+    /// Left unchanged under dynamic scoring, for problems without
+    /// `max_points`, and when there is no positive `raw_total` to scale from.
+    pub fn scale_score(&self, problem_id: i32, score: f64, raw_total: f64) -> f64 {
+        match self.max_points.get(&problem_id) {
+            Some(&max) if !self.dynamic_scoring && raw_total > 0.0 => {
+                score * f64::from(max) / raw_total
+            }
+            _ => score,
+        }
+    }
+
+    /// Drops the ranked `rows` past `public_standings_limit` unless the
+    /// viewer has `contest:manage`.
+    pub fn truncate<T>(&self, rows: &mut Vec<T>) {
+        if let Some(limit) = self.public_standings_limit
+            && !self.is_manager
+        {
+            rows.truncate(usize::try_from(limit).unwrap_or(0));
+        }
+    }
+
+    /// SQL conditions, each starting with ` AND `, on the `submission` rows
+    /// aliased `alias` that leave out what the viewer may not see. Viewers
+    /// without `contest:manage` only count submissions made before the
+    /// contest ended and, while it is frozen, before its `frozen_at`, and
+    /// not those whose results `result_delay_secs` still withholds.
+    pub fn submission_filter(&self, alias: &str) -> String {
+        if self.is_manager {
+            return String::new();
+        }
+        format!(
+            " AND {alias}.created_at < (SELECT LEAST(end_time, COALESCE(frozen_at, end_time)) \
+             FROM contest WHERE id = {alias}.contest_id) AND NOT {}",
+            crate::contest::results_withheld(alias)
+        )
+    }
+}
+
+/// `format` query value that asks a standings route for a
+/// [`StandingsTable`] instead of its own response.
+pub const TABLE_FORMAT: &str = "table";
+
+/// Whether `req` asks for a [`StandingsTable`].
+pub fn wants_table(req: &PluginHttpRequest) -> bool {
+    req.query.get("format").map(String::as_str) == Some(TABLE_FORMAT)
+}
+
+/// A board in the shape every contest type's standings route returns for
+/// `?format=table`, so the server can export and summarize it without
+/// knowing the contest type. It holds what the requesting viewer may see.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandingsTable {
+    /// Problem labels, in contest order.
+    pub labels: Vec<String>,
+    /// Rows in rank order.
+    pub rows: Vec<StandingsTableRow>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandingsTableRow {
+    pub rank: usize,
+    pub user_id: i32,
+    pub username: String,
+    /// One cell per label.
+    pub cells: Vec<StandingsTableCell>,
+    pub total: f64,
+    pub solved: usize,
+    /// Penalty minutes; 0 on boards without a penalty.
+    pub penalty: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandingsTableCell {
+    /// The problem's score on the board. `None` when the participant has
+    /// none or the viewer may not see it.
+    pub score: Option<f64>,
+    /// Judged attempts the board counts for the problem.
+    pub attempts: u32,
+    /// Milliseconds from contest start to the accepted submission that
+    /// solved the problem.
+    pub solve_time_ms: Option<i64>,
+    /// Penalty minutes the problem adds; 0 while unsolved.
+    pub penalty: i64,
+}
+
+/// Points earned by the `solve_rank`-th (1-based) solver of a problem under
+/// dynamic scoring. Never increases with `solve_rank`.
+pub fn dynamic_points(solve_rank: usize) -> f64 {
+    let earlier = solve_rank.saturating_sub(1) as f64;
+    (DYNAMIC_MAX_POINTS - DYNAMIC_DECAY_PER_SOLVE * earlier).max(DYNAMIC_MIN_POINTS)
+}
+
+/// [`dynamic_points`] per `(user_id, problem)` solve. `solves` holds each
+/// solver's `(user_id, problem, solve time)`; a problem's solvers are ordered
+/// by time, ties going to the lower user id.
+pub fn dynamic_solve_points<K: Copy + Eq + Hash + Ord>(
+    mut solves: Vec<(i32, K, i64)>,
+) -> HashMap<(i32, K), f64> {
+    solves.sort_by_key(|&(user_id, problem, at)| (problem, at, user_id));
+    let mut solved_so_far: HashMap<K, usize> = HashMap::new();
+    solves
+        .into_iter()
+        .map(|(user_id, problem, _)| {
+            let rank = solved_so_far.entry(problem).or_default();
+            *rank += 1;
+            ((user_id, problem), dynamic_points(*rank))
+        })
+        .collect()
+}
+
+/// Loads `contest_id`'s standings options for the viewer of `req`.
+#[cfg(feature = "guest")]
+pub fn load_policy(
+    host: &crate::sdk::Host,
+    req: &PluginHttpRequest,
+    contest_id: i32,
+) -> Result<StandingsPolicy, SdkError> {
+    let mut p = crate::db::Params::new();
+    let sql = format!(
+        "SELECT (anonymize_standings_until_end AND NOW() < end_time) AS anonymize, \
+                public_standings_limit, dynamic_scoring, score_selection \
+         FROM contest WHERE id = {}",
+        p.bind(contest_id)
+    );
+    let policy = host
+        .db
+        .query_one_with_args::<StandingsPolicy>(&sql, &p.into_args())?
+        .unwrap_or_default();
+
+    #[derive(Deserialize)]
+    struct MaxPoints {
+        problem_id: i32,
+        max_points: i32,
+    }
+    let mut p = crate::db::Params::new();
+    let sql = format!(
+        "SELECT problem_id, max_points FROM contest_problem \
+         WHERE contest_id = {} AND max_points IS NOT NULL",
+        p.bind(contest_id)
+    );
+    let max_points = host
+        .db
+        .query_with_args::<MaxPoints>(&sql, &p.into_args())?
+        .into_iter()
+        .map(|row| (row.problem_id, row.max_points))
+        .collect();
+
+    Ok(StandingsPolicy {
+        is_manager: req.has_permission("contest:manage"),
+        viewer_id: req.user_id(),
+        max_points,
+        ..policy
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anonymized(viewer_id: i32) -> StandingsPolicy {
+        StandingsPolicy {
+            is_manager: false,
+            viewer_id: Some(viewer_id),
+            anonymize: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn anonymized_names_follow_registration_order() {
+        let policy = anonymized(7);
+        assert_eq!(policy.display_name(3, "carol", 2), "Participant #2");
+        assert_eq!(policy.display_name(7, "alice", 1), "alice");
+    }
+
+    #[test]
+    fn managers_and_plain_boards_see_usernames() {
+        let manager = StandingsPolicy {
+            is_manager: true,
+            ..anonymized(1)
+        };
+        assert_eq!(manager.display_name(3, "carol", 2), "carol");
+        assert_eq!(
+            StandingsPolicy::default().display_name(3, "carol", 2),
+            "carol"
+        );
+    }
+
+    #[test]
+    fn only_non_managers_get_the_freeze_cutoff_and_result_delay() {
+        let manager = StandingsPolicy {
+            is_manager: true,
+            ..Default::default()
+        };
+        assert_eq!(manager.submission_filter("s"), "");
+        let filter = StandingsPolicy::default().submission_filter("s");
+        assert!(filter.starts_with(" AND s.created_at < "));
+        assert!(filter.contains("frozen_at"));
+        assert!(filter.contains("AND NOT EXISTS (SELECT 1 FROM contest_problem cp"));
+        assert!(filter.contains("s.created_at + make_interval(secs => cp.result_delay_secs)"));
+    }
+
+    #[test]
+    fn only_managers_see_past_the_public_limit() {
+        let limited = StandingsPolicy {
+            public_standings_limit: Some(2),
+            ..Default::default()
+        };
+        let mut rows = vec![1, 2, 3];
+        limited.truncate(&mut rows);
+        assert_eq!(rows, [1, 2]);
+
+        let manager = StandingsPolicy {
+            is_manager: true,
+            ..limited
+        };
+        let mut rows = vec![1, 2, 3];
+        manager.truncate(&mut rows);
+        assert_eq!(rows, [1, 2, 3]);
+    }
+
+    #[test]
+    fn later_solvers_earn_fewer_dynamic_points() {
+        assert_eq!(dynamic_points(1), DYNAMIC_MAX_POINTS);
+        for k in 2..=10 {
+            assert!(dynamic_points(k) < dynamic_points(1), "solver {k}");
+            assert!(dynamic_points(k) < dynamic_points(k - 1), "solver {k}");
+        }
+        let late = dynamic_points(10_000);
+        assert_eq!(late, DYNAMIC_MIN_POINTS);
+        assert!(dynamic_points(10_001) <= late);
+    }
+
+    #[test]
+    fn solve_points_follow_solve_time_per_problem() {
+        let points = dynamic_solve_points(vec![(2, 7, 30), (1, 7, 10), (3, 7, 10), (4, 8, 50)]);
+        assert_eq!(points[&(1, 7)], dynamic_points(1));
+        assert_eq!(points[&(3, 7)], dynamic_points(2));
+        assert_eq!(points[&(2, 7)], dynamic_points(3));
+        assert_eq!(points[&(4, 8)], dynamic_points(1));
+    }
+
+    #[test]
+    fn max_points_scale_static_scores_only() {
+        let policy = StandingsPolicy {
+            max_points: HashMap::from([(7, 50)]),
+            ..Default::default()
+        };
+        assert_eq!(policy.scale_score(7, 30.0, 60.0), 25.0);
+        assert_eq!(policy.scale_score(8, 30.0, 60.0), 30.0);
+        assert_eq!(policy.scale_score(7, 30.0, 0.0), 30.0);
+
+        let dynamic = StandingsPolicy {
+            dynamic_scoring: true,
+            ..policy
+        };
+        assert_eq!(dynamic.scale_score(7, 30.0, 60.0), 30.0);
+    }
+}
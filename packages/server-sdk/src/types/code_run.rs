@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::query::TestCaseRow;
-use super::submission::SourceFile;
+use super::submission::{JudgeMode, SourceFile};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OnCodeRunInput {
@@ -16,6 +16,8 @@ pub struct OnCodeRunInput {
     pub problem_type: String,
     #[serde(default)]
     pub test_cases: Vec<TestCaseRow>,
+    #[serde(default, skip_serializing_if = "JudgeMode::is_full")]
+    pub judge_mode: JudgeMode,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
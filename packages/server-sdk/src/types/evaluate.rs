@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-use super::operation::ResourceLimits;
-use super::submission::SourceFile;
+use super::operation::{EnvRule, JudgeJob, ResourceLimits};
+use super::submission::{JudgeMode, SourceFile};
 use super::verdict::Verdict;
 
 pub const DEFAULT_EVALUATION_RESULT_TIMEOUT_MIN_MS: u64 = 15 * 60 * 1000;
@@ -177,6 +177,13 @@ pub struct StartEvaluateCaseInput {
     /// plugins from `OnSubmissionInput.target_worker_id`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub target_worker_id: Option<String>,
+    /// Forwarded from `OnSubmissionInput.judge_mode` / `OnCodeRunInput.judge_mode`.
+    #[serde(default, skip_serializing_if = "JudgeMode::is_full")]
+    pub judge_mode: JudgeMode,
+    /// Set by contest plugins from `OnSubmissionInput::judge_job`; `None`
+    /// for code runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub judge_job: Option<JudgeJob>,
 }
 
 #[cfg(test)]
@@ -226,6 +233,8 @@ mod timeout_tests {
             expected_output: TestCaseBodyRef::blob("abc123"),
             is_custom: false,
             target_worker_id: None,
+            judge_mode: JudgeMode::Full,
+            judge_job: None,
         };
 
         let json = serde_json::to_value(&input).unwrap();
@@ -246,10 +255,13 @@ mod timeout_tests {
         let input = BuildEvalOpsInput {
             problem_id: 1,
             test_case_id: 2,
+            test_case_label: None,
             solution_source: vec![],
             solution_language: "cpp".to_string(),
             time_limit_ms: 1000,
             memory_limit_kb: 262_144,
+            wall_time_multiplier: None,
+            require_zero_exit: true,
             contest_id: None,
             test_input: JudgeFile::inline("1 2\n"),
             expected_output: JudgeFile::blob(FileRef {
@@ -263,6 +275,8 @@ mod timeout_tests {
             checker_source: None,
             additional_file_refs: vec![],
             target_worker_id: None,
+            judge_mode: JudgeMode::Full,
+            judge_job: None,
         };
 
         let json = serde_json::to_value(&input).unwrap();
@@ -286,14 +300,30 @@ mod timeout_tests {
     }
 }
 
+fn default_require_zero_exit() -> bool {
+    true
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildEvalOpsInput {
     pub problem_id: i32,
     pub test_case_id: i32,
+    /// The stored test case's label, e.g. `3` or `sample-1`. `None` for
+    /// custom test cases.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_case_label: Option<String>,
     pub solution_source: Vec<SourceFile>,
     pub solution_language: String,
     pub time_limit_ms: i32,
     pub memory_limit_kb: i32,
+    /// The problem's wall-clock limit as a multiple of `time_limit_ms`.
+    /// `None` leaves the evaluator's configured multiplier in place.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wall_time_multiplier: Option<f64>,
+    /// Judge a non-zero exit as `RuntimeError` even when the output is
+    /// correct. When false, the checker decides.
+    #[serde(default = "default_require_zero_exit")]
+    pub require_zero_exit: bool,
     #[serde(default)]
     pub contest_id: Option<i32>,
 
@@ -316,6 +346,19 @@ pub struct BuildEvalOpsInput {
     /// routes the operation to the pinned worker.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub target_worker_id: Option<String>,
+
+    /// Forwarded from `StartEvaluateCaseInput.judge_mode`. With
+    /// [`JudgeMode::CompileOnly`] evaluators build only the compile step and
+    /// report it through
+    /// [`interpret_compile_result`](crate::evaluator::interpret_compile_result).
+    #[serde(default, skip_serializing_if = "JudgeMode::is_full")]
+    pub judge_mode: JudgeMode,
+
+    /// Forwarded from `StartEvaluateCaseInput.judge_job`. Evaluator plugins
+    /// copy this onto each `OperationTask` they build so workers can drop
+    /// operations of a superseded dispatch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub judge_job: Option<JudgeJob>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -336,6 +379,9 @@ pub struct TestCaseVerdict {
     pub stdout: Option<String>,
     #[serde(default)]
     pub stderr: Option<String>,
+    /// Worker that ran the test case, when one did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_id: Option<String>,
 }
 
 impl TestCaseVerdict {
@@ -349,6 +395,7 @@ impl TestCaseVerdict {
             message: None,
             stdout: None,
             stderr: None,
+            worker_id: None,
         }
     }
 
@@ -362,6 +409,7 @@ impl TestCaseVerdict {
             message: Some("Wrong answer".into()),
             stdout: None,
             stderr: None,
+            worker_id: None,
         }
     }
 
@@ -375,6 +423,7 @@ impl TestCaseVerdict {
             message: Some("Time limit exceeded".into()),
             stdout: None,
             stderr: None,
+            worker_id: None,
         }
     }
 
@@ -388,6 +437,7 @@ impl TestCaseVerdict {
             message: Some("Compilation failed".into()),
             stdout: None,
             stderr: None,
+            worker_id: None,
         }
     }
 
@@ -401,6 +451,7 @@ impl TestCaseVerdict {
             message: Some("System error".into()),
             stdout: None,
             stderr: None,
+            worker_id: None,
         }
     }
 }
@@ -441,6 +492,33 @@ pub struct CompileSpec {
     pub outputs: Vec<OutputSpec>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub resource_limits: Option<ResourceLimits>,
+    /// CPU time limit in seconds for the compile step, independent of the
+    /// problem's run time limit. Overrides only the time limits of
+    /// `resource_limits` or the evaluator's defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit: Option<f64>,
+    /// Extra environment for the compile step. Filtered through
+    /// [`sanitize_untrusted_env_rules`](super::sanitize_untrusted_env_rules)
+    /// by evaluators before reaching the sandbox.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_rules: Vec<EnvRule>,
+}
+
+impl CompileSpec {
+    /// Sandbox limits for the compile step: `resource_limits` if set,
+    /// otherwise `default`, with `time_limit` applied on top. The wall-time
+    /// limit keeps its ratio to the CPU time limit.
+    pub fn limits_or(&self, default: impl FnOnce() -> ResourceLimits) -> ResourceLimits {
+        let mut limits = self.resource_limits.clone().unwrap_or_else(default);
+        if let Some(time_limit) = self.time_limit.filter(|t| t.is_finite() && *t > 0.0) {
+            limits.wall_time_limit = match (limits.time_limit, limits.wall_time_limit) {
+                (Some(cpu), Some(wall)) if cpu > 0.0 => Some(time_limit * wall / cpu),
+                (_, wall) => wall.map(|w| w.max(time_limit)),
+            };
+            limits.time_limit = Some(time_limit);
+        }
+        limits
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -472,4 +550,51 @@ impl OutputSpec {
 pub struct RunSpec {
     pub command: Vec<String>,
     pub extra_files: Vec<String>,
+    /// Extra environment for running the contestant program. Filtered
+    /// through [`sanitize_untrusted_env_rules`](super::sanitize_untrusted_env_rules)
+    /// by evaluators before reaching the sandbox.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_rules: Vec<EnvRule>,
+}
+
+#[cfg(test)]
+mod compile_spec_tests {
+    use super::*;
+
+    fn spec(time_limit: Option<f64>) -> CompileSpec {
+        CompileSpec {
+            command: vec!["g++".into()],
+            cache_inputs: vec![],
+            outputs: vec![],
+            resource_limits: None,
+            time_limit,
+            env_rules: vec![],
+        }
+    }
+
+    fn defaults() -> ResourceLimits {
+        ResourceLimits {
+            time_limit: Some(10.0),
+            wall_time_limit: Some(20.0),
+            memory_limit: Some(524_288),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn without_time_limit_uses_defaults() {
+        let limits = spec(None).limits_or(defaults);
+
+        assert_eq!(limits.time_limit, Some(10.0));
+        assert_eq!(limits.wall_time_limit, Some(20.0));
+    }
+
+    #[test]
+    fn time_limit_overrides_only_time_limits() {
+        let limits = spec(Some(3.0)).limits_or(defaults);
+
+        assert_eq!(limits.time_limit, Some(3.0));
+        assert_eq!(limits.wall_time_limit, Some(6.0));
+        assert_eq!(limits.memory_limit, Some(524_288));
+    }
 }
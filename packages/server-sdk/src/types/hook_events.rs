@@ -12,6 +12,9 @@ pub struct BeforeSubmissionEvent {
     pub contest_id: Option<i32>,
     pub language: String,
     pub file_count: usize,
+    /// Submitted filenames, trimmed. Empty when sent by an older server.
+    #[serde(default)]
+    pub filenames: Vec<String>,
 }
 
 impl HookEvent for BeforeSubmissionEvent {
@@ -60,6 +63,7 @@ mod tests {
             contest_id: Some(3),
             language: "cpp".into(),
             file_count: 1,
+            filenames: vec!["main.cpp".into()],
         };
         let json = serde_json::to_value(&event).unwrap();
         assert_eq!(json["user_id"], 5);
@@ -67,6 +71,7 @@ mod tests {
         let back: BeforeSubmissionEvent = serde_json::from_value(json).unwrap();
         assert_eq!(back.user_id, 5);
         assert_eq!(back.contest_id, Some(3));
+        assert_eq!(back.filenames, vec!["main.cpp".to_string()]);
     }
 
     #[test]
@@ -77,6 +82,7 @@ mod tests {
             contest_id: None,
             language: "py".into(),
             file_count: 2,
+            filenames: vec!["main.py".into(), "util.py".into()],
         };
         let json = serde_json::to_value(&event).unwrap();
         assert!(json.get("contest_id").is_none());
@@ -84,6 +90,18 @@ mod tests {
         assert_eq!(back.contest_id, None);
     }
 
+    #[test]
+    fn before_submission_event_defaults_filenames_when_missing() {
+        let json = serde_json::json!({
+            "user_id": 1,
+            "problem_id": 2,
+            "language": "cpp",
+            "file_count": 1,
+        });
+        let event: BeforeSubmissionEvent = serde_json::from_value(json).unwrap();
+        assert!(event.filenames.is_empty());
+    }
+
     #[test]
     fn after_judging_event_serializes_and_deserializes_with_all_fields() {
         let event = AfterJudgingEvent {
@@ -24,14 +24,15 @@ pub use evaluate::{
 pub use hook_events::{AfterJudgingEvent, AfterSubmissionEvent, BeforeSubmissionEvent, HookEvent};
 pub use http::{PluginHttpAuth, PluginHttpRequest, PluginHttpResponse};
 pub use operation::{
-    Channel, DirectoryOptions, DirectoryRule, EnvRule, Environment, ExecutionResult, IOConfig,
-    IOTarget, OperationResult, OperationTask, ResourceLimits, RunOptions, SandboxResult,
-    SessionFile, Step, StepCacheConfig, TaskExecutionResult,
+    Channel, DISK_QUOTA_EXCEEDED_STATUS, DirectoryOptions, DirectoryRule, EnvRule, Environment,
+    ExecutionResult, IOConfig, IOTarget, JudgeJob, OperationResult, OperationTask, ResourceLimits,
+    RunOptions, SandboxResult, SessionFile, StderrCapture, Step, StepCacheConfig,
+    TaskExecutionResult, UNTRUSTED_ENV_ALLOWLIST, ms_from_seconds, sanitize_untrusted_env_rules,
 };
 pub use persistence::{
-    CodeRunResultRow, CodeRunUpdate, SubmissionStatus, SubmissionUpdate, TestCaseResultRow,
-    sanitize_result_text_field, sanitize_text_field,
+    CodeRunResultRow, CodeRunUpdate, SubmissionStatus, SubmissionUpdate, SubtaskScore,
+    TestCaseResultRow, sanitize_result_text_field, sanitize_text_field,
 };
 pub use query::{ProblemCheckerInfo, TestCaseData, TestCaseRow};
-pub use submission::{OnSubmissionInput, OnSubmissionOutput, SourceFile};
+pub use submission::{JudgeMode, OnSubmissionInput, OnSubmissionOutput, SourceFile};
 pub use verdict::Verdict;
@@ -1,3 +1,4 @@
+use super::Verdict;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -57,13 +58,61 @@ pub struct DirectoryRule {
     pub options: DirectoryOptions,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EnvRule {
     Inherit(String),
     Set(String, String),
     FullEnv,
 }
 
+/// Variables a language config may set or inherit into a sandbox that runs
+/// contestant code. Anything else (credentials, `LD_PRELOAD`, ...) is dropped.
+pub const UNTRUSTED_ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "LANG",
+    "LC_ALL",
+    "LC_CTYPE",
+    "TZ",
+    "PYTHONHASHSEED",
+    "PYTHONIOENCODING",
+    "PYTHONDONTWRITEBYTECODE",
+    "PYTHONUNBUFFERED",
+];
+
+impl EnvRule {
+    /// Whether this rule may be applied to a sandbox running untrusted code.
+    /// `FullEnv` is never allowed: it would leak the worker's environment.
+    pub fn is_allowed_for_untrusted(&self) -> bool {
+        match self {
+            EnvRule::Inherit(var) | EnvRule::Set(var, _) => {
+                UNTRUSTED_ENV_ALLOWLIST.contains(&var.as_str())
+            }
+            EnvRule::FullEnv => false,
+        }
+    }
+}
+
+/// Keeps only the rules allowed by [`UNTRUSTED_ENV_ALLOWLIST`].
+///
+/// The result is never empty: `PATH` is inherited unless the caller already
+/// set or inherited it, because sandboxes treat an empty rule list as
+/// "inherit everything".
+pub fn sanitize_untrusted_env_rules(rules: &[EnvRule]) -> Vec<EnvRule> {
+    let mut sanitized: Vec<EnvRule> = rules
+        .iter()
+        .filter(|rule| rule.is_allowed_for_untrusted())
+        .cloned()
+        .collect();
+    let has_path = sanitized
+        .iter()
+        .any(|rule| matches!(rule, EnvRule::Inherit(var) | EnvRule::Set(var, _) if var == "PATH"));
+    if !has_path {
+        sanitized.insert(0, EnvRule::Inherit("PATH".into()));
+    }
+    sanitized
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ResourceLimits {
     pub time_limit: Option<f64>,
@@ -74,6 +123,38 @@ pub struct ResourceLimits {
     pub open_files_limit: Option<u32>,
     pub file_size_limit: Option<u32>,
     pub process_limit: Option<u32>,
+    /// Total size in KiB the program may write into the sandbox working
+    /// directory, across all files and on top of what the box already held.
+    /// Exceeding it kills the program with [`DISK_QUOTA_EXCEEDED_STATUS`];
+    /// `file_size_limit` only caps each file on its own.
+    pub disk_quota: Option<u32>,
+}
+
+/// Which test cases keep the program's stderr in their verdict. The worker
+/// keeps stderr for everything but [`StderrCapture::Never`], since it cannot
+/// tell a wrong answer from an accepted one; the evaluator drops the rest once
+/// the verdict is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StderrCapture {
+    #[default]
+    Always,
+    /// Every test case that was not accepted, including wrong answers that
+    /// exited cleanly.
+    OnFailure,
+    /// Never; stderr is discarded while the program runs.
+    Never,
+}
+
+impl StderrCapture {
+    /// Whether a test case judged `verdict` keeps its stderr.
+    pub fn keeps(self, verdict: &Verdict) -> bool {
+        match self {
+            Self::Always => true,
+            Self::OnFailure => *verdict != Verdict::Accepted,
+            Self::Never => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,6 +169,7 @@ pub struct RunOptions {
     pub stderr: Option<PathBuf>,
     pub env_rules: Vec<EnvRule>,
     pub directory_rules: Vec<DirectoryRule>,
+    pub stderr_capture: StderrCapture,
 }
 
 impl Default for RunOptions {
@@ -102,6 +184,7 @@ impl Default for RunOptions {
                 open_files_limit: None,
                 file_size_limit: None,
                 process_limit: Some(1),
+                disk_quota: None,
             },
             wait: true,
             as_uid: None,
@@ -111,6 +194,7 @@ impl Default for RunOptions {
             stderr: None,
             env_rules: vec![EnvRule::FullEnv],
             directory_rules: vec![],
+            stderr_capture: StderrCapture::Always,
         }
     }
 }
@@ -163,6 +247,21 @@ pub struct OperationTask {
     /// pool. Used by admin probe / pinned-rejudge flows.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub target_worker_id: Option<String>,
+    /// Contest the operation is judged for. With contest result sharding
+    /// enabled the host replies on that contest's result queue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contest_id: Option<i32>,
+    /// Submission judgement the operation is run for. Workers drop it
+    /// unrun once a rejudge has moved the submission past its epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub judge_job: Option<JudgeJob>,
+}
+
+/// Identifies the dispatch of a submission that produced an operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JudgeJob {
+    pub submission_id: i32,
+    pub judge_epoch: i32,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -170,6 +269,10 @@ pub struct OperationResult {
     pub success: bool,
     pub task_results: HashMap<String, TaskExecutionResult>,
     pub error: Option<String>,
+    /// Worker that ran the operation, filled in by the host from the task
+    /// result envelope.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -181,16 +284,23 @@ pub struct TaskExecutionResult {
     pub collected_outputs: HashMap<String, String>,
 }
 
+/// [`ExecutionResult::status`] of a run killed for exceeding
+/// [`ResourceLimits::disk_quota`].
+pub const DISK_QUOTA_EXCEEDED_STATUS: &str = "DQ";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionResult {
     #[serde(default)]
     pub exit_code: Option<i32>,
     #[serde(default)]
     pub signal: Option<i32>,
+    /// CPU time in seconds.
     #[serde(default)]
     pub time_used: f64,
+    /// Wall-clock time in seconds.
     #[serde(default)]
     pub wall_time_used: f64,
+    /// Peak memory in kilobytes.
     #[serde(default)]
     pub memory_used: Option<u32>,
     #[serde(default)]
@@ -225,4 +335,100 @@ impl Default for ExecutionResult {
     }
 }
 
+impl ExecutionResult {
+    /// [`Self::time_used`] in whole milliseconds, the unit judge results
+    /// store and report.
+    pub fn time_used_ms(&self) -> Option<i64> {
+        ms_from_seconds(self.time_used)
+    }
+
+    /// [`Self::memory_used`] in kilobytes, the unit judge results store and
+    /// report.
+    pub fn memory_used_kb(&self) -> Option<i64> {
+        self.memory_used.map(i64::from)
+    }
+}
+
+/// Converts a sandbox time in seconds to whole milliseconds, rounding up so
+/// a run that took any time at all is never reported as 0 ms. `None` for
+/// negative, non-finite or out-of-range values.
+pub fn ms_from_seconds(seconds: f64) -> Option<i64> {
+    if !seconds.is_finite() || seconds < 0.0 {
+        return None;
+    }
+    // Snap to whole microseconds first so float noise in values like
+    // 0.012 s does not round up to 13 ms.
+    let ms = ((seconds * 1_000_000.0).round() / 1000.0).ceil();
+    (ms < i64::MAX as f64).then_some(ms as i64)
+}
+
 pub type SandboxResult = ExecutionResult;
+
+#[cfg(test)]
+mod ms_from_seconds_tests {
+    use super::*;
+
+    #[test]
+    fn sub_millisecond_times_round_up() {
+        assert_eq!(ms_from_seconds(0.0), Some(0));
+        assert_eq!(ms_from_seconds(0.0005), Some(1));
+        assert_eq!(ms_from_seconds(0.001), Some(1));
+        assert_eq!(ms_from_seconds(0.0011), Some(2));
+        assert_eq!(ms_from_seconds(1.5), Some(1500));
+    }
+
+    #[test]
+    fn whole_millisecond_times_are_not_bumped_by_float_noise() {
+        for ms in [12, 100, 333, 999, 2048] {
+            assert_eq!(ms_from_seconds(ms as f64 / 1000.0), Some(ms));
+        }
+        assert_eq!(ms_from_seconds(0.012), Some(12));
+        assert_eq!(ms_from_seconds(0.07), Some(70));
+    }
+
+    #[test]
+    fn invalid_times_have_no_millisecond_value() {
+        assert_eq!(ms_from_seconds(-0.001), None);
+        assert_eq!(ms_from_seconds(f64::NAN), None);
+        assert_eq!(ms_from_seconds(f64::INFINITY), None);
+        assert_eq!(ms_from_seconds(f64::MAX), None);
+    }
+}
+
+#[cfg(test)]
+mod env_rule_tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_keeps_allowlisted_rules_and_drops_the_rest() {
+        let rules = vec![
+            EnvRule::Set("PYTHONHASHSEED".into(), "0".into()),
+            EnvRule::Inherit("AWS_SECRET_ACCESS_KEY".into()),
+            EnvRule::Set("LD_PRELOAD".into(), "/tmp/evil.so".into()),
+            EnvRule::FullEnv,
+            EnvRule::Inherit("LANG".into()),
+        ];
+        assert_eq!(
+            sanitize_untrusted_env_rules(&rules),
+            vec![
+                EnvRule::Inherit("PATH".into()),
+                EnvRule::Set("PYTHONHASHSEED".into(), "0".into()),
+                EnvRule::Inherit("LANG".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn sanitize_never_returns_empty_rules() {
+        assert_eq!(
+            sanitize_untrusted_env_rules(&[EnvRule::FullEnv]),
+            vec![EnvRule::Inherit("PATH".into())]
+        );
+    }
+
+    #[test]
+    fn sanitize_respects_explicit_path() {
+        let rules = vec![EnvRule::Set("PATH".into(), "/usr/bin".into())];
+        assert_eq!(sanitize_untrusted_env_rules(&rules), rules);
+    }
+}
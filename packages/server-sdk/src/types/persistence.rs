@@ -1,5 +1,7 @@
 use std::borrow::Cow;
 
+use serde::{Deserialize, Serialize};
+
 use super::verdict::Verdict;
 
 pub const RESULT_TEXT_DB_LIMIT_CHARS: usize = 64 * 1024;
@@ -62,6 +64,22 @@ pub struct SubmissionUpdate {
     pub compile_output: Option<Option<String>>,
     pub error_code: Option<Option<String>>,
     pub error_message: Option<Option<String>>,
+    /// Worker that ran the test cases, for debugging.
+    pub judged_by: Option<Option<String>>,
+    /// Per-subtask breakdown of `score`, in subtask order. `None` leaves
+    /// the stored breakdown unchanged.
+    pub subtasks: Option<Vec<SubtaskScore>>,
+}
+
+/// Points awarded for one subtask of a judged submission.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubtaskScore {
+    pub name: String,
+    pub score: f64,
+    pub max_score: f64,
+    /// Worst verdict among the subtask's evaluated test cases; `None` when
+    /// none of them ran.
+    pub verdict: Option<Verdict>,
 }
 
 impl SubmissionUpdate {
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use super::operation::JudgeJob;
 use super::query::TestCaseRow;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,6 +9,27 @@ pub struct SourceFile {
     pub content: String,
 }
 
+/// How far a submission or code run is judged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JudgeMode {
+    /// Compile, then run every test case.
+    #[default]
+    Full,
+    /// Compile and report diagnostics without running any test case.
+    CompileOnly,
+}
+
+impl JudgeMode {
+    pub fn is_full(&self) -> bool {
+        matches!(self, Self::Full)
+    }
+
+    pub fn is_compile_only(&self) -> bool {
+        matches!(self, Self::CompileOnly)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OnSubmissionInput {
     pub submission_id: i32,
@@ -35,6 +57,20 @@ pub struct OnSubmissionInput {
     /// must forward it onto each `StartEvaluateCaseInput`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub target_worker_id: Option<String>,
+    /// With [`JudgeMode::CompileOnly`] contest plugins compile the source
+    /// and record the diagnostics without evaluating any test case.
+    #[serde(default, skip_serializing_if = "JudgeMode::is_full")]
+    pub judge_mode: JudgeMode,
+}
+
+impl OnSubmissionInput {
+    /// The job to forward onto each `StartEvaluateCaseInput`.
+    pub fn judge_job(&self) -> JudgeJob {
+        JudgeJob {
+            submission_id: self.submission_id,
+            judge_epoch: self.judge_epoch,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,7 +74,8 @@ pub async fn mark_submission_system_error_with_epoch<C: ConnectionTrait>(
         (
             r#"UPDATE submission_judgement
                SET status = $1, error_code = $2, error_message = $3,
-                   is_finalized = TRUE, finalized_at = NOW()
+                   is_finalized = TRUE, finalized_at = NOW(),
+                   judging_duration_ms = (EXTRACT(EPOCH FROM (NOW() - created_at)) * 1000)::BIGINT
                WHERE submission_id = $4 AND is_current = TRUE AND is_finalized = FALSE
                  AND judge_epoch = $5"#,
             vec![
@@ -89,7 +90,8 @@ pub async fn mark_submission_system_error_with_epoch<C: ConnectionTrait>(
         (
             r#"UPDATE submission_judgement
                SET status = $1, error_code = $2, error_message = $3,
-                   is_finalized = TRUE, finalized_at = NOW()
+                   is_finalized = TRUE, finalized_at = NOW(),
+                   judging_duration_ms = (EXTRACT(EPOCH FROM (NOW() - created_at)) * 1000)::BIGINT
                WHERE submission_id = $4 AND is_current = TRUE AND is_finalized = FALSE"#,
             vec![
                 SubmissionStatus::SystemError.to_string().into(),
@@ -1,46 +1,190 @@
 use crate::registry::OperationWaiters;
 use common::worker::TaskResult;
-use mq::MqQueue;
-use std::sync::Arc;
+use mq::{ConsumeConfig, MqQueue};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
 use tracing::{debug, error, info, warn};
 
+/// Task ids of the most recently delivered operation results.
+///
+/// The MQ delivers at least once, so the same result can arrive twice. Its
+/// waiter is gone after the first delivery, and remembering the id lets the
+/// consumer tell such a duplicate apart from a result whose batch was
+/// cancelled. Holds at most `capacity` ids, evicting the oldest first.
+pub struct DeliveredResults {
+    capacity: usize,
+    inner: Mutex<DeliveredInner>,
+}
+
+#[derive(Default)]
+struct DeliveredInner {
+    order: VecDeque<String>,
+    ids: HashSet<String>,
+}
+
+impl DeliveredResults {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(DeliveredInner::default()),
+        }
+    }
+
+    fn contains(&self, task_id: &str) -> bool {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.ids.contains(task_id)
+    }
+
+    fn record(&self, task_id: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if !inner.ids.insert(task_id.to_string()) {
+            return;
+        }
+        inner.order.push_back(task_id.to_string());
+        while inner.order.len() > self.capacity {
+            if let Some(evicted) = inner.order.pop_front() {
+                inner.ids.remove(&evicted);
+            }
+        }
+    }
+}
+
+/// What became of a consumed operation result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// Handed to the waiting plugin.
+    Delivered,
+    /// Already delivered once; dropped.
+    Duplicate,
+    /// No waiter and not seen before, e.g. the batch was cancelled.
+    Orphaned,
+}
+
+/// Routes `result` to its waiter at most once.
+pub fn deliver_result(
+    waiters: &OperationWaiters,
+    delivered: &DeliveredResults,
+    result: TaskResult,
+) -> Delivery {
+    let task_id = result.task_id.clone();
+
+    let Some((_, tx)) = waiters.remove(&task_id) else {
+        if delivered.contains(&task_id) {
+            debug!(%task_id, "Duplicate operation result ignored");
+            return Delivery::Duplicate;
+        }
+        warn!(%task_id, "Operation result received but no waiter found (batch may have been cancelled)");
+        return Delivery::Orphaned;
+    };
+
+    delivered.record(&task_id);
+    if tx.send(result).is_err() {
+        error!(%task_id, "Failed to send operation result to waiter (receiver dropped)");
+    } else {
+        debug!(%task_id, "Operation result delivered to plugin");
+    }
+    Delivery::Delivered
+}
+
 pub async fn consume_operation_results(
     mq: Arc<MqQueue>,
     waiters: OperationWaiters,
     queue_name: String,
+    config: ConsumeConfig,
+    dedup_window: usize,
 ) {
     info!(
         queue = %queue_name,
+        concurrency = config.concurrency,
+        dedup_window,
         "Starting operation result consumer"
     );
 
-    if let Err(e) = mq
-        .process_messages(
-            &queue_name,
-            None,
-            None,
-            move |message: mq::BrokerMessage<TaskResult>| {
-                let waiters = waiters.clone();
-                async move {
-                    let result = message.payload;
-                    let task_id = result.task_id.clone();
-
-                    if let Some((_, tx)) = waiters.remove(&task_id) {
-                        if tx.send(result).is_err() {
-                            error!(%task_id, "Failed to send operation result to waiter (receiver dropped)");
-                        } else {
-                            debug!(%task_id, "Operation result delivered to plugin");
-                        }
-                    } else {
-                        warn!(%task_id, "Operation result received but no waiter found (batch may have been cancelled)");
-                    }
-
-                    Ok(())
-                }
-            },
-        )
-        .await
-    {
-        error!(error = %e, "Operation result consumer exited with error");
+    let delivered = Arc::new(DeliveredResults::new(dedup_window));
+
+    mq::consume_with_reconnect(
+        &mq,
+        &queue_name,
+        &config,
+        move |message: mq::BrokerMessage<TaskResult>| {
+            let waiters = waiters.clone();
+            let delivered = delivered.clone();
+            async move {
+                deliver_result(&waiters, &delivered, message.payload);
+                Ok(())
+            }
+        },
+    )
+    .await;
+    info!(queue = %queue_name, "Operation result consumer stopped");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dashmap::DashMap;
+    use tokio::sync::oneshot;
+
+    fn result(task_id: &str) -> TaskResult {
+        TaskResult {
+            task_id: task_id.to_string(),
+            success: true,
+            output: serde_json::json!({}),
+            error: None,
+            worker_id: None,
+        }
+    }
+
+    #[test]
+    fn same_result_delivered_twice_is_applied_once() {
+        let waiters: OperationWaiters = Arc::new(DashMap::new());
+        let delivered = DeliveredResults::new(16);
+        let (tx, mut rx) = oneshot::channel();
+        waiters.insert("task-1".to_string(), tx);
+
+        assert_eq!(
+            deliver_result(&waiters, &delivered, result("task-1")),
+            Delivery::Delivered
+        );
+        assert_eq!(rx.try_recv().unwrap().task_id, "task-1");
+
+        assert_eq!(
+            deliver_result(&waiters, &delivered, result("task-1")),
+            Delivery::Duplicate
+        );
+    }
+
+    #[test]
+    fn result_without_waiter_is_orphaned() {
+        let waiters: OperationWaiters = Arc::new(DashMap::new());
+        let delivered = DeliveredResults::new(16);
+
+        assert_eq!(
+            deliver_result(&waiters, &delivered, result("task-1")),
+            Delivery::Orphaned
+        );
+    }
+
+    #[test]
+    fn window_evicts_oldest_ids() {
+        let delivered = DeliveredResults::new(2);
+        delivered.record("a");
+        delivered.record("b");
+        delivered.record("c");
+
+        assert!(!delivered.contains("a"));
+        assert!(delivered.contains("b"));
+        assert!(delivered.contains("c"));
+    }
+
+    #[test]
+    fn zero_window_remembers_nothing() {
+        let delivered = DeliveredResults::new(0);
+        delivered.record("a");
+
+        assert!(!delivered.contains("a"));
     }
 }
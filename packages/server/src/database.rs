@@ -49,6 +49,9 @@ pub async fn init_db_with_max_connections(
         r#"ALTER TABLE IF EXISTS "test_case" ADD COLUMN IF NOT EXISTS "expected_output_size" BIGINT"#,
         r#"ALTER TABLE IF EXISTS "test_case" ADD COLUMN IF NOT EXISTS "input_preview" TEXT"#,
         r#"ALTER TABLE IF EXISTS "test_case" ADD COLUMN IF NOT EXISTS "expected_output_preview" TEXT"#,
+        r#"ALTER TABLE IF EXISTS "test_case" ADD COLUMN IF NOT EXISTS "is_pretest" BOOLEAN NOT NULL DEFAULT FALSE"#,
+        r#"ALTER TABLE IF EXISTS "problem" ADD COLUMN IF NOT EXISTS "difficulty" INTEGER"#,
+        r#"ALTER TABLE IF EXISTS "problem" ADD COLUMN IF NOT EXISTS "difficulty_is_manual" BOOLEAN NOT NULL DEFAULT FALSE"#,
     ] {
         db.execute_unprepared(stmt).await?;
     }
@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Heavy payload moved out of a terminal submission by the retention task.
+///
+/// The `submission` row itself stays in place as a summary (status,
+/// verdict, score, timings) so standings and history keep working; its
+/// `files` column is emptied and its `test_case_result` rows are deleted.
+/// The original source files and per-test results are kept here as JSON
+/// for audit or export.
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "archived_submission")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub submission_id: i32,
+
+    #[sea_orm(column_type = "JsonBinary")]
+    pub files: serde_json::Value,
+    /// Serialized `test_case_result` rows as they were at archive time.
+    #[sea_orm(column_type = "JsonBinary")]
+    pub test_case_results: serde_json::Value,
+
+    #[sea_orm(belongs_to, from = "submission_id", to = "id")]
+    pub submission: HasOne<super::submission::Entity>,
+
+    #[sea_orm(indexed)]
+    pub archived_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
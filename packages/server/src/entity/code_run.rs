@@ -38,6 +38,10 @@ pub struct Model {
     #[sea_orm(column_type = "JsonBinary")]
     pub custom_test_cases: serde_json::Value,
 
+    /// Compiled only, without running the custom test cases.
+    #[sea_orm(default_value = false)]
+    pub compile_only: bool,
+
     #[sea_orm(belongs_to, from = "user_id", to = "id")]
     pub user: HasOne<super::user::Entity>,
     #[sea_orm(belongs_to, from = "problem_id", to = "id")]
@@ -31,8 +31,51 @@ pub struct Model {
     #[sea_orm(default_value = true)]
     pub show_participants_list: bool,
 
+    /// Hide the problem list, statements and samples from non-managers until
+    /// `start_time`.
+    #[sea_orm(default_value = true)]
+    pub hide_problems_until_start: bool,
+
+    /// Award decreasing points per problem by solve order in server-computed
+    /// standings instead of each participant's best score.
+    #[sea_orm(default_value = false)]
+    pub dynamic_scoring: bool,
+
+    /// Judge only pretests while the contest runs; the full test set runs in
+    /// a system-test pass after it ends.
+    #[sea_orm(default_value = false)]
+    pub two_phase: bool,
+
+    /// After `end_time`, let any authenticated user submit to the contest's
+    /// problems. Such submissions are recorded as practice, without a
+    /// contest, so they never reach the standings.
+    #[sea_orm(default_value = false)]
+    pub practice_after_end: bool,
+
+    /// Show non-managers numbered labels instead of usernames in standings
+    /// until `end_time`.
+    #[sea_orm(default_value = false)]
+    pub anonymize_standings_until_end: bool,
+
+    /// Non-managers only see the first this many standings rows. `None`
+    /// shows everyone.
+    pub public_standings_limit: Option<i32>,
+
+    /// Set by freezeContest: standings shown to non-managers count only
+    /// submissions made before this instant, until unfreezeContest clears it.
+    pub frozen_at: Option<DateTimeUtc>,
+
+    /// Which of a participant's judged submissions to a problem sets its
+    /// score in static-scoring standings.
+    #[sea_orm(default_value = "Best")]
+    pub score_selection: ScoreSelection,
+
     pub contest_type: Option<String>,
 
+    /// Language ids accepted for submissions. `None` accepts every language
+    /// in the registry.
+    pub allowed_languages: Option<serde_json::Value>,
+
     #[sea_orm(has_many, via = "contest_user")]
     pub users: HasMany<super::user::Entity>,
 
@@ -49,9 +92,77 @@ pub struct Model {
 
 impl ActiveModelBehavior for ActiveModel {}
 
+/// Which submission's score counts for a problem in contest standings.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, DeriveValueType,
+)]
+#[sea_orm(value_type = "String")]
+#[derive(utoipa::ToSchema)]
+pub enum ScoreSelection {
+    /// The highest score among the participant's submissions.
+    #[default]
+    Best,
+    /// The score of the participant's most recent submission.
+    Last,
+}
+
+impl ScoreSelection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Best => "Best",
+            Self::Last => "Last",
+        }
+    }
+}
+
+impl std::fmt::Display for ScoreSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseScoreSelectionError {
+    invalid: String,
+}
+
+impl std::fmt::Display for ParseScoreSelectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid score selection '{}'. Valid values: Best, Last",
+            self.invalid
+        )
+    }
+}
+
+impl std::error::Error for ParseScoreSelectionError {}
+
+impl std::str::FromStr for ScoreSelection {
+    type Err = ParseScoreSelectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Best" => Ok(Self::Best),
+            "Last" => Ok(Self::Last),
+            _ => Err(ParseScoreSelectionError {
+                invalid: s.to_string(),
+            }),
+        }
+    }
+}
+
 impl SoftDeletable for Entity {
     type DeletedAtColumn = Column;
     fn deleted_at() -> Self::DeletedAtColumn {
         Column::DeletedAt
     }
 }
+
+impl Model {
+    pub fn get_allowed_languages(&self) -> Option<Vec<String>> {
+        self.allowed_languages
+            .as_ref()
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
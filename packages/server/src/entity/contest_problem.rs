@@ -18,6 +18,17 @@ pub struct Model {
 
     #[sea_orm(default_value = 0)]
     pub position: i32,
+
+    /// Points the problem is worth in standings. When set, a participant's
+    /// raw score is scaled from the problem's test case total to this value;
+    /// NULL keeps raw scores.
+    pub max_points: Option<i32>,
+
+    /// Seconds after a submission is made before its verdict and score are
+    /// shown to viewers without `submission:view_all`. 0 shows them as soon
+    /// as judging finishes.
+    #[sea_orm(default_value = 0)]
+    pub result_delay_secs: i32,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
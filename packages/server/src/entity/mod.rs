@@ -1,4 +1,5 @@
 pub mod additional_file;
+pub mod archived_submission;
 pub mod clarification;
 pub mod clarification_reply;
 pub mod code_run;
@@ -13,12 +14,17 @@ pub mod plugin_config;
 pub mod plugin_storage;
 pub mod problem;
 pub mod problem_attachment;
+pub mod problem_version;
 pub mod refresh_token;
+pub mod rejudge_batch;
 pub mod role;
 pub mod role_permission;
+pub mod saved_view;
 pub mod submission;
+pub mod submission_comment;
 pub mod submission_judgement;
 pub mod test_case;
 pub mod test_case_result;
 pub mod user;
 pub mod user_role;
+pub mod worker;
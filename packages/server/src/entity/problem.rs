@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use crate::utils::soft_delete::SoftDeletable;
 
 #[sea_orm::model]
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "problem")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -17,6 +17,15 @@ pub struct Model {
     pub content: String,
     pub time_limit: i32,
     pub memory_limit: i32,
+    /// Wall-clock limit as a multiple of `time_limit`, for I/O-heavy
+    /// problems. Unset uses the evaluator's configured multiplier.
+    #[sea_orm(column_type = "Double", nullable)]
+    pub wall_time_multiplier: Option<f64>,
+
+    /// Judge a non-zero exit code as a runtime error even when the output is
+    /// correct. When false, such runs are left to the checker.
+    #[sea_orm(default_value = true)]
+    pub require_zero_exit: bool,
 
     #[sea_orm(default_value = "batch")]
     pub problem_type: String,
@@ -30,12 +39,24 @@ pub struct Model {
     #[sea_orm(default_value = "ioi")]
     pub default_contest_type: String,
 
-    #[sea_orm(default_value = false)]
-    pub show_test_details: bool,
+    #[sea_orm(default_value = "SampleOnly")]
+    pub test_details_visibility: TestDetailsVisibility,
 
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub submission_format: Option<serde_json::Value>,
 
+    /// 0-100 difficulty score, either set by an editor or estimated from
+    /// submission stats.
+    pub difficulty: Option<i32>,
+    /// True when `difficulty` was set by an editor. Estimation leaves such
+    /// values alone unless explicitly told to overwrite them.
+    #[sea_orm(default_value = false)]
+    pub difficulty_is_manual: bool,
+    /// Listed in the practice archive, where any signed-in user can read
+    /// and submit to it.
+    #[sea_orm(default_value = false)]
+    pub is_public: bool,
+
     #[sea_orm(has_many)]
     pub submissions: HasMany<super::submission::Entity>,
 
@@ -52,6 +73,91 @@ pub struct Model {
 
 impl ActiveModelBehavior for ActiveModel {}
 
+/// Test case I/O shown to users without `submission:view_all` on
+/// submissions for a problem.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, DeriveValueType,
+)]
+#[sea_orm(value_type = "String")]
+#[derive(utoipa::ToSchema)]
+pub enum TestDetailsVisibility {
+    /// No test case I/O, not even for samples.
+    None,
+    /// I/O of sample test cases only.
+    #[default]
+    SampleOnly,
+    /// I/O of every test case.
+    Full,
+}
+
+impl TestDetailsVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::SampleOnly => "SampleOnly",
+            Self::Full => "Full",
+        }
+    }
+
+    /// Whether I/O of a test case, sample or not, is shown.
+    pub fn reveals(&self, is_sample: bool) -> bool {
+        match self {
+            Self::None => false,
+            Self::SampleOnly => is_sample,
+            Self::Full => true,
+        }
+    }
+}
+
+impl std::fmt::Display for TestDetailsVisibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTestDetailsVisibilityError {
+    invalid: String,
+}
+
+impl std::fmt::Display for ParseTestDetailsVisibilityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid test details visibility '{}'. Valid values: None, SampleOnly, Full",
+            self.invalid
+        )
+    }
+}
+
+impl std::error::Error for ParseTestDetailsVisibilityError {}
+
+impl std::str::FromStr for TestDetailsVisibility {
+    type Err = ParseTestDetailsVisibilityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "None" => Ok(Self::None),
+            "SampleOnly" => Ok(Self::SampleOnly),
+            "Full" => Ok(Self::Full),
+            _ => Err(ParseTestDetailsVisibilityError {
+                invalid: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl From<bool> for TestDetailsVisibility {
+    /// Meaning of the former boolean `show_test_details`.
+    fn from(show_test_details: bool) -> Self {
+        if show_test_details {
+            Self::Full
+        } else {
+            Self::SampleOnly
+        }
+    }
+}
+
 impl SoftDeletable for Entity {
     type DeletedAtColumn = Column;
     fn deleted_at() -> Self::DeletedAtColumn {
@@ -0,0 +1,30 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Statement and limits of a problem as they were before an edit replaced
+/// them. `version` counts up from 1 per problem.
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "problem_version")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub problem_id: i32,
+    pub version: i32,
+
+    pub title: String,
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    pub time_limit: i32,
+    pub memory_limit: i32,
+
+    /// User whose edit replaced this snapshot.
+    pub created_by: i32,
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(belongs_to, from = "problem_id", to = "id")]
+    pub problem: HasOne<super::problem::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
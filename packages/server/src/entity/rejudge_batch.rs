@@ -0,0 +1,30 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Progress of one bulk rejudge.
+///
+/// `total` is the number of judgements the rejudge opened; `remaining`
+/// counts those whose dispatch has not finished yet and is decremented
+/// once per judgement as it reaches a terminal status.
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "rejudge_batch")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub triggered_by_user_id: i32,
+    pub total: i32,
+    pub remaining: i32,
+
+    pub created_at: DateTimeUtc,
+    /// Set when `remaining` reaches zero.
+    pub completed_at: Option<DateTimeUtc>,
+
+    #[sea_orm(belongs_to, from = "triggered_by_user_id", to = "id")]
+    pub triggered_by: HasOne<super::user::Entity>,
+    #[sea_orm(has_many)]
+    pub judgements: HasMany<super::submission_judgement::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
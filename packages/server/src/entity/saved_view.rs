@@ -0,0 +1,25 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Named set of list query parameters a user saved for one list endpoint.
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "saved_view")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub user_id: i32,
+    /// List endpoint the view applies to, e.g. `submissions`.
+    pub resource: String,
+    pub name: String,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub params: serde_json::Value,
+
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(belongs_to, from = "user_id", to = "id")]
+    pub user: HasOne<super::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -34,6 +34,10 @@ pub struct Model {
     pub score: Option<f64>,
     pub time_used: Option<i32>,
     pub memory_used: Option<i32>,
+    /// Per-subtask breakdown of `score` reported by the contest plugin, as
+    /// a JSON array of `{name, score, max_score, verdict}`.
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub subtask_results: Option<serde_json::Value>,
 
     #[sea_orm(belongs_to, from = "user_id", to = "id")]
     pub user: HasOne<super::user::Entity>,
@@ -46,11 +50,23 @@ pub struct Model {
 
     #[sea_orm(default_value = 0)]
     pub judge_epoch: i32,
+    /// Judgement whose terminal result was last written to this row. A
+    /// redelivered result for the same judgement is dropped instead of
+    /// being applied twice.
+    #[sea_orm(nullable)]
+    pub applied_judgement_id: Option<i32>,
 
     /// When set by an admin, every operation produced for this submission is
     /// pinned to the named worker via the worker's private queue.
     #[sea_orm(nullable)]
     pub target_worker_id: Option<String>,
+    /// Worker that ran the test cases of the current judgement.
+    pub judged_by: Option<String>,
+
+    /// Compiled only, without evaluating any test case. Never carries a
+    /// verdict.
+    #[sea_orm(default_value = false)]
+    pub compile_only: bool,
 
     pub created_at: DateTimeUtc,
     pub judged_at: Option<DateTimeUtc>,
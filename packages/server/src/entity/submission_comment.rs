@@ -0,0 +1,29 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Note left by a grader on a submission. Only `is_public` comments are
+/// shown to the submission's owner.
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "submission_comment")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    #[sea_orm(indexed)]
+    pub submission_id: i32,
+    pub author_id: i32,
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    #[sea_orm(default_value = false)]
+    pub is_public: bool,
+
+    pub created_at: DateTimeUtc,
+
+    #[sea_orm(belongs_to, from = "submission_id", to = "id")]
+    pub submission: HasOne<super::submission::Entity>,
+    #[sea_orm(belongs_to, from = "author_id", to = "id")]
+    pub author: HasOne<super::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
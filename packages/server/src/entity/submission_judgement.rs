@@ -44,24 +44,42 @@ pub struct Model {
     pub score: Option<f64>,
     pub time_used: Option<i32>,
     pub memory_used: Option<i32>,
+    /// Mirrors `submission.subtask_results`.
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub subtask_results: Option<serde_json::Value>,
     #[sea_orm(column_type = "Text", nullable)]
     pub compile_output: Option<String>,
     pub error_code: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub error_message: Option<String>,
+    /// Worker that ran the test cases, when one did.
+    pub judged_by: Option<String>,
 
     /// Bumped each time the judgement is sent to the worker so stale
     /// workers can be rejected. Mirrors `submission.judge_epoch`.
     #[sea_orm(default_value = 0)]
     pub judge_epoch: i32,
 
+    /// Bulk rejudge this judgement was opened by, if any.
+    #[sea_orm(indexed)]
+    pub rejudge_batch_id: Option<i32>,
+    /// Set once this judgement has been counted off its rejudge batch, so
+    /// a re-dispatch cannot decrement `remaining` twice.
+    #[sea_orm(default_value = false)]
+    pub rejudge_batch_settled: bool,
+
     pub created_at: DateTimeUtc,
     pub finalized_at: Option<DateTimeUtc>,
+    /// Milliseconds from `created_at` (when the judgement was queued) to
+    /// `finalized_at`.
+    pub judging_duration_ms: Option<i64>,
 
     #[sea_orm(belongs_to, from = "submission_id", to = "id")]
     pub submission: HasOne<super::submission::Entity>,
     #[sea_orm(belongs_to, from = "triggered_by_user_id", to = "id")]
     pub triggered_by: Option<super::user::Entity>,
+    #[sea_orm(belongs_to, from = "rejudge_batch_id", to = "id")]
+    pub rejudge_batch: Option<super::rejudge_batch::Entity>,
     #[sea_orm(has_many)]
     pub test_case_results: HasMany<super::test_case_result::Entity>,
 }
@@ -22,6 +22,10 @@ pub struct Model {
     pub input_preview: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub expected_output_preview: Option<String>,
+    /// Further outputs the default checker accepts besides
+    /// `expected_output`, as a JSON array of strings.
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub accepted_outputs: Option<serde_json::Value>,
     pub score: i32,
 
     #[sea_orm(column_type = "Text", nullable)]
@@ -33,6 +37,11 @@ pub struct Model {
     #[sea_orm(default_value = false)]
     pub is_sample: bool,
 
+    /// Judged during the contest in two-phase contests; the rest only run in
+    /// the post-contest system test.
+    #[sea_orm(default_value = false)]
+    pub is_pretest: bool,
+
     #[sea_orm(default_value = 0)]
     pub position: i32,
 
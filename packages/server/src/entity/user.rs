@@ -22,6 +22,16 @@ pub struct Model {
     #[sea_orm(has_many, via = "contest_user")]
     pub contests: HasMany<super::contest::Entity>,
 
+    /// Deactivated users cannot log in or use existing tokens, but keep
+    /// their data and contest history.
+    #[sea_orm(default_value = true)]
+    pub is_active: bool,
+
+    /// Bumped whenever the password changes. Access tokens signed with an
+    /// older version are rejected.
+    #[sea_orm(default_value = 0)]
+    pub token_version: i32,
+
     pub created_at: DateTimeUtc,
     pub deleted_at: Option<DateTimeUtc>,
 }
@@ -0,0 +1,45 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Durable record of every worker that has ever published a heartbeat.
+///
+/// Redis only holds heartbeats for ~15 seconds, so a crashed worker simply
+/// disappears from it. This table keeps the last observed heartbeat so the
+/// admin UI can keep showing the worker as stale instead of forgetting it.
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "worker")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    pub started_at: DateTimeUtc,
+
+    #[sea_orm(indexed)]
+    pub last_seen: DateTimeUtc,
+
+    pub in_flight: i32,
+
+    pub max_concurrency: Option<i32>,
+
+    pub sandbox_backend: String,
+
+    pub version: String,
+
+    pub hostname: Option<String>,
+
+    #[sea_orm(column_type = "JsonBinary")]
+    pub ip_addresses: serde_json::Value,
+
+    pub os: Option<String>,
+
+    pub arch: Option<String>,
+
+    pub cpu_count: Option<i32>,
+
+    pub pid: Option<i32>,
+
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -22,19 +22,34 @@ pub struct ErrorBody {
 #[derive(Debug)]
 pub enum AppError {
     Validation(String),
+    /// Validation error tied to one request field, reported in `details.field`.
+    FieldValidation {
+        field: String,
+        message: String,
+    },
     PayloadTooLarge(String),
     TokenMissing,
     TokenInvalid,
     InvalidCredentials,
     PermissionDenied,
+    AccountDeactivated,
     NotFound(String),
     MethodNotAllowed,
     Conflict(String),
     UsernameTaken,
+    /// Identical to a recent submission by the same user; carries its id.
+    DuplicateSubmission {
+        submission_id: i32,
+    },
     PluginNotReady(String),
+    PluginTimeout(String),
     RateLimited {
         retry_after: u64,
     },
+    /// The user already has `limit` submissions waiting to be judged.
+    TooManyInFlight {
+        limit: u32,
+    },
     PluginRejection {
         code: String,
         message: String,
@@ -58,6 +73,14 @@ impl AppError {
 
         match self {
             AppError::Validation(msg) => (StatusCode::BAD_REQUEST, simple("VALIDATION_ERROR", msg)),
+            AppError::FieldValidation { field, message } => (
+                StatusCode::BAD_REQUEST,
+                ErrorBody {
+                    code: "VALIDATION_ERROR".into(),
+                    message,
+                    details: Some(serde_json::json!({ "field": field })),
+                },
+            ),
             AppError::PayloadTooLarge(msg) => (
                 StatusCode::PAYLOAD_TOO_LARGE,
                 simple("PAYLOAD_TOO_LARGE", msg),
@@ -78,6 +101,13 @@ impl AppError {
                 StatusCode::FORBIDDEN,
                 simple("PERMISSION_DENIED", "Insufficient permissions".into()),
             ),
+            AppError::AccountDeactivated => (
+                StatusCode::FORBIDDEN,
+                simple(
+                    "ACCOUNT_DEACTIVATED",
+                    "This account has been deactivated".into(),
+                ),
+            ),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, simple("NOT_FOUND", msg)),
             AppError::MethodNotAllowed => (
                 StatusCode::METHOD_NOT_ALLOWED,
@@ -91,9 +121,22 @@ impl AppError {
                 StatusCode::CONFLICT,
                 simple("USERNAME_TAKEN", "Username is already taken".into()),
             ),
+            AppError::DuplicateSubmission { submission_id } => (
+                StatusCode::CONFLICT,
+                ErrorBody {
+                    code: "DUPLICATE_SUBMISSION".into(),
+                    message: format!(
+                        "Identical to recent submission {submission_id}; change the code before resubmitting"
+                    ),
+                    details: Some(serde_json::json!({ "submission_id": submission_id })),
+                },
+            ),
             AppError::PluginNotReady(msg) => {
                 (StatusCode::BAD_REQUEST, simple("PLUGIN_NOT_READY", msg))
             }
+            AppError::PluginTimeout(msg) => {
+                (StatusCode::GATEWAY_TIMEOUT, simple("PLUGIN_TIMEOUT", msg))
+            }
             AppError::RateLimited { retry_after } => (
                 StatusCode::TOO_MANY_REQUESTS,
                 simple(
@@ -101,6 +144,16 @@ impl AppError {
                     format!("Rate limit exceeded. Try again in {} seconds", retry_after),
                 ),
             ),
+            AppError::TooManyInFlight { limit } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                ErrorBody {
+                    code: "TOO_MANY_IN_FLIGHT".into(),
+                    message: format!(
+                        "You already have {limit} submissions waiting to be judged; try again once one finishes"
+                    ),
+                    details: Some(serde_json::json!({ "limit": limit })),
+                },
+            ),
             AppError::PluginRejection {
                 code,
                 message,
@@ -202,6 +255,10 @@ impl From<PluginError> for AppError {
                 tracing::warn!("Plugin not ready: {err}");
                 AppError::PluginNotReady(err.to_string())
             }
+            PluginError::Timeout { .. } => {
+                tracing::warn!("{err}");
+                AppError::PluginTimeout(err.to_string())
+            }
             PluginError::Serialization(_) => AppError::Validation(err.to_string()),
             _ => AppError::Internal(err.to_string()),
         }
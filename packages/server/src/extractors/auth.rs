@@ -1,6 +1,8 @@
 use axum::extract::{FromRef, FromRequestParts, OptionalFromRequestParts};
 use axum::http::request::Parts;
+use sea_orm::{DatabaseConnection, EntityTrait, QuerySelect};
 
+use crate::entity::user;
 use crate::error::AppError;
 use crate::state::AppState;
 use crate::utils::jwt;
@@ -10,6 +12,8 @@ pub struct AuthUser {
     pub username: String,
     pub roles: Vec<String>,
     pub permissions: Vec<String>,
+    /// `user.token_version` the request's token was signed with.
+    pub token_version: i32,
 }
 
 impl AuthUser {
@@ -34,6 +38,27 @@ impl AuthUser {
     }
 }
 
+/// Rejects tokens of deactivated users, and tokens signed before the
+/// user's password last changed. Access tokens are stateless, so this is
+/// checked on every request rather than when the token is issued.
+pub async fn ensure_active_user(
+    db: &DatabaseConnection,
+    claims: &jwt::Claims,
+) -> Result<(), AppError> {
+    let row: Option<(bool, i32)> = user::Entity::find_by_id(claims.uid)
+        .select_only()
+        .column(user::Column::IsActive)
+        .column(user::Column::TokenVersion)
+        .into_tuple()
+        .one(db)
+        .await?;
+    match row {
+        Some((false, _)) => Err(AppError::AccountDeactivated),
+        Some((true, version)) if version != claims.ver => Err(AppError::TokenInvalid),
+        _ => Ok(()),
+    }
+}
+
 impl<S> FromRequestParts<S> for AuthUser
 where
     S: Send + Sync,
@@ -55,12 +80,14 @@ where
         let app_state = AppState::from_ref(state);
         let secret = app_state.config.auth.jwt_secret;
         let claims = jwt::verify(token, &secret).map_err(|_| AppError::TokenInvalid)?;
+        ensure_active_user(&app_state.db, &claims).await?;
 
         Ok(AuthUser {
             user_id: claims.uid,
             username: claims.sub,
             roles: claims.roles,
             permissions: claims.permissions,
+            token_version: claims.ver,
         })
     }
 }
@@ -254,7 +254,9 @@ pub async fn list_additional_files(
     ),
     responses(
         (status = 200, description = "File content"),
+        (status = 206, description = "Requested byte range (Range header)"),
         (status = 304, description = "Not Modified (ETag match)"),
+        (status = 416, description = "Range not satisfiable"),
         (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
         (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
         (status = 404, description = "Not found (NOT_FOUND)", body = ErrorBody),
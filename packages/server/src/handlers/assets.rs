@@ -1,12 +1,14 @@
 use axum::body::Body;
 use axum::extract::State;
 use axum::http::{HeaderMap, StatusCode, header};
-use axum::response::{IntoResponse, Response};
+use axum::response::Response;
+use sha2::{Digest, Sha256};
 use tracing::instrument;
 
 use crate::error::AppError;
 use crate::extractors::path::AppPath;
 use crate::state::AppState;
+use crate::utils::range::{ByteRange, partial_content, range_not_satisfiable};
 
 #[instrument(skip(state, headers))]
 pub async fn serve_plugin_asset(
@@ -24,27 +26,63 @@ pub async fn serve_plugin_asset(
         .map_err(|e| AppError::Internal(format!("IO error: {}", e)))?;
 
     let mime = mime_guess::from_path(&safe_path).first_or_octet_stream();
-
-    let mtime = safe_path
-        .metadata()
-        .and_then(|m| m.modified())
-        .ok()
-        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-        .map(|d| d.as_millis())
-        .unwrap_or(0);
-    let etag = format!("W/\"{:x}-{:x}\"", content.len(), mtime);
+    let etag = format!("\"{}\"", hex::encode(Sha256::digest(&content)));
+    let cache_control = match state.config.server.plugin_asset_max_age_secs {
+        0 => "no-cache".to_string(),
+        secs => format!("public, max-age={secs}"),
+    };
 
     if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH)
         && let Ok(val) = if_none_match.to_str()
-        && (val == etag || val == "*")
+        && etag_matches(val, &etag)
     {
-        return Ok(StatusCode::NOT_MODIFIED.into_response());
+        return Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::CACHE_CONTROL, cache_control)
+            .header(header::ETAG, etag)
+            .body(Body::empty())
+            .map_err(|e| AppError::Internal(e.to_string()));
     }
 
-    Response::builder()
+    let size = content.len() as u64;
+    let (builder, body) = match ByteRange::from_headers(&headers, size) {
+        ByteRange::Full => (
+            Response::builder().header(header::ACCEPT_RANGES, "bytes"),
+            content,
+        ),
+        ByteRange::Partial { start, end } => (
+            partial_content(start, end, size),
+            content[start as usize..=end as usize].to_vec(),
+        ),
+        ByteRange::Unsatisfiable => return range_not_satisfiable(size),
+    };
+
+    builder
         .header(header::CONTENT_TYPE, mime.as_ref())
-        .header(header::CACHE_CONTROL, "no-cache")
+        .header(header::CACHE_CONTROL, cache_control)
         .header(header::ETAG, etag)
-        .body(Body::from(content))
+        .body(Body::from(body))
         .map_err(|e| AppError::Internal(e.to_string()))
 }
+
+/// Whether an `If-None-Match` value names `etag`, using the weak comparison
+/// RFC 9110 prescribes for it.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn etag_matches_lists_and_weak_validators() {
+        let etag = "\"abc\"";
+        assert!(etag_matches("\"abc\"", etag));
+        assert!(etag_matches("\"x\", W/\"abc\"", etag));
+        assert!(etag_matches("*", etag));
+        assert!(!etag_matches("\"abcd\"", etag));
+    }
+}
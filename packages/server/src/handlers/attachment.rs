@@ -194,7 +194,8 @@ pub async fn list_attachments(
     tag = "Problem Attachments",
     operation_id = "downloadAttachment",
     summary = "Download an attachment",
-    description = "Streams the attachment content. Supports ETag-based caching via If-None-Match. \
+    description = "Streams the attachment content. Supports ETag-based caching via If-None-Match \
+        and single byte ranges via Range. \
         Admin/setter access via permission; contestants access if the problem is in a contest \
         they can see (public or enrolled).",
     params(
@@ -203,7 +204,9 @@ pub async fn list_attachments(
     ),
     responses(
         (status = 200, description = "Attachment content"),
+        (status = 206, description = "Requested byte range (Range header)"),
         (status = 304, description = "Not Modified (ETag match)"),
+        (status = 416, description = "Range not satisfiable"),
         (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
         (status = 404, description = "Attachment not found or not accessible (NOT_FOUND)", body = ErrorBody),
     ),
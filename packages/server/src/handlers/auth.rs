@@ -12,7 +12,8 @@ use crate::extractors::json::AppJson;
 use crate::models::auth::{
     CliRefreshRequest, CliTokenResponse, DeviceAuthorizeRequest, DeviceCodeRequest,
     DeviceCodeResponse, DeviceTokenRequest, LoginRequest, LoginResponse, MeResponse,
-    RegisterRequest, RegisterResponse, validate_login_request, validate_register_request,
+    PermissionsResponse, RegisterRequest, RegisterResponse, validate_login_request,
+    validate_register_request,
 };
 use crate::state::AppState;
 use crate::utils::soft_delete::SoftDeletable;
@@ -82,12 +83,13 @@ pub async fn register(
     tag = "Auth",
     operation_id = "loginUser",
     summary = "Log in and obtain a JWT token",
-    description = "Authenticates the user and returns a short-lived JWT access token. Sets a long-lived HttpOnly cookie containing a refresh token. Returns 401 INVALID_CREDENTIALS on wrong username or password.",
+    description = "Authenticates the user and returns a short-lived JWT access token. Sets a long-lived HttpOnly cookie containing a refresh token. Returns 401 INVALID_CREDENTIALS on wrong username or password, and 403 ACCOUNT_DEACTIVATED if the account has been deactivated.",
     request_body = LoginRequest,
     responses(
         (status = 200, description = "Login successful", body = LoginResponse),
         (status = 400, description = "Validation error (VALIDATION_ERROR)", body = ErrorBody),
         (status = 401, description = "Invalid credentials (INVALID_CREDENTIALS)", body = ErrorBody),
+        (status = 403, description = "Account deactivated (ACCOUNT_DEACTIVATED)", body = ErrorBody),
         (status = 429, description = "Rate limited (RATE_LIMITED). Includes Retry-After header when auth rate limiting is enabled.", body = ErrorBody),
     ),
 )]
@@ -116,6 +118,9 @@ pub async fn login(
         Some(u) if is_valid => u,
         _ => return Err(AppError::InvalidCredentials),
     };
+    if !user.is_active {
+        return Err(AppError::AccountDeactivated);
+    }
 
     let role_models = user.find_related(role::Entity).all(&state.db).await?;
     let roles: Vec<String> = role_models.iter().map(|r| r.name.clone()).collect();
@@ -131,6 +136,7 @@ pub async fn login(
     let access_token = jwt::sign_access_token(
         user.id,
         &user.username,
+        user.token_version,
         roles.clone(),
         permissions.clone(),
         &state.config.auth.jwt_secret,
@@ -224,7 +230,7 @@ pub async fn refresh(
     let maybe_user = user::Entity::find_by_id(user_id).one(&txn).await?;
 
     let user = match maybe_user {
-        Some(u) if u.deleted_at.is_none() => u,
+        Some(u) if u.deleted_at.is_none() && u.is_active => u,
         _ => {
             rt_model.delete(&txn).await?;
             txn.commit().await?;
@@ -246,6 +252,7 @@ pub async fn refresh(
     let new_access_token = jwt::sign_access_token(
         user.id,
         &user.username,
+        user.token_version,
         roles.clone(),
         permissions.clone(),
         &state.config.auth.jwt_secret,
@@ -355,6 +362,45 @@ pub async fn me(auth_user: AuthUser) -> Json<MeResponse> {
     })
 }
 
+#[utoipa::path(
+    get,
+    path = "/permissions",
+    tag = "Auth",
+    operation_id = "getCurrentUserPermissions",
+    summary = "Get the current user's effective permissions",
+    description = "Resolves the authenticated user's permissions from their current roles. Unlike `/me`, which echoes the JWT, this reflects role changes made since the token was issued.",
+    responses(
+        (status = 200, description = "Effective permissions", body = PermissionsResponse),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(user_id = auth_user.user_id))]
+pub async fn permissions(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<PermissionsResponse>, AppError> {
+    let roles: Vec<String> = user_role::Entity::find()
+        .filter(user_role::Column::UserId.eq(auth_user.user_id))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|ur| ur.role)
+        .collect();
+
+    let mut permissions: Vec<String> = role_permission::Entity::find()
+        .filter(role_permission::Column::Role.is_in(roles))
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|rp| rp.permission)
+        .collect();
+    permissions.sort();
+    permissions.dedup();
+
+    Ok(Json(PermissionsResponse { permissions }))
+}
+
 const USER_CODE_CHARSET: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
 const USER_CODE_LEN: usize = 8;
 const DEVICE_CODE_EXPIRY_SECS: u64 = 900;
@@ -521,6 +567,7 @@ pub async fn authorize_device(
     let token = jwt::sign_access_token(
         auth_user.user_id,
         &auth_user.username,
+        auth_user.token_version,
         auth_user.roles,
         auth_user.permissions,
         &state.config.auth.jwt_secret,
@@ -643,6 +690,7 @@ pub async fn issue_cli_token(
     let token = jwt::sign_access_token(
         auth_user.user_id,
         &auth_user.username,
+        auth_user.token_version,
         auth_user.roles,
         auth_user.permissions,
         &state.config.auth.jwt_secret,
@@ -703,7 +751,7 @@ pub async fn cli_refresh(
     let user_id = rt_model.user_id;
     let maybe_user = user::Entity::find_by_id(user_id).one(&txn).await?;
     let user = match maybe_user {
-        Some(u) if u.deleted_at.is_none() => u,
+        Some(u) if u.deleted_at.is_none() && u.is_active => u,
         _ => {
             rt_model.delete(&txn).await?;
             txn.commit().await?;
@@ -724,6 +772,7 @@ pub async fn cli_refresh(
     let new_access_token = jwt::sign_access_token(
         user.id,
         &user.username,
+        user.token_version,
         roles,
         permissions,
         &state.config.auth.jwt_secret,
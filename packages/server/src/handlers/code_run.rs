@@ -25,7 +25,10 @@ use crate::state::AppState;
 use crate::utils::contest::{
     find_contest, is_problem_in_contest, require_contest_participant, require_contest_running,
 };
-use crate::utils::judging::{files_from_json, files_to_json, validate_run_language};
+use crate::utils::judging::{
+    files_from_json, files_to_json, judge_mode_of, validate_contest_language,
+    validate_language_problem_type, validate_run_language,
+};
 use crate::utils::problem::find_problem;
 use crate::utils::rate_limit::check_rate_limit;
 use crate::utils::text::sanitize_db_json;
@@ -103,8 +106,11 @@ pub(crate) async fn dispatch_to_plugin(state: AppState, code_run: code_run::Mode
         }
     };
 
-    let custom_tcs: Vec<CustomTestCaseInput> =
-        serde_json::from_value(code_run.custom_test_cases.clone()).unwrap_or_default();
+    let custom_tcs: Vec<CustomTestCaseInput> = if code_run.compile_only {
+        vec![]
+    } else {
+        serde_json::from_value(code_run.custom_test_cases.clone()).unwrap_or_default()
+    };
     let resolved_test_cases: Vec<TestCaseRow> = custom_tcs
         .iter()
         .enumerate()
@@ -136,6 +142,7 @@ pub(crate) async fn dispatch_to_plugin(state: AppState, code_run: code_run::Mode
         memory_limit_kb: problem.memory_limit,
         problem_type: problem.problem_type.clone(),
         test_cases: resolved_test_cases,
+        judge_mode: judge_mode_of(code_run.compile_only),
     };
 
     let input_bytes = match serde_json::to_vec(&input) {
@@ -327,6 +334,7 @@ async fn build_code_run_response(
         contest_id: cr.contest_id,
         contest_type: cr.contest_type,
         custom_test_cases: custom_tcs,
+        judge_mode: judge_mode_of(cr.compile_only),
         created_at: cr.created_at,
         result: result_response,
     })
@@ -338,7 +346,7 @@ async fn build_code_run_response(
     tag = "Code Runs",
     operation_id = "runCode",
     summary = "Run code against custom test cases",
-    description = "Runs code against custom test cases for a problem. Results are ephemeral and don't affect scoring.",
+    description = "Runs code against custom test cases for a problem. Results are ephemeral and don't affect scoring. With `judge_mode` `compile_only` the code is only compiled and the diagnostics are returned as compile output.",
     params(("id" = i32, Path, description = "Problem ID")),
     request_body = RunCodeRequest,
     responses(
@@ -359,7 +367,7 @@ pub async fn run_code(
     AppJson(payload): AppJson<RunCodeRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     auth_user.require_permission("submission:submit")?;
-    validate_run_code(&payload, state.config.submission.max_size)?;
+    validate_run_code(&payload, &state.config.submission)?;
     check_rate_limit(
         &state.db,
         auth_user.user_id,
@@ -379,6 +387,11 @@ pub async fn run_code(
         .cloned()
         .collect();
     validate_run_language(&payload.language, &known_languages)?;
+    validate_language_problem_type(
+        &payload.language,
+        &problem.problem_type,
+        &*state.registries.language_resolver_registry.read().await,
+    )?;
 
     let contest_type = problem.default_contest_type.clone();
     let custom_tcs_json = sanitize_db_json(
@@ -396,6 +409,7 @@ pub async fn run_code(
         contest_id: Set(None),
         contest_type: Set(contest_type),
         custom_test_cases: Set(custom_tcs_json),
+        compile_only: Set(payload.judge_mode.is_compile_only()),
         created_at: Set(now),
         ..Default::default()
     };
@@ -420,7 +434,7 @@ pub async fn run_code(
     tag = "Code Runs",
     operation_id = "runContestCode",
     summary = "Run code against test cases in a contest",
-    description = "Runs code against custom test cases for a contest problem. The user must be a contest participant and the contest must be running.",
+    description = "Runs code against custom test cases for a contest problem. The user must be a contest participant and the contest must be running. With `judge_mode` `compile_only` the code is only compiled and the diagnostics are returned as compile output.",
     params(
         ("id" = i32, Path, description = "Contest ID"),
         ("problem_id" = i32, Path, description = "Problem ID")
@@ -444,7 +458,7 @@ pub async fn run_contest_code(
     AppJson(payload): AppJson<RunCodeRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     auth_user.require_permission("submission:submit")?;
-    validate_run_code(&payload, state.config.submission.max_size)?;
+    validate_run_code(&payload, &state.config.submission)?;
     check_rate_limit(
         &state.db,
         auth_user.user_id,
@@ -456,7 +470,7 @@ pub async fn run_contest_code(
     let txn = state.db.begin().await?;
 
     let contest_model = find_contest(&txn, contest_id).await?;
-    let _problem = find_problem(&txn, problem_id).await?;
+    let problem = find_problem(&txn, problem_id).await?;
     if !is_problem_in_contest(&txn, contest_id, problem_id).await? {
         return Err(AppError::NotFound(
             "Problem not found in this contest".into(),
@@ -476,6 +490,15 @@ pub async fn run_contest_code(
         .cloned()
         .collect();
     validate_run_language(&payload.language, &known_languages)?;
+    validate_language_problem_type(
+        &payload.language,
+        &problem.problem_type,
+        &*state.registries.language_resolver_registry.read().await,
+    )?;
+    validate_contest_language(
+        &payload.language,
+        contest_model.get_allowed_languages().as_deref(),
+    )?;
 
     let custom_tcs_json = sanitize_db_json(
         serde_json::to_value(&payload.custom_test_cases).unwrap_or(serde_json::Value::Null),
@@ -498,6 +521,7 @@ pub async fn run_contest_code(
         contest_id: Set(Some(contest_id)),
         contest_type: Set(contest_type),
         custom_test_cases: Set(custom_tcs_json),
+        compile_only: Set(payload.judge_mode.is_compile_only()),
         created_at: Set(now),
         ..Default::default()
     };
@@ -553,6 +577,57 @@ pub async fn get_code_run(
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = "Code Runs",
+    operation_id = "runProblem",
+    summary = "Run code against custom input",
+    description = "Alias of runCode at `/problems/{id}/run`: takes the same body, applies the same rate limit and returns the same response, whose `id` can be polled at `/runs/{id}`. Runs are kept as code runs, not submissions, and are never scored.",
+    params(("id" = i32, Path, description = "Problem ID")),
+    request_body = RunCodeRequest,
+    responses(
+        (status = 201, description = "Code run created", body = CodeRunResponse),
+        (status = 400, description = "Validation error (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Problem not found (NOT_FOUND)", body = ErrorBody),
+        (status = 429, description = "Rate limited (RATE_LIMITED)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+pub async fn run_problem(
+    auth_user: AuthUser,
+    state: State<AppState>,
+    path: AppPath<i32>,
+    payload: AppJson<RunCodeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    run_code(auth_user, state, path, payload).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = "Code Runs",
+    operation_id = "getRun",
+    summary = "Get a run by ID",
+    description = "Alias of getCodeRun at `/runs/{id}`, for runs created through runProblem.",
+    params(("id" = i32, Path, description = "Code run ID")),
+    responses(
+        (status = 200, description = "Code run details", body = CodeRunResponse),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 404, description = "Code run not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+pub async fn get_run(
+    auth_user: AuthUser,
+    state: State<AppState>,
+    path: AppPath<i32>,
+) -> Result<Json<CodeRunResponse>, AppError> {
+    get_code_run(auth_user, state, path).await
+}
+
 pub fn code_run_body_limit(max_size: usize) -> axum::extract::DefaultBodyLimit {
     axum::extract::DefaultBodyLimit::max(max_size + 4096)
 }
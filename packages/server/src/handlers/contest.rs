@@ -1,6 +1,6 @@
 use axum::Json;
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::http::{StatusCode, header};
 use axum::response::IntoResponse;
 use sea_orm::prelude::Expr;
 use sea_orm::sea_query::{Func, LikeExpr, Query as SeaQuery};
@@ -9,19 +9,23 @@ use serde::Serialize;
 use tracing::instrument;
 
 use crate::entity::{
-    contest, contest_problem, contest_user, problem, role, test_case, user, user_role,
+    contest, contest_problem, contest_user, problem, role, submission, test_case, user, user_role,
 };
 use crate::error::{AppError, ErrorBody};
 use crate::extractors::auth::AuthUser;
 use crate::extractors::json::AppJson;
 use crate::extractors::path::AppPath;
 use crate::handlers::plugin_config::{delete_config_by_scope, delete_config_by_scope_like};
+use crate::handlers::submission::requeue_submissions;
 use crate::models::contest::*;
 use crate::models::plugin_config::config_key;
 use crate::models::shared::{Pagination, escape_like};
+use crate::models::submission::BulkRejudgeResponse;
 use crate::state::AppState;
+use crate::utils::blob::content_disposition_value;
 use crate::utils::contest::{
-    check_contest_access, find_contest, find_contest_problem, require_contest_started,
+    check_contest_access, find_contest, find_contest_problem, load_plugin_standings,
+    require_contest_problems_visible, require_plugin_standings, standings_to_csv,
 };
 use crate::utils::soft_delete::SoftDeletable;
 use crate::utils::test_case_body::read_test_case_body;
@@ -51,6 +55,17 @@ pub async fn create_contest(
 ) -> Result<impl IntoResponse, AppError> {
     auth_user.require_permission("contest:create")?;
     validate_create_contest(&payload)?;
+    if payload.allowed_languages.is_some() {
+        let known_languages: std::collections::HashSet<String> = state
+            .registries
+            .language_resolver_registry
+            .read()
+            .await
+            .keys()
+            .cloned()
+            .collect();
+        validate_allowed_languages(payload.allowed_languages.as_ref(), &known_languages)?;
+    }
 
     let now = chrono::Utc::now();
     let new_contest = contest::ActiveModel {
@@ -64,7 +79,15 @@ pub async fn create_contest(
         submissions_visible: Set(payload.submissions_visible.unwrap_or(false)),
         show_compile_output: Set(payload.show_compile_output.unwrap_or(true)),
         show_participants_list: Set(payload.show_participants_list.unwrap_or(true)),
+        hide_problems_until_start: Set(payload.hide_problems_until_start.unwrap_or(true)),
+        dynamic_scoring: Set(payload.dynamic_scoring.unwrap_or(false)),
+        two_phase: Set(payload.two_phase.unwrap_or(false)),
+        practice_after_end: Set(payload.practice_after_end.unwrap_or(false)),
+        anonymize_standings_until_end: Set(payload.anonymize_standings_until_end.unwrap_or(false)),
+        public_standings_limit: Set(payload.public_standings_limit),
+        score_selection: Set(payload.score_selection.unwrap_or_default()),
         contest_type: Set(payload.contest_type),
+        allowed_languages: Set(payload.allowed_languages.map(normalize_allowed_languages)),
         created_at: Set(now),
         updated_at: Set(now),
         ..Default::default()
@@ -81,10 +104,11 @@ pub async fn create_contest(
     tag = "Contests",
     operation_id = "listContests",
     summary = "List contests with pagination and search",
-    description = "Returns a paginated list of contests with optional search and sorting. Users with `contest:manage` see all contests; others only see active public contests and those they are enrolled in. Supports sorting by `created_at`, `updated_at`, `activate_time`, `start_time`, or `title`.",
+    description = "Returns a paginated list of contests with optional search and sorting. Users with `contest:manage` see all contests; others only see active public contests and those they are enrolled in. Supports sorting by `created_at`, `updated_at`, `activate_time`, `start_time`, or `title`. With `tz` set to an IANA time zone name, each item also carries `local_times` rendered in that zone.",
     params(ContestListQuery),
     responses(
         (status = 200, description = "List of contests", body = ContestListResponse),
+        (status = 400, description = "Invalid sort_by or unknown time zone (VALIDATION_ERROR)", body = ErrorBody),
         (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
     ),
     security(("jwt" = [])),
@@ -95,8 +119,9 @@ pub async fn list_contests(
     State(state): State<AppState>,
     Query(query): Query<ContestListQuery>,
 ) -> Result<Json<ContestListResponse>, AppError> {
+    let tz = parse_time_zone(query.tz.as_deref())?;
     let page = Ord::max(query.page.unwrap_or(1), 1);
-    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let per_page = state.config.server.pagination.per_page(query.per_page);
 
     let mut select = contest::Entity::find_active();
 
@@ -170,7 +195,7 @@ pub async fn list_contests(
     select = select.order_by_with_nulls(sort_column, sort_order, sea_query::NullOrdering::Last);
     let total_pages = total.div_ceil(per_page);
 
-    let data = select
+    let mut data = select
         .select_only()
         .column(contest::Column::Id)
         .column(contest::Column::Title)
@@ -182,6 +207,10 @@ pub async fn list_contests(
         .column(contest::Column::SubmissionsVisible)
         .column(contest::Column::ShowCompileOutput)
         .column(contest::Column::ShowParticipantsList)
+        .column(contest::Column::HideProblemsUntilStart)
+        .column(contest::Column::DynamicScoring)
+        .column(contest::Column::TwoPhase)
+        .column(contest::Column::PracticeAfterEnd)
         .column(contest::Column::CreatedAt)
         .column(contest::Column::UpdatedAt)
         .offset(Some((page - 1) * per_page))
@@ -189,6 +218,17 @@ pub async fn list_contests(
         .into_model::<ContestListItem>()
         .all(&state.db)
         .await?;
+    if let Some(tz) = tz {
+        for item in &mut data {
+            item.local_times = Some(ContestLocalTimes::new(
+                tz,
+                item.activate_time,
+                item.start_time,
+                item.end_time,
+                item.deactivate_time,
+            ));
+        }
+    }
 
     Ok(Json(ContestListResponse {
         data,
@@ -207,24 +247,37 @@ pub async fn list_contests(
     tag = "Contests",
     operation_id = "getContest",
     summary = "Get a contest by ID",
-    description = "Returns the full details of a contest. Users with `contest:manage` can view any contest; others can view active public contests or those they are enrolled in. Returns 404 (not 403) for inaccessible contests to prevent enumeration.",
-    params(("id" = i32, Path, description = "Contest ID")),
+    description = "Returns the full details of a contest. Users with `contest:manage` can view any contest; others can view active public contests or those they are enrolled in. Returns 404 (not 403) for inaccessible contests to prevent enumeration. With `tz` set to an IANA time zone name, the response also carries `local_times` rendered in that zone.",
+    params(("id" = i32, Path, description = "Contest ID"), ContestTimeZoneQuery),
     responses(
         (status = 200, description = "Contest details", body = ContestResponse),
+        (status = 400, description = "Unknown time zone (VALIDATION_ERROR)", body = ErrorBody),
         (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
         (status = 404, description = "Contest not found (NOT_FOUND)", body = ErrorBody),
     ),
     security(("jwt" = [])),
 )]
-#[instrument(skip(state, auth_user), fields(id))]
+#[instrument(skip(state, auth_user, query), fields(id))]
 pub async fn get_contest(
     auth_user: AuthUser,
     State(state): State<AppState>,
     AppPath(id): AppPath<i32>,
+    Query(query): Query<ContestTimeZoneQuery>,
 ) -> Result<Json<ContestResponse>, AppError> {
+    let tz = parse_time_zone(query.tz.as_deref())?;
     let model = find_contest(&state.db, id).await?;
     check_contest_access(&state.db, &auth_user, &model).await?;
-    Ok(Json(model.into()))
+    let mut response = ContestResponse::from(model);
+    if let Some(tz) = tz {
+        response.local_times = Some(ContestLocalTimes::new(
+            tz,
+            response.activate_time,
+            response.start_time,
+            response.end_time,
+            response.deactivate_time,
+        ));
+    }
+    Ok(Json(response))
 }
 
 #[utoipa::path(
@@ -263,6 +316,263 @@ pub async fn get_contest_my_info(
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/{id}/my-status",
+    tag = "Contests",
+    operation_id = "getContestMyStatus",
+    summary = "Get current user's contest progress",
+    description = "Returns the authenticated user's solved problems, attempts and penalty: their row on the standings board served by the contest's contest-type plugin, as the caller sees it, so it matches the scoreboard. Returns 404 when the contest type serves no standings. A caller without a row gets no progress. Uses the same visibility rules as getContest.",
+    params(("id" = i32, Path, description = "Contest ID")),
+    responses(
+        (status = 200, description = "Current user's contest progress", body = ContestMyStatusResponse),
+        (status = 400, description = "Contest has not started yet (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 404, description = "Contest not found, or its contest type serves no standings (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id))]
+pub async fn get_contest_my_status(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+) -> Result<Json<ContestMyStatusResponse>, AppError> {
+    let contest_model = find_contest(&state.db, id).await?;
+    check_contest_access(&state.db, &auth_user, &contest_model).await?;
+    require_contest_problems_visible(&auth_user, &contest_model)?;
+
+    let problems = contest_problem::Entity::find()
+        .filter(contest_problem::Column::ContestId.eq(id))
+        .order_by_asc(contest_problem::Column::Position)
+        .order_by_asc(contest_problem::Column::ProblemId)
+        .all(&state.db)
+        .await?;
+
+    let table = require_plugin_standings(&state, &contest_model, &auth_user).await?;
+    let cells = table
+        .rows
+        .into_iter()
+        .find(|row| row.user_id == auth_user.user_id)
+        .map(|row| row.cells)
+        .unwrap_or_default();
+    let problems: Vec<ContestProblemStatus> = problems
+        .into_iter()
+        .map(|p| {
+            let cell = table
+                .labels
+                .iter()
+                .position(|label| *label == p.label)
+                .and_then(|i| cells.get(i))
+                .cloned()
+                .unwrap_or_default();
+            ContestProblemStatus {
+                problem_id: p.problem_id,
+                label: p.label,
+                attempts: cell.attempts,
+                solved: cell.solve_time_ms.is_some(),
+                first_accepted_at: cell
+                    .solve_time_ms
+                    .map(|ms| contest_model.start_time + chrono::Duration::milliseconds(ms)),
+                penalty: cell.penalty,
+            }
+        })
+        .collect();
+
+    Ok(Json(ContestMyStatusResponse {
+        contest_id: id,
+        user_id: auth_user.user_id,
+        solved: problems.iter().filter(|p| p.solved).count() as u32,
+        penalty: problems.iter().map(|p| p.penalty).sum(),
+        problems,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/standings.csv",
+    tag = "Contests",
+    operation_id = "exportContestStandingsCsv",
+    summary = "Export contest standings as CSV",
+    description = "Returns the standings board served by the contest's contest-type plugin, as the caller sees it, as a CSV attachment with a `rank,username,<problem labels...>,total,penalty` header and one row per participant. Returns 404 when the contest type serves no standings. Uses the same visibility rules as getContest, and additionally requires `contest:manage` permission if `show_participants_list` is false.",
+    params(("id" = i32, Path, description = "Contest ID")),
+    responses(
+        (status = 200, description = "Standings CSV", content_type = "text/csv", body = String),
+        (status = 400, description = "Contest has not started yet (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden when show_participants_list is false (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Contest not found, or its contest type serves no standings (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id))]
+pub async fn export_standings_csv(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let contest_model = find_contest(&state.db, id).await?;
+    check_contest_access(&state.db, &auth_user, &contest_model).await?;
+    require_contest_problems_visible(&auth_user, &contest_model)?;
+    if !contest_model.show_participants_list && !auth_user.has_permission("contest:manage") {
+        return Err(AppError::PermissionDenied);
+    }
+
+    let table = require_plugin_standings(&state, &contest_model, &auth_user).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                content_disposition_value(&format!("contest-{id}-standings.csv")),
+            ),
+        ],
+        standings_to_csv(&table),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/system-test",
+    tag = "Contests",
+    operation_id = "runContestSystemTests",
+    summary = "Run system tests for a two-phase contest",
+    description = "Rejudges every submission made during a two-phase contest against the full test set. During the contest such submissions are judged against pretests only. Uses the same re-queueing as bulkRejudgeSubmissions, with each new judgement applied immediately. The contest must have `two_phase` enabled and must have ended. Requires `contest:manage` permission.",
+    params(("id" = i32, Path, description = "Contest ID")),
+    responses(
+        (status = 200, description = "Submissions re-queued", body = BulkRejudgeResponse),
+        (status = 400, description = "Contest is not two-phase or has not ended (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Contest not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id))]
+pub async fn run_system_tests(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+) -> Result<Json<BulkRejudgeResponse>, AppError> {
+    auth_user.require_permission("contest:manage")?;
+
+    let contest_model = find_contest(&state.db, id).await?;
+    if !contest_model.two_phase {
+        return Err(AppError::Validation(
+            "System tests are only available for two-phase contests".into(),
+        ));
+    }
+    if chrono::Utc::now() < contest_model.end_time {
+        return Err(AppError::Validation("Contest has not ended yet".into()));
+    }
+
+    let submission_ids: Vec<i32> = submission::Entity::find()
+        .filter(submission::Column::ContestId.eq(id))
+        .filter(submission::Column::CreatedAt.lt(contest_model.end_time))
+        .select_only()
+        .column(submission::Column::Id)
+        .into_tuple()
+        .all(&state.db)
+        .await?;
+
+    let response =
+        requeue_submissions(&state, auth_user.user_id, submission_ids, true, None).await?;
+
+    tracing::info!(
+        user_id = auth_user.user_id,
+        contest_id = id,
+        queued = response.queued,
+        "Contest system tests queued"
+    );
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/freeze",
+    tag = "Contests",
+    operation_id = "freezeContest",
+    summary = "Freeze contest standings",
+    description = "Records the current time as the contest's `frozen_at`. Until unfreezeContest is called, standings shown to callers without `contest:manage` permission leave out submissions made from then on, regardless of the contest's timeline. Freezing an already frozen contest keeps the original `frozen_at`. Requires `contest:manage` permission.",
+    params(("id" = i32, Path, description = "Contest ID")),
+    responses(
+        (status = 200, description = "Contest frozen", body = ContestResponse),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Contest not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id))]
+pub async fn freeze_contest(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+) -> Result<Json<ContestResponse>, AppError> {
+    auth_user.require_permission("contest:manage")?;
+    let model = set_frozen(&state, id, true).await?;
+    tracing::info!(
+        user_id = auth_user.user_id,
+        contest_id = id,
+        "Contest standings frozen"
+    );
+    Ok(Json(model.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/unfreeze",
+    tag = "Contests",
+    operation_id = "unfreezeContest",
+    summary = "Unfreeze contest standings",
+    description = "Clears the contest's `frozen_at`, so standings again include every submission made before the contest ended. Unfreezing a contest that is not frozen is a no-op. Requires `contest:manage` permission.",
+    params(("id" = i32, Path, description = "Contest ID")),
+    responses(
+        (status = 200, description = "Contest unfrozen", body = ContestResponse),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Contest not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id))]
+pub async fn unfreeze_contest(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+) -> Result<Json<ContestResponse>, AppError> {
+    auth_user.require_permission("contest:manage")?;
+    let model = set_frozen(&state, id, false).await?;
+    tracing::info!(
+        user_id = auth_user.user_id,
+        contest_id = id,
+        "Contest standings unfrozen"
+    );
+    Ok(Json(model.into()))
+}
+
+/// Sets or clears `frozen_at`, keeping the original instant when the contest
+/// is already frozen.
+async fn set_frozen(state: &AppState, id: i32, frozen: bool) -> Result<contest::Model, AppError> {
+    let txn = state.db.begin().await?;
+    let existing = find_contest_for_update(&txn, id).await?;
+    if existing.frozen_at.is_some() == frozen {
+        txn.commit().await?;
+        return Ok(existing);
+    }
+
+    let now = chrono::Utc::now();
+    let mut active: contest::ActiveModel = existing.into();
+    active.frozen_at = Set(frozen.then_some(now));
+    active.updated_at = Set(now);
+    let model = active.update(&txn).await?;
+    txn.commit().await?;
+    state.standings_cache.invalidate_contest(id);
+    Ok(model)
+}
+
 #[utoipa::path(
     patch,
     path = "/{id}",
@@ -290,6 +600,17 @@ pub async fn update_contest(
 ) -> Result<Json<ContestResponse>, AppError> {
     auth_user.require_permission("contest:manage")?;
     validate_update_contest(&payload)?;
+    if let Some(Some(ref allowed_languages)) = payload.allowed_languages {
+        let known_languages: std::collections::HashSet<String> = state
+            .registries
+            .language_resolver_registry
+            .read()
+            .await
+            .keys()
+            .cloned()
+            .collect();
+        validate_allowed_languages(Some(allowed_languages), &known_languages)?;
+    }
 
     if payload == UpdateContestRequest::default() {
         let existing = find_contest(&state.db, id).await?;
@@ -338,13 +659,38 @@ pub async fn update_contest(
     if let Some(show_participants_list) = payload.show_participants_list {
         active.show_participants_list = Set(show_participants_list);
     }
+    if let Some(hide_problems_until_start) = payload.hide_problems_until_start {
+        active.hide_problems_until_start = Set(hide_problems_until_start);
+    }
+    if let Some(dynamic_scoring) = payload.dynamic_scoring {
+        active.dynamic_scoring = Set(dynamic_scoring);
+    }
+    if let Some(two_phase) = payload.two_phase {
+        active.two_phase = Set(two_phase);
+    }
+    if let Some(practice_after_end) = payload.practice_after_end {
+        active.practice_after_end = Set(practice_after_end);
+    }
+    if let Some(anonymize) = payload.anonymize_standings_until_end {
+        active.anonymize_standings_until_end = Set(anonymize);
+    }
+    if let Some(limit) = payload.public_standings_limit {
+        active.public_standings_limit = Set(limit);
+    }
+    if let Some(score_selection) = payload.score_selection {
+        active.score_selection = Set(score_selection);
+    }
     if let Some(contest_type) = payload.contest_type {
         active.contest_type = Set(Some(contest_type));
     }
+    if let Some(allowed_languages) = payload.allowed_languages {
+        active.allowed_languages = Set(allowed_languages.map(normalize_allowed_languages));
+    }
     active.updated_at = Set(chrono::Utc::now());
 
     let model = active.update(&txn).await?;
     txn.commit().await?;
+    state.standings_cache.invalidate_contest(id);
 
     Ok(Json(model.into()))
 }
@@ -389,6 +735,7 @@ pub async fn delete_contest(
     .await?;
 
     txn.commit().await?;
+    state.standings_cache.invalidate_contest(id);
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -398,7 +745,7 @@ pub async fn delete_contest(
     tag = "Contest Problems",
     operation_id = "addContestProblem",
     summary = "Add a problem to a contest",
-    description = "Associates an existing problem with the contest under a given label. Requires `contest:manage` permission. Labels must be unique within the contest. Position is auto-assigned if omitted. Returns 409 if the problem ID or label is already present.",
+    description = "Associates an existing problem with the contest under a given label. Requires `contest:manage` permission. Labels must be unique within the contest; if omitted, the first free label in the sequence A, B, …, Z, AA, AB, … is assigned. Position is auto-assigned if omitted. A positive `result_delay_secs` hides verdicts and scores from contestants until that many seconds after each submission. Returns 400 once the contest holds `contest.max_problems` problems, and 409 if the problem ID or label is already present.",
     params(("id" = i32, Path, description = "Contest ID")),
     request_body = AddContestProblemRequest,
     responses(
@@ -424,6 +771,19 @@ pub async fn add_contest_problem(
     let txn = state.db.begin().await?;
     let _contest = find_contest_for_update(&txn, contest_id).await?;
 
+    let max_problems = state.config.contest.max_problems;
+    if max_problems > 0 {
+        let count = contest_problem::Entity::find()
+            .filter(contest_problem::Column::ContestId.eq(contest_id))
+            .count(&txn)
+            .await?;
+        if count >= max_problems as u64 {
+            return Err(AppError::Validation(format!(
+                "Contest already has the maximum of {max_problems} problems"
+            )));
+        }
+    }
+
     let problem_model = problem::Entity::find_active_by_id(payload.problem_id)
         .one(&txn)
         .await?
@@ -440,17 +800,25 @@ pub async fn add_contest_problem(
         ));
     }
 
-    let label = payload.label.trim().to_string();
-    let existing_label = contest_problem::Entity::find()
-        .filter(contest_problem::Column::ContestId.eq(contest_id))
-        .filter(contest_problem::Column::Label.eq(&label))
-        .one(&txn)
-        .await?;
-    if existing_label.is_some() {
-        return Err(AppError::Conflict(format!(
-            "Label '{label}' is already used in this contest"
-        )));
-    }
+    // The contest row lock taken above serializes concurrent adds, so the
+    // label picked here cannot be claimed before this insert commits.
+    let label = match payload.label {
+        Some(ref label) => {
+            let label = label.trim().to_string();
+            let existing_label = contest_problem::Entity::find()
+                .filter(contest_problem::Column::ContestId.eq(contest_id))
+                .filter(contest_problem::Column::Label.eq(&label))
+                .one(&txn)
+                .await?;
+            if existing_label.is_some() {
+                return Err(AppError::Conflict(format!(
+                    "Label '{label}' is already used in this contest"
+                )));
+            }
+            label
+        }
+        None => next_problem_label(&txn, contest_id).await?,
+    };
 
     let position = match payload.position {
         Some(p) => p,
@@ -462,10 +830,13 @@ pub async fn add_contest_problem(
         problem_id: Set(payload.problem_id),
         label: Set(label),
         position: Set(position),
+        max_points: Set(payload.max_points),
+        result_delay_secs: Set(payload.result_delay_secs.unwrap_or(0)),
     };
 
     let model = new_cp.insert(&txn).await?;
     txn.commit().await?;
+    state.standings_cache.invalidate_contest(contest_id);
 
     Ok((
         StatusCode::CREATED,
@@ -479,7 +850,7 @@ pub async fn add_contest_problem(
     tag = "Contest Problems",
     operation_id = "listContestProblems",
     summary = "List problems in a contest",
-    description = "Returns all problems in the contest, ordered by position. Same visibility rules as getContest apply.",
+    description = "Returns all problems in the contest, ordered by position. Same visibility rules as getContest apply; when `hide_problems_until_start` is set, non-managers get 400 before the contest starts. `solved` and `attempted` come from the caller's row on the standings board served by the contest's contest-type plugin, and are null when the contest type serves none.",
     params(("id" = i32, Path, description = "Contest ID")),
     responses(
         (status = 200, description = "List of contest problems", body = Vec<ContestProblemResponse>),
@@ -497,7 +868,7 @@ pub async fn list_contest_problems(
 ) -> Result<Json<Vec<ContestProblemResponse>>, AppError> {
     let contest_model = find_contest(&state.db, contest_id).await?;
     check_contest_access(&state.db, &auth_user, &contest_model).await?;
-    require_contest_started(&auth_user, &contest_model)?;
+    require_contest_problems_visible(&auth_user, &contest_model)?;
 
     let rows = contest_problem::Entity::find()
         .filter(contest_problem::Column::ContestId.eq(contest_id))
@@ -505,10 +876,34 @@ pub async fn list_contest_problems(
         .order_by_asc(contest_problem::Column::Position)
         .all(&state.db)
         .await?;
+    let board = load_plugin_standings(&state, &contest_model, &auth_user)
+        .await?
+        .map(|table| {
+            let cells = table
+                .rows
+                .into_iter()
+                .find(|row| row.user_id == auth_user.user_id)
+                .map(|row| row.cells)
+                .unwrap_or_default();
+            (table.labels, cells)
+        });
 
     let items = rows
         .into_iter()
-        .map(|(cp, prob)| contest_problem_response(cp, prob.map(|p| p.title).unwrap_or_default()))
+        .map(|(cp, prob)| {
+            let cell = board.as_ref().map(|(labels, cells)| {
+                labels
+                    .iter()
+                    .position(|label| *label == cp.label)
+                    .and_then(|i| cells.get(i))
+                    .cloned()
+                    .unwrap_or_default()
+            });
+            let mut item = contest_problem_response(cp, prob.map(|p| p.title).unwrap_or_default());
+            item.solved = cell.as_ref().map(|c| c.solve_time_ms.is_some());
+            item.attempted = cell.as_ref().map(|c| c.attempts > 0);
+            item
+        })
         .collect();
 
     Ok(Json(items))
@@ -519,8 +914,8 @@ pub async fn list_contest_problems(
     path = "/{problem_id}",
     tag = "Contest Problems",
     operation_id = "updateContestProblem",
-    summary = "Update a contest problem's label or position",
-    description = "Updates the label or position of a problem within a contest. Requires `contest:manage` permission. Returns 409 CONFLICT on duplicate labels.",
+    summary = "Update a contest problem's label, position or points",
+    description = "Updates the label, position, `max_points` or `result_delay_secs` of a problem within a contest. Requires `contest:manage` permission. Returns 409 CONFLICT on duplicate labels.",
     params(
         ("id" = i32, Path, description = "Contest ID"),
         ("problem_id" = i32, Path, description = "Problem ID"),
@@ -584,6 +979,12 @@ pub async fn update_contest_problem(
     if let Some(position) = payload.position {
         active.position = Set(position);
     }
+    if let Some(max_points) = payload.max_points {
+        active.max_points = Set(max_points);
+    }
+    if let Some(result_delay_secs) = payload.result_delay_secs {
+        active.result_delay_secs = Set(result_delay_secs);
+    }
 
     let model = active.update(&txn).await?;
     let title = problem::Entity::find_by_id(model.problem_id)
@@ -592,6 +993,7 @@ pub async fn update_contest_problem(
         .map(|p| p.title)
         .unwrap_or_default();
     txn.commit().await?;
+    state.standings_cache.invalidate_contest(contest_id);
 
     Ok(Json(contest_problem_response(model, title)))
 }
@@ -637,6 +1039,7 @@ pub async fn remove_contest_problem(
     .await?;
 
     txn.commit().await?;
+    state.standings_cache.invalidate_contest(contest_id);
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -704,6 +1107,7 @@ pub async fn reorder_contest_problems(
     }
 
     txn.commit().await?;
+    state.standings_cache.invalidate_contest(contest_id);
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -752,6 +1156,7 @@ pub async fn add_participant(
     match new_cu.insert(&txn).await {
         Ok(model) => {
             txn.commit().await?;
+            state.standings_cache.invalidate_contest(contest_id);
             Ok((
                 StatusCode::CREATED,
                 Json(ContestParticipantResponse {
@@ -857,6 +1262,7 @@ pub async fn remove_participant(
     let active: contest_user::ActiveModel = cu.into();
     active.delete(&txn).await?;
     txn.commit().await?;
+    state.standings_cache.invalidate_contest(contest_id);
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -907,6 +1313,7 @@ pub async fn register_for_contest(
     match new_cu.insert(&txn).await {
         Ok(_) => {
             txn.commit().await?;
+            state.standings_cache.invalidate_contest(contest_id);
             Ok(StatusCode::CREATED)
         }
         Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
@@ -947,6 +1354,7 @@ pub async fn unregister_from_contest(
     let active: contest_user::ActiveModel = cu.into();
     active.delete(&txn).await?;
     txn.commit().await?;
+    state.standings_cache.invalidate_contest(contest_id);
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -1011,6 +1419,7 @@ pub async fn bulk_delete_contest_problems(
         .await?;
 
     txn.commit().await?;
+    state.standings_cache.invalidate_contest(contest_id);
 
     tracing::info!(
         contest_id,
@@ -1224,6 +1633,7 @@ pub async fn bulk_add_participants(
     }
 
     txn.commit().await?;
+    state.standings_cache.invalidate_contest(contest_id);
 
     tracing::info!(
         contest_id,
@@ -1265,6 +1675,10 @@ fn contest_problem_response(
         label: cp.label,
         position: cp.position,
         problem_title,
+        max_points: cp.max_points,
+        result_delay_secs: cp.result_delay_secs,
+        solved: None,
+        attempted: None,
     }
 }
 
@@ -1286,6 +1700,40 @@ async fn next_problem_position<C: ConnectionTrait>(
         .ok_or_else(|| AppError::Validation("Position overflow".into()))
 }
 
+/// First label in the sequence A, B, …, Z, AA, AB, … not yet used in the contest.
+async fn next_problem_label<C: ConnectionTrait>(
+    db: &C,
+    contest_id: i32,
+) -> Result<String, AppError> {
+    let used: std::collections::HashSet<String> = contest_problem::Entity::find()
+        .filter(contest_problem::Column::ContestId.eq(contest_id))
+        .select_only()
+        .column(contest_problem::Column::Label)
+        .into_tuple::<String>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+    Ok((0..)
+        .map(problem_label)
+        .find(|label| !used.contains(label))
+        .expect("label sequence is unbounded"))
+}
+
+/// Spreadsheet-style label for the `index`-th problem: 0 → A, 25 → Z, 26 → AA.
+fn problem_label(mut index: usize) -> String {
+    let mut label = Vec::new();
+    loop {
+        label.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    label.reverse();
+    String::from_utf8(label).expect("labels are ASCII")
+}
+
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct ProblemSamplesResponse {
     pub samples: Vec<SampleCase>,
@@ -1324,7 +1772,7 @@ pub async fn get_contest_problem_samples(
 ) -> Result<Json<ProblemSamplesResponse>, AppError> {
     let contest_model = find_contest(&state.db, contest_id).await?;
     check_contest_access(&state.db, &auth_user, &contest_model).await?;
-    require_contest_started(&auth_user, &contest_model)?;
+    require_contest_problems_visible(&auth_user, &contest_model)?;
 
     let _cp = find_contest_problem(&state.db, contest_id, problem_id).await?;
 
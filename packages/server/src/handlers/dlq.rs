@@ -21,6 +21,7 @@ use crate::handlers::submission::dispatch_to_plugin;
 use crate::models::dlq::*;
 use crate::models::shared::Pagination;
 use crate::state::AppState;
+use crate::webhook;
 
 #[utoipa::path(
     get,
@@ -52,7 +53,7 @@ pub async fn list_dlq_messages(
         .map_err(AppError::Validation)?;
 
     let page = params.page.unwrap_or(1).max(1);
-    let per_page = params.per_page.unwrap_or(20).clamp(1, 100);
+    let per_page = state.config.server.pagination.per_page(params.per_page);
 
     let dlq = dlq_service(&state.db);
     let (messages, total) = dlq
@@ -139,11 +140,11 @@ pub async fn get_dlq_message(
     tag = "Dead Letter Queue",
     operation_id = "retryDlqMessage",
     summary = "Retry a DLQ message",
-    description = "Retries a dead letter message by resetting the submission to Pending and re-dispatching it to the plugin-based judging system. Only stuck_submission messages can be retried; operation_task messages are coordinated by the plugin and cannot be retried from here. Marks the DLQ entry as resolved. Requires `dlq:manage` permission.",
+    description = "Retries a dead letter message. A stuck_submission message resets the submission to Pending and re-dispatches it to the plugin-based judging system; a webhook_delivery message sends the recorded payload to its URL again, freshly signed. operation_task messages are coordinated by the plugin and cannot be retried from here. Marks the DLQ entry as resolved. Requires `dlq:manage` permission.",
     params(("id" = i32, Path, description = "DLQ message ID")),
     responses(
-        (status = 200, description = "Submission re-dispatched", body = DlqRetryResponse),
-        (status = 400, description = "Only stuck_submission messages can be retried, or submission is not in a retryable state (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 200, description = "Submission re-dispatched or webhook re-sent", body = DlqRetryResponse),
+        (status = 400, description = "Message type cannot be retried, submission is not in a retryable state, or webhooks have no secret configured (VALIDATION_ERROR)", body = ErrorBody),
         (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
         (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
         (status = 404, description = "Message or submission not found (NOT_FOUND)", body = ErrorBody),
@@ -171,9 +172,12 @@ pub async fn retry_dlq_message(
         return Err(AppError::Conflict("Message already resolved".into()));
     }
 
+    if message.message_type == DlqMessageType::WebhookDelivery.as_str() {
+        return retry_webhook_delivery(&state, txn, message, auth_user.user_id).await;
+    }
     if message.message_type != DlqMessageType::StuckSubmission.as_str() {
         return Err(AppError::Validation(
-            "Only stuck_submission messages can be retried. operation_task messages are coordinated by the plugin system.".into(),
+            "Only stuck_submission and webhook_delivery messages can be retried. operation_task messages are coordinated by the plugin system.".into(),
         ));
     }
 
@@ -243,6 +247,61 @@ pub async fn retry_dlq_message(
     }))
 }
 
+/// Resolves a dead-lettered webhook delivery and sends its payload again in
+/// the background. A repeated failure lands in the DLQ as a new entry.
+async fn retry_webhook_delivery(
+    state: &AppState,
+    txn: sea_orm::DatabaseTransaction,
+    message: dead_letter_message::Model,
+    resolved_by: i32,
+) -> Result<Json<DlqRetryResponse>, AppError> {
+    if state.config.webhook.secret.is_empty() {
+        return Err(AppError::Validation(
+            "Cannot retry: webhook.secret is not configured".into(),
+        ));
+    }
+    let url = message.payload["url"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| AppError::Validation("Cannot retry: webhook URL is missing".into()))?;
+    let payload: webhook::JudgeResultPayload =
+        serde_json::from_value(message.payload["body"].clone()).map_err(|e| {
+            AppError::Validation(format!("Cannot retry: webhook payload is malformed: {e}"))
+        })?;
+
+    match DlqService::new(&txn)
+        .resolve(message.id, Some(resolved_by))
+        .await?
+    {
+        ResolveResult::Resolved => {}
+        ResolveResult::AlreadyResolved => {
+            warn!(
+                id = message.id,
+                "DLQ message was resolved concurrently during retry"
+            );
+        }
+        ResolveResult::NotFound => {
+            return Err(AppError::Internal(
+                "DLQ message disappeared during retry".into(),
+            ));
+        }
+    }
+    txn.commit().await?;
+
+    let db = state.db.clone();
+    let config = state.config.webhook.clone();
+    let target = url.clone();
+    tokio::spawn(async move {
+        webhook::redeliver_judge_result(&db, &config, &target, &payload).await;
+    });
+
+    info!(id = message.id, url = %url, "DLQ webhook delivery retried");
+
+    Ok(Json(DlqRetryResponse {
+        message: format!("Webhook delivery to {url} re-sent"),
+    }))
+}
+
 #[utoipa::path(
     delete,
     path = "/{id}",
@@ -0,0 +1,59 @@
+use axum::{Json, body::Bytes, extract::State};
+use chrono::Utc;
+use tracing::instrument;
+
+use crate::error::{AppError, ErrorBody};
+use crate::extractors::auth::AuthUser;
+use crate::models::maintenance::{
+    ArchiveSubmissionsRequest, ArchiveSubmissionsResponse, DEFAULT_ARCHIVE_LIMIT,
+    validate_archive_submissions,
+};
+use crate::state::AppState;
+use crate::utils::retention::archive_submissions as run_archive;
+
+#[utoipa::path(
+    post,
+    path = "/archive-submissions",
+    tag = "Admin",
+    operation_id = "archiveSubmissions",
+    summary = "Archive old submissions",
+    description = "Moves the source files and per-test-case results of terminal submissions older than `older_than_days` (default `submission.archive_after_days`) into `archived_submission`. The submission rows keep their verdict, score and timings. Submissions with a pending rejudge are skipped. At most `limit` submissions are archived per call. Requires `system:admin` permission.",
+    request_body = ArchiveSubmissionsRequest,
+    responses(
+        (status = 200, description = "Archival pass completed", body = ArchiveSubmissionsResponse),
+        (status = 400, description = "Validation error (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, body))]
+pub async fn archive_submissions(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    body: Bytes,
+) -> Result<Json<ArchiveSubmissionsResponse>, AppError> {
+    auth_user.require_permission("system:admin")?;
+
+    let payload = if body.is_empty() {
+        ArchiveSubmissionsRequest::default()
+    } else {
+        serde_json::from_slice::<ArchiveSubmissionsRequest>(&body)
+            .map_err(|e| AppError::Validation(format!("Invalid archive request body: {e}")))?
+    };
+    validate_archive_submissions(&payload)?;
+
+    let days = payload
+        .older_than_days
+        .unwrap_or(state.config.submission.archive_after_days);
+    let limit = payload.limit.unwrap_or(DEFAULT_ARCHIVE_LIMIT);
+    let cutoff = Utc::now() - chrono::Duration::days(i64::from(days));
+
+    let archived = run_archive(&state.db, cutoff, limit).await?;
+
+    Ok(Json(ArchiveSubmissionsResponse {
+        archived,
+        cutoff,
+        has_more: archived >= limit,
+    }))
+}
@@ -10,12 +10,14 @@ pub mod contest;
 pub mod dlq;
 pub mod health;
 pub mod i18n;
+pub mod maintenance;
 pub mod meta;
 pub mod plugin;
 pub mod plugin_config;
 pub mod problem;
 pub mod proxy;
 pub mod role;
+pub mod saved_view;
 pub mod submission;
 pub mod system;
 pub mod telemetry;
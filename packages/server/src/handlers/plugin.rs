@@ -2,10 +2,12 @@ use axum::{Json, extract::State};
 use plugin_core::registry::PluginStatus;
 use tracing::instrument;
 
-use crate::error::AppError;
+use crate::error::{AppError, ErrorBody};
+use crate::extractors::auth::AuthUser;
+use crate::extractors::path::AppPath;
 use crate::models::plugin::{
     ActivePluginResponse, CheckerFormatEntry, ContestTypeEntry, EvaluatorEntry, HookEntryInfo,
-    LanguageRegistryItem, RegistriesResponse,
+    LanguageRegistryItem, PluginAssetsResponse, PluginFunctionsResponse, RegistriesResponse,
 };
 use crate::state::AppState;
 
@@ -147,3 +149,77 @@ pub async fn list_active_plugins(
 
     Ok(Json(active_plugins))
 }
+
+#[utoipa::path(
+    get,
+    path = "/{id}/functions",
+    tag = "Plugins",
+    operation_id = "listPluginFunctions",
+    summary = "List functions exposed by a plugin",
+    description = "Returns the functions referenced by a plugin's server manifest (route handlers, hook functions, and functions with declared I/O schemas), along with the plugin's declared capabilities. Requires `plugin:manage` permission.",
+    params(("id" = String, Path, description = "Plugin ID")),
+    responses(
+        (status = 200, description = "Plugin functions", body = PluginFunctionsResponse),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Plugin not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id))]
+pub async fn list_plugin_functions(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<String>,
+) -> Result<Json<PluginFunctionsResponse>, AppError> {
+    auth_user.require_permission("plugin:manage")?;
+
+    let plugin = state
+        .plugins
+        .list_plugins()
+        .map_err(AppError::from)?
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| AppError::NotFound(format!("Plugin '{}' not found", id)))?;
+
+    Ok(Json(PluginFunctionsResponse::from(plugin)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/assets",
+    tag = "Plugins",
+    operation_id = "listPluginAssets",
+    summary = "List web assets of a plugin",
+    description = "Returns the paths of the files in a plugin's web bundle, as served under `/assets/{id}/`. Requires `plugin:manage` permission.",
+    params(("id" = String, Path, description = "Plugin ID")),
+    responses(
+        (status = 200, description = "Plugin assets", body = PluginAssetsResponse),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Plugin not found or has no web assets (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id))]
+pub async fn list_plugin_assets(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<String>,
+) -> Result<Json<PluginAssetsResponse>, AppError> {
+    auth_user.require_permission("plugin:manage")?;
+
+    if !state.plugins.has_plugin(&id).map_err(AppError::from)? {
+        return Err(AppError::NotFound(format!("Plugin '{}' not found", id)));
+    }
+    let plugins = state.plugins.clone();
+    let plugin_id = id.clone();
+    let assets = tokio::task::spawn_blocking(move || plugins.list_plugin_assets(&plugin_id))
+        .await
+        .map_err(|e| AppError::Internal(format!("Asset listing task panicked: {e}")))??;
+
+    Ok(Json(PluginAssetsResponse {
+        plugin_id: id,
+        assets,
+    }))
+}
@@ -1,7 +1,9 @@
 use std::collections::{BTreeMap, HashMap};
 use std::io::Read;
+use std::path::{Path, PathBuf};
 
 use axum::Json;
+use axum::body::Bytes;
 use axum::extract::{DefaultBodyLimit, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
@@ -11,7 +13,10 @@ use sea_orm::sea_query::{Func, LikeExpr};
 use sea_orm::*;
 use tracing::instrument;
 
-use crate::entity::{contest, contest_problem, problem, test_case, test_case_result};
+use crate::entity::problem::TestDetailsVisibility;
+use crate::entity::{
+    contest, contest_problem, problem, problem_version, test_case, test_case_result,
+};
 use crate::error::{AppError, ErrorBody};
 use crate::extractors::auth::AuthUser;
 use crate::extractors::json::AppJson;
@@ -19,14 +24,20 @@ use crate::extractors::path::AppPath;
 use crate::handlers::plugin_config::{delete_config_by_scope, delete_config_by_scope_like};
 use crate::models::plugin_config::config_key;
 use crate::models::problem::*;
+use crate::models::saved_view::SavedViewResource;
 use crate::state::AppState;
 use crate::upload_limits::{
     BULK_TEST_CASE_MAX_TOTAL_DECOMPRESSED_BYTES, BULK_TEST_CASE_MAX_TOTAL_DECOMPRESSED_MIB,
     LARGE_UPLOAD_LIMIT_BYTES, LARGE_UPLOAD_LIMIT_MIB,
 };
-use crate::utils::contest::require_problem_read_access;
+use crate::utils::contest::{find_contest_problem, require_problem_read_access};
+use crate::utils::difficulty::{estimate_difficulty, load_submission_stats};
 use crate::utils::filename::{is_sample_directory, split_dir_filename};
-use crate::utils::problem::find_problem;
+use crate::utils::problem::{
+    find_problem, load_ioi_subtasks, record_problem_version, summarize_test_case_scores,
+};
+use crate::utils::saved_view::load_saved_view;
+use crate::utils::similarity::find_similar_problems;
 use crate::utils::soft_delete::SoftDeletable;
 use crate::utils::test_case_body::{
     prepare_test_case_body, read_test_case_body, test_case_body_preview, test_case_body_size,
@@ -39,7 +50,7 @@ use crate::utils::text::{sanitize_db_json, sanitize_db_text, sanitize_db_text_op
     tag = "Problems",
     operation_id = "createProblem",
     summary = "Create a new problem",
-    description = "Creates a new problem in the system. Requires `problem:create` permission.",
+    description = "Creates a new problem in the system. Requires `problem:create` permission. Existing problems with a near-identical title or statement are listed in `similar_problems` as a warning; creation is never blocked. Set `force` to skip the check.",
     request_body = CreateProblemRequest,
     responses(
         (status = 201, description = "Problem created", body = ProblemResponse),
@@ -94,16 +105,31 @@ pub async fn create_problem(
     let submission_format_json = payload
         .submission_format
         .map(|sf| sanitize_db_json(serde_json::to_value(sf).unwrap_or(serde_json::Value::Null)));
+    let title = sanitize_db_text(payload.title.trim());
+    let content = sanitize_db_text(payload.content);
+    let similar_problems = if payload.force {
+        vec![]
+    } else {
+        find_similar_problems(&state.db, &title, &content).await?
+    };
     let new_problem = problem::ActiveModel {
-        title: Set(sanitize_db_text(payload.title.trim())),
-        content: Set(sanitize_db_text(payload.content)),
+        title: Set(title),
+        content: Set(content),
         time_limit: Set(payload.time_limit),
         memory_limit: Set(payload.memory_limit),
+        wall_time_multiplier: Set(payload.wall_time_multiplier),
+        require_zero_exit: Set(payload.require_zero_exit.unwrap_or(true)),
         problem_type: Set(problem_type),
         checker_format: Set(payload.checker_format),
         default_contest_type: Set(default_contest_type),
-        show_test_details: Set(payload.show_test_details.unwrap_or(false)),
+        test_details_visibility: Set(payload
+            .test_details_visibility
+            .or(payload.show_test_details.map(TestDetailsVisibility::from))
+            .unwrap_or_default()),
         submission_format: Set(submission_format_json),
+        difficulty: Set(payload.difficulty),
+        difficulty_is_manual: Set(payload.difficulty.is_some()),
+        is_public: Set(payload.is_public),
         created_at: Set(now),
         updated_at: Set(now),
         ..Default::default()
@@ -111,7 +137,13 @@ pub async fn create_problem(
 
     let model = new_problem.insert(&state.db).await?;
 
-    Ok((StatusCode::CREATED, Json(ProblemResponse::from(model))))
+    Ok((
+        StatusCode::CREATED,
+        Json(ProblemResponse {
+            similar_problems,
+            ..ProblemResponse::from(model)
+        }),
+    ))
 }
 
 #[utoipa::path(
@@ -136,11 +168,74 @@ pub async fn list_problems(
     Query(query): Query<ProblemListQuery>,
 ) -> Result<Json<ProblemListResponse>, AppError> {
     auth_user.require_any_permission(&["problem:create", "problem:edit"])?;
+    let query = match query.view.as_deref() {
+        Some(name) => {
+            let saved = load_saved_view(
+                &state.db,
+                auth_user.user_id,
+                SavedViewResource::Problems,
+                name,
+            )
+            .await?;
+            query.or_saved(saved)
+        }
+        None => query,
+    };
+
+    Ok(Json(list_problem_page(&state, &query, false).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/problems",
+    tag = "Problems",
+    operation_id = "listPracticeProblems",
+    summary = "List practice problems",
+    description = "Returns a paginated list of problems marked `is_public`, for any authenticated user. Supports the same search, sorting and saved views as the full problem list. Listed problems can be read and submitted to without problem permissions.",
+    params(ProblemListQuery),
+    responses(
+        (status = 200, description = "List of public problems", body = ProblemListResponse),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, query))]
+pub async fn list_practice_problems(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<ProblemListQuery>,
+) -> Result<Json<ProblemListResponse>, AppError> {
+    let query = match query.view.as_deref() {
+        Some(name) => {
+            let saved = load_saved_view(
+                &state.db,
+                auth_user.user_id,
+                SavedViewResource::Problems,
+                name,
+            )
+            .await?;
+            query.or_saved(saved)
+        }
+        None => query,
+    };
+
+    Ok(Json(list_problem_page(&state, &query, true).await?))
+}
 
+/// One page of active problems matching `query`, restricted to public ones
+/// when `public_only` is set.
+async fn list_problem_page(
+    state: &AppState,
+    query: &ProblemListQuery,
+    public_only: bool,
+) -> Result<ProblemListResponse, AppError> {
     let page = Ord::max(query.page.unwrap_or(1), 1);
-    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let per_page = state.config.server.pagination.per_page(query.per_page);
 
     let mut select = problem::Entity::find_active();
+    if public_only {
+        select = select.filter(problem::Column::IsPublic.eq(true));
+    }
 
     if let Some(ref search) = query.search {
         let term = escape_like(search.trim());
@@ -187,7 +282,9 @@ pub async fn list_problems(
         .column(problem::Column::ProblemType)
         .column(problem::Column::CheckerFormat)
         .column(problem::Column::DefaultContestType)
-        .column(problem::Column::ShowTestDetails)
+        .column(problem::Column::TestDetailsVisibility)
+        .column(problem::Column::Difficulty)
+        .column(problem::Column::IsPublic)
         .column(problem::Column::CreatedAt)
         .column(problem::Column::UpdatedAt)
         .offset(Some((page - 1) * per_page))
@@ -196,7 +293,7 @@ pub async fn list_problems(
         .all(&state.db)
         .await?;
 
-    Ok(Json(ProblemListResponse {
+    Ok(ProblemListResponse {
         data,
         pagination: Pagination {
             page,
@@ -204,7 +301,7 @@ pub async fn list_problems(
             total,
             total_pages,
         },
-    }))
+    })
 }
 
 #[utoipa::path(
@@ -213,7 +310,7 @@ pub async fn list_problems(
     tag = "Problems",
     operation_id = "getProblem",
     summary = "Get a problem by ID",
-    description = "Returns the full details of a problem, including its Markdown content and sample test case metadata. Accessible to users with `problem:create`/`problem:edit` permission, or to participants of any active (started) contest that includes this problem.",
+    description = "Returns the full details of a problem, including its Markdown content and sample test case metadata. Accessible to users with `problem:create`/`problem:edit` permission, or to participants of any active (started) contest that includes this problem. Public problems are readable by any authenticated user.",
     params(("id" = i32, Path, description = "Problem ID")),
     responses(
         (status = 200, description = "Problem details", body = ProblemResponse),
@@ -241,7 +338,7 @@ pub async fn get_problem(
     tag = "Problems",
     operation_id = "updateProblem",
     summary = "Update an existing problem",
-    description = "Partially updates a problem using PATCH semantics — only provided fields are modified. Requires `problem:edit` permission. An empty payload returns the current resource unchanged.",
+    description = "Partially updates a problem using PATCH semantics — only provided fields are modified. Changing the title, content or limits first records the previous values as a new version. Requires `problem:edit` permission. An empty payload returns the current resource unchanged.",
     params(("id" = i32, Path, description = "Problem ID")),
     request_body = UpdateProblemRequest,
     responses(
@@ -292,6 +389,29 @@ pub async fn update_problem(
     let txn = state.db.begin().await?;
 
     let existing = find_problem(&txn, id).await?;
+    let statement_changed = payload
+        .title
+        .as_ref()
+        .is_some_and(|t| sanitize_db_text(t.trim()) != existing.title)
+        || payload
+            .content
+            .as_ref()
+            .is_some_and(|c| sanitize_db_text(c) != existing.content)
+        || payload
+            .time_limit
+            .is_some_and(|tl| tl != existing.time_limit)
+        || payload
+            .memory_limit
+            .is_some_and(|ml| ml != existing.memory_limit);
+    if statement_changed {
+        record_problem_version(&txn, &existing, auth_user.user_id).await?;
+    }
+    // Older clients send back the `show_test_details` they were given, so it
+    // only changes the visibility when it disagrees with the current one.
+    let legacy_visibility = payload
+        .show_test_details
+        .filter(|show| *show != (existing.test_details_visibility == TestDetailsVisibility::Full))
+        .map(TestDetailsVisibility::from);
     let mut active: problem::ActiveModel = existing.into();
 
     if let Some(ref title) = payload.title {
@@ -306,6 +426,12 @@ pub async fn update_problem(
     if let Some(ml) = payload.memory_limit {
         active.memory_limit = Set(ml);
     }
+    if let Some(multiplier) = payload.wall_time_multiplier {
+        active.wall_time_multiplier = Set(multiplier);
+    }
+    if let Some(require_zero_exit) = payload.require_zero_exit {
+        active.require_zero_exit = Set(require_zero_exit);
+    }
     if let Some(problem_type) = payload.problem_type {
         active.problem_type = Set(problem_type);
     }
@@ -315,8 +441,8 @@ pub async fn update_problem(
     if let Some(default_contest_type) = payload.default_contest_type {
         active.default_contest_type = Set(default_contest_type);
     }
-    if let Some(show_test_details) = payload.show_test_details {
-        active.show_test_details = Set(show_test_details);
+    if let Some(visibility) = payload.test_details_visibility.or(legacy_visibility) {
+        active.test_details_visibility = Set(visibility);
     }
     match payload.submission_format {
         Some(Some(sf)) => {
@@ -329,6 +455,13 @@ pub async fn update_problem(
         }
         None => {}
     }
+    if let Some(difficulty) = payload.difficulty {
+        active.difficulty = Set(difficulty);
+        active.difficulty_is_manual = Set(difficulty.is_some());
+    }
+    if let Some(is_public) = payload.is_public {
+        active.is_public = Set(is_public);
+    }
     active.updated_at = Set(chrono::Utc::now());
 
     let model = active.update(&txn).await?;
@@ -339,6 +472,221 @@ pub async fn update_problem(
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/{id}/versions",
+    tag = "Problems",
+    operation_id = "listProblemVersions",
+    summary = "List previous versions of a problem",
+    description = "Returns the title, content and limits the problem had before each edit that changed them, newest first. Requires `problem:manage` permission.",
+    params(("id" = i32, Path, description = "Problem ID")),
+    responses(
+        (status = 200, description = "Problem versions", body = Vec<ProblemVersionResponse>),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Problem not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id))]
+pub async fn list_problem_versions(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+) -> Result<Json<Vec<ProblemVersionResponse>>, AppError> {
+    auth_user.require_permission("problem:manage")?;
+    find_problem(&state.db, id).await?;
+
+    let versions = problem_version::Entity::find()
+        .filter(problem_version::Column::ProblemId.eq(id))
+        .order_by_desc(problem_version::Column::Version)
+        .all(&state.db)
+        .await?;
+    Ok(Json(
+        versions
+            .into_iter()
+            .map(ProblemVersionResponse::from)
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/versions/{version}/restore",
+    tag = "Problems",
+    operation_id = "restoreProblemVersion",
+    summary = "Restore a previous version of a problem",
+    description = "Sets the problem's title, content and limits back to those of `version`. The values being replaced are recorded as a new version, so a restore can itself be undone. Requires `problem:manage` permission.",
+    params(
+        ("id" = i32, Path, description = "Problem ID"),
+        ("version" = i32, Path, description = "Version number to restore"),
+    ),
+    responses(
+        (status = 200, description = "Problem restored", body = ProblemResponse),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Problem or version not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id, version))]
+pub async fn restore_problem_version(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath((id, version)): AppPath<(i32, i32)>,
+) -> Result<Json<ProblemResponse>, AppError> {
+    auth_user.require_permission("problem:manage")?;
+
+    let txn = state.db.begin().await?;
+    let existing = find_problem(&txn, id).await?;
+    let snapshot = problem_version::Entity::find()
+        .filter(problem_version::Column::ProblemId.eq(id))
+        .filter(problem_version::Column::Version.eq(version))
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Problem version not found".into()))?;
+
+    record_problem_version(&txn, &existing, auth_user.user_id).await?;
+    let mut active: problem::ActiveModel = existing.into();
+    active.title = Set(snapshot.title);
+    active.content = Set(snapshot.content);
+    active.time_limit = Set(snapshot.time_limit);
+    active.memory_limit = Set(snapshot.memory_limit);
+    active.updated_at = Set(chrono::Utc::now());
+    let model = active.update(&txn).await?;
+    txn.commit().await?;
+
+    let mut response = ProblemResponse::from(model);
+    response.samples = load_sample_test_cases(&state.db, id).await?;
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/score-summary",
+    tag = "Problems",
+    operation_id = "getProblemScoreSummary",
+    summary = "Summarize test case scores",
+    description = "Returns the total of all test case scores and, with `contest_id`, per-subtask sums for the subtasks in the problem's IOI task config in that contest. `warnings` lists points on sample test cases, subtasks whose `max_score` differs from their test cases' sum or that list unknown test cases, and non-sample test cases in no subtask or in several. Requires `problem:create` or `problem:edit` permission.",
+    params(("id" = i32, Path, description = "Problem ID"), ProblemScoreSummaryQuery),
+    responses(
+        (status = 200, description = "Score summary", body = ProblemScoreSummaryResponse),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Problem not found, or not in `contest_id` (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, query), fields(id))]
+pub async fn get_problem_score_summary(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+    Query(query): Query<ProblemScoreSummaryQuery>,
+) -> Result<Json<ProblemScoreSummaryResponse>, AppError> {
+    auth_user.require_any_permission(&["problem:create", "problem:edit"])?;
+
+    find_problem(&state.db, id).await?;
+    let subtasks = match query.contest_id {
+        Some(contest_id) => {
+            find_contest_problem(&state.db, contest_id, id).await?;
+            load_ioi_subtasks(&state.db, contest_id, id).await?
+        }
+        None => Vec::new(),
+    };
+
+    let test_cases: Vec<(i32, String, i32, bool)> = test_case::Entity::find()
+        .filter(test_case::Column::ProblemId.eq(id))
+        .select_only()
+        .column(test_case::Column::Id)
+        .column(test_case::Column::Label)
+        .column(test_case::Column::Score)
+        .column(test_case::Column::IsSample)
+        .order_by_asc(test_case::Column::Position)
+        .order_by_asc(test_case::Column::Id)
+        .into_tuple()
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(summarize_test_case_scores(id, &test_cases, &subtasks)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/estimate-difficulty",
+    tag = "Problems",
+    operation_id = "estimateProblemDifficulty",
+    summary = "Estimate problem difficulty from submissions",
+    description = "Computes a 0-100 difficulty from the acceptance rate and the share of attempting users who solved the problem, over judged submissions from the last `window_days` days (default 90). The estimate is stored on the problem unless an editor set the difficulty manually; pass `overwrite_manual: true` to replace it. Requires `problem:edit` permission.",
+    params(("id" = i32, Path, description = "Problem ID")),
+    request_body = EstimateDifficultyRequest,
+    responses(
+        (status = 200, description = "Difficulty estimated", body = EstimateDifficultyResponse),
+        (status = 400, description = "Validation error or no judged submissions in window (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Problem not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, body), fields(id))]
+pub async fn estimate_problem_difficulty(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+    body: Bytes,
+) -> Result<Json<EstimateDifficultyResponse>, AppError> {
+    auth_user.require_permission("problem:edit")?;
+
+    let payload = if body.is_empty() {
+        EstimateDifficultyRequest::default()
+    } else {
+        serde_json::from_slice::<EstimateDifficultyRequest>(&body)
+            .map_err(|e| AppError::Validation(format!("Invalid estimate request body: {e}")))?
+    };
+    validate_estimate_difficulty(&payload)?;
+
+    let window_days = payload
+        .window_days
+        .unwrap_or(DEFAULT_DIFFICULTY_WINDOW_DAYS);
+    let since = chrono::Utc::now() - chrono::Duration::days(i64::from(window_days));
+
+    let txn = state.db.begin().await?;
+    let existing = find_problem_for_update(&txn, id).await?;
+
+    let stats = load_submission_stats(&txn, id, since).await?;
+    if stats.total == 0 {
+        return Err(AppError::Validation(format!(
+            "No judged submissions in the last {window_days} days"
+        )));
+    }
+    let estimated = estimate_difficulty(&stats);
+
+    let applied = !existing.difficulty_is_manual || payload.overwrite_manual.unwrap_or(false);
+    let difficulty = if applied {
+        let mut active: problem::ActiveModel = existing.into();
+        active.difficulty = Set(Some(estimated));
+        active.difficulty_is_manual = Set(false);
+        active.updated_at = Set(chrono::Utc::now());
+        active.update(&txn).await?.difficulty
+    } else {
+        existing.difficulty
+    };
+    txn.commit().await?;
+
+    Ok(Json(EstimateDifficultyResponse {
+        problem_id: id,
+        estimated_difficulty: estimated,
+        difficulty,
+        applied,
+        window_days,
+        total_submissions: stats.total,
+        accepted_submissions: stats.accepted,
+        distinct_attempters: stats.attempters,
+        distinct_solvers: stats.solvers,
+    }))
+}
+
 #[utoipa::path(
     delete,
     path = "/{id}",
@@ -460,12 +808,14 @@ pub async fn create_test_case(
         expected_output_size: Set(Some(output_body.size)),
         input_preview: Set(Some(input_body.preview)),
         expected_output_preview: Set(Some(output_body.preview)),
+        accepted_outputs: Set(accepted_outputs_to_json(payload.accepted_outputs)),
         score: Set(payload.score),
         description: Set(sanitize_db_text_opt(
             payload.description.map(|d| d.trim().to_string()),
         )),
         label: Set(sanitize_db_text(label)),
         is_sample: Set(payload.is_sample),
+        is_pretest: Set(payload.is_pretest),
         position: Set(position),
         problem_id: Set(problem_id),
         created_at: Set(chrono::Utc::now()),
@@ -487,7 +837,7 @@ pub async fn create_test_case(
     tag = "Test Cases",
     operation_id = "listTestCases",
     summary = "List test cases for a problem",
-    description = "Returns all test cases for a problem, ordered by position. Requires `problem:create` or `problem:edit` permission. Input and output are truncated to 100-character previews.",
+    description = "Returns all test cases for a problem, ordered by position. Requires `problem:create` or `problem:edit` permission. Input and output are truncated to 100-character previews; `input_size` and `output_size` give their full lengths in bytes.",
     params(("id" = i32, Path, description = "Problem ID")),
     responses(
         (status = 200, description = "List of test cases", body = Vec<TestCaseListItem>),
@@ -517,6 +867,7 @@ pub async fn list_test_cases(
         .column(test_case::Column::Label)
         .column(test_case::Column::Description)
         .column(test_case::Column::IsSample)
+        .column(test_case::Column::IsPretest)
         .column(test_case::Column::Position)
         .column_as(
             Expr::cust(format!(
@@ -530,6 +881,14 @@ pub async fn list_test_cases(
             )),
             "output_preview",
         )
+        .column_as(
+            Expr::cust("COALESCE(\"input_size\", octet_length(\"input\"))"),
+            "input_size",
+        )
+        .column_as(
+            Expr::cust("COALESCE(\"expected_output_size\", octet_length(\"expected_output\"))"),
+            "output_size",
+        )
         .column(test_case::Column::ProblemId)
         .column(test_case::Column::CreatedAt)
         .order_by_asc(test_case::Column::Position)
@@ -645,12 +1004,18 @@ pub async fn update_test_case(
         active.expected_output_size = Set(Some(body.size));
         active.expected_output_preview = Set(Some(body.preview));
     }
+    if let Some(accepted_outputs) = payload.accepted_outputs {
+        active.accepted_outputs = Set(accepted_outputs_to_json(accepted_outputs));
+    }
     if let Some(score) = payload.score {
         active.score = Set(score);
     }
     if let Some(is_sample) = payload.is_sample {
         active.is_sample = Set(is_sample);
     }
+    if let Some(is_pretest) = payload.is_pretest {
+        active.is_pretest = Set(is_pretest);
+    }
     if let Some(position) = payload.position {
         active.position = Set(position);
     }
@@ -761,116 +1126,68 @@ pub async fn upload_test_cases(
             "ZIP contains no valid input/output file pairs matching the specified formats".into(),
         ));
     }
-    let auto_scores = default_uploaded_scores(&entries);
+    let response = store_uploaded_test_cases(&state, problem_id, entries, data.strategy).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
 
-    let txn = state.db.begin().await?;
-    find_problem_for_update(&txn, problem_id).await?;
+#[utoipa::path(
+    post,
+    path = "/import-dir",
+    tag = "Test Cases",
+    operation_id = "importTestCasesFromDir",
+    summary = "Import test cases from a server-side directory",
+    description = "Bulk-creates test cases from files already on the server, matched by the same `*` formats and `sample/` rules as the ZIP upload. `path` is resolved against the configured `problem.test_case_import_dir`; paths that resolve outside it are rejected. Requires `problem:manage` permission.",
+    params(("id" = i32, Path, description = "Problem ID")),
+    request_body = ImportTestCasesDirRequest,
+    responses(
+        (status = 201, description = "Test cases imported", body = UploadTestCasesResponse),
+        (status = 400, description = "Validation error (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Problem not found (NOT_FOUND)", body = ErrorBody),
+        (status = 409, description = "Duplicate label in problem (CONFLICT)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, payload), fields(problem_id))]
+pub async fn import_test_cases_dir(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(problem_id): AppPath<i32>,
+    AppJson(payload): AppJson<ImportTestCasesDirRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    auth_user.require_permission("problem:manage")?;
 
-    let is_replace = matches!(data.strategy, UploadTestCasesMergeStrategy::Replace);
-    if is_replace {
-        test_case::Entity::delete_many()
-            .filter(test_case::Column::ProblemId.eq(problem_id))
-            .exec(&txn)
-            .await?;
+    if payload.input_format.matches('*').count() != 1
+        || payload.output_format.matches('*').count() != 1
+    {
+        return Err(AppError::Validation(
+            "Formats must contain exactly one '*' wildcard".into(),
+        ));
     }
 
-    let mut start_pos = if is_replace {
-        0
-    } else {
-        next_test_case_position(&txn, problem_id).await?
-    };
-
-    let mut existing_cases: std::collections::HashMap<String, test_case::Model> =
-        test_case::Entity::find()
-            .filter(test_case::Column::ProblemId.eq(problem_id))
-            .all(&txn)
-            .await?
-            .into_iter()
-            .map(|m| (m.label.clone(), m))
-            .collect();
-
-    let mut affected = Vec::with_capacity(entries.len());
-    let mut created_count = 0;
-    let mut updated_count = 0;
-
-    let now = chrono::Utc::now();
-
-    for entry in entries {
-        let score = auto_score_for_uploaded_entry(&entry, &auto_scores);
-        let input_body = prepare_test_case_body(entry.input, state.blob_store.clone()).await?;
-        let output_body =
-            prepare_test_case_body(entry.expected_output, state.blob_store.clone()).await?;
-        if let Some(existing) = existing_cases.remove(&entry.label) {
-            match data.strategy {
-                UploadTestCasesMergeStrategy::Abort => {
-                    return Err(AppError::Conflict(format!(
-                        "Test case with label '{}' already exists",
-                        entry.label
-                    )));
-                }
-                UploadTestCasesMergeStrategy::Skip => continue,
-                UploadTestCasesMergeStrategy::Overwrite => {
-                    let mut active: test_case::ActiveModel = existing.into();
-                    active.input = Set(input_body.inline_text);
-                    active.expected_output = Set(output_body.inline_text);
-                    active.input_blob_hash = Set(input_body.blob_hash);
-                    active.expected_output_blob_hash = Set(output_body.blob_hash);
-                    active.input_size = Set(Some(input_body.size));
-                    active.expected_output_size = Set(Some(output_body.size));
-                    active.input_preview = Set(Some(input_body.preview));
-                    active.expected_output_preview = Set(Some(output_body.preview));
-                    active.label = Set(sanitize_db_text(entry.label));
-                    active.score = Set(score);
-                    active.is_sample = Set(entry.is_sample);
-                    let model = active.update(&txn).await?;
-                    affected.push(model);
-                    updated_count += 1;
-                    continue;
-                }
-                UploadTestCasesMergeStrategy::Replace => {
-                    unreachable!();
-                }
-            }
-        }
-
-        let new_tc = test_case::ActiveModel {
-            input: Set(input_body.inline_text),
-            expected_output: Set(output_body.inline_text),
-            input_blob_hash: Set(input_body.blob_hash),
-            expected_output_blob_hash: Set(output_body.blob_hash),
-            input_size: Set(Some(input_body.size)),
-            expected_output_size: Set(Some(output_body.size)),
-            input_preview: Set(Some(input_body.preview)),
-            expected_output_preview: Set(Some(output_body.preview)),
-            score: Set(score),
-            label: Set(sanitize_db_text(entry.label)),
-            description: Set(None),
-            is_sample: Set(entry.is_sample),
-            position: Set(start_pos),
-            problem_id: Set(problem_id),
-            created_at: Set(now),
-            ..Default::default()
-        };
-        let model = new_tc.insert(&txn).await?;
-        affected.push(model);
-        created_count += 1;
-        start_pos = start_pos
-            .checked_add(1)
-            .ok_or_else(|| AppError::Validation("Position overflow".into()))?;
+    let dir = resolve_import_dir(
+        state.config.problem.test_case_import_dir.as_deref(),
+        &payload.path,
+    )?;
+    let input_format = payload.input_format;
+    let output_format = payload.output_format;
+    let entries = tokio::task::spawn_blocking(move || {
+        let mut pairer = TestFilePairer::new(&input_format, &output_format);
+        scan_test_case_dir(&dir, &dir, &mut pairer)?;
+        pairer.finish()
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Directory scan task panicked: {e}")))??;
+    if entries.is_empty() {
+        return Err(AppError::Validation(
+            "Directory contains no valid input/output file pairs matching the specified formats"
+                .into(),
+        ));
     }
 
-    txn.commit().await?;
-
-    let test_cases: Vec<TestCaseListItem> = affected.into_iter().map(tc_to_list_item).collect();
-
-    Ok((
-        StatusCode::CREATED,
-        Json(UploadTestCasesResponse {
-            created: created_count,
-            updated: updated_count,
-            test_cases,
-        }),
-    ))
+    let response = store_uploaded_test_cases(&state, problem_id, entries, payload.strategy).await?;
+    Ok((StatusCode::CREATED, Json(response)))
 }
 
 #[utoipa::path(
@@ -1146,9 +1463,14 @@ fn tc_to_list_item(m: test_case::Model) -> TestCaseListItem {
         description: m.description,
         label: m.label,
         is_sample: m.is_sample,
+        is_pretest: m.is_pretest,
         position: m.position,
         input_preview,
         output_preview,
+        input_size: m.input_size.unwrap_or(m.input.len() as i64),
+        output_size: m
+            .expected_output_size
+            .unwrap_or(m.expected_output.len() as i64),
         problem_id: m.problem_id,
         created_at: m.created_at,
     }
@@ -1180,17 +1502,136 @@ async fn test_case_response_from_model(
         )),
         input,
         expected_output,
+        accepted_outputs: accepted_outputs_from_json(m.accepted_outputs),
         score: m.score,
         description: m.description,
         label: m.label,
         is_sample: m.is_sample,
+        is_pretest: m.is_pretest,
         position: m.position,
         problem_id: m.problem_id,
         created_at: m.created_at,
     })
 }
 
-struct ZipTestEntry {
+/// Writes parsed upload entries to `problem_id` under `strategy`, assigning
+/// default scores, in one transaction.
+async fn store_uploaded_test_cases(
+    state: &AppState,
+    problem_id: i32,
+    entries: Vec<UploadedTestEntry>,
+    strategy: UploadTestCasesMergeStrategy,
+) -> Result<UploadTestCasesResponse, AppError> {
+    let auto_scores = default_uploaded_scores(&entries);
+
+    let txn = state.db.begin().await?;
+    find_problem_for_update(&txn, problem_id).await?;
+
+    let is_replace = matches!(strategy, UploadTestCasesMergeStrategy::Replace);
+    if is_replace {
+        test_case::Entity::delete_many()
+            .filter(test_case::Column::ProblemId.eq(problem_id))
+            .exec(&txn)
+            .await?;
+    }
+
+    let mut start_pos = if is_replace {
+        0
+    } else {
+        next_test_case_position(&txn, problem_id).await?
+    };
+
+    let mut existing_cases: std::collections::HashMap<String, test_case::Model> =
+        test_case::Entity::find()
+            .filter(test_case::Column::ProblemId.eq(problem_id))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|m| (m.label.clone(), m))
+            .collect();
+
+    let mut affected = Vec::with_capacity(entries.len());
+    let mut created_count = 0;
+    let mut updated_count = 0;
+
+    let now = chrono::Utc::now();
+
+    for entry in entries {
+        let score = auto_score_for_uploaded_entry(&entry, &auto_scores);
+        let input_body = prepare_test_case_body(entry.input, state.blob_store.clone()).await?;
+        let output_body =
+            prepare_test_case_body(entry.expected_output, state.blob_store.clone()).await?;
+        if let Some(existing) = existing_cases.remove(&entry.label) {
+            match strategy {
+                UploadTestCasesMergeStrategy::Abort => {
+                    return Err(AppError::Conflict(format!(
+                        "Test case with label '{}' already exists",
+                        entry.label
+                    )));
+                }
+                UploadTestCasesMergeStrategy::Skip => continue,
+                UploadTestCasesMergeStrategy::Overwrite => {
+                    let mut active: test_case::ActiveModel = existing.into();
+                    active.input = Set(input_body.inline_text);
+                    active.expected_output = Set(output_body.inline_text);
+                    active.input_blob_hash = Set(input_body.blob_hash);
+                    active.expected_output_blob_hash = Set(output_body.blob_hash);
+                    active.input_size = Set(Some(input_body.size));
+                    active.expected_output_size = Set(Some(output_body.size));
+                    active.input_preview = Set(Some(input_body.preview));
+                    active.expected_output_preview = Set(Some(output_body.preview));
+                    active.label = Set(sanitize_db_text(entry.label));
+                    active.score = Set(score);
+                    active.is_sample = Set(entry.is_sample);
+                    let model = active.update(&txn).await?;
+                    affected.push(model);
+                    updated_count += 1;
+                    continue;
+                }
+                UploadTestCasesMergeStrategy::Replace => {
+                    unreachable!();
+                }
+            }
+        }
+
+        let new_tc = test_case::ActiveModel {
+            input: Set(input_body.inline_text),
+            expected_output: Set(output_body.inline_text),
+            input_blob_hash: Set(input_body.blob_hash),
+            expected_output_blob_hash: Set(output_body.blob_hash),
+            input_size: Set(Some(input_body.size)),
+            expected_output_size: Set(Some(output_body.size)),
+            input_preview: Set(Some(input_body.preview)),
+            expected_output_preview: Set(Some(output_body.preview)),
+            score: Set(score),
+            label: Set(sanitize_db_text(entry.label)),
+            description: Set(None),
+            is_sample: Set(entry.is_sample),
+            position: Set(start_pos),
+            problem_id: Set(problem_id),
+            created_at: Set(now),
+            ..Default::default()
+        };
+        let model = new_tc.insert(&txn).await?;
+        affected.push(model);
+        created_count += 1;
+        start_pos = start_pos
+            .checked_add(1)
+            .ok_or_else(|| AppError::Validation("Position overflow".into()))?;
+    }
+
+    txn.commit().await?;
+
+    let test_cases: Vec<TestCaseListItem> = affected.into_iter().map(tc_to_list_item).collect();
+
+    Ok(UploadTestCasesResponse {
+        created: created_count,
+        updated: updated_count,
+        test_cases,
+    })
+}
+
+struct UploadedTestEntry {
     label: String,
     input: String,
     expected_output: String,
@@ -1198,7 +1639,7 @@ struct ZipTestEntry {
     sort_key: (u8, String),
 }
 
-fn default_uploaded_scores(entries: &[ZipTestEntry]) -> HashMap<String, i32> {
+fn default_uploaded_scores(entries: &[UploadedTestEntry]) -> HashMap<String, i32> {
     let non_sample_labels: Vec<&str> = entries
         .iter()
         .filter(|entry| !entry.is_sample)
@@ -1226,7 +1667,7 @@ fn default_uploaded_scores(entries: &[ZipTestEntry]) -> HashMap<String, i32> {
     scores
 }
 
-fn auto_score_for_uploaded_entry(entry: &ZipTestEntry, scores: &HashMap<String, i32>) -> i32 {
+fn auto_score_for_uploaded_entry(entry: &UploadedTestEntry, scores: &HashMap<String, i32>) -> i32 {
     if entry.is_sample {
         0
     } else {
@@ -1251,49 +1692,47 @@ fn extract_label<'a>(filename: &'a str, format: &str) -> Option<&'a str> {
     }
 }
 
-fn parse_zip_test_cases(
-    data: &[u8],
-    input_format: &str,
-    output_format: &str,
-) -> Result<Vec<ZipTestEntry>, AppError> {
-    let cursor = std::io::Cursor::new(data);
-    let mut archive = zip::ZipArchive::new(cursor)
-        .map_err(|e| AppError::Validation(format!("Invalid ZIP archive: {e}")))?;
-
-    let mut in_files: BTreeMap<String, (String, bool)> = BTreeMap::new();
-    let mut ans_files: BTreeMap<String, String> = BTreeMap::new();
-    let mut total_decompressed: u64 = 0;
-
-    for i in 0..archive.len() {
-        let file = archive
-            .by_index(i)
-            .map_err(|e| AppError::Validation(format!("ZIP read error: {e}")))?;
+/// Pairs input and answer files by the label their names match in the
+/// upload formats. Shared by the ZIP upload and the directory import.
+struct TestFilePairer<'a> {
+    input_format: &'a str,
+    output_format: &'a str,
+    in_files: BTreeMap<String, (String, bool)>,
+    ans_files: BTreeMap<String, String>,
+    total_size: u64,
+}
 
-        if file.is_dir() {
-            continue;
+impl<'a> TestFilePairer<'a> {
+    fn new(input_format: &'a str, output_format: &'a str) -> Self {
+        Self {
+            input_format,
+            output_format,
+            in_files: BTreeMap::new(),
+            ans_files: BTreeMap::new(),
+            total_size: 0,
         }
+    }
 
-        let name = match file.enclosed_name() {
-            Some(path) => path.to_string_lossy().to_string(),
-            None => continue,
-        };
-
-        let (dir, filename) = split_dir_filename(&name);
-
-        if filename.starts_with('.') {
-            continue;
-        }
+    /// Whether `name`, a `/`-separated relative path, is a non-hidden file
+    /// matching either format.
+    fn matches(&self, name: &str) -> bool {
+        let (_, filename) = split_dir_filename(name);
+        !filename.starts_with('.')
+            && (extract_label(filename, self.input_format).is_some()
+                || extract_label(filename, self.output_format).is_some())
+    }
 
+    /// Reads a file accepted by [`Self::matches`], enforcing the per-file
+    /// and total size limits.
+    fn add(&mut self, name: &str, reader: impl Read) -> Result<(), AppError> {
+        let (dir, filename) = split_dir_filename(name);
         let is_sample = is_sample_directory(dir);
-        let label_as_input = extract_label(filename, input_format);
-        let label_as_output = extract_label(filename, output_format);
-
-        if label_as_input.is_none() && label_as_output.is_none() {
-            continue;
-        }
+        let label_as_input = extract_label(filename, self.input_format);
+        let label_as_output = extract_label(filename, self.output_format);
 
         let mut buf = Vec::new();
-        file.take(MAX_DECOMPRESSED_FILE_SIZE + 1)
+        reader
+            .take(MAX_DECOMPRESSED_FILE_SIZE + 1)
             .read_to_end(&mut buf)
             .map_err(|e| AppError::Validation(format!("Failed to read '{name}': {e}")))?;
 
@@ -1303,10 +1742,10 @@ fn parse_zip_test_cases(
             )));
         }
 
-        total_decompressed += buf.len() as u64;
-        if total_decompressed > MAX_TOTAL_DECOMPRESSED_SIZE {
+        self.total_size += buf.len() as u64;
+        if self.total_size > MAX_TOTAL_DECOMPRESSED_SIZE {
             return Err(AppError::Validation(format!(
-                "Total decompressed ZIP content exceeds {BULK_TEST_CASE_MAX_TOTAL_DECOMPRESSED_MIB}MB limit"
+                "Total test case content exceeds {BULK_TEST_CASE_MAX_TOTAL_DECOMPRESSED_MIB}MB limit"
             )));
         }
 
@@ -1317,68 +1756,177 @@ fn parse_zip_test_cases(
         if let Some(label) = label_as_input {
             let key = sanitize_db_text(label);
             validate_label(&key)?;
-            if in_files.contains_key(&key) {
+            if self.in_files.contains_key(&key) {
                 return Err(AppError::Validation(format!(
                     "Duplicate input file for test case label '{key}'"
                 )));
             }
-            in_files.insert(key, (content, is_sample));
-            continue;
+            self.in_files.insert(key, (content, is_sample));
+            return Ok(());
         }
 
         if let Some(label) = label_as_output {
             let key = sanitize_db_text(label);
             validate_label(&key)?;
-            if ans_files.contains_key(&key) {
+            if self.ans_files.contains_key(&key) {
                 return Err(AppError::Validation(format!(
                     "Duplicate output file for test case label '{key}'"
                 )));
             }
-            ans_files.insert(key, content);
+            self.ans_files.insert(key, content);
         }
+        Ok(())
     }
 
-    let mut unmatched_in: Vec<String> = Vec::new();
-    let mut entries: Vec<ZipTestEntry> = Vec::new();
-
-    for (key, (input, is_sample)) in in_files {
-        if let Some(output) = ans_files.remove(&key) {
-            let sort_priority = if is_sample { 0u8 } else { 1u8 };
-            let sort_key = (sort_priority, key.clone());
-            entries.push(ZipTestEntry {
-                label: key,
-                input,
-                expected_output: output,
-                is_sample,
-                sort_key,
-            });
-        } else {
-            unmatched_in.push(key);
+    /// Pairs the collected files, failing on any file left without a match.
+    fn finish(mut self) -> Result<Vec<UploadedTestEntry>, AppError> {
+        let mut unmatched_in: Vec<String> = Vec::new();
+        let mut entries: Vec<UploadedTestEntry> = Vec::new();
+
+        for (key, (input, is_sample)) in self.in_files {
+            if let Some(output) = self.ans_files.remove(&key) {
+                let sort_priority = if is_sample { 0u8 } else { 1u8 };
+                let sort_key = (sort_priority, key.clone());
+                entries.push(UploadedTestEntry {
+                    label: key,
+                    input,
+                    expected_output: output,
+                    is_sample,
+                    sort_key,
+                });
+            } else {
+                unmatched_in.push(key);
+            }
         }
+
+        let unmatched_ans: Vec<String> = self.ans_files.keys().cloned().collect();
+
+        if !unmatched_in.is_empty() || !unmatched_ans.is_empty() {
+            let mut parts = Vec::new();
+            if !unmatched_in.is_empty() {
+                parts.push(format!(
+                    "Input files without matching output: {}",
+                    unmatched_in.join(", ")
+                ));
+            }
+            if !unmatched_ans.is_empty() {
+                parts.push(format!(
+                    "Output files without matching input: {}",
+                    unmatched_ans.join(", ")
+                ));
+            }
+            return Err(AppError::Validation(parts.join("; ")));
+        }
+
+        entries.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+
+        Ok(entries)
     }
+}
 
-    let unmatched_ans: Vec<String> = ans_files.keys().cloned().collect();
+fn parse_zip_test_cases(
+    data: &[u8],
+    input_format: &str,
+    output_format: &str,
+) -> Result<Vec<UploadedTestEntry>, AppError> {
+    let cursor = std::io::Cursor::new(data);
+    let mut archive = zip::ZipArchive::new(cursor)
+        .map_err(|e| AppError::Validation(format!("Invalid ZIP archive: {e}")))?;
 
-    if !unmatched_in.is_empty() || !unmatched_ans.is_empty() {
-        let mut parts = Vec::new();
-        if !unmatched_in.is_empty() {
-            parts.push(format!(
-                "Input files without matching output: {}",
-                unmatched_in.join(", ")
-            ));
+    let mut pairer = TestFilePairer::new(input_format, output_format);
+
+    for i in 0..archive.len() {
+        let file = archive
+            .by_index(i)
+            .map_err(|e| AppError::Validation(format!("ZIP read error: {e}")))?;
+
+        if file.is_dir() {
+            continue;
         }
-        if !unmatched_ans.is_empty() {
-            parts.push(format!(
-                "Output files without matching input: {}",
-                unmatched_ans.join(", ")
-            ));
+
+        let name = match file.enclosed_name() {
+            Some(path) => path.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        if pairer.matches(&name) {
+            pairer.add(&name, file)?;
         }
-        return Err(AppError::Validation(parts.join("; ")));
     }
 
-    entries.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+    pairer.finish()
+}
 
-    Ok(entries)
+/// Resolves `requested` against the configured import directory, rejecting
+/// anything that does not canonicalize to a directory inside it. Symlinks
+/// are resolved first, so they cannot be used to escape the base.
+fn resolve_import_dir(base: Option<&Path>, requested: &str) -> Result<PathBuf, AppError> {
+    let base = base.ok_or_else(|| {
+        AppError::Validation("Test case directory import is not configured".into())
+    })?;
+    let base = base.canonicalize().map_err(|e| {
+        AppError::Internal(format!(
+            "Test case import directory '{}' is unavailable: {e}",
+            base.display()
+        ))
+    })?;
+
+    let outside = || {
+        AppError::Validation(
+            "Path must be an existing directory inside the test case import directory".into(),
+        )
+    };
+    let target = base
+        .join(requested.trim())
+        .canonicalize()
+        .map_err(|_| outside())?;
+    if !target.starts_with(&base) || !target.is_dir() {
+        return Err(outside());
+    }
+    Ok(target)
+}
+
+/// Feeds every matching file under `dir` into `pairer`, named by its
+/// `/`-separated path relative to `root` so `sample/` directories are
+/// detected as in a ZIP. Hidden entries and symlinks are skipped.
+fn scan_test_case_dir(
+    root: &Path,
+    dir: &Path,
+    pairer: &mut TestFilePairer<'_>,
+) -> Result<(), AppError> {
+    let read_err = |path: &Path, e: std::io::Error| {
+        AppError::Validation(format!("Failed to read '{}': {e}", path.display()))
+    };
+
+    for entry in std::fs::read_dir(dir).map_err(|e| read_err(dir, e))? {
+        let entry = entry.map_err(|e| read_err(dir, e))?;
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        let file_type = entry.file_type().map_err(|e| read_err(&path, e))?;
+        if file_type.is_dir() {
+            scan_test_case_dir(root, &path, pairer)?;
+            continue;
+        }
+        if !file_type.is_file() {
+            continue;
+        }
+
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        let name = relative
+            .iter()
+            .map(|part| part.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if pairer.matches(&name) {
+            let file = std::fs::File::open(&path).map_err(|e| read_err(&path, e))?;
+            pairer.add(&name, file)?;
+        }
+    }
+    Ok(())
 }
 
 #[utoipa::path(
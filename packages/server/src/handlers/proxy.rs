@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use axum::http::header::AUTHORIZATION;
 use axum::{
@@ -7,17 +7,22 @@ use axum::{
     http::{HeaderMap, Method, Response},
     response::IntoResponse,
 };
+use opentelemetry::KeyValue;
 use plugin_core::http::{PluginHttpAuth, PluginHttpRequest, PluginHttpResponse};
 use plugin_core::traits::PluginManagerExt;
 use tracing::{info, instrument, warn};
 
 use crate::error::{AppError, ErrorBody};
-use crate::extractors::auth::AuthUser;
+use crate::extractors::auth::{AuthUser, ensure_active_user};
 use crate::extractors::path::AppPath;
 use crate::state::AppState;
 use crate::utils::jwt;
 
-fn resolve_optional_auth_user(
+/// Response header reporting whether a standings route was served from the
+/// standings cache (`hit`) or computed by the plugin (`miss`).
+pub const STANDINGS_CACHE_HEADER: &str = "x-broccoli-cache";
+
+async fn resolve_optional_auth_user(
     state: &AppState,
     headers: &HeaderMap,
 ) -> Result<Option<AuthUser>, AppError> {
@@ -38,12 +43,18 @@ fn resolve_optional_auth_user(
         Ok(c) => c,
         Err(_) => return Ok(None),
     };
+    match ensure_active_user(&state.db, &claims).await {
+        Ok(()) => {}
+        Err(AppError::TokenInvalid) => return Ok(None),
+        Err(e) => return Err(e),
+    }
 
     Ok(Some(AuthUser {
         user_id: claims.uid,
         username: claims.sub,
         roles: claims.roles,
         permissions: claims.permissions,
+        token_version: claims.ver,
     }))
 }
 
@@ -68,9 +79,9 @@ async fn handle_plugin_request_impl(
         plugin_id, normalized_path
     );
 
-    let auth_user = resolve_optional_auth_user(&state, &headers)?;
+    let auth_user = resolve_optional_auth_user(&state, &headers).await?;
 
-    let (handler_name, required_permission, params) = {
+    let (handler_name, required_permission, is_standings, params, function_schema) = {
         let registry = state
             .plugins
             .get_registry()
@@ -96,14 +107,23 @@ async fn handle_plugin_request_impl(
                 AppError::MethodNotAllowed
             })?;
 
+        let function_schema = entry
+            .manifest
+            .server
+            .as_ref()
+            .and_then(|server| server.functions.get(&route_info.handler))
+            .cloned();
+
         (
             route_info.handler.clone(),
             route_info.permission.clone(),
+            route_info.standings,
             matched_route
                 .params
                 .iter()
                 .map(|(k, v)| (k.to_string(), v.to_string()))
-                .collect(),
+                .collect::<HashMap<String, String>>(),
+            function_schema,
         )
     };
 
@@ -119,6 +139,43 @@ async fn handle_plugin_request_impl(
         user.require_permission(permission)?;
     }
 
+    let body: Option<serde_json::Value> = serde_json::from_str(&body).ok();
+
+    if let Some(schema) = function_schema.as_ref().and_then(|f| f.input.as_ref()) {
+        schema
+            .validate(body.as_ref().unwrap_or(&serde_json::Value::Null))
+            .map_err(|e| {
+                warn!(
+                    "Request body rejected by input schema of '{}'",
+                    handler_name
+                );
+                AppError::Validation(format!("Request body does not match schema: {}", e))
+            })?;
+    }
+
+    let standings_slot = if is_standings && method == Method::GET {
+        standings_cache_slot(
+            &state,
+            &plugin_id,
+            &normalized_path,
+            &params,
+            &query,
+            auth_user.as_ref(),
+        )
+    } else {
+        None
+    };
+
+    if let Some((contest_id, ref key)) = standings_slot
+        && let Some(cached) = state.standings_cache.get(contest_id, key)
+    {
+        state
+            .metrics
+            .standings_cache_requests
+            .add(1, &[KeyValue::new("result", "hit")]);
+        return build_plugin_response(cached, Some("hit"));
+    }
+
     let request = PluginHttpRequest {
         method: method.to_string(),
         path: normalized_path,
@@ -128,7 +185,7 @@ async fn handle_plugin_request_impl(
             .iter()
             .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or_default().to_string()))
             .collect(),
-        body: serde_json::from_str(&body).ok(),
+        body,
         auth: auth_user.map(|user| PluginHttpAuth {
             user_id: user.user_id,
             username: user.username,
@@ -144,8 +201,66 @@ async fn handle_plugin_request_impl(
         .call(&plugin_id, &handler_name, request)
         .await?;
 
+    if let Some(schema) = function_schema.as_ref().and_then(|f| f.output.as_ref()) {
+        schema
+            .validate(response.body.as_ref().unwrap_or(&serde_json::Value::Null))
+            .map_err(|e| {
+                AppError::Internal(format!(
+                    "Plugin '{}' handler '{}' returned a body violating its output schema: {}",
+                    plugin_id, handler_name, e
+                ))
+            })?;
+    }
+
+    let cache_status = standings_slot.map(|(contest_id, key)| {
+        state
+            .metrics
+            .standings_cache_requests
+            .add(1, &[KeyValue::new("result", "miss")]);
+        if (200..300).contains(&response.status) {
+            state
+                .standings_cache
+                .insert(contest_id, key, response.clone());
+        }
+        "miss"
+    });
+
+    build_plugin_response(response, cache_status)
+}
+
+/// Returns the contest and cache key for a standings request, or `None` when
+/// the cache is disabled or the route has no numeric `{contest_id}`.
+fn standings_cache_slot(
+    state: &AppState,
+    plugin_id: &str,
+    path: &str,
+    params: &HashMap<String, String>,
+    query: &HashMap<String, String>,
+    auth_user: Option<&AuthUser>,
+) -> Option<(i32, String)> {
+    if !state.standings_cache.is_enabled() {
+        return None;
+    }
+    let contest_id = params.get("contest_id")?.parse::<i32>().ok()?;
+    let query: BTreeMap<_, _> = query.iter().collect();
+    let viewer = auth_user.map_or_else(|| "anonymous".to_string(), |u| u.user_id.to_string());
+    let key = format!(
+        "{plugin_id}|{path}|{}|{viewer}",
+        serde_json::to_string(&query).unwrap_or_default()
+    );
+    Some((contest_id, key))
+}
+
+fn build_plugin_response(
+    response: PluginHttpResponse,
+    cache_status: Option<&str>,
+) -> Result<Response<Body>, AppError> {
     let mut builder = Response::builder().status(response.status);
 
+    if let Some(status) = cache_status {
+        builder = builder.header(STANDINGS_CACHE_HEADER, status);
+    }
+
     if let Some(h) = response.headers {
         for (k, v) in h {
             builder = builder.header(k, v);
@@ -178,10 +293,12 @@ macro_rules! proxy_handler {
             request_body = $body_type,
             responses(
                 (status = 200, description = "Success", body = serde_json::Value),
+                (status = 400, description = "Request body violates the handler's declared input schema (VALIDATION_ERROR)", body = ErrorBody),
                 (status = 401, description = "Unauthorized", body = ErrorBody),
                 (status = 403, description = "Forbidden", body = ErrorBody),
                 (status = 404, description = "Plugin or Route not found", body = ErrorBody),
                 (status = 405, description = "Method Not Allowed", body = ErrorBody),
+                (status = 504, description = "Plugin handler exceeded the configured execution timeout (PLUGIN_TIMEOUT)", body = ErrorBody),
             ),
             security(("jwt" = []))
         )]
@@ -212,10 +329,12 @@ macro_rules! proxy_handler {
             ),
             responses(
                 (status = 200, description = "Success", body = serde_json::Value),
+                (status = 400, description = "Request body violates the handler's declared input schema (VALIDATION_ERROR)", body = ErrorBody),
                 (status = 401, description = "Unauthorized", body = ErrorBody),
                 (status = 403, description = "Forbidden", body = ErrorBody),
                 (status = 404, description = "Plugin or Route not found", body = ErrorBody),
                 (status = 405, description = "Method Not Allowed", body = ErrorBody),
+                (status = 504, description = "Plugin handler exceeded the configured execution timeout (PLUGIN_TIMEOUT)", body = ErrorBody),
             ),
             security(("jwt" = []))
         )]
@@ -0,0 +1,133 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use chrono::Utc;
+use sea_orm::*;
+use tracing::instrument;
+
+use crate::entity::saved_view;
+use crate::error::{AppError, ErrorBody};
+use crate::extractors::auth::AuthUser;
+use crate::extractors::json::AppJson;
+use crate::extractors::path::AppPath;
+use crate::models::saved_view::*;
+use crate::state::AppState;
+use crate::utils::text::{sanitize_db_json, sanitize_db_text};
+
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = "Saved Views",
+    operation_id = "createSavedView",
+    summary = "Save a list view",
+    description = "Stores named query parameters for `GET /submissions` or `GET /problems`. Pass the name as `?view=` to a list endpoint to apply them; parameters given explicitly take precedence. Names are unique per user and resource.",
+    request_body = CreateSavedViewRequest,
+    responses(
+        (status = 201, description = "View saved", body = SavedViewResponse),
+        (status = 400, description = "Validation error (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 409, description = "A view with this name exists (CONFLICT)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, payload), fields(name = %payload.name))]
+pub async fn create_saved_view(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppJson(payload): AppJson<CreateSavedViewRequest>,
+) -> Result<(StatusCode, Json<SavedViewResponse>), AppError> {
+    validate_create_saved_view(&payload)?;
+    let name = sanitize_db_text(payload.name.trim());
+
+    let existing = saved_view::Entity::find()
+        .filter(saved_view::Column::UserId.eq(auth_user.user_id))
+        .filter(saved_view::Column::Resource.eq(payload.resource.as_str()))
+        .filter(saved_view::Column::Name.eq(&name))
+        .one(&state.db)
+        .await?;
+    if existing.is_some() {
+        return Err(AppError::Conflict(format!(
+            "A {} view named '{name}' already exists",
+            payload.resource.as_str()
+        )));
+    }
+
+    let view = saved_view::ActiveModel {
+        user_id: Set(auth_user.user_id),
+        resource: Set(payload.resource.as_str().to_string()),
+        name: Set(name),
+        params: Set(sanitize_db_json(payload.params)),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(SavedViewResponse::from(view))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "Saved Views",
+    operation_id = "listSavedViews",
+    summary = "List saved views",
+    description = "Returns the caller's saved views, optionally only those for one resource.",
+    params(SavedViewListQuery),
+    responses(
+        (status = 200, description = "Saved views", body = Vec<SavedViewResponse>),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, query))]
+pub async fn list_saved_views(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<SavedViewListQuery>,
+) -> Result<Json<Vec<SavedViewResponse>>, AppError> {
+    let mut select = saved_view::Entity::find()
+        .filter(saved_view::Column::UserId.eq(auth_user.user_id))
+        .order_by_asc(saved_view::Column::Resource)
+        .order_by_asc(saved_view::Column::Name);
+    if let Some(resource) = query.resource {
+        select = select.filter(saved_view::Column::Resource.eq(resource.as_str()));
+    }
+    let views = select.all(&state.db).await?;
+    Ok(Json(
+        views.into_iter().map(SavedViewResponse::from).collect(),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = "Saved Views",
+    operation_id = "deleteSavedView",
+    summary = "Delete a saved view",
+    description = "Deletes one of the caller's saved views.",
+    params(("id" = i32, Path, description = "Saved view ID")),
+    responses(
+        (status = 204, description = "View deleted"),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 404, description = "Saved view not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id))]
+pub async fn delete_saved_view(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let result = saved_view::Entity::delete_many()
+        .filter(saved_view::Column::Id.eq(id))
+        .filter(saved_view::Column::UserId.eq(auth_user.user_id))
+        .exec(&state.db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("Saved view not found".into()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
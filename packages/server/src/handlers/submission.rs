@@ -2,45 +2,59 @@ use std::cmp;
 use std::collections::HashMap;
 
 use axum::Json;
-use axum::body::Bytes;
+use axum::body::{Body, Bytes};
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::http::{StatusCode, header};
 use axum::response::IntoResponse;
 use broccoli_server_sdk::types::{AfterJudgingEvent, AfterSubmissionEvent, BeforeSubmissionEvent};
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use common::SubmissionStatus;
 use common::storage::BlobStore;
 use common::submission_dispatch::TestCaseBodyRef;
 use sea_orm::prelude::Expr;
-use sea_orm::sea_query::LockType;
+use sea_orm::sea_query::{LockType, Query as SeaQuery};
 use sea_orm::*;
 use serde::Deserialize;
 use tracing::{error, info, instrument, warn};
 
 use plugin_core::traits::PluginManagerExt;
 
+use crate::config::{NoTestCasesPolicy, WebhookConfig};
+use crate::entity::problem::TestDetailsVisibility;
 use crate::entity::{
-    contest, problem, submission, submission_judgement, test_case, test_case_result, user,
+    archived_submission, contest, contest_problem, problem, rejudge_batch, submission,
+    submission_comment, submission_judgement, test_case, test_case_result, user,
 };
 use crate::error::{AppError, ErrorBody};
 use crate::extractors::auth::AuthUser;
 use crate::extractors::json::AppJson;
 use crate::extractors::path::AppPath;
 use crate::hooks::{self, HookOutcome};
+use crate::models::saved_view::SavedViewResource;
 use crate::models::shared::{Pagination, escape_like};
 use crate::models::submission::*;
 use crate::state::AppState;
 use crate::utils::contest::{
-    find_contest, is_contest_participant, is_problem_in_contest, require_contest_participant,
-    require_contest_running,
+    find_contest, is_contest_participant, is_open_for_practice, is_problem_in_contest,
+    require_contest_participant, require_contest_running,
 };
+use crate::utils::duplicate::check_duplicate_submission;
 use crate::utils::judging::{
-    files_from_json, files_to_json, validate_code_payload, validate_submission_contract,
+    aggregate_test_case_results, cap_stored_checker_output, files_from_json, files_to_json,
+    judge_mode_of, judges_pretests_only, resolve_submission_language, score_percentage,
+    select_judged_test_cases, source_metrics, subtasks_from_json, validate_code_payload,
+    validate_contest_language, validate_file_encoding, validate_language_problem_type,
+    validate_submission_contract,
 };
-use crate::utils::problem::find_problem;
+use crate::utils::problem::{find_problem, problem_has_test_cases, problem_max_score};
 use crate::utils::query::validate_sorting_params;
-use crate::utils::rate_limit::check_rate_limit;
+use crate::utils::rate_limit::{check_in_flight_limit, check_rate_limit, check_resubmit_cooldown};
+use crate::utils::rejudge_batch::{open_rejudge_batch, settle_rejudge_batch};
+use crate::utils::saved_view::load_saved_view;
 use crate::utils::test_case_body::read_test_case_body;
+use crate::utils::text::{sanitize_db_text, truncate_text};
+use crate::utils::worker::publish_judge_epoch;
+use crate::webhook;
 async fn dispatch_before_submission_hooks(
     state: &AppState,
     event: &BeforeSubmissionEvent,
@@ -92,6 +106,7 @@ fn fire_after_submission_hooks(
 async fn fire_after_judging_hooks(
     db: &DatabaseConnection,
     hook_registry: hooks::SharedHookRegistry,
+    webhooks: &WebhookConfig,
     submission_id: i32,
     user_id: i32,
     problem_id: i32,
@@ -113,6 +128,15 @@ async fn fire_after_judging_hooks(
         return;
     }
 
+    if !webhooks.urls.is_empty() {
+        let db = db.clone();
+        let webhooks = webhooks.clone();
+        let payload = webhook::JudgeResultPayload::from(&sub);
+        tokio::spawn(async move {
+            webhook::deliver_judge_result(&db, &webhooks, &payload).await;
+        });
+    }
+
     let verdict = sub
         .verdict
         .map(|v| v.to_string())
@@ -181,6 +205,46 @@ async fn open_rejudge_judgement(
     let next_version = max_version.unwrap_or(0).saturating_add(1);
 
     if apply_immediately {
+        // A current judgement still in flight belongs to the epoch this
+        // rejudge supersedes; its late results are discarded by the
+        // epoch-guarded writes, so close it out now instead of leaving it
+        // pending forever.
+        let in_flight: Vec<i32> = submission_judgement::Entity::find()
+            .select_only()
+            .column(submission_judgement::Column::Id)
+            .filter(submission_judgement::Column::SubmissionId.eq(sub.id))
+            .filter(submission_judgement::Column::IsCurrent.eq(true))
+            .filter(submission_judgement::Column::IsFinalized.eq(false))
+            .into_tuple()
+            .all(txn)
+            .await?;
+        if !in_flight.is_empty() {
+            submission_judgement::Entity::update_many()
+                .col_expr(
+                    submission_judgement::Column::Status,
+                    Expr::value(SubmissionStatus::SystemError),
+                )
+                .col_expr(
+                    submission_judgement::Column::ErrorCode,
+                    Expr::value(SUPERSEDED_CODE),
+                )
+                .col_expr(
+                    submission_judgement::Column::ErrorMessage,
+                    Expr::value(SUPERSEDED_MESSAGE),
+                )
+                .col_expr(submission_judgement::Column::IsFinalized, Expr::value(true))
+                .col_expr(
+                    submission_judgement::Column::FinalizedAt,
+                    Expr::cust("NOW()"),
+                )
+                .filter(submission_judgement::Column::Id.is_in(in_flight.clone()))
+                .exec(txn)
+                .await?;
+            for judgement_id in in_flight {
+                settle_rejudge_batch(txn, judgement_id).await?;
+            }
+        }
+
         // Demote any judgement currently flagged as current. There should be
         // at most one row matching this filter (enforced by the partial
         // unique index `idx_submission_judgement_one_current`).
@@ -300,26 +364,127 @@ async fn mark_submission_dispatch_system_error(
     judge_epoch: i32,
 ) -> anyhow::Result<()> {
     if judgement_id > 0 {
-        let active = submission_judgement::ActiveModel {
-            id: Set(judgement_id),
-            status: Set(SubmissionStatus::SystemError),
-            error_code: Set(Some(error_code.to_string())),
-            error_message: Set(Some(error_message.to_string())),
-            is_finalized: Set(true),
-            finalized_at: Set(Some(Utc::now())),
-            ..Default::default()
-        };
-        active.update(db).await?;
+        submission_judgement::Entity::update_many()
+            .col_expr(
+                submission_judgement::Column::Status,
+                Expr::value(SubmissionStatus::SystemError),
+            )
+            .col_expr(
+                submission_judgement::Column::ErrorCode,
+                Expr::value(error_code.to_string()),
+            )
+            .col_expr(
+                submission_judgement::Column::ErrorMessage,
+                Expr::value(error_message.to_string()),
+            )
+            .col_expr(submission_judgement::Column::IsFinalized, Expr::value(true))
+            .col_expr(
+                submission_judgement::Column::FinalizedAt,
+                Expr::cust("NOW()"),
+            )
+            .col_expr(
+                submission_judgement::Column::JudgingDurationMs,
+                Expr::cust("(EXTRACT(EPOCH FROM (NOW() - created_at)) * 1000)::BIGINT"),
+            )
+            .filter(submission_judgement::Column::Id.eq(judgement_id))
+            .exec(db)
+            .await?;
     }
 
-    crate::consumers::mark_submission_system_error_with_epoch(
+    let marked = crate::consumers::mark_submission_system_error_with_epoch(
         db,
         submission_id,
         error_code,
         error_message,
         Some(judge_epoch),
     )
-    .await
+    .await;
+    settle_rejudge_progress(db, judgement_id).await;
+    marked
+}
+
+async fn settle_rejudge_progress(db: &DatabaseConnection, judgement_id: i32) {
+    if let Err(e) = settle_rejudge_batch(db, judgement_id).await {
+        warn!(judgement_id, error = %e, "Failed to update rejudge batch progress");
+    }
+}
+
+const SUPERSEDED_CODE: &str = "SUPERSEDED";
+const SUPERSEDED_MESSAGE: &str = "Superseded by a newer rejudge";
+
+/// Whether a rejudge bumped the submission's `judge_epoch` past the one
+/// this dispatch carries. Results of a superseded dispatch would be
+/// discarded by the epoch-guarded writes anyway, so there is no point
+/// enqueueing its judge jobs. Fails open on a lookup error.
+async fn is_superseded(db: &DatabaseConnection, sub: &submission::Model) -> bool {
+    let current = submission::Entity::find_by_id(sub.id)
+        .select_only()
+        .column(submission::Column::JudgeEpoch)
+        .into_tuple::<i32>()
+        .one(db)
+        .await;
+    match current {
+        Ok(Some(epoch)) => epoch > sub.judge_epoch,
+        Ok(None) => false,
+        Err(e) => {
+            warn!(error = %e, submission_id = sub.id, "Judge epoch lookup failed, dispatching anyway");
+            false
+        }
+    }
+}
+
+const NO_TEST_CASES_CODE: &str = "NO_TEST_CASES";
+const NO_TEST_CASES_MESSAGE: &str = "Problem has no test cases";
+
+/// Applies `submission.no_test_cases` to a new submission. Returns `true`
+/// when the submission must be stored as a finalized system error instead of
+/// being dispatched.
+async fn check_no_test_cases<C: ConnectionTrait>(
+    db: &C,
+    problem_id: i32,
+    policy: NoTestCasesPolicy,
+) -> Result<bool, AppError> {
+    if problem_has_test_cases(db, problem_id).await? {
+        return Ok(false);
+    }
+    match policy {
+        NoTestCasesPolicy::Reject => Err(AppError::Validation(NO_TEST_CASES_MESSAGE.into())),
+        NoTestCasesPolicy::SystemError => Ok(true),
+    }
+}
+
+/// Inserts a submission that is finalized as `SystemError` on creation,
+/// together with its finalized v1 judgement.
+async fn insert_no_test_cases_submission(
+    txn: &DatabaseTransaction,
+    mut new_submission: submission::ActiveModel,
+    now: chrono::DateTime<Utc>,
+) -> Result<submission::Model, AppError> {
+    new_submission.status = Set(SubmissionStatus::SystemError);
+    new_submission.error_code = Set(Some(NO_TEST_CASES_CODE.into()));
+    new_submission.error_message = Set(Some(NO_TEST_CASES_MESSAGE.into()));
+    new_submission.judged_at = Set(Some(now));
+    let model = new_submission.insert(txn).await?;
+
+    submission_judgement::ActiveModel {
+        submission_id: Set(model.id),
+        version: Set(1),
+        is_current: Set(true),
+        is_finalized: Set(true),
+        triggered_by_user_id: Set(None),
+        status: Set(SubmissionStatus::SystemError),
+        error_code: Set(Some(NO_TEST_CASES_CODE.into())),
+        error_message: Set(Some(NO_TEST_CASES_MESSAGE.into())),
+        judge_epoch: Set(model.judge_epoch),
+        created_at: Set(now),
+        finalized_at: Set(Some(now)),
+        judging_duration_ms: Set(Some(0)),
+        ..Default::default()
+    }
+    .insert(txn)
+    .await?;
+
+    Ok(model)
 }
 
 #[instrument(skip(state), fields(submission_id = submission.id))]
@@ -343,6 +508,33 @@ pub(crate) async fn dispatch_to_plugin_with_judgement(
         ensure_active_judgement_id(&state.db, &submission).await
     };
 
+    if is_superseded(&state.db, &submission).await {
+        info!(
+            submission_id = submission.id,
+            judge_epoch = submission.judge_epoch,
+            "Skipping dispatch superseded by a newer rejudge"
+        );
+        let _ = mark_submission_dispatch_system_error(
+            &state.db,
+            submission.id,
+            judgement_id,
+            SUPERSEDED_CODE,
+            SUPERSEDED_MESSAGE,
+            submission.judge_epoch,
+        )
+        .await;
+        return;
+    }
+
+    // Jobs already queued for an older epoch are dropped by the worker once
+    // it sees the epoch this dispatch applies.
+    if fire_after_judging
+        && submission.judge_epoch > 0
+        && let Some(client) = &state.redis_client
+    {
+        publish_judge_epoch(client, submission.id, submission.judge_epoch).await;
+    }
+
     let contest_type = Some(submission.contest_type.clone());
 
     let handler = {
@@ -421,6 +613,26 @@ pub(crate) async fn dispatch_to_plugin_with_judgement(
         }
     };
 
+    let pretests_only = match submission.contest_id {
+        Some(contest_id) => match contest::Entity::find_by_id(contest_id).one(&state.db).await {
+            Ok(contest) => contest.is_some_and(|c| judges_pretests_only(&c, Utc::now())),
+            Err(e) => {
+                error!(error = %e, "DB error fetching contest");
+                let _ = mark_submission_dispatch_system_error(
+                    &state.db,
+                    submission.id,
+                    judgement_id,
+                    "DATABASE_ERROR",
+                    &format!("Failed to fetch contest: {}", e),
+                    submission.judge_epoch,
+                )
+                .await;
+                return;
+            }
+        },
+        None => false,
+    };
+
     let resolved_test_cases = {
         let db_tcs = match test_case::Entity::find()
             .filter(test_case::Column::ProblemId.eq(submission.problem_id))
@@ -428,6 +640,7 @@ pub(crate) async fn dispatch_to_plugin_with_judgement(
             .column(test_case::Column::Id)
             .column(test_case::Column::Score)
             .column(test_case::Column::IsSample)
+            .column(test_case::Column::IsPretest)
             .column(test_case::Column::Position)
             .column(test_case::Column::Description)
             .column(test_case::Column::Label)
@@ -463,7 +676,7 @@ pub(crate) async fn dispatch_to_plugin_with_judgement(
                 return;
             }
         };
-        db_tcs
+        select_judged_test_cases(db_tcs, pretests_only, |tc| tc.is_pretest)
             .into_iter()
             .map(|tc| common::submission_dispatch::TestCaseRow {
                 id: tc.id,
@@ -490,9 +703,14 @@ pub(crate) async fn dispatch_to_plugin_with_judgement(
         time_limit_ms: problem.time_limit,
         memory_limit_kb: problem.memory_limit,
         problem_type: problem.problem_type.clone(),
-        test_cases: resolved_test_cases,
+        test_cases: if submission.compile_only {
+            vec![]
+        } else {
+            resolved_test_cases
+        },
         judge_epoch: submission.judge_epoch,
         target_worker_id: submission.target_worker_id.clone(),
+        judge_mode: judge_mode_of(submission.compile_only),
     };
 
     let input_bytes = match serde_json::to_vec(&input) {
@@ -516,6 +734,9 @@ pub(crate) async fn dispatch_to_plugin_with_judgement(
     let function_name = handler.submission_fn.clone();
     let plugins = state.plugins.clone();
     let hook_registry = state.registries.hook_registry.clone();
+    let standings_cache = state.standings_cache.clone();
+    let webhooks = state.config.webhook.clone();
+    let max_checker_output_bytes = state.config.submission.max_checker_output_bytes;
     let db = state.db.clone();
     let submission_id = submission.id;
     let judge_epoch = submission.judge_epoch;
@@ -586,10 +807,23 @@ pub(crate) async fn dispatch_to_plugin_with_judgement(
             }
         }
 
+        if let Err(e) =
+            cap_stored_checker_output(&db, submission_id, judgement_id, max_checker_output_bytes)
+                .await
+        {
+            warn!(submission_id, error = %e, "Failed to cap stored checker output");
+        }
+        settle_rejudge_progress(&db, judgement_id).await;
+
+        if let Some(contest_id) = contest_id {
+            standings_cache.invalidate_contest(contest_id);
+        }
+
         if fire_after_judging {
             fire_after_judging_hooks(
                 &db,
                 hook_registry,
+                &webhooks,
                 submission_id,
                 user_id,
                 problem_id,
@@ -626,10 +860,13 @@ async fn build_submission_list_items(
         let problem_model = problems
             .get(&sub.problem_id)
             .ok_or_else(|| AppError::Internal("Problem not found".into()))?;
+        let (source_size, line_count) = source_metrics(&sub.files);
 
         data.push(SubmissionListItem {
             id: sub.id,
             language: sub.language,
+            source_size,
+            line_count,
             status: sub.status,
             verdict: sub.verdict,
             user_id: sub.user_id,
@@ -656,11 +893,152 @@ struct VisibilityContext {
     has_view_all: bool,
 }
 
+/// When the contest problem's `result_delay_secs` still withholds the
+/// result of a submission made at `created_at` from the viewer, the time it
+/// will be shown. Viewers with `submission:view_all` are never delayed.
+async fn results_hidden_until<C: ConnectionTrait>(
+    db: &C,
+    contest_id: Option<i32>,
+    problem_id: i32,
+    created_at: DateTime<Utc>,
+    visibility: Option<&VisibilityContext>,
+) -> Result<Option<DateTime<Utc>>, AppError> {
+    let Some(contest_id) = contest_id else {
+        return Ok(None);
+    };
+    if visibility.is_some_and(|ctx| ctx.has_view_all) {
+        return Ok(None);
+    }
+    let delay_secs = contest_problem::Entity::find_by_id((contest_id, problem_id))
+        .select_only()
+        .column(contest_problem::Column::ResultDelaySecs)
+        .into_tuple::<i32>()
+        .one(db)
+        .await?
+        .unwrap_or(0);
+    Ok(result_release_time(created_at, delay_secs).filter(|at| Utc::now() < *at))
+}
+
+fn result_release_time(created_at: DateTime<Utc>, delay_secs: i32) -> Option<DateTime<Utc>> {
+    (delay_secs > 0).then(|| created_at + Duration::seconds(i64::from(delay_secs)))
+}
+
+/// Status reported while results are withheld: finished submissions all
+/// read as `Judged`, so a compilation or system error isn't revealed early.
+fn withheld_status(status: SubmissionStatus) -> SubmissionStatus {
+    if status.is_terminal() {
+        SubmissionStatus::Judged
+    } else {
+        status
+    }
+}
+
+/// SQL predicate on `submission` rows whose results their contest problem's
+/// `result_delay_secs` still withholds.
+const RESULTS_WITHHELD_SQL: &str = "EXISTS (SELECT 1 FROM \"contest_problem\" cp \
+     WHERE cp.\"contest_id\" = \"submission\".\"contest_id\" \
+     AND cp.\"problem_id\" = \"submission\".\"problem_id\" \
+     AND cp.\"result_delay_secs\" > 0 \
+     AND \"submission\".\"created_at\" + make_interval(secs => cp.\"result_delay_secs\") > NOW())";
+
+/// Filters on the status a viewer without `submission:view_all` is shown,
+/// i.e. after [`withheld_status`] is applied to delayed rows.
+fn filter_visible_status<E: EntityTrait>(
+    select: Select<E>,
+    status: SubmissionStatus,
+    has_view_all: bool,
+) -> Select<E> {
+    if has_view_all || !status.is_terminal() {
+        return select.filter(submission::Column::Status.eq(status));
+    }
+    if status == SubmissionStatus::Judged {
+        select.filter(
+            Condition::any()
+                .add(submission::Column::Status.eq(SubmissionStatus::Judged))
+                .add(
+                    Condition::all()
+                        .add(submission::Column::Status.is_in([
+                            SubmissionStatus::CompilationError,
+                            SubmissionStatus::SystemError,
+                        ]))
+                        .add(Expr::cust(RESULTS_WITHHELD_SQL)),
+                ),
+        )
+    } else {
+        select
+            .filter(submission::Column::Status.eq(status))
+            .filter(Expr::cust(RESULTS_WITHHELD_SQL).not())
+    }
+}
+
+/// Orders by the status a viewer is shown (see [`filter_visible_status`]).
+fn order_by_visible_status<E: EntityTrait>(
+    select: SelectTwo<E, user::Entity>,
+    order: Order,
+    has_view_all: bool,
+) -> SelectTwo<E, user::Entity> {
+    if has_view_all {
+        return select.order_by(submission::Column::Status, order);
+    }
+    select.order_by(
+        Expr::cust(format!(
+            "CASE WHEN \"submission\".\"status\" IN ('CompilationError', 'SystemError') \
+             AND {RESULTS_WITHHELD_SQL} THEN 'Judged' ELSE \"submission\".\"status\" END"
+        )),
+        order,
+    )
+}
+
+/// Clears the verdict, score and usage of list items whose results are still
+/// delayed for the viewer (see [`results_hidden_until`]).
+async fn hide_delayed_list_results<C: ConnectionTrait>(
+    db: &C,
+    items: &mut [SubmissionListItem],
+    visibility: Option<&VisibilityContext>,
+) -> Result<(), AppError> {
+    if visibility.is_some_and(|ctx| ctx.has_view_all) {
+        return Ok(());
+    }
+    let contest_ids: Vec<i32> = items.iter().filter_map(|item| item.contest_id).collect();
+    if contest_ids.is_empty() {
+        return Ok(());
+    }
+    let delays: HashMap<(i32, i32), i32> = contest_problem::Entity::find()
+        .select_only()
+        .column(contest_problem::Column::ContestId)
+        .column(contest_problem::Column::ProblemId)
+        .column(contest_problem::Column::ResultDelaySecs)
+        .filter(contest_problem::Column::ContestId.is_in(contest_ids))
+        .filter(contest_problem::Column::ResultDelaySecs.gt(0))
+        .into_tuple::<(i32, i32, i32)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(contest_id, problem_id, delay)| ((contest_id, problem_id), delay))
+        .collect();
+
+    let now = Utc::now();
+    for item in items {
+        let delay = item
+            .contest_id
+            .and_then(|contest_id| delays.get(&(contest_id, item.problem_id)));
+        if let Some(&delay) = delay
+            && result_release_time(item.created_at, delay).is_some_and(|at| now < at)
+        {
+            item.status = withheld_status(item.status.clone());
+            item.verdict = None;
+            item.score = None;
+            item.time_used = None;
+            item.memory_used = None;
+        }
+    }
+    Ok(())
+}
+
 #[derive(FromQueryResult)]
 struct TestCaseMeta {
     id: i32,
     is_sample: bool,
-    position: i32,
 }
 
 #[derive(FromQueryResult)]
@@ -677,6 +1055,7 @@ struct SubmissionDispatchTestCaseRow {
     id: i32,
     score: i32,
     is_sample: bool,
+    is_pretest: bool,
     position: i32,
     description: Option<String>,
     label: String,
@@ -747,6 +1126,7 @@ async fn build_submission_response(
     blob_store: &dyn BlobStore,
     sub: submission::Model,
     visibility: Option<VisibilityContext>,
+    max_checker_output_bytes: usize,
 ) -> Result<SubmissionResponse, AppError> {
     let user_model = user::Entity::find_by_id(sub.user_id)
         .one(db)
@@ -786,18 +1166,36 @@ async fn build_submission_response(
             .as_ref()
             .is_some_and(|c| c.show_compile_output);
 
+    let hidden_until = results_hidden_until(
+        db,
+        sub.contest_id,
+        sub.problem_id,
+        sub.created_at,
+        visibility.as_ref(),
+    )
+    .await?;
     let is_running = sub.status == SubmissionStatus::Running;
-    let show_results = sub.status.is_terminal() || is_running;
+    let show_results = (sub.status.is_terminal() || is_running) && hidden_until.is_none();
+
+    let current_judgement = submission_judgement::Entity::find()
+        .filter(submission_judgement::Column::SubmissionId.eq(sub.id))
+        .filter(submission_judgement::Column::IsCurrent.eq(true))
+        .one(db)
+        .await?;
 
     let result_response = if show_results {
-        let current_judgement_id = submission_judgement::Entity::find()
-            .filter(submission_judgement::Column::SubmissionId.eq(sub.id))
-            .filter(submission_judgement::Column::IsCurrent.eq(true))
-            .one(db)
-            .await?
-            .map(|j| j.id);
+        let current_judgement_id = current_judgement.as_ref().map(|j| j.id);
+        // Results follow the test set's display order; rows whose test case
+        // was deleted sort last.
         let mut results_query = test_case_result::Entity::find()
-            .filter(test_case_result::Column::SubmissionId.eq(sub.id));
+            .filter(test_case_result::Column::SubmissionId.eq(sub.id))
+            .left_join(test_case::Entity)
+            .order_by_with_nulls(
+                test_case::Column::Position,
+                Order::Asc,
+                sea_query::NullOrdering::Last,
+            )
+            .order_by_asc(test_case_result::Column::Id);
         if let Some(judgement_id) = current_judgement_id {
             results_query =
                 results_query.filter(test_case_result::Column::JudgementId.eq(Some(judgement_id)));
@@ -813,7 +1211,6 @@ async fn build_submission_response(
                 .select_only()
                 .column(test_case::Column::Id)
                 .column(test_case::Column::IsSample)
-                .column(test_case::Column::Position)
                 .into_model::<TestCaseMeta>()
                 .all(db)
                 .await?
@@ -822,37 +1219,30 @@ async fn build_submission_response(
                 .collect()
         };
 
-        let mut results_with_pos: Vec<_> = results
-            .into_iter()
-            .map(|r| {
-                let pos = r
-                    .test_case_id
-                    .and_then(|tc_id| tc_meta.get(&tc_id))
-                    .map_or(i32::MAX, |m| m.position);
-                (r, pos)
-            })
-            .collect();
-        results_with_pos.sort_by_key(|(_, pos)| *pos);
-
-        let io_ids: Vec<i32> = if has_view_all || problem_model.show_test_details {
+        let test_details = if has_view_all {
+            TestDetailsVisibility::Full
+        } else {
+            problem_model.test_details_visibility
+        };
+        let io_ids: Vec<i32> = if test_details == TestDetailsVisibility::Full {
             tc_ids
         } else {
             tc_meta
                 .values()
-                .filter(|m| m.is_sample)
+                .filter(|m| test_details.reveals(m.is_sample))
                 .map(|m| m.id)
                 .collect()
         };
         let io_data = load_test_case_io_data(db, io_ids, blob_store).await?;
 
-        let test_case_results = results_with_pos
+        let test_case_results = results
             .into_iter()
-            .map(|(result, _)| {
+            .map(|result| {
                 let is_sample = result
                     .test_case_id
                     .and_then(|tc_id| tc_meta.get(&tc_id))
                     .is_some_and(|m| m.is_sample);
-                let show_io = has_view_all || problem_model.show_test_details || is_sample;
+                let show_io = test_details.reveals(is_sample);
 
                 let (tc_input, tc_expected) = if show_io {
                     let io = result.test_case_id.and_then(|tc_id| io_data.get(&tc_id));
@@ -875,7 +1265,13 @@ async fn build_submission_response(
                     expected_output: tc_expected,
                     stdout: if show_io { result.stdout } else { None },
                     stderr: if show_io { result.stderr } else { None },
-                    checker_output: if show_io { result.checker_output } else { None },
+                    checker_output: if show_io {
+                        result
+                            .checker_output
+                            .map(|out| truncate_text(out, max_checker_output_bytes))
+                    } else {
+                        None
+                    },
                 }
             })
             .collect();
@@ -884,17 +1280,22 @@ async fn build_submission_response(
             Some(JudgeResultResponse {
                 verdict: None,
                 score: None,
+                score_percentage: None,
                 time_used: None,
                 memory_used: None,
                 compile_output: None,
                 error_message: None,
                 judged_at: None,
                 test_case_results,
+                subtasks: Vec::new(),
             })
         } else {
+            let subtasks = subtasks_from_json(sub.subtask_results.as_ref());
+            let max_score = max_possible_score(db, sub.problem_id, &subtasks).await?;
             Some(JudgeResultResponse {
                 verdict: sub.verdict,
                 score: sub.score,
+                score_percentage: score_percentage(sub.score, max_score),
                 time_used: sub.time_used,
                 memory_used: sub.memory_used,
                 compile_output: if show_compile_output {
@@ -909,6 +1310,7 @@ async fn build_submission_response(
                 },
                 judged_at: sub.judged_at,
                 test_case_results,
+                subtasks,
             })
         }
     } else {
@@ -921,11 +1323,31 @@ async fn build_submission_response(
         vec![]
     };
 
+    let (queue_position, pending_seconds) = if sub.status.is_terminal() {
+        (None, None)
+    } else {
+        let queued_at = current_judgement
+            .as_ref()
+            .map_or(sub.created_at, |j| j.created_at);
+        (
+            Some(unfinished_submissions_before(db, sub.id).await? + 1),
+            Some(Ord::max((Utc::now() - queued_at).num_seconds(), 0)),
+        )
+    };
+
+    let (source_size, line_count) = source_metrics(&sub.files);
+
     Ok(SubmissionResponse {
         id: sub.id,
         files,
+        source_size,
+        line_count,
         language: sub.language,
-        status: sub.status,
+        status: if hidden_until.is_some() {
+            withheld_status(sub.status)
+        } else {
+            sub.status
+        },
         user_id: sub.user_id,
         username: user_model.username,
         problem_id: sub.problem_id,
@@ -934,11 +1356,48 @@ async fn build_submission_response(
         contest_type: sub.contest_type.clone(),
         judge_epoch: sub.judge_epoch,
         target_worker_id: sub.target_worker_id,
+        judged_by: if has_view_all { sub.judged_by } else { None },
+        judge_mode: judge_mode_of(sub.compile_only),
+        queue_position,
+        pending_seconds,
+        judging_duration_ms: current_judgement.and_then(|j| j.judging_duration_ms),
         created_at: sub.created_at,
         result: result_response,
+        results_hidden_until: hidden_until,
     })
 }
 
+/// Most points a submission to `problem_id` can earn: the subtask maxima
+/// when the contest type reported a breakdown, else the test case scores.
+async fn max_possible_score<C: ConnectionTrait>(
+    db: &C,
+    problem_id: i32,
+    subtasks: &[SubtaskResultResponse],
+) -> Result<f64, AppError> {
+    if subtasks.is_empty() {
+        problem_max_score(db, problem_id).await
+    } else {
+        Ok(subtasks.iter().map(|s| s.max_score).sum())
+    }
+}
+
+/// Unfinished submissions queued ahead of `submission_id`, across all
+/// problems and contests.
+async fn unfinished_submissions_before(
+    db: &DatabaseConnection,
+    submission_id: i32,
+) -> Result<u64, AppError> {
+    Ok(submission::Entity::find()
+        .filter(submission::Column::Id.lt(submission_id))
+        .filter(submission::Column::Status.is_in([
+            SubmissionStatus::Pending,
+            SubmissionStatus::Compiling,
+            SubmissionStatus::Running,
+        ]))
+        .count(db)
+        .await?)
+}
+
 async fn require_submission_visible(
     db: &DatabaseConnection,
     auth_user: &AuthUser,
@@ -969,10 +1428,18 @@ async fn build_judgement_response(
     blob_store: &dyn BlobStore,
     judgement: submission_judgement::Model,
     show_compile_output: bool,
-    show_test_details: bool,
+    test_details: TestDetailsVisibility,
+    max_checker_output_bytes: usize,
 ) -> Result<SubmissionJudgementResponse, AppError> {
     let results = test_case_result::Entity::find()
         .filter(test_case_result::Column::JudgementId.eq(Some(judgement.id)))
+        .left_join(test_case::Entity)
+        .order_by_with_nulls(
+            test_case::Column::Position,
+            Order::Asc,
+            sea_query::NullOrdering::Last,
+        )
+        .order_by_asc(test_case_result::Column::Id)
         .all(db)
         .await?;
 
@@ -985,7 +1452,6 @@ async fn build_judgement_response(
             .select_only()
             .column(test_case::Column::Id)
             .column(test_case::Column::IsSample)
-            .column(test_case::Column::Position)
             .into_model::<TestCaseMeta>()
             .all(db)
             .await?
@@ -994,37 +1460,25 @@ async fn build_judgement_response(
             .collect()
     };
 
-    let mut results_with_pos: Vec<_> = results
-        .into_iter()
-        .map(|r| {
-            let pos = r
-                .test_case_id
-                .and_then(|tc_id| tc_meta.get(&tc_id))
-                .map_or(i32::MAX, |m| m.position);
-            (r, pos)
-        })
-        .collect();
-    results_with_pos.sort_by_key(|(_, pos)| *pos);
-
-    let io_ids: Vec<i32> = if show_test_details {
+    let io_ids: Vec<i32> = if test_details == TestDetailsVisibility::Full {
         tc_ids
     } else {
         tc_meta
             .values()
-            .filter(|m| m.is_sample)
+            .filter(|m| test_details.reveals(m.is_sample))
             .map(|m| m.id)
             .collect()
     };
     let io_data = load_test_case_io_data(db, io_ids, blob_store).await?;
 
-    let test_case_results = results_with_pos
+    let test_case_results = results
         .into_iter()
-        .map(|(result, _)| {
+        .map(|result| {
             let is_sample = result
                 .test_case_id
                 .and_then(|tc_id| tc_meta.get(&tc_id))
                 .is_some_and(|m| m.is_sample);
-            let show_io = show_test_details || is_sample;
+            let show_io = test_details.reveals(is_sample);
 
             let (tc_input, tc_expected) = if show_io {
                 let io = result.test_case_id.and_then(|tc_id| io_data.get(&tc_id));
@@ -1047,7 +1501,13 @@ async fn build_judgement_response(
                 expected_output: tc_expected,
                 stdout: if show_io { result.stdout } else { None },
                 stderr: if show_io { result.stderr } else { None },
-                checker_output: if show_io { result.checker_output } else { None },
+                checker_output: if show_io {
+                    result
+                        .checker_output
+                        .map(|out| truncate_text(out, max_checker_output_bytes))
+                } else {
+                    None
+                },
             }
         })
         .collect();
@@ -1083,6 +1543,7 @@ async fn build_judgement_response(
         created_at: judgement.created_at,
         finalized_at: judgement.finalized_at,
         test_case_results,
+        subtasks: subtasks_from_json(judgement.subtask_results.as_ref()),
     })
 }
 
@@ -1192,22 +1653,35 @@ async fn apply_filter_to_judgement_response(
     mut response: SubmissionJudgementResponse,
     visibility: &VisibilityContext,
 ) -> Result<SubmissionJudgementResponse, AppError> {
-    let result_response =
-        if response.status.is_terminal() || response.status == SubmissionStatus::Running {
-            Some(JudgeResultResponse {
-                verdict: response.verdict,
-                score: response.score,
-                time_used: response.time_used,
-                memory_used: response.memory_used,
-                compile_output: response.compile_output.clone(),
-                error_message: response.error_message.clone(),
-                judged_at: response.finalized_at,
-                test_case_results: response.test_case_results.clone(),
-            })
-        } else {
-            None
-        };
+    let hidden_until = results_hidden_until(
+        &state.db,
+        sub.contest_id,
+        sub.problem_id,
+        sub.created_at,
+        Some(visibility),
+    )
+    .await?;
+    let result_response = if hidden_until.is_none()
+        && (response.status.is_terminal() || response.status == SubmissionStatus::Running)
+    {
+        let max_score = max_possible_score(&state.db, sub.problem_id, &response.subtasks).await?;
+        Some(JudgeResultResponse {
+            verdict: response.verdict,
+            score: response.score,
+            score_percentage: score_percentage(response.score, max_score),
+            time_used: response.time_used,
+            memory_used: response.memory_used,
+            compile_output: response.compile_output.clone(),
+            error_message: response.error_message.clone(),
+            judged_at: response.finalized_at,
+            test_case_results: response.test_case_results.clone(),
+            subtasks: response.subtasks.clone(),
+        })
+    } else {
+        None
+    };
 
+    let (source_size, line_count) = source_metrics(&sub.files);
     let synthetic_submission = SubmissionResponse {
         id: sub.id,
         files: if visibility.has_view_all || visibility.viewer_id == sub.user_id {
@@ -1215,6 +1689,8 @@ async fn apply_filter_to_judgement_response(
         } else {
             vec![]
         },
+        source_size,
+        line_count,
         language: sub.language.clone(),
         status: response.status.clone(),
         user_id: sub.user_id,
@@ -1225,8 +1701,14 @@ async fn apply_filter_to_judgement_response(
         contest_type: sub.contest_type.clone(),
         judge_epoch: response.judge_epoch,
         target_worker_id: response.target_worker_id.clone(),
+        judged_by: None,
+        judge_mode: judge_mode_of(sub.compile_only),
+        queue_position: None,
+        pending_seconds: None,
+        judging_duration_ms: None,
         created_at: sub.created_at,
         result: result_response,
+        results_hidden_until: None,
     };
 
     let filtered_submission =
@@ -1242,6 +1724,7 @@ async fn apply_filter_to_judgement_response(
             response.error_message = result.error_message;
             response.finalized_at = result.judged_at;
             response.test_case_results = result.test_case_results;
+            response.subtasks = result.subtasks;
             if response.compile_output.is_none() && response.error_message.is_none() {
                 response.error_code = None;
             }
@@ -1255,8 +1738,12 @@ async fn apply_filter_to_judgement_response(
             response.error_code = None;
             response.error_message = None;
             response.test_case_results.clear();
+            response.subtasks.clear();
         }
     }
+    if hidden_until.is_some() {
+        response.status = withheld_status(response.status);
+    }
 
     Ok(response)
 }
@@ -1290,7 +1777,7 @@ async fn apply_filter_to_list(
     tag = "Submissions",
     operation_id = "createSubmission",
     summary = "Submit a solution to a problem",
-    description = "Creates a new submission for the specified problem. The submission will be queued for judging. Requires `submission:submit` permission.",
+    description = "Creates a new submission for the specified problem. The submission will be queued for judging, or only compiled with `judge_mode` `compile_only`. Requires `submission:submit` permission.",
     params(
         ("id" = i32, Path, description = "Problem ID")
     ),
@@ -1301,7 +1788,8 @@ async fn apply_filter_to_list(
         (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
         (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
         (status = 404, description = "Problem not found (NOT_FOUND)", body = ErrorBody),
-        (status = 429, description = "Rate limit or plugin rejection (RATE_LIMITED, PLUGIN_REJECTED)", body = ErrorBody),
+        (status = 409, description = "Duplicates a recent submission (DUPLICATE_SUBMISSION)", body = ErrorBody),
+        (status = 429, description = "Rate limit, per-problem resubmit cooldown, in-flight cap or plugin rejection (RATE_LIMITED, TOO_MANY_IN_FLIGHT, PLUGIN_REJECTED)", body = ErrorBody),
     ),
     security(("jwt" = [])),
 )]
@@ -1313,10 +1801,22 @@ pub async fn create_submission(
     AppJson(payload): AppJson<CreateSubmissionRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     auth_user.require_permission("submission:submit")?;
-    validate_code_payload(
+    let language = resolve_submission_language(
+        payload.language.as_deref(),
         &payload.files,
-        &payload.language,
-        state.config.submission.max_size,
+        &*state.registries.language_resolver_registry.read().await,
+    )?;
+    validate_code_payload(&payload.files, &language, &state.config.submission)?;
+    validate_file_encoding(
+        &payload.files,
+        state
+            .registries
+            .language_resolver_registry
+            .read()
+            .await
+            .get(&language)
+            .map(|entry| entry.binary_extensions.as_slice())
+            .unwrap_or_default(),
     )?;
     check_rate_limit(
         &state.db,
@@ -1324,8 +1824,21 @@ pub async fn create_submission(
         state.config.submission.rate_limit_per_minute,
     )
     .await?;
+    check_resubmit_cooldown(
+        &state.db,
+        auth_user.user_id,
+        problem_id,
+        state.config.submission.resubmit_cooldown_secs,
+    )
+    .await?;
 
     let txn = state.db.begin().await?;
+    check_in_flight_limit(
+        &txn,
+        auth_user.user_id,
+        state.config.submission.max_in_flight_per_user,
+    )
+    .await?;
 
     let problem = find_problem(&txn, problem_id).await?;
     let known_languages: std::collections::HashSet<String> = state
@@ -1338,10 +1851,31 @@ pub async fn create_submission(
         .collect();
     validate_submission_contract(
         &payload.files,
-        &payload.language,
+        &language,
         problem.get_submission_format(),
         &known_languages,
     )?;
+    validate_language_problem_type(
+        &language,
+        &problem.problem_type,
+        &*state.registries.language_resolver_registry.read().await,
+    )?;
+    let compile_only = payload.judge_mode.is_compile_only();
+    let skip_judging = !compile_only
+        && check_no_test_cases(&txn, problem_id, state.config.submission.no_test_cases).await?;
+    let files_json = files_to_json(&payload.files);
+    if !compile_only {
+        check_duplicate_submission(
+            &txn,
+            auth_user.user_id,
+            problem_id,
+            None,
+            &language,
+            &files_json,
+            state.config.submission.duplicate_window_secs,
+        )
+        .await?;
+    }
 
     let contest_type = match payload.contest_type {
         Some(ref ct) => {
@@ -1363,27 +1897,36 @@ pub async fn create_submission(
         user_id: auth_user.user_id,
         problem_id,
         contest_id: None,
-        language: payload.language.trim().to_string(),
+        language: language.clone(),
         file_count: payload.files.len(),
+        filenames: payload
+            .files
+            .iter()
+            .map(|f| f.filename.trim().to_string())
+            .collect(),
     };
     let enabled_plugins = hooks::fetch_resource_enablements(problem_id, None, &state.db).await?;
     dispatch_before_submission_hooks(&state, &hook_event, Some(&enabled_plugins)).await?;
 
     let now = Utc::now();
-    let language = payload.language.trim().to_string();
     let new_submission = submission::ActiveModel {
-        files: Set(files_to_json(&payload.files)),
+        files: Set(files_json),
         language: Set(language.clone()),
         status: Set(SubmissionStatus::Pending),
         user_id: Set(auth_user.user_id),
         problem_id: Set(problem_id),
         contest_id: Set(None),
         contest_type: Set(contest_type),
+        compile_only: Set(compile_only),
         created_at: Set(now),
         ..Default::default()
     };
 
-    let model = new_submission.insert(&txn).await?;
+    let model = if skip_judging {
+        insert_no_test_cases_submission(&txn, new_submission, now).await?
+    } else {
+        new_submission.insert(&txn).await?
+    };
     txn.commit().await?;
 
     fire_after_submission_hooks(
@@ -1396,79 +1939,262 @@ pub async fn create_submission(
         Some(enabled_plugins),
     );
 
-    let state_clone = state.clone();
-    let model_clone = model.clone();
-    tokio::spawn(async move {
-        dispatch_to_plugin(state_clone, model_clone).await;
-    });
+    if !skip_judging {
+        let state_clone = state.clone();
+        let model_clone = model.clone();
+        tokio::spawn(async move {
+            dispatch_to_plugin(state_clone, model_clone).await;
+        });
+    }
 
     let visibility = Some(VisibilityContext {
         viewer_id: auth_user.user_id,
         has_view_all: auth_user.has_permission("submission:view_all"),
     });
-    let response =
-        build_submission_response(&state.db, &*state.blob_store, model, visibility).await?;
+    let response = build_submission_response(
+        &state.db,
+        &*state.blob_store,
+        model,
+        visibility,
+        state.config.submission.max_checker_output_bytes,
+    )
+    .await?;
 
     Ok((StatusCode::CREATED, Json(response)))
 }
 
 #[utoipa::path(
-    get,
-    path = "/",
+    post,
+    path = "/{id}/clone-as-practice",
     tag = "Submissions",
-    operation_id = "listSubmissions",
-    summary = "List submissions",
-    description = "Returns a paginated list of submissions. Users see their own submissions; users with `submission:view_all` permission see all submissions.",
-    params(SubmissionListQuery),
+    operation_id = "cloneSubmissionAsPractice",
+    summary = "Resubmit a submission as practice",
+    description = "Creates a new standalone submission (no contest) owned by the caller from an existing submission's files and language, and queues it for judging. Only the owner or users with `submission:view_all` may clone a submission. A contest submission can only be cloned once its contest has ended, unless the caller has `contest:manage` permission. The files go through the same validation as createSubmission. Requires `submission:submit` permission.",
+    params(
+        ("id" = i32, Path, description = "Submission ID")
+    ),
     responses(
-        (status = 200, description = "List of submissions", body = SubmissionListResponse),
+        (status = 201, description = "Practice submission created", body = SubmissionResponse),
         (status = 400, description = "Validation error (VALIDATION_ERROR)", body = ErrorBody),
         (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Submission or problem not found (NOT_FOUND)", body = ErrorBody),
+        (status = 409, description = "Submission has been archived or its contest has not ended (CONFLICT), or duplicates a recent one (DUPLICATE_SUBMISSION)", body = ErrorBody),
+        (status = 429, description = "Rate limit, per-problem resubmit cooldown, in-flight cap or plugin rejection (RATE_LIMITED, TOO_MANY_IN_FLIGHT, PLUGIN_REJECTED)", body = ErrorBody),
     ),
     security(("jwt" = [])),
 )]
-#[instrument(skip(state, auth_user, query))]
-pub async fn list_submissions(
+#[instrument(skip(state, auth_user), fields(submission_id = %id))]
+pub async fn clone_submission_as_practice(
     auth_user: AuthUser,
     State(state): State<AppState>,
-    Query(query): Query<SubmissionListQuery>,
-) -> Result<Json<SubmissionListResponse>, AppError> {
-    validate_sorting_params(
-        query.sort_by.as_deref(),
-        query.sort_order.as_deref(),
-        &["created_at", "status"],
+    AppPath(id): AppPath<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    auth_user.require_permission("submission:submit")?;
+
+    let source = find_submission(&state.db, id).await?;
+    if source.user_id != auth_user.user_id && !auth_user.has_permission("submission:view_all") {
+        return Err(AppError::NotFound("Submission not found".into()));
+    }
+    if archived_submission::Entity::find_by_id(source.id)
+        .one(&state.db)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::Conflict(
+            "Submission has been archived and its source files are no longer available".into(),
+        ));
+    }
+    // A practice clone is judged on the full test set with its result shown
+    // at once, so before the contest ends it would reveal what pretest-only
+    // judging, result delays and the freeze hold back.
+    if let Some(contest_id) = source.contest_id
+        && !auth_user.has_permission("contest:manage")
+    {
+        let contest_running = contest::Entity::find_by_id(contest_id)
+            .one(&state.db)
+            .await?
+            .is_some_and(|c| Utc::now() < c.end_time);
+        if contest_running {
+            return Err(AppError::Conflict(
+                "Cannot clone a contest submission as practice before the contest ends".into(),
+            ));
+        }
+    }
+
+    let files = files_from_json(&source.files);
+    validate_code_payload(&files, &source.language, &state.config.submission)?;
+    validate_file_encoding(
+        &files,
+        state
+            .registries
+            .language_resolver_registry
+            .read()
+            .await
+            .get(&source.language)
+            .map(|entry| entry.binary_extensions.as_slice())
+            .unwrap_or_default(),
     )?;
+    check_rate_limit(
+        &state.db,
+        auth_user.user_id,
+        state.config.submission.rate_limit_per_minute,
+    )
+    .await?;
+    check_resubmit_cooldown(
+        &state.db,
+        auth_user.user_id,
+        source.problem_id,
+        state.config.submission.resubmit_cooldown_secs,
+    )
+    .await?;
 
-    let can_view_all = auth_user.has_permission("submission:view_all");
+    let txn = state.db.begin().await?;
+    check_in_flight_limit(
+        &txn,
+        auth_user.user_id,
+        state.config.submission.max_in_flight_per_user,
+    )
+    .await?;
 
-    let page = cmp::max(query.page.unwrap_or(1), 1);
-    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let problem_id = source.problem_id;
+    let problem = find_problem(&txn, problem_id).await?;
+    let known_languages: std::collections::HashSet<String> = state
+        .registries
+        .language_resolver_registry
+        .read()
+        .await
+        .keys()
+        .cloned()
+        .collect();
+    validate_submission_contract(
+        &files,
+        &source.language,
+        problem.get_submission_format(),
+        &known_languages,
+    )?;
+    validate_language_problem_type(
+        &source.language,
+        &problem.problem_type,
+        &*state.registries.language_resolver_registry.read().await,
+    )?;
+    let skip_judging =
+        check_no_test_cases(&txn, problem_id, state.config.submission.no_test_cases).await?;
+    let files_json = files_to_json(&files);
+    check_duplicate_submission(
+        &txn,
+        auth_user.user_id,
+        problem_id,
+        None,
+        &source.language,
+        &files_json,
+        state.config.submission.duplicate_window_secs,
+    )
+    .await?;
+
+    let hook_event = BeforeSubmissionEvent {
+        user_id: auth_user.user_id,
+        problem_id,
+        contest_id: None,
+        language: source.language.clone(),
+        file_count: files.len(),
+        filenames: files.iter().map(|f| f.filename.clone()).collect(),
+    };
+    let enabled_plugins = hooks::fetch_resource_enablements(problem_id, None, &state.db).await?;
+    dispatch_before_submission_hooks(&state, &hook_event, Some(&enabled_plugins)).await?;
+
+    let now = Utc::now();
+    let new_submission = submission::ActiveModel {
+        files: Set(files_json),
+        language: Set(source.language.clone()),
+        status: Set(SubmissionStatus::Pending),
+        user_id: Set(auth_user.user_id),
+        problem_id: Set(problem_id),
+        contest_id: Set(None),
+        contest_type: Set(problem.default_contest_type.clone()),
+        created_at: Set(now),
+        ..Default::default()
+    };
+
+    let model = if skip_judging {
+        insert_no_test_cases_submission(&txn, new_submission, now).await?
+    } else {
+        new_submission.insert(&txn).await?
+    };
+    txn.commit().await?;
+
+    info!(
+        source_submission_id = source.id,
+        submission_id = model.id,
+        "Cloned submission as practice"
+    );
+
+    fire_after_submission_hooks(
+        &state,
+        model.id,
+        auth_user.user_id,
+        problem_id,
+        None,
+        source.language,
+        Some(enabled_plugins),
+    );
 
-    let mut base_select = submission::Entity::find();
+    if !skip_judging {
+        let state_clone = state.clone();
+        let model_clone = model.clone();
+        tokio::spawn(async move {
+            dispatch_to_plugin(state_clone, model_clone).await;
+        });
+    }
+
+    let visibility = Some(VisibilityContext {
+        viewer_id: auth_user.user_id,
+        has_view_all: auth_user.has_permission("submission:view_all"),
+    });
+    let response = build_submission_response(
+        &state.db,
+        &*state.blob_store,
+        model,
+        visibility,
+        state.config.submission.max_checker_output_bytes,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Submissions matching the list filters in `query`, restricted to the
+/// viewer's own unless `can_view_all`.
+fn filter_submission_list(
+    query: &SubmissionListQuery,
+    viewer_id: i32,
+    can_view_all: bool,
+) -> Result<Select<submission::Entity>, AppError> {
+    let mut base_select = filter_created_range(submission::Entity::find(), query)?;
 
     if !can_view_all {
-        base_select = base_select.filter(submission::Column::UserId.eq(auth_user.user_id));
+        base_select = base_select.filter(submission::Column::UserId.eq(viewer_id));
     }
 
     if let Some(pid) = query.problem_id {
         base_select = base_select.filter(submission::Column::ProblemId.eq(pid));
     }
     if let Some(uid) = query.user_id
-        && (can_view_all || uid == auth_user.user_id)
+        && (can_view_all || uid == viewer_id)
     {
         base_select = base_select.filter(submission::Column::UserId.eq(uid));
     }
     if let Some(ref lang) = query.language {
         base_select = base_select.filter(submission::Column::Language.eq(lang.trim()));
     }
-    if let Some(status) = query.status {
-        base_select = base_select.filter(submission::Column::Status.eq(status));
+    if let Some(status) = query.status.clone() {
+        base_select = filter_visible_status(base_select, status, can_view_all);
     }
     if let Some(ref raw) = query.q {
         let escaped = escape_like(raw.trim());
         if !escaped.is_empty() {
             use sea_orm::prelude::Expr;
-            use sea_orm::sea_query::{Func, LikeExpr, Query as SeaQuery};
+            use sea_orm::sea_query::{Func, LikeExpr};
 
             let pattern = format!("%{}%", escaped.to_lowercase());
             let user_subq = SeaQuery::select()
@@ -1505,6 +2231,71 @@ pub async fn list_submissions(
         }
     }
 
+    Ok(base_select)
+}
+
+/// Narrows `select` to the `created_after`/`created_before` window in `query`.
+fn filter_created_range(
+    mut select: Select<submission::Entity>,
+    query: &SubmissionListQuery,
+) -> Result<Select<submission::Entity>, AppError> {
+    let CreatedRange { after, before } = query.created_range()?;
+    if let Some(after) = after {
+        select = select.filter(submission::Column::CreatedAt.gte(after));
+    }
+    if let Some(before) = before {
+        select = select.filter(submission::Column::CreatedAt.lt(before));
+    }
+    Ok(select)
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "Submissions",
+    operation_id = "listSubmissions",
+    summary = "List submissions",
+    description = "Returns a paginated list of submissions. Users see their own submissions; users with `submission:view_all` permission see all submissions.",
+    params(SubmissionListQuery),
+    responses(
+        (status = 200, description = "List of submissions", body = SubmissionListResponse),
+        (status = 400, description = "Validation error (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, query))]
+pub async fn list_submissions(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<SubmissionListQuery>,
+) -> Result<Json<SubmissionListResponse>, AppError> {
+    let query = match query.view.as_deref() {
+        Some(name) => {
+            let saved = load_saved_view(
+                &state.db,
+                auth_user.user_id,
+                SavedViewResource::Submissions,
+                name,
+            )
+            .await?;
+            query.or_saved(saved)
+        }
+        None => query,
+    };
+    validate_sorting_params(
+        query.sort_by.as_deref(),
+        query.sort_order.as_deref(),
+        &["created_at", "status"],
+    )?;
+
+    let can_view_all = auth_user.has_permission("submission:view_all");
+
+    let page = cmp::max(query.page.unwrap_or(1), 1);
+    let per_page = state.config.server.pagination.per_page(query.per_page);
+
+    let base_select = filter_submission_list(&query, auth_user.user_id, can_view_all)?;
+
     let total = base_select.clone().count(&state.db).await?;
 
     let select = base_select.find_also_related(user::Entity);
@@ -1517,7 +2308,7 @@ pub async fn list_submissions(
 
     let select = match query.sort_by.as_deref().unwrap_or("created_at") {
         "created_at" => select.order_by(submission::Column::CreatedAt, sort_order),
-        "status" => select.order_by(submission::Column::Status, sort_order),
+        "status" => order_by_visible_status(select, sort_order, can_view_all),
         _ => select.order_by(submission::Column::CreatedAt, Order::Desc),
     };
 
@@ -1532,7 +2323,8 @@ pub async fn list_submissions(
         viewer_id: auth_user.user_id,
         has_view_all: can_view_all,
     });
-    let data = apply_filter_to_list(&state, data, visibility.as_ref()).await?;
+    let mut data = apply_filter_to_list(&state, data, visibility.as_ref()).await?;
+    hide_delayed_list_results(&state.db, &mut data, visibility.as_ref()).await?;
     let total_pages = total.div_ceil(per_page);
 
     Ok(Json(SubmissionListResponse {
@@ -1546,6 +2338,111 @@ pub async fn list_submissions(
     }))
 }
 
+/// Submissions fetched per query while streaming an export.
+const EXPORT_BATCH_SIZE: u64 = 500;
+
+#[utoipa::path(
+    get,
+    path = "/export",
+    tag = "Submissions",
+    operation_id = "exportSubmissions",
+    summary = "Export submissions as JSONL",
+    description = "Streams every submission matching the listSubmissions filters (`problem_id`, `user_id`, `language`, `status`, `q`, `created_after`, `created_before`, `view`) as JSON Lines: one submission list item per line, ordered by ID. Paging and sorting parameters are ignored. Requires `submission:view_all` permission.",
+    params(SubmissionListQuery),
+    responses(
+        (status = 200, description = "One JSON submission list item per line", content_type = "application/x-ndjson", body = String),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, query))]
+pub async fn export_submissions(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<SubmissionListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    auth_user.require_permission("submission:view_all")?;
+    let query = match query.view.as_deref() {
+        Some(name) => {
+            let saved = load_saved_view(
+                &state.db,
+                auth_user.user_id,
+                SavedViewResource::Submissions,
+                name,
+            )
+            .await?;
+            query.or_saved(saved)
+        }
+        None => query,
+    };
+
+    let select = filter_submission_list(&query, auth_user.user_id, true)?;
+    let visibility = VisibilityContext {
+        viewer_id: auth_user.user_id,
+        has_view_all: true,
+    };
+    // Keyset pagination on id: each batch is fetched only once the previous
+    // one has been sent, so memory use does not grow with the export.
+    let lines = futures::stream::unfold(Some(0), move |after_id| {
+        let state = state.clone();
+        let select = select.clone();
+        async move {
+            let after_id = after_id?;
+            match export_batch(&state, select, after_id, &visibility).await {
+                Ok(Some((chunk, last_id))) => Some((Ok(chunk), Some(last_id))),
+                Ok(None) => None,
+                Err(e) => {
+                    error!(error = ?e, "Submission export failed mid-stream");
+                    Some((Err(std::io::Error::other("submission export failed")), None))
+                }
+            }
+        }
+    });
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"submissions.jsonl\"",
+            ),
+        ],
+        Body::from_stream(lines),
+    ))
+}
+
+/// JSONL for the next batch of `select` after `after_id`, and the last id
+/// in it. `None` once there are no more rows.
+async fn export_batch(
+    state: &AppState,
+    select: Select<submission::Entity>,
+    after_id: i32,
+    visibility: &VisibilityContext,
+) -> Result<Option<(Vec<u8>, i32)>, AppError> {
+    let rows = select
+        .filter(submission::Column::Id.gt(after_id))
+        .find_also_related(user::Entity)
+        .order_by_asc(submission::Column::Id)
+        .limit(EXPORT_BATCH_SIZE)
+        .all(&state.db)
+        .await?;
+    let Some(last_id) = rows.last().map(|(sub, _)| sub.id) else {
+        return Ok(None);
+    };
+
+    let items = build_submission_list_items(&state.db, rows).await?;
+    let items = apply_filter_to_list(state, items, Some(visibility)).await?;
+    let mut chunk = Vec::new();
+    for item in &items {
+        serde_json::to_writer(&mut chunk, item).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize exported submission: {e}"))
+        })?;
+        chunk.push(b'\n');
+    }
+    Ok(Some((chunk, last_id)))
+}
+
 #[utoipa::path(
     get,
     path = "/{id}",
@@ -1573,12 +2470,128 @@ pub async fn get_submission(
     let sub = find_submission(&state.db, id).await?;
 
     let visibility = Some(require_submission_visible(&state.db, &auth_user, &sub).await?);
-    let response =
-        build_submission_response(&state.db, &*state.blob_store, sub, visibility).await?;
+    let response = build_submission_response(
+        &state.db,
+        &*state.blob_store,
+        sub,
+        visibility,
+        state.config.submission.max_checker_output_bytes,
+    )
+    .await?;
     let response = apply_filter_to_response(&state, response, visibility.as_ref()).await?;
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    post,
+    path = "/{id}/comments",
+    tag = "Submissions",
+    operation_id = "createSubmissionComment",
+    summary = "Comment on a submission",
+    description = "Adds a grader note to a submission. Comments with `is_public` set are shown to the submission's owner; the rest are visible only to users with `submission:view_all`. Requires `submission:view_all` permission.",
+    params(
+        ("id" = i32, Path, description = "Submission ID")
+    ),
+    request_body = CreateSubmissionCommentRequest,
+    responses(
+        (status = 201, description = "Comment created", body = SubmissionCommentResponse),
+        (status = 400, description = "Validation error (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Submission not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, payload), fields(submission_id = %id))]
+pub async fn create_submission_comment(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+    AppJson(payload): AppJson<CreateSubmissionCommentRequest>,
+) -> Result<(StatusCode, Json<SubmissionCommentResponse>), AppError> {
+    auth_user.require_permission("submission:view_all")?;
+    validate_create_submission_comment(&payload)?;
+    let sub = find_submission(&state.db, id).await?;
+
+    let comment = submission_comment::ActiveModel {
+        submission_id: Set(sub.id),
+        author_id: Set(auth_user.user_id),
+        content: Set(sanitize_db_text(payload.content.trim())),
+        is_public: Set(payload.is_public),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SubmissionCommentResponse {
+            id: comment.id,
+            submission_id: comment.submission_id,
+            author_id: comment.author_id,
+            author_name: auth_user.username,
+            content: comment.content,
+            is_public: comment.is_public,
+            created_at: comment.created_at,
+        }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/comments",
+    tag = "Submissions",
+    operation_id = "listSubmissionComments",
+    summary = "List comments on a submission",
+    description = "Returns grader comments on a submission, oldest first. Users with `submission:view_all` see every comment; the owner sees only public ones.",
+    params(
+        ("id" = i32, Path, description = "Submission ID")
+    ),
+    responses(
+        (status = 200, description = "Submission comments", body = Vec<SubmissionCommentResponse>),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 404, description = "Submission not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(submission_id = %id))]
+pub async fn list_submission_comments(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+) -> Result<Json<Vec<SubmissionCommentResponse>>, AppError> {
+    let sub = find_submission(&state.db, id).await?;
+    let visibility = require_submission_visible(&state.db, &auth_user, &sub).await?;
+
+    let mut query = submission_comment::Entity::find()
+        .filter(submission_comment::Column::SubmissionId.eq(sub.id))
+        .find_also_related(user::Entity)
+        .order_by_asc(submission_comment::Column::Id);
+    if !visibility.has_view_all {
+        if sub.user_id != auth_user.user_id {
+            return Ok(Json(vec![]));
+        }
+        query = query.filter(submission_comment::Column::IsPublic.eq(true));
+    }
+    let comments = query.all(&state.db).await?;
+
+    Ok(Json(
+        comments
+            .into_iter()
+            .map(|(comment, author)| SubmissionCommentResponse {
+                id: comment.id,
+                submission_id: comment.submission_id,
+                author_id: comment.author_id,
+                author_name: author.map(|u| u.username).unwrap_or_default(),
+                content: comment.content,
+                is_public: comment.is_public,
+                created_at: comment.created_at,
+            })
+            .collect(),
+    ))
+}
+
 #[utoipa::path(
     get,
     path = "/{id}/judgements",
@@ -1634,7 +2647,11 @@ pub async fn list_submission_judgements(
         || contest_model
             .as_ref()
             .is_some_and(|c| c.show_compile_output);
-    let show_test_details = visibility.has_view_all || problem_model.show_test_details;
+    let test_details = if visibility.has_view_all {
+        TestDetailsVisibility::Full
+    } else {
+        problem_model.test_details_visibility
+    };
 
     let judgements = submission_judgement::Entity::find()
         .filter(submission_judgement::Column::SubmissionId.eq(sub.id))
@@ -1649,7 +2666,8 @@ pub async fn list_submission_judgements(
             &*state.blob_store,
             judgement,
             show_compile_output,
-            show_test_details,
+            test_details,
+            state.config.submission.max_checker_output_bytes,
         )
         .await?;
         let response = apply_filter_to_judgement_response(
@@ -1736,15 +2754,22 @@ pub async fn apply_submission_judgement(
     active_submission.score = Set(judgement.score);
     active_submission.time_used = Set(judgement.time_used);
     active_submission.memory_used = Set(judgement.memory_used);
+    active_submission.subtask_results = Set(judgement.subtask_results);
     active_submission.judged_at = Set(judgement.finalized_at);
+    active_submission.judged_by = Set(judgement.judged_by);
     active_submission.judge_epoch = Set(judgement.judge_epoch);
     active_submission.target_worker_id = Set(judgement.target_worker_id);
     let updated = active_submission.update(&txn).await?;
     txn.commit().await?;
 
+    if let Some(contest_id) = updated.contest_id {
+        state.standings_cache.invalidate_contest(contest_id);
+    }
+
     fire_after_judging_hooks(
         &state.db,
         state.registries.hook_registry.clone(),
+        &state.config.webhook,
         updated.id,
         updated.user_id,
         updated.problem_id,
@@ -1756,8 +2781,14 @@ pub async fn apply_submission_judgement(
         viewer_id: auth_user.user_id,
         has_view_all: true,
     });
-    let response =
-        build_submission_response(&state.db, &*state.blob_store, updated, visibility).await?;
+    let response = build_submission_response(
+        &state.db,
+        &*state.blob_store,
+        updated,
+        visibility,
+        state.config.submission.max_checker_output_bytes,
+    )
+    .await?;
     Ok(Json(response))
 }
 
@@ -1825,6 +2856,144 @@ pub async fn discard_submission_judgement(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    post,
+    path = "/{id}/recompute",
+    tag = "Submissions",
+    operation_id = "recomputeSubmissionResult",
+    summary = "Recompute a submission's result from stored test-case results",
+    description = "Re-aggregates `verdict`, `score`, `time_used` and `memory_used` from the current judgement's test-case result rows without re-running the submission. Intended for manual data fixes. Only submissions of contest types registered with summed scores can be recomputed; others, such as ICPC and IOI, fold the rows through their own scoring, so they are refused, as are submissions scored per subtask. Rejudge those instead. Requires `submission:rejudge` permission.",
+    params(("id" = i32, Path, description = "Submission ID")),
+    responses(
+        (status = 200, description = "Recomputed submission", body = SubmissionResponse),
+        (status = 400, description = "No test-case results to aggregate (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Submission not found (NOT_FOUND)", body = ErrorBody),
+        (status = 409, description = "Submission is still being judged, its contest type does not sum test case scores, or it is scored per subtask (CONFLICT)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(submission_id = %id))]
+pub async fn recompute_submission_result(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+) -> Result<Json<SubmissionResponse>, AppError> {
+    auth_user.require_permission("submission:rejudge")?;
+
+    let txn = state.db.begin().await?;
+    let sub = submission::Entity::find_by_id(id)
+        .lock(LockType::Update)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Submission not found".into()))?;
+
+    // Other contest types fold the rows through their own scoring (e.g.
+    // ICPC's binary score), which summing the rows cannot reproduce.
+    let summed_scores = state
+        .registries
+        .contest_type_registry
+        .read()
+        .await
+        .get(&sub.contest_type)
+        .is_some_and(|handlers| handlers.summed_scores);
+    if !summed_scores {
+        return Err(AppError::Conflict(format!(
+            "Cannot recompute a '{}' submission; its contest type scores it itself, rejudge it instead",
+            sub.contest_type
+        )));
+    }
+
+    let judgement = submission_judgement::Entity::find()
+        .filter(submission_judgement::Column::SubmissionId.eq(id))
+        .filter(submission_judgement::Column::IsCurrent.eq(true))
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Judgement not found".into()))?;
+
+    if !judgement.is_finalized {
+        return Err(AppError::Conflict(
+            "Cannot recompute a submission that is still being judged".into(),
+        ));
+    }
+    // The contest plugin folded the rows through its subtask scoring
+    // (e.g. all-or-nothing groups), which summing the rows cannot reproduce.
+    let has_subtasks = judgement
+        .subtask_results
+        .as_ref()
+        .and_then(|v| v.as_array())
+        .is_some_and(|subtasks| !subtasks.is_empty());
+    if has_subtasks {
+        return Err(AppError::Conflict(
+            "Cannot recompute a submission scored per subtask; rejudge it instead".into(),
+        ));
+    }
+
+    let rows = test_case_result::Entity::find()
+        .filter(test_case_result::Column::JudgementId.eq(Some(judgement.id)))
+        .all(&txn)
+        .await?;
+    if rows.is_empty() {
+        return Err(AppError::Validation(
+            "Submission has no test case results to recompute".into(),
+        ));
+    }
+
+    let aggregated = aggregate_test_case_results(&rows);
+
+    let mut active_judgement: submission_judgement::ActiveModel = judgement.into();
+    active_judgement.verdict = Set(Some(aggregated.verdict.clone()));
+    active_judgement.score = Set(Some(aggregated.score));
+    active_judgement.time_used = Set(aggregated.time_used);
+    active_judgement.memory_used = Set(aggregated.memory_used);
+    active_judgement.update(&txn).await?;
+
+    let mut active_submission: submission::ActiveModel = sub.into();
+    active_submission.verdict = Set(Some(aggregated.verdict));
+    active_submission.score = Set(Some(aggregated.score));
+    active_submission.time_used = Set(aggregated.time_used);
+    active_submission.memory_used = Set(aggregated.memory_used);
+    let updated = active_submission.update(&txn).await?;
+    txn.commit().await?;
+
+    info!(
+        submission_id = id,
+        verdict = ?updated.verdict,
+        score = ?updated.score,
+        "Recomputed submission result"
+    );
+
+    if let Some(contest_id) = updated.contest_id {
+        state.standings_cache.invalidate_contest(contest_id);
+    }
+
+    fire_after_judging_hooks(
+        &state.db,
+        state.registries.hook_registry.clone(),
+        &state.config.webhook,
+        updated.id,
+        updated.user_id,
+        updated.problem_id,
+        updated.contest_id,
+    )
+    .await;
+
+    let visibility = Some(VisibilityContext {
+        viewer_id: auth_user.user_id,
+        has_view_all: true,
+    });
+    let response = build_submission_response(
+        &state.db,
+        &*state.blob_store,
+        updated,
+        visibility,
+        state.config.submission.max_checker_output_bytes,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
 #[derive(Deserialize, utoipa::IntoParams)]
 #[into_params(parameter_in = Query)]
 pub struct RejudgeQuery {
@@ -1850,6 +3019,7 @@ pub struct RejudgeQuery {
         (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
         (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
         (status = 404, description = "Submission not found (NOT_FOUND)", body = ErrorBody),
+        (status = 409, description = "Submission has been archived (CONFLICT)", body = ErrorBody),
     ),
     security(("jwt" = [])),
 )]
@@ -1901,6 +3071,16 @@ pub async fn rejudge_submission(
         .await?
         .ok_or_else(|| AppError::NotFound("Submission not found".into()))?;
 
+    if archived_submission::Entity::find_by_id(sub.id)
+        .one(&txn)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::Conflict(
+            "Submission has been archived and its source files are no longer available".into(),
+        ));
+    }
+
     let new_epoch = sub.judge_epoch.saturating_add(1);
 
     // The prior verdict (and its test_case_result rows) stay attached to
@@ -1930,7 +3110,9 @@ pub async fn rejudge_submission(
         active.score = Set(None);
         active.time_used = Set(None);
         active.memory_used = Set(None);
+        active.subtask_results = Set(None);
         active.judged_at = Set(None);
+        active.judged_by = Set(None);
         active.judge_epoch = Set(new_epoch);
         if let Some(target) = new_target.clone() {
             active.target_worker_id = Set(target);
@@ -1966,8 +3148,14 @@ pub async fn rejudge_submission(
         viewer_id: auth_user.user_id,
         has_view_all: true,
     });
-    let response =
-        build_submission_response(&state.db, &*state.blob_store, updated, visibility).await?;
+    let response = build_submission_response(
+        &state.db,
+        &*state.blob_store,
+        updated,
+        visibility,
+        state.config.submission.max_checker_output_bytes,
+    )
+    .await?;
     Ok(Json(response))
 }
 
@@ -1977,7 +3165,7 @@ pub async fn rejudge_submission(
     tag = "Submissions",
     operation_id = "createContestSubmission",
     summary = "Submit a solution to a contest problem",
-    description = "Creates a new submission for a problem within a contest. The user must be a contest participant (or have `contest:manage` permission), and the contest must be active. Requires `submission:submit` permission.",
+    description = "Creates a new submission for a problem within a contest. The user must be a contest participant (or have `contest:manage` permission), and the contest must be active. Once a contest with `practice_after_end` has ended, any user may submit; such submissions are recorded as practice, without a contest, and do not count towards standings. Requires `submission:submit` permission.",
     params(
         ("id" = i32, Path, description = "Contest ID"),
         ("problem_id" = i32, Path, description = "Problem ID")
@@ -1989,7 +3177,8 @@ pub async fn rejudge_submission(
         (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
         (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
         (status = 404, description = "Contest or problem not found (NOT_FOUND)", body = ErrorBody),
-        (status = 429, description = "Rate limit or plugin rejection (RATE_LIMITED, PLUGIN_REJECTED)", body = ErrorBody),
+        (status = 409, description = "Duplicates a recent submission (DUPLICATE_SUBMISSION)", body = ErrorBody),
+        (status = 429, description = "Rate limit, per-problem resubmit cooldown, in-flight cap or plugin rejection (RATE_LIMITED, TOO_MANY_IN_FLIGHT, PLUGIN_REJECTED)", body = ErrorBody),
     ),
     security(("jwt" = [])),
 )]
@@ -2001,20 +3190,50 @@ pub async fn create_contest_submission(
     AppJson(payload): AppJson<CreateSubmissionRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     auth_user.require_permission("submission:submit")?;
-    validate_code_payload(
+    let language = resolve_submission_language(
+        payload.language.as_deref(),
         &payload.files,
-        &payload.language,
-        state.config.submission.max_size,
+        &*state.registries.language_resolver_registry.read().await,
     )?;
+    validate_code_payload(&payload.files, &language, &state.config.submission)?;
+    validate_file_encoding(
+        &payload.files,
+        state
+            .registries
+            .language_resolver_registry
+            .read()
+            .await
+            .get(&language)
+            .map(|entry| entry.binary_extensions.as_slice())
+            .unwrap_or_default(),
+    )?;
+    if payload.judge_mode.is_compile_only() {
+        return Err(AppError::Validation(
+            "judge_mode compile_only is not available for contest submissions".into(),
+        ));
+    }
     check_rate_limit(
         &state.db,
         auth_user.user_id,
         state.config.submission.rate_limit_per_minute,
     )
     .await?;
+    check_resubmit_cooldown(
+        &state.db,
+        auth_user.user_id,
+        problem_id,
+        state.config.submission.resubmit_cooldown_secs,
+    )
+    .await?;
 
     let contest_id = id;
     let txn = state.db.begin().await?;
+    check_in_flight_limit(
+        &txn,
+        auth_user.user_id,
+        state.config.submission.max_in_flight_per_user,
+    )
+    .await?;
 
     let contest_model = find_contest(&txn, contest_id).await?;
 
@@ -2026,8 +3245,14 @@ pub async fn create_contest_submission(
     }
 
     let now = Utc::now();
-    require_contest_running(&auth_user, &contest_model, now)?;
-    require_contest_participant(&state.db, &auth_user, &contest_model).await?;
+    // Once a practice-enabled contest ends, anyone may submit, but the
+    // submission is standalone so it stays out of the standings.
+    let practice = is_open_for_practice(&contest_model, now);
+    if !practice {
+        require_contest_running(&auth_user, &contest_model, now)?;
+        require_contest_participant(&state.db, &auth_user, &contest_model).await?;
+    }
+    let submission_contest_id = (!practice).then_some(contest_id);
     let known_languages: std::collections::HashSet<String> = state
         .registries
         .language_resolver_registry
@@ -2038,43 +3263,71 @@ pub async fn create_contest_submission(
         .collect();
     validate_submission_contract(
         &payload.files,
-        &payload.language,
+        &language,
         problem.get_submission_format(),
         &known_languages,
     )?;
+    validate_language_problem_type(
+        &language,
+        &problem.problem_type,
+        &*state.registries.language_resolver_registry.read().await,
+    )?;
+    validate_contest_language(&language, contest_model.get_allowed_languages().as_deref())?;
+    let skip_judging =
+        check_no_test_cases(&txn, problem_id, state.config.submission.no_test_cases).await?;
+    let files_json = files_to_json(&payload.files);
+    check_duplicate_submission(
+        &txn,
+        auth_user.user_id,
+        problem_id,
+        submission_contest_id,
+        &language,
+        &files_json,
+        state.config.submission.duplicate_window_secs,
+    )
+    .await?;
 
     let enabled_plugins =
-        hooks::fetch_resource_enablements(problem_id, Some(contest_id), &state.db).await?;
+        hooks::fetch_resource_enablements(problem_id, submission_contest_id, &state.db).await?;
     let hook_event = BeforeSubmissionEvent {
         user_id: auth_user.user_id,
         problem_id,
-        contest_id: Some(contest_id),
-        language: payload.language.trim().to_string(),
+        contest_id: submission_contest_id,
+        language: language.clone(),
         file_count: payload.files.len(),
+        filenames: payload
+            .files
+            .iter()
+            .map(|f| f.filename.trim().to_string())
+            .collect(),
     };
     dispatch_before_submission_hooks(&state, &hook_event, Some(&enabled_plugins)).await?;
 
-    let language = payload.language.trim().to_string();
-    let contest_type = match &contest_model.contest_type {
-        Some(ct) => ct.clone(),
-        None => {
-            let reg = state.registries.contest_type_registry.read().await;
-            reg.keys().min().cloned().unwrap_or_default()
-        }
+    let contest_type = if practice {
+        problem.default_contest_type.clone()
+    } else if let Some(ct) = &contest_model.contest_type {
+        ct.clone()
+    } else {
+        let reg = state.registries.contest_type_registry.read().await;
+        reg.keys().min().cloned().unwrap_or_default()
     };
     let new_submission = submission::ActiveModel {
-        files: Set(files_to_json(&payload.files)),
+        files: Set(files_json),
         language: Set(language.clone()),
         status: Set(SubmissionStatus::Pending),
         user_id: Set(auth_user.user_id),
         problem_id: Set(problem_id),
-        contest_id: Set(Some(contest_id)),
+        contest_id: Set(submission_contest_id),
         contest_type: Set(contest_type),
         created_at: Set(now),
         ..Default::default()
     };
 
-    let model = new_submission.insert(&txn).await?;
+    let model = if skip_judging {
+        insert_no_test_cases_submission(&txn, new_submission, now).await?
+    } else {
+        new_submission.insert(&txn).await?
+    };
     txn.commit().await?;
 
     fire_after_submission_hooks(
@@ -2082,23 +3335,31 @@ pub async fn create_contest_submission(
         model.id,
         auth_user.user_id,
         problem_id,
-        Some(contest_id),
+        submission_contest_id,
         language,
         Some(enabled_plugins),
     );
 
-    let state_clone = state.clone();
-    let model_clone = model.clone();
-    tokio::spawn(async move {
-        dispatch_to_plugin(state_clone, model_clone).await;
-    });
+    if !skip_judging {
+        let state_clone = state.clone();
+        let model_clone = model.clone();
+        tokio::spawn(async move {
+            dispatch_to_plugin(state_clone, model_clone).await;
+        });
+    }
 
     let visibility = Some(VisibilityContext {
         viewer_id: auth_user.user_id,
         has_view_all: auth_user.has_permission("submission:view_all"),
     });
-    let response =
-        build_submission_response(&state.db, &*state.blob_store, model, visibility).await?;
+    let response = build_submission_response(
+        &state.db,
+        &*state.blob_store,
+        model,
+        visibility,
+        state.config.submission.max_checker_output_bytes,
+    )
+    .await?;
 
     Ok((StatusCode::CREATED, Json(response)))
 }
@@ -2129,6 +3390,19 @@ pub async fn list_contest_submissions(
     AppPath(contest_id): AppPath<i32>,
     Query(query): Query<SubmissionListQuery>,
 ) -> Result<Json<SubmissionListResponse>, AppError> {
+    let query = match query.view.as_deref() {
+        Some(name) => {
+            let saved = load_saved_view(
+                &state.db,
+                auth_user.user_id,
+                SavedViewResource::Submissions,
+                name,
+            )
+            .await?;
+            query.or_saved(saved)
+        }
+        None => query,
+    };
     validate_sorting_params(
         query.sort_by.as_deref(),
         query.sort_order.as_deref(),
@@ -2147,10 +3421,12 @@ pub async fn list_contest_submissions(
     let can_see_all = can_view_all || contest_model.submissions_visible;
 
     let page = cmp::max(query.page.unwrap_or(1), 1);
-    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let per_page = state.config.server.pagination.per_page(query.per_page);
 
-    let mut base_select =
-        submission::Entity::find().filter(submission::Column::ContestId.eq(Some(contest_id)));
+    let mut base_select = filter_created_range(
+        submission::Entity::find().filter(submission::Column::ContestId.eq(Some(contest_id))),
+        &query,
+    )?;
 
     if !can_see_all {
         base_select = base_select.filter(submission::Column::UserId.eq(auth_user.user_id));
@@ -2168,7 +3444,7 @@ pub async fn list_contest_submissions(
         base_select = base_select.filter(submission::Column::Language.eq(lang.trim()));
     }
     if let Some(status) = query.status {
-        base_select = base_select.filter(submission::Column::Status.eq(status));
+        base_select = filter_visible_status(base_select, status, can_view_all);
     }
 
     let total = base_select.clone().count(&state.db).await?;
@@ -2183,7 +3459,7 @@ pub async fn list_contest_submissions(
 
     let select = match query.sort_by.as_deref().unwrap_or("created_at") {
         "created_at" => select.order_by(submission::Column::CreatedAt, sort_order),
-        "status" => select.order_by(submission::Column::Status, sort_order),
+        "status" => order_by_visible_status(select, sort_order, can_view_all),
         _ => select.order_by(submission::Column::CreatedAt, Order::Desc),
     };
 
@@ -2198,7 +3474,8 @@ pub async fn list_contest_submissions(
         viewer_id: auth_user.user_id,
         has_view_all: can_view_all,
     });
-    let data = apply_filter_to_list(&state, data, visibility.as_ref()).await?;
+    let mut data = apply_filter_to_list(&state, data, visibility.as_ref()).await?;
+    hide_delayed_list_results(&state.db, &mut data, visibility.as_ref()).await?;
     let total_pages = total.div_ceil(per_page);
 
     Ok(Json(SubmissionListResponse {
@@ -2218,7 +3495,7 @@ pub async fn list_contest_submissions(
     tag = "Submissions",
     operation_id = "bulkRejudgeSubmissions",
     summary = "Bulk rejudge submissions",
-    description = "Re-queues submissions in the provided ID list for rejudging. Max 10,000 IDs per request. Requires `submission:rejudge` permission.",
+    description = "Re-queues submissions in the provided ID list for rejudging. Max 10,000 IDs per request. The response carries a `rejudge_batch_id` whose progress can be polled via getRejudgeBatch. Requires `submission:rejudge` permission.",
     request_body = BulkRejudgeRequest,
     responses(
         (status = 200, description = "Submissions re-queued", body = BulkRejudgeResponse),
@@ -2263,8 +3540,83 @@ pub async fn bulk_rejudge_submissions(
     requested_ids.dedup();
     let requested_unique = requested_ids.len();
 
+    let response = requeue_submissions(
+        &state,
+        auth_user.user_id,
+        requested_ids,
+        payload.apply_immediately,
+        new_target,
+    )
+    .await?;
+
+    info!(
+        user_id = auth_user.user_id,
+        requested,
+        requested_unique,
+        queued = response.queued,
+        rejudge_batch_id = ?response.rejudge_batch_id,
+        "Bulk rejudge completed"
+    );
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = "Submissions",
+    operation_id = "getRejudgeBatch",
+    summary = "Get bulk rejudge progress",
+    description = "Returns how many of the judgements queued by a bulk rejudge are still unfinished. Visible to the user who started the rejudge; others need `submission:rejudge` permission.",
+    params(("id" = i32, Path, description = "Rejudge batch ID")),
+    responses(
+        (status = 200, description = "Rejudge batch progress", body = RejudgeBatchResponse),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "Rejudge batch not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user), fields(id))]
+pub async fn get_rejudge_batch(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+) -> Result<Json<RejudgeBatchResponse>, AppError> {
+    let batch = rejudge_batch::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Rejudge batch not found".into()))?;
+    if batch.triggered_by_user_id != auth_user.user_id {
+        auth_user.require_permission("submission:rejudge")?;
+    }
+
+    Ok(Json(batch.into()))
+}
+
+/// Opens a new judgement for every non-archived submission in
+/// `submission_ids` and dispatches it, recording them as one rejudge batch
+/// whose progress can be polled.
+/// `new_target` follows the bulk rejudge convention: `None` keeps existing
+/// worker pins, `Some(None)` clears them and `Some(Some(id))` pins to `id`.
+pub(crate) async fn requeue_submissions(
+    state: &AppState,
+    actor_user_id: i32,
+    submission_ids: Vec<i32>,
+    apply_immediately: bool,
+    new_target: Option<Option<String>>,
+) -> Result<BulkRejudgeResponse, AppError> {
+    // Archived submissions have no source files left to judge.
     let all_ids: Vec<i32> = submission::Entity::find()
-        .filter(submission::Column::Id.is_in(requested_ids.clone()))
+        .filter(submission::Column::Id.is_in(submission_ids))
+        .filter(
+            submission::Column::Id.not_in_subquery(
+                SeaQuery::select()
+                    .column(archived_submission::Column::SubmissionId)
+                    .from(archived_submission::Entity)
+                    .to_owned(),
+            ),
+        )
         .select_only()
         .column(submission::Column::Id)
         .order_by_asc(submission::Column::Id)
@@ -2273,7 +3625,10 @@ pub async fn bulk_rejudge_submissions(
         .await?;
 
     if all_ids.is_empty() {
-        return Ok(Json(BulkRejudgeResponse { queued: 0 }));
+        return Ok(BulkRejudgeResponse {
+            queued: 0,
+            rejudge_batch_id: None,
+        });
     }
 
     const BATCH_SIZE: usize = 500;
@@ -2297,15 +3652,15 @@ pub async fn bulk_rejudge_submissions(
             let new_judgement = open_rejudge_judgement(
                 &txn,
                 sub,
-                auth_user.user_id,
+                actor_user_id,
                 resolved_target.clone(),
                 None,
                 new_epoch,
-                payload.apply_immediately,
+                apply_immediately,
             )
             .await?;
 
-            if payload.apply_immediately {
+            if apply_immediately {
                 let mut active: submission::ActiveModel = sub.clone().into();
                 active.status = Set(SubmissionStatus::Pending);
                 active.verdict = Set(None);
@@ -2315,7 +3670,9 @@ pub async fn bulk_rejudge_submissions(
                 active.score = Set(None);
                 active.time_used = Set(None);
                 active.memory_used = Set(None);
+                active.subtask_results = Set(None);
                 active.judged_at = Set(None);
+                active.judged_by = Set(None);
                 active.judge_epoch = Set(new_epoch);
                 if let Some(target) = new_target.clone() {
                     active.target_worker_id = Set(target);
@@ -2335,10 +3692,12 @@ pub async fn bulk_rejudge_submissions(
     }
 
     let queued = all_enqueue_data.len();
+    let judgement_ids = all_enqueue_data.iter().map(|(_, id)| *id).collect();
+    let batch = open_rejudge_batch(&state.db, actor_user_id, judgement_ids).await?;
 
     for (sub, judgement_id) in all_enqueue_data {
         let state_clone = state.clone();
-        let fire_after_judging = payload.apply_immediately;
+        let fire_after_judging = apply_immediately;
         tokio::spawn(async move {
             dispatch_to_plugin_with_judgement(
                 state_clone,
@@ -2350,12 +3709,10 @@ pub async fn bulk_rejudge_submissions(
         });
     }
 
-    info!(
-        user_id = auth_user.user_id,
-        requested, requested_unique, queued, "Bulk rejudge completed"
-    );
-
-    Ok(Json(BulkRejudgeResponse { queued }))
+    Ok(BulkRejudgeResponse {
+        queued,
+        rejudge_batch_id: Some(batch.id),
+    })
 }
 
 pub fn submission_body_limit(max_size: usize) -> axum::extract::DefaultBodyLimit {
@@ -2387,10 +3744,17 @@ pub async fn admin_fan_out_submission(
 ) -> Result<impl IntoResponse, AppError> {
     auth_user.require_permission("system:admin")?;
     validate_admin_fan_out(&payload)?;
-    validate_code_payload(
+    validate_code_payload(&payload.files, &payload.language, &state.config.submission)?;
+    validate_file_encoding(
         &payload.files,
-        &payload.language,
-        state.config.submission.max_size,
+        state
+            .registries
+            .language_resolver_registry
+            .read()
+            .await
+            .get(&payload.language)
+            .map(|entry| entry.binary_extensions.as_slice())
+            .unwrap_or_default(),
     )?;
 
     let live_workers = crate::handlers::system::live_worker_ids(&state).await;
@@ -2425,6 +3789,11 @@ pub async fn admin_fan_out_submission(
         problem.get_submission_format(),
         &known_languages,
     )?;
+    validate_language_problem_type(
+        &payload.language,
+        &problem.problem_type,
+        &*state.registries.language_resolver_registry.read().await,
+    )?;
 
     if let Some(contest_id) = payload.contest_id {
         let _contest = find_contest(&txn, contest_id).await?;
@@ -2505,8 +3874,14 @@ pub async fn admin_fan_out_submission(
             dispatch_to_plugin(dispatch_state, dispatch_model).await;
         });
 
-        let response =
-            build_submission_response(&state.db, &*state.blob_store, model, visibility).await?;
+        let response = build_submission_response(
+            &state.db,
+            &*state.blob_store,
+            model,
+            visibility,
+            state.config.submission.max_checker_output_bytes,
+        )
+        .await?;
         responses.push(response);
     }
 
@@ -3,7 +3,6 @@ use std::collections::HashMap;
 use axum::{Json, extract::State};
 use chrono::Utc;
 use common::SubmissionStatus;
-use redis::AsyncCommands;
 use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
 use tracing::{instrument, warn};
 
@@ -14,17 +13,15 @@ use crate::models::system::{
     QueueInfo, QueuesResponse, SystemOverviewResponse, WorkerInfo, WorkersResponse,
 };
 use crate::state::AppState;
-
-const HEARTBEAT_KEY_PREFIX: &str = "broccoli:worker:heartbeat:";
-const STALE_AFTER_SECS: i64 = 10;
+use crate::utils::worker as worker_registry;
 
 #[utoipa::path(
     get,
     path = "/workers",
     tag = "System",
     operation_id = "listSystemWorkers",
-    summary = "List active workers",
-    description = "Returns workers that have written a heartbeat to Redis within the past ~15 seconds. Requires `system:view` permission.",
+    summary = "List workers",
+    description = "Returns every worker that has published a heartbeat, with its last recorded `last_seen`. Workers without a Redis heartbeat from the last 10 seconds are flagged `stale`. Requires `system:view` permission.",
     responses(
         (status = 200, description = "List of workers", body = WorkersResponse),
         (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
@@ -114,92 +111,39 @@ pub async fn system_overview(
     }))
 }
 
-/// Returns the set of worker IDs that have a live (non-stale) heartbeat in
-/// Redis. Used by admin endpoints that need to validate `target_worker_id`
-/// values before they are persisted on submissions.
+/// Returns the IDs of workers with a fresh heartbeat key in Redis. Used by
+/// admin endpoints that need to validate `target_worker_id` values before
+/// they are persisted on submissions.
 pub(crate) async fn live_worker_ids(state: &AppState) -> std::collections::HashSet<String> {
-    read_workers(state)
+    let now = Utc::now();
+    read_live_heartbeats(state)
         .await
         .into_iter()
-        .filter(|w| !w.stale)
-        .map(|w| w.id)
+        .filter(|hb| !worker_registry::is_stale(hb.last_seen, now))
+        .map(|hb| hb.id)
         .collect()
 }
 
-async fn read_workers(state: &AppState) -> Vec<WorkerInfo> {
-    let Some(client) = state.redis_client.as_ref() else {
-        return Vec::new();
-    };
-
-    let mut conn = match client.get_multiplexed_async_connection().await {
-        Ok(c) => c,
-        Err(e) => {
-            warn!(error = %e, "Failed to connect to Redis for worker heartbeats");
-            return Vec::new();
-        }
-    };
-
-    let pattern = format!("{HEARTBEAT_KEY_PREFIX}*");
-    let keys: Vec<String> = match conn.scan_match::<&str, String>(&pattern).await {
-        Ok(mut iter) => {
-            let mut acc: Vec<String> = Vec::new();
-            while let Some(item) = iter.next_item().await {
-                match item {
-                    Ok(key) => acc.push(key),
-                    Err(e) => {
-                        warn!(error = %e, "Worker heartbeat SCAN entry failed");
-                        return Vec::new();
-                    }
-                }
-            }
-            acc
-        }
-        Err(e) => {
-            warn!(error = %e, "Worker heartbeat SCAN failed");
-            return Vec::new();
-        }
-    };
-
-    if keys.is_empty() {
-        return Vec::new();
+async fn read_live_heartbeats(state: &AppState) -> Vec<worker_registry::WorkerHeartbeat> {
+    match state.redis_client.as_ref() {
+        Some(client) => worker_registry::read_heartbeats(client).await,
+        None => Vec::new(),
     }
+}
 
-    let values: Vec<Option<String>> = match conn.mget(&keys).await {
-        Ok(v) => v,
+/// Lists the workers recorded in the `worker` table together with any whose
+/// Redis heartbeat has not been recorded yet. Liveness comes from the
+/// heartbeats; the table only supplies history and `last_seen`.
+async fn read_workers(state: &AppState) -> Vec<WorkerInfo> {
+    let heartbeats = read_live_heartbeats(state).await;
+    let rows = match worker_registry::list_known_workers(&state.db).await {
+        Ok(rows) => rows,
         Err(e) => {
-            warn!(error = %e, "Worker heartbeat MGET failed");
-            return Vec::new();
+            warn!(error = %e, "Failed to list recorded workers, showing Redis heartbeats only");
+            Vec::new()
         }
     };
-
-    let now = Utc::now();
-    let mut workers: Vec<WorkerInfo> = values
-        .into_iter()
-        .filter_map(|v| v.and_then(|s| serde_json::from_str::<HeartbeatPayload>(&s).ok()))
-        .map(|p| {
-            let elapsed = (now - p.last_seen).num_seconds();
-            WorkerInfo {
-                id: p.id,
-                started_at: p.started_at,
-                last_seen: p.last_seen,
-                seconds_since_last_seen: elapsed.max(0) as u64,
-                stale: elapsed > STALE_AFTER_SECS,
-                in_flight: p.in_flight,
-                max_concurrency: p.max_concurrency,
-                sandbox_backend: p.sandbox_backend,
-                version: p.version,
-                hostname: p.hostname,
-                ip_addresses: p.ip_addresses,
-                os: p.os,
-                arch: p.arch,
-                cpu_count: p.cpu_count,
-                pid: p.pid,
-            }
-        })
-        .collect();
-
-    workers.sort_by(|a, b| a.id.cmp(&b.id));
-    workers
+    worker_registry::merge_workers(rows, heartbeats, Utc::now())
 }
 
 async fn read_queues(state: &AppState) -> Vec<QueueInfo> {
@@ -207,11 +151,15 @@ async fn read_queues(state: &AppState) -> Vec<QueueInfo> {
         return Vec::new();
     };
 
-    let queue_names = [
-        state.config.mq.operation_queue_name.clone(),
-        state.config.mq.operation_result_queue_name.clone(),
-        state.config.mq.operation_dlq_queue_name.clone(),
-    ];
+    let queue_names: Vec<String> = std::iter::once(state.config.mq.operation_queue_name.clone())
+        .chain(crate::config::operation_result_queue_names(
+            &state.config.mq.operation_result_queue_name,
+            state.config.mq.operation_result_contest_shards,
+        ))
+        .chain(std::iter::once(
+            state.config.mq.operation_dlq_queue_name.clone(),
+        ))
+        .collect();
 
     let mut out = Vec::with_capacity(queue_names.len());
     for name in queue_names {
@@ -231,26 +179,3 @@ async fn read_queues(state: &AppState) -> Vec<QueueInfo> {
     }
     out
 }
-
-#[derive(serde::Deserialize)]
-struct HeartbeatPayload {
-    id: String,
-    started_at: chrono::DateTime<Utc>,
-    last_seen: chrono::DateTime<Utc>,
-    in_flight: u32,
-    max_concurrency: Option<u32>,
-    sandbox_backend: String,
-    version: String,
-    #[serde(default)]
-    hostname: Option<String>,
-    #[serde(default)]
-    ip_addresses: Vec<String>,
-    #[serde(default)]
-    os: Option<String>,
-    #[serde(default)]
-    arch: Option<String>,
-    #[serde(default)]
-    cpu_count: Option<u32>,
-    #[serde(default)]
-    pid: Option<u32>,
-}
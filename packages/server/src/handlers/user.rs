@@ -11,10 +11,13 @@ use crate::entity::{contest, contest_user, refresh_token, role, user, user_role}
 use crate::error::{AppError, ErrorBody};
 use crate::extractors::auth::AuthUser;
 use crate::extractors::path::AppPath;
-use crate::models::user::{RoleAssignmentRequest, UpdateUserRequest, UserResponse};
+use crate::models::user::{
+    ResetPasswordRequest, ResetPasswordResponse, RoleAssignmentRequest, UpdateUserRequest,
+    UpdateUserStatusRequest, UserResponse, validate_reset_password_request,
+};
 use crate::state::AppState;
-use crate::utils::hash;
 use crate::utils::soft_delete::SoftDeletable;
+use crate::utils::{hash, password};
 
 #[utoipa::path(
     get,
@@ -116,6 +119,7 @@ pub async fn update_user(
 
     let txn = state.db.begin().await?;
 
+    let token_version = user_model.token_version;
     let mut active: user::ActiveModel = user_model.into();
     if let Some(username) = payload.username {
         active.username = Set(username);
@@ -124,6 +128,7 @@ pub async fn update_user(
         let password_hash = hash::hash_password(&password)
             .map_err(|_| AppError::Validation("Failed to hash password".into()))?;
         active.password = Set(password_hash);
+        active.token_version = Set(token_version + 1);
         refresh_token::Entity::revoke_all_for_user(&txn, id).await?;
     }
     let updated_user = active.update(&txn).await?;
@@ -140,6 +145,123 @@ pub async fn update_user(
     Ok(Json(UserResponse::from(user_with_roles)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/{id}/reset-password",
+    tag = "Users",
+    operation_id = "resetUserPassword",
+    summary = "Reset a user's password",
+    description = "Sets the user's password to the one supplied, or to a generated one which is returned in `generated_password`. Revokes all of the user's refresh tokens and rejects their existing access tokens with 401 TOKEN_INVALID, ending their sessions. Requires `user:manage` permission.",
+    params(("id" = i32, Path, description = "User ID")),
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password reset", body = ResetPasswordResponse),
+        (status = 400, description = "Validation error (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "User not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, payload), fields(id, user_id = auth_user.user_id))]
+pub async fn reset_password(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+    Json(payload): Json<ResetPasswordRequest>,
+) -> Result<Json<ResetPasswordResponse>, AppError> {
+    auth_user.require_permission("user:manage")?;
+    validate_reset_password_request(&payload)?;
+
+    let user_model = user::Entity::find_active_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+
+    let (new_password, generated_password) = match payload.password {
+        Some(new_password) => (new_password, None),
+        None => {
+            let generated = password::generate_password(12);
+            (generated.clone(), Some(generated))
+        }
+    };
+    let password_hash = hash::hash_password(&new_password)
+        .map_err(|_| AppError::Internal("Failed to hash password".into()))?;
+
+    let txn = state.db.begin().await?;
+    let username = user_model.username.clone();
+    let token_version = user_model.token_version;
+    let mut active: user::ActiveModel = user_model.into();
+    active.password = Set(password_hash);
+    active.token_version = Set(token_version + 1);
+    active.update(&txn).await?;
+    refresh_token::Entity::revoke_all_for_user(&txn, id).await?;
+    txn.commit().await?;
+
+    Ok(Json(ResetPasswordResponse {
+        user_id: id,
+        username,
+        generated_password,
+    }))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/{id}/status",
+    tag = "Users",
+    operation_id = "updateUserStatus",
+    summary = "Activate or deactivate a user",
+    description = "Deactivated users cannot log in, and requests with their existing access tokens are rejected with 403 ACCOUNT_DEACTIVATED. Deactivation also revokes all of the user's refresh tokens. Users cannot deactivate themselves. Requires `user:manage` permission.",
+    params(("id" = i32, Path, description = "User ID")),
+    request_body = UpdateUserStatusRequest,
+    responses(
+        (status = 200, description = "Updated user details", body = UserResponse),
+        (status = 400, description = "Validation error (VALIDATION_ERROR)", body = ErrorBody),
+        (status = 401, description = "Unauthorized (TOKEN_MISSING, TOKEN_INVALID)", body = ErrorBody),
+        (status = 403, description = "Forbidden (PERMISSION_DENIED)", body = ErrorBody),
+        (status = 404, description = "User not found (NOT_FOUND)", body = ErrorBody),
+    ),
+    security(("jwt" = [])),
+)]
+#[instrument(skip(state, auth_user, payload), fields(id, user_id = auth_user.user_id))]
+pub async fn update_user_status(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    AppPath(id): AppPath<i32>,
+    Json(payload): Json<UpdateUserStatusRequest>,
+) -> Result<Json<UserResponse>, AppError> {
+    auth_user.require_permission("user:manage")?;
+
+    if id == auth_user.user_id && !payload.is_active {
+        return Err(AppError::Validation(
+            "You cannot deactivate your own account".into(),
+        ));
+    }
+
+    let user_model = user::Entity::find_active_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+
+    let txn = state.db.begin().await?;
+    let mut active: user::ActiveModel = user_model.into();
+    active.is_active = Set(payload.is_active);
+    active.update(&txn).await?;
+    if !payload.is_active {
+        refresh_token::Entity::revoke_all_for_user(&txn, id).await?;
+    }
+    txn.commit().await?;
+
+    let user_with_roles = user::Entity::load()
+        .filter(user::Column::Id.eq(id))
+        .with(role::Entity)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found after update".into()))?;
+
+    Ok(Json(UserResponse::from(user_with_roles)))
+}
+
 #[utoipa::path(
     delete,
     path = "/{id}",
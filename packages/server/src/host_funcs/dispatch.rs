@@ -1,10 +1,10 @@
 use crate::registry::{BatchState, OperationBatches, OperationWaiters};
 use broccoli_server_sdk::types::{OperationTask, SessionFile};
 use common::storage::BlobStore;
-use common::worker::{Task, TaskResult};
+use common::worker::{TASK_SCHEMA_VERSION, Task, TaskResult};
 use extism::{Function, UserData, Val, ValType};
-use mq::MqQueue;
 use mq::config::PublishConfig;
+use mq::{Envelope, MessageHeaders, MqQueue};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
@@ -37,6 +37,7 @@ struct DispatchContext {
     waiters: OperationWaiters,
     operation_queue_name: String,
     result_queue_name: String,
+    result_contest_shards: usize,
 }
 
 type DispatchUserData = DispatchContext;
@@ -50,6 +51,7 @@ fn is_valid_worker_id(id: &str) -> bool {
     crate::config::is_valid_server_id(id)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_dispatch_functions(
     plugin_id: String,
     mq: Option<Arc<MqQueue>>,
@@ -58,6 +60,7 @@ pub fn create_dispatch_functions(
     operation_waiters: OperationWaiters,
     operation_queue_name: String,
     operation_result_queue_name: String,
+    operation_result_contest_shards: usize,
 ) -> Vec<Function> {
     let user_data: UserData<DispatchUserData> = UserData::new(DispatchContext {
         plugin_id,
@@ -67,6 +70,7 @@ pub fn create_dispatch_functions(
         waiters: operation_waiters,
         operation_queue_name,
         result_queue_name: operation_result_queue_name,
+        result_contest_shards: operation_result_contest_shards,
     });
 
     vec![
@@ -104,7 +108,16 @@ fn start_operation_batch_fn(
     let operations: Vec<OperationTask> = serde_json::from_slice(&input_bytes)
         .map_err(|e| extism::Error::msg(format!("Failed to deserialize operations: {}", e)))?;
 
-    let (plugin_id, mq, blob_store, batches, waiters, queue_name, result_queue_name) = {
+    let (
+        plugin_id,
+        mq,
+        blob_store,
+        batches,
+        waiters,
+        queue_name,
+        result_queue_name,
+        result_contest_shards,
+    ) = {
         let user_data_guard = user_data.get()?;
         let guard = user_data_guard
             .lock()
@@ -117,6 +130,7 @@ fn start_operation_batch_fn(
             guard.waiters.clone(),
             guard.operation_queue_name.clone(),
             guard.result_queue_name.clone(),
+            guard.result_contest_shards,
         )
     };
 
@@ -174,6 +188,7 @@ fn start_operation_batch_fn(
                         success: false,
                         output: serde_json::json!({}),
                         error: Some("Operation cancelled or timed out".into()),
+                        worker_id: None,
                     };
                     let _ = batch_tx.send(error_result);
                     pending_count.fetch_sub(1, Ordering::SeqCst);
@@ -210,26 +225,41 @@ fn start_operation_batch_fn(
             extism::Error::msg(format!("Blob store error: {}", e))
         })?;
 
+        let reply_queue = crate::config::contest_result_queue_name(
+            &result_queue_name,
+            result_contest_shards,
+            op.contest_id,
+        );
         let task = Task {
             id: correlation_id.clone(),
             task_type: "operation".to_string(),
             executor_name: "operation".to_string(),
             payload: serde_json::to_value(&op)
                 .map_err(|e| extism::Error::msg(format!("Failed to serialize operation: {}", e)))?,
-            result_queue: result_queue_name.clone(),
-            reply_queue: Some(result_queue_name.clone()),
+            result_queue: reply_queue.clone(),
+            reply_queue: Some(reply_queue),
             priority: op.priority,
             trace_context: common::observability::inject_trace_context(),
+            schema_version: Some(TASK_SCHEMA_VERSION),
         };
+        let headers = MessageHeaders::json()
+            .with_schema_version(TASK_SCHEMA_VERSION)
+            .with_trace_id(
+                task.trace_context
+                    .as_deref()
+                    .and_then(common::observability::trace_id_from_traceparent)
+                    .map(str::to_string),
+            );
+        let priority = task.priority;
+        let envelope = Envelope::new(headers, task);
 
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current().block_on(async {
                 mq.publish(
                     &target_queue,
                     None,
-                    &task,
-                    task.priority
-                        .map(|p| PublishConfig::builder().priority(p).build()),
+                    &envelope,
+                    priority.map(|p| PublishConfig::builder().priority(p).build()),
                 )
                 .await
             })
@@ -7,7 +7,7 @@ use common::submission_dispatch::{
 };
 use extism::{Function, UserData, Val, ValType};
 use plugin_core::traits::PluginManager;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -244,8 +244,50 @@ fn start_evaluate_batch_fn(
                         .collect()
                 };
 
+                let stored_ids: Vec<i32> = test_cases
+                    .iter()
+                    .filter(|tc| !tc.is_custom)
+                    .map(|tc| tc.test_case_id)
+                    .collect();
+                let stored_meta: HashMap<i32, (String, Option<serde_json::Value>)> = if stored_ids
+                    .is_empty()
+                {
+                    HashMap::new()
+                } else {
+                    test_case::Entity::find()
+                        .select_only()
+                        .column(test_case::Column::Id)
+                        .column(test_case::Column::Label)
+                        .column(test_case::Column::AcceptedOutputs)
+                        .filter(test_case::Column::ProblemId.eq(problem_id))
+                        .filter(test_case::Column::Id.is_in(stored_ids))
+                        .into_tuple::<(i32, String, Option<serde_json::Value>)>()
+                        .all(&db)
+                        .await
+                        .map_err(|e| {
+                            extism::Error::msg(format!("Failed to query test case metadata: {}", e))
+                        })?
+                        .into_iter()
+                        .map(|(id, label, accepted)| (id, (label, accepted)))
+                        .collect()
+                };
+
                 let mut resolved = Vec::with_capacity(test_cases.len());
                 for tc in test_cases {
+                    let (test_case_label, accepted_outputs) = if tc.is_custom {
+                        (None, None)
+                    } else {
+                        match stored_meta.get(&tc.test_case_id) {
+                            Some((label, accepted)) => (Some(label.clone()), accepted.clone()),
+                            None => (None, None),
+                        }
+                    };
+                    let tc_checker_config = match accepted_outputs {
+                        Some(outputs) => {
+                            with_accepted_outputs(checker_config_value.clone(), outputs)
+                        }
+                        None => checker_config_value.clone(),
+                    };
                     let db_case = if !tc.is_custom
                         && (tc.input.is_missing() || tc.expected_output.is_missing())
                     {
@@ -301,18 +343,23 @@ fn start_evaluate_batch_fn(
                     resolved.push(BuildEvalOpsInput {
                         problem_id: tc.problem_id,
                         test_case_id: tc.test_case_id,
+                        test_case_label,
                         solution_source: tc.solution_source,
                         solution_language: tc.solution_language,
                         time_limit_ms: tc.time_limit_ms,
                         memory_limit_kb: tc.memory_limit_kb,
+                        wall_time_multiplier: problem_model.wall_time_multiplier,
+                        require_zero_exit: problem_model.require_zero_exit,
                         contest_id: tc.contest_id,
                         test_input: test_input.file,
                         expected_output: expected_output.file,
                         checker_format: tc_checker_format,
-                        checker_config: checker_config_value.clone(),
+                        checker_config: tc_checker_config,
                         checker_source: parsed_checker_source.clone(),
                         additional_file_refs: additional_file_refs.clone(),
                         target_worker_id: tc.target_worker_id,
+                        judge_mode: tc.judge_mode,
+                        judge_job: tc.judge_job,
                     });
                 }
 
@@ -370,6 +417,7 @@ fn start_evaluate_batch_fn(
                         message: Some("Evaluator dispatcher is shutting down".into()),
                         stdout: None,
                         stderr: None,
+                        worker_id: None,
                     });
                     pending.fetch_sub(1, Ordering::SeqCst);
                     return;
@@ -388,6 +436,7 @@ fn start_evaluate_batch_fn(
                         message: Some(format!("Failed to serialize evaluator input: {}", e)),
                         stdout: None,
                         stderr: None,
+                        worker_id: None,
                     });
                     pending.fetch_sub(1, Ordering::SeqCst);
                     return;
@@ -416,6 +465,7 @@ fn start_evaluate_batch_fn(
                                 )),
                                 stdout: None,
                                 stderr: None,
+                                worker_id: None,
                             });
                         }
                     }
@@ -430,6 +480,7 @@ fn start_evaluate_batch_fn(
                         message: Some(format!("Evaluator call failed: {}", e)),
                         stdout: None,
                         stderr: None,
+                        worker_id: None,
                     });
                 }
             }
@@ -451,6 +502,20 @@ struct ResolvedEvaluateBody {
     present: bool,
 }
 
+/// Adds a test case's `accepted_outputs` to the problem's checker config,
+/// where the standard checkers read it.
+fn with_accepted_outputs(
+    config: Option<serde_json::Value>,
+    outputs: serde_json::Value,
+) -> Option<serde_json::Value> {
+    let mut config = match config {
+        Some(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    config.insert("accepted_outputs".to_string(), outputs);
+    Some(serde_json::Value::Object(config))
+}
+
 async fn resolve_evaluate_body(
     body: TestCaseBodyRef,
     db_inline: Option<String>,
@@ -73,6 +73,50 @@ pub fn init_host_functions(
         )
     });
 
+    let db_clone = db.clone();
+    hr.register("storage", move |plugin_id| {
+        Function::new(
+            "store_set_many",
+            [ValType::I64, ValType::I64],
+            [],
+            UserData::new((plugin_id.to_string(), db_clone.clone())),
+            storage::store_set_many,
+        )
+    });
+
+    let db_clone = db.clone();
+    hr.register("storage", move |plugin_id| {
+        Function::new(
+            "store_get_many",
+            [ValType::I64, ValType::I64],
+            [ValType::I64],
+            UserData::new((plugin_id.to_string(), db_clone.clone())),
+            storage::store_get_many,
+        )
+    });
+
+    let db_clone = db.clone();
+    hr.register("storage", move |plugin_id| {
+        Function::new(
+            "store_keys",
+            [ValType::I64, ValType::I64],
+            [ValType::I64],
+            UserData::new((plugin_id.to_string(), db_clone.clone())),
+            storage::store_keys,
+        )
+    });
+
+    let db_clone = db.clone();
+    hr.register("storage", move |plugin_id| {
+        Function::new(
+            "store_has",
+            [ValType::I64],
+            [ValType::I64],
+            UserData::new((plugin_id.to_string(), db_clone.clone())),
+            storage::store_has,
+        )
+    });
+
     let db_clone = db.clone();
     hr.register("storage", move |plugin_id| {
         Function::new(
@@ -257,6 +301,7 @@ pub fn init_host_functions(
     let waiters = operation_waiters;
     let op_queue = config.mq.operation_queue_name.clone();
     let res_queue = config.mq.operation_result_queue_name.clone();
+    let res_contest_shards = config.mq.operation_result_contest_shards;
     hr.register_many("operations:dispatch", move |plugin_id| {
         dispatch::create_dispatch_functions(
             plugin_id.to_string(),
@@ -266,6 +311,7 @@ pub fn init_host_functions(
             waiters.clone(),
             op_queue.clone(),
             res_queue.clone(),
+            res_contest_shards,
         )
     });
 
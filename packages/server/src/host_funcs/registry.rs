@@ -16,6 +16,8 @@ struct RegisterContestTypeInput {
     code_run_handler: String,
     #[serde(default)]
     filter_submission_handler: Option<String>,
+    #[serde(default)]
+    summed_scores: bool,
 }
 
 #[derive(Deserialize)]
@@ -43,7 +45,11 @@ struct RegisterLanguageResolverInput {
     #[serde(default)]
     extensions: Vec<String>,
     #[serde(default)]
+    binary_extensions: Vec<String>,
+    #[serde(default)]
     template: String,
+    #[serde(default)]
+    problem_types: Vec<String>,
 }
 
 fn default_source_filename() -> String {
@@ -185,6 +191,7 @@ fn register_contest_type_fn(
                         .as_ref()
                         .filter(|s| !s.is_empty())
                         .cloned(),
+                    summed_scores: input.summed_scores,
                 },
             );
             tracing::info!(
@@ -193,6 +200,7 @@ fn register_contest_type_fn(
                 submission_fn = %input.submission_handler,
                 code_run_fn = %input.code_run_handler,
                 filter_submission_fn = ?input.filter_submission_handler,
+                summed_scores = input.summed_scores,
                 "Contest type registered"
             );
         })
@@ -300,12 +308,15 @@ fn register_language_resolver_fn(
         input.display_name
     };
 
-    let extensions: Vec<String> = input
-        .extensions
-        .into_iter()
-        .map(|e| e.trim_start_matches('.').to_ascii_lowercase())
-        .filter(|e| !e.is_empty())
-        .collect();
+    let normalize_extensions = |extensions: Vec<String>| -> Vec<String> {
+        extensions
+            .into_iter()
+            .map(|e| e.trim_start_matches('.').to_ascii_lowercase())
+            .filter(|e| !e.is_empty())
+            .collect()
+    };
+    let extensions = normalize_extensions(input.extensions);
+    let binary_extensions = normalize_extensions(input.binary_extensions);
 
     tokio::task::block_in_place(|| {
         tokio::runtime::Handle::current().block_on(async {
@@ -318,7 +329,9 @@ fn register_language_resolver_fn(
                     display_name,
                     default_filename: input.default_filename,
                     extensions,
+                    binary_extensions,
                     template: input.template,
+                    problem_types: input.problem_types,
                 },
             );
             tracing::info!(
@@ -5,10 +5,10 @@ use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 use common::storage::{BlobStore, ContentHash};
 use dashmap::DashMap;
 use extism::host_fn;
-use sea_orm::sea_query::OnConflict;
+use sea_orm::sea_query::{LikeExpr, OnConflict};
 use sea_orm::{
-    ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, QueryFilter, Set,
-    Statement, TransactionTrait,
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, Statement, TransactionTrait,
 };
 use serde::Deserialize;
 use serde_json::Value;
@@ -177,6 +177,40 @@ host_fn!(pub store_get(user_data: (String, DatabaseConnection); input: String) -
     Ok(serde_json::json!({ "values": values }).to_string())
 });
 
+#[derive(Deserialize)]
+struct StoreHasInput {
+    key: String,
+}
+
+// Reports whether `key` exists without transferring its value, so guests can
+// tell a missing key apart from one whose stored value is the string `"null"`.
+host_fn!(pub store_has(user_data: (String, DatabaseConnection); input: String) -> String {
+    let user_data_guard = user_data.get()?;
+    let user_data = user_data_guard.lock().map_err(|_| extism::Error::msg("Lock poisoned"))?;
+    let (plugin_id, db) = &*user_data;
+
+    let mut parsed: StoreHasInput = serde_json::from_str(&input)
+        .map_err(|e| extism::Error::msg(format!("Invalid store_has input: {e}")))?;
+    parsed.key = sanitize_db_text(&parsed.key);
+
+    let count = tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(async {
+            plugin_storage::Entity::find()
+                .filter(plugin_storage::Column::PluginId.eq(plugin_id))
+                .filter(plugin_storage::Column::Collection.eq(DEFAULT_COLLECTION))
+                .filter(plugin_storage::Column::Key.eq(&parsed.key))
+                .count(db)
+                .await
+        })
+    })
+    .map_err(|e| {
+        error!("DB store_has error: {e}");
+        extism::Error::msg("Database error")
+    })?;
+
+    Ok(serde_json::json!({ "exists": count > 0 }).to_string())
+});
+
 #[derive(Deserialize)]
 struct StoreSetEntry {
     key: String,
@@ -207,42 +241,207 @@ host_fn!(pub store_set(user_data: (String, DatabaseConnection); input: String) -
     tokio::task::block_in_place(|| {
         tokio::runtime::Handle::current().block_on(async {
             let txn = db.begin().await?;
+            upsert_entries(&txn, plugin_id, DEFAULT_COLLECTION, parsed.entries).await?;
+            txn.commit().await
+        })
+    })
+    .map_err(|e| {
+        error!("DB store_set error: {e}");
+        extism::Error::msg("Database error")
+    })?;
 
-            for entry in parsed.entries {
-                let model = plugin_storage::ActiveModel {
-                    plugin_id: Set(plugin_id.clone()),
-                    collection: Set(DEFAULT_COLLECTION.to_string()),
-                    key: Set(entry.key),
-                    data: Set(Value::String(entry.value)),
-                    created_at: Set(chrono::Utc::now()),
-                };
-
-                plugin_storage::Entity::insert(model)
-                    .on_conflict(
-                        OnConflict::columns([
-                            plugin_storage::Column::PluginId,
-                            plugin_storage::Column::Collection,
-                            plugin_storage::Column::Key,
-                        ])
-                        .update_columns([plugin_storage::Column::Data])
-                        .to_owned(),
-                    )
-                    .exec(&txn)
-                    .await?;
-            }
+    Ok(())
+});
+
+/// Upper bound on entries or keys in a single batch call. Keeps each batch to
+/// one statement well inside Postgres' bind-parameter limit.
+pub const MAX_BATCH_ENTRIES: usize = 1000;
+
+/// Upsert `entries` into `collection`, one multi-row statement per
+/// `MAX_BATCH_ENTRIES` chunk. Later entries win when a key repeats, since one
+/// statement cannot update the same row twice.
+async fn upsert_entries<C: ConnectionTrait>(
+    db: &C,
+    plugin_id: &str,
+    collection: &str,
+    entries: Vec<StoreSetEntry>,
+) -> Result<(), sea_orm::DbErr> {
+    let deduped: Vec<(String, String)> = entries
+        .into_iter()
+        .map(|e| (e.key, e.value))
+        .collect::<HashMap<_, _>>()
+        .into_iter()
+        .collect();
+
+    let now = chrono::Utc::now();
+    for chunk in deduped.chunks(MAX_BATCH_ENTRIES) {
+        let models = chunk
+            .iter()
+            .map(|(key, value)| plugin_storage::ActiveModel {
+                plugin_id: Set(plugin_id.to_string()),
+                collection: Set(collection.to_string()),
+                key: Set(key.clone()),
+                data: Set(Value::String(value.clone())),
+                created_at: Set(now),
+            });
+
+        plugin_storage::Entity::insert_many(models)
+            .on_conflict(
+                OnConflict::columns([
+                    plugin_storage::Column::PluginId,
+                    plugin_storage::Column::Collection,
+                    plugin_storage::Column::Key,
+                ])
+                .update_columns([plugin_storage::Column::Data])
+                .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await?;
+    }
+    Ok(())
+}
+
+fn parse_collection(collection: &str) -> Result<String, extism::Error> {
+    let collection = sanitize_db_text(collection.trim());
+    if collection.is_empty() {
+        return Err(extism::Error::msg("collection must not be empty"));
+    }
+    Ok(collection)
+}
+
+fn check_batch_size(func: &str, len: usize) -> Result<(), extism::Error> {
+    if len > MAX_BATCH_ENTRIES {
+        return Err(extism::Error::msg(format!(
+            "{func} batch of {len} exceeds the limit of {MAX_BATCH_ENTRIES}"
+        )));
+    }
+    Ok(())
+}
+
+host_fn!(pub store_set_many(user_data: (String, DatabaseConnection); collection: String, entries_json: String) -> () {
+    let user_data_guard = user_data.get()?;
+    let user_data = user_data_guard.lock().map_err(|_| extism::Error::msg("Lock poisoned"))?;
+    let (plugin_id, db) = &*user_data;
 
-            txn.commit().await?;
-            Ok::<(), sea_orm::DbErr>(())
+    let collection = parse_collection(&collection)?;
+    let mut entries: Vec<StoreSetEntry> = serde_json::from_str(&entries_json)
+        .map_err(|e| extism::Error::msg(format!("Invalid store_set_many entries: {e}")))?;
+    check_batch_size("store_set_many", entries.len())?;
+    for entry in &mut entries {
+        entry.key = sanitize_db_text(&entry.key);
+        entry.value = sanitize_db_text(&entry.value);
+    }
+
+    tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(async {
+            upsert_entries(db, plugin_id, &collection, entries).await
         })
     })
     .map_err(|e| {
-        error!("DB store_set error: {e}");
+        error!("DB store_set_many error: {e}");
         extism::Error::msg("Database error")
     })?;
 
     Ok(())
 });
 
+host_fn!(pub store_get_many(user_data: (String, DatabaseConnection); collection: String, keys_json: String) -> String {
+    let user_data_guard = user_data.get()?;
+    let user_data = user_data_guard.lock().map_err(|_| extism::Error::msg("Lock poisoned"))?;
+    let (plugin_id, db) = &*user_data;
+
+    let collection = parse_collection(&collection)?;
+    let keys: Vec<String> = serde_json::from_str(&keys_json)
+        .map_err(|e| extism::Error::msg(format!("Invalid store_get_many keys: {e}")))?;
+    check_batch_size("store_get_many", keys.len())?;
+    let keys: Vec<String> = keys.into_iter().map(sanitize_db_text).collect();
+
+    if keys.is_empty() {
+        return Ok(serde_json::json!({ "values": {} }).to_string());
+    }
+
+    let results = tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(async {
+            plugin_storage::Entity::find()
+                .filter(plugin_storage::Column::PluginId.eq(plugin_id))
+                .filter(plugin_storage::Column::Collection.eq(&collection))
+                .filter(plugin_storage::Column::Key.is_in(&keys))
+                .all(db)
+                .await
+        })
+    })
+    .map_err(|e| {
+        error!("DB store_get_many error: {e}");
+        extism::Error::msg("Database error")
+    })?;
+
+    let values: HashMap<&str, String> = results
+        .iter()
+        .map(|r| (r.key.as_str(), extract_str(&r.data)))
+        .collect();
+
+    Ok(serde_json::json!({ "values": values }).to_string())
+});
+
+/// Maximum number of keys a single `store_keys` call returns.
+pub const MAX_LISTED_KEYS: u64 = 1000;
+
+fn escape_like(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod escape_like_tests {
+    use super::escape_like;
+
+    #[test]
+    fn escapes_like_wildcards_and_escape_char() {
+        assert_eq!(escape_like("rank:"), "rank:");
+        assert_eq!(escape_like("a%b_c\\d"), "a\\%b\\_c\\\\d");
+    }
+}
+
+host_fn!(pub store_keys(user_data: (String, DatabaseConnection); collection: String, prefix: String) -> String {
+    let user_data_guard = user_data.get()?;
+    let user_data = user_data_guard.lock().map_err(|_| extism::Error::msg("Lock poisoned"))?;
+    let (plugin_id, db) = &*user_data;
+
+    let collection = parse_collection(&collection)?;
+    let pattern = format!("{}%", escape_like(&sanitize_db_text(&prefix)));
+
+    let mut keys: Vec<String> = tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(async {
+            plugin_storage::Entity::find()
+                .select_only()
+                .column(plugin_storage::Column::Key)
+                .filter(plugin_storage::Column::PluginId.eq(plugin_id))
+                .filter(plugin_storage::Column::Collection.eq(&collection))
+                .filter(plugin_storage::Column::Key.like(LikeExpr::new(pattern).escape('\\')))
+                .order_by_asc(plugin_storage::Column::Key)
+                .limit(MAX_LISTED_KEYS + 1)
+                .into_tuple()
+                .all(db)
+                .await
+        })
+    })
+    .map_err(|e| {
+        error!("DB store_keys error: {e}");
+        extism::Error::msg("Database error")
+    })?;
+
+    let truncated = keys.len() as u64 > MAX_LISTED_KEYS;
+    keys.truncate(MAX_LISTED_KEYS as usize);
+
+    Ok(serde_json::json!({ "keys": keys, "truncated": truncated }).to_string())
+});
+
 #[derive(Deserialize)]
 struct StoreDeleteInput {
     keys: Vec<String>,
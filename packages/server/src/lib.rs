@@ -16,9 +16,11 @@ pub mod registry;
 pub mod routes;
 pub mod seed;
 pub mod serve;
+pub mod standings_cache;
 pub mod state;
 pub mod upload_limits;
 pub mod utils;
+pub mod webhook;
 
 use std::path::{Path, PathBuf};
 
@@ -68,10 +70,15 @@ use crate::state::AppState;
         (name = "Plugin Config", description = "Per-plugin configuration values keyed by scope"),
         (name = "Problem Attachments", description = "Contestant-visible files attached to a problem"),
         (name = "Roles", description = "Role and permission management"),
+        (name = "Saved Views", description = "Per-user saved query parameters for list endpoints"),
         (name = "Submissions", description = "Submitted solutions to problems"),
         (name = "Telemetry", description = "Public client error and web-vitals reporting"),
         (name = "Users", description = "User account management"),
     ),
+    components(schemas(
+        common::submission_status::SubmissionStatus,
+        common::submission_status::Verdict,
+    )),
     modifiers(&SecurityAddon),
 )]
 struct ApiDoc;
@@ -273,6 +280,40 @@ mod tests {
     use serde_json::json;
     use tower::ServiceExt;
 
+    fn enum_values(schema: &serde_json::Value, name: &str) -> Vec<String> {
+        schema["enum"]
+            .as_array()
+            .unwrap_or_else(|| panic!("{name} should be an enum schema"))
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn openapi_exposes_submission_status_and_verdict_enums() {
+        use common::submission_status::{SubmissionStatus, Verdict};
+
+        let openapi = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        let schemas = &openapi["components"]["schemas"];
+
+        let statuses = enum_values(&schemas["SubmissionStatus"], "SubmissionStatus");
+        let expected: Vec<_> = SubmissionStatus::ALL.iter().map(|s| s.as_str()).collect();
+        assert_eq!(statuses, expected);
+
+        // Plugins may report verdicts outside the built-in set, so the schema
+        // accepts any string alongside the documented values.
+        let variants = schemas["Verdict"]["oneOf"]
+            .as_array()
+            .expect("Verdict should be a oneOf schema");
+        assert_eq!(variants.len(), 2);
+        let verdicts = enum_values(&variants[0], "Verdict");
+        let expected: Vec<_> = Verdict::ALL.iter().map(|v| v.as_str()).collect();
+        assert_eq!(verdicts, expected);
+        assert_eq!(variants[1]["type"], "string");
+        assert!(variants[1].get("enum").is_none());
+    }
+
     #[tokio::test]
     async fn spa_fallback_serves_index_for_root_and_client_routes() {
         let dist = tempfile::tempdir().unwrap();
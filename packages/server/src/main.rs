@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -12,8 +13,11 @@ use tokio::sync::RwLock;
 use tower_http::cors::CorsLayer;
 use tracing::{info, warn};
 
+use mq::{ConsumeConfig, ReconnectPolicy};
 use server::build_router;
-use server::config::{AppConfig, per_replica_result_queue_name, resolve_server_id};
+use server::config::{
+    AppConfig, operation_result_queue_names, per_replica_result_queue_name, resolve_server_id,
+};
 use server::consumers::{consume_operation_dlq, consume_operation_results};
 use server::dlq::run_stuck_job_detector;
 use server::manager::ServerManager;
@@ -51,6 +55,14 @@ async fn main() -> anyhow::Result<()> {
         "Resolved server identity"
     );
 
+    let seed_file = if app_config.bootstrap.seed_file.is_empty() {
+        None
+    } else {
+        Some(server::seed::SeedFile::load(Path::new(
+            &app_config.bootstrap.seed_file,
+        ))?)
+    };
+
     let (metrics, prometheus_registry) =
         common::observability::init_metrics(&app_config.observability.otlp.service_name);
 
@@ -68,6 +80,9 @@ async fn main() -> anyhow::Result<()> {
     );
 
     server::seed::seed_role_permissions(&db).await?;
+    if let Some(seed_file) = &seed_file {
+        server::seed::seed_roles_from_file(&db, seed_file).await?;
+    }
     server::seed::ensure_bootstrap_admin(
         &db,
         &app_config.bootstrap.admin_username,
@@ -76,6 +91,7 @@ async fn main() -> anyhow::Result<()> {
     .await?;
     server::seed::ensure_indexes(&db).await?;
     server::seed::backfill_submission_judgements(&db).await?;
+    server::seed::migrate_test_details_visibility(&db).await?;
     server::seed::spawn_large_test_case_body_backfill(db.clone(), Arc::clone(&blob_store));
 
     let mq = if app_config.mq.enabled {
@@ -114,12 +130,30 @@ async fn main() -> anyhow::Result<()> {
         None
     };
 
+    if let Some(ref client) = redis_client {
+        let recorder_db = db.clone();
+        let recorder_client = Arc::clone(client);
+        tokio::spawn(async move {
+            server::utils::worker::run_heartbeat_recorder(recorder_db, recorder_client).await;
+        });
+        info!("Worker heartbeat recorder started");
+    }
+
     if let Some(ref mq_arc) = mq {
         let op_dlq_consumer_db = db.clone();
         let op_dlq_consumer_mq = Arc::clone(mq_arc);
         let op_dlq_queue = app_config.mq.operation_dlq_queue_name.clone();
+        let op_dlq_config =
+            ConsumeConfig::with_concurrency(app_config.mq.operation_dlq_concurrency)
+                .with_reconnect(ReconnectPolicy::from(&app_config.mq));
         tokio::spawn(async move {
-            consume_operation_dlq(op_dlq_consumer_db, op_dlq_consumer_mq, op_dlq_queue).await;
+            consume_operation_dlq(
+                op_dlq_consumer_db,
+                op_dlq_consumer_mq,
+                op_dlq_queue,
+                op_dlq_config,
+            )
+            .await;
         });
         info!("Operation DLQ consumer started");
     }
@@ -183,16 +217,31 @@ async fn main() -> anyhow::Result<()> {
     );
 
     if let Some(ref mq_arc) = mq {
-        let op_consumer_mq = Arc::clone(mq_arc);
-        let op_result_queue = app_config.mq.operation_result_queue_name.clone();
-        let op_waiters = operation_waiters.clone();
-        tokio::spawn(async move {
-            consume_operation_results(op_consumer_mq, op_waiters, op_result_queue).await;
-        });
-        info!(
-            queue = %app_config.mq.operation_result_queue_name,
-            "Operation result consumer started (per-replica)"
-        );
+        for op_result_queue in operation_result_queue_names(
+            &app_config.mq.operation_result_queue_name,
+            app_config.mq.operation_result_contest_shards,
+        ) {
+            let op_consumer_mq = Arc::clone(mq_arc);
+            let op_waiters = operation_waiters.clone();
+            let op_result_config =
+                ConsumeConfig::with_concurrency(app_config.mq.operation_result_concurrency)
+                    .with_reconnect(ReconnectPolicy::from(&app_config.mq));
+            let op_result_dedup_window = app_config.mq.operation_result_dedup_window;
+            info!(
+                queue = %op_result_queue,
+                "Operation result consumer started (per-replica)"
+            );
+            tokio::spawn(async move {
+                consume_operation_results(
+                    op_consumer_mq,
+                    op_waiters,
+                    op_result_queue,
+                    op_result_config,
+                    op_result_dedup_window,
+                )
+                .await;
+            });
+        }
     }
 
     let manager = ServerManager::new(
@@ -254,6 +303,9 @@ async fn main() -> anyhow::Result<()> {
             hook_registry: server::hooks::new_shared_registry(),
         },
         device_codes,
+        standings_cache: server::standings_cache::StandingsCache::new(Duration::from_secs(
+            app_config.server.standings_cache_ttl_secs,
+        )),
         metrics,
         prometheus_registry,
     };
@@ -93,6 +93,13 @@ pub struct MeResponse {
     pub permissions: Vec<String>,
 }
 
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct PermissionsResponse {
+    /// Distinct permissions granted by the user's roles, sorted.
+    #[schema(example = json!(["contest:manage", "submission:submit"]))]
+    pub permissions: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
 pub struct DeviceCodeRequest {}
 
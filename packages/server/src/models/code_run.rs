@@ -1,10 +1,12 @@
 use chrono::{DateTime, Utc};
+use common::submission_dispatch::JudgeMode;
 use common::{SubmissionStatus, Verdict};
 use serde::{Deserialize, Serialize};
 
 use super::submission::SubmissionFileDto;
+use crate::config::SubmissionConfig;
 use crate::error::AppError;
-use crate::utils::filename::validate_flat_filename;
+use crate::utils::judging::validate_submission_files;
 
 #[derive(Clone, Debug, Deserialize, Serialize, utoipa::ToSchema)]
 pub struct CustomTestCaseInput {
@@ -17,7 +19,13 @@ pub struct RunCodeRequest {
     pub files: Vec<SubmissionFileDto>,
     #[schema(example = "cpp")]
     pub language: String,
+    #[serde(default)]
     pub custom_test_cases: Vec<CustomTestCaseInput>,
+    /// `compile_only` compiles and returns the diagnostics without running
+    /// any custom test case, which may then be omitted.
+    #[serde(default)]
+    #[schema(value_type = String, example = "full")]
+    pub judge_mode: JudgeMode,
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -41,6 +49,8 @@ pub struct CodeRunResponse {
     #[schema(example = "ioi")]
     pub contest_type: String,
     pub custom_test_cases: Vec<CustomTestCaseInput>,
+    #[schema(value_type = String, example = "full")]
+    pub judge_mode: JudgeMode,
     #[schema(example = "2025-10-01T14:30:00Z")]
     pub created_at: DateTime<Utc>,
     pub result: Option<CodeRunJudgeResult>,
@@ -48,12 +58,13 @@ pub struct CodeRunResponse {
 
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct CodeRunJudgeResult {
-    #[schema(value_type = Option<String>, example = "Accepted")]
     pub verdict: Option<Verdict>,
     #[schema(example = 2.0)]
     pub score: Option<f64>,
+    /// CPU time in milliseconds, rounded up.
     #[schema(example = 50)]
     pub time_used: Option<i32>,
+    /// Peak memory in kilobytes.
     #[schema(example = 1024)]
     pub memory_used: Option<i32>,
     pub compile_output: Option<String>,
@@ -66,12 +77,13 @@ pub struct CodeRunJudgeResult {
 pub struct CodeRunResultResponse {
     #[schema(example = 1)]
     pub id: i32,
-    #[schema(value_type = String, example = "Accepted")]
     pub verdict: Verdict,
     #[schema(example = 1.0)]
     pub score: f64,
+    /// CPU time in milliseconds, rounded up.
     #[schema(example = 5)]
     pub time_used: Option<i32>,
+    /// Peak memory in kilobytes.
     #[schema(example = 256)]
     pub memory_used: Option<i32>,
     pub run_index: i32,
@@ -82,50 +94,15 @@ pub struct CodeRunResultResponse {
     pub checker_output: Option<String>,
 }
 
-pub fn validate_run_code(req: &RunCodeRequest, max_size: usize) -> Result<(), AppError> {
-    use std::collections::HashSet;
-
-    if req.files.is_empty() {
-        return Err(AppError::Validation("At least one file is required".into()));
-    }
-
-    let mut total_size = 0usize;
-    let mut seen_filenames = HashSet::with_capacity(req.files.len());
-
-    for file in &req.files {
-        let filename = validate_flat_filename(&file.filename)
-            .map_err(|e| AppError::Validation(e.message().into()))?;
-
-        if !seen_filenames.insert(filename) {
-            return Err(AppError::Validation(format!(
-                "Duplicate filename: '{}'",
-                filename
-            )));
-        }
-
-        if file.content.is_empty() {
-            return Err(AppError::Validation(format!(
-                "File '{}' cannot be empty",
-                filename
-            )));
-        }
-
-        total_size = total_size.saturating_add(file.content.len());
-    }
-
-    if total_size > max_size {
-        return Err(AppError::Validation(format!(
-            "Total submission size ({} bytes) exceeds maximum ({} bytes)",
-            total_size, max_size
-        )));
-    }
+pub fn validate_run_code(req: &RunCodeRequest, limits: &SubmissionConfig) -> Result<(), AppError> {
+    validate_submission_files(&req.files, limits)?;
 
     let language = req.language.trim();
     if language.is_empty() {
         return Err(AppError::Validation("Language is required".into()));
     }
 
-    if req.custom_test_cases.is_empty() {
+    if req.custom_test_cases.is_empty() && !req.judge_mode.is_compile_only() {
         return Err(AppError::Validation(
             "At least one custom test case is required".into(),
         ));
@@ -1,4 +1,7 @@
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 
@@ -6,6 +9,7 @@ use super::shared::{
     Pagination, double_option, validate_bulk_ids, validate_optional_position, validate_reorder_ids,
     validate_title,
 };
+use crate::entity::contest::ScoreSelection;
 use crate::error::AppError;
 
 #[derive(Deserialize, utoipa::ToSchema)]
@@ -30,8 +34,36 @@ pub struct CreateContestRequest {
     pub show_compile_output: Option<bool>,
     #[schema(example = true)]
     pub show_participants_list: Option<bool>,
+    #[schema(example = true)]
+    pub hide_problems_until_start: Option<bool>,
+    /// Score standings by solve order (earlier solvers earn more) instead of
+    /// best score. Defaults to false.
+    #[schema(example = false)]
+    pub dynamic_scoring: Option<bool>,
+    /// Judge only pretests during the contest and the full test set in a
+    /// post-contest system test. Defaults to false.
+    #[schema(example = false)]
+    pub two_phase: Option<bool>,
+    /// Open the problems to every authenticated user for practice once the
+    /// contest ends. Defaults to false.
+    #[schema(example = false)]
+    pub practice_after_end: Option<bool>,
+    /// Hide usernames from non-managers in standings until the contest
+    /// ends. Defaults to false.
+    #[schema(example = false)]
+    pub anonymize_standings_until_end: Option<bool>,
+    /// Show non-managers only the top this many standings rows. Omit to
+    /// show everyone.
+    #[schema(example = 50, minimum = 1)]
+    pub public_standings_limit: Option<i32>,
+    /// Whether standings use each participant's best or most recent score
+    /// per problem. Defaults to `Best`. ICPC boards, which count the first
+    /// accepted submission, and IOI `BestTokenedOrLast` boards ignore it.
+    pub score_selection: Option<ScoreSelection>,
     #[schema(example = "ioi")]
     pub contest_type: Option<String>,
+    #[schema(example = json!(["cpp"]))]
+    pub allowed_languages: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Default, PartialEq, utoipa::ToSchema)]
@@ -58,18 +90,57 @@ pub struct UpdateContestRequest {
     pub show_compile_output: Option<bool>,
     #[schema(example = true)]
     pub show_participants_list: Option<bool>,
+    #[schema(example = true)]
+    pub hide_problems_until_start: Option<bool>,
+    /// Score standings by solve order (earlier solvers earn more) instead of
+    /// best score. Defaults to false.
+    #[schema(example = false)]
+    pub dynamic_scoring: Option<bool>,
+    /// Judge only pretests during the contest and the full test set in a
+    /// post-contest system test. Defaults to false.
+    #[schema(example = false)]
+    pub two_phase: Option<bool>,
+    /// Open the problems to every authenticated user for practice once the
+    /// contest ends. Defaults to false.
+    #[schema(example = false)]
+    pub practice_after_end: Option<bool>,
+    /// Hide usernames from non-managers in standings until the contest
+    /// ends. Defaults to false.
+    #[schema(example = false)]
+    pub anonymize_standings_until_end: Option<bool>,
+    /// Show non-managers only the top this many standings rows. Set to
+    /// `null` to show everyone.
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<i32>, example = 50, minimum = 1)]
+    pub public_standings_limit: Option<Option<i32>>,
+    /// Whether standings use each participant's best or most recent score
+    /// per problem. Defaults to `Best`. ICPC boards, which count the first
+    /// accepted submission, and IOI `BestTokenedOrLast` boards ignore it.
+    pub score_selection: Option<ScoreSelection>,
     #[schema(example = "icpc")]
     pub contest_type: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["cpp", "python3"]))]
+    pub allowed_languages: Option<Option<Vec<String>>>,
 }
 
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct AddContestProblemRequest {
     #[schema(example = 1)]
     pub problem_id: i32,
+    /// Omit to take the first free label in the sequence A, B, …, Z, AA, AB, ….
     #[schema(example = "A")]
-    pub label: String,
+    pub label: Option<String>,
     #[schema(example = 0)]
     pub position: Option<i32>,
+    /// Scale this problem's standings score to this many points. On ICPC
+    /// boards, solving the problem earns this many points.
+    #[schema(example = 100)]
+    pub max_points: Option<i32>,
+    /// Hide verdicts and scores from contestants for this many seconds
+    /// after they submit. Defaults to 0.
+    #[schema(example = 0, minimum = 0)]
+    pub result_delay_secs: Option<i32>,
 }
 
 #[derive(Deserialize, Default, PartialEq, utoipa::ToSchema)]
@@ -78,6 +149,12 @@ pub struct UpdateContestProblemRequest {
     pub label: Option<String>,
     #[schema(example = 1)]
     pub position: Option<i32>,
+    /// Set to `null` to go back to raw test case scores.
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<i32>, example = 100)]
+    pub max_points: Option<Option<i32>>,
+    #[schema(example = 300, minimum = 0)]
+    pub result_delay_secs: Option<i32>,
 }
 
 #[derive(Deserialize, utoipa::ToSchema)]
@@ -105,6 +182,63 @@ pub struct ContestListQuery {
     pub sort_by: Option<String>,
     #[param(example = "asc")]
     pub sort_order: Option<String>,
+    /// IANA time zone for the `local_times` display fields.
+    #[param(example = "Asia/Tokyo")]
+    pub tz: Option<String>,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ContestTimeZoneQuery {
+    /// IANA time zone for the `local_times` display fields.
+    #[param(example = "Asia/Tokyo")]
+    pub tz: Option<String>,
+}
+
+/// Contest times rendered in a requested time zone, as RFC 3339 strings
+/// carrying that zone's offset. Only present when `?tz=` is given; the UTC
+/// fields alongside are unchanged.
+#[derive(Serialize, Clone, utoipa::ToSchema)]
+pub struct ContestLocalTimes {
+    #[schema(example = "Asia/Tokyo")]
+    pub time_zone: String,
+    #[schema(example = "2025-09-30T21:00:00+09:00")]
+    pub activate_time: Option<String>,
+    #[schema(example = "2025-10-01T23:00:00+09:00")]
+    pub start_time: String,
+    #[schema(example = "2025-10-02T02:00:00+09:00")]
+    pub end_time: String,
+    #[schema(example = "2025-10-02T21:00:00+09:00")]
+    pub deactivate_time: Option<String>,
+}
+
+impl ContestLocalTimes {
+    pub fn new(
+        tz: Tz,
+        activate_time: Option<DateTime<Utc>>,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        deactivate_time: Option<DateTime<Utc>>,
+    ) -> Self {
+        let fmt = |t: DateTime<Utc>| t.with_timezone(&tz).to_rfc3339();
+        Self {
+            time_zone: tz.name().to_string(),
+            activate_time: activate_time.map(fmt),
+            start_time: fmt(start_time),
+            end_time: fmt(end_time),
+            deactivate_time: deactivate_time.map(fmt),
+        }
+    }
+}
+
+/// Parses an optional `?tz=` value as an IANA time zone name.
+pub fn parse_time_zone(tz: Option<&str>) -> Result<Option<Tz>, AppError> {
+    tz.map(|name| {
+        name.trim()
+            .parse::<Tz>()
+            .map_err(|_| AppError::Validation(format!("Unknown time zone '{name}'")))
+    })
+    .transpose()
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -131,12 +265,33 @@ pub struct ContestResponse {
     pub show_compile_output: bool,
     #[schema(example = true)]
     pub show_participants_list: bool,
+    #[schema(example = true)]
+    pub hide_problems_until_start: bool,
+    #[schema(example = false)]
+    pub dynamic_scoring: bool,
+    #[schema(example = false)]
+    pub two_phase: bool,
+    #[schema(example = false)]
+    pub practice_after_end: bool,
+    #[schema(example = false)]
+    pub anonymize_standings_until_end: bool,
+    /// Rows of standings shown to non-managers; `null` shows everyone.
+    #[schema(example = 50)]
+    pub public_standings_limit: Option<i32>,
+    pub score_selection: ScoreSelection,
+    /// When standings were manually frozen; `null` while they are live.
+    #[schema(example = "2025-10-01T16:00:00Z")]
+    pub frozen_at: Option<DateTime<Utc>>,
     #[schema(example = "ioi")]
     pub contest_type: Option<String>,
+    #[schema(example = json!(["cpp"]))]
+    pub allowed_languages: Option<Vec<String>>,
     #[schema(example = "2025-09-25T10:00:00Z")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2025-09-25T10:30:00Z")]
     pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_times: Option<ContestLocalTimes>,
 }
 
 #[derive(Serialize, FromQueryResult, utoipa::ToSchema)]
@@ -161,12 +316,25 @@ pub struct ContestListItem {
     pub show_compile_output: bool,
     #[schema(example = true)]
     pub show_participants_list: bool,
+    #[schema(example = true)]
+    pub hide_problems_until_start: bool,
+    #[schema(example = false)]
+    pub dynamic_scoring: bool,
+    #[schema(example = false)]
+    pub two_phase: bool,
+    #[schema(example = false)]
+    pub practice_after_end: bool,
+    #[schema(example = false)]
+    pub anonymize_standings_until_end: bool,
     #[schema(example = "ioi")]
     pub contest_type: Option<String>,
     #[schema(example = "2025-09-25T10:00:00Z")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2025-09-25T10:30:00Z")]
     pub updated_at: DateTime<Utc>,
+    #[sea_orm(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_times: Option<ContestLocalTimes>,
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -187,6 +355,20 @@ pub struct ContestProblemResponse {
     pub position: i32,
     #[schema(example = "Two Sum")]
     pub problem_title: String,
+    #[schema(example = 100)]
+    pub max_points: Option<i32>,
+    #[schema(example = 0)]
+    pub result_delay_secs: i32,
+    /// Whether the caller has solved this problem on the contest's standings
+    /// board. Only set when listing contest problems of a contest type that
+    /// serves standings.
+    #[schema(example = false)]
+    pub solved: Option<bool>,
+    /// Whether the caller has attempts for this problem on the contest's
+    /// standings board. Only set when listing contest problems of a contest
+    /// type that serves standings.
+    #[schema(example = true)]
+    pub attempted: Option<bool>,
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -214,8 +396,43 @@ pub struct ContestUserContextResponse {
     pub registered_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ContestProblemStatus {
+    #[schema(example = 5)]
+    pub problem_id: i32,
+    #[schema(example = "A")]
+    pub label: String,
+    /// Judged submissions up to and including the first accepted one, or
+    /// all of them if the problem is unsolved.
+    #[schema(example = 3)]
+    pub attempts: u32,
+    #[schema(example = true)]
+    pub solved: bool,
+    #[schema(example = "2025-10-01T14:30:00Z")]
+    pub first_accepted_at: Option<DateTime<Utc>>,
+    /// Penalty minutes the contest type's board charges for the problem,
+    /// including its own per-attempt penalty. 0 while unsolved.
+    #[schema(example = 70)]
+    pub penalty: i64,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ContestMyStatusResponse {
+    #[schema(example = 1)]
+    pub contest_id: i32,
+    #[schema(example = 7)]
+    pub user_id: i32,
+    #[schema(example = 1)]
+    pub solved: u32,
+    #[schema(example = 70)]
+    pub penalty: i64,
+    /// One entry per contest problem, in contest position order.
+    pub problems: Vec<ContestProblemStatus>,
+}
+
 impl From<crate::entity::contest::Model> for ContestResponse {
     fn from(m: crate::entity::contest::Model) -> Self {
+        let allowed_languages = m.get_allowed_languages();
         Self {
             id: m.id,
             title: m.title,
@@ -228,9 +445,19 @@ impl From<crate::entity::contest::Model> for ContestResponse {
             submissions_visible: m.submissions_visible,
             show_compile_output: m.show_compile_output,
             show_participants_list: m.show_participants_list,
+            hide_problems_until_start: m.hide_problems_until_start,
+            dynamic_scoring: m.dynamic_scoring,
+            two_phase: m.two_phase,
+            practice_after_end: m.practice_after_end,
+            anonymize_standings_until_end: m.anonymize_standings_until_end,
+            public_standings_limit: m.public_standings_limit,
+            score_selection: m.score_selection,
+            frozen_at: m.frozen_at,
+            allowed_languages,
             contest_type: m.contest_type,
             created_at: m.created_at,
             updated_at: m.updated_at,
+            local_times: None,
         }
     }
 }
@@ -276,6 +503,7 @@ pub fn validate_create_contest(req: &CreateContestRequest) -> Result<(), AppErro
         req.end_time,
         req.deactivate_time.unwrap_or(None),
     )?;
+    validate_public_standings_limit(req.public_standings_limit)?;
     Ok(())
 }
 
@@ -290,17 +518,98 @@ pub fn validate_update_contest(req: &UpdateContestRequest) -> Result<(), AppErro
             "Description must be non-empty and at most 1MB".into(),
         ));
     }
+    validate_public_standings_limit(req.public_standings_limit.flatten())?;
+    Ok(())
+}
+
+fn validate_public_standings_limit(limit: Option<i32>) -> Result<(), AppError> {
+    if let Some(limit) = limit
+        && limit < 1
+    {
+        return Err(AppError::Validation(
+            "public_standings_limit must be at least 1".into(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_allowed_languages(
+    allowed_languages: Option<&Vec<String>>,
+    known_languages: &HashSet<String>,
+) -> Result<(), AppError> {
+    let Some(allowed_languages) = allowed_languages else {
+        return Ok(());
+    };
+
+    if allowed_languages.is_empty() {
+        return Err(AppError::Validation(
+            "allowed_languages must include at least one language".into(),
+        ));
+    }
+
+    let mut seen = HashSet::with_capacity(allowed_languages.len());
+    for language in allowed_languages {
+        let language = language.trim();
+        if language.is_empty() {
+            return Err(AppError::Validation(
+                "allowed_languages entries must be non-empty".into(),
+            ));
+        }
+        if !known_languages.is_empty() && !known_languages.contains(language) {
+            return Err(AppError::Validation(format!(
+                "allowed_languages contains unsupported language '{}'",
+                language
+            )));
+        }
+        if !seen.insert(language) {
+            return Err(AppError::Validation(format!(
+                "allowed_languages contains duplicate language '{}'",
+                language
+            )));
+        }
+    }
+
     Ok(())
 }
 
+/// Trims entries so the stored list matches submitted language ids exactly.
+pub fn normalize_allowed_languages(allowed_languages: Vec<String>) -> serde_json::Value {
+    serde_json::Value::Array(
+        allowed_languages
+            .into_iter()
+            .map(|l| serde_json::Value::String(l.trim().to_string()))
+            .collect(),
+    )
+}
+
 pub fn validate_add_contest_problem(req: &AddContestProblemRequest) -> Result<(), AppError> {
-    let label = req.label.trim();
-    if label.is_empty() || label.chars().count() > 10 {
-        return Err(AppError::Validation("Label must be 1-10 characters".into()));
+    if let Some(ref label) = req.label {
+        let label = label.trim();
+        if label.is_empty() || label.chars().count() > 10 {
+            return Err(AppError::Validation("Label must be 1-10 characters".into()));
+        }
     }
+    validate_max_points(req.max_points)?;
+    validate_result_delay(req.result_delay_secs)?;
     validate_optional_position(req.position)
 }
 
+fn validate_max_points(max_points: Option<i32>) -> Result<(), AppError> {
+    if max_points.is_some_and(|p| p <= 0) {
+        return Err(AppError::Validation("max_points must be positive".into()));
+    }
+    Ok(())
+}
+
+fn validate_result_delay(result_delay_secs: Option<i32>) -> Result<(), AppError> {
+    if result_delay_secs.is_some_and(|d| d < 0) {
+        return Err(AppError::Validation(
+            "result_delay_secs must not be negative".into(),
+        ));
+    }
+    Ok(())
+}
+
 pub fn validate_reorder_contest_problems(
     req: &ReorderContestProblemsRequest,
 ) -> Result<(), AppError> {
@@ -441,5 +750,7 @@ pub fn validate_update_contest_problem(req: &UpdateContestProblemRequest) -> Res
             return Err(AppError::Validation("Label must be 1-10 characters".into()));
         }
     }
+    validate_max_points(req.max_points.flatten())?;
+    validate_result_delay(req.result_delay_secs)?;
     validate_optional_position(req.position)
 }
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
+use common::retry::RetryAttempt;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
 use crate::dlq::DlqStats;
 use crate::entity::dead_letter_message;
@@ -67,6 +69,28 @@ impl From<dead_letter_message::Model> for DlqMessageResponse {
     }
 }
 
+/// One failed delivery or processing attempt before the message was
+/// dead-lettered.
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct DlqRetryAttemptResponse {
+    #[schema(example = 1)]
+    pub attempt: u8,
+    #[schema(example = "Database connection timeout")]
+    pub error: String,
+    #[schema(example = "2025-09-01T08:00:00Z")]
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<RetryAttempt> for DlqRetryAttemptResponse {
+    fn from(a: RetryAttempt) -> Self {
+        Self {
+            attempt: a.attempt,
+            error: a.error,
+            timestamp: a.timestamp,
+        }
+    }
+}
+
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct DlqMessageDetailResponse {
     #[schema(example = 1)]
@@ -84,7 +108,8 @@ pub struct DlqMessageDetailResponse {
     pub error_message: String,
     #[schema(example = 3)]
     pub retry_count: i32,
-    pub retry_history: serde_json::Value,
+    /// Attempts in the order they were made.
+    pub retry_history: Vec<DlqRetryAttemptResponse>,
     #[schema(example = "2025-09-01T08:00:00Z")]
     pub first_failed_at: DateTime<Utc>,
     #[schema(example = "2025-09-01T08:05:00Z")]
@@ -97,6 +122,14 @@ pub struct DlqMessageDetailResponse {
 
 impl From<dead_letter_message::Model> for DlqMessageDetailResponse {
     fn from(m: dead_letter_message::Model) -> Self {
+        let retry_history = serde_json::from_value::<Vec<RetryAttempt>>(m.retry_history)
+            .unwrap_or_else(|e| {
+                warn!(id = m.id, error = %e, "Undecodable DLQ retry history");
+                Vec::new()
+            })
+            .into_iter()
+            .map(Into::into)
+            .collect();
         Self {
             id: m.id,
             message_id: m.message_id,
@@ -106,7 +139,7 @@ impl From<dead_letter_message::Model> for DlqMessageDetailResponse {
             error_code: m.error_code,
             error_message: m.error_message,
             retry_count: m.retry_count,
-            retry_history: m.retry_history,
+            retry_history,
             first_failed_at: m.first_failed_at,
             created_at: m.created_at,
             resolved: m.resolved,
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// Default number of submissions archived by one request.
+pub const DEFAULT_ARCHIVE_LIMIT: u64 = 1000;
+/// Upper bound on `limit` for one archive request.
+pub const MAX_ARCHIVE_LIMIT: u64 = 10_000;
+
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+pub struct ArchiveSubmissionsRequest {
+    /// Archive terminal submissions created more than this many days ago.
+    /// Defaults to `submission.archive_after_days` from the server config.
+    #[schema(example = 180, minimum = 1)]
+    pub older_than_days: Option<u32>,
+    /// Maximum number of submissions to archive in this call. Defaults to 1000.
+    #[schema(example = 1000, minimum = 1, maximum = 10000)]
+    pub limit: Option<u64>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ArchiveSubmissionsResponse {
+    /// Number of submissions archived by this call.
+    #[schema(example = 120)]
+    pub archived: u64,
+    /// Submissions created before this instant were eligible.
+    #[schema(example = "2025-03-01T00:00:00Z")]
+    pub cutoff: DateTime<Utc>,
+    /// True when `archived` reached `limit`; call again to continue.
+    pub has_more: bool,
+}
+
+pub fn validate_archive_submissions(req: &ArchiveSubmissionsRequest) -> Result<(), AppError> {
+    if req.older_than_days == Some(0) {
+        return Err(AppError::Validation(
+            "'older_than_days' must be at least 1".into(),
+        ));
+    }
+    if let Some(limit) = req.limit
+        && !(1..=MAX_ARCHIVE_LIMIT).contains(&limit)
+    {
+        return Err(AppError::Validation(format!(
+            "'limit' must be between 1 and {MAX_ARCHIVE_LIMIT}"
+        )));
+    }
+    Ok(())
+}
@@ -5,9 +5,11 @@ pub mod code_run;
 pub mod config_upload;
 pub mod contest;
 pub mod dlq;
+pub mod maintenance;
 pub mod plugin;
 pub mod plugin_config;
 pub mod problem;
+pub mod saved_view;
 pub mod shared;
 pub mod submission;
 pub mod system;
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use plugin_core::manifest::{ComponentMap, ServerRouteConfig, WebRouteConfig, WebSlotConfig};
 use plugin_core::registry::{PluginInfo, PluginStatus};
 use serde::Serialize;
@@ -207,6 +209,96 @@ pub struct PluginFullDetailResponse {
     pub config_schemas: Vec<ConfigSchemaResponse>,
 }
 
+/// Functions a plugin exposes, derived from its server manifest.
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct PluginFunctionsResponse {
+    #[schema(example = "plugin-123")]
+    pub plugin_id: String,
+    /// Host capabilities (permissions) the plugin's server module declares.
+    #[schema(example = json!(["storage", "sql"]))]
+    pub capabilities: Vec<String>,
+    pub functions: Vec<PluginFunctionInfo>,
+}
+
+/// Files a plugin serves under `/assets/{id}/`.
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct PluginAssetsResponse {
+    #[schema(example = "plugin-123")]
+    pub plugin_id: String,
+    /// Paths relative to the plugin's web root, sorted.
+    #[schema(example = json!(["index.js", "styles/theme.css"]))]
+    pub assets: Vec<String>,
+}
+
+/// A single exported function referenced by the plugin manifest.
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct PluginFunctionInfo {
+    #[schema(example = "greet")]
+    pub name: String,
+    /// HTTP routes dispatched to this function.
+    pub routes: Vec<ServerRouteConfig>,
+    /// Hook topics this function is bound to.
+    #[schema(example = json!(["after_judging"]))]
+    pub hooks: Vec<String>,
+    /// JSON schema for the function's input, if declared.
+    pub input_schema: Option<serde_json::Value>,
+    /// JSON schema for the function's output, if declared.
+    pub output_schema: Option<serde_json::Value>,
+}
+
+impl PluginFunctionInfo {
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            routes: Vec::new(),
+            hooks: Vec::new(),
+            input_schema: None,
+            output_schema: None,
+        }
+    }
+}
+
+impl From<PluginInfo> for PluginFunctionsResponse {
+    fn from(info: PluginInfo) -> Self {
+        let Some(server) = info.manifest.server else {
+            return Self {
+                plugin_id: info.id,
+                capabilities: Vec::new(),
+                functions: Vec::new(),
+            };
+        };
+
+        let mut functions: BTreeMap<String, PluginFunctionInfo> = BTreeMap::new();
+        for route in server.routes {
+            functions
+                .entry(route.handler.clone())
+                .or_insert_with(|| PluginFunctionInfo::named(&route.handler))
+                .routes
+                .push(route);
+        }
+        for hook in server.hooks {
+            functions
+                .entry(hook.function.clone())
+                .or_insert_with(|| PluginFunctionInfo::named(&hook.function))
+                .hooks
+                .push(hook.topic);
+        }
+        for (name, schema) in server.functions {
+            let entry = functions
+                .entry(name.clone())
+                .or_insert_with(|| PluginFunctionInfo::named(&name));
+            entry.input_schema = schema.input.map(|s| s.to_json_schema());
+            entry.output_schema = schema.output.map(|s| s.to_json_schema());
+        }
+
+        Self {
+            plugin_id: info.id,
+            capabilities: server.permissions,
+            functions: functions.into_values().collect(),
+        }
+    }
+}
+
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct ServerDetailResponse {
     pub permissions: Vec<String>,
@@ -7,7 +7,9 @@ use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+use crate::entity::problem::TestDetailsVisibility;
 use crate::error::AppError;
+use crate::utils::difficulty::{MAX_DIFFICULTY, MIN_DIFFICULTY};
 use crate::utils::filename::validate_flat_filename;
 use crate::utils::test_case_body::{test_case_body_preview, test_case_body_size};
 
@@ -27,6 +29,14 @@ pub struct CreateProblemRequest {
     pub time_limit: i32,
     #[schema(example = 262144)]
     pub memory_limit: i32,
+    /// Wall-clock limit as a multiple of `time_limit`; at least 1. Omit to
+    /// use the evaluator's default.
+    #[schema(example = 2.0, minimum = 1.0)]
+    pub wall_time_multiplier: Option<f64>,
+    /// Judge a non-zero exit as a runtime error even with correct output.
+    /// Defaults to true.
+    #[schema(example = true)]
+    pub require_zero_exit: Option<bool>,
     #[serde(default)]
     #[schema(example = "batch")]
     pub problem_type: String,
@@ -36,10 +46,27 @@ pub struct CreateProblemRequest {
     #[serde(default)]
     #[schema(example = "ioi")]
     pub default_contest_type: String,
+    /// Test case I/O shown to users who cannot view all submissions.
+    /// Defaults to `SampleOnly`.
+    pub test_details_visibility: Option<TestDetailsVisibility>,
+    /// Legacy boolean form of `test_details_visibility`: `true` is `Full`,
+    /// `false` is `SampleOnly`. Ignored when `test_details_visibility` is set.
     #[schema(example = false)]
     pub show_test_details: Option<bool>,
     #[schema(example = json!({"cpp": ["solution.cpp"], "java": ["Main.java"]}))]
     pub submission_format: Option<std::collections::HashMap<String, Vec<String>>>,
+    /// Manual 0-100 difficulty. Estimation will not overwrite it by default.
+    #[schema(example = 40, minimum = 0, maximum = 100)]
+    pub difficulty: Option<i32>,
+    /// List the problem in the practice archive.
+    #[serde(default)]
+    #[schema(example = false)]
+    pub is_public: bool,
+    /// Skip the check for existing problems with a similar title or
+    /// statement, e.g. when deliberately creating a variant.
+    #[serde(default)]
+    #[schema(example = false)]
+    pub force: bool,
 }
 
 #[derive(Deserialize, Default, PartialEq, utoipa::ToSchema)]
@@ -52,17 +79,35 @@ pub struct UpdateProblemRequest {
     pub time_limit: Option<i32>,
     #[schema(example = 524288)]
     pub memory_limit: Option<i32>,
+    /// Wall-clock limit as a multiple of `time_limit`; at least 1. `null`
+    /// reverts to the evaluator's default.
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<f64>, example = 3.0, minimum = 1.0)]
+    pub wall_time_multiplier: Option<Option<f64>>,
+    #[schema(example = false)]
+    pub require_zero_exit: Option<bool>,
     #[schema(example = "batch")]
     pub problem_type: Option<String>,
     #[schema(example = "ignore_case")]
     pub checker_format: Option<String>,
     #[schema(example = "ioi")]
     pub default_contest_type: Option<String>,
+    pub test_details_visibility: Option<TestDetailsVisibility>,
+    /// Legacy boolean form of `test_details_visibility`. Ignored when
+    /// `test_details_visibility` is set or when it matches the current
+    /// visibility's `show_test_details`.
     #[schema(example = true)]
     pub show_test_details: Option<bool>,
     #[serde(default, deserialize_with = "double_option")]
     #[schema(value_type = Option<std::collections::HashMap<String, Vec<String>>>, example = json!({"cpp": ["solution.cpp"], "java": ["Main.java"]}))]
     pub submission_format: Option<Option<std::collections::HashMap<String, Vec<String>>>>,
+    /// Sets a manual 0-100 difficulty. `null` clears it and hands the field
+    /// back to estimation.
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<i32>, example = 40, minimum = 0, maximum = 100)]
+    pub difficulty: Option<Option<i32>>,
+    #[schema(example = true)]
+    pub is_public: Option<bool>,
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -77,6 +122,13 @@ pub struct ProblemResponse {
     pub time_limit: i32,
     #[schema(example = 262144)]
     pub memory_limit: i32,
+    /// Wall-clock limit as a multiple of `time_limit`; `null` when the
+    /// evaluator's default applies.
+    #[schema(example = 2.0)]
+    pub wall_time_multiplier: Option<f64>,
+    /// Whether a non-zero exit is a runtime error even with correct output.
+    #[schema(example = true)]
+    pub require_zero_exit: bool,
     #[schema(example = "batch")]
     pub problem_type: String,
     pub checker_source: Option<serde_json::Value>,
@@ -84,15 +136,64 @@ pub struct ProblemResponse {
     pub checker_format: String,
     #[schema(example = "ioi")]
     pub default_contest_type: String,
+    pub test_details_visibility: TestDetailsVisibility,
+    /// Whether `test_details_visibility` is `Full`. Kept for older clients.
     #[schema(example = false)]
     pub show_test_details: bool,
     #[schema(example = json!({"cpp": ["solution.cpp"], "java": ["Main.java"]}))]
     pub submission_format: Option<std::collections::HashMap<String, Vec<String>>>,
+    #[schema(example = 40)]
+    pub difficulty: Option<i32>,
+    #[schema(example = false)]
+    pub difficulty_is_manual: bool,
+    /// Whether the problem is listed in the practice archive.
+    #[schema(example = false)]
+    pub is_public: bool,
     pub samples: Vec<SampleTestCaseMeta>,
     #[schema(example = "2025-09-01T08:00:00Z")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2025-09-01T08:30:00Z")]
     pub updated_at: DateTime<Utc>,
+    /// On creation, existing problems that look like duplicates of this
+    /// one. Omitted when there are none or the check was skipped.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub similar_problems: Vec<SimilarProblem>,
+}
+
+/// An existing problem that resembles one being created.
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct SimilarProblem {
+    #[schema(example = 7)]
+    pub id: i32,
+    #[schema(example = "Two Sum II")]
+    pub title: String,
+    /// 0-1 similarity of the titles.
+    #[schema(example = 0.86)]
+    pub title_similarity: f64,
+    /// 0-1 similarity of the statements; 0 when either is too short to
+    /// compare.
+    #[schema(example = 0.0)]
+    pub content_similarity: f64,
+}
+
+/// Statement and limits a problem had before an edit replaced them.
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ProblemVersionResponse {
+    #[schema(example = 3)]
+    pub version: i32,
+    #[schema(example = "Two Sum")]
+    pub title: String,
+    #[schema(example = "Given an array of integers...")]
+    pub content: String,
+    #[schema(example = 1000)]
+    pub time_limit: i32,
+    #[schema(example = 262144)]
+    pub memory_limit: i32,
+    /// User whose edit replaced this version.
+    #[schema(example = 1)]
+    pub created_by: i32,
+    #[schema(example = "2025-09-01T08:30:00Z")]
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -123,8 +224,11 @@ pub struct ProblemListItem {
     pub checker_format: String,
     #[schema(example = "ioi")]
     pub default_contest_type: String,
+    pub test_details_visibility: TestDetailsVisibility,
+    #[schema(example = 40)]
+    pub difficulty: Option<i32>,
     #[schema(example = false)]
-    pub show_test_details: bool,
+    pub is_public: bool,
     #[schema(example = "2025-09-01T08:00:00Z")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2025-09-01T08:30:00Z")]
@@ -150,6 +254,23 @@ pub struct ProblemListQuery {
     pub sort_by: Option<String>,
     #[param(example = "desc")]
     pub sort_order: Option<String>,
+    /// Name of a saved view whose params fill in any not given here.
+    #[param(example = "Recent")]
+    pub view: Option<String>,
+}
+
+impl ProblemListQuery {
+    /// Fills parameters missing from `self` with those of a saved view.
+    pub fn or_saved(self, saved: Self) -> Self {
+        Self {
+            page: self.page.or(saved.page),
+            per_page: self.per_page.or(saved.per_page),
+            search: self.search.or(saved.search),
+            sort_by: self.sort_by.or(saved.sort_by),
+            sort_order: self.sort_order.or(saved.sort_order),
+            view: self.view,
+        }
+    }
 }
 
 #[derive(Deserialize, utoipa::ToSchema)]
@@ -158,10 +279,17 @@ pub struct CreateTestCaseRequest {
     pub input: String,
     #[schema(example = "0 1")]
     pub expected_output: String,
+    /// Other outputs the default `exact` checker accepts.
+    #[schema(example = json!(["1 0"]))]
+    pub accepted_outputs: Option<Vec<String>>,
     #[schema(example = 10)]
     pub score: i32,
     #[schema(example = true)]
     pub is_sample: bool,
+    /// Run during two-phase contests. Defaults to false.
+    #[serde(default)]
+    #[schema(example = true)]
+    pub is_pretest: bool,
     #[schema(example = 0)]
     pub position: Option<i32>,
     #[schema(example = "Basic case")]
@@ -176,10 +304,16 @@ pub struct UpdateTestCaseRequest {
     pub input: Option<String>,
     #[schema(example = "1 2")]
     pub expected_output: Option<String>,
+    /// Set to `null` to accept only `expected_output` again.
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["2 1"]))]
+    pub accepted_outputs: Option<Option<Vec<String>>>,
     #[schema(example = 20)]
     pub score: Option<i32>,
     #[schema(example = false)]
     pub is_sample: Option<bool>,
+    #[schema(example = true)]
+    pub is_pretest: Option<bool>,
     #[schema(example = 1)]
     pub position: Option<i32>,
     #[serde(default, deserialize_with = "double_option")]
@@ -212,6 +346,20 @@ pub struct UploadTestCasesRequest {
     pub strategy: UploadTestCasesMergeStrategy,
 }
 
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct ImportTestCasesDirRequest {
+    /// Directory to import, relative to the configured test case import
+    /// directory. Absolute paths are accepted only if they lie inside it.
+    #[schema(example = "aplusb/tests")]
+    pub path: String,
+    #[schema(example = "*.in")]
+    pub input_format: String,
+    #[schema(example = "*.ans")]
+    pub output_format: String,
+    #[schema(example = "abort")]
+    pub strategy: UploadTestCasesMergeStrategy,
+}
+
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct ReorderTestCasesRequest {
     #[schema(example = json!([3, 1, 2]))]
@@ -226,6 +374,8 @@ pub struct TestCaseResponse {
     pub input: String,
     #[schema(example = "0 1")]
     pub expected_output: String,
+    #[schema(example = json!(["1 0"]))]
+    pub accepted_outputs: Option<Vec<String>>,
     #[schema(example = 12)]
     pub input_size: usize,
     #[schema(example = 4)]
@@ -242,6 +392,8 @@ pub struct TestCaseResponse {
     pub label: String,
     #[schema(example = true)]
     pub is_sample: bool,
+    #[schema(example = true)]
+    pub is_pretest: bool,
     #[schema(example = 0)]
     pub position: i32,
     #[schema(example = 1)]
@@ -262,18 +414,70 @@ pub struct TestCaseListItem {
     pub label: String,
     #[schema(example = true)]
     pub is_sample: bool,
+    #[schema(example = true)]
+    pub is_pretest: bool,
     #[schema(example = 0)]
     pub position: i32,
     #[schema(example = "4\n2 7 11 15\n9")]
     pub input_preview: String,
     #[schema(example = "0 1")]
     pub output_preview: String,
+    /// Full input length in bytes.
+    #[schema(example = 12)]
+    pub input_size: i64,
+    /// Full expected output length in bytes.
+    #[schema(example = 4)]
+    pub output_size: i64,
     #[schema(example = 1)]
     pub problem_id: i32,
     #[schema(example = "2025-09-01T09:00:00Z")]
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Serialize, Debug, PartialEq, utoipa::ToSchema)]
+pub struct SubtaskScoreSummary {
+    /// Name of the subtask in the IOI task config.
+    #[schema(example = "Subtask 1")]
+    pub name: String,
+    /// Listed test cases that exist on the problem.
+    #[schema(example = 4)]
+    pub test_case_count: usize,
+    /// Sum of the listed test cases' scores.
+    #[schema(example = 30)]
+    pub score: i64,
+    /// `max_score` configured for the subtask.
+    #[schema(example = 30.0)]
+    pub max_score: f64,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProblemScoreSummaryQuery {
+    /// Contest whose IOI task config defines the problem's subtasks.
+    #[param(example = 1)]
+    pub contest_id: Option<i32>,
+}
+
+#[derive(Serialize, Debug, PartialEq, utoipa::ToSchema)]
+pub struct ProblemScoreSummaryResponse {
+    #[schema(example = 1)]
+    pub problem_id: i32,
+    /// Sum of all test case scores, samples included.
+    #[schema(example = 100)]
+    pub total_score: i64,
+    #[schema(example = 12)]
+    pub test_case_count: usize,
+    #[schema(example = 0)]
+    pub sample_score: i64,
+    /// The configured IOI subtasks, in config order; empty without a
+    /// `contest_id` or a task config.
+    pub subtasks: Vec<SubtaskScoreSummary>,
+    /// Human-readable inconsistencies, e.g. a subtask whose `max_score`
+    /// differs from its test cases' sum.
+    #[schema(example = json!(["Test case '1_03' belongs to no subtask"]))]
+    pub warnings: Vec<String>,
+}
+
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct UploadTestCasesResponse {
     #[schema(example = 5)]
@@ -294,15 +498,36 @@ impl From<crate::entity::problem::Model> for ProblemResponse {
             content: m.content,
             time_limit: m.time_limit,
             memory_limit: m.memory_limit,
+            wall_time_multiplier: m.wall_time_multiplier,
+            require_zero_exit: m.require_zero_exit,
             problem_type: m.problem_type,
             checker_source: m.checker_source,
             checker_format: m.checker_format,
             default_contest_type: m.default_contest_type,
-            show_test_details: m.show_test_details,
+            test_details_visibility: m.test_details_visibility,
+            show_test_details: m.test_details_visibility == TestDetailsVisibility::Full,
             submission_format,
+            difficulty: m.difficulty,
+            difficulty_is_manual: m.difficulty_is_manual,
+            is_public: m.is_public,
             samples: vec![],
             created_at: m.created_at,
             updated_at: m.updated_at,
+            similar_problems: vec![],
+        }
+    }
+}
+
+impl From<crate::entity::problem_version::Model> for ProblemVersionResponse {
+    fn from(m: crate::entity::problem_version::Model) -> Self {
+        Self {
+            version: m.version,
+            title: m.title,
+            content: m.content,
+            time_limit: m.time_limit,
+            memory_limit: m.memory_limit,
+            created_by: m.created_by,
+            created_at: m.created_at,
         }
     }
 }
@@ -320,10 +545,12 @@ impl From<crate::entity::test_case::Model> for TestCaseResponse {
             ),
             input: m.input,
             expected_output: m.expected_output,
+            accepted_outputs: accepted_outputs_from_json(m.accepted_outputs),
             score: m.score,
             description: m.description,
             label: m.label,
             is_sample: m.is_sample,
+            is_pretest: m.is_pretest,
             position: m.position,
             problem_id: m.problem_id,
             created_at: m.created_at,
@@ -435,6 +662,30 @@ pub fn validate_create_problem(req: &CreateProblemRequest) -> Result<(), AppErro
             "Memory limit must be 1-1048576 KB".into(),
         ));
     }
+    if let Some(multiplier) = req.wall_time_multiplier {
+        validate_wall_time_multiplier(multiplier)?;
+    }
+    if let Some(difficulty) = req.difficulty {
+        validate_difficulty(difficulty)?;
+    }
+    Ok(())
+}
+
+fn validate_wall_time_multiplier(multiplier: f64) -> Result<(), AppError> {
+    if !multiplier.is_finite() || multiplier < 1.0 {
+        return Err(AppError::Validation(
+            "Wall time multiplier must be at least 1".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_difficulty(difficulty: i32) -> Result<(), AppError> {
+    if !(MIN_DIFFICULTY..=MAX_DIFFICULTY).contains(&difficulty) {
+        return Err(AppError::Validation(format!(
+            "Difficulty must be {MIN_DIFFICULTY}-{MAX_DIFFICULTY}"
+        )));
+    }
     Ok(())
 }
 
@@ -506,10 +757,64 @@ pub fn validate_update_problem(req: &UpdateProblemRequest) -> Result<(), AppErro
             "Memory limit must be 1-1048576 KB".into(),
         ));
     }
+    if let Some(Some(multiplier)) = req.wall_time_multiplier {
+        validate_wall_time_multiplier(multiplier)?;
+    }
+    if let Some(Some(difficulty)) = req.difficulty {
+        validate_difficulty(difficulty)?;
+    }
 
     Ok(())
 }
 
+#[derive(Deserialize, Default, utoipa::ToSchema)]
+pub struct EstimateDifficultyRequest {
+    /// Only submissions from the last this-many days are counted. Defaults to 90.
+    #[schema(example = 90, minimum = 1, maximum = 3650)]
+    pub window_days: Option<u32>,
+    /// Replace a manually set difficulty with the estimate. Defaults to false.
+    #[schema(example = false)]
+    pub overwrite_manual: Option<bool>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct EstimateDifficultyResponse {
+    #[schema(example = 1)]
+    pub problem_id: i32,
+    /// Estimated 0-100 difficulty.
+    #[schema(example = 62)]
+    pub estimated_difficulty: i32,
+    /// Difficulty stored on the problem after this call.
+    #[schema(example = 62)]
+    pub difficulty: Option<i32>,
+    /// False when a manual difficulty was kept.
+    pub applied: bool,
+    #[schema(example = 90)]
+    pub window_days: u32,
+    #[schema(example = 240)]
+    pub total_submissions: u64,
+    #[schema(example = 60)]
+    pub accepted_submissions: u64,
+    #[schema(example = 80)]
+    pub distinct_attempters: u64,
+    #[schema(example = 35)]
+    pub distinct_solvers: u64,
+}
+
+pub const DEFAULT_DIFFICULTY_WINDOW_DAYS: u32 = 90;
+pub const MAX_DIFFICULTY_WINDOW_DAYS: u32 = 3650;
+
+pub fn validate_estimate_difficulty(req: &EstimateDifficultyRequest) -> Result<(), AppError> {
+    if let Some(days) = req.window_days
+        && !(1..=MAX_DIFFICULTY_WINDOW_DAYS).contains(&days)
+    {
+        return Err(AppError::Validation(format!(
+            "'window_days' must be between 1 and {MAX_DIFFICULTY_WINDOW_DAYS}"
+        )));
+    }
+    Ok(())
+}
+
 pub fn validate_submission_format(
     submission_format: Option<&HashMap<String, Vec<String>>>,
     known_languages: &HashSet<String>,
@@ -573,9 +878,44 @@ pub fn validate_create_test_case(req: &CreateTestCaseRequest) -> Result<(), AppE
     if let Some(ref label) = req.label {
         validate_label(label)?;
     }
+    validate_accepted_outputs(req.accepted_outputs.as_deref())?;
+    Ok(())
+}
+
+/// At most this many alternative outputs per test case.
+const MAX_ACCEPTED_OUTPUTS: usize = 64;
+/// Alternative outputs are compared inline, so each is kept small.
+const MAX_ACCEPTED_OUTPUT_BYTES: usize = 64 * 1024;
+
+fn validate_accepted_outputs(outputs: Option<&[String]>) -> Result<(), AppError> {
+    let Some(outputs) = outputs else {
+        return Ok(());
+    };
+    if outputs.len() > MAX_ACCEPTED_OUTPUTS {
+        return Err(AppError::Validation(format!(
+            "accepted_outputs must have at most {MAX_ACCEPTED_OUTPUTS} entries"
+        )));
+    }
+    if outputs.iter().any(|o| o.len() > MAX_ACCEPTED_OUTPUT_BYTES) {
+        return Err(AppError::Validation(format!(
+            "Each accepted output must be at most {MAX_ACCEPTED_OUTPUT_BYTES} bytes"
+        )));
+    }
     Ok(())
 }
 
+/// Stored form of `accepted_outputs`; `None` when there are none.
+pub fn accepted_outputs_to_json(outputs: Option<Vec<String>>) -> Option<serde_json::Value> {
+    outputs
+        .filter(|o| !o.is_empty())
+        .map(serde_json::Value::from)
+}
+
+/// Decodes a stored `accepted_outputs` column, dropping malformed data.
+pub fn accepted_outputs_from_json(value: Option<serde_json::Value>) -> Option<Vec<String>> {
+    value.and_then(|v| serde_json::from_value(v).ok())
+}
+
 pub(crate) fn validate_label(label: &str) -> Result<(), AppError> {
     let trimmed = label.trim();
     if trimmed.is_empty() {
@@ -626,5 +966,8 @@ pub fn validate_update_test_case(req: &UpdateTestCaseRequest) -> Result<(), AppE
     if let Some(ref label) = req.label {
         validate_label(label)?;
     }
+    if let Some(ref outputs) = req.accepted_outputs {
+        validate_accepted_outputs(outputs.as_deref())?;
+    }
     Ok(())
 }
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::models::problem::ProblemListQuery;
+use crate::models::submission::SubmissionListQuery;
+
+/// List endpoint a saved view applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedViewResource {
+    /// `GET /submissions`
+    Submissions,
+    /// `GET /problems`
+    Problems,
+}
+
+impl SavedViewResource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Submissions => "submissions",
+            Self::Problems => "problems",
+        }
+    }
+
+    /// Checks that `params` is a valid query for this resource.
+    pub fn validate_params(&self, params: &serde_json::Value) -> Result<(), AppError> {
+        if params.get("view").is_some() {
+            return Err(AppError::Validation(
+                "Saved view params cannot reference another view".into(),
+            ));
+        }
+        let result = match self {
+            Self::Submissions => {
+                serde_json::from_value::<SubmissionListQuery>(params.clone()).map(|_| ())
+            }
+            Self::Problems => {
+                serde_json::from_value::<ProblemListQuery>(params.clone()).map(|_| ())
+            }
+        };
+        result.map_err(|e| AppError::Validation(format!("Invalid view params: {e}")))
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct CreateSavedViewRequest {
+    #[schema(example = "My pending C++")]
+    pub name: String,
+    pub resource: SavedViewResource,
+    /// Query parameters of the list endpoint, as JSON values.
+    #[schema(example = json!({"language": "cpp", "status": "Pending", "sort_order": "asc"}))]
+    pub params: serde_json::Value,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SavedViewListQuery {
+    pub resource: Option<SavedViewResource>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct SavedViewResponse {
+    #[schema(example = 1)]
+    pub id: i32,
+    #[schema(example = "My pending C++")]
+    pub name: String,
+    #[schema(example = "submissions")]
+    pub resource: String,
+    pub params: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<crate::entity::saved_view::Model> for SavedViewResponse {
+    fn from(m: crate::entity::saved_view::Model) -> Self {
+        Self {
+            id: m.id,
+            name: m.name,
+            resource: m.resource,
+            params: m.params,
+            created_at: m.created_at,
+        }
+    }
+}
+
+pub fn validate_create_saved_view(req: &CreateSavedViewRequest) -> Result<(), AppError> {
+    let name = req.name.trim();
+    if name.is_empty() || name.chars().count() > 64 {
+        return Err(AppError::Validation("Name must be 1-64 characters".into()));
+    }
+    if !req.params.is_object() {
+        return Err(AppError::Validation("params must be a JSON object".into()));
+    }
+    req.resource.validate_params(&req.params)
+}
@@ -1,7 +1,9 @@
 use chrono::{DateTime, Utc};
+use common::submission_dispatch::JudgeMode;
 use common::{SubmissionStatus, Verdict};
 use serde::{Deserialize, Serialize};
 
+use crate::entity::rejudge_batch;
 use crate::error::AppError;
 
 use super::shared::Pagination;
@@ -41,10 +43,17 @@ impl From<SubmissionFile> for SubmissionFileDto {
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct CreateSubmissionRequest {
     pub files: Vec<SubmissionFileDto>,
+    /// Omit or pass `auto` to infer the language from the file extensions.
+    #[serde(default)]
     #[schema(example = "cpp")]
-    pub language: String,
+    pub language: Option<String>,
     #[schema(example = "ioi")]
     pub contest_type: Option<String>,
+    /// `compile_only` compiles and records the diagnostics without
+    /// evaluating any test case. Not accepted for contest submissions.
+    #[serde(default)]
+    #[schema(value_type = String, example = "full")]
+    pub judge_mode: JudgeMode,
 }
 
 #[derive(Deserialize, utoipa::IntoParams)]
@@ -64,10 +73,68 @@ pub struct SubmissionListQuery {
     /// Free-text search across username, problem title, and contest title (case-insensitive).
     #[param(example = "alice")]
     pub q: Option<String>,
+    /// Only submissions created at or after this RFC3339 timestamp.
+    #[param(example = "2025-10-01T00:00:00Z")]
+    pub created_after: Option<String>,
+    /// Only submissions created before this RFC3339 timestamp.
+    #[param(example = "2025-10-08T00:00:00Z")]
+    pub created_before: Option<String>,
     #[param(example = "created_at")]
     pub sort_by: Option<String>,
     #[param(example = "desc")]
     pub sort_order: Option<String>,
+    /// Name of a saved view whose params fill in any not given here.
+    #[param(example = "My pending C++")]
+    pub view: Option<String>,
+}
+
+impl SubmissionListQuery {
+    /// Fills parameters missing from `self` with those of a saved view.
+    pub fn or_saved(self, saved: Self) -> Self {
+        Self {
+            page: self.page.or(saved.page),
+            per_page: self.per_page.or(saved.per_page),
+            problem_id: self.problem_id.or(saved.problem_id),
+            user_id: self.user_id.or(saved.user_id),
+            language: self.language.or(saved.language),
+            status: self.status.or(saved.status),
+            q: self.q.or(saved.q),
+            created_after: self.created_after.or(saved.created_after),
+            created_before: self.created_before.or(saved.created_before),
+            sort_by: self.sort_by.or(saved.sort_by),
+            sort_order: self.sort_order.or(saved.sort_order),
+            view: self.view,
+        }
+    }
+
+    /// Parsed `created_after` and `created_before` bounds.
+    pub fn created_range(&self) -> Result<CreatedRange, AppError> {
+        let after = parse_timestamp_param("created_after", self.created_after.as_deref())?;
+        let before = parse_timestamp_param("created_before", self.created_before.as_deref())?;
+        if let (Some(after), Some(before)) = (after, before)
+            && after >= before
+        {
+            return Err(AppError::Validation(
+                "created_after must be earlier than created_before".into(),
+            ));
+        }
+        Ok(CreatedRange { after, before })
+    }
+}
+
+/// Half-open creation-time window from a [`SubmissionListQuery`].
+pub struct CreatedRange {
+    pub after: Option<DateTime<Utc>>,
+    pub before: Option<DateTime<Utc>>,
+}
+
+fn parse_timestamp_param(name: &str, raw: Option<&str>) -> Result<Option<DateTime<Utc>>, AppError> {
+    raw.map(|raw| {
+        DateTime::parse_from_rfc3339(raw.trim())
+            .map(|t| t.with_timezone(&Utc))
+            .map_err(|_| AppError::Validation(format!("{name} must be an RFC3339 timestamp")))
+    })
+    .transpose()
 }
 
 #[derive(Serialize, Deserialize, utoipa::ToSchema)]
@@ -75,6 +142,12 @@ pub struct SubmissionResponse {
     #[schema(example = 1)]
     pub id: i32,
     pub files: Vec<SubmissionFileDto>,
+    /// Total size of the submitted files in bytes.
+    #[schema(example = 1024)]
+    pub source_size: u64,
+    /// Total number of lines across the submitted files.
+    #[schema(example = 42)]
+    pub line_count: u64,
     #[schema(example = "cpp")]
     pub language: String,
     pub status: SubmissionStatus,
@@ -96,9 +169,33 @@ pub struct SubmissionResponse {
     /// and every operation it produces will run there.
     #[schema(example = "worker-1")]
     pub target_worker_id: Option<String>,
+    /// Worker that ran the test cases. Only shown to `submission:view_all`
+    /// holders.
+    #[schema(example = "worker-1")]
+    pub judged_by: Option<String>,
+    #[schema(value_type = String, example = "full")]
+    pub judge_mode: JudgeMode,
+    /// Estimated place in the judging queue: 1 plus the number of unfinished
+    /// submissions created before this one. Only set while this submission
+    /// is unfinished; re-fetch for a fresh estimate.
+    #[schema(example = 3)]
+    pub queue_position: Option<u64>,
+    /// Seconds since the current judgement was queued. Only set while this
+    /// submission is unfinished.
+    #[schema(example = 12)]
+    pub pending_seconds: Option<i64>,
+    /// Milliseconds the current judgement took from being queued to being
+    /// finalized. `null` until it finishes.
+    #[schema(example = 1830)]
+    pub judging_duration_ms: Option<i64>,
     #[schema(example = "2025-10-01T14:30:00Z")]
     pub created_at: DateTime<Utc>,
     pub result: Option<JudgeResultResponse>,
+    /// Set while the contest problem's `result_delay_secs` still withholds
+    /// `result` from the caller: when it will be shown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "2025-10-01T14:35:00Z")]
+    pub results_hidden_until: Option<DateTime<Utc>>,
 }
 
 #[derive(Serialize, Deserialize, utoipa::ToSchema)]
@@ -107,8 +204,13 @@ pub struct SubmissionListItem {
     pub id: i32,
     #[schema(example = "cpp")]
     pub language: String,
+    /// Total size of the submitted files in bytes.
+    #[schema(example = 1024)]
+    pub source_size: u64,
+    /// Total number of lines across the submitted files.
+    #[schema(example = 42)]
+    pub line_count: u64,
     pub status: SubmissionStatus,
-    #[schema(value_type = Option<String>, example = "Accepted")]
     pub verdict: Option<Verdict>,
     #[schema(example = 1)]
     pub user_id: i32,
@@ -129,8 +231,10 @@ pub struct SubmissionListItem {
     pub created_at: DateTime<Utc>,
     #[schema(example = 100.0)]
     pub score: Option<f64>,
+    /// CPU time in milliseconds, rounded up.
     #[schema(example = 50)]
     pub time_used: Option<i32>,
+    /// Peak memory in kilobytes.
     #[schema(example = 1024)]
     pub memory_used: Option<i32>,
 }
@@ -143,18 +247,42 @@ pub struct SubmissionListResponse {
 
 #[derive(Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct JudgeResultResponse {
-    #[schema(value_type = Option<String>, example = "Accepted")]
     pub verdict: Option<Verdict>,
     #[schema(example = 100.0)]
     pub score: Option<f64>,
+    /// `score` as a percentage of the most the problem awards, rounded to
+    /// two decimals. `null` while unscored or when the problem awards no
+    /// points.
+    #[serde(default)]
+    #[schema(example = 50.0)]
+    pub score_percentage: Option<f64>,
+    /// CPU time in milliseconds, rounded up.
     #[schema(example = 50)]
     pub time_used: Option<i32>,
+    /// Peak memory in kilobytes.
     #[schema(example = 1024)]
     pub memory_used: Option<i32>,
     pub compile_output: Option<String>,
     pub error_message: Option<String>,
     pub judged_at: Option<DateTime<Utc>>,
     pub test_case_results: Vec<TestCaseResultResponse>,
+    /// Per-subtask breakdown of `score`, in subtask order. Empty when the
+    /// contest type does not report subtasks.
+    #[serde(default)]
+    pub subtasks: Vec<SubtaskResultResponse>,
+}
+
+#[derive(Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SubtaskResultResponse {
+    #[schema(example = "Subtask 1")]
+    pub name: String,
+    /// Points awarded for this subtask.
+    #[schema(example = 20.0)]
+    pub score: f64,
+    #[schema(example = 30.0)]
+    pub max_score: f64,
+    /// Worst verdict among the subtask's evaluated test cases.
+    pub verdict: Option<Verdict>,
 }
 
 #[derive(Clone, Serialize, Deserialize, utoipa::ToSchema)]
@@ -168,12 +296,13 @@ pub struct SubmissionJudgementResponse {
     pub is_current: bool,
     pub is_finalized: bool,
     pub status: SubmissionStatus,
-    #[schema(value_type = Option<String>, example = "Accepted")]
     pub verdict: Option<Verdict>,
     #[schema(example = 100.0)]
     pub score: Option<f64>,
+    /// CPU time in milliseconds, rounded up.
     #[schema(example = 50)]
     pub time_used: Option<i32>,
+    /// Peak memory in kilobytes.
     #[schema(example = 1024)]
     pub memory_used: Option<i32>,
     pub compile_output: Option<String>,
@@ -187,18 +316,21 @@ pub struct SubmissionJudgementResponse {
     pub created_at: DateTime<Utc>,
     pub finalized_at: Option<DateTime<Utc>>,
     pub test_case_results: Vec<TestCaseResultResponse>,
+    #[serde(default)]
+    pub subtasks: Vec<SubtaskResultResponse>,
 }
 
 #[derive(Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct TestCaseResultResponse {
     #[schema(example = 1)]
     pub id: i32,
-    #[schema(value_type = String, example = "Accepted")]
     pub verdict: Verdict,
     #[schema(example = 10.0)]
     pub score: f64,
+    /// CPU time in milliseconds, rounded up.
     #[schema(example = 5)]
     pub time_used: Option<i32>,
+    /// Peak memory in kilobytes.
     #[schema(example = 256)]
     pub memory_used: Option<i32>,
     pub test_case_id: Option<i32>,
@@ -282,6 +414,77 @@ pub struct AdminFanOutSubmissionResponse {
 pub struct BulkRejudgeResponse {
     #[schema(example = 1234)]
     pub queued: usize,
+    /// Progress handle for the queued judgements; null when nothing was queued.
+    #[schema(example = 7)]
+    pub rejudge_batch_id: Option<i32>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RejudgeBatchResponse {
+    #[schema(example = 7)]
+    pub id: i32,
+    #[schema(example = 1)]
+    pub triggered_by_user_id: i32,
+    /// Judgements queued by the rejudge.
+    #[schema(example = 1234)]
+    pub total: i32,
+    /// Judgements not yet finished.
+    #[schema(example = 200)]
+    pub remaining: i32,
+    pub created_at: DateTime<Utc>,
+    /// Set once `remaining` reaches zero.
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<rejudge_batch::Model> for RejudgeBatchResponse {
+    fn from(m: rejudge_batch::Model) -> Self {
+        Self {
+            id: m.id,
+            triggered_by_user_id: m.triggered_by_user_id,
+            total: m.total,
+            remaining: m.remaining,
+            created_at: m.created_at,
+            completed_at: m.completed_at,
+        }
+    }
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct CreateSubmissionCommentRequest {
+    #[schema(example = "Consider the case where n = 0.")]
+    pub content: String,
+    /// Whether the submission's owner can see the comment.
+    #[serde(default)]
+    #[schema(example = false)]
+    pub is_public: bool,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct SubmissionCommentResponse {
+    #[schema(example = 1)]
+    pub id: i32,
+    #[schema(example = 42)]
+    pub submission_id: i32,
+    #[schema(example = 3)]
+    pub author_id: i32,
+    #[schema(example = "grader")]
+    pub author_name: String,
+    pub content: String,
+    #[schema(example = false)]
+    pub is_public: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+pub fn validate_create_submission_comment(
+    req: &CreateSubmissionCommentRequest,
+) -> Result<(), AppError> {
+    let content = req.content.trim();
+    if content.is_empty() || content.chars().count() > 10_000 {
+        return Err(AppError::Validation(
+            "Content must be 1 – 10 000 characters".into(),
+        ));
+    }
+    Ok(())
 }
 
 pub fn validate_bulk_rejudge(req: &BulkRejudgeRequest) -> Result<(), AppError> {
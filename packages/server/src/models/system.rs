@@ -10,7 +10,8 @@ pub struct WorkerInfo {
     /// Seconds since the worker last wrote a heartbeat. 0 means just now.
     #[schema(example = 3)]
     pub seconds_since_last_seen: u64,
-    /// True when the heartbeat is older than 10s — worker is likely unhealthy.
+    /// True when Redis holds no heartbeat from the last 10s — worker is likely
+    /// unhealthy or gone.
     pub stale: bool,
     #[schema(example = 0)]
     pub in_flight: u32,
@@ -1,3 +1,4 @@
+use crate::error::AppError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +12,8 @@ pub struct UserResponse {
     pub password: String,
     #[schema(example = json!(["contestant"]))]
     pub roles: Vec<String>,
+    #[schema(example = true)]
+    pub is_active: bool,
     #[schema(example = "2026-03-05T10:00:00Z")]
     pub created_at: DateTime<Utc>,
 }
@@ -22,6 +25,7 @@ impl From<crate::entity::user::ModelEx> for UserResponse {
             username: user.username,
             password: user.password,
             roles: user.roles.into_iter().map(|r| r.name).collect(),
+            is_active: user.is_active,
             created_at: user.created_at,
         }
     }
@@ -33,6 +37,44 @@ pub struct UpdateUserRequest {
     pub password: Option<String>,
 }
 
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct UpdateUserStatusRequest {
+    /// `false` deactivates the account; `true` reactivates it.
+    #[schema(example = false)]
+    pub is_active: bool,
+}
+
+#[derive(Deserialize, Default, utoipa::ToSchema)]
+pub struct ResetPasswordRequest {
+    /// New password, 8-128 characters. Omit to have one generated.
+    #[serde(default)]
+    #[schema(example = "n3w_P@ssword")]
+    pub password: Option<String>,
+}
+
+pub fn validate_reset_password_request(payload: &ResetPasswordRequest) -> Result<(), AppError> {
+    if let Some(password) = &payload.password
+        && (password.len() < 8 || password.len() > 128)
+    {
+        return Err(AppError::Validation(
+            "Password must be 8-128 characters".into(),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ResetPasswordResponse {
+    #[schema(example = 5)]
+    pub user_id: i32,
+    #[schema(example = "alice")]
+    pub username: String,
+    /// The generated password; absent when the request supplied one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "aB3$kLm9xQ2z")]
+    pub generated_password: Option<String>,
+}
+
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct RoleAssignmentRequest {
     #[schema(example = "admin")]
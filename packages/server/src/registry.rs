@@ -19,6 +19,10 @@ pub struct ContestTypeHandlers {
     pub submission_fn: String,
     pub code_run_fn: String,
     pub filter_submission_fn: Option<String>,
+    /// The contest type scores a submission as the sum of its test case
+    /// scores, with the most severe test case verdict, so its result can be
+    /// recomputed from the stored rows.
+    pub summed_scores: bool,
 }
 
 pub struct BatchState<T> {
@@ -42,7 +46,13 @@ pub struct LanguageResolverEntry {
     pub display_name: String,
     pub default_filename: String,
     pub extensions: Vec<String>,
+    /// Extensions of files that may hold arbitrary bytes and are exempt from
+    /// the UTF-8 check on submission.
+    pub binary_extensions: Vec<String>,
     pub template: String,
+    /// Problem types this language may be submitted to. Empty means every
+    /// problem type.
+    pub problem_types: Vec<String>,
 }
 
 pub type LanguageResolverRegistry = Arc<RwLock<HashMap<String, LanguageResolverEntry>>>;
@@ -34,10 +34,15 @@ pub fn routes(config: &AppConfig) -> OpenApiRouter<AppState> {
         .nest("/i18n", i18n_routes())
         .nest("/config/upload", config_upload_routes())
         .nest("/problems", problem_routes(submission_max_size))
+        .nest("/practice", practice_routes())
         .nest("/contests", contest_routes(submission_max_size))
         .nest("/submissions", submission_routes())
+        .nest("/rejudge-batches", rejudge_batch_routes())
+        .nest("/views", saved_view_routes())
         .nest("/code-runs", code_run_routes())
+        .nest("/runs", run_routes())
         .nest("/dlq", dlq_routes())
+        .nest("/maintenance", maintenance_routes())
         .nest("/telemetry", telemetry_routes())
 }
 
@@ -74,6 +79,7 @@ fn auth_routes(rate_limit_auth: bool) -> OpenApiRouter<AppState> {
         .routes(routes!(handlers::auth::refresh))
         .routes(routes!(handlers::auth::logout))
         .routes(routes!(handlers::auth::me))
+        .routes(routes!(handlers::auth::permissions))
         .routes(routes!(handlers::auth::request_device_code))
         .routes(routes!(handlers::auth::authorize_device))
         .routes(routes!(handlers::auth::poll_device_token))
@@ -177,6 +183,8 @@ fn user_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(handlers::user::get_user))
         .routes(routes!(handlers::user::delete_user))
         .routes(routes!(handlers::user::update_user))
+        .routes(routes!(handlers::user::reset_password))
+        .routes(routes!(handlers::user::update_user_status))
         .routes(routes!(handlers::user::assign_role))
         .routes(routes!(handlers::user::revoke_role))
 }
@@ -234,6 +242,8 @@ fn plugin_routes() -> OpenApiRouter<AppState> {
     OpenApiRouter::new()
         .routes(routes!(handlers::plugin::list_registries))
         .routes(routes!(handlers::plugin::list_active_plugins))
+        .routes(routes!(handlers::plugin::list_plugin_functions))
+        .routes(routes!(handlers::plugin::list_plugin_assets))
 }
 
 fn proxy_routes() -> OpenApiRouter<AppState> {
@@ -266,6 +276,10 @@ fn problem_routes(submission_max_size: usize) -> OpenApiRouter<AppState> {
             handlers::problem::update_problem,
             handlers::problem::delete_problem,
         ))
+        .routes(routes!(handlers::problem::list_problem_versions))
+        .routes(routes!(handlers::problem::restore_problem_version))
+        .routes(routes!(handlers::problem::get_problem_score_summary))
+        .routes(routes!(handlers::problem::estimate_problem_difficulty))
         .nest("/{id}/test-cases", test_case_routes())
         .nest("/{id}/attachments", attachment_routes())
         .nest("/{id}/additional-files", additional_file_routes())
@@ -279,6 +293,11 @@ fn problem_routes(submission_max_size: usize) -> OpenApiRouter<AppState> {
             "/{id}/code-runs",
             problem_code_run_routes(submission_max_size),
         )
+        .nest("/{id}/run", problem_run_routes(submission_max_size))
+}
+
+fn practice_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(handlers::problem::list_practice_problems))
 }
 
 fn problem_submission_routes(submission_max_size: usize) -> OpenApiRouter<AppState> {
@@ -307,6 +326,7 @@ fn test_case_routes() -> OpenApiRouter<AppState> {
         ))
         .routes(routes!(handlers::problem::reorder_test_cases))
         .routes(routes!(handlers::problem::bulk_delete_test_cases))
+        .routes(routes!(handlers::problem::import_test_cases_dir))
         .routes(routes!(
             handlers::problem::get_test_case,
             handlers::problem::update_test_case,
@@ -363,6 +383,11 @@ fn contest_routes(submission_max_size: usize) -> OpenApiRouter<AppState> {
             handlers::contest::delete_contest,
         ))
         .routes(routes!(handlers::contest::get_contest_my_info))
+        .routes(routes!(handlers::contest::get_contest_my_status))
+        .routes(routes!(handlers::contest::export_standings_csv))
+        .routes(routes!(handlers::contest::run_system_tests))
+        .routes(routes!(handlers::contest::freeze_contest))
+        .routes(routes!(handlers::contest::unfreeze_contest))
         .nest(
             "/{id}/problems",
             contest_problem_routes(submission_max_size),
@@ -437,12 +462,19 @@ fn contest_participant_routes() -> OpenApiRouter<AppState> {
 fn submission_routes() -> OpenApiRouter<AppState> {
     OpenApiRouter::new()
         .routes(routes!(handlers::submission::list_submissions))
+        .routes(routes!(handlers::submission::export_submissions))
         .routes(routes!(handlers::submission::bulk_rejudge_submissions))
         .routes(routes!(handlers::submission::get_submission))
+        .routes(routes!(
+            handlers::submission::list_submission_comments,
+            handlers::submission::create_submission_comment,
+        ))
         .routes(routes!(handlers::submission::list_submission_judgements))
         .routes(routes!(handlers::submission::apply_submission_judgement))
         .routes(routes!(handlers::submission::discard_submission_judgement))
+        .routes(routes!(handlers::submission::recompute_submission_result))
         .routes(routes!(handlers::submission::rejudge_submission))
+        .routes(routes!(handlers::submission::clone_submission_as_practice))
 }
 
 fn problem_config_routes() -> OpenApiRouter<AppState> {
@@ -481,6 +513,16 @@ fn code_run_routes() -> OpenApiRouter<AppState> {
     OpenApiRouter::new().routes(routes!(handlers::code_run::get_code_run))
 }
 
+fn run_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(handlers::code_run::get_run))
+}
+
+fn problem_run_routes(submission_max_size: usize) -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(handlers::code_run::run_problem))
+        .layer(handlers::code_run::code_run_body_limit(submission_max_size))
+}
+
 fn problem_code_run_routes(submission_max_size: usize) -> OpenApiRouter<AppState> {
     OpenApiRouter::new()
         .routes(routes!(handlers::code_run::run_code))
@@ -506,6 +548,23 @@ fn dlq_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(handlers::dlq::retry_dlq_message))
 }
 
+fn rejudge_batch_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(handlers::submission::get_rejudge_batch))
+}
+
+fn saved_view_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(
+            handlers::saved_view::list_saved_views,
+            handlers::saved_view::create_saved_view,
+        ))
+        .routes(routes!(handlers::saved_view::delete_saved_view))
+}
+
+fn maintenance_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(handlers::maintenance::archive_submissions))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
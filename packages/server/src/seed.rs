@@ -1,12 +1,18 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, bail};
 use common::storage::BlobStore;
+use config::{Config, File};
 use sea_orm::*;
 use sea_query::{Expr, Index, PostgresQueryBuilder};
+use serde::Deserialize;
 use tracing::{error, info};
 
 use crate::entity::{
-    additional_file, clarification, dead_letter_message, plugin_storage, problem_attachment, role,
-    role_permission, submission, submission_judgement, test_case, test_case_result, user,
-    user_role,
+    additional_file, clarification, dead_letter_message, plugin_storage, problem_attachment,
+    problem_version, role, role_permission, saved_view, submission, submission_judgement,
+    test_case, test_case_result, user, user_role,
 };
 use crate::utils::hash;
 use crate::utils::test_case_body::{INLINE_TEST_CASE_BODY_THRESHOLD_BYTES, prepare_test_case_body};
@@ -15,6 +21,7 @@ const DEFAULT_ROLES: &[&str] = &["admin", "problem_setter", "contestant"];
 const SERVER_MIGRATION_PLUGIN_ID: &str = "__server_migration";
 const SERVER_MIGRATION_COLLECTION: &str = "default";
 const LARGE_TEST_CASE_BODY_BACKFILL_KEY: &str = "large_test_case_body_backfill_v1";
+const TEST_DETAILS_VISIBILITY_MIGRATION_KEY: &str = "test_details_visibility_migration_v1";
 const LARGE_TEST_CASE_BODY_BACKFILL_BATCH_SIZE: u64 = 2;
 
 const DEFAULT_MAPPINGS: &[(&str, &str)] = &[
@@ -24,6 +31,7 @@ const DEFAULT_MAPPINGS: &[(&str, &str)] = &[
     ("admin", "problem:create"),
     ("admin", "problem:edit"),
     ("admin", "problem:delete"),
+    ("admin", "problem:manage"),
     ("admin", "contest:create"),
     ("admin", "contest:manage"),
     ("admin", "contest:delete"),
@@ -40,9 +48,94 @@ const DEFAULT_MAPPINGS: &[(&str, &str)] = &[
     ("contestant", "submission:submit"),
 ];
 
+/// Roles and the permissions granted to them, as read from
+/// `bootstrap.seed_file`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeedFile {
+    #[serde(default)]
+    pub roles: Vec<SeedRole>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeedRole {
+    pub name: String,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+impl SeedFile {
+    /// Reads and validates a seed file. The format follows the extension
+    /// (`.toml`, `.json`, `.yaml`).
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let seed: Self = Config::builder()
+            .add_source(File::from(path))
+            .build()
+            .and_then(Config::try_deserialize)
+            .with_context(|| format!("Invalid seed file {}", path.display()))?;
+        seed.validate()
+            .with_context(|| format!("Invalid seed file {}", path.display()))?;
+        Ok(seed)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        let mut seen = HashSet::new();
+        for role in &self.roles {
+            if role.name.is_empty() || role.name.contains(char::is_whitespace) {
+                bail!(
+                    "role name {:?} must be non-empty without whitespace",
+                    role.name
+                );
+            }
+            if !seen.insert(role.name.as_str()) {
+                bail!("role {:?} is listed more than once", role.name);
+            }
+            for permission in &role.permissions {
+                let valid = permission
+                    .split_once(':')
+                    .is_some_and(|(resource, action)| {
+                        !resource.is_empty()
+                            && !action.is_empty()
+                            && !permission.contains(char::is_whitespace)
+                    });
+                if !valid {
+                    bail!(
+                        "permission {permission:?} of role {:?} must look like `resource:action`",
+                        role.name
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Seeds the built-in roles and permissions.
 pub async fn seed_role_permissions(db: &DatabaseConnection) -> Result<(), DbErr> {
+    seed_roles(db, DEFAULT_ROLES, DEFAULT_MAPPINGS).await
+}
+
+/// Seeds the roles and permissions of `seed` on top of the built-in ones.
+/// Like the built-in seed, it only adds: existing roles and grants are
+/// left alone.
+pub async fn seed_roles_from_file(db: &DatabaseConnection, seed: &SeedFile) -> Result<(), DbErr> {
+    let names: Vec<&str> = seed.roles.iter().map(|r| r.name.as_str()).collect();
+    let mappings: Vec<(&str, &str)> = seed
+        .roles
+        .iter()
+        .flat_map(|r| r.permissions.iter().map(|p| (r.name.as_str(), p.as_str())))
+        .collect();
+    seed_roles(db, &names, &mappings).await
+}
+
+async fn seed_roles(
+    db: &DatabaseConnection,
+    roles: &[&str],
+    mappings: &[(&str, &str)],
+) -> Result<(), DbErr> {
     let mut roles_inserted = 0u32;
-    for &name in DEFAULT_ROLES {
+    for &name in roles {
         let model = role::ActiveModel {
             name: Set(name.to_string()),
         };
@@ -68,7 +161,7 @@ pub async fn seed_role_permissions(db: &DatabaseConnection) -> Result<(), DbErr>
     }
 
     let mut perms_inserted = 0u32;
-    for &(role, permission) in DEFAULT_MAPPINGS {
+    for &(role, permission) in mappings {
         let model = role_permission::ActiveModel {
             role: Set(role.to_string()),
             permission: Set(permission.to_string()),
@@ -377,6 +470,35 @@ pub async fn ensure_indexes(db: &DatabaseConnection) -> Result<(), DbErr> {
         Err(e) => tracing::warn!("Failed to create idx_test_case_result_judgement: {}", e),
     }
 
+    let stmt = Index::create()
+        .if_not_exists()
+        .unique()
+        .name("idx_problem_version_unique")
+        .table(problem_version::Entity)
+        .col(problem_version::Column::ProblemId)
+        .col(problem_version::Column::Version)
+        .to_string(PostgresQueryBuilder);
+    let result = db.execute_unprepared(&stmt).await;
+    match result {
+        Ok(_) => info!("Ensured idx_problem_version_unique exists"),
+        Err(e) => tracing::warn!("Failed to create idx_problem_version_unique: {}", e),
+    }
+
+    let stmt = Index::create()
+        .if_not_exists()
+        .unique()
+        .name("idx_saved_view_user_resource_name")
+        .table(saved_view::Entity)
+        .col(saved_view::Column::UserId)
+        .col(saved_view::Column::Resource)
+        .col(saved_view::Column::Name)
+        .to_string(PostgresQueryBuilder);
+    let result = db.execute_unprepared(&stmt).await;
+    match result {
+        Ok(_) => info!("Ensured idx_saved_view_user_resource_name exists"),
+        Err(e) => tracing::warn!("Failed to create idx_saved_view_user_resource_name: {}", e),
+    }
+
     Ok(())
 }
 
@@ -450,6 +572,80 @@ pub async fn backfill_submission_judgements(db: &DatabaseConnection) -> Result<(
     Ok(())
 }
 
+/// One-shot migration from the boolean `problem.show_test_details` column
+/// to `test_details_visibility`: `true` becomes `Full`, `false` becomes
+/// `SampleOnly`.
+///
+/// The column is converted and dropped in the same transaction that records
+/// the migration as done, so it runs at most once per database; fresh
+/// databases, which never had the column, only record it.
+pub async fn migrate_test_details_visibility(db: &DatabaseConnection) -> Result<(), DbErr> {
+    if plugin_storage::Entity::find_by_id((
+        SERVER_MIGRATION_PLUGIN_ID.to_string(),
+        SERVER_MIGRATION_COLLECTION.to_string(),
+        TEST_DETAILS_VISIBILITY_MIGRATION_KEY.to_string(),
+    ))
+    .one(db)
+    .await?
+    .is_some()
+    {
+        return Ok(());
+    }
+
+    let txn = db.begin().await?;
+    let legacy_column = txn
+        .query_one_raw(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "SELECT 1 AS present FROM information_schema.columns \
+             WHERE table_schema = current_schema() \
+               AND table_name = 'problem' \
+               AND column_name = 'show_test_details'",
+        ))
+        .await?;
+    let mut migrated = 0;
+    if legacy_column.is_some() {
+        migrated = txn
+            .execute_unprepared(
+                "UPDATE problem SET test_details_visibility = \
+                    CASE WHEN show_test_details THEN 'Full' ELSE 'SampleOnly' END",
+            )
+            .await?
+            .rows_affected();
+        txn.execute_unprepared("ALTER TABLE problem DROP COLUMN show_test_details")
+            .await?;
+    }
+    plugin_storage::Entity::insert(plugin_storage::ActiveModel {
+        plugin_id: Set(SERVER_MIGRATION_PLUGIN_ID.to_string()),
+        collection: Set(SERVER_MIGRATION_COLLECTION.to_string()),
+        key: Set(TEST_DETAILS_VISIBILITY_MIGRATION_KEY.to_string()),
+        data: Set(serde_json::json!({
+            "completed_at": chrono::Utc::now(),
+            "rows": migrated,
+        })),
+        created_at: Set(chrono::Utc::now()),
+    })
+    .on_conflict(
+        sea_query::OnConflict::columns([
+            plugin_storage::Column::PluginId,
+            plugin_storage::Column::Collection,
+            plugin_storage::Column::Key,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .exec_without_returning(&txn)
+    .await?;
+    txn.commit().await?;
+
+    if legacy_column.is_some() {
+        info!(
+            rows = migrated,
+            "Migrated problem.show_test_details to test_details_visibility"
+        );
+    }
+    Ok(())
+}
+
 pub async fn backfill_large_test_case_bodies(
     db: &DatabaseConnection,
     blob_store: std::sync::Arc<dyn BlobStore>,
@@ -577,3 +773,75 @@ pub fn spawn_large_test_case_body_backfill(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(name: &str, contents: &str) -> anyhow::Result<SeedFile> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).unwrap();
+        SeedFile::load(&path)
+    }
+
+    fn error_message(result: anyhow::Result<SeedFile>) -> String {
+        format!("{:#}", result.unwrap_err())
+    }
+
+    #[test]
+    fn loads_roles_from_toml_and_json() {
+        let seed = load(
+            "seed.toml",
+            "[[roles]]\nname = \"judge\"\npermissions = [\"submission:rejudge\"]\n",
+        )
+        .unwrap();
+        assert_eq!(seed.roles.len(), 1);
+        assert_eq!(seed.roles[0].name, "judge");
+        assert_eq!(seed.roles[0].permissions, ["submission:rejudge"]);
+
+        let seed = load("seed.json", r#"{"roles": [{"name": "observer"}]}"#).unwrap();
+        assert_eq!(seed.roles[0].name, "observer");
+        assert!(seed.roles[0].permissions.is_empty());
+    }
+
+    #[test]
+    fn malformed_file_is_rejected_with_its_path() {
+        let message = error_message(load("seed.toml", "[[roles]\nname = "));
+        assert!(message.starts_with("Invalid seed file "), "{message}");
+        assert!(message.contains("seed.toml"), "{message}");
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let message = error_message(load(
+            "seed.toml",
+            "[[roles]]\nname = \"judge\"\nperms = [\"submission:rejudge\"]\n",
+        ));
+        assert!(message.contains("perms"), "{message}");
+    }
+
+    #[test]
+    fn invalid_roles_and_permissions_are_rejected() {
+        let message = error_message(load(
+            "seed.toml",
+            "[[roles]]\nname = \"judge\"\n[[roles]]\nname = \"judge\"\n",
+        ));
+        assert!(message.contains("listed more than once"), "{message}");
+
+        let message = error_message(load(
+            "seed.toml",
+            "[[roles]]\nname = \"judge\"\npermissions = [\"rejudge\"]\n",
+        ));
+        assert!(message.contains("resource:action"), "{message}");
+
+        let message = error_message(load("seed.toml", "[[roles]]\nname = \"\"\n"));
+        assert!(message.contains("non-empty"), "{message}");
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        let result = SeedFile::load(Path::new("/nonexistent/seed.toml"));
+        assert!(error_message(result).contains("/nonexistent/seed.toml"));
+    }
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use plugin_core::http::PluginHttpResponse;
+
+/// Entry count above which an insert first sweeps expired entries.
+const SWEEP_THRESHOLD: usize = 1024;
+
+struct CachedStandings {
+    response: PluginHttpResponse,
+    stored_at: Instant,
+}
+
+/// In-memory TTL cache for plugin routes marked `standings = true`.
+///
+/// Entries are grouped by contest so that applying a judged result for one
+/// contest only drops that contest's standings. The key within a contest
+/// identifies the route, query and viewer, since plugins may tailor the
+/// scoreboard to who is asking. Contest, contest-problem and participant
+/// changes also drop the contest's entries.
+///
+/// The cache is per replica: invalidation only reaches the replica that
+/// handled the change, so other replicas may serve stale standings for up to
+/// one TTL.
+#[derive(Clone)]
+pub struct StandingsCache {
+    ttl: Duration,
+    entries: Arc<DashMap<(i32, String), CachedStandings>>,
+}
+
+impl StandingsCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Arc::new(DashMap::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    pub fn get(&self, contest_id: i32, key: &str) -> Option<PluginHttpResponse> {
+        let cache_key = (contest_id, key.to_string());
+        let entry = self.entries.get(&cache_key)?;
+        if entry.stored_at.elapsed() < self.ttl {
+            return Some(entry.response.clone());
+        }
+        drop(entry);
+        self.entries
+            .remove_if(&cache_key, |_, e| e.stored_at.elapsed() >= self.ttl);
+        None
+    }
+
+    pub fn insert(&self, contest_id: i32, key: String, response: PluginHttpResponse) {
+        if !self.is_enabled() {
+            return;
+        }
+        if self.entries.len() >= SWEEP_THRESHOLD {
+            self.entries.retain(|_, e| e.stored_at.elapsed() < self.ttl);
+        }
+        self.entries.insert(
+            (contest_id, key),
+            CachedStandings {
+                response,
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Drops every cached standings response for `contest_id`.
+    pub fn invalidate_contest(&self, contest_id: i32) {
+        self.entries.retain(|(id, _), _| *id != contest_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(marker: i64) -> PluginHttpResponse {
+        PluginHttpResponse {
+            status: 200,
+            headers: None,
+            body: Some(serde_json::json!({ "marker": marker })),
+        }
+    }
+
+    #[test]
+    fn returns_cached_response_within_ttl() {
+        let cache = StandingsCache::new(Duration::from_secs(60));
+        cache.insert(1, "k".into(), response(7));
+
+        let hit = cache.get(1, "k").expect("entry should be cached");
+        assert_eq!(hit.body, Some(serde_json::json!({ "marker": 7 })));
+        assert!(cache.get(1, "other").is_none());
+        assert!(cache.get(2, "k").is_none());
+    }
+
+    #[test]
+    fn expired_entries_are_not_returned() {
+        let cache = StandingsCache::new(Duration::from_millis(1));
+        cache.insert(1, "k".into(), response(1));
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert!(cache.get(1, "k").is_none());
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn invalidation_is_scoped_to_one_contest() {
+        let cache = StandingsCache::new(Duration::from_secs(60));
+        cache.insert(1, "a".into(), response(1));
+        cache.insert(1, "b".into(), response(2));
+        cache.insert(2, "a".into(), response(3));
+
+        cache.invalidate_contest(1);
+
+        assert!(cache.get(1, "a").is_none());
+        assert!(cache.get(1, "b").is_none());
+        assert!(cache.get(2, "a").is_some());
+    }
+
+    #[test]
+    fn zero_ttl_disables_caching() {
+        let cache = StandingsCache::new(Duration::ZERO);
+        cache.insert(1, "k".into(), response(1));
+
+        assert!(!cache.is_enabled());
+        assert!(cache.get(1, "k").is_none());
+    }
+}
@@ -13,6 +13,7 @@ use crate::registry::{
     CheckerFormatRegistry, ContestTypeRegistry, EvaluateBatches, EvaluatorRegistry,
     LanguageResolverRegistry, OperationBatches, OperationWaiters,
 };
+use crate::standings_cache::StandingsCache;
 
 pub struct PendingDeviceAuth {
     pub user_code: String,
@@ -46,6 +47,7 @@ pub struct AppState {
     pub blob_store: Arc<dyn BlobStore>,
     pub registries: RegistryState,
     pub device_codes: DeviceCodeStore,
+    pub standings_cache: StandingsCache,
     pub metrics: common::metrics::Metrics,
     pub prometheus_registry: prometheus::Registry,
 }
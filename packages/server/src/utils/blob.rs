@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 use crate::entity::{additional_file, problem_attachment};
 use crate::error::AppError;
+use crate::utils::range::{ByteRange, partial_content, range_not_satisfiable};
 
 pub struct BlobMetadata {
     pub content_hash: String,
@@ -51,8 +52,28 @@ pub async fn build_blob_response(
         return Ok(StatusCode::NOT_MODIFIED.into_response());
     }
 
+    let size = metadata.size.max(0) as u64;
+    let range = ByteRange::from_headers(headers, size);
+    if range == ByteRange::Unsatisfiable {
+        return range_not_satisfiable(size);
+    }
+
     let hash = ContentHash::from_hex(&metadata.content_hash)?;
-    let reader = blob_store.get_stream(&hash).await?;
+    let (reader, builder) = match range {
+        ByteRange::Partial { start, end } => (
+            blob_store
+                .get_range_stream(&hash, start, end - start + 1)
+                .await?,
+            partial_content(start, end, size),
+        ),
+        _ => (
+            blob_store.get_stream(&hash).await?,
+            Response::builder()
+                .status(StatusCode::OK)
+                .header(header::ACCEPT_RANGES, "bytes")
+                .header(header::CONTENT_LENGTH, metadata.size.to_string()),
+        ),
+    };
     let stream = ReaderStream::new(reader);
     let body = Body::from_stream(stream);
 
@@ -61,10 +82,8 @@ pub async fn build_blob_response(
         .as_deref()
         .unwrap_or("application/octet-stream");
 
-    let response = Response::builder()
-        .status(StatusCode::OK)
+    let response = builder
         .header(header::CONTENT_TYPE, content_type)
-        .header(header::CONTENT_LENGTH, metadata.size.to_string())
         .header(
             header::CONTENT_DISPOSITION,
             content_disposition_value(&metadata.filename),
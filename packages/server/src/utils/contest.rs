@@ -1,8 +1,14 @@
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+use std::collections::HashMap;
+
+use broccoli_server_sdk::standings::{StandingsTable, TABLE_FORMAT};
+use plugin_core::http::{PluginHttpAuth, PluginHttpRequest, PluginHttpResponse};
+use plugin_core::traits::PluginManagerExt;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QuerySelect};
 
 use crate::entity::{contest, contest_problem, contest_user, problem};
 use crate::error::AppError;
 use crate::extractors::auth::AuthUser;
+use crate::state::AppState;
 use crate::utils::soft_delete::SoftDeletable;
 
 pub async fn is_problem_in_contest<C: sea_orm::ConnectionTrait>(
@@ -65,7 +71,9 @@ pub async fn find_contest_problem<C: sea_orm::ConnectionTrait>(
         .ok_or_else(|| AppError::NotFound("Contest problem not found".into()))
 }
 
-pub fn require_contest_started(
+/// Lets non-managers see the contest's problems only while the contest is
+/// active and, with `hide_problems_until_start`, once it has started.
+pub fn require_contest_problems_visible(
     auth_user: &AuthUser,
     contest: &contest::Model,
 ) -> Result<(), AppError> {
@@ -78,7 +86,7 @@ pub fn require_contest_started(
     {
         return Err(AppError::NotFound("Contest not found".into()));
     }
-    if now < contest.start_time {
+    if contest.hide_problems_until_start && now < contest.start_time {
         return Err(AppError::Validation("Contest has not started yet".into()));
     }
     Ok(())
@@ -106,6 +114,15 @@ pub fn require_contest_running(
     Ok(())
 }
 
+/// Whether an activated contest has ended with `practice_after_end` set, so
+/// any authenticated user may submit to its problems as practice.
+pub fn is_open_for_practice(contest: &contest::Model, now: chrono::DateTime<chrono::Utc>) -> bool {
+    contest.practice_after_end
+        && now >= contest.end_time
+        && contest.activate_time.is_some_and(|at| at <= now)
+        && contest.deactivate_time.is_none_or(|dt| dt > now)
+}
+
 pub async fn is_contest_participant<C: sea_orm::ConnectionTrait>(
     db: &C,
     contest_id: i32,
@@ -160,11 +177,21 @@ pub async fn can_access_problem_via_contest<C: sea_orm::ConnectionTrait>(
     }
 
     let now = chrono::Utc::now();
+    let problems_visible = Condition::any()
+        .add(contest::Column::StartTime.lte(now))
+        .add(contest::Column::HideProblemsUntilStart.eq(false));
 
+    let open_to_all = Condition::any()
+        .add(contest::Column::IsPublic.eq(true))
+        .add(
+            Condition::all()
+                .add(contest::Column::PracticeAfterEnd.eq(true))
+                .add(contest::Column::EndTime.lte(now)),
+        );
     let has_public = contest::Entity::find_active()
         .filter(contest::Column::Id.is_in(contest_ids.clone()))
-        .filter(contest::Column::IsPublic.eq(true))
-        .filter(contest::Column::StartTime.lte(now))
+        .filter(open_to_all)
+        .filter(problems_visible.clone())
         .one(db)
         .await?
         .is_some();
@@ -172,18 +199,18 @@ pub async fn can_access_problem_via_contest<C: sea_orm::ConnectionTrait>(
         return Ok(());
     }
 
-    let started_contest_ids: Vec<i32> = contest::Entity::find_active()
+    let visible_contest_ids: Vec<i32> = contest::Entity::find_active()
         .filter(contest::Column::Id.is_in(contest_ids))
-        .filter(contest::Column::StartTime.lte(now))
+        .filter(problems_visible)
         .select_only()
         .column(contest::Column::Id)
         .into_tuple()
         .all(db)
         .await?;
 
-    if !started_contest_ids.is_empty() {
+    if !visible_contest_ids.is_empty() {
         let is_participant = contest_user::Entity::find()
-            .filter(contest_user::Column::ContestId.is_in(started_contest_ids))
+            .filter(contest_user::Column::ContestId.is_in(visible_contest_ids))
             .filter(contest_user::Column::UserId.eq(auth_user.user_id))
             .one(db)
             .await?
@@ -208,5 +235,171 @@ pub async fn require_problem_read_access<C: sea_orm::ConnectionTrait>(
             .ok_or_else(|| AppError::NotFound("Problem not found".into()))?;
         return Ok(());
     }
+    let is_public = problem::Entity::find_active_by_id(problem_id)
+        .filter(problem::Column::IsPublic.eq(true))
+        .one(db)
+        .await?
+        .is_some();
+    if is_public {
+        return Ok(());
+    }
     can_access_problem_via_contest(db, auth_user, problem_id).await
 }
+
+/// The board served by the standings route of `contest`'s contest-type
+/// plugin, requested as `viewer` in [`StandingsTable`] form, so it carries
+/// the plugin's own phase, visibility and scoring rules. `None` when no
+/// plugin serves standings for the contest type.
+pub async fn load_plugin_standings(
+    state: &AppState,
+    contest: &contest::Model,
+    viewer: &AuthUser,
+) -> Result<Option<StandingsTable>, AppError> {
+    let Some(contest_type) = contest.contest_type.as_deref() else {
+        return Ok(None);
+    };
+    let plugin_id = {
+        let registry = state.registries.contest_type_registry.read().await;
+        match registry.get(contest_type) {
+            Some(handlers) => handlers.plugin_id.clone(),
+            None => return Ok(None),
+        }
+    };
+    let route = {
+        let registry = state
+            .plugins
+            .get_registry()
+            .read()
+            .map_err(|_| AppError::Internal("Failed to acquire plugin registry lock".into()))?;
+        registry
+            .get(&plugin_id)
+            .and_then(|entry| entry.manifest.server.as_ref())
+            .and_then(|server| {
+                server.routes.iter().find(|route| {
+                    route.standings
+                        && route.method.eq_ignore_ascii_case("GET")
+                        && route.path.contains("{contest_id}")
+                })
+            })
+            .map(|route| (route.path.clone(), route.handler.clone()))
+    };
+    let Some((path, handler)) = route else {
+        return Ok(None);
+    };
+
+    let contest_id = contest.id.to_string();
+    let request = PluginHttpRequest {
+        method: "GET".into(),
+        path: path.replace("{contest_id}", &contest_id),
+        params: HashMap::from([("contest_id".to_string(), contest_id)]),
+        query: HashMap::from([("format".to_string(), TABLE_FORMAT.to_string())]),
+        headers: HashMap::new(),
+        body: None,
+        auth: Some(PluginHttpAuth {
+            user_id: viewer.user_id,
+            username: viewer.username.clone(),
+            roles: viewer.roles.clone(),
+            permissions: viewer.permissions.clone(),
+        }),
+    };
+    let response: PluginHttpResponse = state.plugins.call(&plugin_id, &handler, request).await?;
+    if !(200..300).contains(&response.status) {
+        return Err(AppError::Internal(format!(
+            "Plugin '{}' handler '{}' answered standings with status {}",
+            plugin_id, handler, response.status
+        )));
+    }
+    let table = serde_json::from_value(response.body.unwrap_or_default()).map_err(|e| {
+        AppError::Internal(format!(
+            "Plugin '{}' handler '{}' returned malformed standings: {}",
+            plugin_id, handler, e
+        ))
+    })?;
+    Ok(Some(table))
+}
+
+/// [`load_plugin_standings`], failing when the contest type serves no
+/// standings.
+pub async fn require_plugin_standings(
+    state: &AppState,
+    contest: &contest::Model,
+    viewer: &AuthUser,
+) -> Result<StandingsTable, AppError> {
+    load_plugin_standings(state, contest, viewer)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Contest type has no standings".into()))
+}
+
+/// Renders `table` as RFC 4180 CSV: a
+/// `rank,username,<labels...>,total,penalty` header followed by one row per
+/// participant. Cells without a score are left empty.
+pub fn standings_to_csv(table: &StandingsTable) -> String {
+    let mut out = String::new();
+    let header = ["rank", "username"]
+        .into_iter()
+        .map(str::to_string)
+        .chain(table.labels.iter().cloned())
+        .chain(["total".to_string(), "penalty".to_string()]);
+    push_record(&mut out, header);
+
+    for row in &table.rows {
+        let fields = [row.rank.to_string(), row.username.clone()]
+            .into_iter()
+            .chain(
+                row.cells
+                    .iter()
+                    .map(|cell| cell.score.map(|v| v.to_string()).unwrap_or_default()),
+            )
+            .chain([row.total.to_string(), row.penalty.to_string()]);
+        push_record(&mut out, fields);
+    }
+    out
+}
+
+fn push_record(out: &mut String, fields: impl Iterator<Item = String>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(&field);
+        }
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use broccoli_server_sdk::standings::{StandingsTableCell, StandingsTableRow};
+
+    use super::*;
+
+    #[test]
+    fn csv_has_header_and_escapes_fields() {
+        let table = StandingsTable {
+            labels: vec!["A".into(), "B,2".into()],
+            rows: vec![StandingsTableRow {
+                rank: 1,
+                username: "o\"neil".into(),
+                cells: vec![
+                    StandingsTableCell {
+                        score: Some(37.5),
+                        ..Default::default()
+                    },
+                    StandingsTableCell::default(),
+                ],
+                total: 37.5,
+                ..Default::default()
+            }],
+        };
+
+        assert_eq!(
+            standings_to_csv(&table),
+            "rank,username,A,\"B,2\",total,penalty\r\n1,\"o\"\"neil\",37.5,,37.5,0\r\n"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{ConnectionTrait, DbBackend, DbErr, FromQueryResult, Statement};
+
+/// Pseudo-observations blended into each ratio so that a handful of
+/// submissions cannot push the estimate to either extreme.
+const PRIOR_WEIGHT: f64 = 5.0;
+/// Ratio assumed before any submissions are seen.
+const PRIOR_RATE: f64 = 0.5;
+/// Share of the estimate driven by the per-submission acceptance rate; the
+/// rest comes from the share of attempting users who solved the problem.
+const ACCEPTANCE_WEIGHT: f64 = 0.6;
+
+pub const MIN_DIFFICULTY: i32 = 0;
+pub const MAX_DIFFICULTY: i32 = 100;
+
+/// Judged submission counts for one problem over an estimation window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubmissionStats {
+    pub total: u64,
+    pub accepted: u64,
+    /// Distinct users with at least one counted submission.
+    pub attempters: u64,
+    /// Distinct users with at least one accepted submission.
+    pub solvers: u64,
+}
+
+#[derive(FromQueryResult)]
+struct SubmissionStatsRow {
+    total: i64,
+    accepted: i64,
+    attempters: i64,
+    solvers: i64,
+}
+
+fn smoothed_rate(hits: u64, trials: u64) -> f64 {
+    (hits as f64 + PRIOR_WEIGHT * PRIOR_RATE) / (trials as f64 + PRIOR_WEIGHT)
+}
+
+/// Maps submission stats to a difficulty score in
+/// `MIN_DIFFICULTY..=MAX_DIFFICULTY`. Lower acceptance and fewer solvers
+/// per attempter both give a higher score.
+pub fn estimate_difficulty(stats: &SubmissionStats) -> i32 {
+    let acceptance = smoothed_rate(stats.accepted, stats.total);
+    let solve_rate = smoothed_rate(stats.solvers, stats.attempters);
+    let ease = ACCEPTANCE_WEIGHT * acceptance + (1.0 - ACCEPTANCE_WEIGHT) * solve_rate;
+    let score = ((1.0 - ease) * f64::from(MAX_DIFFICULTY)).round() as i32;
+    score.clamp(MIN_DIFFICULTY, MAX_DIFFICULTY)
+}
+
+/// Collects stats over submissions to `problem_id` created at or after
+/// `since`. Only `Judged` and `CompilationError` submissions count; system
+/// errors say nothing about the problem.
+pub async fn load_submission_stats<C: ConnectionTrait>(
+    db: &C,
+    problem_id: i32,
+    since: DateTime<Utc>,
+) -> Result<SubmissionStats, DbErr> {
+    let row = SubmissionStatsRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"SELECT COUNT(*) AS total,
+                  COUNT(*) FILTER (WHERE verdict = 'Accepted') AS accepted,
+                  COUNT(DISTINCT user_id) AS attempters,
+                  COUNT(DISTINCT user_id) FILTER (WHERE verdict = 'Accepted') AS solvers
+           FROM submission
+           WHERE problem_id = $1
+             AND created_at >= $2
+             AND status IN ('Judged', 'CompilationError')"#,
+        vec![problem_id.into(), since.into()],
+    ))
+    .one(db)
+    .await?;
+
+    Ok(row
+        .map(|r| SubmissionStats {
+            total: r.total.max(0) as u64,
+            accepted: r.accepted.max(0) as u64,
+            attempters: r.attempters.max(0) as u64,
+            solvers: r.solvers.max(0) as u64,
+        })
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(total: u64, accepted: u64, attempters: u64, solvers: u64) -> SubmissionStats {
+        SubmissionStats {
+            total,
+            accepted,
+            attempters,
+            solvers,
+        }
+    }
+
+    #[test]
+    fn lower_acceptance_never_lowers_difficulty() {
+        let scores: Vec<i32> = (0..=200)
+            .map(|accepted| estimate_difficulty(&stats(200, accepted, 40, 20)))
+            .collect();
+        assert!(
+            scores.windows(2).all(|w| w[0] >= w[1]),
+            "difficulty must not rise as acceptance rises: {scores:?}"
+        );
+        assert!(scores[0] > scores[200]);
+    }
+
+    #[test]
+    fn fewer_solvers_never_lowers_difficulty() {
+        let scores: Vec<i32> = (0..=40)
+            .map(|solvers| estimate_difficulty(&stats(200, 60, 40, solvers)))
+            .collect();
+        assert!(
+            scores.windows(2).all(|w| w[0] >= w[1]),
+            "difficulty must not rise as solvers rise: {scores:?}"
+        );
+        assert!(scores[0] > scores[40]);
+    }
+
+    #[test]
+    fn extremes_stay_in_range_and_ordered() {
+        let hardest = estimate_difficulty(&stats(10_000, 0, 1_000, 0));
+        let easiest = estimate_difficulty(&stats(10_000, 10_000, 1_000, 1_000));
+        assert!((MIN_DIFFICULTY..=MAX_DIFFICULTY).contains(&hardest));
+        assert!((MIN_DIFFICULTY..=MAX_DIFFICULTY).contains(&easiest));
+        assert!(hardest >= 95, "hardest was {hardest}");
+        assert!(easiest <= 5, "easiest was {easiest}");
+    }
+
+    #[test]
+    fn sparse_data_stays_near_the_middle() {
+        assert_eq!(estimate_difficulty(&SubmissionStats::default()), 50);
+
+        let one_failure = estimate_difficulty(&stats(1, 0, 1, 0));
+        let many_failures = estimate_difficulty(&stats(100, 0, 50, 0));
+        assert!(one_failure > 50);
+        assert!(one_failure < many_failures);
+    }
+}
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::*;
+
+use crate::entity::submission;
+use crate::error::AppError;
+
+/// Rejects a submission whose language and files exactly match one the same
+/// user made to the same problem (and contest, if any) in the last
+/// `window_secs` seconds. `files` must be in the stored form produced by
+/// `files_to_json`. A window of 0 disables the check.
+pub async fn check_duplicate_submission<C: ConnectionTrait>(
+    db: &C,
+    user_id: i32,
+    problem_id: i32,
+    contest_id: Option<i32>,
+    language: &str,
+    files: &serde_json::Value,
+    window_secs: u64,
+) -> Result<(), AppError> {
+    if window_secs == 0 {
+        return Ok(());
+    }
+
+    let window = Duration::try_seconds(i64::try_from(window_secs).unwrap_or(i64::MAX))
+        .unwrap_or(Duration::MAX);
+    let since = Utc::now()
+        .checked_sub_signed(window)
+        .unwrap_or(DateTime::<Utc>::MIN_UTC);
+    let contest_filter = match contest_id {
+        Some(id) => submission::Column::ContestId.eq(id),
+        None => submission::Column::ContestId.is_null(),
+    };
+
+    let recent = submission::Entity::find()
+        .select_only()
+        .column(submission::Column::Id)
+        .column(submission::Column::Files)
+        .filter(submission::Column::UserId.eq(user_id))
+        .filter(submission::Column::ProblemId.eq(problem_id))
+        .filter(contest_filter)
+        .filter(submission::Column::Language.eq(language))
+        .filter(submission::Column::CompileOnly.eq(false))
+        .filter(submission::Column::CreatedAt.gt(since))
+        .order_by_desc(submission::Column::CreatedAt)
+        .into_tuple::<(i32, serde_json::Value)>()
+        .all(db)
+        .await?;
+
+    match recent.into_iter().find(|(_, prior)| prior == files) {
+        Some((submission_id, _)) => Err(AppError::DuplicateSubmission { submission_id }),
+        None => Ok(()),
+    }
+}
@@ -138,10 +138,51 @@ pub fn is_sample_directory(dir: &str) -> bool {
     lower == "sample" || lower.ends_with("/sample")
 }
 
+/// Whether `name` matches `pattern`, where `*` stands for any run of
+/// characters and `?` for exactly one. Matching is case-sensitive.
+pub fn matches_glob(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn glob_matches_literals_and_wildcards() {
+        assert!(matches_glob("Main.java", "Main.java"));
+        assert!(!matches_glob("Main.java", "main.java"));
+        assert!(matches_glob("*.py", "solution.py"));
+        assert!(!matches_glob("*.py", "solution.pyc"));
+        assert!(matches_glob("sol?.cpp", "sol1.cpp"));
+        assert!(matches_glob("a*b*c", "axxbyyc"));
+        assert!(!matches_glob("a*b*c", "axxbyy"));
+    }
+
     #[test]
     fn flat_filename_accepts_typical_source_filenames() {
         assert!(validate_flat_filename("solution.cpp").is_ok());
@@ -1,52 +1,226 @@
-use std::collections::{HashMap, HashSet};
-
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use common::Verdict;
+use common::submission_dispatch::JudgeMode;
+use sea_orm::prelude::Expr;
+use sea_orm::*;
+use tracing::warn;
+
+use crate::config::SubmissionConfig;
+use crate::entity::{contest, test_case_result};
 use crate::error::AppError;
-use crate::models::submission::{SubmissionFile, SubmissionFileDto};
-use crate::utils::filename::validate_flat_filename;
-use crate::utils::text::sanitize_db_text;
+use crate::models::submission::{SubmissionFile, SubmissionFileDto, SubtaskResultResponse};
+use crate::registry::LanguageResolverEntry;
+use crate::utils::filename::{matches_glob, validate_flat_filename};
+use crate::utils::text::{sanitize_db_text, truncate_text};
 
 pub fn validate_code_payload(
     files: &[SubmissionFileDto],
     language: &str,
-    max_size: usize,
+    limits: &SubmissionConfig,
 ) -> Result<(), AppError> {
     if files.is_empty() {
-        return Err(AppError::Validation("At least one file is required".into()));
+        return Err(AppError::FieldValidation {
+            field: "files".into(),
+            message: "At least one file is required".into(),
+        });
     }
 
     if language.trim().is_empty() {
         return Err(AppError::Validation("Language is required".into()));
     }
 
+    validate_submission_files(files, limits)?;
+    validate_required_filename(files, language, &limits.required_filenames)
+}
+
+/// Rejects a submission in a language with a configured required filename
+/// when none of its files matches it.
+fn validate_required_filename(
+    files: &[SubmissionFileDto],
+    language: &str,
+    required_filenames: &HashMap<String, String>,
+) -> Result<(), AppError> {
+    let Some(pattern) = required_filenames.get(language.trim()) else {
+        return Ok(());
+    };
+    if files
+        .iter()
+        .any(|file| matches_glob(pattern, file.filename.trim()))
+    {
+        return Ok(());
+    }
+    Err(AppError::FieldValidation {
+        field: "files".into(),
+        message: format!(
+            "Submissions in '{}' must include a file named '{}'",
+            language.trim(),
+            pattern
+        ),
+    })
+}
+
+/// Language a submission is judged in. An omitted language or `auto` is
+/// inferred from the file extensions using the registered languages; files
+/// with an extension no language claims (headers, data files) are ignored.
+/// Fails when no file identifies a language or the files point at more than
+/// one.
+pub fn resolve_submission_language(
+    language: Option<&str>,
+    files: &[SubmissionFileDto],
+    languages: &HashMap<String, LanguageResolverEntry>,
+) -> Result<String, AppError> {
+    match language.map(str::trim) {
+        Some(language) if !language.eq_ignore_ascii_case("auto") => {
+            return Ok(language.to_string());
+        }
+        _ => {}
+    }
+
+    let mut candidates: Option<BTreeSet<&str>> = None;
+    for file in files {
+        let Some(extension) = Path::new(file.filename.trim())
+            .extension()
+            .and_then(|e| e.to_str())
+        else {
+            continue;
+        };
+        let extension = extension.to_ascii_lowercase();
+        let matching: BTreeSet<&str> = languages
+            .iter()
+            .filter(|(_, entry)| entry.extensions.contains(&extension))
+            .map(|(id, _)| id.as_str())
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
+        candidates = Some(match candidates {
+            Some(previous) => previous.intersection(&matching).copied().collect(),
+            None => matching,
+        });
+    }
+
+    let Some(candidates) = candidates else {
+        return Err(AppError::Validation(
+            "Could not detect the language from the file extensions; specify `language`".into(),
+        ));
+    };
+    let mut candidates = candidates.into_iter();
+    match (candidates.next(), candidates.next()) {
+        (Some(language), None) => Ok(language.to_string()),
+        (None, _) => Err(AppError::Validation(
+            "Files have extensions of different languages; specify `language`".into(),
+        )),
+        (Some(first), Some(second)) => {
+            let all: Vec<&str> = [first, second].into_iter().chain(candidates).collect();
+            Err(AppError::Validation(format!(
+                "Language is ambiguous between {}; specify `language`",
+                all.join(", ")
+            )))
+        }
+    }
+}
+
+/// Rejects text files that contain binary data. Request bodies are JSON, so
+/// contents always decode; raw binary data shows up as NUL characters, which
+/// would only surface later as a confusing compile error. U+FFFD is left
+/// alone since valid source may contain it. Files with one of
+/// `binary_extensions` are skipped.
+pub fn validate_file_encoding(
+    files: &[SubmissionFileDto],
+    binary_extensions: &[String],
+) -> Result<(), AppError> {
+    for (index, file) in files.iter().enumerate() {
+        let is_binary = Path::new(file.filename.trim())
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| binary_extensions.iter().any(|b| b.eq_ignore_ascii_case(e)));
+        if is_binary {
+            continue;
+        }
+        if file.content.contains('\0') {
+            return Err(AppError::FieldValidation {
+                field: format!("files[{index}].content"),
+                message: format!("File '{}' contains binary (NUL) data", file.filename),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks filenames, file count, per-file size and total size against the
+/// configured submission limits. Shared by submissions and code runs.
+pub fn validate_submission_files(
+    files: &[SubmissionFileDto],
+    limits: &SubmissionConfig,
+) -> Result<(), AppError> {
+    if files.is_empty() {
+        return Err(AppError::FieldValidation {
+            field: "files".into(),
+            message: "At least one file is required".into(),
+        });
+    }
+
+    if files.len() > limits.max_files {
+        return Err(AppError::FieldValidation {
+            field: "files".into(),
+            message: format!(
+                "Too many files ({}); at most {} allowed",
+                files.len(),
+                limits.max_files
+            ),
+        });
+    }
+
     let mut total_size = 0usize;
     let mut seen_filenames = HashSet::with_capacity(files.len());
 
-    for file in files {
-        let filename = validate_flat_filename(&file.filename)
-            .map_err(|e| AppError::Validation(e.message().into()))?;
+    for (index, file) in files.iter().enumerate() {
+        let filename =
+            validate_flat_filename(&file.filename).map_err(|e| AppError::FieldValidation {
+                field: format!("files[{index}].filename"),
+                message: e.message().into(),
+            })?;
 
         if !seen_filenames.insert(filename) {
-            return Err(AppError::Validation(format!(
-                "Duplicate filename: '{}'",
-                filename
-            )));
+            return Err(AppError::FieldValidation {
+                field: format!("files[{index}].filename"),
+                message: format!("Duplicate filename: '{}'", filename),
+            });
         }
 
         if file.content.is_empty() {
-            return Err(AppError::Validation(format!(
-                "File '{}' cannot be empty",
-                filename
-            )));
+            return Err(AppError::FieldValidation {
+                field: format!("files[{index}].content"),
+                message: format!("File '{}' cannot be empty", filename),
+            });
+        }
+
+        if file.content.len() > limits.max_file_size {
+            return Err(AppError::FieldValidation {
+                field: format!("files[{index}].content"),
+                message: format!(
+                    "File '{}' ({} bytes) exceeds maximum file size ({} bytes)",
+                    filename,
+                    file.content.len(),
+                    limits.max_file_size
+                ),
+            });
         }
 
         total_size = total_size.saturating_add(file.content.len());
     }
 
-    if total_size > max_size {
-        return Err(AppError::Validation(format!(
-            "Total code size ({} bytes) exceeds maximum ({} bytes)",
-            total_size, max_size
-        )));
+    if total_size > limits.max_size {
+        return Err(AppError::FieldValidation {
+            field: "files".into(),
+            message: format!(
+                "Total code size ({} bytes) exceeds maximum ({} bytes)",
+                total_size, limits.max_size
+            ),
+        });
     }
 
     Ok(())
@@ -118,6 +292,48 @@ pub fn files_from_json(value: &serde_json::Value) -> Vec<SubmissionFileDto> {
         .collect()
 }
 
+/// Total size in bytes and line count across a stored `files` column.
+/// A trailing newline does not start another line.
+pub fn source_metrics(value: &serde_json::Value) -> (u64, u64) {
+    let files = serde_json::from_value::<Vec<SubmissionFile>>(value.clone()).unwrap_or_default();
+    files.iter().fold((0, 0), |(bytes, lines), f| {
+        (
+            bytes + f.content.len() as u64,
+            lines + f.content.lines().count() as u64,
+        )
+    })
+}
+
+/// `score` as a percentage of `max_score`, rounded to two decimals. `None`
+/// while unscored or when nothing can be earned.
+pub fn score_percentage(score: Option<f64>, max_score: f64) -> Option<f64> {
+    let score = score?;
+    if !max_score.is_finite() || max_score <= 0.0 {
+        return None;
+    }
+    Some((score / max_score * 10_000.0).round() / 100.0)
+}
+
+/// Decodes a stored `subtask_results` column, dropping it if malformed.
+pub fn subtasks_from_json(value: Option<&serde_json::Value>) -> Vec<SubtaskResultResponse> {
+    let Some(value) = value else {
+        return Vec::new();
+    };
+    serde_json::from_value(value.clone()).unwrap_or_else(|e| {
+        warn!(error = %e, "Ignoring malformed subtask_results");
+        Vec::new()
+    })
+}
+
+/// The judge mode stored as a submission's or code run's `compile_only` flag.
+pub fn judge_mode_of(compile_only: bool) -> JudgeMode {
+    if compile_only {
+        JudgeMode::CompileOnly
+    } else {
+        JudgeMode::Full
+    }
+}
+
 pub fn validate_run_language(
     language: &str,
     known_languages: &HashSet<String>,
@@ -132,6 +348,139 @@ pub fn validate_run_language(
     Ok(())
 }
 
+/// Rejects languages outside a contest's `allowed_languages`. `None` defers
+/// to the global language registry.
+pub fn validate_contest_language(
+    language: &str,
+    allowed_languages: Option<&[String]>,
+) -> Result<(), AppError> {
+    let Some(allowed) = allowed_languages else {
+        return Ok(());
+    };
+    let language = language.trim();
+    if !allowed.iter().any(|l| l == language) {
+        return Err(AppError::Validation(format!(
+            "Language '{}' is not allowed in this contest; allowed languages: {}",
+            language,
+            allowed.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Rejects a language registered only for other problem types, such as the
+/// `output` language of output-only problems.
+pub fn validate_language_problem_type(
+    language: &str,
+    problem_type: &str,
+    languages: &HashMap<String, LanguageResolverEntry>,
+) -> Result<(), AppError> {
+    let language = language.trim();
+    let Some(entry) = languages.get(language) else {
+        return Ok(());
+    };
+    if entry.problem_types.is_empty() || entry.problem_types.iter().any(|t| t == problem_type) {
+        return Ok(());
+    }
+    Err(AppError::Validation(format!(
+        "Language '{}' is only available for problems of type: {}",
+        language,
+        entry.problem_types.join(", ")
+    )))
+}
+
+/// Submission-level result derived from per-test-case rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregatedResult {
+    pub verdict: Verdict,
+    pub score: f64,
+    pub time_used: Option<i32>,
+    pub memory_used: Option<i32>,
+}
+
+/// Folds test-case rows into a submission result the same way the evaluator
+/// plugins persist one: the most severe non-skipped verdict, the maximum
+/// time and memory, and the sum of per-case scores.
+pub fn aggregate_test_case_results(rows: &[test_case_result::Model]) -> AggregatedResult {
+    let non_skipped: Vec<_> = rows.iter().filter(|r| !r.verdict.is_skipped()).collect();
+
+    let verdict = non_skipped
+        .iter()
+        .map(|r| r.verdict.clone())
+        .max_by_key(|v| v.severity())
+        .unwrap_or(Verdict::Accepted);
+
+    AggregatedResult {
+        verdict,
+        score: rows.iter().map(|r| r.score).sum(),
+        time_used: non_skipped.iter().filter_map(|r| r.time_used).max(),
+        memory_used: non_skipped.iter().filter_map(|r| r.memory_used).max(),
+    }
+}
+
+/// Cuts the stored `checker_output` of one judgement's result rows to
+/// `max_bytes` (see [`truncate_text`]). Plugins write the rows themselves,
+/// so this runs once the judging call returns. `max_bytes == 0` leaves
+/// them untouched.
+pub async fn cap_stored_checker_output<C: ConnectionTrait>(
+    db: &C,
+    submission_id: i32,
+    judgement_id: i32,
+    max_bytes: usize,
+) -> Result<(), DbErr> {
+    if max_bytes == 0 {
+        return Ok(());
+    }
+
+    let judgement_filter = if judgement_id > 0 {
+        test_case_result::Column::JudgementId.eq(judgement_id)
+    } else {
+        test_case_result::Column::JudgementId.is_null()
+    };
+    let oversized = test_case_result::Entity::find()
+        .filter(test_case_result::Column::SubmissionId.eq(submission_id))
+        .filter(judgement_filter)
+        .filter(Expr::cust_with_values(
+            "octet_length(checker_output) > $1",
+            [i64::try_from(max_bytes).unwrap_or(i64::MAX)],
+        ))
+        .all(db)
+        .await?;
+
+    for row in oversized {
+        let Some(output) = row.checker_output else {
+            continue;
+        };
+        test_case_result::ActiveModel {
+            id: Set(row.id),
+            checker_output: Set(Some(truncate_text(output, max_bytes))),
+            ..Default::default()
+        }
+        .update(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Whether a submission to `contest` dispatched at `now` is judged against
+/// pretests only. Two-phase contests judge pretests until they end.
+pub fn judges_pretests_only(contest: &contest::Model, now: DateTime<Utc>) -> bool {
+    contest.two_phase && now < contest.end_time
+}
+
+/// Keeps only the pretests when `pretests_only` is set. A problem with no
+/// pretests keeps its full set rather than being judged against nothing.
+pub fn select_judged_test_cases<T>(
+    cases: Vec<T>,
+    pretests_only: bool,
+    is_pretest: impl Fn(&T) -> bool,
+) -> Vec<T> {
+    if !pretests_only || !cases.iter().any(&is_pretest) {
+        return cases;
+    }
+    cases.into_iter().filter(|c| is_pretest(c)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,19 +489,130 @@ mod tests {
         HashSet::from(["cpp".to_string(), "c".to_string(), "python3".to_string()])
     }
 
+    fn limits(max_size: usize) -> SubmissionConfig {
+        SubmissionConfig {
+            max_size,
+            ..SubmissionConfig::default()
+        }
+    }
+
+    fn file(filename: &str, content: &str) -> SubmissionFileDto {
+        SubmissionFileDto {
+            filename: filename.into(),
+            content: content.into(),
+        }
+    }
+
+    fn languages() -> HashMap<String, LanguageResolverEntry> {
+        [
+            ("c", vec!["c", "h"]),
+            ("cpp", vec!["cpp", "h"]),
+            ("python3", vec!["py"]),
+        ]
+        .into_iter()
+        .map(|(id, extensions)| {
+            (
+                id.to_string(),
+                LanguageResolverEntry {
+                    plugin_id: "test".into(),
+                    function_name: "resolve".into(),
+                    display_name: id.into(),
+                    default_filename: String::new(),
+                    extensions: extensions.into_iter().map(String::from).collect(),
+                    binary_extensions: Vec::new(),
+                    template: String::new(),
+                    problem_types: Vec::new(),
+                },
+            )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn resolve_submission_language_keeps_explicit_language() {
+        let files = vec![file("main.py", "print(1)")];
+        let language = resolve_submission_language(Some(" cpp "), &files, &languages()).unwrap();
+        assert_eq!(language, "cpp");
+    }
+
+    #[test]
+    fn resolve_submission_language_detects_python_from_extension() {
+        let files = vec![file("main.py", "print(1)")];
+        assert_eq!(
+            resolve_submission_language(None, &files, &languages()).unwrap(),
+            "python3"
+        );
+        assert_eq!(
+            resolve_submission_language(Some("auto"), &files, &languages()).unwrap(),
+            "python3"
+        );
+    }
+
+    #[test]
+    fn resolve_submission_language_narrows_shared_extensions() {
+        let files = vec![file("main.cpp", "int main() {}"), file("util.h", "")];
+        assert_eq!(
+            resolve_submission_language(None, &files, &languages()).unwrap(),
+            "cpp"
+        );
+    }
+
+    #[test]
+    fn resolve_submission_language_rejects_mixed_or_unknown_files() {
+        let mixed = vec![file("main.py", "print(1)"), file("main.cpp", "")];
+        let err = resolve_submission_language(None, &mixed, &languages()).unwrap_err();
+        assert!(matches!(err, AppError::Validation(msg) if msg.contains("different languages")));
+
+        let unknown = vec![file("notes.txt", "hi")];
+        let err = resolve_submission_language(Some("auto"), &unknown, &languages()).unwrap_err();
+        assert!(matches!(err, AppError::Validation(msg) if msg.contains("Could not detect")));
+
+        let ambiguous = vec![file("util.h", "")];
+        let err = resolve_submission_language(None, &ambiguous, &languages()).unwrap_err();
+        assert!(
+            matches!(err, AppError::Validation(msg) if msg.contains("ambiguous between c, cpp"))
+        );
+    }
+
+    #[test]
+    fn validate_file_encoding_rejects_binary_text() {
+        let files = vec![file("main.cpp", "int main() {}"), file("data.txt", "ab\0c")];
+        let err = validate_file_encoding(&files, &[]).unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::FieldValidation { field, message }
+                if field == "files[1].content" && message.contains("binary (NUL) data")
+        ));
+    }
+
+    #[test]
+    fn validate_file_encoding_accepts_replacement_character() {
+        let files = vec![file("main.cpp", "// \u{FFFD}\nint main() {}")];
+        assert!(validate_file_encoding(&files, &[]).is_ok());
+    }
+
+    #[test]
+    fn validate_file_encoding_skips_binary_extensions() {
+        let files = vec![
+            file("main.cpp", "int main() {}"),
+            file("model.BIN", "\0\u{FFFD}"),
+        ];
+        assert!(validate_file_encoding(&files, &["bin".to_string()]).is_ok());
+    }
+
     #[test]
     fn test_validate_code_payload_success() {
         let files = vec![SubmissionFileDto {
             filename: "main.cpp".into(),
             content: "int main() {}".into(),
         }];
-        assert!(validate_code_payload(&files, "cpp", 1000).is_ok());
+        assert!(validate_code_payload(&files, "cpp", &limits(1000)).is_ok());
     }
 
     #[test]
     fn test_validate_code_payload_empty_files() {
         let files = vec![];
-        assert!(validate_code_payload(&files, "cpp", 1000).is_err());
+        assert!(validate_code_payload(&files, "cpp", &limits(1000)).is_err());
     }
 
     #[test]
@@ -167,7 +627,7 @@ mod tests {
                 content: "b".into(),
             },
         ];
-        assert!(validate_code_payload(&files, "cpp", 1000).is_err());
+        assert!(validate_code_payload(&files, "cpp", &limits(1000)).is_err());
     }
 
     #[test]
@@ -176,7 +636,7 @@ mod tests {
             filename: "large.cpp".into(),
             content: "12345".into(),
         }];
-        assert!(validate_code_payload(&files, "cpp", 4).is_err());
+        assert!(validate_code_payload(&files, "cpp", &limits(4)).is_err());
     }
 
     #[test]
@@ -185,7 +645,77 @@ mod tests {
             filename: "empty.cpp".into(),
             content: "".into(),
         }];
-        assert!(validate_code_payload(&files, "cpp", 1000).is_err());
+        assert!(validate_code_payload(&files, "cpp", &limits(1000)).is_err());
+    }
+
+    #[test]
+    fn test_validate_code_payload_too_many_files() {
+        let limits = SubmissionConfig {
+            max_files: 2,
+            ..SubmissionConfig::default()
+        };
+        let files = vec![file("a.cpp", "a"), file("b.cpp", "b"), file("c.cpp", "c")];
+        let err = validate_code_payload(&files, "cpp", &limits).unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::FieldValidation { ref field, ref message }
+                if field == "files" && message.contains("Too many files (3)")
+        ));
+    }
+
+    #[test]
+    fn test_validate_code_payload_names_oversized_file() {
+        let limits = SubmissionConfig {
+            max_file_size: 4,
+            ..SubmissionConfig::default()
+        };
+        let files = vec![file("ok.cpp", "1234"), file("big.cpp", "12345")];
+        let err = validate_code_payload(&files, "cpp", &limits).unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::FieldValidation { ref field, ref message }
+                if field == "files[1].content" && message.contains("'big.cpp'")
+        ));
+    }
+
+    #[test]
+    fn test_validate_code_payload_requires_configured_filename() {
+        let limits = SubmissionConfig {
+            required_filenames: HashMap::from([("java".to_string(), "Main.java".to_string())]),
+            ..SubmissionConfig::default()
+        };
+
+        let err = validate_code_payload(&[file("Solution.java", "class S {}")], "java", &limits)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::FieldValidation { ref field, ref message }
+                if field == "files" && message.contains("Main.java")
+        ));
+        assert!(
+            validate_code_payload(
+                &[
+                    file("Main.java", "class Main {}"),
+                    file("Util.java", "class Util {}")
+                ],
+                "java",
+                &limits
+            )
+            .is_ok()
+        );
+        assert!(validate_code_payload(&[file("a.cpp", "int x;")], "cpp", &limits).is_ok());
+    }
+
+    #[test]
+    fn test_validate_code_payload_multi_file_within_limits() {
+        let limits = SubmissionConfig {
+            max_size: 10,
+            max_files: 2,
+            max_file_size: 5,
+            ..SubmissionConfig::default()
+        };
+        let files = vec![file("main.cpp", "12345"), file("helper.h", "12345")];
+        assert!(validate_code_payload(&files, "cpp", &limits).is_ok());
     }
 
     #[test]
@@ -232,6 +762,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_contest_language() {
+        let allowed = vec!["cpp".to_string()];
+        assert!(validate_contest_language("cpp", Some(&allowed)).is_ok());
+        assert!(validate_contest_language(" cpp ", Some(&allowed)).is_ok());
+        let err = validate_contest_language("python3", Some(&allowed)).unwrap_err();
+        assert!(matches!(err, AppError::Validation(msg) if msg.contains("allowed languages: cpp")));
+        assert!(validate_contest_language("python3", None).is_ok());
+    }
+
+    #[test]
+    fn test_validate_language_problem_type() {
+        let mut languages = languages();
+        let mut output = languages["c"].clone();
+        output.problem_types = vec!["output_only".into()];
+        languages.insert("output".into(), output);
+
+        assert!(validate_language_problem_type("output", "output_only", &languages).is_ok());
+        assert!(validate_language_problem_type("cpp", "output_only", &languages).is_ok());
+        assert!(validate_language_problem_type("cpp", "batch", &languages).is_ok());
+        let err = validate_language_problem_type("output", "batch", &languages).unwrap_err();
+        assert!(
+            matches!(err, AppError::Validation(msg) if msg.contains("only available for problems of type: output_only"))
+        );
+    }
+
+    fn result_row(
+        verdict: Verdict,
+        score: f64,
+        time_used: Option<i32>,
+        memory_used: Option<i32>,
+    ) -> test_case_result::Model {
+        test_case_result::Model {
+            id: 0,
+            submission_id: 1,
+            judgement_id: Some(1),
+            test_case_id: None,
+            run_index: None,
+            verdict,
+            score,
+            time_used,
+            memory_used,
+            stdout: None,
+            stderr: None,
+            checker_output: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_aggregate_test_case_results() {
+        let rows = vec![
+            result_row(Verdict::Accepted, 30.0, Some(120), Some(2048)),
+            result_row(Verdict::TimeLimitExceeded, 0.0, Some(1000), Some(1024)),
+            result_row(Verdict::WrongAnswer, 10.0, Some(50), Some(4096)),
+            result_row(Verdict::Skipped, 0.0, Some(9999), Some(9999)),
+        ];
+        let aggregated = aggregate_test_case_results(&rows);
+        assert_eq!(aggregated.verdict, Verdict::TimeLimitExceeded);
+        assert_eq!(aggregated.score, 40.0);
+        assert_eq!(aggregated.time_used, Some(1000));
+        assert_eq!(aggregated.memory_used, Some(4096));
+    }
+
+    #[test]
+    fn test_aggregate_all_skipped_is_accepted() {
+        let rows = vec![result_row(Verdict::Skipped, 0.0, None, None)];
+        let aggregated = aggregate_test_case_results(&rows);
+        assert_eq!(aggregated.verdict, Verdict::Accepted);
+        assert_eq!(aggregated.time_used, None);
+    }
+
     #[test]
     fn test_files_to_json_and_from_json() {
         let files = vec![
@@ -249,6 +851,31 @@ mod tests {
         assert_eq!(files, parsed_files);
     }
 
+    #[test]
+    fn test_score_percentage() {
+        assert_eq!(score_percentage(Some(50.0), 100.0), Some(50.0));
+        assert_eq!(score_percentage(Some(1.0), 3.0), Some(33.33));
+        assert_eq!(score_percentage(Some(0.0), 0.0), None);
+        assert_eq!(score_percentage(None, 100.0), None);
+    }
+
+    #[test]
+    fn test_source_metrics_sums_bytes_and_lines() {
+        let files = vec![
+            SubmissionFileDto {
+                filename: "main.cpp".into(),
+                content: "int main() {\n  return 0;\n}\n".into(),
+            },
+            SubmissionFileDto {
+                filename: "util.h".into(),
+                content: "#pragma once".into(),
+            },
+        ];
+
+        assert_eq!(source_metrics(&files_to_json(&files)), (39, 4));
+        assert_eq!(source_metrics(&serde_json::json!([])), (0, 0));
+    }
+
     #[test]
     fn test_files_to_json_replaces_nul_bytes() {
         let files = vec![SubmissionFileDto {
@@ -279,4 +906,28 @@ mod tests {
         let languages = HashSet::new();
         assert!(validate_run_language("anything", &languages).is_ok());
     }
+
+    #[test]
+    fn pretest_phase_judges_only_pretests() {
+        let cases = vec![(1, true), (2, false), (3, true)];
+        let ids = |cases: Vec<(i32, bool)>| cases.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+
+        assert_eq!(
+            ids(select_judged_test_cases(cases.clone(), true, |c| c.1)),
+            vec![1, 3]
+        );
+        assert_eq!(
+            ids(select_judged_test_cases(cases, false, |c| c.1)),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn pretest_phase_without_pretests_judges_full_set() {
+        let cases = vec![(1, false), (2, false)];
+        assert_eq!(
+            select_judged_test_cases(cases.clone(), true, |c| c.1),
+            cases
+        );
+    }
 }
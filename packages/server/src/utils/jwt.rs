@@ -12,12 +12,16 @@ pub struct Claims {
     pub uid: i32,
     pub roles: Vec<String>,
     pub permissions: Vec<String>,
+    /// `user.token_version` at signing time.
+    #[serde(default)]
+    pub ver: i32,
     pub exp: u64,
 }
 
 pub fn sign_access_token(
     user_id: i32,
     username: &str,
+    token_version: i32,
     roles: Vec<String>,
     permissions: Vec<String>,
     secret: &str,
@@ -33,6 +37,7 @@ pub fn sign_access_token(
         uid: user_id,
         roles,
         permissions,
+        ver: token_version,
         exp: expiration as u64,
     };
 
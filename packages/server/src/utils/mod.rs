@@ -1,5 +1,7 @@
 pub mod blob;
 pub mod contest;
+pub mod difficulty;
+pub mod duplicate;
 pub mod filename;
 pub mod hash;
 pub mod judging;
@@ -8,8 +10,14 @@ pub mod password;
 pub mod plugin;
 pub mod problem;
 pub mod query;
+pub mod range;
 pub mod rate_limit;
 pub mod refresh;
+pub mod rejudge_batch;
+pub mod retention;
+pub mod saved_view;
+pub mod similarity;
 pub mod soft_delete;
 pub mod test_case_body;
 pub mod text;
+pub mod worker;
@@ -1,5 +1,15 @@
-use crate::entity::problem;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+
+use serde::Deserialize;
+
+use crate::entity::{plugin_config, problem, problem_version, test_case};
 use crate::error::AppError;
+use crate::host_funcs::config::resolve_namespace;
+use crate::models::plugin_config::config_key;
+use crate::models::problem::{ProblemScoreSummaryResponse, SubtaskScoreSummary};
 use crate::utils::soft_delete::SoftDeletable;
 
 pub async fn find_problem<C: sea_orm::ConnectionTrait>(
@@ -11,3 +21,272 @@ pub async fn find_problem<C: sea_orm::ConnectionTrait>(
         .await?
         .ok_or_else(|| AppError::NotFound("Problem not found".into()))
 }
+
+pub async fn problem_has_test_cases<C: sea_orm::ConnectionTrait>(
+    db: &C,
+    problem_id: i32,
+) -> Result<bool, AppError> {
+    let count = test_case::Entity::find()
+        .filter(test_case::Column::ProblemId.eq(problem_id))
+        .count(db)
+        .await?;
+    Ok(count > 0)
+}
+
+/// Sum of the test case scores of `problem_id`: the most a submission can
+/// earn when its contest type reports no subtask breakdown.
+pub async fn problem_max_score<C: sea_orm::ConnectionTrait>(
+    db: &C,
+    problem_id: i32,
+) -> Result<f64, AppError> {
+    let scores: Vec<i32> = test_case::Entity::find()
+        .filter(test_case::Column::ProblemId.eq(problem_id))
+        .select_only()
+        .column(test_case::Column::Score)
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(scores.into_iter().map(f64::from).sum())
+}
+
+/// Records `problem`'s statement and limits as its next version, before
+/// `user_id` overwrites them.
+pub async fn record_problem_version<C: sea_orm::ConnectionTrait>(
+    db: &C,
+    problem: &problem::Model,
+    user_id: i32,
+) -> Result<problem_version::Model, AppError> {
+    let latest: Option<i32> = problem_version::Entity::find()
+        .filter(problem_version::Column::ProblemId.eq(problem.id))
+        .select_only()
+        .column(problem_version::Column::Version)
+        .order_by_desc(problem_version::Column::Version)
+        .into_tuple()
+        .one(db)
+        .await?;
+    let version = problem_version::ActiveModel {
+        problem_id: Set(problem.id),
+        version: Set(latest.unwrap_or(0) + 1),
+        title: Set(problem.title.clone()),
+        content: Set(problem.content.clone()),
+        time_limit: Set(problem.time_limit),
+        memory_limit: Set(problem.memory_limit),
+        created_by: Set(user_id),
+        created_at: Set(chrono::Utc::now()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(version)
+}
+
+/// The fields of the IOI plugin's `SubtaskDef` the score summary checks.
+#[derive(Debug, Default, Deserialize)]
+pub struct IoiSubtaskDef {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub max_score: f64,
+    /// Labels of the test cases in the subtask; an unlabelled test case is
+    /// referred to by its ID.
+    #[serde(default)]
+    pub test_cases: Vec<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct IoiTaskConfig {
+    #[serde(default)]
+    subtasks: Vec<IoiSubtaskDef>,
+}
+
+/// Subtasks configured for `problem_id` through the IOI plugin's `task`
+/// config in `contest_id`; empty when none are configured.
+pub async fn load_ioi_subtasks<C: sea_orm::ConnectionTrait>(
+    db: &C,
+    contest_id: i32,
+    problem_id: i32,
+) -> Result<Vec<IoiSubtaskDef>, AppError> {
+    let row = plugin_config::Entity::find_by_id((
+        "contest_problem".to_string(),
+        config_key::contest_problem(contest_id, problem_id),
+        resolve_namespace("contest_problem", "ioi", "task"),
+    ))
+    .one(db)
+    .await?;
+    Ok(row
+        .and_then(|r| serde_json::from_value::<IoiTaskConfig>(r.config).ok())
+        .unwrap_or_default()
+        .subtasks)
+}
+
+/// Summarizes `(id, label, score, is_sample)` test cases, given in position
+/// order, against the configured `subtasks` into totals, per-subtask sums and
+/// consistency warnings.
+pub fn summarize_test_case_scores(
+    problem_id: i32,
+    test_cases: &[(i32, String, i32, bool)],
+    subtasks: &[IoiSubtaskDef],
+) -> ProblemScoreSummaryResponse {
+    let labels: Vec<String> = test_cases
+        .iter()
+        .map(|(id, label, _, _)| {
+            if label.is_empty() {
+                id.to_string()
+            } else {
+                label.clone()
+            }
+        })
+        .collect();
+    let sample_score: i64 = test_cases
+        .iter()
+        .filter(|(_, _, _, is_sample)| *is_sample)
+        .map(|(_, _, score, _)| i64::from(*score))
+        .sum();
+    let total_score: i64 = test_cases
+        .iter()
+        .map(|(_, _, score, _)| i64::from(*score))
+        .sum();
+
+    let mut warnings = Vec::new();
+    if sample_score != 0 {
+        warnings.push(format!("Sample test cases carry {sample_score} points"));
+    }
+
+    let mut memberships = vec![0usize; test_cases.len()];
+    let mut summaries = Vec::with_capacity(subtasks.len());
+    for subtask in subtasks {
+        let mut summary = SubtaskScoreSummary {
+            name: subtask.name.clone(),
+            test_case_count: 0,
+            score: 0,
+            max_score: subtask.max_score,
+        };
+        for label in &subtask.test_cases {
+            match labels.iter().position(|l| l == label) {
+                Some(index) => {
+                    memberships[index] += 1;
+                    summary.test_case_count += 1;
+                    summary.score += i64::from(test_cases[index].2);
+                }
+                None => warnings.push(format!(
+                    "Subtask '{}' lists unknown test case '{label}'",
+                    subtask.name
+                )),
+            }
+        }
+        if (summary.score as f64 - subtask.max_score).abs() > 1e-6 {
+            warnings.push(format!(
+                "Subtask '{}' has max_score {} but its test cases sum to {}",
+                subtask.name, subtask.max_score, summary.score
+            ));
+        }
+        summaries.push(summary);
+    }
+
+    if !subtasks.is_empty() {
+        for ((label, count), (_, _, _, is_sample)) in
+            labels.iter().zip(&memberships).zip(test_cases)
+        {
+            match count {
+                0 if !*is_sample => {
+                    warnings.push(format!("Test case '{label}' belongs to no subtask"))
+                }
+                0 | 1 => {}
+                n => warnings.push(format!("Test case '{label}' belongs to {n} subtasks")),
+            }
+        }
+    }
+
+    ProblemScoreSummaryResponse {
+        problem_id,
+        total_score,
+        test_case_count: test_cases.len(),
+        sample_score,
+        subtasks: summaries,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tc(id: i32, label: &str, score: i32, is_sample: bool) -> (i32, String, i32, bool) {
+        (id, label.to_string(), score, is_sample)
+    }
+
+    fn subtask(name: &str, max_score: f64, test_cases: &[&str]) -> IoiSubtaskDef {
+        IoiSubtaskDef {
+            name: name.to_string(),
+            max_score,
+            test_cases: test_cases.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn sums_configured_subtasks_in_config_order() {
+        let summary = summarize_test_case_scores(
+            7,
+            &[
+                tc(1, "sample_01", 0, true),
+                tc(2, "a", 20, false),
+                tc(3, "b", 15, false),
+                tc(4, "c", 25, false),
+                tc(5, "", 15, false),
+            ],
+            &[
+                subtask("Large", 45.0, &["a", "c"]),
+                subtask("Small", 30.0, &["b", "5"]),
+            ],
+        );
+
+        assert_eq!(summary.total_score, 75);
+        assert_eq!(summary.test_case_count, 5);
+        let subtasks: Vec<(&str, usize, i64)> = summary
+            .subtasks
+            .iter()
+            .map(|s| (s.name.as_str(), s.test_case_count, s.score))
+            .collect();
+        assert_eq!(subtasks, vec![("Large", 2, 45), ("Small", 2, 30)]);
+        assert!(summary.warnings.is_empty(), "{:?}", summary.warnings);
+    }
+
+    #[test]
+    fn warns_on_inconsistent_subtasks() {
+        let summary = summarize_test_case_scores(
+            7,
+            &[
+                tc(1, "sample_01", 5, true),
+                tc(2, "a", 60, false),
+                tc(3, "b", 20, false),
+                tc(4, "c", 20, false),
+            ],
+            &[
+                subtask("1", 50.0, &["a"]),
+                subtask("2", 40.0, &["a", "b", "missing"]),
+            ],
+        );
+
+        assert_eq!(summary.sample_score, 5);
+        assert_eq!(
+            summary.warnings,
+            vec![
+                "Sample test cases carry 5 points".to_string(),
+                "Subtask '1' has max_score 50 but its test cases sum to 60".to_string(),
+                "Subtask '2' lists unknown test case 'missing'".to_string(),
+                "Subtask '2' has max_score 40 but its test cases sum to 80".to_string(),
+                "Test case 'a' belongs to 2 subtasks".to_string(),
+                "Test case 'c' belongs to no subtask".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_no_subtasks_without_a_config() {
+        let summary = summarize_test_case_scores(7, &[tc(1, "a", 30, false)], &[]);
+
+        assert_eq!(summary.total_score, 30);
+        assert!(summary.subtasks.is_empty());
+        assert!(summary.warnings.is_empty(), "{:?}", summary.warnings);
+    }
+}
@@ -0,0 +1,131 @@
+use axum::body::Body;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::Response;
+
+use crate::error::AppError;
+
+/// How to answer a request given its `Range` header and the body size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// No usable range: send the whole body with 200.
+    Full,
+    /// Send bytes `start..=end` with 206.
+    Partial { start: u64, end: u64 },
+    /// The range lies past the end of the body: answer 416.
+    Unsatisfiable,
+}
+
+impl ByteRange {
+    /// Resolves the request's `Range` header against a body of `size` bytes.
+    pub fn from_headers(headers: &HeaderMap, size: u64) -> Self {
+        headers
+            .get(header::RANGE)
+            .and_then(|v| v.to_str().ok())
+            .map_or(Self::Full, |v| Self::parse(v, size))
+    }
+
+    /// Parses a single `bytes=` range (`a-b`, `a-` or `-n`). Malformed
+    /// values, other units and multi-range requests fall back to `Full`,
+    /// which RFC 9110 allows.
+    pub fn parse(value: &str, size: u64) -> Self {
+        let Some(spec) = value.trim().strip_prefix("bytes=") else {
+            return Self::Full;
+        };
+        if spec.contains(',') {
+            return Self::Full;
+        }
+        let Some((first, last)) = spec.trim().split_once('-') else {
+            return Self::Full;
+        };
+        let (first, last) = (first.trim(), last.trim());
+
+        let (start, end) = if first.is_empty() {
+            let Ok(suffix) = last.parse::<u64>() else {
+                return Self::Full;
+            };
+            if suffix == 0 || size == 0 {
+                return Self::Unsatisfiable;
+            }
+            (size.saturating_sub(suffix), size - 1)
+        } else {
+            let Ok(start) = first.parse::<u64>() else {
+                return Self::Full;
+            };
+            let end = if last.is_empty() {
+                u64::MAX
+            } else {
+                match last.parse::<u64>() {
+                    Ok(end) if end >= start => end,
+                    _ => return Self::Full,
+                }
+            };
+            if start >= size {
+                return Self::Unsatisfiable;
+            }
+            (start, end.min(size - 1))
+        };
+        Self::Partial { start, end }
+    }
+}
+
+/// Starts a 206 response for bytes `start..=end` of a `size`-byte body.
+pub fn partial_content(start: u64, end: u64, size: u64) -> axum::http::response::Builder {
+    Response::builder()
+        .status(StatusCode::PARTIAL_CONTENT)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::CONTENT_RANGE, format!("bytes {start}-{end}/{size}"))
+        .header(header::CONTENT_LENGTH, (end - start + 1).to_string())
+}
+
+/// 416 response for a range outside a `size`-byte body.
+pub fn range_not_satisfiable(size: u64) -> Result<Response, AppError> {
+    Response::builder()
+        .status(StatusCode::RANGE_NOT_SATISFIABLE)
+        .header(header::CONTENT_RANGE, format!("bytes */{size}"))
+        .body(Body::empty())
+        .map_err(|e| AppError::Internal(format!("Failed to build response: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bounded_open_and_suffix_ranges() {
+        assert_eq!(
+            ByteRange::parse("bytes=2-5", 10),
+            ByteRange::Partial { start: 2, end: 5 }
+        );
+        assert_eq!(
+            ByteRange::parse("bytes=7-", 10),
+            ByteRange::Partial { start: 7, end: 9 }
+        );
+        assert_eq!(
+            ByteRange::parse("bytes=-3", 10),
+            ByteRange::Partial { start: 7, end: 9 }
+        );
+        assert_eq!(
+            ByteRange::parse("bytes=5-100", 10),
+            ByteRange::Partial { start: 5, end: 9 }
+        );
+        assert_eq!(
+            ByteRange::parse("bytes=-100", 10),
+            ByteRange::Partial { start: 0, end: 9 }
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_ranges_are_unsatisfiable() {
+        assert_eq!(ByteRange::parse("bytes=10-", 10), ByteRange::Unsatisfiable);
+        assert_eq!(ByteRange::parse("bytes=-0", 10), ByteRange::Unsatisfiable);
+        assert_eq!(ByteRange::parse("bytes=0-", 0), ByteRange::Unsatisfiable);
+    }
+
+    #[test]
+    fn unsupported_or_malformed_ranges_serve_everything() {
+        assert_eq!(ByteRange::parse("items=0-1", 10), ByteRange::Full);
+        assert_eq!(ByteRange::parse("bytes=0-1,4-5", 10), ByteRange::Full);
+        assert_eq!(ByteRange::parse("bytes=5-2", 10), ByteRange::Full);
+        assert_eq!(ByteRange::parse("bytes=x-", 10), ByteRange::Full);
+    }
+}
@@ -1,6 +1,7 @@
 use std::cmp;
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
+use common::SubmissionStatus;
 use sea_orm::*;
 
 use crate::entity::{code_run, submission};
@@ -68,3 +69,85 @@ pub async fn check_rate_limit(
 
     Ok(())
 }
+
+/// Advisory-lock namespace for [`check_in_flight_limit`]; the user id is the
+/// second key.
+const IN_FLIGHT_LOCK_NAMESPACE: i32 = 1;
+
+/// Rejects a new submission while the user already has `limit` unfinished
+/// ones. A limit of 0 disables the check.
+///
+/// `txn` must be the transaction that inserts the submission: the check holds
+/// a per-user advisory lock until it commits, so concurrent submits from the
+/// same user are counted one after another.
+pub async fn check_in_flight_limit<C: ConnectionTrait>(
+    txn: &C,
+    user_id: i32,
+    limit: u32,
+) -> Result<(), AppError> {
+    if limit == 0 {
+        return Ok(());
+    }
+
+    txn.execute_raw(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT pg_advisory_xact_lock($1, $2)",
+        [IN_FLIGHT_LOCK_NAMESPACE.into(), user_id.into()],
+    ))
+    .await?;
+
+    let in_flight = submission::Entity::find()
+        .filter(submission::Column::UserId.eq(user_id))
+        .filter(submission::Column::Status.is_in([
+            SubmissionStatus::Pending,
+            SubmissionStatus::Compiling,
+            SubmissionStatus::Running,
+        ]))
+        .count(txn)
+        .await?;
+
+    if in_flight >= u64::from(limit) {
+        return Err(AppError::TooManyInFlight { limit });
+    }
+
+    Ok(())
+}
+
+/// Rejects a submission to `problem_id` made less than `cooldown_secs` after
+/// the user's previous submission to the same problem. A cooldown of 0
+/// disables the check.
+pub async fn check_resubmit_cooldown(
+    db: &DatabaseConnection,
+    user_id: i32,
+    problem_id: i32,
+    cooldown_secs: u64,
+) -> Result<(), AppError> {
+    if cooldown_secs == 0 {
+        return Ok(());
+    }
+
+    let cooldown = Duration::try_seconds(i64::try_from(cooldown_secs).unwrap_or(i64::MAX))
+        .unwrap_or(Duration::MAX);
+    let since = Utc::now()
+        .checked_sub_signed(cooldown)
+        .unwrap_or(DateTime::<Utc>::MIN_UTC);
+    let latest = submission::Entity::find()
+        .filter(submission::Column::UserId.eq(user_id))
+        .filter(submission::Column::ProblemId.eq(problem_id))
+        .filter(submission::Column::CreatedAt.gt(since))
+        .order_by_desc(submission::Column::CreatedAt)
+        .one(db)
+        .await?;
+
+    if let Some(latest) = latest {
+        let retry_after = latest
+            .created_at
+            .checked_add_signed(cooldown)
+            .map_or(cooldown_secs, |expires| {
+                cmp::max((expires - Utc::now()).num_seconds(), 1) as u64
+            });
+        return Err(AppError::RateLimited { retry_after });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use chrono::Utc;
+use sea_orm::prelude::Expr;
+use sea_orm::*;
+
+use crate::entity::{rejudge_batch, submission_judgement};
+
+/// Records a bulk rejudge of `judgement_ids` as a new batch and links each
+/// judgement to it. Must run before the judgements are dispatched so none
+/// can finish before it is counted.
+pub async fn open_rejudge_batch<C: ConnectionTrait>(
+    db: &C,
+    triggered_by_user_id: i32,
+    judgement_ids: Vec<i32>,
+) -> Result<rejudge_batch::Model, DbErr> {
+    let total = i32::try_from(judgement_ids.len()).unwrap_or(i32::MAX);
+    let batch = rejudge_batch::ActiveModel {
+        triggered_by_user_id: Set(triggered_by_user_id),
+        total: Set(total),
+        remaining: Set(total),
+        created_at: Set(Utc::now()),
+        completed_at: Set(None),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    for ids in judgement_ids.chunks(500) {
+        submission_judgement::Entity::update_many()
+            .col_expr(
+                submission_judgement::Column::RejudgeBatchId,
+                Expr::value(batch.id),
+            )
+            .filter(submission_judgement::Column::Id.is_in(ids.to_vec()))
+            .exec(db)
+            .await?;
+    }
+
+    Ok(batch)
+}
+
+/// Counts a finished judgement off its rejudge batch, if it belongs to one.
+///
+/// The judgement's `rejudge_batch_settled` flag is flipped in the same
+/// statement, so calling this again for the same judgement (a re-dispatch,
+/// or both the error and completion paths) leaves the batch untouched.
+pub async fn settle_rejudge_batch<C: ConnectionTrait>(
+    db: &C,
+    judgement_id: i32,
+) -> Result<(), DbErr> {
+    if judgement_id <= 0 {
+        return Ok(());
+    }
+
+    db.execute_raw(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"WITH settled AS (
+               UPDATE submission_judgement SET rejudge_batch_settled = TRUE
+               WHERE id = $1 AND rejudge_batch_id IS NOT NULL
+                 AND rejudge_batch_settled = FALSE
+               RETURNING rejudge_batch_id
+           )
+           UPDATE rejudge_batch
+           SET remaining = rejudge_batch.remaining - 1,
+               completed_at = CASE WHEN rejudge_batch.remaining = 1
+                                   THEN NOW() ELSE rejudge_batch.completed_at END
+           FROM settled
+           WHERE rejudge_batch.id = settled.rejudge_batch_id
+             AND rejudge_batch.remaining > 0"#,
+        [judgement_id.into()],
+    ))
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use common::SubmissionStatus;
+use sea_orm::prelude::Expr;
+use sea_orm::sea_query::{LockType, Query as SeaQuery};
+use sea_orm::*;
+use tracing::info;
+
+use crate::entity::{archived_submission, submission, submission_judgement, test_case_result};
+
+const TERMINAL_STATUSES: [SubmissionStatus; 3] = [
+    SubmissionStatus::Judged,
+    SubmissionStatus::CompilationError,
+    SubmissionStatus::SystemError,
+];
+
+/// Submissions archived per transaction.
+const ARCHIVE_BATCH_SIZE: usize = 100;
+
+/// Archive up to `limit` terminal submissions created before `cutoff`.
+///
+/// For each one, the source files and `test_case_result` rows are copied
+/// into `archived_submission`, then the result rows are deleted and
+/// `submission.files` is replaced with an empty array. The submission row
+/// keeps its verdict, score and timings. Submissions with an unfinalized
+/// judgement (e.g. a pending rejudge) are skipped. Returns the number of
+/// submissions archived.
+pub async fn archive_submissions(
+    db: &DatabaseConnection,
+    cutoff: DateTime<Utc>,
+    limit: u64,
+) -> Result<u64, DbErr> {
+    let candidate_ids: Vec<i32> = submission::Entity::find()
+        .select_only()
+        .column(submission::Column::Id)
+        .filter(submission::Column::Status.is_in(TERMINAL_STATUSES))
+        .filter(submission::Column::CreatedAt.lt(cutoff))
+        .filter(
+            submission::Column::Id.not_in_subquery(
+                SeaQuery::select()
+                    .column(archived_submission::Column::SubmissionId)
+                    .from(archived_submission::Entity)
+                    .to_owned(),
+            ),
+        )
+        .filter(
+            submission::Column::Id.not_in_subquery(
+                SeaQuery::select()
+                    .column(submission_judgement::Column::SubmissionId)
+                    .from(submission_judgement::Entity)
+                    .and_where(submission_judgement::Column::IsFinalized.eq(false))
+                    .to_owned(),
+            ),
+        )
+        .order_by_asc(submission::Column::Id)
+        .limit(limit)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut archived = 0u64;
+    for batch_ids in candidate_ids.chunks(ARCHIVE_BATCH_SIZE) {
+        archived += archive_batch(db, batch_ids).await?;
+    }
+
+    if archived > 0 {
+        info!(archived, %cutoff, "Archived terminal submissions");
+    }
+    Ok(archived)
+}
+
+async fn archive_batch(db: &DatabaseConnection, ids: &[i32]) -> Result<u64, DbErr> {
+    let txn = db.begin().await?;
+
+    // Re-check under the row lock: a rejudge may have reopened a submission
+    // between candidate selection and now.
+    let locked: Vec<submission::Model> = submission::Entity::find()
+        .filter(submission::Column::Id.is_in(ids.to_vec()))
+        .filter(submission::Column::Status.is_in(TERMINAL_STATUSES))
+        .lock(LockType::Update)
+        .all(&txn)
+        .await?;
+    if locked.is_empty() {
+        txn.commit().await?;
+        return Ok(0);
+    }
+    let locked_ids: Vec<i32> = locked.iter().map(|s| s.id).collect();
+
+    let results = test_case_result::Entity::find()
+        .filter(test_case_result::Column::SubmissionId.is_in(locked_ids.clone()))
+        .order_by_asc(test_case_result::Column::Id)
+        .all(&txn)
+        .await?;
+
+    let now = Utc::now();
+    let archives = locked.iter().map(|sub| {
+        let rows: Vec<&test_case_result::Model> = results
+            .iter()
+            .filter(|r| r.submission_id == sub.id)
+            .collect();
+        archived_submission::ActiveModel {
+            submission_id: Set(sub.id),
+            files: Set(sub.files.clone()),
+            test_case_results: Set(serde_json::to_value(rows).unwrap_or_default()),
+            archived_at: Set(now),
+        }
+    });
+    archived_submission::Entity::insert_many(archives)
+        .exec(&txn)
+        .await?;
+
+    test_case_result::Entity::delete_many()
+        .filter(test_case_result::Column::SubmissionId.is_in(locked_ids.clone()))
+        .exec(&txn)
+        .await?;
+
+    submission::Entity::update_many()
+        .col_expr(
+            submission::Column::Files,
+            Expr::value(serde_json::Value::Array(Vec::new())),
+        )
+        .filter(submission::Column::Id.is_in(locked_ids))
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+    Ok(locked.len() as u64)
+}
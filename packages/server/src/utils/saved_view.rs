@@ -0,0 +1,28 @@
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde::de::DeserializeOwned;
+
+use crate::entity::saved_view;
+use crate::error::AppError;
+use crate::models::saved_view::SavedViewResource;
+
+/// Query parameters stored in `user_id`'s view `name` for `resource`.
+pub async fn load_saved_view<C, T>(
+    db: &C,
+    user_id: i32,
+    resource: SavedViewResource,
+    name: &str,
+) -> Result<T, AppError>
+where
+    C: sea_orm::ConnectionTrait,
+    T: DeserializeOwned,
+{
+    let view = saved_view::Entity::find()
+        .filter(saved_view::Column::UserId.eq(user_id))
+        .filter(saved_view::Column::Resource.eq(resource.as_str()))
+        .filter(saved_view::Column::Name.eq(name))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Saved view not found".into()))?;
+    serde_json::from_value(view.params)
+        .map_err(|e| AppError::Validation(format!("Saved view is no longer valid: {e}")))
+}
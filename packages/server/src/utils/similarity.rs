@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use sea_orm::*;
+
+use crate::entity::problem;
+use crate::error::AppError;
+use crate::models::problem::SimilarProblem;
+use crate::utils::soft_delete::SoftDeletable;
+
+/// Title similarity at or above which a new problem is flagged.
+pub const TITLE_SIMILARITY_THRESHOLD: f64 = 0.8;
+/// Statement similarity at or above which a new problem is flagged.
+pub const CONTENT_SIMILARITY_THRESHOLD: f64 = 0.9;
+/// Statements shorter than this (in chars) are too generic to compare.
+const MIN_CONTENT_CHARS: usize = 100;
+
+/// Dice coefficient over the character bigrams of `a` and `b`, ignoring
+/// case, punctuation and whitespace. 1.0 means identical after
+/// normalization; 0.0 means nothing in common or either side is empty.
+pub fn text_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (bigrams(a), bigrams(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let shared = a.intersection(&b).count();
+    2.0 * shared as f64 / (a.len() + b.len()) as f64
+}
+
+fn bigrams(text: &str) -> HashSet<(char, char)> {
+    let chars: Vec<char> = text
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if chars.len() == 1 {
+        return HashSet::from([(chars[0], chars[0])]);
+    }
+    chars.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+/// Active problems whose title or statement is close to `title`/`content`,
+/// most similar first.
+pub async fn find_similar_problems<C: ConnectionTrait>(
+    db: &C,
+    title: &str,
+    content: &str,
+) -> Result<Vec<SimilarProblem>, AppError> {
+    let compare_content = content.chars().count() >= MIN_CONTENT_CHARS;
+    let candidates = problem::Entity::find_active()
+        .select_only()
+        .column(problem::Column::Id)
+        .column(problem::Column::Title)
+        .column(problem::Column::Content)
+        .into_tuple::<(i32, String, String)>()
+        .all(db)
+        .await?;
+
+    let mut similar: Vec<SimilarProblem> = candidates
+        .into_iter()
+        .filter_map(|(id, other_title, other_content)| {
+            let title_similarity = text_similarity(title, &other_title);
+            let content_similarity =
+                if compare_content && other_content.chars().count() >= MIN_CONTENT_CHARS {
+                    text_similarity(content, &other_content)
+                } else {
+                    0.0
+                };
+            (title_similarity >= TITLE_SIMILARITY_THRESHOLD
+                || content_similarity >= CONTENT_SIMILARITY_THRESHOLD)
+                .then_some(SimilarProblem {
+                    id,
+                    title: other_title,
+                    title_similarity,
+                    content_similarity,
+                })
+        })
+        .collect();
+    similar.sort_by(|a, b| {
+        let key = |p: &SimilarProblem| p.title_similarity.max(p.content_similarity);
+        key(b).total_cmp(&key(a)).then(a.id.cmp(&b.id))
+    });
+    Ok(similar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_text_after_normalization_scores_one() {
+        assert_eq!(text_similarity("Two Sum", "two-sum!"), 1.0);
+    }
+
+    #[test]
+    fn near_duplicate_titles_clear_the_threshold() {
+        assert!(
+            text_similarity("Longest Common Subsequence", "Longest Common Subsequences")
+                >= TITLE_SIMILARITY_THRESHOLD
+        );
+        assert!(text_similarity("Two Sum", "Shortest Path in a Grid") < TITLE_SIMILARITY_THRESHOLD);
+    }
+
+    #[test]
+    fn empty_text_is_never_similar() {
+        assert_eq!(text_similarity("", "Two Sum"), 0.0);
+        assert_eq!(text_similarity("!!", "??"), 0.0);
+    }
+}
@@ -23,6 +23,23 @@ pub fn sanitize_db_json(value: Value) -> Value {
     }
 }
 
+const TRUNCATION_MARKER: &str = "\n... (truncated)";
+
+/// Cuts `value` to at most `max_bytes` bytes on a char boundary, marking the
+/// cut. `max_bytes == 0` leaves it untouched.
+pub fn truncate_text(mut value: String, max_bytes: usize) -> String {
+    if max_bytes == 0 || value.len() <= max_bytes {
+        return value;
+    }
+    let mut end = max_bytes;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    value.truncate(end);
+    value.push_str(TRUNCATION_MARKER);
+    value
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,4 +62,12 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn truncate_text_cuts_on_char_boundary() {
+        assert_eq!(truncate_text("short".into(), 16), "short");
+        assert_eq!(truncate_text("abcdef".into(), 4), "abcd\n... (truncated)");
+        assert_eq!(truncate_text("aé".into(), 2), "a\n... (truncated)");
+        assert_eq!(truncate_text("unbounded".into(), 0), "unbounded");
+    }
 }
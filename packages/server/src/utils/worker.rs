@@ -0,0 +1,445 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use redis::AsyncCommands;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Set,
+};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::entity::worker;
+use crate::models::system::WorkerInfo;
+
+pub const HEARTBEAT_KEY_PREFIX: &str = "broccoli:worker:heartbeat:";
+
+/// Prefix of the key holding a submission's newest applied `judge_epoch`,
+/// `broccoli:judge_epoch:<submission-id>`. Workers drop operations whose
+/// `judge_job` carries an older epoch.
+pub const JUDGE_EPOCH_KEY_PREFIX: &str = "broccoli:judge_epoch:";
+
+/// How long a recorded judge epoch is kept; far longer than any operation
+/// waits in the queue.
+const JUDGE_EPOCH_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Raises the recorded epoch to ARGV[1], never lowering it.
+const RAISE_EPOCH_SCRIPT: &str = r#"
+local current = tonumber(redis.call('GET', KEYS[1]) or '-1')
+if tonumber(ARGV[1]) >= current then
+  redis.call('SET', KEYS[1], ARGV[1], 'EX', ARGV[2])
+end
+return 1
+"#;
+
+/// A worker whose last heartbeat is older than this is reported as stale.
+pub const STALE_AFTER_SECS: i64 = 10;
+
+/// A worker row whose last heartbeat is older than this is deleted by the
+/// recorder, so workers that were retired or renamed drop off the list.
+pub const PRUNE_AFTER_SECS: i64 = 24 * 60 * 60;
+
+const RECORD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Heartbeat body written by `broccoli-worker` under
+/// `broccoli:worker:heartbeat:<id>`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkerHeartbeat {
+    pub id: String,
+    pub started_at: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub in_flight: u32,
+    pub max_concurrency: Option<u32>,
+    pub sandbox_backend: String,
+    pub version: String,
+    #[serde(default)]
+    pub hostname: Option<String>,
+    #[serde(default)]
+    pub ip_addresses: Vec<String>,
+    #[serde(default)]
+    pub os: Option<String>,
+    #[serde(default)]
+    pub arch: Option<String>,
+    #[serde(default)]
+    pub cpu_count: Option<u32>,
+    #[serde(default)]
+    pub pid: Option<u32>,
+}
+
+pub fn is_stale(last_seen: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    now - last_seen > chrono::Duration::seconds(STALE_AFTER_SECS)
+}
+
+/// Upserts the `worker` row for a heartbeat. An older heartbeat never
+/// overwrites a newer one, so replicas racing on the same key are harmless.
+pub async fn record_heartbeat<C: ConnectionTrait>(
+    conn: &C,
+    heartbeat: &WorkerHeartbeat,
+) -> Result<(), DbErr> {
+    let active = worker::ActiveModel {
+        id: Set(heartbeat.id.clone()),
+        started_at: Set(heartbeat.started_at),
+        last_seen: Set(heartbeat.last_seen),
+        in_flight: Set(heartbeat.in_flight as i32),
+        max_concurrency: Set(heartbeat.max_concurrency.map(|v| v as i32)),
+        sandbox_backend: Set(heartbeat.sandbox_backend.clone()),
+        version: Set(heartbeat.version.clone()),
+        hostname: Set(heartbeat.hostname.clone()),
+        ip_addresses: Set(serde_json::json!(heartbeat.ip_addresses)),
+        os: Set(heartbeat.os.clone()),
+        arch: Set(heartbeat.arch.clone()),
+        cpu_count: Set(heartbeat.cpu_count.map(|v| v as i32)),
+        pid: Set(heartbeat.pid.map(|v| v as i32)),
+        created_at: Set(Utc::now()),
+    };
+
+    worker::Entity::insert(active)
+        .on_conflict(
+            OnConflict::column(worker::Column::Id)
+                .update_columns([
+                    worker::Column::StartedAt,
+                    worker::Column::LastSeen,
+                    worker::Column::InFlight,
+                    worker::Column::MaxConcurrency,
+                    worker::Column::SandboxBackend,
+                    worker::Column::Version,
+                    worker::Column::Hostname,
+                    worker::Column::IpAddresses,
+                    worker::Column::Os,
+                    worker::Column::Arch,
+                    worker::Column::CpuCount,
+                    worker::Column::Pid,
+                ])
+                .action_and_where(worker::Column::LastSeen.lte(heartbeat.last_seen))
+                .to_owned(),
+        )
+        .exec_without_returning(conn)
+        .await?;
+    Ok(())
+}
+
+/// Lists every worker that has ever been recorded, ordered by id.
+pub async fn list_known_workers<C: ConnectionTrait>(conn: &C) -> Result<Vec<worker::Model>, DbErr> {
+    worker::Entity::find()
+        .order_by_asc(worker::Column::Id)
+        .all(conn)
+        .await
+}
+
+/// Combines recorded worker `rows` with the `heartbeats` currently in Redis,
+/// ordered by id. Liveness comes from the heartbeats alone: a recorded
+/// worker without one is reported stale with its last recorded `last_seen`.
+pub fn merge_workers(
+    rows: Vec<worker::Model>,
+    heartbeats: Vec<WorkerHeartbeat>,
+    now: DateTime<Utc>,
+) -> Vec<WorkerInfo> {
+    let mut workers: BTreeMap<String, WorkerInfo> = rows
+        .into_iter()
+        .map(|row| (row.id.clone(), worker_info(row, now)))
+        .collect();
+    for heartbeat in heartbeats {
+        workers.insert(heartbeat.id.clone(), heartbeat_worker_info(heartbeat, now));
+    }
+    workers.into_values().collect()
+}
+
+/// Deletes worker rows last seen more than [`PRUNE_AFTER_SECS`] before
+/// `now`. Returns how many were removed.
+pub async fn prune_stale_workers<C: ConnectionTrait>(
+    conn: &C,
+    now: DateTime<Utc>,
+) -> Result<u64, DbErr> {
+    let cutoff = now - chrono::Duration::seconds(PRUNE_AFTER_SECS);
+    let result = worker::Entity::delete_many()
+        .filter(worker::Column::LastSeen.lt(cutoff))
+        .exec(conn)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// Reports a recorded worker whose heartbeat key is gone, so always stale.
+pub fn worker_info(row: worker::Model, now: DateTime<Utc>) -> WorkerInfo {
+    let elapsed = (now - row.last_seen).num_seconds();
+    WorkerInfo {
+        id: row.id,
+        started_at: row.started_at,
+        last_seen: row.last_seen,
+        seconds_since_last_seen: elapsed.max(0) as u64,
+        stale: true,
+        in_flight: row.in_flight.max(0) as u32,
+        max_concurrency: row.max_concurrency.map(|v| v.max(0) as u32),
+        sandbox_backend: row.sandbox_backend,
+        version: row.version,
+        hostname: row.hostname,
+        ip_addresses: serde_json::from_value(row.ip_addresses).unwrap_or_default(),
+        os: row.os,
+        arch: row.arch,
+        cpu_count: row.cpu_count.map(|v| v.max(0) as u32),
+        pid: row.pid.map(|v| v.max(0) as u32),
+    }
+}
+
+pub fn heartbeat_worker_info(heartbeat: WorkerHeartbeat, now: DateTime<Utc>) -> WorkerInfo {
+    let elapsed = (now - heartbeat.last_seen).num_seconds();
+    WorkerInfo {
+        id: heartbeat.id,
+        started_at: heartbeat.started_at,
+        last_seen: heartbeat.last_seen,
+        seconds_since_last_seen: elapsed.max(0) as u64,
+        stale: is_stale(heartbeat.last_seen, now),
+        in_flight: heartbeat.in_flight,
+        max_concurrency: heartbeat.max_concurrency,
+        sandbox_backend: heartbeat.sandbox_backend,
+        version: heartbeat.version,
+        hostname: heartbeat.hostname,
+        ip_addresses: heartbeat.ip_addresses,
+        os: heartbeat.os,
+        arch: heartbeat.arch,
+        cpu_count: heartbeat.cpu_count,
+        pid: heartbeat.pid,
+    }
+}
+
+/// Records `judge_epoch` as the newest dispatch of `submission_id`, so
+/// workers skip operations still queued for an earlier one. Errors are
+/// logged; result writes are epoch-guarded regardless.
+pub async fn publish_judge_epoch(client: &redis::Client, submission_id: i32, judge_epoch: i32) {
+    let result: Result<i64, redis::RedisError> = async {
+        let mut conn = client.get_multiplexed_async_connection().await?;
+        redis::cmd("EVAL")
+            .arg(RAISE_EPOCH_SCRIPT)
+            .arg(1)
+            .arg(format!("{JUDGE_EPOCH_KEY_PREFIX}{submission_id}"))
+            .arg(judge_epoch)
+            .arg(JUDGE_EPOCH_TTL_SECS)
+            .query_async(&mut conn)
+            .await
+    }
+    .await;
+    if let Err(e) = result {
+        warn!(submission_id, judge_epoch, error = %e, "Failed to publish judge epoch");
+    }
+}
+
+/// Reads all live heartbeats from Redis. Errors are logged and yield an
+/// empty list so admin endpoints degrade instead of failing.
+pub async fn read_heartbeats(client: &redis::Client) -> Vec<WorkerHeartbeat> {
+    let mut conn = match client.get_multiplexed_async_connection().await {
+        Ok(c) => c,
+        Err(e) => {
+            warn!(error = %e, "Failed to connect to Redis for worker heartbeats");
+            return Vec::new();
+        }
+    };
+
+    let pattern = format!("{HEARTBEAT_KEY_PREFIX}*");
+    let keys: Vec<String> = match conn.scan_match::<&str, String>(&pattern).await {
+        Ok(mut iter) => {
+            let mut acc: Vec<String> = Vec::new();
+            while let Some(item) = iter.next_item().await {
+                match item {
+                    Ok(key) => acc.push(key),
+                    Err(e) => {
+                        warn!(error = %e, "Worker heartbeat SCAN entry failed");
+                        return Vec::new();
+                    }
+                }
+            }
+            acc
+        }
+        Err(e) => {
+            warn!(error = %e, "Worker heartbeat SCAN failed");
+            return Vec::new();
+        }
+    };
+
+    if keys.is_empty() {
+        return Vec::new();
+    }
+
+    let values: Vec<Option<String>> = match conn.mget(&keys).await {
+        Ok(v) => v,
+        Err(e) => {
+            warn!(error = %e, "Worker heartbeat MGET failed");
+            return Vec::new();
+        }
+    };
+
+    values
+        .into_iter()
+        .filter_map(|v| v.and_then(|s| serde_json::from_str::<WorkerHeartbeat>(&s).ok()))
+        .collect()
+}
+
+/// Records every live heartbeat into the `worker` table. Returns the
+/// heartbeats that were read so callers can fall back to them if the
+/// database is unavailable.
+pub async fn sync_heartbeats<C: ConnectionTrait>(
+    conn: &C,
+    client: &redis::Client,
+) -> Vec<WorkerHeartbeat> {
+    let heartbeats = read_heartbeats(client).await;
+    for heartbeat in &heartbeats {
+        if let Err(e) = record_heartbeat(conn, heartbeat).await {
+            warn!(worker_id = %heartbeat.id, error = %e, "Failed to record worker heartbeat");
+        }
+    }
+    heartbeats
+}
+
+/// Periodically copies Redis heartbeats into the `worker` table so the
+/// server keeps a `last_seen` for workers even after their key expires, and
+/// prunes rows of workers gone for longer than [`PRUNE_AFTER_SECS`].
+pub async fn run_heartbeat_recorder(db: DatabaseConnection, client: std::sync::Arc<redis::Client>) {
+    info!(
+        interval_secs = RECORD_INTERVAL.as_secs(),
+        "Starting worker heartbeat recorder"
+    );
+
+    let mut interval = tokio::time::interval(RECORD_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        sync_heartbeats(&db, &client).await;
+        match prune_stale_workers(&db, Utc::now()).await {
+            Ok(0) => {}
+            Ok(pruned) => info!(pruned, "Pruned stale worker rows"),
+            Err(e) => warn!(error = %e, "Failed to prune stale worker rows"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heartbeat(last_seen: DateTime<Utc>) -> WorkerHeartbeat {
+        WorkerHeartbeat {
+            id: "worker-1".into(),
+            started_at: last_seen - chrono::Duration::minutes(5),
+            last_seen,
+            in_flight: 2,
+            max_concurrency: Some(4),
+            sandbox_backend: "isolate".into(),
+            version: "0.1.0".into(),
+            hostname: Some("lab-pc-01".into()),
+            ip_addresses: vec!["10.0.0.7".into()],
+            os: Some("linux".into()),
+            arch: Some("x86_64".into()),
+            cpu_count: Some(8),
+            pid: Some(42),
+        }
+    }
+
+    #[test]
+    fn test_is_stale_threshold() {
+        let now = Utc::now();
+        assert!(!is_stale(now, now));
+        assert!(!is_stale(
+            now - chrono::Duration::seconds(STALE_AFTER_SECS),
+            now
+        ));
+        assert!(is_stale(
+            now - chrono::Duration::seconds(STALE_AFTER_SECS + 1),
+            now
+        ));
+    }
+
+    #[test]
+    fn test_worker_info_reports_stale_row() {
+        let now = Utc::now();
+        let row = worker::Model {
+            id: "worker-1".into(),
+            started_at: now - chrono::Duration::hours(1),
+            last_seen: now - chrono::Duration::minutes(2),
+            in_flight: 1,
+            max_concurrency: Some(4),
+            sandbox_backend: "isolate".into(),
+            version: "0.1.0".into(),
+            hostname: None,
+            ip_addresses: serde_json::json!(["10.0.0.7"]),
+            os: None,
+            arch: None,
+            cpu_count: None,
+            pid: None,
+            created_at: now - chrono::Duration::hours(1),
+        };
+        let info = worker_info(row, now);
+        assert!(info.stale);
+        assert_eq!(info.seconds_since_last_seen, 120);
+        assert_eq!(info.ip_addresses, vec!["10.0.0.7".to_string()]);
+    }
+
+    #[test]
+    fn test_heartbeat_worker_info_fresh() {
+        let now = Utc::now();
+        let info = heartbeat_worker_info(heartbeat(now), now);
+        assert!(!info.stale);
+        assert_eq!(info.in_flight, 2);
+        assert_eq!(info.max_concurrency, Some(4));
+    }
+
+    #[test]
+    fn test_merge_workers_takes_liveness_from_heartbeats() {
+        let now = Utc::now();
+        let row = |id: &str| worker::Model {
+            id: id.into(),
+            started_at: now - chrono::Duration::hours(1),
+            last_seen: now - chrono::Duration::seconds(1),
+            in_flight: 0,
+            max_concurrency: None,
+            sandbox_backend: "isolate".into(),
+            version: "0.1.0".into(),
+            hostname: None,
+            ip_addresses: serde_json::json!([]),
+            os: None,
+            arch: None,
+            cpu_count: None,
+            pid: None,
+            created_at: now - chrono::Duration::hours(1),
+        };
+        let mut live = heartbeat(now);
+        live.id = "worker-b".into();
+        let mut unrecorded = heartbeat(now);
+        unrecorded.id = "worker-c".into();
+
+        let workers = merge_workers(
+            vec![row("worker-a"), row("worker-b")],
+            vec![unrecorded, live],
+            now,
+        );
+
+        let summary: Vec<(&str, bool, u32)> = workers
+            .iter()
+            .map(|w| (w.id.as_str(), w.stale, w.in_flight))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("worker-a", true, 0),
+                ("worker-b", false, 2),
+                ("worker-c", false, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heartbeat_deserializes_legacy_payload() {
+        let legacy = r#"{
+            "id":"old-worker",
+            "started_at":"2026-05-01T00:00:00Z",
+            "last_seen":"2026-05-01T00:00:05Z",
+            "in_flight":0,
+            "max_concurrency":null,
+            "sandbox_backend":"isolate",
+            "version":"0.1.0"
+        }"#;
+        let hb: WorkerHeartbeat = serde_json::from_str(legacy).unwrap();
+        assert_eq!(hb.id, "old-worker");
+        assert!(hb.ip_addresses.is_empty());
+    }
+}
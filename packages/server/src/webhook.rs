@@ -0,0 +1,258 @@
+//! Signed HTTP callbacks that push judge results to external systems.
+//!
+//! Every configured URL gets a JSON POST per finalized submission, sent to
+//! all URLs at once. Each request carries a Unix timestamp and an
+//! HMAC-SHA256 of `<timestamp>.<body>` keyed by `webhook.secret`, so
+//! receivers can verify it came from this server and reject replays.
+//! Nothing is sent while the secret is empty. Transport errors, 429 and
+//! 5xx responses are retried with exponential backoff; deliveries that
+//! never succeed are recorded in the dead-letter queue, from where an admin
+//! can retry them.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use common::retry::RetryAttempt;
+use common::{DlqEnvelope, DlqErrorCode, DlqMessageType};
+use hmac::{Hmac, Mac};
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tracing::{error, info, warn};
+
+use crate::config::WebhookConfig;
+use crate::dlq::DlqService;
+use crate::entity::submission;
+
+pub const SIGNATURE_HEADER: &str = "x-broccoli-signature";
+pub const TIMESTAMP_HEADER: &str = "x-broccoli-timestamp";
+pub const EVENT_HEADER: &str = "x-broccoli-event";
+pub const JUDGE_RESULT_EVENT: &str = "submission.judged";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JudgeResultPayload {
+    pub event: String,
+    pub submission_id: i32,
+    pub user_id: i32,
+    pub problem_id: i32,
+    pub contest_id: Option<i32>,
+    pub status: String,
+    pub verdict: Option<String>,
+    pub score: Option<f64>,
+    /// CPU time in milliseconds.
+    pub time_used: Option<i32>,
+    /// Peak memory in kilobytes.
+    pub memory_used: Option<i32>,
+    pub judge_epoch: i32,
+    pub judged_at: Option<DateTime<Utc>>,
+}
+
+impl From<&submission::Model> for JudgeResultPayload {
+    fn from(sub: &submission::Model) -> Self {
+        Self {
+            event: JUDGE_RESULT_EVENT.to_string(),
+            submission_id: sub.id,
+            user_id: sub.user_id,
+            problem_id: sub.problem_id,
+            contest_id: sub.contest_id,
+            status: sub.status.to_string(),
+            verdict: sub.verdict.as_ref().map(|v| v.to_string()),
+            score: sub.score,
+            time_used: sub.time_used,
+            memory_used: sub.memory_used,
+            judge_epoch: sub.judge_epoch,
+            judged_at: sub.judged_at,
+        }
+    }
+}
+
+/// Value of [`SIGNATURE_HEADER`] for `body` sent at `timestamp`:
+/// `sha256=<hex HMAC of "<timestamp>.<body>">`.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+enum DeliveryFailure {
+    Exhausted(Vec<RetryAttempt>),
+    Rejected(Vec<RetryAttempt>),
+}
+
+/// Delivers `payload` to every configured URL concurrently, dead-lettering
+/// the ones that fail. Returns once all deliveries have finished; callers
+/// on a request path should spawn it.
+pub async fn deliver_judge_result(
+    db: &DatabaseConnection,
+    config: &WebhookConfig,
+    payload: &JudgeResultPayload,
+) {
+    if config.urls.is_empty() {
+        return;
+    }
+    let Some(client) = prepare(config) else {
+        return;
+    };
+    futures::future::join_all(config.urls.iter().map(|url| {
+        let message_id = format!(
+            "webhook:{}:{}:{}",
+            payload.submission_id, payload.judge_epoch, url
+        );
+        deliver_and_record(db, &client, config, url, payload, message_id)
+    }))
+    .await;
+}
+
+/// Delivers `payload` to `url` again, e.g. when an admin retries a
+/// dead-lettered delivery. A failure is dead-lettered as a new entry.
+pub async fn redeliver_judge_result(
+    db: &DatabaseConnection,
+    config: &WebhookConfig,
+    url: &str,
+    payload: &JudgeResultPayload,
+) {
+    let Some(client) = prepare(config) else {
+        return;
+    };
+    let message_id = format!(
+        "webhook:{}:{}:{}:{}",
+        payload.submission_id,
+        payload.judge_epoch,
+        url,
+        Utc::now().timestamp_millis()
+    );
+    deliver_and_record(db, &client, config, url, payload, message_id).await;
+}
+
+/// HTTP client for a round of deliveries, or `None` when nothing may be
+/// sent.
+fn prepare(config: &WebhookConfig) -> Option<reqwest::Client> {
+    if config.secret.is_empty() {
+        error!("webhook.secret is empty, refusing to send unsigned webhooks");
+        return None;
+    }
+    match reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.timeout_secs))
+        .build()
+    {
+        Ok(client) => Some(client),
+        Err(e) => {
+            error!(error = %e, "Failed to build webhook HTTP client");
+            None
+        }
+    }
+}
+
+async fn deliver_and_record(
+    db: &DatabaseConnection,
+    client: &reqwest::Client,
+    config: &WebhookConfig,
+    url: &str,
+    payload: &JudgeResultPayload,
+    message_id: String,
+) {
+    let body = match serde_json::to_vec(payload) {
+        Ok(body) => body,
+        Err(e) => {
+            error!(error = %e, "Failed to serialize webhook payload");
+            return;
+        }
+    };
+    let failure = match deliver(client, url, &body, config).await {
+        Ok(()) => {
+            info!(submission_id = payload.submission_id, url = %url, "Webhook delivered");
+            return;
+        }
+        Err(failure) => failure,
+    };
+    let (error_code, retry_history) = match failure {
+        DeliveryFailure::Exhausted(history) => (DlqErrorCode::MaxRetriesExceeded, history),
+        DeliveryFailure::Rejected(history) => (DlqErrorCode::DeliveryRejected, history),
+    };
+    let error_message = retry_history
+        .last()
+        .map(|attempt| attempt.error.clone())
+        .unwrap_or_default();
+    warn!(
+        submission_id = payload.submission_id,
+        url = %url,
+        error = %error_message,
+        "Webhook delivery failed, sending to DLQ"
+    );
+    let envelope = DlqEnvelope {
+        message_id,
+        message_type: DlqMessageType::WebhookDelivery,
+        submission_id: Some(payload.submission_id),
+        payload: serde_json::json!({ "url": url, "body": payload }),
+        error_code,
+        error_message,
+        retry_history,
+    };
+    if let Err(e) = DlqService::new(db).send_to_dlq(&envelope).await {
+        error!(error = %e, url = %url, "Failed to record webhook delivery in DLQ");
+    }
+}
+
+async fn deliver(
+    client: &reqwest::Client,
+    url: &str,
+    body: &[u8],
+    config: &WebhookConfig,
+) -> Result<(), DeliveryFailure> {
+    let max_attempts = config.max_attempts.max(1);
+    let mut history = Vec::new();
+    for attempt in 1..=max_attempts {
+        let timestamp = Utc::now().timestamp();
+        let response = client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp)
+            .header(SIGNATURE_HEADER, sign(&config.secret, timestamp, body))
+            .header(EVENT_HEADER, JUDGE_RESULT_EVENT)
+            .body(body.to_vec())
+            .send()
+            .await;
+        let retryable = match response {
+            Ok(resp) if resp.status().is_success() => return Ok(()),
+            Ok(resp) => {
+                let status = resp.status();
+                history.push(RetryAttempt::new(attempt, format!("HTTP {status}")));
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(e) => {
+                history.push(RetryAttempt::new(attempt, e.to_string()));
+                true
+            }
+        };
+        if !retryable {
+            return Err(DeliveryFailure::Rejected(history));
+        }
+        if attempt < max_attempts {
+            let delay = config
+                .retry_delay_ms
+                .saturating_mul(1 << (attempt - 1).min(16));
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+    }
+    Err(DeliveryFailure::Exhausted(history))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_covers_timestamp_and_body() {
+        assert_eq!(
+            sign("Jefe", 1_700_000_000, b"what do ya want for nothing?"),
+            "sha256=1cdd0650c8be1cb0974b1788d458b1e781206cfef59b85faafc582d2e182c57e"
+        );
+        assert_ne!(
+            sign("Jefe", 1_700_000_001, b"what do ya want for nothing?"),
+            sign("Jefe", 1_700_000_000, b"what do ya want for nothing?")
+        );
+    }
+}
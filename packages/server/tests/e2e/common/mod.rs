@@ -5,7 +5,7 @@ use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 use common::worker::{Executor, Task, TaskResult};
-use mq::{BrokerMessage, MqConfig, init_mq};
+use mq::{BrokerMessage, ConsumeConfig, MqConfig, init_mq};
 use plugin_core::config::PluginConfig;
 use reqwest::Client;
 use sea_orm::{
@@ -25,8 +25,9 @@ use worker::models::operation::sandbox::isolate::IsolateSandboxManager;
 use worker::models::operation::sandbox::mock::MockSandboxManager;
 
 use server::config::{
-    AppConfig, AuthConfig, BlobStoreConfig, BootstrapConfig, CorsConfig, DatabaseConfig,
-    MqAppConfig, ServerConfig, SubmissionConfig,
+    AppConfig, AuthConfig, BlobStoreConfig, BootstrapConfig, ContestConfig, CorsConfig,
+    DatabaseConfig, MqAppConfig, PaginationConfig, ProblemConfig, ServerConfig, SubmissionConfig,
+    WebhookConfig,
 };
 use server::consumers::consume_operation_results;
 use server::entity::{user, user_role};
@@ -413,6 +414,9 @@ impl E2eTestApp {
                 trusted_proxies: vec![],
                 rate_limit_auth: false,
                 id: String::new(),
+                standings_cache_ttl_secs: 5,
+                pagination: PaginationConfig::default(),
+                plugin_asset_max_age_secs: 0,
             },
             database: DatabaseConfig {
                 url: db_url.clone(),
@@ -427,6 +431,8 @@ impl E2eTestApp {
                 ..Default::default()
             },
             submission: SubmissionConfig::default(),
+            problem: ProblemConfig::default(),
+            contest: ContestConfig::default(),
             storage: BlobStoreConfig::default(),
             mq: MqAppConfig {
                 enabled: true,
@@ -440,6 +446,7 @@ impl E2eTestApp {
             observability: common::config::ObservabilityConfig::default(),
             batch_max_age_secs: 600,
             bootstrap: BootstrapConfig::default(),
+            webhook: WebhookConfig::default(),
         };
 
         let contest_type_registry: ContestTypeRegistry = Arc::new(RwLock::new(HashMap::new()));
@@ -488,6 +495,9 @@ impl E2eTestApp {
                 hook_registry: server::hooks::new_shared_registry(),
             },
             device_codes: Arc::new(dashmap::DashMap::new()),
+            standings_cache: server::standings_cache::StandingsCache::new(
+                std::time::Duration::from_secs(5),
+            ),
             metrics: e2e_metrics.clone(),
             prometheus_registry: e2e_prom_registry,
         };
@@ -511,7 +521,14 @@ impl E2eTestApp {
             let consumer_waiters = operation_waiters.clone();
             let consumer_queue = result_queue.clone();
             result_consumer_handle_opt = Some(tokio::spawn(async move {
-                consume_operation_results(consumer_mq, consumer_waiters, consumer_queue).await;
+                consume_operation_results(
+                    consumer_mq,
+                    consumer_waiters,
+                    consumer_queue,
+                    ConsumeConfig::default(),
+                    1024,
+                )
+                .await;
             }));
 
             let sandbox_manager = create_sandbox_manager();
@@ -546,6 +563,7 @@ impl E2eTestApp {
                                             success: false,
                                             output: serde_json::Value::String(err_str.clone()),
                                             error: Some(err_str),
+                                            worker_id: None,
                                         }
                                     }
                                 };
@@ -584,6 +602,7 @@ impl E2eTestApp {
                     submission_fn: "noop".into(),
                     code_run_fn: "noop".into(),
                     filter_submission_fn: None,
+                    summed_scores: true,
                 },
             );
         }
@@ -877,6 +896,14 @@ impl E2eTestApp {
         res.id()
     }
 
+    /// Creates a problem with a single test case so submissions to it are
+    /// accepted under the default `submission.no_test_cases` policy.
+    pub async fn create_problem_with_test_case(&self, token: &str, title: &str) -> i32 {
+        let problem_id = self.create_problem(token, title).await;
+        self.create_test_case(problem_id, token).await;
+        problem_id
+    }
+
     pub async fn create_test_case(&self, problem_id: i32, token: &str) -> i32 {
         self.create_test_case_with(problem_id, "5\n1 2 3 4 5", "15", 10, true, token)
             .await
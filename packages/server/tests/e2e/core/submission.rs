@@ -15,7 +15,9 @@ mod submission_creation {
             .create_authenticated_user("sub_cr2", "password123")
             .await;
 
-        let pid = app.create_problem(&admin, "Submit Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "Submit Problem")
+            .await;
 
         let res = app
             .post_with_token(
@@ -42,7 +44,9 @@ mod submission_creation {
             .create_authenticated_user("sub_cr4", "password123")
             .await;
 
-        let pid = app.create_problem(&admin, "Empty Files Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "Empty Files Problem")
+            .await;
 
         let res = app
             .post_with_token(
@@ -69,7 +73,9 @@ mod submission_creation {
             .create_authenticated_user("sub_cr6", "password123")
             .await;
 
-        let pid = app.create_problem(&admin, "No Lang Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "No Lang Problem")
+            .await;
 
         let res = app
             .post_with_token(
@@ -96,7 +102,9 @@ mod submission_creation {
             .create_authenticated_user("sub_cr8", "password123")
             .await;
 
-        let pid = app.create_problem(&admin, "Empty Content Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "Empty Content Problem")
+            .await;
 
         let res = app
             .post_with_token(
@@ -123,7 +131,9 @@ mod submission_creation {
             .create_authenticated_user("sub_cr10", "password123")
             .await;
 
-        let pid = app.create_problem(&admin, "Traversal Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "Traversal Problem")
+            .await;
 
         let res = app
             .post_with_token(
@@ -147,7 +157,9 @@ mod submission_creation {
             .create_user_with_role("sub_cr11", "password123", "admin")
             .await;
 
-        let pid = app.create_problem(&admin, "No Auth Submit").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "No Auth Submit")
+            .await;
 
         let res = app
             .post_without_token(
@@ -176,7 +188,9 @@ mod submission_retrieval {
             .create_authenticated_user("sub_get2", "password123")
             .await;
 
-        let pid = app.create_problem(&admin, "Get Sub Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "Get Sub Problem")
+            .await;
         let sid = app
             .create_submission(pid, &user, "cpp", "int main() {}")
             .await;
@@ -202,7 +216,9 @@ mod submission_retrieval {
             .create_authenticated_user("sub_get5", "password123")
             .await;
 
-        let pid = app.create_problem(&admin, "Hidden Sub Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "Hidden Sub Problem")
+            .await;
         let sid = app
             .create_submission(pid, &user1, "cpp", "int main() {}")
             .await;
@@ -225,7 +241,9 @@ mod submission_retrieval {
             .create_authenticated_user("sub_get7", "password123")
             .await;
 
-        let pid = app.create_problem(&admin, "Admin View Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "Admin View Problem")
+            .await;
         let sid = app
             .create_submission(pid, &user, "cpp", "int main() {}")
             .await;
@@ -268,7 +286,9 @@ mod submission_listing {
             .create_authenticated_user("sub_ls3", "password123")
             .await;
 
-        let pid = app.create_problem(&admin, "List Sub Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "List Sub Problem")
+            .await;
         app.create_submission(pid, &user1, "cpp", "int main() { return 0; }")
             .await;
         app.create_submission(pid, &user2, "cpp", "int main() { return 1; }")
@@ -291,7 +311,9 @@ mod submission_listing {
             .create_authenticated_user("sub_ls5", "password123")
             .await;
 
-        let pid = app.create_problem(&admin, "Admin List Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "Admin List Problem")
+            .await;
         app.create_submission(pid, &user, "cpp", "int main() { return 0; }")
             .await;
         app.create_submission(pid, &admin, "cpp", "int main() { return 1; }")
@@ -311,8 +333,8 @@ mod submission_listing {
             .create_user_with_role("sub_ls6", "password123", "admin")
             .await;
 
-        let p1 = app.create_problem(&admin, "Filter P1").await;
-        let p2 = app.create_problem(&admin, "Filter P2").await;
+        let p1 = app.create_problem_with_test_case(&admin, "Filter P1").await;
+        let p2 = app.create_problem_with_test_case(&admin, "Filter P2").await;
         app.create_submission(p1, &admin, "cpp", "int main() { return 0; }")
             .await;
         app.create_submission(p2, &admin, "cpp", "int main() { return 1; }")
@@ -335,7 +357,9 @@ mod submission_listing {
             .create_user_with_role("sub_ls7", "password123", "admin")
             .await;
 
-        let pid = app.create_problem(&admin, "Filter Lang Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "Filter Lang Problem")
+            .await;
         app.create_submission(pid, &admin, "cpp", "int main() { return 0; }")
             .await;
         app.create_submission(pid, &admin, "python3", "print('hello')")
@@ -358,7 +382,9 @@ mod submission_listing {
             .create_user_with_role("sub_ls8", "password123", "admin")
             .await;
 
-        let pid = app.create_problem(&admin, "Paginate Problem").await;
+        let pid = app
+            .create_problem_with_test_case(&admin, "Paginate Problem")
+            .await;
         for _ in 0..3 {
             app.create_submission(pid, &admin, "cpp", "int main() { return 0; }")
                 .await;
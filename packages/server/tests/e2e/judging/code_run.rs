@@ -248,6 +248,50 @@ async fn code_run_max_test_cases_validation() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires a non-mock judge sandbox and C++ toolchain"]
+async fn code_run_returns_program_stdout_for_custom_input() {
+    if skip_with_mock_sandbox() {
+        return;
+    }
+
+    let app = E2eTestApp::spawn().await;
+    let admin = app
+        .create_user_with_role("cr_admin_stdout", "pass1234", "admin")
+        .await;
+
+    let problem_id = app.create_problem(&admin, "Code Run Stdout").await;
+
+    let res = app
+        .post_with_token(
+            &format!("/api/v1/problems/{problem_id}/code-runs"),
+            &json!({
+                "files": [{"filename": "main.cpp", "content": CPP_ECHO}],
+                "language": "cpp",
+                "custom_test_cases": [{"input": "custom input line"}],
+            }),
+            &admin,
+        )
+        .await;
+    assert_eq!(res.status, 201, "create code run failed: {}", res.text);
+    let cr_id = res.id();
+
+    let terminal = app.wait_for_code_run_terminal(cr_id, &admin, 60).await;
+    assert_eq!(
+        terminal.body["status"], "Judged",
+        "code run should judge cleanly: {}",
+        terminal.text
+    );
+
+    let tcrs = terminal.body["result"]["test_case_results"]
+        .as_array()
+        .expect("judged code run should have test case results");
+    assert_eq!(tcrs.len(), 1);
+    let stdout = tcrs[0]["stdout"].as_str().unwrap_or_default();
+    assert_eq!(stdout.trim(), "custom input line", "{}", terminal.text);
+    assert!(tcrs[0]["time_used"].is_number(), "{}", terminal.text);
+}
+
 const CPP_ECHO: &str = r#"
 #include <iostream>
 #include <string>
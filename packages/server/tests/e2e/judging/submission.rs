@@ -364,6 +364,97 @@ int main() {
     assert_eq!(res.body["verdict"].as_str(), Some("Accepted"));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires a non-mock judge sandbox and C++ toolchain"]
+async fn two_phase_contest_judges_pretests_then_full_set_in_system_test() {
+    if skip_with_mock_sandbox() {
+        return;
+    }
+
+    let app = E2eTestApp::spawn().await;
+    let admin = app
+        .create_user_with_role("two_phase_admin1", "pass1234", "admin")
+        .await;
+    let user = app
+        .create_authenticated_user("two_phase_user1", "pass1234")
+        .await;
+
+    let problem_id = app.create_problem(&admin, "Two Phase").await;
+    let pretest = app
+        .create_test_case_with(problem_id, "1\n", "1\n", 10, false, &admin)
+        .await;
+    app.create_test_case_with(problem_id, "2\n", "2\n", 10, false, &admin)
+        .await;
+    app.create_test_case_with(problem_id, "3\n", "3\n", 10, false, &admin)
+        .await;
+    let res = app
+        .patch_with_token(
+            &format!("/api/v1/problems/{problem_id}/test-cases/{pretest}"),
+            &json!({ "is_pretest": true }),
+            &admin,
+        )
+        .await;
+    assert_eq!(res.status, 200, "mark pretest failed: {}", res.text);
+
+    let contest_id = app
+        .create_contest(&admin, "Two Phase Round", true, true)
+        .await;
+    let res = app
+        .patch_with_token(
+            &format!("/api/v1/contests/{contest_id}"),
+            &json!({ "two_phase": true }),
+            &admin,
+        )
+        .await;
+    assert_eq!(res.status, 200, "enable two_phase failed: {}", res.text);
+    app.add_problem_to_contest(contest_id, problem_id, &admin)
+        .await;
+    app.register_for_contest(contest_id, &user).await;
+
+    let sub_id = app
+        .create_contest_submission(contest_id, problem_id, &user, "cpp", CPP_SUM)
+        .await;
+    let res = app.wait_for_submission_terminal(sub_id, &admin, 60).await;
+    let tcrs = res.body["result"]["test_case_results"].as_array().unwrap();
+    let judged: Vec<i64> = tcrs
+        .iter()
+        .filter_map(|r| r["test_case_id"].as_i64())
+        .collect();
+    assert_eq!(
+        judged,
+        vec![pretest as i64],
+        "only the pretest should run during the contest: {}",
+        res.text
+    );
+
+    let res = app
+        .patch_with_token(
+            &format!("/api/v1/contests/{contest_id}"),
+            &json!({ "end_time": chrono::Utc::now().to_rfc3339() }),
+            &admin,
+        )
+        .await;
+    assert_eq!(res.status, 200, "end contest failed: {}", res.text);
+    let res = app
+        .post_with_token(
+            &format!("/api/v1/contests/{contest_id}/system-test"),
+            &json!({}),
+            &admin,
+        )
+        .await;
+    assert_eq!(res.status, 200, "system test failed: {}", res.text);
+    assert_eq!(res.body["queued"], 1);
+
+    let res = app.wait_for_submission_terminal(sub_id, &admin, 60).await;
+    let tcrs = res.body["result"]["test_case_results"].as_array().unwrap();
+    assert_eq!(
+        tcrs.len(),
+        3,
+        "system test should judge the full set: {}",
+        res.text
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn concurrent_submissions_all_complete() {
     let app = E2eTestApp::spawn().await;
@@ -2,7 +2,7 @@ use chrono::Utc;
 use common::{SubmissionStatus, Verdict};
 use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
 use serde_json::json;
-use server::entity::{plugin_storage, submission, user};
+use server::entity::{submission, submission_judgement, test_case_result, user};
 
 use crate::common::E2eTestApp;
 
@@ -38,27 +38,6 @@ async fn seed_accepted_icpc_submission(
     .await
     .expect("insert ICPC submission");
 
-    plugin_storage::ActiveModel {
-        plugin_id: Set("icpc".into()),
-        collection: Set("default".into()),
-        key: Set(format!(
-            "standings:{contest_id}:{}:{problem_id}",
-            user_model.id
-        )),
-        data: Set(json!(
-            serde_json::to_string(&json!({
-                "attempts": 1,
-                "solved": true,
-                "solve_time_ms": 60_000
-            }))
-            .unwrap()
-        )),
-        created_at: Set(now),
-    }
-    .insert(&app.db)
-    .await
-    .expect("insert ICPC standings state");
-
     submission.id
 }
 
@@ -189,3 +168,273 @@ async fn icpc_config_penalty_minutes() {
         Some(true)
     );
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn icpc_standings_leave_out_submissions_made_after_a_freeze() {
+    let app = E2eTestApp::spawn().await;
+
+    let admin = app
+        .create_user_with_role("icpc_admin5", "password", "admin")
+        .await;
+    let contestant = app
+        .create_authenticated_user("icpc_user5", "password")
+        .await;
+
+    let problem_id = app.create_problem(&admin, "ICPC Problem 5").await;
+    let contest_id = app
+        .create_typed_contest(&admin, "ICPC Contest 5", "icpc", true, true)
+        .await;
+    app.add_problem_to_contest(contest_id, problem_id, &admin)
+        .await;
+    app.register_for_contest(contest_id, &contestant).await;
+
+    let res = app
+        .post_with_token(
+            &format!("/api/v1/contests/{contest_id}/freeze"),
+            &json!({}),
+            &admin,
+        )
+        .await;
+    assert_eq!(res.status, 200, "Freeze failed: {}", res.text);
+    seed_accepted_icpc_submission(&app, "icpc_user5", problem_id, contest_id).await;
+
+    let standings_path = format!("/api/v1/p/icpc/api/plugins/icpc/contests/{contest_id}/standings");
+    let res = app.get_with_token(&standings_path, &contestant).await;
+    assert_eq!(res.status, 200, "Standings request failed: {}", res.text);
+    assert_eq!(res.body["rows"][0]["solved"], 0, "{}", res.text);
+    let res = app.get_with_token(&standings_path, &admin).await;
+    assert_eq!(res.body["rows"][0]["solved"], 1, "{}", res.text);
+
+    let res = app
+        .post_with_token(
+            &format!("/api/v1/contests/{contest_id}/unfreeze"),
+            &json!({}),
+            &admin,
+        )
+        .await;
+    assert_eq!(res.status, 200, "Unfreeze failed: {}", res.text);
+    let res = app.get_with_token(&standings_path, &contestant).await;
+    assert_eq!(res.body["rows"][0]["solved"], 1, "{}", res.text);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn icpc_standings_withhold_delayed_results_from_contestants() {
+    let app = E2eTestApp::spawn().await;
+
+    let admin = app
+        .create_user_with_role("icpc_admin6", "password", "admin")
+        .await;
+    let contestant = app
+        .create_authenticated_user("icpc_user6", "password")
+        .await;
+
+    let problem_id = app.create_problem(&admin, "ICPC Problem 6").await;
+    let contest_id = app
+        .create_typed_contest(&admin, "ICPC Contest 6", "icpc", true, true)
+        .await;
+    app.add_problem_to_contest(contest_id, problem_id, &admin)
+        .await;
+    app.register_for_contest(contest_id, &contestant).await;
+
+    let res = app
+        .patch_with_token(
+            &format!("/api/v1/contests/{contest_id}/problems/{problem_id}"),
+            &json!({ "result_delay_secs": 300 }),
+            &admin,
+        )
+        .await;
+    assert_eq!(res.status, 200, "Update failed: {}", res.text);
+    seed_accepted_icpc_submission(&app, "icpc_user6", problem_id, contest_id).await;
+
+    let standings_path = format!("/api/v1/p/icpc/api/plugins/icpc/contests/{contest_id}/standings");
+    let res = app.get_with_token(&standings_path, &contestant).await;
+    assert_eq!(res.status, 200, "Standings request failed: {}", res.text);
+    assert_eq!(res.body["rows"][0]["solved"], 0, "{}", res.text);
+    let res = app.get_with_token(&standings_path, &admin).await;
+    assert_eq!(res.body["rows"][0]["solved"], 1, "{}", res.text);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn icpc_standings_csv_follows_the_plugin_board() {
+    let app = E2eTestApp::spawn().await;
+
+    let admin = app
+        .create_user_with_role("icpc_admin7", "password", "admin")
+        .await;
+    let alice = app
+        .create_authenticated_user("icpc_user7a", "password")
+        .await;
+    let bob = app
+        .create_authenticated_user("icpc_user7b", "password")
+        .await;
+
+    let problem_id = app.create_problem(&admin, "ICPC Problem 7").await;
+    let contest_id = app
+        .create_typed_contest(&admin, "ICPC Contest 7", "icpc", true, true)
+        .await;
+    app.add_problem_to_contest(contest_id, problem_id, &admin)
+        .await;
+    app.register_for_contest(contest_id, &alice).await;
+    app.register_for_contest(contest_id, &bob).await;
+    seed_accepted_icpc_submission(&app, "icpc_user7b", problem_id, contest_id).await;
+
+    let csv_path = format!("/api/v1/contests/{contest_id}/standings.csv");
+    let res = app.get_with_token(&csv_path, &alice).await;
+    assert_eq!(res.status, 200, "CSV request failed: {}", res.text);
+    let lines: Vec<&str> = res.text.lines().collect();
+    assert_eq!(
+        lines.len(),
+        2,
+        "contestant should only get their row: {}",
+        res.text
+    );
+    assert!(lines[1].contains("icpc_user7a"), "{}", res.text);
+
+    let res = app.get_with_token(&csv_path, &admin).await;
+    assert_eq!(res.status, 200, "CSV request failed: {}", res.text);
+    let lines: Vec<&str> = res.text.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", res.text);
+    assert!(lines[1].starts_with("1,icpc_user7b,1,"), "{}", res.text);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn icpc_my_status_matches_the_plugin_board() {
+    let app = E2eTestApp::spawn().await;
+
+    let admin = app
+        .create_user_with_role("icpc_admin8", "password", "admin")
+        .await;
+    let contestant = app
+        .create_authenticated_user("icpc_user8", "password")
+        .await;
+
+    let problem_id = app.create_problem(&admin, "ICPC Problem 8").await;
+    let contest_id = app
+        .create_typed_contest(&admin, "ICPC Contest 8", "icpc", true, true)
+        .await;
+    app.add_problem_to_contest(contest_id, problem_id, &admin)
+        .await;
+    app.register_for_contest(contest_id, &contestant).await;
+    seed_accepted_icpc_submission(&app, "icpc_user8", problem_id, contest_id).await;
+
+    let standings_path = format!("/api/v1/p/icpc/api/plugins/icpc/contests/{contest_id}/standings");
+    let res = app.get_with_token(&standings_path, &contestant).await;
+    assert_eq!(res.status, 200, "Standings request failed: {}", res.text);
+    let row = &res.body["rows"][0];
+
+    let status_path = format!("/api/v1/contests/{contest_id}/my-status");
+    let res = app.get_with_token(&status_path, &contestant).await;
+    assert_eq!(res.status, 200, "My-status request failed: {}", res.text);
+    assert_eq!(res.body["solved"], row["solved"], "{}", res.text);
+    assert_eq!(res.body["penalty"], row["penalty"], "{}", res.text);
+    let problem = &res.body["problems"][0];
+    assert_eq!(problem["problem_id"].as_i64(), Some(i64::from(problem_id)));
+    assert_eq!(problem["solved"], true, "{}", res.text);
+    assert_eq!(problem["attempts"].as_u64(), Some(1), "{}", res.text);
+    assert!(problem["first_accepted_at"].is_string(), "{}", res.text);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn icpc_problem_list_flags_follow_the_plugin_board() {
+    let app = E2eTestApp::spawn().await;
+
+    let admin = app
+        .create_user_with_role("icpc_admin9", "password", "admin")
+        .await;
+    let contestant = app
+        .create_authenticated_user("icpc_user9", "password")
+        .await;
+
+    let solved_id = app.create_problem(&admin, "ICPC Problem 9a").await;
+    let untouched_id = app.create_problem(&admin, "ICPC Problem 9b").await;
+    let contest_id = app
+        .create_typed_contest(&admin, "ICPC Contest 9", "icpc", true, true)
+        .await;
+    app.add_problem_to_contest_with_label(contest_id, solved_id, "A", &admin)
+        .await;
+    app.add_problem_to_contest_with_label(contest_id, untouched_id, "B", &admin)
+        .await;
+    app.register_for_contest(contest_id, &contestant).await;
+    seed_accepted_icpc_submission(&app, "icpc_user9", solved_id, contest_id).await;
+
+    let res = app
+        .get_with_token(
+            &format!("/api/v1/contests/{contest_id}/problems"),
+            &contestant,
+        )
+        .await;
+    assert_eq!(res.status, 200, "Problem list failed: {}", res.text);
+    for problem in res.body.as_array().unwrap() {
+        let solved = problem["problem_id"].as_i64() == Some(i64::from(solved_id));
+        assert_eq!(problem["solved"], solved, "{}", res.text);
+        assert_eq!(problem["attempted"], solved, "{}", res.text);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn icpc_submission_cannot_be_recomputed_from_its_rows() {
+    let app = E2eTestApp::spawn().await;
+
+    let admin = app
+        .create_user_with_role("icpc_admin10", "password", "admin")
+        .await;
+    let contestant = app
+        .create_authenticated_user("icpc_user10", "password")
+        .await;
+
+    let problem_id = app.create_problem(&admin, "ICPC Problem 10").await;
+    let contest_id = app
+        .create_typed_contest(&admin, "ICPC Contest 10", "icpc", true, true)
+        .await;
+    app.add_problem_to_contest(contest_id, problem_id, &admin)
+        .await;
+    app.register_for_contest(contest_id, &contestant).await;
+    let submission_id =
+        seed_accepted_icpc_submission(&app, "icpc_user10", problem_id, contest_id).await;
+
+    let judgement = submission_judgement::ActiveModel {
+        submission_id: Set(submission_id),
+        version: Set(1),
+        is_current: Set(true),
+        is_finalized: Set(true),
+        status: Set(SubmissionStatus::Judged),
+        verdict: Set(Some(Verdict::Accepted)),
+        score: Set(Some(1.0)),
+        judge_epoch: Set(1),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(&app.db)
+    .await
+    .expect("insert judgement");
+    for _ in 0..10 {
+        test_case_result::ActiveModel {
+            submission_id: Set(submission_id),
+            judgement_id: Set(Some(judgement.id)),
+            verdict: Set(Verdict::Accepted),
+            score: Set(1.0),
+            created_at: Set(Utc::now()),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert test case result");
+    }
+
+    let res = app
+        .post_with_token(
+            &format!("/api/v1/submissions/{submission_id}/recompute"),
+            &json!({}),
+            &admin,
+        )
+        .await;
+    assert_eq!(res.status, 409, "Recompute should be refused: {}", res.text);
+
+    let sub = submission::Entity::find_by_id(submission_id)
+        .one(&app.db)
+        .await
+        .expect("query submission")
+        .expect("submission should exist");
+    assert_eq!(sub.score, Some(1.0));
+    assert_eq!(sub.verdict, Some(Verdict::Accepted));
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
 
 use extism_pdk::{FnResult, host_fn, plugin_fn};
 use serde::{Deserialize, Serialize};
@@ -34,6 +35,10 @@ struct HostDbResponse {
 extern "ExtismHost" {
     fn store_set(input: String);
     fn store_get(input: String) -> String;
+    fn store_has(input: String) -> String;
+    fn store_set_many(collection: String, entries: String);
+    fn store_get_many(collection: String, keys: String) -> String;
+    fn store_keys(collection: String, prefix: String) -> String;
     fn db_execute(sql: String, args: String) -> String;
     fn db_query(sql: String, args: String) -> String;
 }
@@ -93,6 +98,75 @@ pub fn kv_read(input: String) -> FnResult<String> {
     })?)
 }
 
+#[plugin_fn]
+pub fn kv_exists(input: String) -> FnResult<String> {
+    let req: PluginHttpRequest = serde_json::from_str(&input)?;
+    let key = req.params.get("key").cloned().unwrap();
+
+    let raw = unsafe { store_has(serde_json::to_string(&serde_json::json!({ "key": key }))?)? };
+    let result: serde_json::Value = serde_json::from_str(&raw)?;
+    Ok(serde_json::to_string(&PluginHttpResponse {
+        status: 200,
+        body: Some(serde_json::json!({ "exists": result["exists"] })),
+    })?)
+}
+
+#[plugin_fn]
+pub fn kv_batch_set(input: String) -> FnResult<String> {
+    let req: PluginHttpRequest = serde_json::from_str(&input)?;
+    let collection = req.params.get("collection").cloned().unwrap();
+    let entries: Vec<serde_json::Value> = req
+        .body
+        .as_ref()
+        .and_then(|b| b.get("entries"))
+        .and_then(|e| e.as_object())
+        .map(|obj| {
+            obj.iter()
+                .map(|(k, v)| serde_json::json!({ "key": k, "value": v }))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let (status, body) =
+        match unsafe { store_set_many(collection, serde_json::to_string(&entries)?) } {
+            Ok(()) => (200, None),
+            Err(e) => (400, Some(serde_json::json!({ "error": e.to_string() }))),
+        };
+    Ok(serde_json::to_string(&PluginHttpResponse { status, body })?)
+}
+
+#[plugin_fn]
+pub fn kv_batch_get(input: String) -> FnResult<String> {
+    let req: PluginHttpRequest = serde_json::from_str(&input)?;
+    let collection = req.params.get("collection").cloned().unwrap();
+    let keys = req
+        .body
+        .as_ref()
+        .and_then(|b| b.get("keys").cloned())
+        .unwrap_or_else(|| serde_json::json!([]));
+
+    let raw = unsafe { store_get_many(collection, serde_json::to_string(&keys)?)? };
+    let result: serde_json::Value = serde_json::from_str(&raw)?;
+    Ok(serde_json::to_string(&PluginHttpResponse {
+        status: 200,
+        body: Some(result),
+    })?)
+}
+
+#[plugin_fn]
+pub fn kv_keys(input: String) -> FnResult<String> {
+    let req: PluginHttpRequest = serde_json::from_str(&input)?;
+    let collection = req.params.get("collection").cloned().unwrap();
+    let prefix = req.query.get("prefix").cloned().unwrap_or_default();
+
+    let raw = unsafe { store_keys(collection, prefix)? };
+    let result: serde_json::Value = serde_json::from_str(&raw)?;
+    Ok(serde_json::to_string(&PluginHttpResponse {
+        status: 200,
+        body: Some(result),
+    })?)
+}
+
 #[plugin_fn]
 pub fn sql_parameterized(input: String) -> FnResult<String> {
     let req: PluginHttpRequest = serde_json::from_str(&input)?;
@@ -127,3 +201,113 @@ pub fn sql_parameterized(input: String) -> FnResult<String> {
         body: Some(serde_json::json!({ "found": rows.len() })),
     })?)
 }
+
+/// Writes a terminal result for a submission through the SDK, the same way
+/// a contest plugin finishes judging, and reports the affected row count.
+#[plugin_fn]
+pub fn apply_result(input: String) -> FnResult<String> {
+    use broccoli_server_sdk::prelude::{Host, SubmissionStatus, SubmissionUpdate, Verdict};
+
+    let req: PluginHttpRequest = serde_json::from_str(&input)?;
+    let body = req.body.unwrap_or_default();
+    let submission_id: i32 = req.params.get("id").unwrap().parse()?;
+
+    let host = Host::new();
+    let affected = host
+        .submission
+        .update(&SubmissionUpdate {
+            status: Some(SubmissionStatus::Judged),
+            verdict: Some(Some(Verdict::Accepted)),
+            score: body["score"].as_f64(),
+            ..SubmissionUpdate::new(
+                submission_id,
+                body["judgement_id"].as_i64().unwrap() as i32,
+                body["judge_epoch"].as_i64().unwrap() as i32,
+            )
+        })
+        .map_err(|e| extism_pdk::Error::msg(e.to_string()))?;
+
+    Ok(serde_json::to_string(&PluginHttpResponse {
+        status: 200,
+        body: Some(serde_json::json!({ "affected": affected })),
+    })?)
+}
+
+#[plugin_fn]
+pub fn echo(input: String) -> FnResult<String> {
+    let req: PluginHttpRequest = serde_json::from_str(&input)?;
+    Ok(serde_json::to_string(&PluginHttpResponse {
+        status: 200,
+        body: req.body,
+    })?)
+}
+
+#[plugin_fn]
+pub fn greet(input: String) -> FnResult<String> {
+    let req: PluginHttpRequest = serde_json::from_str(&input)?;
+    let name = req
+        .body
+        .as_ref()
+        .and_then(|b| b.get("name"))
+        .and_then(|n| n.as_str())
+        .unwrap_or("stranger")
+        .to_string();
+    Ok(serde_json::to_string(&PluginHttpResponse {
+        status: 200,
+        body: Some(serde_json::json!({ "greeting": format!("Hello, {name}!") })),
+    })?)
+}
+
+/// Per-instance state that survives a trap unless the host rebuilds the instance.
+static CRASHES: AtomicU32 = AtomicU32::new(0);
+
+#[plugin_fn]
+pub fn crash(_input: String) -> FnResult<String> {
+    CRASHES.fetch_add(1, Ordering::SeqCst);
+    panic!("deliberate crash");
+}
+
+#[plugin_fn]
+pub fn crash_count(_input: String) -> FnResult<String> {
+    Ok(serde_json::to_string(&PluginHttpResponse {
+        status: 200,
+        body: Some(serde_json::json!({ "count": CRASHES.load(Ordering::SeqCst) })),
+    })?)
+}
+
+#[plugin_fn]
+pub fn spin(_input: String) -> FnResult<String> {
+    let mut counter: u64 = 0;
+    loop {
+        counter = std::hint::black_box(counter.wrapping_add(1));
+    }
+}
+
+/// Counts how often standings were actually computed (i.e. not served from
+/// the server's standings cache). Kept in plugin storage so the count is
+/// shared across pooled instances.
+#[plugin_fn]
+pub fn standings(input: String) -> FnResult<String> {
+    let req: PluginHttpRequest = serde_json::from_str(&input)?;
+    let contest_id = req.params.get("contest_id").cloned().unwrap();
+    let key = format!("standings_computed:{contest_id}");
+
+    let raw = unsafe { store_get(serde_json::to_string(&serde_json::json!({ "keys": [key] }))?)? };
+    let result: serde_json::Value = serde_json::from_str(&raw)?;
+    let computed = result["values"][&key]
+        .as_str()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(0)
+        + 1;
+
+    let store_input = serde_json::json!({
+        "entries": [{ "key": key, "value": computed.to_string() }],
+    });
+    unsafe {
+        store_set(serde_json::to_string(&store_input)?)?;
+    }
+    Ok(serde_json::to_string(&PluginHttpResponse {
+        status: 200,
+        body: Some(serde_json::json!({ "contest_id": contest_id, "computed": computed })),
+    })?)
+}
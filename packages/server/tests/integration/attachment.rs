@@ -428,6 +428,54 @@ mod attachment_download {
         assert_eq!(res.status().as_u16(), 304);
     }
 
+    #[tokio::test]
+    async fn ranged_download_returns_partial_content() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin15r", "pass1234", "admin")
+            .await;
+        let problem_id = app.create_problem(&token, "Range test").await;
+
+        let upload = app
+            .upload_attachment(
+                problem_id,
+                "video.bin",
+                b"0123456789".to_vec(),
+                None,
+                &token,
+            )
+            .await;
+        let ref_id = upload.body["id"].as_str().unwrap();
+        let url = format!(
+            "http://{}{}",
+            app.addr,
+            routes::attachment(problem_id, ref_id)
+        );
+
+        let res = app
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Range", "bytes=2-5")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 206);
+        assert_eq!(res.headers()["content-range"], "bytes 2-5/10");
+        assert_eq!(res.bytes().await.unwrap().as_ref(), b"2345");
+
+        let res = app
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Range", "bytes=20-")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 416);
+        assert_eq!(res.headers()["content-range"], "bytes */10");
+    }
+
     #[tokio::test]
     async fn download_returns_404_for_nonexistent_ref() {
         let app = TestApp::spawn().await;
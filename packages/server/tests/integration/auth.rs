@@ -364,6 +364,28 @@ mod authenticated_access {
         assert!(res.body["permissions"].is_array());
     }
 
+    #[tokio::test]
+    async fn effective_permissions_are_resolved_from_roles() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("perm_admin", "password123", "admin")
+            .await;
+        let contestant = app
+            .create_authenticated_user("perm_contestant", "password123")
+            .await;
+
+        let res = app.get_with_token(routes::PERMISSIONS, &admin).await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        let admin_perms = res.body["permissions"].as_array().unwrap();
+        assert!(admin_perms.contains(&json!("contest:manage")));
+
+        let res = app.get_with_token(routes::PERMISSIONS, &contestant).await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        let contestant_perms = res.body["permissions"].as_array().unwrap();
+        assert!(contestant_perms.contains(&json!("submission:submit")));
+        assert!(!contestant_perms.contains(&json!("contest:manage")));
+    }
+
     #[tokio::test]
     async fn request_without_token_is_rejected() {
         let app = TestApp::spawn().await;
@@ -105,6 +105,36 @@ mod code_run_creation {
         assert_eq!(res.status, 404);
         assert_eq!(res.body["code"], "NOT_FOUND");
     }
+
+    #[tokio::test]
+    async fn run_alias_creates_a_code_run_that_can_be_fetched() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+
+        let res = app
+            .post_with_token(
+                &routes::problem_run(problem_id),
+                &valid_code_run_body(),
+                &user_token,
+            )
+            .await;
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert_eq!(res.body["problem_id"], problem_id);
+        let run_id = res.id();
+
+        let res = app.get_with_token(&routes::run(run_id), &user_token).await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["id"], run_id);
+        let res = app
+            .get_with_token(&routes::code_run(run_id), &user_token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+    }
 }
 
 mod code_run_validation {
@@ -318,7 +348,9 @@ mod code_run_isolation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
 
@@ -379,7 +411,9 @@ mod code_run_isolation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
 
@@ -397,6 +431,115 @@ mod code_run_isolation {
     }
 }
 
+mod compile_only {
+    use super::*;
+
+    fn compile_only_body() -> serde_json::Value {
+        json!({
+            "files": [{"filename": "main.cpp", "content": "int main() {}"}],
+            "language": "cpp",
+            "judge_mode": "compile_only"
+        })
+    }
+
+    #[tokio::test]
+    async fn compile_only_run_needs_no_custom_test_cases() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+
+        let res = app
+            .post_with_token(
+                &routes::problem_code_runs(problem_id),
+                &compile_only_body(),
+                &user_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 201);
+        assert_eq!(res.body["judge_mode"], "compile_only");
+        assert_eq!(res.body["custom_test_cases"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn full_run_reports_full_judge_mode() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+
+        let res = app
+            .post_with_token(
+                &routes::problem_code_runs(problem_id),
+                &valid_code_run_body(),
+                &user_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 201);
+        assert_eq!(res.body["judge_mode"], "full");
+    }
+
+    #[tokio::test]
+    async fn compile_only_submission_is_accepted_outside_contests() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &compile_only_body(),
+                &admin_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 201);
+        assert_eq!(res.body["judge_mode"], "compile_only");
+    }
+
+    #[tokio::test]
+    async fn compile_only_contest_submission_is_rejected() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
+        let contest_id = app
+            .create_contest(&admin_token, "Test Contest", true, false)
+            .await;
+        app.add_problem_to_contest(contest_id, problem_id, &admin_token)
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        app.register_for_contest(contest_id, &user_token).await;
+
+        let res = app
+            .post_with_token(
+                &routes::contest_problem_submissions(contest_id, problem_id),
+                &compile_only_body(),
+                &user_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+    }
+}
+
 mod contest_code_runs {
     use super::*;
 
@@ -458,6 +601,46 @@ mod contest_code_runs {
         assert_eq!(res.status, 403);
     }
 
+    #[tokio::test]
+    async fn contest_code_run_rejects_language_outside_allowed_languages() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let contest_id = app
+            .create_contest(&admin_token, "Test Contest", true, false)
+            .await;
+        app.add_problem_to_contest(contest_id, problem_id, &admin_token)
+            .await;
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "allowed_languages": ["c"] }),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        app.register_for_contest(contest_id, &user_token).await;
+
+        let res = app
+            .post_with_token(
+                &routes::contest_problem_code_runs(contest_id, problem_id),
+                &valid_code_run_body(),
+                &user_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(
+            res.body["message"],
+            "Language 'cpp' is not allowed in this contest; allowed languages: c"
+        );
+    }
+
     #[tokio::test]
     async fn contest_code_run_returns_404_for_problem_not_in_contest() {
         let app = TestApp::spawn().await;
@@ -491,7 +674,9 @@ mod contest_code_runs {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
         let contest_id = app
             .create_contest(&admin_token, "Test Contest", true, true)
             .await;
@@ -534,3 +719,33 @@ mod contest_code_runs {
         assert_eq!(data[0]["id"], sub_res.body["id"]);
     }
 }
+
+mod rate_limiting {
+    use super::*;
+
+    #[tokio::test]
+    async fn code_runs_return_429_when_rate_limit_exceeded() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let body = valid_code_run_body();
+
+        for i in 0..10 {
+            let res = app
+                .post_with_token(&routes::problem_code_runs(problem_id), &body, &user_token)
+                .await;
+            assert_eq!(res.status, 201, "Code run {} failed", i + 1);
+        }
+
+        let res = app
+            .post_with_token(&routes::problem_code_runs(problem_id), &body, &user_token)
+            .await;
+
+        assert_eq!(res.status, 429);
+        assert_eq!(res.body["code"], "RATE_LIMITED");
+    }
+}
@@ -25,8 +25,9 @@ use tokio::sync::{Mutex, OnceCell, RwLock};
 
 use common::storage::config::create_blob_store;
 use server::config::{
-    AppConfig, AuthConfig, BlobStoreConfig, BootstrapConfig, CorsConfig, DatabaseConfig,
-    MqAppConfig, ServerConfig, SubmissionConfig,
+    AppConfig, AuthConfig, BlobStoreConfig, BootstrapConfig, ContestConfig, CorsConfig,
+    DatabaseConfig, MqAppConfig, PaginationConfig, ProblemConfig, ServerConfig, SubmissionConfig,
+    WebhookConfig,
 };
 use server::entity::{user, user_role};
 use server::manager::ServerManager;
@@ -226,6 +227,7 @@ pub mod routes {
     pub const REFRESH: &str = "/api/v1/auth/refresh";
     pub const LOGOUT: &str = "/api/v1/auth/logout";
     pub const ME: &str = "/api/v1/auth/me";
+    pub const PERMISSIONS: &str = "/api/v1/auth/permissions";
 
     pub const USERS: &str = "/api/v1/users";
 
@@ -233,6 +235,14 @@ pub mod routes {
         format!("/api/v1/users/{id}")
     }
 
+    pub fn user_reset_password(id: i32) -> String {
+        format!("/api/v1/users/{id}/reset-password")
+    }
+
+    pub fn user_status(id: i32) -> String {
+        format!("/api/v1/users/{id}/status")
+    }
+
     pub fn user_roles(id: i32) -> String {
         format!("/api/v1/users/{id}/roles")
     }
@@ -263,6 +273,14 @@ pub mod routes {
         format!("/api/v1/admin/plugins/{id}/disable")
     }
 
+    pub fn plugin_functions(id: &str) -> String {
+        format!("/api/v1/plugins/{id}/functions")
+    }
+
+    pub fn plugin_assets(id: &str) -> String {
+        format!("/api/v1/plugins/{id}/assets")
+    }
+
     pub fn plugin_proxy(id: &str, path: &str) -> String {
         let path = path.trim_start_matches('/');
         format!("/api/v1/p/{id}/{path}")
@@ -279,11 +297,28 @@ pub mod routes {
     }
 
     pub const PROBLEMS: &str = "/api/v1/problems";
+    pub const PRACTICE_PROBLEMS: &str = "/api/v1/practice/problems";
 
     pub fn problem(id: i32) -> String {
         format!("/api/v1/problems/{id}")
     }
 
+    pub fn problem_versions(id: i32) -> String {
+        format!("/api/v1/problems/{id}/versions")
+    }
+
+    pub fn problem_version_restore(id: i32, version: i32) -> String {
+        format!("/api/v1/problems/{id}/versions/{version}/restore")
+    }
+
+    pub fn problem_score_summary(id: i32) -> String {
+        format!("/api/v1/problems/{id}/score-summary")
+    }
+
+    pub fn problem_estimate_difficulty(id: i32) -> String {
+        format!("/api/v1/problems/{id}/estimate-difficulty")
+    }
+
     pub fn test_cases(problem_id: i32) -> String {
         format!("/api/v1/problems/{problem_id}/test-cases")
     }
@@ -292,6 +327,10 @@ pub mod routes {
         format!("/api/v1/problems/{problem_id}/test-cases/{tc_id}")
     }
 
+    pub fn test_cases_import_dir(problem_id: i32) -> String {
+        format!("/api/v1/problems/{problem_id}/test-cases/import-dir")
+    }
+
     pub fn test_cases_upload(problem_id: i32) -> String {
         format!("/api/v1/problems/{problem_id}/test-cases/upload")
     }
@@ -326,6 +365,26 @@ pub mod routes {
         format!("/api/v1/contests/{id}/register")
     }
 
+    pub fn contest_standings_csv(id: i32) -> String {
+        format!("/api/v1/contests/{id}/standings.csv")
+    }
+
+    pub fn contest_my_status(id: i32) -> String {
+        format!("/api/v1/contests/{id}/my-status")
+    }
+
+    pub fn contest_system_test(id: i32) -> String {
+        format!("/api/v1/contests/{id}/system-test")
+    }
+
+    pub fn contest_freeze(id: i32) -> String {
+        format!("/api/v1/contests/{id}/freeze")
+    }
+
+    pub fn contest_unfreeze(id: i32) -> String {
+        format!("/api/v1/contests/{id}/unfreeze")
+    }
+
     pub fn contest_my_info(id: i32) -> String {
         format!("/api/v1/contests/{id}/me")
     }
@@ -335,11 +394,21 @@ pub mod routes {
     }
 
     pub const SUBMISSIONS: &str = "/api/v1/submissions";
+    pub const SUBMISSIONS_EXPORT: &str = "/api/v1/submissions/export";
+    pub const VIEWS: &str = "/api/v1/views";
+
+    pub fn view(id: i32) -> String {
+        format!("/api/v1/views/{id}")
+    }
 
     pub fn submission(id: i32) -> String {
         format!("/api/v1/submissions/{id}")
     }
 
+    pub fn submission_comments(id: i32) -> String {
+        format!("/api/v1/submissions/{id}/comments")
+    }
+
     pub fn submission_rejudge(id: i32) -> String {
         format!("/api/v1/submissions/{id}/rejudge")
     }
@@ -356,6 +425,14 @@ pub mod routes {
         format!("/api/v1/submissions/{id}/judgements/{judgement_id}/discard")
     }
 
+    pub fn submission_recompute(id: i32) -> String {
+        format!("/api/v1/submissions/{id}/recompute")
+    }
+
+    pub fn submission_clone_as_practice(id: i32) -> String {
+        format!("/api/v1/submissions/{id}/clone-as-practice")
+    }
+
     pub fn problem_submissions(problem_id: i32) -> String {
         format!("/api/v1/problems/{problem_id}/submissions")
     }
@@ -380,6 +457,14 @@ pub mod routes {
         format!("/api/v1/code-runs/{id}")
     }
 
+    pub fn problem_run(problem_id: i32) -> String {
+        format!("/api/v1/problems/{problem_id}/run")
+    }
+
+    pub fn run(id: i32) -> String {
+        format!("/api/v1/runs/{id}")
+    }
+
     pub fn contest_clarifications(contest_id: i32) -> String {
         format!("/api/v1/contests/{contest_id}/clarifications")
     }
@@ -425,6 +510,11 @@ pub mod routes {
     pub const DLQ_BULK: &str = "/api/v1/dlq/bulk";
     pub const SUBMISSIONS_BULK_REJUDGE: &str = "/api/v1/submissions/bulk-rejudge";
 
+    pub fn rejudge_batch(id: i32) -> String {
+        format!("/api/v1/rejudge-batches/{id}")
+    }
+    pub const MAINTENANCE_ARCHIVE_SUBMISSIONS: &str = "/api/v1/maintenance/archive-submissions";
+
     pub fn attachments(problem_id: i32) -> String {
         format!("/api/v1/problems/{problem_id}/attachments")
     }
@@ -516,14 +606,73 @@ pub struct TestResponse {
 
 impl TestApp {
     pub async fn spawn() -> Self {
-        Self::spawn_internal(false).await
+        Self::spawn_internal(false, SubmissionConfig::default(), None, None, None, None).await
     }
 
     pub async fn spawn_with_plugins() -> Self {
-        Self::spawn_internal(true).await
+        Self::spawn_internal(true, SubmissionConfig::default(), None, None, None, None).await
+    }
+
+    pub async fn spawn_with_submission_config(submission: SubmissionConfig) -> Self {
+        Self::spawn_internal(false, submission, None, None, None, None).await
+    }
+
+    pub async fn spawn_with_plugin_exec_timeout(exec_timeout_ms: u64) -> Self {
+        Self::spawn_internal(
+            true,
+            SubmissionConfig::default(),
+            Some(exec_timeout_ms),
+            None,
+            None,
+            None,
+        )
+        .await
+    }
+
+    pub async fn spawn_with_problem_config(problem: ProblemConfig) -> Self {
+        Self::spawn_internal(
+            false,
+            SubmissionConfig::default(),
+            None,
+            Some(problem),
+            None,
+            None,
+        )
+        .await
+    }
+
+    pub async fn spawn_with_contest_config(contest: ContestConfig) -> Self {
+        Self::spawn_internal(
+            false,
+            SubmissionConfig::default(),
+            None,
+            None,
+            Some(contest),
+            None,
+        )
+        .await
     }
 
-    async fn spawn_internal(load_plugins: bool) -> Self {
+    pub async fn spawn_with_pagination_config(pagination: PaginationConfig) -> Self {
+        Self::spawn_internal(
+            false,
+            SubmissionConfig::default(),
+            None,
+            None,
+            None,
+            Some(pagination),
+        )
+        .await
+    }
+
+    async fn spawn_internal(
+        load_plugins: bool,
+        submission: SubmissionConfig,
+        exec_timeout_ms: Option<u64>,
+        problem: Option<ProblemConfig>,
+        contest: Option<ContestConfig>,
+        pagination: Option<PaginationConfig>,
+    ) -> Self {
         let port = shared_pg_port().await;
         let db_name = format!("test_{}", DB_COUNTER.fetch_add(1, Ordering::Relaxed));
 
@@ -558,6 +707,9 @@ impl TestApp {
                 trusted_proxies: vec![],
                 rate_limit_auth: false,
                 id: String::new(),
+                standings_cache_ttl_secs: 5,
+                pagination: pagination.unwrap_or_default(),
+                plugin_asset_max_age_secs: 0,
             },
             database: DatabaseConfig {
                 url: db_url.clone(),
@@ -569,9 +721,12 @@ impl TestApp {
             },
             plugin: PluginConfig {
                 plugins_dir: fixtures_dir(),
+                exec_timeout_ms,
                 ..Default::default()
             },
-            submission: SubmissionConfig::default(),
+            submission,
+            problem: problem.unwrap_or_default(),
+            contest: contest.unwrap_or_default(),
             storage: BlobStoreConfig::default(),
             mq: MqAppConfig {
                 enabled: false,
@@ -580,6 +735,10 @@ impl TestApp {
             observability: common::config::ObservabilityConfig::default(),
             batch_max_age_secs: 600,
             bootstrap: BootstrapConfig::default(),
+            webhook: WebhookConfig {
+                secret: "test-webhook-secret".to_string(),
+                ..Default::default()
+            },
         };
 
         let contest_type_registry: ContestTypeRegistry = Arc::new(RwLock::new(HashMap::new()));
@@ -632,6 +791,7 @@ impl TestApp {
                     submission_fn: "noop".into(),
                     code_run_fn: "noop".into(),
                     filter_submission_fn: None,
+                    summed_scores: true,
                 },
             );
             let mut languages = language_resolver_registry.write().await;
@@ -654,7 +814,9 @@ impl TestApp {
                         display_name: display_name.into(),
                         default_filename: default_filename.into(),
                         extensions,
+                        binary_extensions: Vec::new(),
                         template: String::new(),
+                        problem_types: Vec::new(),
                     },
                 );
             }
@@ -681,6 +843,9 @@ impl TestApp {
                 hook_registry: server::hooks::new_shared_registry(),
             },
             device_codes: std::sync::Arc::new(dashmap::DashMap::new()),
+            standings_cache: server::standings_cache::StandingsCache::new(
+                std::time::Duration::from_secs(5),
+            ),
             metrics: test_metrics.clone(),
             prometheus_registry: test_prom_registry.clone(),
         };
@@ -909,6 +1074,14 @@ impl TestApp {
         res.id()
     }
 
+    /// Creates a problem with a single test case so submissions to it are
+    /// accepted under the default `submission.no_test_cases` policy.
+    pub async fn create_problem_with_test_case(&self, token: &str, title: &str) -> i32 {
+        let problem_id = self.create_problem(token, title).await;
+        self.create_test_case(problem_id, token).await;
+        problem_id
+    }
+
     pub async fn create_test_case(&self, problem_id: i32, token: &str) -> i32 {
         let res = self
             .post_with_token(
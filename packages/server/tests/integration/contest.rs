@@ -219,6 +219,30 @@ mod contest_creation {
         assert_eq!(res.status, 201);
         assert_eq!(res.body["title"], "My Contest");
     }
+
+    #[tokio::test]
+    async fn rejects_unknown_language_in_allowed_languages() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+
+        let mut body = valid_contest_body("Restricted", false);
+        body["allowed_languages"] = json!(["cpp", "ruby"]);
+        let res = app.post_with_token(routes::CONTESTS, &body, &token).await;
+
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(
+            res.body["message"],
+            "allowed_languages contains unsupported language 'ruby'"
+        );
+
+        body["allowed_languages"] = json!(["cpp"]);
+        let res = app.post_with_token(routes::CONTESTS, &body, &token).await;
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert_eq!(res.body["allowed_languages"], json!(["cpp"]));
+    }
 }
 
 mod contest_listing {
@@ -550,6 +574,62 @@ mod contest_retrieval {
         assert!(res.body["description"].as_str().is_some());
     }
 
+    #[tokio::test]
+    async fn tz_query_adds_local_times_in_that_zone() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let id = create_contest_as_admin(&app, &admin, "Zoned", false).await;
+
+        let res = app
+            .get_with_token(&format!("{}?tz=Asia/Tokyo", routes::contest(id)), &admin)
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.text);
+        assert_eq!(res.body["start_time"], "2020-01-01T00:00:00Z");
+        assert_eq!(res.body["local_times"]["time_zone"], "Asia/Tokyo");
+        assert_eq!(
+            res.body["local_times"]["start_time"],
+            "2020-01-01T09:00:00+09:00"
+        );
+        assert_eq!(
+            res.body["local_times"]["end_time"],
+            "2099-01-02T09:00:00+09:00"
+        );
+
+        let res = app
+            .get_with_token(&format!("{}?tz=Asia/Tokyo", routes::CONTESTS), &admin)
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(
+            res.body["data"][0]["local_times"]["start_time"],
+            "2020-01-01T09:00:00+09:00"
+        );
+
+        let res = app.get_with_token(&routes::contest(id), &admin).await;
+        assert!(res.body.get("local_times").is_none());
+    }
+
+    #[tokio::test]
+    async fn invalid_tz_is_rejected() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let id = create_contest_as_admin(&app, &admin, "Zoned", false).await;
+
+        let res = app
+            .get_with_token(&format!("{}?tz=Mars/Olympus", routes::contest(id)), &admin)
+            .await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+
+        let res = app
+            .get_with_token(&format!("{}?tz=Mars/Olympus", routes::CONTESTS), &admin)
+            .await;
+        assert_eq!(res.status, 400);
+    }
+
     #[tokio::test]
     async fn participant_can_get_enrolled_private_contest() {
         let app = TestApp::spawn().await;
@@ -1508,6 +1588,122 @@ mod contest_problems {
         assert_eq!(res.status, 200);
         assert_eq!(res.body.as_array().unwrap().len(), 1);
     }
+
+    #[tokio::test]
+    async fn hide_problems_until_start_defaults_to_true() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let res = app
+            .post_with_token(
+                routes::CONTESTS,
+                &valid_contest_body("Default Hidden", true),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 201);
+        assert_eq!(res.body["hide_problems_until_start"], true);
+    }
+
+    #[tokio::test]
+    async fn participant_sees_hidden_problem_only_after_start_while_manager_always_can() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let user = app
+            .create_user_with_role("user1", "pass1234", "contestant")
+            .await;
+        let body = json!({
+            "title": "Future Contest",
+            "description": "desc",
+            "activate_time": "2020-01-01T00:00:00Z",
+            "start_time": "2099-01-01T00:00:00Z",
+            "end_time": "2099-01-02T00:00:00Z",
+            "is_public": true,
+            "hide_problems_until_start": true,
+        });
+        let contest_id = app
+            .post_with_token(routes::CONTESTS, &body, &admin)
+            .await
+            .id();
+        let p1 = app.create_problem(&admin, "P1").await;
+        app.add_problem_to_contest(contest_id, p1, &admin).await;
+        app.register_for_contest(contest_id, &user).await;
+
+        let res = app
+            .get_with_token(&routes::contest_problems(contest_id), &user)
+            .await;
+        assert_eq!(res.status, 400, "{}", res.text);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert!(
+            res.body["message"]
+                .as_str()
+                .unwrap()
+                .contains("not started"),
+            "{}",
+            res.text
+        );
+        let res = app.get_with_token(&routes::problem(p1), &user).await;
+        assert_eq!(res.status, 404);
+        let res = app
+            .get_with_token(&routes::contest_problems(contest_id), &admin)
+            .await;
+        assert_eq!(res.status, 200);
+        let res = app.get_with_token(&routes::problem(p1), &admin).await;
+        assert_eq!(res.status, 200);
+
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({"start_time": "2020-01-01T00:00:00Z"}),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+
+        let res = app
+            .get_with_token(&routes::contest_problems(contest_id), &user)
+            .await;
+        assert_eq!(res.status, 200);
+        let res = app.get_with_token(&routes::problem(p1), &user).await;
+        assert_eq!(res.status, 200);
+    }
+
+    #[tokio::test]
+    async fn participant_can_see_problems_before_start_when_not_hidden() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let user = app
+            .create_user_with_role("user1", "pass1234", "contestant")
+            .await;
+        let body = json!({
+            "title": "Open Preview",
+            "description": "desc",
+            "activate_time": "2020-01-01T00:00:00Z",
+            "start_time": "2099-01-01T00:00:00Z",
+            "end_time": "2099-01-02T00:00:00Z",
+            "is_public": true,
+            "hide_problems_until_start": false,
+        });
+        let contest_id = app
+            .post_with_token(routes::CONTESTS, &body, &admin)
+            .await
+            .id();
+        let p1 = app.create_problem(&admin, "P1").await;
+        app.add_problem_to_contest(contest_id, p1, &admin).await;
+
+        let res = app
+            .get_with_token(&routes::contest_problems(contest_id), &user)
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body.as_array().unwrap().len(), 1);
+        let res = app.get_with_token(&routes::problem(p1), &user).await;
+        assert_eq!(res.status, 200);
+    }
 }
 
 mod contest_participants {
@@ -2584,3 +2780,813 @@ mod bulk_add_participants {
         assert_eq!(res.body["code"], "PERMISSION_DENIED");
     }
 }
+
+mod standings_csv {
+    use super::*;
+    use crate::common::TestResponse;
+    use serde_json::Value;
+
+    async fn add_problem(app: &TestApp, admin: &str, contest_id: i32, label: &str) -> i32 {
+        let pid = app.create_problem(admin, &format!("Problem {label}")).await;
+        let res = app
+            .post_with_token(
+                &routes::contest_problems(contest_id),
+                &json!({ "problem_id": pid, "label": label }),
+                admin,
+            )
+            .await;
+        assert_eq!(res.status, 201, "add problem failed: {}", res.text);
+        pid
+    }
+
+    async fn add_participant(
+        app: &TestApp,
+        admin: &str,
+        contest_id: i32,
+        name: &str,
+    ) -> (String, i32) {
+        let token = app
+            .create_user_with_role(name, "pass1234", "contestant")
+            .await;
+        let uid = app.get_with_token(routes::ME, &token).await.id();
+        let res = app
+            .post_with_token(
+                &routes::contest_participants(contest_id),
+                &json!({ "user_id": uid }),
+                admin,
+            )
+            .await;
+        assert_eq!(res.status, 201);
+        (token, uid)
+    }
+
+    #[tokio::test]
+    async fn csv_needs_a_contest_type_standings_board() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+        add_problem(&app, &admin, contest_id, "A").await;
+        let (alice_token, _) = add_participant(&app, &admin, contest_id, "alice").await;
+
+        for token in [&alice_token, &admin] {
+            let res = app
+                .get_with_token(&routes::contest_standings_csv(contest_id), token)
+                .await;
+            assert_eq!(res.status, 404, "unexpected body: {}", res.text);
+            assert_eq!(res.body["code"], "NOT_FOUND");
+        }
+    }
+
+    async fn set_max_points(
+        app: &TestApp,
+        admin: &str,
+        contest_id: i32,
+        problem_id: i32,
+        max_points: Value,
+    ) -> TestResponse {
+        app.patch_with_token(
+            &routes::contest_problem(contest_id, problem_id),
+            &json!({ "max_points": max_points }),
+            admin,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn max_points_can_be_set_and_cleared() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+        let pa = add_problem(&app, &admin, contest_id, "A").await;
+
+        let res = set_max_points(&app, &admin, contest_id, pa, json!(100)).await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["max_points"], 100);
+
+        let res = set_max_points(&app, &admin, contest_id, pa, Value::Null).await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert!(res.body["max_points"].is_null());
+    }
+
+    #[tokio::test]
+    async fn max_points_must_be_positive() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+        let pa = add_problem(&app, &admin, contest_id, "A").await;
+
+        let res = set_max_points(&app, &admin, contest_id, pa, json!(0)).await;
+        assert_eq!(res.status, 400, "{}", res.text);
+    }
+
+    #[tokio::test]
+    async fn anonymize_standings_until_end_can_be_enabled() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "anonymize_standings_until_end": true }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["anonymize_standings_until_end"], true);
+    }
+
+    #[tokio::test]
+    async fn public_standings_limit_must_be_positive() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "public_standings_limit": 2 }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["public_standings_limit"], 2);
+
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "public_standings_limit": 0 }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 400, "{}", res.text);
+    }
+
+    #[tokio::test]
+    async fn score_selection_defaults_to_best_and_can_be_last() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+        let res = app
+            .get_with_token(&routes::contest(contest_id), &admin)
+            .await;
+        assert_eq!(res.body["score_selection"], "Best");
+
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "score_selection": "Last" }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["score_selection"], "Last");
+    }
+
+    #[tokio::test]
+    async fn unknown_score_selection_is_rejected() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "score_selection": "Average" }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 400, "{}", res.text);
+    }
+
+    #[tokio::test]
+    async fn manual_freeze_sets_frozen_at_until_unfrozen() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+
+        let res = app
+            .post_with_token(&routes::contest_freeze(contest_id), &json!({}), &admin)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        let frozen_at = res.body["frozen_at"].clone();
+        assert!(frozen_at.is_string(), "{}", res.text);
+        // Freezing again keeps the original instant.
+        let res = app
+            .post_with_token(&routes::contest_freeze(contest_id), &json!({}), &admin)
+            .await;
+        assert_eq!(res.body["frozen_at"], frozen_at);
+
+        let res = app
+            .post_with_token(&routes::contest_unfreeze(contest_id), &json!({}), &admin)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert!(res.body["frozen_at"].is_null());
+    }
+
+    #[tokio::test]
+    async fn contestant_cannot_freeze_or_unfreeze() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+        let (token, _) = add_participant(&app, &admin, contest_id, "alice").await;
+
+        for route in [
+            routes::contest_freeze(contest_id),
+            routes::contest_unfreeze(contest_id),
+        ] {
+            let res = app.post_with_token(&route, &json!({}), &token).await;
+            assert_eq!(res.status, 403, "{route}: {}", res.text);
+            assert_eq!(res.body["code"], "PERMISSION_DENIED");
+        }
+    }
+
+    #[tokio::test]
+    async fn freezing_missing_contest_returns_404() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+
+        let res = app
+            .post_with_token(&routes::contest_freeze(9999), &json!({}), &admin)
+            .await;
+        assert_eq!(res.status, 404);
+    }
+
+    #[tokio::test]
+    async fn hidden_participant_list_requires_manage_permission() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+        let (token, _) = add_participant(&app, &admin, contest_id, "alice").await;
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "show_participants_list": false }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 200);
+
+        let res = app
+            .get_with_token(&routes::contest_standings_csv(contest_id), &token)
+            .await;
+        assert_eq!(res.status, 403);
+        assert_eq!(res.body["code"], "PERMISSION_DENIED");
+
+        // Past the permission check, the contest type serves no board.
+        let res = app
+            .get_with_token(&routes::contest_standings_csv(contest_id), &admin)
+            .await;
+        assert_eq!(res.status, 404);
+        assert_eq!(res.body["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn non_participant_cannot_see_private_contest_standings() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let outsider = app
+            .create_user_with_role("outsider", "pass1234", "contestant")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+
+        let res = app
+            .get_with_token(&routes::contest_standings_csv(contest_id), &outsider)
+            .await;
+        assert_eq!(res.status, 404);
+    }
+}
+
+mod my_status {
+    use super::*;
+
+    async fn setup(app: &TestApp) -> (i32, String) {
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(app, &admin, "C1", true).await;
+        let pa = app.create_problem(&admin, "Problem A").await;
+        let res = app
+            .post_with_token(
+                &routes::contest_problems(contest_id),
+                &json!({ "problem_id": pa, "label": "A" }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 201, "add problem failed: {}", res.text);
+        let token = app
+            .create_user_with_role("alice", "pass1234", "contestant")
+            .await;
+        app.register_for_contest(contest_id, &token).await;
+        (contest_id, token)
+    }
+
+    #[tokio::test]
+    async fn my_status_needs_a_contest_type_standings_board() {
+        let app = TestApp::spawn().await;
+        let (contest_id, token) = setup(&app).await;
+
+        let res = app
+            .get_with_token(&routes::contest_my_status(contest_id), &token)
+            .await;
+        assert_eq!(res.status, 404, "unexpected body: {}", res.text);
+        assert_eq!(res.body["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn problem_list_leaves_flags_unset_without_a_standings_board() {
+        let app = TestApp::spawn().await;
+        let (contest_id, token) = setup(&app).await;
+
+        let res = app
+            .get_with_token(&routes::contest_problems(contest_id), &token)
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.text);
+        assert!(res.body[0]["solved"].is_null());
+        assert!(res.body[0]["attempted"].is_null());
+    }
+}
+
+mod system_tests {
+    use super::*;
+    use common::{SubmissionStatus, Verdict};
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+    use server::entity::submission;
+
+    async fn create_ended_contest(app: &TestApp, admin: &str, two_phase: bool) -> i32 {
+        let res = app
+            .post_with_token(
+                routes::CONTESTS,
+                &json!({
+                    "title": "Round 1",
+                    "description": "Two-phase round",
+                    "activate_time": "2020-01-01T00:00:00Z",
+                    "start_time": "2020-01-01T00:00:00Z",
+                    "end_time": "2020-01-02T00:00:00Z",
+                    "is_public": true,
+                    "two_phase": two_phase,
+                }),
+                admin,
+            )
+            .await;
+        assert_eq!(res.status, 201, "create contest failed: {}", res.text);
+        assert_eq!(res.body["two_phase"], two_phase);
+        res.id()
+    }
+
+    async fn insert_judged(
+        app: &TestApp,
+        contest_id: i32,
+        problem_id: i32,
+        user_id: i32,
+        created_at: &str,
+    ) -> i32 {
+        submission::ActiveModel {
+            problem_id: Set(problem_id),
+            contest_id: Set(Some(contest_id)),
+            user_id: Set(user_id),
+            language: Set("cpp".into()),
+            files: Set(json!([{"filename": "main.cpp", "content": "int main() {}"}])),
+            status: Set(SubmissionStatus::Judged),
+            verdict: Set(Some(Verdict::Accepted)),
+            score: Set(Some(100.0)),
+            judge_epoch: Set(1),
+            created_at: Set(created_at.parse().unwrap()),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert submission")
+        .id
+    }
+
+    async fn judge_epoch(app: &TestApp, id: i32) -> i32 {
+        submission::Entity::find_by_id(id)
+            .one(&app.db)
+            .await
+            .unwrap()
+            .unwrap()
+            .judge_epoch
+    }
+
+    #[tokio::test]
+    async fn rejudges_submissions_made_during_the_contest() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let admin_id = app.get_with_token(routes::ME, &admin).await.id();
+        let contest_id = create_ended_contest(&app, &admin, true).await;
+        let pid = app.create_problem_with_test_case(&admin, "P").await;
+
+        let during_a = insert_judged(&app, contest_id, pid, admin_id, "2020-01-01T01:00:00Z").await;
+        let during_b = insert_judged(&app, contest_id, pid, admin_id, "2020-01-01T02:00:00Z").await;
+        let after = insert_judged(&app, contest_id, pid, admin_id, "2020-01-03T00:00:00Z").await;
+
+        let res = app
+            .post_with_token(&routes::contest_system_test(contest_id), &json!({}), &admin)
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.text);
+        assert_eq!(res.body["queued"], 2);
+
+        assert_eq!(judge_epoch(&app, during_a).await, 2);
+        assert_eq!(judge_epoch(&app, during_b).await, 2);
+        assert_eq!(
+            judge_epoch(&app, after).await,
+            1,
+            "post-contest submissions are not part of the system test"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_contest_without_two_phase() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_ended_contest(&app, &admin, false).await;
+
+        let res = app
+            .post_with_token(&routes::contest_system_test(contest_id), &json!({}), &admin)
+            .await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn rejects_contest_that_has_not_ended() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let mut body = valid_contest_body("Running", true);
+        body["two_phase"] = json!(true);
+        let contest_id = app
+            .post_with_token(routes::CONTESTS, &body, &admin)
+            .await
+            .id();
+
+        let res = app
+            .post_with_token(&routes::contest_system_test(contest_id), &json!({}), &admin)
+            .await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn contestant_cannot_run_system_tests() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contestant = app
+            .create_user_with_role("user1", "pass1234", "contestant")
+            .await;
+        let contest_id = create_ended_contest(&app, &admin, true).await;
+
+        let res = app
+            .post_with_token(
+                &routes::contest_system_test(contest_id),
+                &json!({}),
+                &contestant,
+            )
+            .await;
+        assert_eq!(res.status, 403);
+    }
+}
+
+mod result_delay {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use common::{SubmissionStatus, Verdict};
+    use sea_orm::{ActiveModelTrait, Set};
+    use server::entity::submission;
+
+    async fn insert_judged(
+        app: &TestApp,
+        contest_id: i32,
+        problem_id: i32,
+        user_id: i32,
+        created_at: chrono::DateTime<Utc>,
+    ) -> i32 {
+        insert_finished(
+            app,
+            contest_id,
+            problem_id,
+            user_id,
+            created_at,
+            SubmissionStatus::Judged,
+        )
+        .await
+    }
+
+    /// Inserts a finished submission; `Judged` ones are accepted with 100.
+    async fn insert_finished(
+        app: &TestApp,
+        contest_id: i32,
+        problem_id: i32,
+        user_id: i32,
+        created_at: chrono::DateTime<Utc>,
+        status: SubmissionStatus,
+    ) -> i32 {
+        let judged = status == SubmissionStatus::Judged;
+        submission::ActiveModel {
+            problem_id: Set(problem_id),
+            contest_id: Set(Some(contest_id)),
+            user_id: Set(user_id),
+            language: Set("cpp".into()),
+            files: Set(json!([{"filename": "main.cpp", "content": "int main() {}"}])),
+            status: Set(status),
+            verdict: Set(judged.then_some(Verdict::Accepted)),
+            score: Set(judged.then_some(100.0)),
+            created_at: Set(created_at),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert submission")
+        .id
+    }
+
+    /// A contest whose only problem delays results by five minutes, with
+    /// one contestant. Returns (admin token, contest, problem, contestant
+    /// token, contestant id).
+    async fn setup(app: &TestApp) -> (String, i32, i32, String, i32) {
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(app, &admin, "C1", false).await;
+        let pid = app.create_problem(&admin, "Problem A").await;
+        let res = app
+            .post_with_token(
+                &routes::contest_problems(contest_id),
+                &json!({ "problem_id": pid, "label": "A", "result_delay_secs": 300 }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert_eq!(res.body["result_delay_secs"], 300);
+
+        let token = app
+            .create_user_with_role("alice", "pass1234", "contestant")
+            .await;
+        let uid = app.get_with_token(routes::ME, &token).await.id();
+        let res = app
+            .post_with_token(
+                &routes::contest_participants(contest_id),
+                &json!({ "user_id": uid }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 201);
+        (admin, contest_id, pid, token, uid)
+    }
+
+    #[tokio::test]
+    async fn fresh_result_is_hidden_from_owner_during_delay() {
+        let app = TestApp::spawn().await;
+        let (admin, contest_id, pid, token, uid) = setup(&app).await;
+        let id = insert_judged(&app, contest_id, pid, uid, Utc::now()).await;
+
+        let res = app.get_with_token(&routes::submission(id), &token).await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert!(res.body["result"].is_null());
+        assert!(res.body["results_hidden_until"].is_string());
+
+        let res = app.get_with_token(routes::SUBMISSIONS, &token).await;
+        let item = &res.body["data"][0];
+        assert_eq!(item["id"], id);
+        assert!(item["verdict"].is_null());
+        assert!(item["score"].is_null());
+
+        // Managers see the result straight away.
+        let res = app.get_with_token(&routes::submission(id), &admin).await;
+        assert_eq!(res.body["result"]["verdict"], "Accepted");
+        assert!(res.body.get("results_hidden_until").is_none());
+    }
+
+    #[tokio::test]
+    async fn result_is_shown_once_delay_has_elapsed() {
+        let app = TestApp::spawn().await;
+        let (_, contest_id, pid, token, uid) = setup(&app).await;
+        let id = insert_judged(
+            &app,
+            contest_id,
+            pid,
+            uid,
+            Utc::now() - Duration::minutes(10),
+        )
+        .await;
+
+        let res = app.get_with_token(&routes::submission(id), &token).await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["result"]["verdict"], "Accepted");
+        assert_eq!(res.body["result"]["score"], 100.0);
+        assert!(res.body.get("results_hidden_until").is_none());
+
+        let res = app.get_with_token(routes::SUBMISSIONS, &token).await;
+        assert_eq!(res.body["data"][0]["verdict"], "Accepted");
+    }
+
+    #[tokio::test]
+    async fn negative_result_delay_is_rejected() {
+        let app = TestApp::spawn().await;
+        let (admin, contest_id, pid, _, _) = setup(&app).await;
+
+        let res = app
+            .patch_with_token(
+                &routes::contest_problem(contest_id, pid),
+                &json!({ "result_delay_secs": -1 }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 400);
+    }
+
+    #[tokio::test]
+    async fn compilation_error_reads_as_judged_during_delay() {
+        let app = TestApp::spawn().await;
+        let (admin, contest_id, pid, token, uid) = setup(&app).await;
+        let id = insert_finished(
+            &app,
+            contest_id,
+            pid,
+            uid,
+            Utc::now(),
+            SubmissionStatus::CompilationError,
+        )
+        .await;
+
+        let res = app.get_with_token(&routes::submission(id), &token).await;
+        assert_eq!(res.body["status"], "Judged");
+
+        let res = app.get_with_token(routes::SUBMISSIONS, &token).await;
+        assert_eq!(res.body["data"][0]["status"], "Judged");
+
+        let res = app
+            .get_with_token(&routes::contest_submissions(contest_id), &token)
+            .await;
+        assert_eq!(res.body["data"][0]["status"], "Judged");
+
+        // Filtering by status must not tell the two apart either.
+        let res = app
+            .get_with_token(
+                &format!("{}?status=CompilationError", routes::SUBMISSIONS),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["data"].as_array().unwrap().len(), 0);
+        let res = app
+            .get_with_token(&format!("{}?status=Judged", routes::SUBMISSIONS), &token)
+            .await;
+        assert_eq!(res.body["data"][0]["id"], id);
+
+        let res = app.get_with_token(&routes::submission(id), &admin).await;
+        assert_eq!(res.body["status"], "CompilationError");
+    }
+}
+
+mod problem_cap {
+    use super::*;
+    use server::config::ContestConfig;
+
+    #[tokio::test]
+    async fn adding_beyond_max_problems_is_rejected() {
+        let app = TestApp::spawn_with_contest_config(ContestConfig { max_problems: 2 }).await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+
+        for label in ["A", "B"] {
+            let pid = app
+                .create_problem(&admin, &format!("Problem {label}"))
+                .await;
+            let res = app
+                .post_with_token(
+                    &routes::contest_problems(contest_id),
+                    &json!({ "problem_id": pid, "label": label }),
+                    &admin,
+                )
+                .await;
+            assert_eq!(res.status, 201, "{}", res.text);
+        }
+
+        let pid = app.create_problem(&admin, "Problem C").await;
+        let res = app
+            .post_with_token(
+                &routes::contest_problems(contest_id),
+                &json!({ "problem_id": pid, "label": "C" }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 400, "{}", res.text);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+
+        let res = app
+            .get_with_token(&routes::contest_problems(contest_id), &admin)
+            .await;
+        assert_eq!(res.body.as_array().unwrap().len(), 2);
+    }
+}
+
+mod problem_label_assignment {
+    use super::*;
+
+    #[tokio::test]
+    async fn problems_added_without_labels_get_sequential_letters() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+
+        for expected in ["A", "B", "C"] {
+            let pid = app
+                .create_problem(&admin, &format!("Problem {expected}"))
+                .await;
+            let res = app
+                .post_with_token(
+                    &routes::contest_problems(contest_id),
+                    &json!({ "problem_id": pid }),
+                    &admin,
+                )
+                .await;
+            assert_eq!(res.status, 201, "{}", res.text);
+            assert_eq!(res.body["label"], expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_label_fills_the_first_gap() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let contest_id = create_contest_as_admin(&app, &admin, "C1", false).await;
+
+        let mut problem_ids = Vec::new();
+        for _ in 0..3 {
+            let pid = app
+                .create_problem(&admin, &format!("Problem {}", problem_ids.len()))
+                .await;
+            let res = app
+                .post_with_token(
+                    &routes::contest_problems(contest_id),
+                    &json!({ "problem_id": pid }),
+                    &admin,
+                )
+                .await;
+            assert_eq!(res.status, 201, "{}", res.text);
+            problem_ids.push(pid);
+        }
+
+        let res = app
+            .delete_with_token(&routes::contest_problem(contest_id, problem_ids[1]), &admin)
+            .await;
+        assert_eq!(res.status, 204, "{}", res.text);
+
+        let pid = app.create_problem(&admin, "Replacement").await;
+        let res = app
+            .post_with_token(
+                &routes::contest_problems(contest_id),
+                &json!({ "problem_id": pid }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert_eq!(res.body["label"], "B");
+
+        let pid = app.create_problem(&admin, "Next").await;
+        let res = app
+            .post_with_token(
+                &routes::contest_problems(contest_id),
+                &json!({ "problem_id": pid }),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.body["label"], "D");
+    }
+}
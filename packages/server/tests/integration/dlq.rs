@@ -279,6 +279,56 @@ mod dlq_detail {
         assert!(res.body["retry_history"].is_array());
     }
 
+    #[tokio::test]
+    async fn detail_includes_retry_history_of_dead_lettered_job() {
+        use common::retry::RetryAttempt;
+        use common::{DlqEnvelope, DlqErrorCode, DlqMessageType};
+        use server::dlq::DlqService;
+
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_dlq_hist", "password123", "admin")
+            .await;
+
+        let envelope = DlqEnvelope {
+            message_id: "job-retry-history".into(),
+            message_type: DlqMessageType::OperationTask,
+            submission_id: Some(310),
+            payload: json!({"job_id": "job-retry-history"}),
+            error_code: DlqErrorCode::MaxRetriesExceeded,
+            error_message: "sandbox crashed".into(),
+            retry_history: vec![
+                RetryAttempt::new(1, "worker timed out"),
+                RetryAttempt::new(2, "sandbox crashed"),
+            ],
+        };
+        let model = DlqService::new(&app.db)
+            .send_to_dlq(&envelope)
+            .await
+            .expect("dead-letter job");
+
+        let res = app
+            .get_with_token(&routes::dlq_message(model.id), &admin_token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["error_code"], "MAX_RETRIES_EXCEEDED");
+        assert_eq!(res.body["retry_count"], 2);
+        assert_eq!(res.body["payload"]["job_id"], "job-retry-history");
+
+        let history = res.body["retry_history"].as_array().unwrap();
+        assert_eq!(history.len(), 2);
+        for (entry, sent) in history.iter().zip(&envelope.retry_history) {
+            assert_eq!(entry["attempt"], sent.attempt);
+            assert_eq!(entry["error"], sent.error);
+            let timestamp: chrono::DateTime<Utc> =
+                serde_json::from_value(entry["timestamp"].clone()).expect("RFC 3339 timestamp");
+            assert_eq!(
+                timestamp.timestamp_millis(),
+                sent.timestamp.timestamp_millis()
+            );
+        }
+    }
+
     #[tokio::test]
     async fn returns_404_for_nonexistent_message() {
         let app = TestApp::spawn().await;
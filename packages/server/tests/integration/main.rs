@@ -10,10 +10,16 @@ mod downloads;
 #[cfg(not(feature = "bundled-stress-test"))]
 mod downloads_slim;
 mod health;
+mod maintenance;
+mod message_headers;
 mod meta;
 mod plugin;
 mod plugin_config;
 mod problem;
+mod saved_view;
 mod scaling;
+mod seed;
 mod submission;
+mod system;
 mod user;
+mod webhook;
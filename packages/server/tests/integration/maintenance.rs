@@ -0,0 +1,263 @@
+use chrono::{DateTime, Duration, Utc};
+use common::{SubmissionStatus, Verdict};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, Set};
+use serde_json::json;
+use server::entity::{archived_submission, submission, test_case_result};
+
+use crate::common::{TestApp, routes};
+
+async fn insert_submission(
+    app: &TestApp,
+    user_id: i32,
+    problem_id: i32,
+    test_case_id: i32,
+    status: SubmissionStatus,
+    created_at: DateTime<Utc>,
+) -> i32 {
+    let sub = submission::ActiveModel {
+        files: Set(json!([{ "filename": "main.cpp", "content": "int main() {}" }])),
+        language: Set("cpp".into()),
+        user_id: Set(user_id),
+        problem_id: Set(problem_id),
+        status: Set(status),
+        verdict: Set(Some(Verdict::Accepted)),
+        score: Set(Some(100.0)),
+        time_used: Set(Some(12)),
+        memory_used: Set(Some(256)),
+        created_at: Set(created_at),
+        judged_at: Set(Some(created_at)),
+        ..Default::default()
+    }
+    .insert(&app.db)
+    .await
+    .expect("insert submission");
+
+    test_case_result::ActiveModel {
+        submission_id: Set(sub.id),
+        test_case_id: Set(Some(test_case_id)),
+        verdict: Set(Verdict::Accepted),
+        score: Set(100.0),
+        time_used: Set(Some(12)),
+        memory_used: Set(Some(256)),
+        stdout: Set(Some("42".into())),
+        created_at: Set(created_at),
+        ..Default::default()
+    }
+    .insert(&app.db)
+    .await
+    .expect("insert test case result");
+
+    sub.id
+}
+
+async fn result_count(app: &TestApp, submission_id: i32) -> u64 {
+    test_case_result::Entity::find()
+        .filter(test_case_result::Column::SubmissionId.eq(submission_id))
+        .count(&app.db)
+        .await
+        .unwrap()
+}
+
+async fn setup(app: &TestApp) -> (String, i32, i32, i32) {
+    let token = app
+        .create_user_with_role("archiver", "password123", "admin")
+        .await;
+    let me = app.get_with_token(routes::ME, &token).await;
+    let problem_id = app.create_problem(&token, "Archive Problem").await;
+    let test_case_id = app.create_test_case(problem_id, &token).await;
+    (token, me.id(), problem_id, test_case_id)
+}
+
+mod archive_submissions {
+    use super::*;
+
+    #[tokio::test]
+    async fn old_submission_is_archived_and_recent_one_is_untouched() {
+        let app = TestApp::spawn().await;
+        let (token, user_id, pid, tc_id) = setup(&app).await;
+        let now = Utc::now();
+        let old_id = insert_submission(
+            &app,
+            user_id,
+            pid,
+            tc_id,
+            SubmissionStatus::Judged,
+            now - Duration::days(400),
+        )
+        .await;
+        let recent_id =
+            insert_submission(&app, user_id, pid, tc_id, SubmissionStatus::Judged, now).await;
+
+        let res = app
+            .post_with_token(routes::MAINTENANCE_ARCHIVE_SUBMISSIONS, &json!({}), &token)
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+        assert_eq!(res.body["archived"], 1);
+        assert_eq!(res.body["has_more"], false);
+
+        let old = submission::Entity::find_by_id(old_id)
+            .one(&app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(old.files, json!([]));
+        assert_eq!(old.status, SubmissionStatus::Judged);
+        assert_eq!(old.verdict, Some(Verdict::Accepted));
+        assert_eq!(old.score, Some(100.0));
+        assert_eq!(result_count(&app, old_id).await, 0);
+
+        let archive = archived_submission::Entity::find_by_id(old_id)
+            .one(&app.db)
+            .await
+            .unwrap()
+            .expect("archive row should exist");
+        assert_eq!(archive.files[0]["filename"], "main.cpp");
+        assert_eq!(archive.test_case_results.as_array().unwrap().len(), 1);
+        assert_eq!(archive.test_case_results[0]["stdout"], "42");
+
+        let summary = app
+            .get_with_token(&routes::submission(old_id), &token)
+            .await;
+        assert_eq!(summary.status, 200);
+        assert_eq!(summary.body["status"], "Judged");
+        assert_eq!(summary.body["score"], 100.0);
+
+        let recent = submission::Entity::find_by_id(recent_id)
+            .one(&app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(recent.files[0]["filename"], "main.cpp");
+        assert_eq!(result_count(&app, recent_id).await, 1);
+        assert!(
+            archived_submission::Entity::find_by_id(recent_id)
+                .one(&app.db)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn in_progress_submission_is_not_archived() {
+        let app = TestApp::spawn().await;
+        let (token, user_id, pid, tc_id) = setup(&app).await;
+        let sub_id = insert_submission(
+            &app,
+            user_id,
+            pid,
+            tc_id,
+            SubmissionStatus::Running,
+            Utc::now() - Duration::days(400),
+        )
+        .await;
+
+        let res = app
+            .post_with_token(routes::MAINTENANCE_ARCHIVE_SUBMISSIONS, &json!({}), &token)
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["archived"], 0);
+        assert_eq!(result_count(&app, sub_id).await, 1);
+    }
+
+    #[tokio::test]
+    async fn older_than_days_and_limit_override_defaults() {
+        let app = TestApp::spawn().await;
+        let (token, user_id, pid, tc_id) = setup(&app).await;
+        let ten_days_ago = Utc::now() - Duration::days(10);
+        for _ in 0..3 {
+            insert_submission(
+                &app,
+                user_id,
+                pid,
+                tc_id,
+                SubmissionStatus::Judged,
+                ten_days_ago,
+            )
+            .await;
+        }
+
+        let res = app
+            .post_with_token(
+                routes::MAINTENANCE_ARCHIVE_SUBMISSIONS,
+                &json!({ "older_than_days": 7, "limit": 2 }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["archived"], 2);
+        assert_eq!(res.body["has_more"], true);
+
+        let res = app
+            .post_with_token(
+                routes::MAINTENANCE_ARCHIVE_SUBMISSIONS,
+                &json!({ "older_than_days": 7 }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.body["archived"], 1);
+
+        let res = app
+            .post_with_token(
+                routes::MAINTENANCE_ARCHIVE_SUBMISSIONS,
+                &json!({ "older_than_days": 7 }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.body["archived"], 0);
+    }
+
+    #[tokio::test]
+    async fn archived_submission_cannot_be_rejudged() {
+        let app = TestApp::spawn().await;
+        let (token, user_id, pid, tc_id) = setup(&app).await;
+        let sub_id = insert_submission(
+            &app,
+            user_id,
+            pid,
+            tc_id,
+            SubmissionStatus::Judged,
+            Utc::now() - Duration::days(400),
+        )
+        .await;
+        app.post_with_token(routes::MAINTENANCE_ARCHIVE_SUBMISSIONS, &json!({}), &token)
+            .await;
+
+        let res = app
+            .post_with_token(&routes::submission_rejudge(sub_id), &json!({}), &token)
+            .await;
+        assert_eq!(res.status, 409);
+        assert_eq!(res.body["code"], "CONFLICT");
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_older_than_days() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("archiver", "password123", "admin")
+            .await;
+
+        let res = app
+            .post_with_token(
+                routes::MAINTENANCE_ARCHIVE_SUBMISSIONS,
+                &json!({ "older_than_days": 0 }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn contestant_cannot_archive() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_authenticated_user("someone", "password123")
+            .await;
+
+        let res = app
+            .post_with_token(routes::MAINTENANCE_ARCHIVE_SUBMISSIONS, &json!({}), &token)
+            .await;
+        assert_eq!(res.status, 403);
+    }
+}
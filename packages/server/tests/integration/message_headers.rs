@@ -0,0 +1,79 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use common::worker::{TASK_SCHEMA_VERSION, Task};
+use mq::{BrokerMessage, ConsumeConfig, Envelope, MessageHeaders, MqConfig, init_mq};
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn published_headers_round_trip_to_consumer() {
+    let redis = Redis::default()
+        .start()
+        .await
+        .expect("failed to start Redis container");
+    let port = redis
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("failed to get Redis port");
+    let mq = init_mq(MqConfig {
+        url: format!("redis://127.0.0.1:{port}"),
+        pool_size: 2,
+    })
+    .await
+    .expect("failed to create MQ client");
+
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before UNIX_EPOCH")
+        .as_nanos();
+    let queue = format!("operation_queue.headers_{suffix}");
+
+    let headers = MessageHeaders::json()
+        .with_schema_version(TASK_SCHEMA_VERSION)
+        .with_trace_id(Some("4bf92f3577b34da6a3ce929d0e0e4736".to_string()));
+    let task = Task {
+        id: "task-1".to_string(),
+        task_type: "operation".to_string(),
+        executor_name: "operation".to_string(),
+        payload: serde_json::json!({}),
+        result_queue: "operation_results".to_string(),
+        reply_queue: None,
+        priority: None,
+        trace_context: None,
+        schema_version: Some(TASK_SCHEMA_VERSION),
+    };
+    mq.publish(&queue, None, &Envelope::new(headers.clone(), task), None)
+        .await
+        .expect("failed to publish task");
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let consumer = {
+        let queue = queue.clone();
+        tokio::spawn(async move {
+            mq::consume(
+                &mq,
+                &queue,
+                &ConsumeConfig::default(),
+                move |message: BrokerMessage<Envelope<Task>>| {
+                    let tx = tx.clone();
+                    async move {
+                        let _ = tx.send(message.payload);
+                        Ok(())
+                    }
+                },
+            )
+            .await
+        })
+    };
+
+    let received = timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("consumer did not receive the task")
+        .expect("consumer channel closed");
+    assert_eq!(received.headers, headers);
+    assert_eq!(received.payload.id, "task-1");
+
+    consumer.abort();
+}
@@ -105,6 +105,117 @@ mod plugin_management {
     }
 }
 
+mod plugin_functions {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn lists_echo_and_greet_with_declared_metadata() {
+        let app = TestApp::spawn_with_plugins().await;
+        let token = app
+            .create_user_with_role("fn_admin", "securepass", "admin")
+            .await;
+
+        let res = app
+            .get_with_token(&routes::plugin_functions("server-plugin"), &token)
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["plugin_id"], "server-plugin");
+        assert_eq!(res.body["capabilities"], json!(["storage", "sql"]));
+
+        let functions = res.body["functions"].as_array().unwrap();
+        let find = |name: &str| {
+            functions
+                .iter()
+                .find(|f| f["name"] == name)
+                .unwrap_or_else(|| panic!("function '{name}' not listed"))
+        };
+
+        let echo = find("echo");
+        assert_eq!(echo["routes"][0]["method"], "POST");
+        assert_eq!(echo["routes"][0]["path"], "/echo");
+        assert!(echo["input_schema"].is_null());
+
+        let greet = find("greet");
+        assert_eq!(greet["routes"][0]["path"], "/greet");
+        assert_eq!(greet["input_schema"]["type"], "object");
+        assert_eq!(greet["input_schema"]["required"], json!(["name"]));
+        assert_eq!(
+            greet["output_schema"]["properties"]["greeting"]["type"],
+            "string"
+        );
+    }
+
+    #[tokio::test]
+    async fn requires_plugin_manage_permission() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("fn_user", "securepass", "contestant")
+            .await;
+
+        let res = app
+            .get_without_token(&routes::plugin_functions("server-plugin"))
+            .await;
+        assert_eq!(res.status, 401);
+
+        let res = app
+            .get_with_token(&routes::plugin_functions("server-plugin"), &token)
+            .await;
+        assert_eq!(res.status, 403);
+    }
+
+    #[tokio::test]
+    async fn unknown_plugin_returns_not_found() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("fn_admin", "securepass", "admin")
+            .await;
+
+        let res = app
+            .get_with_token(&routes::plugin_functions("no-such-plugin"), &token)
+            .await;
+        assert_eq!(res.status, 404);
+        assert_eq!(res.body["code"], "NOT_FOUND");
+    }
+}
+
+mod plugin_assets {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn lists_web_bundle_files() {
+        let app = TestApp::spawn_with_plugins().await;
+        let token = app
+            .create_user_with_role("asset_admin", "securepass", "admin")
+            .await;
+
+        let res = app
+            .get_with_token(&routes::plugin_assets("web-plugin"), &token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["plugin_id"], "web-plugin");
+        assert_eq!(res.body["assets"], json!(["index.js", "styles/theme.css"]));
+
+        let res = app
+            .get_with_token(&routes::plugin_assets("server-plugin"), &token)
+            .await;
+        assert_eq!(res.status, 404);
+        assert_eq!(res.body["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn requires_plugin_manage_permission() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("asset_user", "securepass", "contestant")
+            .await;
+
+        let res = app
+            .get_with_token(&routes::plugin_assets("web-plugin"), &token)
+            .await;
+        assert_eq!(res.status, 403);
+    }
+}
+
 mod plugin_routing {
     use super::*;
 
@@ -204,6 +315,540 @@ mod plugin_routing {
         assert_eq!(res.status, 200);
         assert_eq!(res.body["value"], "42");
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn guest_distinguishes_missing_key_stored_null_and_stored_value() {
+        let app = TestApp::spawn_with_plugins().await;
+        let exists = |key: &str| routes::plugin_proxy("server-plugin", &format!("kv/{key}/exists"));
+        let value = |key: &str| routes::plugin_proxy("server-plugin", &format!("kv/{key}"));
+
+        // Guests store JSON text, so a serialized `None` lands as "null".
+        app.post_without_token(&value("nullish"), &json!({"value": "null"}))
+            .await;
+        app.post_without_token(&value("present"), &json!({"value": "v"}))
+            .await;
+
+        let res = app.get_without_token(&exists("missing")).await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["exists"], false);
+        let res = app.get_without_token(&value("missing")).await;
+        assert_eq!(res.status, 404);
+
+        let res = app.get_without_token(&exists("nullish")).await;
+        assert_eq!(res.body["exists"], true);
+        let res = app.get_without_token(&value("nullish")).await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["value"], "null");
+
+        let res = app.get_without_token(&exists("present")).await;
+        assert_eq!(res.body["exists"], true);
+        let res = app.get_without_token(&value("present")).await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["value"], "v");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn request_body_is_validated_against_declared_input_schema() {
+        let app = TestApp::spawn_with_plugins().await;
+        let url = routes::plugin_proxy("server-plugin", "kv/schema-key");
+
+        let res = app.post_without_token(&url, &json!({"value": 42})).await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert!(
+            res.body["message"]
+                .as_str()
+                .unwrap()
+                .contains("$.value: expected string")
+        );
+
+        let res = app.post_without_token(&url, &json!({})).await;
+        assert_eq!(res.status, 400);
+
+        let res = app.get_without_token(&url).await;
+        assert_eq!(res.status, 404);
+
+        let res = app.post_without_token(&url, &json!({"value": "ok"})).await;
+        assert_eq!(res.status, 200);
+
+        let res = app.get_without_token(&url).await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["value"], "ok");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn trapping_handler_fails_cleanly_and_instance_is_rebuilt() {
+        let app = TestApp::spawn_with_plugins().await;
+
+        let res = app
+            .get_without_token(&routes::plugin_proxy("server-plugin", "crash"))
+            .await;
+        assert_eq!(res.status, 500);
+        assert_eq!(res.body["code"], "INTERNAL_ERROR");
+
+        // The crashed instance bumped its counter before trapping; a rebuilt
+        // instance starts from zero.
+        let res = app
+            .get_without_token(&routes::plugin_proxy("server-plugin", "crash-count"))
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["count"], 0);
+
+        let res = app
+            .get_without_token(&routes::plugin_proxy("server-plugin", "reflect/7"))
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["params"]["id"], "7");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn slow_plugin_handler_times_out_with_gateway_timeout() {
+        let app = TestApp::spawn_with_plugin_exec_timeout(500).await;
+
+        let started = std::time::Instant::now();
+        let res = app
+            .get_without_token(&routes::plugin_proxy("server-plugin", "spin"))
+            .await;
+
+        assert_eq!(res.status, 504);
+        assert_eq!(res.body["code"], "PLUGIN_TIMEOUT");
+        assert!(started.elapsed() < std::time::Duration::from_secs(30));
+
+        let res = app
+            .get_without_token(&routes::plugin_proxy("server-plugin", "reflect/1"))
+            .await;
+        assert_eq!(res.status, 200);
+    }
+}
+
+mod kv_batch {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn batch_set_then_batch_get_round_trips_multiple_keys() {
+        let app = TestApp::spawn_with_plugins().await;
+
+        let res = app
+            .post_without_token(
+                &routes::plugin_proxy("server-plugin", "kv-batch/scores"),
+                &json!({"entries": {"alice": "10", "bob": "20", "carol": "30"}}),
+            )
+            .await;
+        assert_eq!(res.status, 200);
+
+        let res = app
+            .post_without_token(
+                &routes::plugin_proxy("server-plugin", "kv-batch/scores/get"),
+                &json!({"keys": ["alice", "bob", "carol", "dave"]}),
+            )
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(
+            res.body["values"],
+            json!({"alice": "10", "bob": "20", "carol": "30"})
+        );
+
+        // Collections are separate namespaces, including from the default one.
+        let res = app
+            .post_without_token(
+                &routes::plugin_proxy("server-plugin", "kv-batch/other/get"),
+                &json!({"keys": ["alice"]}),
+            )
+            .await;
+        assert_eq!(res.body["values"], json!({}));
+        let res = app
+            .get_without_token(&routes::plugin_proxy("server-plugin", "kv/alice"))
+            .await;
+        assert_eq!(res.status, 404);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn batch_limit_applies_to_the_whole_batch() {
+        let app = TestApp::spawn_with_plugins().await;
+        let limit = server::host_funcs::storage::MAX_BATCH_ENTRIES;
+
+        let entries: serde_json::Map<String, serde_json::Value> =
+            (0..=limit).map(|i| (format!("k{i}"), json!("v"))).collect();
+        let res = app
+            .post_without_token(
+                &routes::plugin_proxy("server-plugin", "kv-batch/bulk"),
+                &json!({"entries": entries}),
+            )
+            .await;
+        assert_eq!(res.status, 400);
+        assert!(
+            res.body["error"]
+                .as_str()
+                .unwrap()
+                .contains("exceeds the limit")
+        );
+
+        let res = app
+            .post_without_token(
+                &routes::plugin_proxy("server-plugin", "kv-batch/bulk/get"),
+                &json!({"keys": ["k0"]}),
+            )
+            .await;
+        assert_eq!(res.body["values"], json!({}));
+
+        let entries: serde_json::Map<String, serde_json::Value> =
+            (0..limit).map(|i| (format!("k{i}"), json!("v"))).collect();
+        let res = app
+            .post_without_token(
+                &routes::plugin_proxy("server-plugin", "kv-batch/bulk"),
+                &json!({"entries": entries}),
+            )
+            .await;
+        assert_eq!(res.status, 200);
+    }
+}
+
+mod kv_keys {
+    use sea_orm::{ActiveModelTrait, Set};
+    use server::entity::plugin_storage;
+
+    use super::*;
+
+    async fn seed_batch(app: &TestApp, collection: &str, keys: &[&str]) {
+        let entries: serde_json::Map<String, serde_json::Value> =
+            keys.iter().map(|k| (k.to_string(), json!("v"))).collect();
+        let res = app
+            .post_without_token(
+                &routes::plugin_proxy("server-plugin", &format!("kv-batch/{collection}")),
+                &json!({"entries": entries}),
+            )
+            .await;
+        assert_eq!(res.status, 200);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn lists_only_the_calling_plugins_keys() {
+        let app = TestApp::spawn_with_plugins().await;
+        seed_batch(&app, "board", &["rank:1", "rank:2"]).await;
+
+        plugin_storage::ActiveModel {
+            plugin_id: Set("other-plugin".into()),
+            collection: Set("board".into()),
+            key: Set("rank:3".into()),
+            data: Set(json!("v")),
+            created_at: Set(chrono::Utc::now()),
+        }
+        .insert(&app.db)
+        .await
+        .unwrap();
+
+        let res = app
+            .get_without_token(&routes::plugin_proxy("server-plugin", "kv-keys/board"))
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["keys"], json!(["rank:1", "rank:2"]));
+        assert_eq!(res.body["truncated"], false);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn prefix_filters_keys_literally() {
+        let app = TestApp::spawn_with_plugins().await;
+        seed_batch(&app, "board", &["rank:1", "rank:2", "rank_x", "team:1"]).await;
+
+        let res = app
+            .get_without_token(&routes::plugin_proxy_with_query(
+                "server-plugin",
+                "kv-keys/board",
+                "prefix=rank:",
+            ))
+            .await;
+        assert_eq!(res.body["keys"], json!(["rank:1", "rank:2"]));
+
+        // `_` is a LIKE wildcard; it must only match itself.
+        let res = app
+            .get_without_token(&routes::plugin_proxy_with_query(
+                "server-plugin",
+                "kv-keys/board",
+                "prefix=rank_",
+            ))
+            .await;
+        assert_eq!(res.body["keys"], json!(["rank_x"]));
+
+        let res = app
+            .get_without_token(&routes::plugin_proxy_with_query(
+                "server-plugin",
+                "kv-keys/board",
+                "prefix=none",
+            ))
+            .await;
+        assert_eq!(res.body["keys"], json!([]));
+    }
+}
+
+mod standings_cache {
+    use chrono::Utc;
+    use common::{SubmissionStatus, Verdict};
+    use sea_orm::{ActiveModelTrait, Set};
+    use server::entity::{submission, submission_judgement};
+
+    use super::*;
+
+    const CACHE_HEADER: &str = "x-broccoli-cache";
+
+    fn standings_path(contest_id: i32) -> String {
+        routes::plugin_proxy("server-plugin", &format!("contests/{contest_id}/standings"))
+    }
+
+    fn metric_value(metrics: &str, result: &str) -> Option<u64> {
+        metrics
+            .lines()
+            .filter(|l| l.starts_with("broccoli_standings_cache_requests_total{"))
+            .find(|l| l.contains(&format!("result=\"{result}\"")))
+            .and_then(|l| l.rsplit(' ').next())
+            .and_then(|v| v.parse().ok())
+    }
+
+    /// Inserts a judged submission in `contest_id` with a finalized,
+    /// not-yet-current judgement and returns `(submission_id, judgement_id)`.
+    async fn seed_pending_result(
+        app: &TestApp,
+        token: &str,
+        contest_id: i32,
+        problem_id: i32,
+    ) -> (i32, i32) {
+        let user_id = app.get_with_token(routes::ME, token).await.id();
+        let now = Utc::now();
+        let sub = submission::ActiveModel {
+            files: Set(json!([{ "filename": "main.cpp", "content": "int main() {}" }])),
+            language: Set("cpp".into()),
+            user_id: Set(user_id),
+            problem_id: Set(problem_id),
+            contest_id: Set(Some(contest_id)),
+            status: Set(SubmissionStatus::Judged),
+            verdict: Set(Some(Verdict::WrongAnswer)),
+            score: Set(Some(0.0)),
+            created_at: Set(now),
+            judged_at: Set(Some(now)),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert submission");
+        let judgement = submission_judgement::ActiveModel {
+            submission_id: Set(sub.id),
+            version: Set(1),
+            is_current: Set(false),
+            is_finalized: Set(true),
+            status: Set(SubmissionStatus::Judged),
+            verdict: Set(Some(Verdict::Accepted)),
+            score: Set(Some(100.0)),
+            created_at: Set(now),
+            finalized_at: Set(Some(now)),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert judgement");
+        (sub.id, judgement.id)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn repeated_standings_requests_hit_the_cache() {
+        let app = TestApp::spawn_with_plugins().await;
+        let token = app
+            .create_user_with_role("admin_user", "securepass", "admin")
+            .await;
+        let contest_id = app.create_contest(&token, "Cached", true, true).await;
+
+        let first = app
+            .get_with_token(&standings_path(contest_id), &token)
+            .await;
+        assert_eq!(first.status, 200, "unexpected body: {}", first.body);
+        assert_eq!(first.headers[CACHE_HEADER], "miss");
+        assert_eq!(first.body["computed"], 1);
+
+        let second = app
+            .get_with_token(&standings_path(contest_id), &token)
+            .await;
+        assert_eq!(second.status, 200);
+        assert_eq!(second.headers[CACHE_HEADER], "hit");
+        assert_eq!(second.body, first.body);
+
+        let metrics = app.get_without_token("/metrics").await;
+        assert_eq!(metric_value(&metrics.text, "hit"), Some(1));
+        assert_eq!(metric_value(&metrics.text, "miss"), Some(1));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn applied_result_invalidates_only_that_contest() {
+        let app = TestApp::spawn_with_plugins().await;
+        let token = app
+            .create_user_with_role("admin_user", "securepass", "admin")
+            .await;
+        let judged_contest = app.create_contest(&token, "Judged", true, true).await;
+        let other_contest = app.create_contest(&token, "Other", true, true).await;
+        let problem_id = app.create_problem(&token, "Standings Problem").await;
+
+        app.get_with_token(&standings_path(judged_contest), &token)
+            .await;
+        app.get_with_token(&standings_path(other_contest), &token)
+            .await;
+
+        let (submission_id, judgement_id) =
+            seed_pending_result(&app, &token, judged_contest, problem_id).await;
+        let res = app
+            .post_with_token(
+                &routes::submission_judgement_apply(submission_id, judgement_id),
+                &json!({}),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+
+        let judged = app
+            .get_with_token(&standings_path(judged_contest), &token)
+            .await;
+        assert_eq!(judged.headers[CACHE_HEADER], "miss");
+        assert_eq!(judged.body["computed"], 2);
+
+        let other = app
+            .get_with_token(&standings_path(other_contest), &token)
+            .await;
+        assert_eq!(other.headers[CACHE_HEADER], "hit");
+        assert_eq!(other.body["computed"], 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn contest_setting_changes_invalidate_that_contest() {
+        let app = TestApp::spawn_with_plugins().await;
+        let token = app
+            .create_user_with_role("admin_user", "securepass", "admin")
+            .await;
+        let contest_id = app.create_contest(&token, "Settings", true, true).await;
+
+        app.get_with_token(&standings_path(contest_id), &token)
+            .await;
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "public_standings_limit": 5 }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+        let after_update = app
+            .get_with_token(&standings_path(contest_id), &token)
+            .await;
+        assert_eq!(after_update.headers[CACHE_HEADER], "miss");
+        assert_eq!(after_update.body["computed"], 2);
+
+        let res = app
+            .post_with_token(&routes::contest_freeze(contest_id), &json!({}), &token)
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+        let after_freeze = app
+            .get_with_token(&standings_path(contest_id), &token)
+            .await;
+        assert_eq!(after_freeze.headers[CACHE_HEADER], "miss");
+        assert_eq!(after_freeze.body["computed"], 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn non_standings_routes_are_not_cached() {
+        let app = TestApp::spawn_with_plugins().await;
+
+        let res = app
+            .get_without_token(&routes::plugin_proxy("server-plugin", "reflect/1"))
+            .await;
+        assert_eq!(res.status, 200);
+        assert!(res.headers.get(CACHE_HEADER).is_none());
+    }
+}
+
+mod judge_result_idempotency {
+    use chrono::Utc;
+    use common::SubmissionStatus;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+    use server::entity::{submission, submission_judgement};
+
+    use super::*;
+
+    /// Inserts a submission that is still being judged and its current
+    /// judgement, returning `(submission_id, judgement_id)`.
+    async fn seed_judging_submission(app: &TestApp, token: &str) -> (i32, i32) {
+        let user_id = app.get_with_token(routes::ME, token).await.id();
+        let problem_id = app.create_problem(token, "Idempotent Problem").await;
+        let now = Utc::now();
+        let sub = submission::ActiveModel {
+            files: Set(json!([{ "filename": "main.cpp", "content": "int main() {}" }])),
+            language: Set("cpp".into()),
+            user_id: Set(user_id),
+            problem_id: Set(problem_id),
+            status: Set(SubmissionStatus::Running),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert submission");
+        let judgement = submission_judgement::ActiveModel {
+            submission_id: Set(sub.id),
+            version: Set(1),
+            is_current: Set(true),
+            status: Set(SubmissionStatus::Running),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert judgement");
+        (sub.id, judgement.id)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn same_result_delivered_twice_is_applied_once() {
+        let app = TestApp::spawn_with_plugins().await;
+        let token = app
+            .create_user_with_role("admin_user", "securepass", "admin")
+            .await;
+        let (submission_id, judgement_id) = seed_judging_submission(&app, &token).await;
+        let path = routes::plugin_proxy(
+            "server-plugin",
+            &format!("submissions/{submission_id}/result"),
+        );
+
+        let first = app
+            .post_without_token(
+                &path,
+                &json!({ "judgement_id": judgement_id, "judge_epoch": 0, "score": 40.0 }),
+            )
+            .await;
+        assert_eq!(first.status, 200, "unexpected body: {}", first.body);
+        assert_eq!(first.body["affected"], 1);
+
+        let redelivered = app
+            .post_without_token(
+                &path,
+                &json!({ "judgement_id": judgement_id, "judge_epoch": 0, "score": 90.0 }),
+            )
+            .await;
+        assert_eq!(
+            redelivered.status, 200,
+            "unexpected body: {}",
+            redelivered.body
+        );
+        assert_eq!(redelivered.body["affected"], 0);
+
+        let sub = submission::Entity::find_by_id(submission_id)
+            .one(&app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(sub.status, SubmissionStatus::Judged);
+        assert_eq!(sub.applied_judgement_id, Some(judgement_id));
+        assert_eq!(sub.score, Some(40.0));
+        let judgement = submission_judgement::Entity::find_by_id(judgement_id)
+            .one(&app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(judgement.score, Some(40.0));
+    }
 }
 
 mod sql {
@@ -252,6 +897,54 @@ mod sql {
         assert_eq!(res.headers["Content-Type"], "text/javascript");
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn web_plugin_asset_has_etag_and_revalidates_with_not_modified() {
+        let app = TestApp::spawn_with_plugins().await;
+
+        let res = app
+            .get_without_token(&routes::plugin_asset("web-plugin", "index.js"))
+            .await;
+        assert_eq!(res.status, 200);
+        let etag = res.headers["ETag"].to_str().unwrap().to_string();
+        assert!(etag.starts_with('"') && etag.len() > 2, "{etag}");
+        assert!(res.headers.contains_key("Cache-Control"));
+
+        let res = app
+            .client
+            .get(format!(
+                "http://{}{}",
+                app.addr,
+                routes::plugin_asset("web-plugin", "index.js")
+            ))
+            .header("If-None-Match", &etag)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 304);
+        assert_eq!(res.headers()["etag"], etag.as_str());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ranged_asset_request_returns_byte_slice() {
+        let app = TestApp::spawn_with_plugins().await;
+
+        let res = app
+            .client
+            .get(format!(
+                "http://{}{}",
+                app.addr,
+                routes::plugin_asset("web-plugin", "index.js")
+            ))
+            .header("Range", "bytes=0-10")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 206);
+        let content_range = res.headers()["content-range"].to_str().unwrap().to_string();
+        assert!(content_range.starts_with("bytes 0-10/"), "{content_range}");
+        assert_eq!(res.bytes().await.unwrap().as_ref(), b"console.log");
+    }
+
     #[tokio::test]
     async fn asset_request_for_plugin_without_web_assets_returns_not_found() {
         let app = TestApp::spawn().await;
@@ -77,6 +77,8 @@ async fn insert_contest_association_for_problem(app: &TestApp, problem_id: i32)
         problem_id: Set(problem_id),
         label: Set("A".into()),
         position: Set(0),
+        max_points: Set(None),
+        result_delay_secs: Set(0),
     };
     cp.insert(&app.db).await.expect("insert contest_problem");
 }
@@ -196,6 +198,71 @@ mod problem_creation {
         assert_eq!(res.body["code"], "VALIDATION_ERROR");
     }
 
+    #[tokio::test]
+    async fn wall_time_multiplier_is_stored_and_validated() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin3", "password123", "admin")
+            .await;
+
+        let res = app
+            .post_with_token(
+                routes::PROBLEMS,
+                &json!({
+                    "title": "Slow IO",
+                    "content": "Lots of input.",
+                    "time_limit": 1000,
+                    "memory_limit": 262144,
+                    "wall_time_multiplier": 4.0
+                }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 201);
+        assert_eq!(res.body["wall_time_multiplier"], 4.0);
+
+        let res = app
+            .post_with_token(
+                routes::PROBLEMS,
+                &json!({
+                    "title": "Too Tight",
+                    "content": "Wall below CPU.",
+                    "time_limit": 1000,
+                    "memory_limit": 262144,
+                    "wall_time_multiplier": 0.5
+                }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn require_zero_exit_defaults_on_and_can_be_disabled() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin_zero_exit", "password123", "admin")
+            .await;
+
+        let id = app
+            .create_problem_with_test_case(&token, "Exit Code Problem")
+            .await;
+        let res = app.get_with_token(&routes::problem(id), &token).await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["require_zero_exit"], true);
+
+        let res = app
+            .patch_with_token(
+                &routes::problem(id),
+                &json!({"require_zero_exit": false}),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["require_zero_exit"], false);
+    }
+
     #[tokio::test]
     async fn create_problem_trims_title_whitespace() {
         let app = TestApp::spawn().await;
@@ -219,10 +286,80 @@ mod problem_creation {
         assert_eq!(res.status, 201);
         assert_eq!(res.body["title"], "Padded Title");
     }
+
+    async fn create_titled(
+        app: &TestApp,
+        token: &str,
+        title: &str,
+        force: bool,
+    ) -> crate::common::TestResponse {
+        app.post_with_token(
+            routes::PROBLEMS,
+            &json!({
+                "title": title,
+                "content": "Some content",
+                "time_limit": 1000,
+                "memory_limit": 262144,
+                "force": force
+            }),
+            token,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn near_duplicate_title_is_reported_as_similar() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin_dup1", "password123", "admin")
+            .await;
+        let original = app
+            .create_problem(&token, "Longest Common Subsequence")
+            .await;
+
+        let res = create_titled(&app, &token, "Longest Common Subsequences", false).await;
+
+        assert_eq!(res.status, 201, "{}", res.text);
+        let similar = res.body["similar_problems"].as_array().unwrap();
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0]["id"], original);
+        assert!(similar[0]["title_similarity"].as_f64().unwrap() >= 0.8);
+    }
+
+    #[tokio::test]
+    async fn distinct_title_has_no_similar_problems() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin_dup2", "password123", "admin")
+            .await;
+        app.create_problem(&token, "Longest Common Subsequence")
+            .await;
+
+        let res = create_titled(&app, &token, "Shortest Path in a Grid", false).await;
+
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert!(res.body.get("similar_problems").is_none());
+    }
+
+    #[tokio::test]
+    async fn forced_create_skips_similarity_check() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin_dup3", "password123", "admin")
+            .await;
+        app.create_problem(&token, "Longest Common Subsequence")
+            .await;
+
+        let res = create_titled(&app, &token, "Longest Common Subsequence", true).await;
+
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert!(res.body.get("similar_problems").is_none());
+    }
 }
 
 mod problem_listing {
     use super::*;
+    use server::config::PaginationConfig;
 
     #[tokio::test]
     async fn list_returns_paginated_results() {
@@ -255,6 +392,45 @@ mod problem_listing {
         assert_eq!(res.body["pagination"]["total_pages"], 2);
     }
 
+    #[tokio::test]
+    async fn list_uses_configured_page_size_defaults() {
+        let app = TestApp::spawn_with_pagination_config(PaginationConfig {
+            default_per_page: 2,
+            max_per_page: 3,
+        })
+        .await;
+        let token = app
+            .create_user_with_role("admin3", "password123", "admin")
+            .await;
+
+        for i in 0..4 {
+            app.post_with_token(
+                routes::PROBLEMS,
+                &json!({
+                    "title": format!("Problem {i}"),
+                    "content": "Content",
+                    "time_limit": 1000,
+                    "memory_limit": 262144
+                }),
+                &token,
+            )
+            .await;
+        }
+
+        let res = app.get_with_token(routes::PROBLEMS, &token).await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["data"].as_array().unwrap().len(), 2);
+        assert_eq!(res.body["pagination"]["per_page"], 2);
+
+        let res = app
+            .get_with_token(&format!("{}?per_page=50", routes::PROBLEMS), &token)
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["data"].as_array().unwrap().len(), 3);
+        assert_eq!(res.body["pagination"]["per_page"], 3);
+        assert_eq!(res.body["pagination"]["total_pages"], 2);
+    }
+
     #[tokio::test]
     async fn list_can_filter_problems_by_title_search() {
         let app = TestApp::spawn().await;
@@ -383,157 +559,742 @@ mod problem_listing {
         )
         .await;
 
-        let res = app
-            .get_with_token(
-                &format!("{}?sort_by=title&sort_order=asc", routes::PROBLEMS),
-                &token,
-            )
+        let res = app
+            .get_with_token(
+                &format!("{}?sort_by=title&sort_order=asc", routes::PROBLEMS),
+                &token,
+            )
+            .await;
+
+        assert_eq!(res.status, 200);
+        let data = res.body["data"].as_array().unwrap();
+        assert_eq!(data[0]["title"], "Apple");
+        assert_eq!(data[1]["title"], "Zebra");
+    }
+
+    #[tokio::test]
+    async fn contestant_cannot_list_problems() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("contestant2", "password123", "contestant")
+            .await;
+
+        let res = app.get_with_token(routes::PROBLEMS, &token).await;
+
+        assert_eq!(res.status, 403);
+        assert_eq!(res.body["code"], "PERMISSION_DENIED");
+    }
+}
+
+mod practice_archive {
+    use super::*;
+
+    #[tokio::test]
+    async fn contestant_sees_and_submits_to_public_problems_only() {
+        let app = TestApp::spawn().await;
+        let admin = app
+            .create_user_with_role("admin_practice", "password123", "admin")
+            .await;
+        let public_id = app
+            .create_problem_with_test_case(&admin, "Public Problem")
+            .await;
+        let private_id = app
+            .create_problem_with_test_case(&admin, "Private Problem")
+            .await;
+        let res = app
+            .patch_with_token(
+                &routes::problem(public_id),
+                &json!({"is_public": true}),
+                &admin,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["is_public"], true);
+
+        let contestant = app
+            .create_authenticated_user("practice_user", "password123")
+            .await;
+        let res = app
+            .get_with_token(routes::PRACTICE_PROBLEMS, &contestant)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        let ids: Vec<i64> = res.body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["id"].as_i64().unwrap())
+            .collect();
+        assert_eq!(ids, vec![public_id as i64]);
+        assert_eq!(res.body["pagination"]["total"], 1);
+
+        let res = app
+            .get_with_token(&routes::problem(public_id), &contestant)
+            .await;
+        assert_eq!(res.status, 200);
+        let res = app
+            .get_with_token(&routes::problem(private_id), &contestant)
+            .await;
+        assert_eq!(res.status, 404);
+
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(public_id),
+                &json!({
+                    "files": [{"filename": "main.cpp", "content": "int main() {}"}],
+                    "language": "cpp",
+                }),
+                &contestant,
+            )
+            .await;
+        assert_eq!(res.status, 201, "{}", res.text);
+    }
+
+    #[tokio::test]
+    async fn practice_list_requires_authentication() {
+        let app = TestApp::spawn().await;
+
+        let res = app.get_without_token(routes::PRACTICE_PROBLEMS).await;
+
+        assert_eq!(res.status, 401);
+    }
+}
+
+mod problem_detail {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_retrieve_a_problem_with_full_content() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin7", "password123", "admin")
+            .await;
+
+        let id = app.create_problem(&token, "Test Problem").await;
+
+        let res = app.get_with_token(&routes::problem(id), &token).await;
+
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["id"], id);
+        assert!(
+            res.body["content"].is_string(),
+            "Detail should include content"
+        );
+    }
+
+    #[tokio::test]
+    async fn cannot_retrieve_a_nonexistent_problem() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin8", "password123", "admin")
+            .await;
+
+        let res = app.get_with_token(&routes::problem(99999), &token).await;
+
+        assert_eq!(res.status, 404);
+        assert_eq!(res.body["code"], "NOT_FOUND");
+    }
+}
+
+mod problem_update {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_partially_update_a_problem() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin9", "password123", "admin")
+            .await;
+
+        let id = app.create_problem(&token, "Test Problem").await;
+
+        let res = app
+            .patch_with_token(
+                &routes::problem(id),
+                &json!({ "title": "Updated Title" }),
+                &token,
+            )
+            .await;
+
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["title"], "Updated Title");
+        assert_eq!(res.body["time_limit"], 1000);
+    }
+
+    #[tokio::test]
+    async fn cannot_update_a_nonexistent_problem() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin37", "password123", "admin")
+            .await;
+
+        let res = app
+            .patch_with_token(
+                &routes::problem(99999),
+                &json!({ "title": "Ghost" }),
+                &token,
+            )
+            .await;
+
+        assert_eq!(res.status, 404);
+        assert_eq!(res.body["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn contestant_cannot_update_a_problem() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin10", "password123", "admin")
+            .await;
+        let contestant_token = app
+            .create_user_with_role("contestant3", "password123", "contestant")
+            .await;
+
+        let id = app.create_problem(&admin_token, "Test Problem").await;
+
+        let res = app
+            .patch_with_token(
+                &routes::problem(id),
+                &json!({ "title": "Hacked" }),
+                &contestant_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 403);
+        assert_eq!(res.body["code"], "PERMISSION_DENIED");
+    }
+
+    #[tokio::test]
+    async fn empty_patch_body_returns_unchanged_problem() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin45", "password123", "admin")
+            .await;
+
+        let id = app.create_problem(&token, "Test Problem").await;
+
+        let original = app.get_with_token(&routes::problem(id), &token).await;
+        assert_eq!(original.status, 200);
+
+        let res = app
+            .patch_with_token(&routes::problem(id), &json!({}), &token)
+            .await;
+
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["title"], original.body["title"]);
+        assert_eq!(res.body["time_limit"], original.body["time_limit"]);
+        assert_eq!(res.body["updated_at"], original.body["updated_at"]);
+    }
+
+    #[tokio::test]
+    async fn legacy_show_test_details_maps_to_visibility() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin47", "password123", "admin")
+            .await;
+        let problem_id = app.create_problem(&token, "Legacy Problem").await;
+
+        let res = app
+            .patch_with_token(
+                &routes::problem(problem_id),
+                &json!({ "show_test_details": true }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["test_details_visibility"], "Full");
+        assert_eq!(res.body["show_test_details"], true);
+
+        let res = app
+            .patch_with_token(
+                &routes::problem(problem_id),
+                &json!({ "show_test_details": false }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["test_details_visibility"], "SampleOnly");
+        assert_eq!(res.body["show_test_details"], false);
+    }
+
+    #[tokio::test]
+    async fn echoed_legacy_show_test_details_keeps_visibility() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin48", "password123", "admin")
+            .await;
+        let problem_id = app.create_problem(&token, "Hidden Details").await;
+
+        let res = app
+            .patch_with_token(
+                &routes::problem(problem_id),
+                &json!({ "test_details_visibility": "None" }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["show_test_details"], false);
+
+        // An older form saving other fields sends the derived `false` back.
+        let res = app
+            .patch_with_token(
+                &routes::problem(problem_id),
+                &json!({ "title": "Renamed", "show_test_details": false }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["test_details_visibility"], "None");
+    }
+
+    #[tokio::test]
+    async fn legacy_show_test_details_column_is_migrated() {
+        use sea_orm::ConnectionTrait;
+
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin46", "password123", "admin")
+            .await;
+        let shown = app.create_problem(&token, "Shown").await;
+        let hidden = app.create_problem(&token, "Hidden").await;
+
+        app.db
+            .execute_unprepared(&format!(
+                "ALTER TABLE problem ADD COLUMN show_test_details BOOLEAN NOT NULL DEFAULT FALSE; \
+                 UPDATE problem SET show_test_details = TRUE WHERE id = {shown}"
+            ))
+            .await
+            .expect("add legacy column");
+
+        server::seed::migrate_test_details_visibility(&app.db)
+            .await
+            .expect("migrate");
+
+        let res = app.get_with_token(&routes::problem(shown), &token).await;
+        assert_eq!(res.body["test_details_visibility"], "Full");
+        let res = app.get_with_token(&routes::problem(hidden), &token).await;
+        assert_eq!(res.body["test_details_visibility"], "SampleOnly");
+
+        // Recorded as done: later boots leave the schema alone.
+        app.db
+            .execute_unprepared(
+                "ALTER TABLE problem ADD COLUMN show_test_details BOOLEAN NOT NULL DEFAULT FALSE",
+            )
+            .await
+            .expect("re-add legacy column");
+        server::seed::migrate_test_details_visibility(&app.db)
+            .await
+            .expect("migrate again");
+        let res = app.get_with_token(&routes::problem(shown), &token).await;
+        assert_eq!(res.body["test_details_visibility"], "Full");
+        let column = app
+            .db
+            .query_one_raw(sea_orm::Statement::from_string(
+                sea_orm::DatabaseBackend::Postgres,
+                "SELECT 1 AS present FROM information_schema.columns \
+                 WHERE table_schema = current_schema() \
+                   AND table_name = 'problem' \
+                   AND column_name = 'show_test_details'",
+            ))
+            .await
+            .unwrap();
+        assert!(column.is_some());
+    }
+}
+
+mod problem_versions {
+    use super::*;
+
+    #[tokio::test]
+    async fn update_records_the_previous_statement() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("version_admin", "password123", "admin")
+            .await;
+        let id = app.create_problem(&token, "Versioned Problem").await;
+        let original = app.get_with_token(&routes::problem(id), &token).await;
+
+        let res = app
+            .patch_with_token(
+                &routes::problem(id),
+                &json!({ "content": "Edited statement", "time_limit": 2000 }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+
+        let res = app
+            .get_with_token(&routes::problem_versions(id), &token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        let versions = res.body.as_array().unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0]["version"], 1);
+        assert_eq!(versions[0]["content"], original.body["content"]);
+        assert_eq!(versions[0]["time_limit"], 1000);
+    }
+
+    #[tokio::test]
+    async fn update_without_statement_changes_records_nothing() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("version_admin", "password123", "admin")
+            .await;
+        let id = app.create_problem(&token, "Versioned Problem").await;
+
+        let res = app
+            .patch_with_token(&routes::problem(id), &json!({ "difficulty": 30 }), &token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+
+        let res = app
+            .get_with_token(&routes::problem_versions(id), &token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body, json!([]));
+    }
+
+    #[tokio::test]
+    async fn restore_reverts_the_statement() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("version_admin", "password123", "admin")
+            .await;
+        let id = app.create_problem(&token, "Versioned Problem").await;
+        let original = app.get_with_token(&routes::problem(id), &token).await;
+
+        let res = app
+            .patch_with_token(
+                &routes::problem(id),
+                &json!({ "title": "Broken Title", "content": "Broken statement" }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+
+        let res = app
+            .post_with_token(&routes::problem_version_restore(id, 1), &json!({}), &token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["title"], original.body["title"]);
+        assert_eq!(res.body["content"], original.body["content"]);
+
+        let res = app.get_with_token(&routes::problem(id), &token).await;
+        assert_eq!(res.body["content"], original.body["content"]);
+
+        let res = app
+            .get_with_token(&routes::problem_versions(id), &token)
+            .await;
+        let versions = res.body.as_array().unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0]["version"], 2);
+        assert_eq!(versions[0]["content"], "Broken statement");
+    }
+
+    #[tokio::test]
+    async fn restoring_unknown_version_is_not_found() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("version_admin", "password123", "admin")
+            .await;
+        let id = app.create_problem(&token, "Versioned Problem").await;
+
+        let res = app
+            .post_with_token(&routes::problem_version_restore(id, 7), &json!({}), &token)
+            .await;
+        assert_eq!(res.status, 404);
+        assert_eq!(res.body["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn problem_setter_cannot_restore() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("version_admin", "password123", "admin")
+            .await;
+        let setter_token = app
+            .create_user_with_role("version_setter", "password123", "problem_setter")
+            .await;
+        let id = app.create_problem(&admin_token, "Versioned Problem").await;
+
+        let res = app
+            .get_with_token(&routes::problem_versions(id), &setter_token)
+            .await;
+        assert_eq!(res.status, 403);
+
+        let res = app
+            .post_with_token(
+                &routes::problem_version_restore(id, 1),
+                &json!({}),
+                &setter_token,
+            )
+            .await;
+        assert_eq!(res.status, 403);
+        assert_eq!(res.body["code"], "PERMISSION_DENIED");
+    }
+}
+
+mod problem_score_summary {
+    use super::*;
+
+    async fn add_test_case(
+        app: &TestApp,
+        pid: i32,
+        token: &str,
+        label: &str,
+        score: i32,
+        is_sample: bool,
+    ) {
+        let res = app
+            .post_with_token(
+                &routes::test_cases(pid),
+                &json!({
+                    "input": "1",
+                    "expected_output": "1",
+                    "score": score,
+                    "is_sample": is_sample,
+                    "label": label
+                }),
+                token,
+            )
+            .await;
+        assert_eq!(res.status, 201, "create test case failed: {}", res.text);
+    }
+
+    #[tokio::test]
+    async fn summary_checks_the_contest_ioi_subtasks() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("summary_admin", "password123", "admin")
+            .await;
+        let pid = app.create_problem(&token, "Summary Problem").await;
+        add_test_case(&app, pid, &token, "sample_01", 0, true).await;
+        add_test_case(&app, pid, &token, "1_01", 20, false).await;
+        add_test_case(&app, pid, &token, "1_02", 20, false).await;
+        add_test_case(&app, pid, &token, "2_01", 60, false).await;
+        let contest_id = app
+            .create_contest(&token, "Summary Contest", true, true)
+            .await;
+        app.add_problem_to_contest(contest_id, pid, &token).await;
+        let res = app
+            .put_with_token(
+                &routes::contest_problem_config_ns(contest_id, pid, "ioi", "task"),
+                &json!({ "config": { "subtasks": [
+                    { "name": "Small", "max_score": 40, "test_cases": ["1_01", "1_02"] },
+                    { "name": "Large", "max_score": 50, "test_cases": ["1_02"] },
+                ] } }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.text);
+
+        let res = app
+            .get_with_token(
+                &format!(
+                    "{}?contest_id={contest_id}",
+                    routes::problem_score_summary(pid)
+                ),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.text);
+        assert_eq!(res.body["total_score"], 100);
+        assert_eq!(res.body["test_case_count"], 4);
+        assert_eq!(
+            res.body["subtasks"],
+            json!([
+                { "name": "Small", "test_case_count": 2, "score": 40, "max_score": 40.0 },
+                { "name": "Large", "test_case_count": 1, "score": 20, "max_score": 50.0 },
+            ])
+        );
+        assert_eq!(
+            res.body["warnings"],
+            json!([
+                "Subtask 'Large' has max_score 50 but its test cases sum to 20",
+                "Test case '1_02' belongs to 2 subtasks",
+                "Test case '2_01' belongs to no subtask",
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn summary_without_contest_has_only_totals() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("summary_admin", "password123", "admin")
             .await;
+        let pid = app.create_problem(&token, "Summary Problem").await;
+        add_test_case(&app, pid, &token, "1_01", 30, false).await;
+        add_test_case(&app, pid, &token, "1_02", 45, false).await;
 
+        let res = app
+            .get_with_token(&routes::problem_score_summary(pid), &token)
+            .await;
         assert_eq!(res.status, 200);
-        let data = res.body["data"].as_array().unwrap();
-        assert_eq!(data[0]["title"], "Apple");
-        assert_eq!(data[1]["title"], "Zebra");
+        assert_eq!(res.body["total_score"], 75);
+        assert_eq!(res.body["subtasks"], json!([]));
+        assert_eq!(res.body["warnings"], json!([]));
     }
 
     #[tokio::test]
-    async fn contestant_cannot_list_problems() {
+    async fn contestant_cannot_view_score_summary() {
         let app = TestApp::spawn().await;
-        let token = app
-            .create_user_with_role("contestant2", "password123", "contestant")
+        let admin = app
+            .create_user_with_role("summary_admin", "password123", "admin")
             .await;
+        let contestant = app
+            .create_user_with_role("summary_contestant", "password123", "contestant")
+            .await;
+        let pid = app.create_problem(&admin, "Summary Problem").await;
 
-        let res = app.get_with_token(routes::PROBLEMS, &token).await;
-
+        let res = app
+            .get_with_token(&routes::problem_score_summary(pid), &contestant)
+            .await;
         assert_eq!(res.status, 403);
-        assert_eq!(res.body["code"], "PERMISSION_DENIED");
     }
 }
 
-mod problem_detail {
+mod problem_difficulty {
     use super::*;
+    use common::{SubmissionStatus, Verdict};
+    use sea_orm::{ActiveModelTrait, Set};
+    use server::entity::submission;
 
-    #[tokio::test]
-    async fn can_retrieve_a_problem_with_full_content() {
-        let app = TestApp::spawn().await;
+    async fn insert_judged(app: &TestApp, problem_id: i32, user_id: i32, verdict: Verdict) {
+        submission::ActiveModel {
+            problem_id: Set(problem_id),
+            user_id: Set(user_id),
+            language: Set("cpp".into()),
+            files: Set(json!([{"filename": "main.cpp", "content": "int main() {}"}])),
+            status: Set(SubmissionStatus::Judged),
+            verdict: Set(Some(verdict)),
+            created_at: Set(chrono::Utc::now()),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert submission");
+    }
+
+    async fn setup(app: &TestApp) -> (String, i32, i32) {
         let token = app
-            .create_user_with_role("admin7", "password123", "admin")
+            .create_user_with_role("difficulty_admin", "password123", "admin")
             .await;
-
-        let id = app.create_problem(&token, "Test Problem").await;
-
-        let res = app.get_with_token(&routes::problem(id), &token).await;
-
-        assert_eq!(res.status, 200);
-        assert_eq!(res.body["id"], id);
-        assert!(
-            res.body["content"].is_string(),
-            "Detail should include content"
-        );
+        let me = app.get_with_token(routes::ME, &token).await;
+        let pid = app.create_problem(&token, "Difficulty Problem").await;
+        (token, me.id(), pid)
     }
 
     #[tokio::test]
-    async fn cannot_retrieve_a_nonexistent_problem() {
+    async fn estimate_is_written_to_the_problem() {
         let app = TestApp::spawn().await;
-        let token = app
-            .create_user_with_role("admin8", "password123", "admin")
-            .await;
+        let (token, user_id, pid) = setup(&app).await;
+        for _ in 0..9 {
+            insert_judged(&app, pid, user_id, Verdict::WrongAnswer).await;
+        }
+        insert_judged(&app, pid, user_id, Verdict::Accepted).await;
 
-        let res = app.get_with_token(&routes::problem(99999), &token).await;
+        let res = app
+            .post_with_token(
+                &routes::problem_estimate_difficulty(pid),
+                &json!({}),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+        assert_eq!(res.body["applied"], true);
+        assert_eq!(res.body["total_submissions"], 10);
+        assert_eq!(res.body["accepted_submissions"], 1);
+        assert_eq!(res.body["distinct_solvers"], 1);
+        let estimated = res.body["estimated_difficulty"].as_i64().unwrap();
+        assert!(estimated > 50, "mostly rejected problem got {estimated}");
 
-        assert_eq!(res.status, 404);
-        assert_eq!(res.body["code"], "NOT_FOUND");
+        let problem = app.get_with_token(&routes::problem(pid), &token).await;
+        assert_eq!(problem.body["difficulty"], estimated);
+        assert_eq!(problem.body["difficulty_is_manual"], false);
     }
-}
-
-mod problem_update {
-    use super::*;
 
     #[tokio::test]
-    async fn can_partially_update_a_problem() {
+    async fn manual_difficulty_is_kept_unless_overwritten() {
         let app = TestApp::spawn().await;
-        let token = app
-            .create_user_with_role("admin9", "password123", "admin")
-            .await;
+        let (token, user_id, pid) = setup(&app).await;
+        insert_judged(&app, pid, user_id, Verdict::Accepted).await;
 
-        let id = app.create_problem(&token, "Test Problem").await;
+        let res = app
+            .patch_with_token(&routes::problem(pid), &json!({ "difficulty": 90 }), &token)
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["difficulty_is_manual"], true);
 
         let res = app
-            .patch_with_token(
-                &routes::problem(id),
-                &json!({ "title": "Updated Title" }),
+            .post_with_token(
+                &routes::problem_estimate_difficulty(pid),
+                &json!({}),
                 &token,
             )
             .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["applied"], false);
+        assert_eq!(res.body["difficulty"], 90);
 
+        let res = app
+            .post_with_token(
+                &routes::problem_estimate_difficulty(pid),
+                &json!({ "overwrite_manual": true }),
+                &token,
+            )
+            .await;
         assert_eq!(res.status, 200);
-        assert_eq!(res.body["title"], "Updated Title");
-        assert_eq!(res.body["time_limit"], 1000);
+        assert_eq!(res.body["applied"], true);
+        assert_eq!(res.body["difficulty"], res.body["estimated_difficulty"]);
+
+        let problem = app.get_with_token(&routes::problem(pid), &token).await;
+        assert_eq!(problem.body["difficulty_is_manual"], false);
     }
 
     #[tokio::test]
-    async fn cannot_update_a_nonexistent_problem() {
+    async fn problem_without_submissions_is_rejected() {
         let app = TestApp::spawn().await;
-        let token = app
-            .create_user_with_role("admin37", "password123", "admin")
-            .await;
+        let (token, _, pid) = setup(&app).await;
 
         let res = app
-            .patch_with_token(
-                &routes::problem(99999),
-                &json!({ "title": "Ghost" }),
+            .post_with_token(
+                &routes::problem_estimate_difficulty(pid),
+                &json!({}),
                 &token,
             )
             .await;
-
-        assert_eq!(res.status, 404);
-        assert_eq!(res.body["code"], "NOT_FOUND");
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
     }
 
     #[tokio::test]
-    async fn contestant_cannot_update_a_problem() {
+    async fn rejects_out_of_range_manual_difficulty() {
         let app = TestApp::spawn().await;
-        let admin_token = app
-            .create_user_with_role("admin10", "password123", "admin")
-            .await;
-        let contestant_token = app
-            .create_user_with_role("contestant3", "password123", "contestant")
-            .await;
-
-        let id = app.create_problem(&admin_token, "Test Problem").await;
+        let (token, _, pid) = setup(&app).await;
 
         let res = app
-            .patch_with_token(
-                &routes::problem(id),
-                &json!({ "title": "Hacked" }),
-                &contestant_token,
-            )
+            .patch_with_token(&routes::problem(pid), &json!({ "difficulty": 101 }), &token)
             .await;
-
-        assert_eq!(res.status, 403);
-        assert_eq!(res.body["code"], "PERMISSION_DENIED");
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
     }
 
     #[tokio::test]
-    async fn empty_patch_body_returns_unchanged_problem() {
+    async fn contestant_cannot_estimate_difficulty() {
         let app = TestApp::spawn().await;
-        let token = app
-            .create_user_with_role("admin45", "password123", "admin")
+        let (_, _, pid) = setup(&app).await;
+        let contestant = app
+            .create_user_with_role("difficulty_contestant", "password123", "contestant")
             .await;
 
-        let id = app.create_problem(&token, "Test Problem").await;
-
-        let original = app.get_with_token(&routes::problem(id), &token).await;
-        assert_eq!(original.status, 200);
-
         let res = app
-            .patch_with_token(&routes::problem(id), &json!({}), &token)
+            .post_with_token(
+                &routes::problem_estimate_difficulty(pid),
+                &json!({}),
+                &contestant,
+            )
             .await;
-
-        assert_eq!(res.status, 200);
-        assert_eq!(res.body["title"], original.body["title"]);
-        assert_eq!(res.body["time_limit"], original.body["time_limit"]);
-        assert_eq!(res.body["updated_at"], original.body["updated_at"]);
+        assert_eq!(res.status, 403);
     }
 }
 
@@ -734,6 +1495,34 @@ mod test_case_creation {
         );
     }
 
+    #[tokio::test]
+    async fn list_reports_full_input_and_output_sizes_in_bytes() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin_tc_sizes", "password123", "admin")
+            .await;
+        let pid = app.create_problem(&token, "Sized Problem").await;
+
+        let input = "é".repeat(300);
+        let res = app
+            .post_with_token(
+                &routes::test_cases(pid),
+                &json!({
+                    "input": input,
+                    "expected_output": "hello\n",
+                    "score": 10,
+                }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 201, "response body: {}", res.body);
+
+        let list_res = app.get_with_token(&routes::test_cases(pid), &token).await;
+        assert_eq!(list_res.status, 200, "list body: {}", list_res.body);
+        assert_eq!(list_res.body[0]["input_size"], 600);
+        assert_eq!(list_res.body[0]["output_size"], 6);
+    }
+
     #[tokio::test]
     async fn create_defaults_label_to_position_when_omitted() {
         let app = TestApp::spawn().await;
@@ -1184,6 +1973,71 @@ mod test_case_update {
         assert_eq!(res.body["is_sample"], true);
     }
 
+    #[tokio::test]
+    async fn is_pretest_defaults_to_false_and_can_be_toggled() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin_pretest", "password123", "admin")
+            .await;
+
+        let pid = app.create_problem(&token, "Pretest Problem").await;
+        let tc_id = app.create_test_case(pid, &token).await;
+
+        let res = app
+            .get_with_token(&routes::test_case(pid, tc_id), &token)
+            .await;
+        assert_eq!(res.body["is_pretest"], false);
+
+        let res = app
+            .patch_with_token(
+                &routes::test_case(pid, tc_id),
+                &json!({ "is_pretest": true }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["is_pretest"], true);
+
+        let res = app.get_with_token(&routes::test_cases(pid), &token).await;
+        assert_eq!(res.body[0]["is_pretest"], true);
+    }
+
+    #[tokio::test]
+    async fn accepted_outputs_can_be_set_and_cleared() {
+        let app = TestApp::spawn().await;
+        let token = app
+            .create_user_with_role("admin_accepted", "password123", "admin")
+            .await;
+
+        let pid = app.create_problem(&token, "Accepted Outputs Problem").await;
+        let tc_id = app.create_test_case(pid, &token).await;
+
+        let res = app
+            .get_with_token(&routes::test_case(pid, tc_id), &token)
+            .await;
+        assert!(res.body["accepted_outputs"].is_null());
+
+        let res = app
+            .patch_with_token(
+                &routes::test_case(pid, tc_id),
+                &json!({ "accepted_outputs": ["1 0", "0 1"] }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["accepted_outputs"], json!(["1 0", "0 1"]));
+
+        let res = app
+            .patch_with_token(
+                &routes::test_case(pid, tc_id),
+                &json!({ "accepted_outputs": null }),
+                &token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert!(res.body["accepted_outputs"].is_null());
+    }
+
     #[tokio::test]
     async fn can_patch_large_expected_output_and_round_trip_full_body() {
         let app = TestApp::spawn().await;
@@ -2104,6 +2958,144 @@ mod test_case_zip_upload {
     }
 }
 
+mod test_case_dir_import {
+    use super::*;
+    use server::config::ProblemConfig;
+
+    fn write_files(dir: &std::path::Path, files: &[(&str, &str)]) {
+        for (name, content) in files {
+            let path = dir.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).expect("create dir");
+            std::fs::write(path, content).expect("write test file");
+        }
+    }
+
+    async fn spawn_with_import_dir(base: &std::path::Path) -> TestApp {
+        TestApp::spawn_with_problem_config(ProblemConfig {
+            test_case_import_dir: Some(base.to_path_buf()),
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn imports_cases_from_a_directory_inside_the_base() {
+        let base = tempfile::tempdir().unwrap();
+        write_files(
+            &base.path().join("aplusb"),
+            &[
+                ("sample/01.in", "1 2\n"),
+                ("sample/01.ans", "3\n"),
+                ("02.in", "10 20\n"),
+                ("02.ans", "30\n"),
+                (".hidden.in", "ignored\n"),
+            ],
+        );
+        let app = spawn_with_import_dir(base.path()).await;
+        let token = app
+            .create_user_with_role("admin_dir1", "password123", "admin")
+            .await;
+        let pid = app.create_problem(&token, "Dir Import").await;
+
+        let res = app
+            .post_with_token(
+                &routes::test_cases_import_dir(pid),
+                &json!({
+                    "path": "aplusb",
+                    "input_format": "*.in",
+                    "output_format": "*.ans",
+                    "strategy": "abort"
+                }),
+                &token,
+            )
+            .await;
+
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert_eq!(res.body["created"], 2);
+        let tcs = res.body["test_cases"].as_array().unwrap();
+        assert_eq!(tcs[0]["label"], "01");
+        assert_eq!(tcs[0]["is_sample"], true);
+        assert_eq!(tcs[1]["label"], "02");
+        assert_eq!(tcs[1]["score"], 100);
+
+        let detail = app
+            .get_with_token(
+                &routes::test_case(pid, tcs[1]["id"].as_i64().unwrap() as i32),
+                &token,
+            )
+            .await;
+        assert_eq!(detail.body["input"], "10 20\n");
+        assert_eq!(detail.body["expected_output"], "30\n");
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_the_base() {
+        let root = tempfile::tempdir().unwrap();
+        let base = root.path().join("base");
+        let outside = root.path().join("outside");
+        write_files(&base, &[("ok/01.in", "1\n"), ("ok/01.ans", "1\n")]);
+        write_files(&outside, &[("01.in", "1\n"), ("01.ans", "1\n")]);
+        let app = spawn_with_import_dir(&base).await;
+        let token = app
+            .create_user_with_role("admin_dir2", "password123", "admin")
+            .await;
+        let pid = app.create_problem(&token, "Dir Import").await;
+
+        for path in [
+            "../outside".to_string(),
+            outside.display().to_string(),
+            "does-not-exist".to_string(),
+        ] {
+            let res = app
+                .post_with_token(
+                    &routes::test_cases_import_dir(pid),
+                    &json!({
+                        "path": path,
+                        "input_format": "*.in",
+                        "output_format": "*.ans",
+                        "strategy": "abort"
+                    }),
+                    &token,
+                )
+                .await;
+            assert_eq!(res.status, 400, "path {path}: {}", res.text);
+            assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        }
+
+        let list = app.get_with_token(&routes::test_cases(pid), &token).await;
+        assert!(list.body.as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn requires_problem_manage_permission() {
+        let base = tempfile::tempdir().unwrap();
+        write_files(base.path(), &[("01.in", "1\n"), ("01.ans", "1\n")]);
+        let app = spawn_with_import_dir(base.path()).await;
+        let admin = app
+            .create_user_with_role("admin_dir3", "password123", "admin")
+            .await;
+        let setter = app
+            .create_user_with_role("setter_dir3", "password123", "problem_setter")
+            .await;
+        let pid = app.create_problem(&admin, "Dir Import").await;
+
+        let res = app
+            .post_with_token(
+                &routes::test_cases_import_dir(pid),
+                &json!({
+                    "path": ".",
+                    "input_format": "*.in",
+                    "output_format": "*.ans",
+                    "strategy": "abort"
+                }),
+                &setter,
+            )
+            .await;
+
+        assert_eq!(res.status, 403);
+        assert_eq!(res.body["code"], "PERMISSION_DENIED");
+    }
+}
+
 mod bulk_delete_test_cases {
     use super::*;
 
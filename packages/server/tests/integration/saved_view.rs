@@ -0,0 +1,205 @@
+use serde_json::{Value, json};
+
+use crate::common::{TestApp, routes};
+
+fn submission_body() -> Value {
+    json!({
+        "files": [{"filename": "main.cpp", "content": "int main() {}"}],
+        "language": "cpp",
+    })
+}
+
+fn ids(body: &Value) -> Vec<i64> {
+    body["data"]
+        .as_array()
+        .expect("data should be an array")
+        .iter()
+        .map(|item| item["id"].as_i64().unwrap())
+        .collect()
+}
+
+/// Two problems with one submission each by `user1`; returns
+/// `(admin_token, first_problem_id, second_problem_id)`.
+async fn seed_submissions(app: &TestApp) -> (String, i32, i32) {
+    let admin_token = app
+        .create_user_with_role("admin1", "pass1234", "admin")
+        .await;
+    let user_token = app.create_authenticated_user("user1", "pass1234").await;
+    let first = app
+        .create_problem_with_test_case(&admin_token, "First Problem")
+        .await;
+    let second = app
+        .create_problem_with_test_case(&admin_token, "Second Problem")
+        .await;
+    for problem_id in [first, second] {
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &submission_body(),
+                &user_token,
+            )
+            .await;
+        assert_eq!(res.status, 201, "{}", res.text);
+    }
+    (admin_token, first, second)
+}
+
+#[tokio::test]
+async fn applying_a_view_matches_passing_params_directly() {
+    let app = TestApp::spawn().await;
+    let (admin_token, problem_id, _) = seed_submissions(&app).await;
+
+    let res = app
+        .post_with_token(
+            routes::VIEWS,
+            &json!({
+                "name": "First problem",
+                "resource": "submissions",
+                "params": { "problem_id": problem_id, "sort_order": "asc" },
+            }),
+            &admin_token,
+        )
+        .await;
+    assert_eq!(res.status, 201, "{}", res.text);
+    assert_eq!(res.body["resource"], "submissions");
+
+    let direct = app
+        .get_with_token(
+            &format!(
+                "{}?problem_id={problem_id}&sort_order=asc",
+                routes::SUBMISSIONS
+            ),
+            &admin_token,
+        )
+        .await;
+    assert_eq!(direct.status, 200, "{}", direct.text);
+    let via_view = app
+        .get_with_token(
+            &format!("{}?view=First%20problem", routes::SUBMISSIONS),
+            &admin_token,
+        )
+        .await;
+    assert_eq!(via_view.status, 200, "{}", via_view.text);
+
+    assert_eq!(ids(&direct.body).len(), 1);
+    assert_eq!(ids(&via_view.body), ids(&direct.body));
+}
+
+#[tokio::test]
+async fn explicit_params_override_the_view() {
+    let app = TestApp::spawn().await;
+    let (admin_token, first, second) = seed_submissions(&app).await;
+
+    let res = app
+        .post_with_token(
+            routes::VIEWS,
+            &json!({
+                "name": "First only",
+                "resource": "submissions",
+                "params": { "problem_id": first, "per_page": 1 },
+            }),
+            &admin_token,
+        )
+        .await;
+    assert_eq!(res.status, 201, "{}", res.text);
+
+    let res = app
+        .get_with_token(
+            &format!(
+                "{}?view=First%20only&problem_id={second}&per_page=5",
+                routes::SUBMISSIONS
+            ),
+            &admin_token,
+        )
+        .await;
+    assert_eq!(res.status, 200, "{}", res.text);
+    assert_eq!(res.body["pagination"]["per_page"], 5);
+    assert_eq!(res.body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(res.body["data"][0]["problem_id"], second);
+}
+
+#[tokio::test]
+async fn problem_views_apply_to_problem_listing() {
+    let app = TestApp::spawn().await;
+    let (admin_token, problem_id, _) = seed_submissions(&app).await;
+
+    let res = app
+        .post_with_token(
+            routes::VIEWS,
+            &json!({
+                "name": "Firsts",
+                "resource": "problems",
+                "params": { "search": "First" },
+            }),
+            &admin_token,
+        )
+        .await;
+    assert_eq!(res.status, 201, "{}", res.text);
+
+    let res = app
+        .get_with_token(&format!("{}?view=Firsts", routes::PROBLEMS), &admin_token)
+        .await;
+    assert_eq!(res.status, 200, "{}", res.text);
+    assert_eq!(ids(&res.body), vec![problem_id as i64]);
+}
+
+#[tokio::test]
+async fn views_are_private_to_their_owner() {
+    let app = TestApp::spawn().await;
+    let (admin_token, _, _) = seed_submissions(&app).await;
+    let res = app
+        .post_with_token(
+            routes::VIEWS,
+            &json!({ "name": "Mine", "resource": "submissions", "params": {} }),
+            &admin_token,
+        )
+        .await;
+    assert_eq!(res.status, 201, "{}", res.text);
+    let view_id = res.body["id"].as_i64().unwrap() as i32;
+
+    let other_token = app.create_authenticated_user("user2", "pass1234").await;
+    let res = app
+        .get_with_token(&format!("{}?view=Mine", routes::SUBMISSIONS), &other_token)
+        .await;
+    assert_eq!(res.status, 404);
+
+    let res = app.get_with_token(routes::VIEWS, &other_token).await;
+    assert_eq!(res.status, 200, "{}", res.text);
+    assert_eq!(res.body, json!([]));
+
+    let res = app
+        .delete_with_token(&routes::view(view_id), &other_token)
+        .await;
+    assert_eq!(res.status, 404);
+
+    let res = app
+        .delete_with_token(&routes::view(view_id), &admin_token)
+        .await;
+    assert_eq!(res.status, 204);
+}
+
+#[tokio::test]
+async fn invalid_views_are_rejected() {
+    let app = TestApp::spawn().await;
+    let token = app.create_authenticated_user("user1", "pass1234").await;
+
+    let res = app
+        .post_with_token(
+            routes::VIEWS,
+            &json!({
+                "name": "Bad",
+                "resource": "submissions",
+                "params": { "problem_id": "not a number" },
+            }),
+            &token,
+        )
+        .await;
+    assert_eq!(res.status, 400);
+    assert_eq!(res.body["code"], "VALIDATION_ERROR");
+
+    let body = json!({ "name": "Dup", "resource": "problems", "params": {} });
+    let res = app.post_with_token(routes::VIEWS, &body, &token).await;
+    assert_eq!(res.status, 201, "{}", res.text);
+    let res = app.post_with_token(routes::VIEWS, &body, &token).await;
+    assert_eq!(res.status, 409);
+}
@@ -3,8 +3,10 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use common::worker::TaskResult;
 use dashmap::DashMap;
-use mq::{MqConfig, init_mq};
-use server::config::per_replica_result_queue_name;
+use mq::{ConsumeConfig, MqConfig, init_mq};
+use server::config::{
+    contest_result_queue_name, operation_result_queue_names, per_replica_result_queue_name,
+};
 use server::consumers::consume_operation_results;
 use server::registry::OperationWaiters;
 use testcontainers::runners::AsyncRunner;
@@ -66,11 +68,15 @@ async fn per_replica_result_queue_delivers_to_originating_replica_only() {
         Arc::clone(&mq_a),
         Arc::clone(&waiters_a),
         queue_a.clone(),
+        ConsumeConfig::default(),
+        1024,
     ));
     let consumer_b = tokio::spawn(consume_operation_results(
         Arc::clone(&mq_b),
         Arc::clone(&waiters_b),
         queue_b,
+        ConsumeConfig::default(),
+        1024,
     ));
 
     tokio::time::sleep(Duration::from_millis(250)).await;
@@ -84,6 +90,7 @@ async fn per_replica_result_queue_delivers_to_originating_replica_only() {
                 success: true,
                 output: serde_json::json!({ "replica": "a" }),
                 error: None,
+                worker_id: None,
             },
             None,
         )
@@ -106,3 +113,97 @@ async fn per_replica_result_queue_delivers_to_originating_replica_only() {
     consumer_a.abort();
     consumer_b.abort();
 }
+
+#[tokio::test]
+async fn contest_result_lands_on_its_contest_result_queue() {
+    let redis = Redis::default()
+        .start()
+        .await
+        .expect("failed to start Redis container");
+    let port = redis
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("failed to get Redis port");
+    let redis_url = format!("redis://127.0.0.1:{port}");
+
+    let mq = Arc::new(
+        init_mq(MqConfig {
+            url: redis_url.clone(),
+            pool_size: 2,
+        })
+        .await
+        .expect("failed to create replica MQ client"),
+    );
+    let publisher = init_mq(MqConfig {
+        url: redis_url,
+        pool_size: 2,
+    })
+    .await
+    .expect("failed to create publisher MQ client");
+
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before UNIX_EPOCH")
+        .as_nanos();
+    let base_queue = per_replica_result_queue_name(
+        &format!("operation_results.contest_shards_{suffix}"),
+        "replica-a",
+    );
+    let shards = 4;
+    let contest_queue = contest_result_queue_name(&base_queue, shards, Some(7));
+    assert_ne!(contest_queue, base_queue);
+
+    // One waiter map per queue so the test can tell which consumer delivered.
+    let mut consumers = Vec::new();
+    let mut receivers = Vec::new();
+    for queue in operation_result_queue_names(&base_queue, shards) {
+        let waiters: OperationWaiters = Arc::new(DashMap::new());
+        let (tx, rx) = oneshot::channel();
+        waiters.insert("task-1".to_string(), tx);
+        receivers.push((queue.clone(), rx));
+        consumers.push(tokio::spawn(consume_operation_results(
+            Arc::clone(&mq),
+            waiters,
+            queue,
+            ConsumeConfig::default(),
+            1024,
+        )));
+    }
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    publisher
+        .publish(
+            &contest_queue,
+            None,
+            &TaskResult {
+                task_id: "task-1".to_string(),
+                success: true,
+                output: serde_json::json!({ "contest": 7 }),
+                error: None,
+                worker_id: None,
+            },
+            None,
+        )
+        .await
+        .expect("failed to publish task result");
+
+    for (queue, rx) in receivers {
+        if queue == contest_queue {
+            let delivered = timeout(Duration::from_secs(5), rx)
+                .await
+                .expect("contest queue consumer did not receive the result")
+                .expect("contest queue waiter dropped");
+            assert_eq!(delivered.task_id, "task-1");
+        } else {
+            assert!(
+                timeout(Duration::from_millis(200), rx).await.is_err(),
+                "{queue} must not receive a contest 7 result"
+            );
+        }
+    }
+
+    for consumer in consumers {
+        consumer.abort();
+    }
+}
@@ -0,0 +1,40 @@
+use server::seed::{SeedFile, seed_roles_from_file};
+
+use crate::common::{TestApp, routes};
+
+#[tokio::test]
+async fn custom_seed_file_adds_role_with_permissions() {
+    let app = TestApp::spawn().await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("seed.toml");
+    std::fs::write(
+        &path,
+        r#"
+[[roles]]
+name = "teaching_assistant"
+permissions = ["submission:submit", "submission:view_all"]
+"#,
+    )
+    .unwrap();
+
+    let seed = SeedFile::load(&path).unwrap();
+    seed_roles_from_file(&app.db, &seed).await.unwrap();
+    // Seeding again is a no-op rather than a conflict.
+    seed_roles_from_file(&app.db, &seed).await.unwrap();
+
+    let token = app
+        .create_user_with_role("seed_admin", "password123", "admin")
+        .await;
+    let res = app.get_with_token(routes::ROLES, &token).await;
+    assert_eq!(res.status, 200, "{}", res.text);
+    assert!(res.text.contains("teaching_assistant"));
+    assert!(res.text.contains("contestant"));
+
+    let res = app
+        .get_with_token(&routes::role_permissions("teaching_assistant"), &token)
+        .await;
+    assert_eq!(res.status, 200);
+    let mut permissions: Vec<String> = serde_json::from_value(res.body).unwrap();
+    permissions.sort();
+    assert_eq!(permissions, ["submission:submit", "submission:view_all"]);
+}
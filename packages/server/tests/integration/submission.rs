@@ -1,5 +1,6 @@
 use crate::common::{TestApp, routes};
-use serde_json::json;
+use serde_json::{Value, json};
+use server::config::SubmissionConfig;
 
 fn valid_submission_body(language: &str) -> serde_json::Value {
     json!({
@@ -27,7 +28,9 @@ mod submission_creation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = valid_submission_body("cpp");
@@ -47,7 +50,9 @@ mod submission_creation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = valid_submission_body("cpp");
@@ -66,7 +71,9 @@ mod submission_creation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let body = valid_submission_body("cpp");
         let res = app
@@ -97,7 +104,9 @@ mod submission_creation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Java Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Java Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = multi_file_submission_body();
@@ -111,18 +120,164 @@ mod submission_creation {
         assert_eq!(files[0]["filename"], "Main.java");
         assert_eq!(files[1]["filename"], "Helper.java");
     }
+
+    #[tokio::test]
+    async fn reports_aggregate_source_size_and_line_count() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Java Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &multi_file_submission_body(),
+                &user_token,
+            )
+            .await;
+        assert_eq!(res.status, 201);
+        let id = res.id();
+        assert_eq!(res.body["source_size"], 42);
+        assert_eq!(res.body["line_count"], 2);
+
+        let res = app.get_with_token(routes::SUBMISSIONS, &user_token).await;
+        assert_eq!(res.status, 200);
+        let item = &res.body["data"][0];
+        assert_eq!(item["id"], id);
+        assert_eq!(item["source_size"], 42);
+        assert_eq!(item["line_count"], 2);
+    }
 }
 
 mod submission_validation {
     use super::*;
 
+    #[tokio::test]
+    async fn java_submission_must_include_configured_main_file() {
+        let app = TestApp::spawn_with_submission_config(SubmissionConfig {
+            required_filenames: [("java".to_string(), "Main.java".to_string())].into(),
+            ..SubmissionConfig::default()
+        })
+        .await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Java Problem")
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &json!({
+                    "files": [{"filename": "Solution.java", "content": "public class Solution {}"}],
+                    "language": "java",
+                }),
+                &user_token,
+            )
+            .await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(res.body["details"]["field"], "files");
+        assert!(
+            res.body["message"].as_str().unwrap().contains("Main.java"),
+            "{}",
+            res.body
+        );
+
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &multi_file_submission_body(),
+                &user_token,
+            )
+            .await;
+        assert_eq!(res.status, 201, "{}", res.body);
+    }
+
+    #[tokio::test]
+    async fn rejects_too_many_files() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let max_files = SubmissionConfig::default().max_files;
+        let files: Vec<_> = (0..=max_files)
+            .map(|i| json!({"filename": format!("f{i}.cpp"), "content": "int x;"}))
+            .collect();
+        let body = json!({
+            "files": files,
+            "language": "cpp",
+        });
+        let res = app
+            .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
+            .await;
+
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(res.body["details"]["field"], "files");
+        assert!(
+            res.body["message"]
+                .as_str()
+                .unwrap()
+                .contains("Too many files"),
+            "{}",
+            res.text
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_single_oversized_file_by_name() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let too_large = "a".repeat(SubmissionConfig::default().max_file_size + 1);
+        let body = json!({
+            "files": [
+                {"filename": "main.cpp", "content": "int main() {}"},
+                {"filename": "big.cpp", "content": too_large},
+            ],
+            "language": "cpp",
+        });
+        let res = app
+            .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
+            .await;
+
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(res.body["details"]["field"], "files[1].content");
+        assert!(
+            res.body["message"].as_str().unwrap().contains("'big.cpp'"),
+            "{}",
+            res.text
+        );
+    }
+
     #[tokio::test]
     async fn rejects_empty_files_array() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = json!({
@@ -143,7 +298,9 @@ mod submission_validation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = json!({
@@ -164,7 +321,9 @@ mod submission_validation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = json!({
@@ -185,7 +344,9 @@ mod submission_validation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = json!({
@@ -206,7 +367,9 @@ mod submission_validation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = json!({
@@ -227,7 +390,9 @@ mod submission_validation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = json!({
@@ -248,7 +413,9 @@ mod submission_validation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = json!({
@@ -264,6 +431,7 @@ mod submission_validation {
 
         assert_eq!(res.status, 400);
         assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(res.body["details"]["field"], "files[1].filename");
         assert!(
             res.body["message"]
                 .as_str()
@@ -278,7 +446,9 @@ mod submission_validation {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = json!({
@@ -294,482 +464,2122 @@ mod submission_validation {
     }
 
     #[tokio::test]
-    async fn rejects_hidden_files() {
+    async fn omitted_language_is_detected_from_extension() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let body = json!({
-            "files": [{"filename": ".hidden", "content": "code"}],
-            "language": "cpp",
+            "files": [{"filename": "main.py", "content": "print(input())"}],
         });
         let res = app
             .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
             .await;
 
-        assert_eq!(res.status, 400);
-        assert_eq!(res.body["code"], "VALIDATION_ERROR");
-        assert!(res.body["message"].as_str().unwrap().contains("hidden"));
+        assert_eq!(res.status, 201);
+        assert_eq!(res.body["language"], "python3");
     }
-}
-
-mod rate_limiting {
-    use super::*;
 
     #[tokio::test]
-    async fn returns_429_when_rate_limit_exceeded() {
+    async fn auto_language_rejects_mixed_extensions() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
-        let body = valid_submission_body("cpp");
-
-        for i in 0..10 {
-            let res = app
-                .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
-                .await;
-            assert_eq!(res.status, 201, "Submission {} failed", i + 1);
-        }
-
+        let body = json!({
+            "files": [
+                {"filename": "main.py", "content": "print(1)"},
+                {"filename": "main.cpp", "content": "int main() {}"},
+            ],
+            "language": "auto",
+        });
         let res = app
             .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
             .await;
 
-        assert_eq!(res.status, 429);
-        assert_eq!(res.body["code"], "RATE_LIMITED");
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
     }
 
     #[tokio::test]
-    async fn rate_limit_is_per_user() {
+    async fn rejects_binary_file_content() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
-
-        let user1_token = app.create_authenticated_user("user1", "pass1234").await;
-        let user2_token = app.create_authenticated_user("user2", "pass1234").await;
-        let body = valid_submission_body("cpp");
-
-        for _ in 0..10 {
-            app.post_with_token(
-                &routes::problem_submissions(problem_id),
-                &body,
-                &user1_token,
-            )
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
             .await;
-        }
 
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let body = json!({
+            "files": [
+                {"filename": "main.cpp", "content": "int main() {}"},
+                {"filename": "util.h", "content": "int x = 1;\0\0"},
+            ],
+            "language": "cpp",
+        });
         let res = app
-            .post_with_token(
-                &routes::problem_submissions(problem_id),
-                &body,
-                &user2_token,
-            )
+            .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
             .await;
 
-        assert_eq!(res.status, 201);
+        assert_eq!(res.status, 400, "{}", res.text);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(res.body["details"]["field"], "files[1].content");
     }
-}
-
-mod submission_listing {
-    use super::*;
 
     #[tokio::test]
-    async fn user_sees_own_submissions() {
+    async fn accepts_non_ascii_utf8_content() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
-        app.create_submission(problem_id, &user_token, "cpp", "int main() {}")
+        let body = json!({
+            "files": [
+                {"filename": "main.cpp", "content": "// 你好, ünïcødé \u{FFFD}\nint main() {}"},
+            ],
+            "language": "cpp",
+        });
+        let res = app
+            .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
             .await;
 
-        let res = app.get_with_token(routes::SUBMISSIONS, &user_token).await;
-
-        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
-        let data = res.body["data"].as_array().expect("data should be array");
-        assert_eq!(data.len(), 1);
-        assert_eq!(data[0]["username"], "user1");
+        assert_eq!(res.status, 201, "{}", res.text);
     }
 
     #[tokio::test]
-    async fn user_does_not_see_other_users_submissions() {
+    async fn rejects_hidden_files() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
-
-        let user1_token = app.create_authenticated_user("user1", "pass1234").await;
-        let user2_token = app.create_authenticated_user("user2", "pass1234").await;
-
-        app.create_submission(problem_id, &user1_token, "cpp", "int main() {}")
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
             .await;
 
-        let res = app.get_with_token(routes::SUBMISSIONS, &user2_token).await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let body = json!({
+            "files": [{"filename": ".hidden", "content": "code"}],
+            "language": "cpp",
+        });
+        let res = app
+            .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
+            .await;
 
-        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
-        let data = res.body["data"].as_array().expect("data should be array");
-        assert_eq!(data.len(), 0);
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert!(res.body["message"].as_str().unwrap().contains("hidden"));
     }
+}
+
+mod no_test_cases {
+    use super::*;
+    use common::SubmissionStatus;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use server::config::NoTestCasesPolicy;
+    use server::entity::{submission, submission_judgement};
 
     #[tokio::test]
-    async fn admin_sees_all_submissions() {
+    async fn rejects_submission_to_problem_without_test_cases_by_default() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
-
-        let user1_token = app.create_authenticated_user("user1", "pass1234").await;
-        let user2_token = app.create_authenticated_user("user2", "pass1234").await;
+        let problem_id = app.create_problem(&admin_token, "Empty Problem").await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
 
-        app.create_submission(problem_id, &user1_token, "cpp", "int main() {}")
-            .await;
-        app.create_submission(problem_id, &user2_token, "java", "class Main {}")
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &valid_submission_body("cpp"),
+                &user_token,
+            )
             .await;
 
-        let res = app.get_with_token(routes::SUBMISSIONS, &admin_token).await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(res.body["message"], "Problem has no test cases");
 
-        assert_eq!(res.status, 200);
-        let data = res.body["data"].as_array().expect("data should be array");
-        assert_eq!(data.len(), 2);
+        let stored = submission::Entity::find()
+            .filter(submission::Column::ProblemId.eq(problem_id))
+            .all(&app.db)
+            .await
+            .expect("query submissions");
+        assert!(stored.is_empty());
     }
 
     #[tokio::test]
-    async fn can_filter_by_problem_id() {
+    async fn rejects_contest_submission_to_problem_without_test_cases_by_default() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem1_id = app.create_problem(&admin_token, "Problem 1").await;
-        let problem2_id = app.create_problem(&admin_token, "Problem 2").await;
-
-        app.create_submission(problem1_id, &admin_token, "cpp", "int main() {}")
+        let problem_id = app.create_problem(&admin_token, "Empty Problem").await;
+        let contest_id = app
+            .create_contest(&admin_token, "Test Contest", true, false)
             .await;
-        app.create_submission(problem2_id, &admin_token, "cpp", "int main() {}")
+        app.add_problem_to_contest(contest_id, problem_id, &admin_token)
             .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        app.register_for_contest(contest_id, &user_token).await;
 
-        let url = format!("{}?problem_id={}", routes::SUBMISSIONS, problem1_id);
-        let res = app.get_with_token(&url, &admin_token).await;
+        let res = app
+            .post_with_token(
+                &routes::contest_problem_submissions(contest_id, problem_id),
+                &valid_submission_body("cpp"),
+                &user_token,
+            )
+            .await;
 
-        assert_eq!(res.status, 200);
-        let data = res.body["data"].as_array().expect("data should be array");
-        assert_eq!(data.len(), 1);
-        assert_eq!(data[0]["problem_id"], problem1_id);
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(res.body["message"], "Problem has no test cases");
     }
 
     #[tokio::test]
-    async fn can_filter_by_language() {
-        let app = TestApp::spawn().await;
+    async fn system_error_policy_accepts_and_finalizes_submission() {
+        let app = TestApp::spawn_with_submission_config(SubmissionConfig {
+            no_test_cases: NoTestCasesPolicy::SystemError,
+            ..SubmissionConfig::default()
+        })
+        .await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app.create_problem(&admin_token, "Empty Problem").await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
 
-        app.create_submission(problem_id, &admin_token, "cpp", "int main() {}")
-            .await;
-        app.create_submission(problem_id, &admin_token, "python3", "print('hi')")
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &valid_submission_body("cpp"),
+                &user_token,
+            )
             .await;
 
-        let url = format!("{}?language=cpp", routes::SUBMISSIONS);
-        let res = app.get_with_token(&url, &admin_token).await;
+        assert_eq!(res.status, 201, "unexpected body: {}", res.body);
+        assert_eq!(res.body["status"], "SystemError");
+        assert_eq!(
+            res.body["result"]["error_message"],
+            "Problem has no test cases"
+        );
+        let submission_id = res.id();
 
-        assert_eq!(res.status, 200);
-        let data = res.body["data"].as_array().expect("data should be array");
-        assert_eq!(data.len(), 1);
-        assert_eq!(data[0]["language"], "cpp");
+        let sub = submission::Entity::find_by_id(submission_id)
+            .one(&app.db)
+            .await
+            .expect("query submission")
+            .expect("submission should exist");
+        assert_eq!(sub.status, SubmissionStatus::SystemError);
+        assert_eq!(sub.error_code.as_deref(), Some("NO_TEST_CASES"));
+        assert!(sub.judged_at.is_some());
+
+        let judgements = submission_judgement::Entity::find()
+            .filter(submission_judgement::Column::SubmissionId.eq(submission_id))
+            .all(&app.db)
+            .await
+            .expect("query judgements");
+        assert_eq!(judgements.len(), 1);
+        assert!(judgements[0].is_current);
+        assert!(judgements[0].is_finalized);
+        assert_eq!(judgements[0].status, SubmissionStatus::SystemError);
     }
+}
+
+mod rate_limiting {
+    use super::*;
 
     #[tokio::test]
-    async fn rejects_unsupported_language() {
+    async fn returns_429_when_rate_limit_exceeded() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let body = valid_submission_body("cpp");
+
+        for i in 0..10 {
+            let res = app
+                .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
+                .await;
+            assert_eq!(res.status, 201, "Submission {} failed", i + 1);
+        }
 
-        let body = json!({
-            "files": [{"filename": "main.rb", "content": "puts 'hi'"}],
-            "language": "ruby",
-        });
         let res = app
             .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
             .await;
 
-        assert_eq!(res.status, 400);
-        assert_eq!(res.body["code"], "VALIDATION_ERROR");
-        assert_eq!(res.body["message"], "Unsupported language: ruby");
+        assert_eq!(res.status, 429);
+        assert_eq!(res.body["code"], "RATE_LIMITED");
     }
 
     #[tokio::test]
-    async fn rejects_submission_format_filename_mismatch() {
+    async fn rate_limit_is_per_user() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_res = app
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user1_token = app.create_authenticated_user("user1", "pass1234").await;
+        let user2_token = app.create_authenticated_user("user2", "pass1234").await;
+        let body = valid_submission_body("cpp");
+
+        for _ in 0..10 {
+            app.post_with_token(
+                &routes::problem_submissions(problem_id),
+                &body,
+                &user1_token,
+            )
+            .await;
+        }
+
+        let res = app
             .post_with_token(
-                routes::PROBLEMS,
-                &json!({
-                    "title": "Multi-file Problem",
-                    "content": "## Description\nSolve this.",
-                    "time_limit": 1000,
-                    "memory_limit": 262144,
-                    "problem_type": "standard",
-                    "checker_format": "exact",
-                    "submission_format": {
-                        "cpp": ["main.cpp", "grader.cpp"]
-                    },
-                }),
-                &admin_token,
+                &routes::problem_submissions(problem_id),
+                &body,
+                &user2_token,
             )
             .await;
-        assert_eq!(
-            problem_res.status, 201,
-            "create_problem failed: {}",
-            problem_res.text
-        );
-        let problem_id = problem_res.id();
 
-        let user_token = app.create_authenticated_user("user1", "pass1234").await;
-        let body = json!({
-            "files": [
-                {"filename": "main.cpp", "content": "int main() {}"},
-            ],
+        assert_eq!(res.status, 201);
+    }
+}
+
+mod in_flight_limit {
+    use super::*;
+    use common::SubmissionStatus;
+    use sea_orm::{ActiveModelTrait, Set};
+    use server::entity::submission;
+
+    fn in_flight_config() -> SubmissionConfig {
+        SubmissionConfig {
+            max_in_flight_per_user: 2,
+            ..SubmissionConfig::default()
+        }
+    }
+
+    fn submission_body(i: usize) -> Value {
+        json!({
+            "files": [{"filename": "main.cpp", "content": format!("int main() {{ return {i}; }}")}],
             "language": "cpp",
-        });
+        })
+    }
+
+    #[tokio::test]
+    async fn exceeding_in_flight_cap_is_rejected() {
+        let app = TestApp::spawn_with_submission_config(in_flight_config()).await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        for i in 0..2 {
+            let res = app
+                .post_with_token(
+                    &routes::problem_submissions(problem_id),
+                    &submission_body(i),
+                    &user_token,
+                )
+                .await;
+            assert_eq!(res.status, 201, "Submission {} failed", i + 1);
+        }
+
         let res = app
-            .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &submission_body(2),
+                &user_token,
+            )
             .await;
+        assert_eq!(res.status, 429);
+        assert_eq!(res.body["code"], "TOO_MANY_IN_FLIGHT");
+        assert_eq!(res.body["details"]["limit"], 2);
 
-        assert_eq!(res.status, 400);
-        assert_eq!(res.body["code"], "VALIDATION_ERROR");
-        assert_eq!(
-            res.body["message"],
-            "Files for language 'cpp' must exactly match: grader.cpp, main.cpp",
+        let other_token = app.create_authenticated_user("user2", "pass1234").await;
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &submission_body(0),
+                &other_token,
+            )
+            .await;
+        assert_eq!(res.status, 201, "cap is per user");
+    }
+
+    #[tokio::test]
+    async fn concurrent_submissions_cannot_exceed_in_flight_cap() {
+        let app = TestApp::spawn_with_submission_config(in_flight_config()).await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let route = routes::problem_submissions(problem_id);
+        let bodies: Vec<Value> = (0..6).map(submission_body).collect();
+        let responses = futures::future::join_all(
+            bodies
+                .iter()
+                .map(|body| app.post_with_token(&route, body, &user_token)),
+        )
+        .await;
+
+        let created = responses.iter().filter(|res| res.status == 201).count();
+        assert_eq!(created, 2);
+        assert!(
+            responses
+                .iter()
+                .all(|res| res.status == 201 || res.body["code"] == "TOO_MANY_IN_FLIGHT")
         );
     }
 
     #[tokio::test]
-    async fn returns_pagination_info() {
-        let app = TestApp::spawn().await;
+    async fn finished_submission_frees_a_slot() {
+        let app = TestApp::spawn_with_submission_config(in_flight_config()).await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
 
-        for _ in 0..5 {
-            app.create_submission(problem_id, &admin_token, "cpp", "int main() {}")
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let mut ids = Vec::new();
+        for i in 0..2 {
+            let res = app
+                .post_with_token(
+                    &routes::problem_submissions(problem_id),
+                    &submission_body(i),
+                    &user_token,
+                )
                 .await;
+            assert_eq!(res.status, 201);
+            ids.push(res.id());
         }
 
-        let url = format!("{}?per_page=2", routes::SUBMISSIONS);
-        let res = app.get_with_token(&url, &admin_token).await;
+        submission::ActiveModel {
+            id: Set(ids[0]),
+            status: Set(SubmissionStatus::Judged),
+            ..Default::default()
+        }
+        .update(&app.db)
+        .await
+        .expect("finish first submission");
 
-        assert_eq!(res.status, 200);
-        assert_eq!(res.body["pagination"]["per_page"], 2);
-        assert_eq!(res.body["pagination"]["total"], 5);
-        assert_eq!(res.body["pagination"]["total_pages"], 3);
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &submission_body(2),
+                &user_token,
+            )
+            .await;
+        assert_eq!(res.status, 201);
     }
 }
 
-mod submission_detail {
+mod resubmit_cooldown {
     use super::*;
 
+    fn cooldown_config() -> SubmissionConfig {
+        SubmissionConfig {
+            resubmit_cooldown_secs: 60,
+            ..SubmissionConfig::default()
+        }
+    }
+
+    fn submission_body(i: usize) -> Value {
+        json!({
+            "files": [{"filename": "main.cpp", "content": format!("int main() {{ return {i}; }}")}],
+            "language": "cpp",
+        })
+    }
+
     #[tokio::test]
-    async fn owner_can_view_submission_detail() {
-        let app = TestApp::spawn().await;
+    async fn fast_resubmit_to_same_problem_is_rejected() {
+        let app = TestApp::spawn_with_submission_config(cooldown_config()).await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
-
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
-        let submission_id = app
-            .create_submission(problem_id, &user_token, "cpp", "int main() {}")
+
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &submission_body(0),
+                &user_token,
+            )
             .await;
+        assert_eq!(res.status, 201, "{}", res.text);
 
         let res = app
-            .get_with_token(&routes::submission(submission_id), &user_token)
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &submission_body(1),
+                &user_token,
+            )
+            .await;
+        assert_eq!(res.status, 429);
+        assert_eq!(res.body["code"], "RATE_LIMITED");
+        let retry_after: u64 = res.headers["retry-after"]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=60).contains(&retry_after), "retry_after {retry_after}");
+
+        let other_token = app.create_authenticated_user("user2", "pass1234").await;
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &submission_body(0),
+                &other_token,
+            )
             .await;
+        assert_eq!(res.status, 201, "cooldown is per user");
+    }
 
-        assert_eq!(res.status, 200);
-        assert_eq!(res.body["id"], submission_id);
-        assert!(res.body["files"].as_array().is_some());
+    #[tokio::test]
+    async fn submission_to_different_problem_is_not_delayed() {
+        let app = TestApp::spawn_with_submission_config(cooldown_config()).await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let first = app
+            .create_problem_with_test_case(&admin_token, "Problem A")
+            .await;
+        let second = app
+            .create_problem_with_test_case(&admin_token, "Problem B")
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+
+        for problem_id in [first, second] {
+            let res = app
+                .post_with_token(
+                    &routes::problem_submissions(problem_id),
+                    &submission_body(0),
+                    &user_token,
+                )
+                .await;
+            assert_eq!(res.status, 201, "problem {problem_id}: {}", res.text);
+        }
+    }
+}
+
+mod duplicate_submissions {
+    use super::*;
+
+    fn duplicate_window_config() -> SubmissionConfig {
+        SubmissionConfig {
+            duplicate_window_secs: 300,
+            ..SubmissionConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn identical_resubmit_within_window_is_rejected() {
+        let app = TestApp::spawn_with_submission_config(duplicate_window_config()).await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let body = valid_submission_body("cpp");
+        let first = app
+            .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
+            .await;
+        assert_eq!(first.status, 201);
+
+        let res = app
+            .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
+            .await;
+
+        assert_eq!(res.status, 409);
+        assert_eq!(res.body["code"], "DUPLICATE_SUBMISSION");
+        assert_eq!(res.body["details"]["submission_id"], first.body["id"]);
+    }
+
+    #[tokio::test]
+    async fn changed_resubmit_is_accepted() {
+        let app = TestApp::spawn_with_submission_config(duplicate_window_config()).await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let first = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &valid_submission_body("cpp"),
+                &user_token,
+            )
+            .await;
+        assert_eq!(first.status, 201);
+
+        let changed = json!({
+            "files": [{"filename": "main.cpp", "content": "int main() { return 0; }"}],
+            "language": "cpp",
+        });
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &changed,
+                &user_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 201);
+    }
+
+    #[tokio::test]
+    async fn identical_resubmit_by_another_user_is_accepted() {
+        let app = TestApp::spawn_with_submission_config(duplicate_window_config()).await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user1_token = app.create_authenticated_user("user1", "pass1234").await;
+        let user2_token = app.create_authenticated_user("user2", "pass1234").await;
+        let body = valid_submission_body("cpp");
+        let first = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &body,
+                &user1_token,
+            )
+            .await;
+        assert_eq!(first.status, 201);
+
+        let res = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &body,
+                &user2_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 201);
+    }
+
+    #[tokio::test]
+    async fn identical_resubmit_is_accepted_when_check_disabled() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let body = valid_submission_body("cpp");
+        for _ in 0..2 {
+            let res = app
+                .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
+                .await;
+            assert_eq!(res.status, 201);
+        }
+    }
+}
+
+mod submission_listing {
+    use super::*;
+
+    #[tokio::test]
+    async fn user_sees_own_submissions() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        app.create_submission(problem_id, &user_token, "cpp", "int main() {}")
+            .await;
+
+        let res = app.get_with_token(routes::SUBMISSIONS, &user_token).await;
+
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+        let data = res.body["data"].as_array().expect("data should be array");
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["username"], "user1");
+    }
+
+    #[tokio::test]
+    async fn user_does_not_see_other_users_submissions() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user1_token = app.create_authenticated_user("user1", "pass1234").await;
+        let user2_token = app.create_authenticated_user("user2", "pass1234").await;
+
+        app.create_submission(problem_id, &user1_token, "cpp", "int main() {}")
+            .await;
+
+        let res = app.get_with_token(routes::SUBMISSIONS, &user2_token).await;
+
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+        let data = res.body["data"].as_array().expect("data should be array");
+        assert_eq!(data.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn admin_sees_all_submissions() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user1_token = app.create_authenticated_user("user1", "pass1234").await;
+        let user2_token = app.create_authenticated_user("user2", "pass1234").await;
+
+        app.create_submission(problem_id, &user1_token, "cpp", "int main() {}")
+            .await;
+        app.create_submission(problem_id, &user2_token, "java", "class Main {}")
+            .await;
+
+        let res = app.get_with_token(routes::SUBMISSIONS, &admin_token).await;
+
+        assert_eq!(res.status, 200);
+        let data = res.body["data"].as_array().expect("data should be array");
+        assert_eq!(data.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn can_filter_by_problem_id() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem1_id = app
+            .create_problem_with_test_case(&admin_token, "Problem 1")
+            .await;
+        let problem2_id = app
+            .create_problem_with_test_case(&admin_token, "Problem 2")
+            .await;
+
+        app.create_submission(problem1_id, &admin_token, "cpp", "int main() {}")
+            .await;
+        app.create_submission(problem2_id, &admin_token, "cpp", "int main() {}")
+            .await;
+
+        let url = format!("{}?problem_id={}", routes::SUBMISSIONS, problem1_id);
+        let res = app.get_with_token(&url, &admin_token).await;
+
+        assert_eq!(res.status, 200);
+        let data = res.body["data"].as_array().expect("data should be array");
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["problem_id"], problem1_id);
+    }
+
+    #[tokio::test]
+    async fn can_filter_by_language() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        app.create_submission(problem_id, &admin_token, "cpp", "int main() {}")
+            .await;
+        app.create_submission(problem_id, &admin_token, "python3", "print('hi')")
+            .await;
+
+        let url = format!("{}?language=cpp", routes::SUBMISSIONS);
+        let res = app.get_with_token(&url, &admin_token).await;
+
+        assert_eq!(res.status, 200);
+        let data = res.body["data"].as_array().expect("data should be array");
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["language"], "cpp");
+    }
+
+    #[tokio::test]
+    async fn can_filter_by_created_range() {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+        use server::entity::submission;
+
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let old_id = app
+            .create_submission(problem_id, &admin_token, "cpp", "int main() {}")
+            .await;
+        let new_id = app
+            .create_submission(problem_id, &admin_token, "cpp", "int main() { }")
+            .await;
+        let old_time: chrono::DateTime<chrono::Utc> = "2025-01-15T12:00:00Z".parse().unwrap();
+        submission::Entity::update_many()
+            .col_expr(
+                submission::Column::CreatedAt,
+                sea_orm::sea_query::Expr::value(old_time),
+            )
+            .filter(submission::Column::Id.eq(old_id))
+            .exec(&app.db)
+            .await
+            .expect("backdate submission");
+
+        let url = format!(
+            "{}?created_after=2025-01-01T00:00:00Z&created_before=2025-02-01T00:00:00Z",
+            routes::SUBMISSIONS
+        );
+        let res = app.get_with_token(&url, &admin_token).await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+        let data = res.body["data"].as_array().expect("data should be array");
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["id"], old_id);
+
+        let url = format!(
+            "{}?created_after=2025-02-01T00:00:00%2B08:00",
+            routes::SUBMISSIONS
+        );
+        let res = app.get_with_token(&url, &admin_token).await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+        let data = res.body["data"].as_array().expect("data should be array");
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["id"], new_id);
+    }
+
+    #[tokio::test]
+    async fn invalid_created_range_is_rejected() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+
+        for params in [
+            "created_after=yesterday",
+            "created_before=2025-13-01T00:00:00Z",
+            "created_after=2025-02-01T00:00:00Z&created_before=2025-01-01T00:00:00Z",
+        ] {
+            let url = format!("{}?{params}", routes::SUBMISSIONS);
+            let res = app.get_with_token(&url, &admin_token).await;
+            assert_eq!(res.status, 400, "{params}: {}", res.body);
+            assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        }
+
+        let contest_id = app
+            .create_contest(&admin_token, "Test Contest", true, false)
+            .await;
+        let url = format!(
+            "{}?created_before=not-a-date",
+            routes::contest_submissions(contest_id)
+        );
+        let res = app.get_with_token(&url, &admin_token).await;
+        assert_eq!(res.status, 400, "unexpected body: {}", res.body);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn export_streams_one_json_object_per_filtered_submission() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem1_id = app
+            .create_problem_with_test_case(&admin_token, "Problem 1")
+            .await;
+        let problem2_id = app
+            .create_problem_with_test_case(&admin_token, "Problem 2")
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+
+        let first = app
+            .create_submission(problem1_id, &user_token, "cpp", "int main() {}")
+            .await;
+        let second = app
+            .create_submission(problem1_id, &admin_token, "cpp", "int main() { return 0; }")
+            .await;
+        app.create_submission(problem1_id, &admin_token, "python3", "print('hi')")
+            .await;
+        app.create_submission(problem2_id, &admin_token, "cpp", "int main() {}")
+            .await;
+
+        let url = format!(
+            "{}?problem_id={problem1_id}&language=cpp",
+            routes::SUBMISSIONS_EXPORT
+        );
+        let res = app.get_with_token(&url, &admin_token).await;
+
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.headers["content-type"], "application/x-ndjson");
+        let lines: Vec<serde_json::Value> = res
+            .text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let ids: Vec<i64> = lines.iter().map(|l| l["id"].as_i64().unwrap()).collect();
+        assert_eq!(ids, [first as i64, second as i64]);
+        for line in &lines {
+            assert_eq!(line["problem_id"], problem1_id);
+            assert_eq!(line["language"], "cpp");
+        }
+        assert_eq!(lines[0]["username"], "user1");
+    }
+
+    #[tokio::test]
+    async fn export_requires_view_all() {
+        let app = TestApp::spawn().await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+
+        let res = app
+            .get_with_token(routes::SUBMISSIONS_EXPORT, &user_token)
+            .await;
+
+        assert_eq!(res.status, 403);
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_language() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+
+        let body = json!({
+            "files": [{"filename": "main.rb", "content": "puts 'hi'"}],
+            "language": "ruby",
+        });
+        let res = app
+            .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
+            .await;
+
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(res.body["message"], "Unsupported language: ruby");
+    }
+
+    #[tokio::test]
+    async fn rejects_submission_format_filename_mismatch() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_res = app
+            .post_with_token(
+                routes::PROBLEMS,
+                &json!({
+                    "title": "Multi-file Problem",
+                    "content": "## Description\nSolve this.",
+                    "time_limit": 1000,
+                    "memory_limit": 262144,
+                    "problem_type": "standard",
+                    "checker_format": "exact",
+                    "submission_format": {
+                        "cpp": ["main.cpp", "grader.cpp"]
+                    },
+                }),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(
+            problem_res.status, 201,
+            "create_problem failed: {}",
+            problem_res.text
+        );
+        let problem_id = problem_res.id();
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let body = json!({
+            "files": [
+                {"filename": "main.cpp", "content": "int main() {}"},
+            ],
+            "language": "cpp",
+        });
+        let res = app
+            .post_with_token(&routes::problem_submissions(problem_id), &body, &user_token)
+            .await;
+
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(
+            res.body["message"],
+            "Files for language 'cpp' must exactly match: grader.cpp, main.cpp",
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_pagination_info() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        for _ in 0..5 {
+            app.create_submission(problem_id, &admin_token, "cpp", "int main() {}")
+                .await;
+        }
+
+        let url = format!("{}?per_page=2", routes::SUBMISSIONS);
+        let res = app.get_with_token(&url, &admin_token).await;
+
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["pagination"]["per_page"], 2);
+        assert_eq!(res.body["pagination"]["total"], 5);
+        assert_eq!(res.body["pagination"]["total_pages"], 3);
+    }
+}
+
+mod submission_detail {
+    use super::*;
+
+    #[tokio::test]
+    async fn owner_can_view_submission_detail() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let submission_id = app
+            .create_submission(problem_id, &user_token, "cpp", "int main() {}")
+            .await;
+
+        let res = app
+            .get_with_token(&routes::submission(submission_id), &user_token)
+            .await;
+
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["id"], submission_id);
+        assert!(res.body["files"].as_array().is_some());
+    }
+
+    #[tokio::test]
+    async fn non_owner_cannot_view_submission() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user1_token = app.create_authenticated_user("user1", "pass1234").await;
+        let user2_token = app.create_authenticated_user("user2", "pass1234").await;
+        let submission_id = app
+            .create_submission(problem_id, &user1_token, "cpp", "int main() {}")
+            .await;
+
+        let res = app
+            .get_with_token(&routes::submission(submission_id), &user2_token)
+            .await;
+
+        assert_eq!(res.status, 404);
+        assert_eq!(res.body["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn admin_can_view_any_submission() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let submission_id = app
+            .create_submission(problem_id, &user_token, "cpp", "int main() {}")
+            .await;
+
+        let res = app
+            .get_with_token(&routes::submission(submission_id), &admin_token)
+            .await;
+
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["id"], submission_id);
+    }
+
+    #[tokio::test]
+    async fn results_follow_test_case_position_after_reorder() {
+        use common::{SubmissionStatus, Verdict};
+        use sea_orm::{ActiveModelTrait, Set};
+        use server::entity::{submission, test_case_result};
+
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let admin_id = app.get_with_token(routes::ME, &admin_token).await.id();
+        let problem_id = app.create_problem(&admin_token, "Ordered Problem").await;
+        let tc1 = app.create_test_case(problem_id, &admin_token).await;
+        let tc2 = app.create_test_case(problem_id, &admin_token).await;
+        let tc3 = app.create_test_case(problem_id, &admin_token).await;
+
+        let now = chrono::Utc::now();
+        let sub = submission::ActiveModel {
+            files: Set(json!([{ "filename": "main.cpp", "content": "int main() {}" }])),
+            language: Set("cpp".into()),
+            user_id: Set(admin_id),
+            problem_id: Set(problem_id),
+            status: Set(SubmissionStatus::Judged),
+            verdict: Set(Some(Verdict::Accepted)),
+            created_at: Set(now),
+            judged_at: Set(Some(now)),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert submission");
+        for tc_id in [tc1, tc2, tc3] {
+            test_case_result::ActiveModel {
+                submission_id: Set(sub.id),
+                test_case_id: Set(Some(tc_id)),
+                verdict: Set(Verdict::Accepted),
+                score: Set(1.0),
+                created_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&app.db)
+            .await
+            .expect("insert test case result");
+        }
+
+        let res = app
+            .put_with_token(
+                &routes::test_cases_reorder(problem_id),
+                &json!({ "test_case_ids": [tc3, tc1, tc2] }),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 204);
+
+        let res = app
+            .get_with_token(&routes::submission(sub.id), &admin_token)
+            .await;
+        assert_eq!(res.status, 200);
+        let order: Vec<i64> = res.body["result"]["test_case_results"]
+            .as_array()
+            .expect("test case results")
+            .iter()
+            .map(|r| r["test_case_id"].as_i64().unwrap())
+            .collect();
+        assert_eq!(order, vec![tc3 as i64, tc1 as i64, tc2 as i64]);
+    }
+
+    #[tokio::test]
+    async fn pending_submissions_report_queue_position() {
+        use common::SubmissionStatus;
+        use sea_orm::{ActiveModelTrait, Set};
+        use server::entity::submission;
+
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let code = format!("int main() {{ return {i}; }}");
+            ids.push(
+                app.create_submission(problem_id, &user_token, "cpp", &code)
+                    .await,
+            );
+        }
+
+        let mut positions = Vec::new();
+        for &id in &ids {
+            let res = app
+                .get_with_token(&routes::submission(id), &user_token)
+                .await;
+            assert_eq!(res.status, 200);
+            positions.push(res.body["queue_position"].as_u64().unwrap());
+        }
+        assert_eq!(positions, vec![1, 2, 3]);
+
+        submission::ActiveModel {
+            id: Set(ids[0]),
+            status: Set(SubmissionStatus::Judged),
+            ..Default::default()
+        }
+        .update(&app.db)
+        .await
+        .expect("finish first submission");
+
+        let finished = app
+            .get_with_token(&routes::submission(ids[0]), &user_token)
+            .await;
+        assert!(finished.body["queue_position"].is_null());
+        let last = app
+            .get_with_token(&routes::submission(ids[2]), &user_token)
+            .await;
+        assert_eq!(last.body["queue_position"], 2);
+    }
+
+    #[tokio::test]
+    async fn reports_pending_seconds_then_judging_duration() {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, sea_query::Expr};
+        use server::entity::submission_judgement;
+
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let id = app
+            .create_submission(problem_id, &user_token, "cpp", "int main() {}")
+            .await;
+
+        // Pretend the judgement was queued five seconds ago.
+        submission_judgement::Entity::update_many()
+            .col_expr(
+                submission_judgement::Column::CreatedAt,
+                Expr::cust("NOW() - INTERVAL '5 seconds'"),
+            )
+            .filter(submission_judgement::Column::SubmissionId.eq(id))
+            .exec(&app.db)
+            .await
+            .expect("backdate judgement");
+
+        let pending = app
+            .get_with_token(&routes::submission(id), &user_token)
+            .await;
+        assert_eq!(pending.status, 200, "{}", pending.text);
+        assert!(pending.body["pending_seconds"].as_i64().unwrap() >= 5);
+        assert!(pending.body["judging_duration_ms"].is_null());
+
+        server::consumers::mark_submission_system_error(&app.db, id, "TEST", "finished")
+            .await
+            .expect("finalize submission");
+
+        let finished = app
+            .get_with_token(&routes::submission(id), &user_token)
+            .await;
+        assert_eq!(finished.status, 200, "{}", finished.text);
+        assert!(finished.body["pending_seconds"].is_null());
+        assert!(finished.body["judging_duration_ms"].as_i64().unwrap() >= 5000);
+    }
+
+    #[tokio::test]
+    async fn returns_404_for_nonexistent_submission() {
+        let app = TestApp::spawn().await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+
+        let res = app
+            .get_with_token(&routes::submission(99999), &user_token)
+            .await;
+
+        assert_eq!(res.status, 404);
+        assert_eq!(res.body["code"], "NOT_FOUND");
+    }
+}
+
+mod rejudge {
+    use super::*;
+    use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+    use server::entity::{role_permission, submission};
+
+    #[tokio::test]
+    async fn admin_can_rejudge_submission() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+        let submission_id = app
+            .create_submission(problem_id, &admin_token, "cpp", "int main() {}")
+            .await;
+
+        let res = app
+            .post_with_token(
+                &routes::submission_rejudge(submission_id),
+                &json!({}),
+                &admin_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["status"], "Pending");
+    }
+
+    #[tokio::test]
+    async fn contestant_cannot_rejudge() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let submission_id = app
+            .create_submission(problem_id, &user_token, "cpp", "int main() {}")
+            .await;
+
+        let res = app
+            .post_with_token(
+                &routes::submission_rejudge(submission_id),
+                &json!({}),
+                &user_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 403);
+        assert_eq!(res.body["code"], "PERMISSION_DENIED");
+    }
+
+    #[tokio::test]
+    async fn rejudge_requires_system_admin_to_clear_worker_pin() {
+        let app = TestApp::spawn().await;
+        role_permission::ActiveModel {
+            role: Set("problem_setter".to_string()),
+            permission: Set("submission:rejudge".to_string()),
+        }
+        .insert(&app.db)
+        .await
+        .expect("grant rejudge permission");
+
+        let admin_token = app
+            .create_user_with_role("admin_clear_pin_single", "pass1234", "admin")
+            .await;
+        let rejudge_token = app
+            .create_user_with_role("setter_clear_pin_single", "pass1234", "problem_setter")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Pinned Rejudge Problem")
+            .await;
+        let submission_id = app
+            .create_submission(problem_id, &admin_token, "cpp", "int main() {}")
+            .await;
+
+        submission::Entity::update_many()
+            .col_expr(
+                submission::Column::TargetWorkerId,
+                sea_orm::sea_query::Expr::value(Some("worker-a".to_string())),
+            )
+            .filter(submission::Column::Id.eq(submission_id))
+            .exec(&app.db)
+            .await
+            .expect("pin submission");
+
+        let res = app
+            .post_with_token(
+                &routes::submission_rejudge(submission_id),
+                &json!({"target_worker_id": ""}),
+                &rejudge_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 403, "unexpected body: {}", res.body);
+        assert_eq!(res.body["code"], "PERMISSION_DENIED");
+
+        let submission = submission::Entity::find_by_id(submission_id)
+            .one(&app.db)
+            .await
+            .expect("load submission")
+            .expect("submission exists");
+        assert_eq!(submission.target_worker_id.as_deref(), Some("worker-a"));
+    }
+
+    #[tokio::test]
+    async fn back_to_back_rejudges_apply_only_the_latest_result() {
+        use common::SubmissionStatus;
+        use sea_orm::sea_query::Expr;
+        use server::entity::submission_judgement;
+
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_rj_twice", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Double Rejudge Problem")
+            .await;
+        let submission_id = app
+            .create_submission(problem_id, &admin_token, "cpp", "int main() {}")
+            .await;
+
+        let load_judgements = || async {
+            submission_judgement::Entity::find()
+                .filter(submission_judgement::Column::SubmissionId.eq(submission_id))
+                .all(&app.db)
+                .await
+                .expect("load judgements")
+        };
+
+        // No judging plugin is loaded, so the first dispatch finishes
+        // straight away as a system error. Put it back in flight so the
+        // rejudge below supersedes a judgement that is still running.
+        for _ in 0..50 {
+            if load_judgements().await.iter().all(|j| j.is_finalized) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        submission_judgement::Entity::update_many()
+            .col_expr(
+                submission_judgement::Column::Status,
+                Expr::value(SubmissionStatus::Running),
+            )
+            .col_expr(
+                submission_judgement::Column::ErrorCode,
+                Expr::value(Option::<String>::None),
+            )
+            .col_expr(
+                submission_judgement::Column::IsFinalized,
+                Expr::value(false),
+            )
+            .filter(submission_judgement::Column::SubmissionId.eq(submission_id))
+            .exec(&app.db)
+            .await
+            .expect("reopen judgement");
+        let earlier_epoch = submission::Entity::find_by_id(submission_id)
+            .one(&app.db)
+            .await
+            .expect("load submission")
+            .expect("submission exists")
+            .judge_epoch;
+
+        let res = app
+            .post_with_token(
+                &routes::submission_rejudge(submission_id),
+                &json!({}),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+
+        // The in-flight judgement is closed out by the rejudge itself, not
+        // left to race the new dispatch.
+        let judgements = load_judgements().await;
+        let earlier = judgements
+            .iter()
+            .find(|j| j.judge_epoch == earlier_epoch)
+            .expect("earlier judgement");
+        assert!(!earlier.is_current);
+        assert!(earlier.is_finalized);
+        assert_eq!(earlier.status, SubmissionStatus::SystemError);
+        assert_eq!(earlier.error_code.as_deref(), Some("SUPERSEDED"));
+
+        let mut judgements = judgements;
+        for _ in 0..50 {
+            if judgements.iter().all(|j| j.is_finalized) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            judgements = load_judgements().await;
+        }
+        assert!(judgements.iter().all(|j| j.is_finalized));
+
+        let sub = submission::Entity::find_by_id(submission_id)
+            .one(&app.db)
+            .await
+            .expect("load submission")
+            .expect("submission exists");
+        let latest = judgements
+            .iter()
+            .max_by_key(|j| j.judge_epoch)
+            .expect("latest judgement");
+        assert_eq!(latest.judge_epoch, earlier_epoch + 1);
+        assert!(latest.is_current);
+        assert_eq!(sub.judge_epoch, latest.judge_epoch);
+        assert_eq!(sub.error_code, latest.error_code);
+        assert_ne!(latest.error_code.as_deref(), Some("SUPERSEDED"));
+    }
+}
+
+mod clone_as_practice {
+    use super::*;
+
+    #[tokio::test]
+    async fn clone_of_contest_submission_is_standalone_and_queued() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
+        let contest_id = app
+            .create_contest(&admin_token, "Test Contest", true, false)
+            .await;
+        app.add_problem_to_contest(contest_id, problem_id, &admin_token)
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        app.register_for_contest(contest_id, &user_token).await;
+        let original = app
+            .post_with_token(
+                &routes::contest_problem_submissions(contest_id, problem_id),
+                &valid_submission_body("cpp"),
+                &user_token,
+            )
+            .await;
+        assert_eq!(original.status, 201);
+        let original_id = original.body["id"].as_i64().unwrap() as i32;
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "end_time": "2021-01-01T00:00:00Z" }),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+
+        let res = app
+            .post_with_token(
+                &routes::submission_clone_as_practice(original_id),
+                &json!({}),
+                &user_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert_ne!(res.body["id"], original_id);
+        assert!(res.body["contest_id"].is_null());
+        assert_eq!(res.body["status"], "Pending");
+        assert_eq!(res.body["problem_id"], problem_id);
+        assert_eq!(res.body["language"], "cpp");
+        assert_eq!(res.body["username"], "user1");
+        assert_eq!(res.body["files"], original.body["files"]);
+    }
+
+    #[tokio::test]
+    async fn clone_of_running_contest_submission_is_refused() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
+        let contest_id = app
+            .create_contest(&admin_token, "Test Contest", true, false)
+            .await;
+        app.add_problem_to_contest(contest_id, problem_id, &admin_token)
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        app.register_for_contest(contest_id, &user_token).await;
+        let original = app
+            .post_with_token(
+                &routes::contest_problem_submissions(contest_id, problem_id),
+                &valid_submission_body("cpp"),
+                &user_token,
+            )
+            .await;
+        assert_eq!(original.status, 201);
+        let original_id = original.body["id"].as_i64().unwrap() as i32;
+
+        let res = app
+            .post_with_token(
+                &routes::submission_clone_as_practice(original_id),
+                &json!({}),
+                &user_token,
+            )
+            .await;
+        assert_eq!(res.status, 409, "{}", res.text);
+        assert_eq!(res.body["code"], "CONFLICT");
+
+        // Contest managers may still clone while it runs.
+        let res = app
+            .post_with_token(
+                &routes::submission_clone_as_practice(original_id),
+                &json!({}),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 201, "{}", res.text);
+    }
+
+    #[tokio::test]
+    async fn clone_preserves_multi_file_submissions() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let original = app
+            .post_with_token(
+                &routes::problem_submissions(problem_id),
+                &multi_file_submission_body(),
+                &user_token,
+            )
+            .await;
+        assert_eq!(original.status, 201);
+        let original_id = original.body["id"].as_i64().unwrap() as i32;
+
+        let res = app
+            .post_with_token(
+                &routes::submission_clone_as_practice(original_id),
+                &json!({}),
+                &user_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert_eq!(res.body["files"].as_array().unwrap().len(), 2);
+        assert_eq!(res.body["files"], original.body["files"]);
+    }
+
+    #[tokio::test]
+    async fn non_owner_cannot_clone() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user1_token = app.create_authenticated_user("user1", "pass1234").await;
+        let user2_token = app.create_authenticated_user("user2", "pass1234").await;
+        let submission_id = app
+            .create_submission(problem_id, &user1_token, "cpp", "int main() {}")
+            .await;
+
+        let res = app
+            .post_with_token(
+                &routes::submission_clone_as_practice(submission_id),
+                &json!({}),
+                &user2_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 404);
+        assert_eq!(res.body["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn admin_clone_is_owned_by_admin() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let submission_id = app
+            .create_submission(problem_id, &user_token, "cpp", "int main() {}")
+            .await;
+
+        let res = app
+            .post_with_token(
+                &routes::submission_clone_as_practice(submission_id),
+                &json!({}),
+                &admin_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert_eq!(res.body["username"], "admin1");
+    }
+}
+
+mod test_details_visibility {
+    use super::*;
+    use chrono::Utc;
+    use common::{SubmissionStatus, Verdict};
+    use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+    use server::entity::{submission, submission_judgement, test_case, test_case_result, user};
+    use server::utils::judging::cap_stored_checker_output;
+
+    /// Problem with one sample and one hidden test case and a judged
+    /// submission by `username` covering both; returns the submission id.
+    async fn seed_judged_submission(
+        app: &TestApp,
+        admin_token: &str,
+        username: &str,
+        visibility: &str,
+    ) -> i32 {
+        let problem_id = app.create_problem(admin_token, "Visibility Problem").await;
+        let res = app
+            .patch_with_token(
+                &routes::problem(problem_id),
+                &json!({ "test_details_visibility": visibility }),
+                admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["test_details_visibility"], visibility);
+
+        let now = Utc::now();
+        let user_model = user::Entity::find()
+            .filter(user::Column::Username.eq(username))
+            .one(&app.db)
+            .await
+            .expect("query user")
+            .expect("user should exist");
+        let submission = submission::ActiveModel {
+            files: Set(json!([{ "filename": "main.cpp", "content": "int main() {}" }])),
+            language: Set("cpp".into()),
+            user_id: Set(user_model.id),
+            problem_id: Set(problem_id),
+            contest_id: Set(None),
+            contest_type: Set("standard".into()),
+            status: Set(SubmissionStatus::Judged),
+            verdict: Set(Some(Verdict::WrongAnswer)),
+            score: Set(Some(50.0)),
+            created_at: Set(now),
+            judged_at: Set(Some(now)),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert submission");
+        let judgement = submission_judgement::ActiveModel {
+            submission_id: Set(submission.id),
+            version: Set(1),
+            is_current: Set(true),
+            is_finalized: Set(true),
+            status: Set(SubmissionStatus::Judged),
+            verdict: Set(Some(Verdict::WrongAnswer)),
+            score: Set(Some(50.0)),
+            created_at: Set(now),
+            finalized_at: Set(Some(now)),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert judgement");
+
+        for (position, is_sample, input, verdict) in [
+            (1, true, "sample in\n", Verdict::Accepted),
+            (2, false, "hidden in\n", Verdict::WrongAnswer),
+        ] {
+            let test_case = test_case::ActiveModel {
+                problem_id: Set(problem_id),
+                input: Set(input.to_string()),
+                expected_output: Set("out\n".to_string()),
+                score: Set(50),
+                label: Set(format!("case{position}")),
+                is_sample: Set(is_sample),
+                position: Set(position),
+                created_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&app.db)
+            .await
+            .expect("insert test case");
+            test_case_result::ActiveModel {
+                submission_id: Set(submission.id),
+                judgement_id: Set(Some(judgement.id)),
+                test_case_id: Set(Some(test_case.id)),
+                verdict: Set(verdict),
+                score: Set(if is_sample { 50.0 } else { 0.0 }),
+                stdout: Set(Some("out\n".to_string())),
+                created_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&app.db)
+            .await
+            .expect("insert test case result");
+        }
+
+        submission.id
+    }
+
+    /// Inputs of the current judgement's results as seen by `token`, in
+    /// test case order.
+    async fn visible_inputs(app: &TestApp, submission_id: i32, token: &str) -> Vec<Value> {
+        let res = app
+            .get_with_token(&routes::submission(submission_id), token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        res.body["result"]["test_case_results"]
+            .as_array()
+            .expect("test_case_results should be an array")
+            .iter()
+            .map(|r| r["input"].clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn none_hides_every_case_from_the_owner() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let submission_id = seed_judged_submission(&app, &admin_token, "user1", "None").await;
+
+        assert_eq!(
+            visible_inputs(&app, submission_id, &user_token).await,
+            vec![Value::Null, Value::Null]
+        );
+        assert_eq!(
+            visible_inputs(&app, submission_id, &admin_token).await,
+            vec![json!("sample in\n"), json!("hidden in\n")]
+        );
+    }
+
+    #[tokio::test]
+    async fn sample_only_reveals_sample_cases() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let submission_id = seed_judged_submission(&app, &admin_token, "user1", "SampleOnly").await;
+
+        assert_eq!(
+            visible_inputs(&app, submission_id, &user_token).await,
+            vec![json!("sample in\n"), Value::Null]
+        );
+    }
+
+    #[tokio::test]
+    async fn full_reveals_every_case() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let submission_id = seed_judged_submission(&app, &admin_token, "user1", "Full").await;
+
+        assert_eq!(
+            visible_inputs(&app, submission_id, &user_token).await,
+            vec![json!("sample in\n"), json!("hidden in\n")]
+        );
+    }
+
+    #[tokio::test]
+    async fn half_passing_submission_reports_fifty_percent() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let submission_id = seed_judged_submission(&app, &admin_token, "user1", "Full").await;
+
+        let res = app
+            .get_with_token(&routes::submission(submission_id), &user_token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["result"]["score"], 50.0);
+        assert_eq!(res.body["result"]["score_percentage"], 50.0);
+    }
+
+    #[tokio::test]
+    async fn verbose_checker_output_is_truncated_and_gated() {
+        let app = TestApp::spawn_with_submission_config(SubmissionConfig {
+            max_checker_output_bytes: 16,
+            ..SubmissionConfig::default()
+        })
+        .await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let submission_id = seed_judged_submission(&app, &admin_token, "user1", "SampleOnly").await;
+
+        let rows = test_case_result::Entity::find()
+            .filter(test_case_result::Column::SubmissionId.eq(submission_id))
+            .all(&app.db)
+            .await
+            .unwrap();
+        for row in rows {
+            let mut active: test_case_result::ActiveModel = row.into();
+            active.checker_output = Set(Some("diff ".repeat(1000)));
+            active.update(&app.db).await.unwrap();
+        }
+
+        let checker_outputs = |body: &Value| -> Vec<Value> {
+            body["result"]["test_case_results"]
+                .as_array()
+                .expect("test_case_results should be an array")
+                .iter()
+                .map(|r| r["checker_output"].clone())
+                .collect()
+        };
+        let truncated = json!(format!("{}\n... (truncated)", &"diff ".repeat(4)[..16]));
+
+        let res = app
+            .get_with_token(&routes::submission(submission_id), &user_token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(
+            checker_outputs(&res.body),
+            vec![truncated.clone(), Value::Null]
+        );
+
+        let res = app
+            .get_with_token(&routes::submission(submission_id), &admin_token)
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(
+            checker_outputs(&res.body),
+            vec![truncated.clone(), truncated]
+        );
+
+        let other_token = app.create_authenticated_user("user2", "pass1234").await;
+        let res = app
+            .get_with_token(&routes::submission(submission_id), &other_token)
+            .await;
+        assert_eq!(res.status, 404, "{}", res.text);
     }
 
     #[tokio::test]
-    async fn non_owner_cannot_view_submission() {
+    async fn stored_checker_output_is_capped() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        app.create_authenticated_user("user1", "pass1234").await;
+        let submission_id = seed_judged_submission(&app, &admin_token, "user1", "Full").await;
 
-        let user1_token = app.create_authenticated_user("user1", "pass1234").await;
-        let user2_token = app.create_authenticated_user("user2", "pass1234").await;
-        let submission_id = app
-            .create_submission(problem_id, &user1_token, "cpp", "int main() {}")
-            .await;
+        let rows = test_case_result::Entity::find()
+            .filter(test_case_result::Column::SubmissionId.eq(submission_id))
+            .all(&app.db)
+            .await
+            .unwrap();
+        let judgement_id = rows[0].judgement_id.unwrap();
+        for (row, output) in rows.into_iter().zip(["diff ".repeat(1000), "short".into()]) {
+            let mut active: test_case_result::ActiveModel = row.into();
+            active.checker_output = Set(Some(output));
+            active.update(&app.db).await.unwrap();
+        }
 
-        let res = app
-            .get_with_token(&routes::submission(submission_id), &user2_token)
-            .await;
+        cap_stored_checker_output(&app.db, submission_id, judgement_id, 16)
+            .await
+            .unwrap();
 
-        assert_eq!(res.status, 404);
-        assert_eq!(res.body["code"], "NOT_FOUND");
+        let mut stored: Vec<Option<String>> = test_case_result::Entity::find()
+            .filter(test_case_result::Column::SubmissionId.eq(submission_id))
+            .all(&app.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.checker_output)
+            .collect();
+        stored.sort();
+        assert_eq!(
+            stored,
+            vec![
+                Some(format!("{}\n... (truncated)", &"diff ".repeat(4)[..16])),
+                Some("short".to_string()),
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn admin_can_view_any_submission() {
+    async fn judging_worker_is_stored_and_shown_to_view_all_holders() {
         let app = TestApp::spawn().await;
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
-
         let user_token = app.create_authenticated_user("user1", "pass1234").await;
-        let submission_id = app
-            .create_submission(problem_id, &user_token, "cpp", "int main() {}")
-            .await;
+        let submission_id = seed_judged_submission(&app, &admin_token, "user1", "Full").await;
+
+        let sub = submission::Entity::find_by_id(submission_id)
+            .one(&app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        let mut active: submission::ActiveModel = sub.into();
+        active.judged_by = Set(Some("judge-node-7".into()));
+        active.update(&app.db).await.unwrap();
+
+        let stored = submission::Entity::find_by_id(submission_id)
+            .one(&app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.judged_by.as_deref(), Some("judge-node-7"));
 
         let res = app
             .get_with_token(&routes::submission(submission_id), &admin_token)
             .await;
-
-        assert_eq!(res.status, 200);
-        assert_eq!(res.body["id"], submission_id);
-    }
-
-    #[tokio::test]
-    async fn returns_404_for_nonexistent_submission() {
-        let app = TestApp::spawn().await;
-        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["judged_by"], "judge-node-7");
 
         let res = app
-            .get_with_token(&routes::submission(99999), &user_token)
+            .get_with_token(&routes::submission(submission_id), &user_token)
             .await;
-
-        assert_eq!(res.status, 404);
-        assert_eq!(res.body["code"], "NOT_FOUND");
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert!(res.body["judged_by"].is_null());
     }
 }
 
-mod rejudge {
+mod submission_comments {
     use super::*;
-    use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
-    use server::entity::{role_permission, submission};
 
-    #[tokio::test]
-    async fn admin_can_rejudge_submission() {
-        let app = TestApp::spawn().await;
+    /// Pending submission by a fresh `user1`; returns
+    /// `(admin_token, user_token, submission_id)`.
+    async fn setup(app: &TestApp) -> (String, String, i32) {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
-        let submission_id = app
-            .create_submission(problem_id, &admin_token, "cpp", "int main() {}")
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Test Problem")
             .await;
-
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
         let res = app
             .post_with_token(
-                &routes::submission_rejudge(submission_id),
-                &json!({}),
-                &admin_token,
+                &routes::problem_submissions(problem_id),
+                &valid_submission_body("cpp"),
+                &user_token,
             )
             .await;
-
-        assert_eq!(res.status, 200);
-        assert_eq!(res.body["status"], "Pending");
+        assert_eq!(res.status, 201, "{}", res.text);
+        let submission_id = res.body["id"].as_i64().unwrap() as i32;
+        (admin_token, user_token, submission_id)
     }
 
     #[tokio::test]
-    async fn contestant_cannot_rejudge() {
+    async fn owner_sees_only_public_comments() {
         let app = TestApp::spawn().await;
-        let admin_token = app
-            .create_user_with_role("admin1", "pass1234", "admin")
+        let (admin_token, user_token, submission_id) = setup(&app).await;
+
+        for (content, is_public) in [("Private note", false), ("Check n = 0", true)] {
+            let res = app
+                .post_with_token(
+                    &routes::submission_comments(submission_id),
+                    &json!({ "content": content, "is_public": is_public }),
+                    &admin_token,
+                )
+                .await;
+            assert_eq!(res.status, 201, "{}", res.text);
+            assert_eq!(res.body["content"], content);
+            assert_eq!(res.body["author_name"], "admin1");
+        }
+
+        let res = app
+            .get_with_token(&routes::submission_comments(submission_id), &admin_token)
             .await;
-        let problem_id = app.create_problem(&admin_token, "Test Problem").await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        let contents: Vec<&str> = res
+            .body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["content"].as_str().unwrap())
+            .collect();
+        assert_eq!(contents, vec!["Private note", "Check n = 0"]);
 
-        let user_token = app.create_authenticated_user("user1", "pass1234").await;
-        let submission_id = app
-            .create_submission(problem_id, &user_token, "cpp", "int main() {}")
+        let res = app
+            .get_with_token(&routes::submission_comments(submission_id), &user_token)
             .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        let comments = res.body.as_array().unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0]["content"], "Check n = 0");
+        assert_eq!(comments[0]["is_public"], true);
+    }
+
+    #[tokio::test]
+    async fn owner_cannot_comment() {
+        let app = TestApp::spawn().await;
+        let (_, user_token, submission_id) = setup(&app).await;
 
         let res = app
             .post_with_token(
-                &routes::submission_rejudge(submission_id),
-                &json!({}),
+                &routes::submission_comments(submission_id),
+                &json!({ "content": "Please regrade", "is_public": true }),
                 &user_token,
             )
             .await;
-
         assert_eq!(res.status, 403);
         assert_eq!(res.body["code"], "PERMISSION_DENIED");
     }
 
     #[tokio::test]
-    async fn rejudge_requires_system_admin_to_clear_worker_pin() {
+    async fn empty_comment_is_rejected() {
         let app = TestApp::spawn().await;
-        role_permission::ActiveModel {
-            role: Set("problem_setter".to_string()),
-            permission: Set("submission:rejudge".to_string()),
-        }
-        .insert(&app.db)
-        .await
-        .expect("grant rejudge permission");
-
-        let admin_token = app
-            .create_user_with_role("admin_clear_pin_single", "pass1234", "admin")
-            .await;
-        let rejudge_token = app
-            .create_user_with_role("setter_clear_pin_single", "pass1234", "problem_setter")
-            .await;
-        let problem_id = app
-            .create_problem(&admin_token, "Pinned Rejudge Problem")
-            .await;
-        let submission_id = app
-            .create_submission(problem_id, &admin_token, "cpp", "int main() {}")
-            .await;
-
-        submission::Entity::update_many()
-            .col_expr(
-                submission::Column::TargetWorkerId,
-                sea_orm::sea_query::Expr::value(Some("worker-a".to_string())),
-            )
-            .filter(submission::Column::Id.eq(submission_id))
-            .exec(&app.db)
-            .await
-            .expect("pin submission");
+        let (admin_token, _, submission_id) = setup(&app).await;
 
         let res = app
             .post_with_token(
-                &routes::submission_rejudge(submission_id),
-                &json!({"target_worker_id": ""}),
-                &rejudge_token,
+                &routes::submission_comments(submission_id),
+                &json!({ "content": "   " }),
+                &admin_token,
             )
             .await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+    }
 
-        assert_eq!(res.status, 403, "unexpected body: {}", res.body);
-        assert_eq!(res.body["code"], "PERMISSION_DENIED");
+    #[tokio::test]
+    async fn other_users_cannot_list_comments() {
+        let app = TestApp::spawn().await;
+        let (_, _, submission_id) = setup(&app).await;
+        let other_token = app.create_authenticated_user("user2", "pass1234").await;
 
-        let submission = submission::Entity::find_by_id(submission_id)
-            .one(&app.db)
-            .await
-            .expect("load submission")
-            .expect("submission exists");
-        assert_eq!(submission.target_worker_id.as_deref(), Some("worker-a"));
+        let res = app
+            .get_with_token(&routes::submission_comments(submission_id), &other_token)
+            .await;
+        assert_eq!(res.status, 404);
     }
 }
 
@@ -894,7 +2704,9 @@ mod judgement_history {
         let admin_token = app
             .create_user_with_role("admin_jhist1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "History Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "History Problem")
+            .await;
         let (submission_id, _old_judgement_id, current_judgement_id) =
             seed_history(&app, "admin_jhist1", problem_id).await;
 
@@ -924,7 +2736,7 @@ mod judgement_history {
             .create_user_with_role("admin_jhist2", "pass1234", "admin")
             .await;
         let problem_id = app
-            .create_problem(&admin_token, "History List Problem")
+            .create_problem_with_test_case(&admin_token, "History List Problem")
             .await;
         let (submission_id, _, _) = seed_history(&app, "admin_jhist2", problem_id).await;
 
@@ -953,7 +2765,9 @@ mod judgement_history {
         let admin_token = app
             .create_user_with_role("admin_jhist3", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Deferred Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Deferred Problem")
+            .await;
         let (submission_id, _old_judgement_id, current_judgement_id) =
             seed_history(&app, "admin_jhist3", problem_id).await;
 
@@ -994,7 +2808,9 @@ mod judgement_history {
         let admin_token = app
             .create_user_with_role("admin_jhist4", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Apply Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Apply Problem")
+            .await;
         let (submission_id, old_judgement_id, _current_judgement_id) =
             seed_history(&app, "admin_jhist4", problem_id).await;
 
@@ -1024,74 +2840,232 @@ mod judgement_history {
         let admin_token = app
             .create_user_with_role("admin_jhist5", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Discard Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Discard Problem")
+            .await;
         let (submission_id, old_judgement_id, current_judgement_id) =
             seed_history(&app, "admin_jhist5", problem_id).await;
 
         let res = app
             .post_with_token(
-                &routes::submission_judgement_discard(submission_id, old_judgement_id),
+                &routes::submission_judgement_discard(submission_id, old_judgement_id),
+                &json!({}),
+                &admin_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 204, "unexpected body: {}", res.body);
+        let old = submission_judgement::Entity::find_by_id(old_judgement_id)
+            .one(&app.db)
+            .await
+            .expect("query discarded judgement");
+        assert!(old.is_none());
+
+        let current_rows = test_case_result::Entity::find()
+            .filter(test_case_result::Column::JudgementId.eq(Some(current_judgement_id)))
+            .all(&app.db)
+            .await
+            .expect("query current rows");
+        assert_eq!(current_rows.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn discard_rejects_pending_non_current_judgement() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_jhist6", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Pending Discard Problem")
+            .await;
+        let (submission_id, _, _) = seed_history(&app, "admin_jhist6", problem_id).await;
+
+        let pending = submission_judgement::ActiveModel {
+            submission_id: Set(submission_id),
+            version: Set(12),
+            is_current: Set(false),
+            is_finalized: Set(false),
+            triggered_by_user_id: Set(None),
+            status: Set(SubmissionStatus::Running),
+            judge_epoch: Set(12),
+            created_at: Set(Utc::now()),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert pending judgement");
+
+        let res = app
+            .post_with_token(
+                &routes::submission_judgement_discard(submission_id, pending.id),
+                &json!({}),
+                &admin_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 409, "unexpected body: {}", res.body);
+        let still_exists = submission_judgement::Entity::find_by_id(pending.id)
+            .one(&app.db)
+            .await
+            .expect("query pending judgement")
+            .is_some();
+        assert!(still_exists);
+    }
+
+    #[tokio::test]
+    async fn recompute_reaggregates_tampered_rows_without_rejudging() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_jhist7", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Recompute Problem")
+            .await;
+        let (submission_id, _, current_judgement_id) =
+            seed_history(&app, "admin_jhist7", problem_id).await;
+
+        let row = test_case_result::Entity::find()
+            .filter(test_case_result::Column::JudgementId.eq(Some(current_judgement_id)))
+            .one(&app.db)
+            .await
+            .expect("query current row")
+            .expect("current row should exist");
+        let mut tampered: test_case_result::ActiveModel = row.clone().into();
+        tampered.verdict = Set(Verdict::Accepted);
+        tampered.score = Set(60.0);
+        tampered.time_used = Set(Some(15));
+        tampered.memory_used = Set(Some(256));
+        tampered.update(&app.db).await.expect("tamper row");
+        test_case_result::ActiveModel {
+            submission_id: Set(submission_id),
+            judgement_id: Set(Some(current_judgement_id)),
+            test_case_id: Set(row.test_case_id),
+            run_index: Set(None),
+            verdict: Set(Verdict::WrongAnswer),
+            score: Set(0.0),
+            time_used: Set(Some(40)),
+            memory_used: Set(Some(64)),
+            created_at: Set(Utc::now()),
+            ..Default::default()
+        }
+        .insert(&app.db)
+        .await
+        .expect("insert extra row");
+
+        let res = app
+            .post_with_token(
+                &routes::submission_recompute(submission_id),
+                &json!({}),
+                &admin_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+        assert_eq!(res.body["status"], "Judged");
+        assert_eq!(res.body["result"]["verdict"], "WrongAnswer");
+        assert_eq!(res.body["result"]["score"], 60.0);
+        assert_eq!(res.body["result"]["time_used"], 40);
+        assert_eq!(res.body["result"]["memory_used"], 256);
+
+        let sub = submission::Entity::find_by_id(submission_id)
+            .one(&app.db)
+            .await
+            .expect("query submission")
+            .expect("submission should exist");
+        assert_eq!(sub.status, SubmissionStatus::Judged);
+        assert_eq!(sub.verdict, Some(Verdict::WrongAnswer));
+        assert_eq!(sub.score, Some(60.0));
+        assert_eq!(sub.judge_epoch, 11);
+
+        let judgements = submission_judgement::Entity::find()
+            .filter(submission_judgement::Column::SubmissionId.eq(submission_id))
+            .all(&app.db)
+            .await
+            .expect("query judgements");
+        assert_eq!(
+            judgements.len(),
+            2,
+            "recompute must not create a new judgement"
+        );
+        let current = judgements
+            .iter()
+            .find(|j| j.id == current_judgement_id)
+            .expect("current judgement should exist");
+        assert!(current.is_current);
+        assert_eq!(current.judge_epoch, 11);
+        assert_eq!(current.verdict, Some(Verdict::WrongAnswer));
+        assert_eq!(current.score, Some(60.0));
+        assert_eq!(current.time_used, Some(40));
+        assert_eq!(current.memory_used, Some(256));
+    }
+
+    #[tokio::test]
+    async fn recompute_refuses_submissions_scored_per_subtask() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_jhist9", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Recompute Subtask Problem")
+            .await;
+        let (submission_id, _, current_judgement_id) =
+            seed_history(&app, "admin_jhist9", problem_id).await;
+
+        let judgement = submission_judgement::Entity::find_by_id(current_judgement_id)
+            .one(&app.db)
+            .await
+            .expect("query current judgement")
+            .expect("current judgement should exist");
+        let score_before = judgement.score;
+        let mut grouped: submission_judgement::ActiveModel = judgement.into();
+        grouped.subtask_results = Set(Some(json!([
+            { "name": "Group 1", "score": 0.0, "max_score": 100.0, "verdict": "WrongAnswer" }
+        ])));
+        grouped
+            .update(&app.db)
+            .await
+            .expect("set subtask breakdown");
+
+        let res = app
+            .post_with_token(
+                &routes::submission_recompute(submission_id),
                 &json!({}),
                 &admin_token,
             )
             .await;
 
-        assert_eq!(res.status, 204, "unexpected body: {}", res.body);
-        let old = submission_judgement::Entity::find_by_id(old_judgement_id)
+        assert_eq!(res.status, 409, "unexpected body: {}", res.body);
+        assert_eq!(res.body["code"], "CONFLICT");
+        let current = submission_judgement::Entity::find_by_id(current_judgement_id)
             .one(&app.db)
             .await
-            .expect("query discarded judgement");
-        assert!(old.is_none());
-
-        let current_rows = test_case_result::Entity::find()
-            .filter(test_case_result::Column::JudgementId.eq(Some(current_judgement_id)))
-            .all(&app.db)
-            .await
-            .expect("query current rows");
-        assert_eq!(current_rows.len(), 1);
+            .expect("query current judgement")
+            .expect("current judgement should exist");
+        assert_eq!(current.score, score_before);
     }
 
     #[tokio::test]
-    async fn discard_rejects_pending_non_current_judgement() {
+    async fn contestant_cannot_recompute() {
         let app = TestApp::spawn().await;
         let admin_token = app
-            .create_user_with_role("admin_jhist6", "pass1234", "admin")
+            .create_user_with_role("admin_jhist8", "pass1234", "admin")
             .await;
         let problem_id = app
-            .create_problem(&admin_token, "Pending Discard Problem")
+            .create_problem_with_test_case(&admin_token, "Recompute Perm Problem")
             .await;
-        let (submission_id, _, _) = seed_history(&app, "admin_jhist6", problem_id).await;
-
-        let pending = submission_judgement::ActiveModel {
-            submission_id: Set(submission_id),
-            version: Set(12),
-            is_current: Set(false),
-            is_finalized: Set(false),
-            triggered_by_user_id: Set(None),
-            status: Set(SubmissionStatus::Running),
-            judge_epoch: Set(12),
-            created_at: Set(Utc::now()),
-            ..Default::default()
-        }
-        .insert(&app.db)
-        .await
-        .expect("insert pending judgement");
+        let (submission_id, _, _) = seed_history(&app, "admin_jhist8", problem_id).await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
 
         let res = app
             .post_with_token(
-                &routes::submission_judgement_discard(submission_id, pending.id),
+                &routes::submission_recompute(submission_id),
                 &json!({}),
-                &admin_token,
+                &user_token,
             )
             .await;
 
-        assert_eq!(res.status, 409, "unexpected body: {}", res.body);
-        let still_exists = submission_judgement::Entity::find_by_id(pending.id)
-            .one(&app.db)
-            .await
-            .expect("query pending judgement")
-            .is_some();
-        assert!(still_exists);
+        assert_eq!(res.status, 403);
+        assert_eq!(res.body["code"], "PERMISSION_DENIED");
     }
 }
 
@@ -1104,7 +3078,9 @@ mod contest_submissions {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
         let contest_id = app
             .create_contest(&admin_token, "Test Contest", true, false)
             .await;
@@ -1133,7 +3109,9 @@ mod contest_submissions {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
         let contest_id = app
             .create_contest(&admin_token, "Test Contest", true, false)
             .await;
@@ -1161,7 +3139,9 @@ mod contest_submissions {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
         let contest_id = app
             .create_contest(&admin_token, "Private Contest", false, false)
             .await;
@@ -1189,7 +3169,9 @@ mod contest_submissions {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Not In Contest").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Not In Contest")
+            .await;
         let contest_id = app
             .create_contest(&admin_token, "Test Contest", true, false)
             .await;
@@ -1216,7 +3198,9 @@ mod contest_submissions {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
         let contest_id = app
             .create_contest(&admin_token, "Test Contest", true, false)
             .await;
@@ -1241,7 +3225,9 @@ mod contest_submissions {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
 
         let res = app
             .post_with_token(
@@ -1285,7 +3271,9 @@ mod contest_submissions {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
 
         let res = app
             .post_with_token(
@@ -1330,7 +3318,9 @@ mod contest_submissions {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
 
         let res = app
             .post_with_token(
@@ -1381,7 +3371,9 @@ mod contest_submissions {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
 
         let res = app
             .post_with_token(
@@ -1419,6 +3411,172 @@ mod contest_submissions {
         assert_eq!(res.body["code"], "VALIDATION_ERROR");
         assert!(res.body["message"].as_str().unwrap().contains("ended"));
     }
+
+    #[tokio::test]
+    async fn practice_after_end_accepts_post_end_submissions_as_practice() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
+
+        let res = app
+            .post_with_token(
+                routes::CONTESTS,
+                &json!({
+                    "title": "Past Private Contest",
+                    "description": "Already ended",
+                    "activate_time": "2020-01-01T00:00:00Z",
+                    "start_time": "2020-01-01T00:00:00Z",
+                    "end_time": "2020-01-02T00:00:00Z",
+                    "is_public": false,
+                    "submissions_visible": false,
+                }),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 201);
+        assert_eq!(res.body["practice_after_end"], false);
+        let contest_id = res.id();
+        app.add_problem_to_contest(contest_id, problem_id, &admin_token)
+            .await;
+
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        let body = valid_submission_body("cpp");
+        let url = routes::contest_problem_submissions(contest_id, problem_id);
+
+        let res = app.post_with_token(&url, &body, &user_token).await;
+        assert_eq!(res.status, 400);
+        assert!(res.body["message"].as_str().unwrap().contains("ended"));
+
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "practice_after_end": true }),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["practice_after_end"], true);
+
+        let res = app.post_with_token(&url, &body, &user_token).await;
+        assert_eq!(res.status, 201, "{}", res.text);
+        assert_eq!(res.body["problem_id"], problem_id);
+        assert!(res.body["contest_id"].is_null());
+    }
+
+    async fn setup_running_contest(app: &TestApp, admin_token: &str) -> (i32, i32, String) {
+        let problem_id = app
+            .create_problem_with_test_case(admin_token, "Contest Problem")
+            .await;
+        let contest_id = app
+            .create_contest(admin_token, "Test Contest", true, false)
+            .await;
+        app.add_problem_to_contest(contest_id, problem_id, admin_token)
+            .await;
+        let user_token = app.create_authenticated_user("user1", "pass1234").await;
+        app.register_for_contest(contest_id, &user_token).await;
+        (contest_id, problem_id, user_token)
+    }
+
+    #[tokio::test]
+    async fn rejects_language_outside_allowed_languages() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let (contest_id, problem_id, user_token) = setup_running_contest(&app, &admin_token).await;
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "allowed_languages": ["cpp", "c"] }),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["allowed_languages"], json!(["cpp", "c"]));
+
+        let res = app
+            .post_with_token(
+                &routes::contest_problem_submissions(contest_id, problem_id),
+                &valid_submission_body("python3"),
+                &user_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+        assert_eq!(
+            res.body["message"],
+            "Language 'python3' is not allowed in this contest; allowed languages: cpp, c"
+        );
+    }
+
+    #[tokio::test]
+    async fn accepts_language_in_allowed_languages() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let (contest_id, problem_id, user_token) = setup_running_contest(&app, &admin_token).await;
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "allowed_languages": ["cpp"] }),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+
+        let res = app
+            .post_with_token(
+                &routes::contest_problem_submissions(contest_id, problem_id),
+                &valid_submission_body("cpp"),
+                &user_token,
+            )
+            .await;
+
+        assert_eq!(res.status, 201, "{}", res.text);
+    }
+
+    #[tokio::test]
+    async fn null_allowed_languages_accepts_every_registry_language() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin1", "pass1234", "admin")
+            .await;
+        let (contest_id, problem_id, user_token) = setup_running_contest(&app, &admin_token).await;
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "allowed_languages": ["cpp"] }),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        let res = app
+            .patch_with_token(
+                &routes::contest(contest_id),
+                &json!({ "allowed_languages": null }),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert!(res.body["allowed_languages"].is_null());
+
+        for language in ["c", "cpp", "java", "python3"] {
+            let res = app
+                .post_with_token(
+                    &routes::contest_problem_submissions(contest_id, problem_id),
+                    &valid_submission_body(language),
+                    &user_token,
+                )
+                .await;
+            assert_eq!(res.status, 201, "{language}: {}", res.text);
+        }
+    }
 }
 
 mod bulk_rejudge {
@@ -1434,7 +3592,9 @@ mod bulk_rejudge {
         let admin_token = app
             .create_user_with_role("admin_brj1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Rejudge Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Rejudge Problem")
+            .await;
 
         let sub1 = app
             .create_submission(problem_id, &admin_token, "cpp", "int main() {}")
@@ -1534,7 +3694,7 @@ mod bulk_rejudge {
             .create_user_with_role("admin_brj_custom", "pass1234", "admin")
             .await;
         let problem_id = app
-            .create_problem(&admin_token, "Custom Verdict Problem")
+            .create_problem_with_test_case(&admin_token, "Custom Verdict Problem")
             .await;
 
         let terminal_id = app
@@ -1619,7 +3779,9 @@ mod bulk_rejudge {
             .create_authenticated_user("contestant_brj4", "pass1234")
             .await;
 
-        let problem_id = app.create_problem(&admin_token, "Rejudge Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Rejudge Problem")
+            .await;
         let submission_id = app
             .create_submission(problem_id, &admin_token, "cpp", "int main() {}")
             .await;
@@ -1657,7 +3819,7 @@ mod bulk_rejudge {
             .create_user_with_role("setter_clear_pin_bulk", "pass1234", "problem_setter")
             .await;
         let problem_id = app
-            .create_problem(&admin_token, "Pinned Bulk Rejudge Problem")
+            .create_problem_with_test_case(&admin_token, "Pinned Bulk Rejudge Problem")
             .await;
         let submission_id = app
             .create_submission(problem_id, &admin_token, "cpp", "int main() {}")
@@ -1693,6 +3855,143 @@ mod bulk_rejudge {
     }
 }
 
+mod rejudge_batch_progress {
+    use super::*;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+    use server::entity::{submission_judgement, user};
+    use server::utils::rejudge_batch::{open_rejudge_batch, settle_rejudge_batch};
+
+    async fn user_id(app: &TestApp, username: &str) -> i32 {
+        user::Entity::find()
+            .filter(user::Column::Username.eq(username))
+            .one(&app.db)
+            .await
+            .unwrap()
+            .unwrap()
+            .id
+    }
+
+    #[tokio::test]
+    async fn batch_counts_down_to_zero_as_judgements_finish() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_rjb1", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Progress Problem")
+            .await;
+        let mut submission_ids = Vec::new();
+        for i in 0..3 {
+            let code = format!("int main() {{ return {i}; }}");
+            submission_ids.push(
+                app.create_submission(problem_id, &admin_token, "cpp", &code)
+                    .await,
+            );
+        }
+        let judgement_ids: Vec<i32> = submission_judgement::Entity::find()
+            .filter(submission_judgement::Column::SubmissionId.is_in(submission_ids))
+            .select_only()
+            .column(submission_judgement::Column::Id)
+            .into_tuple()
+            .all(&app.db)
+            .await
+            .unwrap();
+        assert_eq!(judgement_ids.len(), 3);
+
+        let admin_id = user_id(&app, "admin_rjb1").await;
+        let batch = open_rejudge_batch(&app.db, admin_id, judgement_ids.clone())
+            .await
+            .unwrap();
+
+        let res = app
+            .get_with_token(&routes::rejudge_batch(batch.id), &admin_token)
+            .await;
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body["total"], 3);
+        assert_eq!(res.body["remaining"], 3);
+        assert!(res.body["completed_at"].is_null());
+
+        for id in &judgement_ids {
+            settle_rejudge_batch(&app.db, *id).await.unwrap();
+        }
+        // Settling a judgement twice must not count it twice.
+        settle_rejudge_batch(&app.db, judgement_ids[0])
+            .await
+            .unwrap();
+
+        let res = app
+            .get_with_token(&routes::rejudge_batch(batch.id), &admin_token)
+            .await;
+        assert_eq!(res.body["total"], 3);
+        assert_eq!(res.body["remaining"], 0);
+        assert!(!res.body["completed_at"].is_null());
+    }
+
+    #[tokio::test]
+    async fn bulk_rejudge_returns_a_batch_that_completes() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_rjb2", "pass1234", "admin")
+            .await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Progress Problem")
+            .await;
+        let sub1 = app
+            .create_submission(problem_id, &admin_token, "cpp", "int main() {}")
+            .await;
+        let sub2 = app
+            .create_submission(problem_id, &admin_token, "cpp", "int main() { return 0; }")
+            .await;
+
+        let res = app
+            .post_with_token(
+                routes::SUBMISSIONS_BULK_REJUDGE,
+                &json!({"submission_ids": [sub1, sub2]}),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200);
+        let batch_id = res.body["rejudge_batch_id"].as_i64().unwrap() as i32;
+
+        // No judging plugin is loaded, so every dispatch finishes straight
+        // away as a system error.
+        let mut progress = serde_json::Value::Null;
+        for _ in 0..50 {
+            progress = app
+                .get_with_token(&routes::rejudge_batch(batch_id), &admin_token)
+                .await
+                .body;
+            if progress["remaining"] == 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        assert_eq!(progress["total"], 2);
+        assert_eq!(progress["remaining"], 0);
+    }
+
+    #[tokio::test]
+    async fn other_users_need_rejudge_permission_to_view_a_batch() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_rjb3", "pass1234", "admin")
+            .await;
+        let user_token = app.create_authenticated_user("user_rjb3", "pass1234").await;
+        let admin_id = user_id(&app, "admin_rjb3").await;
+        let batch = open_rejudge_batch(&app.db, admin_id, vec![]).await.unwrap();
+
+        let res = app
+            .get_with_token(&routes::rejudge_batch(batch.id), &user_token)
+            .await;
+        assert_eq!(res.status, 403);
+
+        let res = app
+            .get_with_token(&routes::rejudge_batch(batch.id + 1), &admin_token)
+            .await;
+        assert_eq!(res.status, 404);
+    }
+}
+
 mod contest_submission_visibility {
     use super::*;
 
@@ -1702,7 +4001,9 @@ mod contest_submission_visibility {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
         let contest_id = app
             .create_contest(&admin_token, "Test Contest", true, false)
             .await;
@@ -1735,7 +4036,9 @@ mod contest_submission_visibility {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
         let contest_id = app
             .create_contest(&admin_token, "Test Contest", true, false)
             .await;
@@ -1770,7 +4073,9 @@ mod contest_submission_visibility {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
         let contest_id = app
             .create_contest(&admin_token, "Test Contest", true, true)
             .await;
@@ -1805,7 +4110,9 @@ mod contest_submission_visibility {
         let admin_token = app
             .create_user_with_role("admin1", "pass1234", "admin")
             .await;
-        let problem_id = app.create_problem(&admin_token, "Contest Problem").await;
+        let problem_id = app
+            .create_problem_with_test_case(&admin_token, "Contest Problem")
+            .await;
         let contest_id = app
             .create_contest(&admin_token, "Test Contest", true, false)
             .await;
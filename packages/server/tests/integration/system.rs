@@ -0,0 +1,132 @@
+use chrono::{Duration, Utc};
+use sea_orm::EntityTrait;
+use server::entity::worker;
+use server::utils::worker::{
+    PRUNE_AFTER_SECS, WorkerHeartbeat, list_known_workers, merge_workers, prune_stale_workers,
+    record_heartbeat,
+};
+
+use crate::common::TestApp;
+
+fn heartbeat(id: &str, last_seen: chrono::DateTime<Utc>, in_flight: u32) -> WorkerHeartbeat {
+    WorkerHeartbeat {
+        id: id.into(),
+        started_at: last_seen - Duration::minutes(10),
+        last_seen,
+        in_flight,
+        max_concurrency: Some(4),
+        sandbox_backend: "isolate".into(),
+        version: "0.1.0".into(),
+        hostname: Some("lab-pc-01".into()),
+        ip_addresses: vec!["10.0.0.7".into()],
+        os: Some("linux".into()),
+        arch: Some("x86_64".into()),
+        cpu_count: Some(8),
+        pid: Some(4242),
+    }
+}
+
+mod worker_registry {
+    use super::*;
+
+    #[tokio::test]
+    async fn heartbeat_upserts_worker_row() {
+        let app = TestApp::spawn().await;
+        let now = Utc::now();
+
+        record_heartbeat(
+            &app.db,
+            &heartbeat("worker-a", now - Duration::seconds(3), 1),
+        )
+        .await
+        .unwrap();
+        record_heartbeat(&app.db, &heartbeat("worker-a", now, 3))
+            .await
+            .unwrap();
+
+        let rows = worker::Entity::find().all(&app.db).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].id, "worker-a");
+        assert_eq!(rows[0].in_flight, 3);
+        assert_eq!(rows[0].last_seen.timestamp(), now.timestamp());
+    }
+
+    #[tokio::test]
+    async fn older_heartbeat_does_not_overwrite_newer() {
+        let app = TestApp::spawn().await;
+        let now = Utc::now();
+
+        record_heartbeat(&app.db, &heartbeat("worker-a", now, 2))
+            .await
+            .unwrap();
+        record_heartbeat(
+            &app.db,
+            &heartbeat("worker-a", now - Duration::seconds(30), 0),
+        )
+        .await
+        .unwrap();
+
+        let row = worker::Entity::find_by_id("worker-a".to_string())
+            .one(&app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.in_flight, 2);
+        assert_eq!(row.last_seen.timestamp(), now.timestamp());
+    }
+
+    #[tokio::test]
+    async fn recorded_worker_without_heartbeat_is_stale() {
+        let app = TestApp::spawn().await;
+        let now = Utc::now();
+
+        record_heartbeat(&app.db, &heartbeat("worker-fresh", now, 0))
+            .await
+            .unwrap();
+        record_heartbeat(
+            &app.db,
+            &heartbeat("worker-gone", now - Duration::minutes(5), 0),
+        )
+        .await
+        .unwrap();
+
+        let rows = list_known_workers(&app.db).await.unwrap();
+        let workers = merge_workers(rows, vec![heartbeat("worker-fresh", now, 0)], now);
+        assert_eq!(workers.len(), 2);
+        let fresh = workers.iter().find(|w| w.id == "worker-fresh").unwrap();
+        let gone = workers.iter().find(|w| w.id == "worker-gone").unwrap();
+        assert!(!fresh.stale);
+        assert!(gone.stale);
+        assert!(gone.seconds_since_last_seen >= 300);
+    }
+
+    #[tokio::test]
+    async fn workers_gone_past_retention_are_pruned() {
+        let app = TestApp::spawn().await;
+        let now = Utc::now();
+
+        record_heartbeat(
+            &app.db,
+            &heartbeat("worker-stale", now - Duration::minutes(5), 0),
+        )
+        .await
+        .unwrap();
+        record_heartbeat(
+            &app.db,
+            &heartbeat(
+                "worker-retired",
+                now - Duration::seconds(PRUNE_AFTER_SECS + 60),
+                0,
+            ),
+        )
+        .await
+        .unwrap();
+
+        let pruned = prune_stale_workers(&app.db, now).await.unwrap();
+        assert_eq!(pruned, 1);
+
+        let rows = worker::Entity::find().all(&app.db).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].id, "worker-stale");
+    }
+}
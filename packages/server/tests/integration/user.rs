@@ -300,6 +300,225 @@ mod user_modification {
     }
 }
 
+mod password_reset {
+    use super::*;
+
+    #[tokio::test]
+    async fn generated_password_works_and_old_session_is_revoked() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_reset", "securepass", "admin")
+            .await;
+        let victim_token = app
+            .create_authenticated_user("forgetful", "old_pass1")
+            .await;
+        let victim_id = app.get_with_token(routes::ME, &victim_token).await.id();
+
+        let res = app
+            .post_with_token(
+                &routes::user_reset_password(victim_id),
+                &json!({}),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["user_id"], victim_id);
+        let generated = res.body["generated_password"]
+            .as_str()
+            .expect("generated password should be returned")
+            .to_string();
+
+        let old_bearer = app.get_with_token(routes::ME, &victim_token).await;
+        assert_eq!(old_bearer.status, 401);
+        assert_eq!(old_bearer.body["code"], "TOKEN_INVALID");
+
+        let login_old = app
+            .post_without_token(
+                routes::LOGIN,
+                &json!({"username": "forgetful", "password": "old_pass1"}),
+            )
+            .await;
+        assert_eq!(login_old.status, 401);
+
+        let login_new = app
+            .post_without_token(
+                routes::LOGIN,
+                &json!({"username": "forgetful", "password": generated}),
+            )
+            .await;
+        assert_eq!(login_new.status, 200);
+    }
+
+    #[tokio::test]
+    async fn admin_supplied_password_is_not_echoed() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_reset2", "securepass", "admin")
+            .await;
+        let victim_token = app
+            .create_authenticated_user("forgetful2", "old_pass1")
+            .await;
+        let victim_id = app.get_with_token(routes::ME, &victim_token).await.id();
+
+        let res = app
+            .post_with_token(
+                &routes::user_reset_password(victim_id),
+                &json!({"password": "chosen_pass"}),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert!(res.body.get("generated_password").is_none());
+
+        let login_new = app
+            .post_without_token(
+                routes::LOGIN,
+                &json!({"username": "forgetful2", "password": "chosen_pass"}),
+            )
+            .await;
+        assert_eq!(login_new.status, 200);
+    }
+
+    #[tokio::test]
+    async fn rejects_short_password_and_requires_permission() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_reset3", "securepass", "admin")
+            .await;
+        let user_token = app
+            .create_authenticated_user("plain_user", "securepass")
+            .await;
+        let user_id = app.get_with_token(routes::ME, &user_token).await.id();
+
+        let res = app
+            .post_with_token(
+                &routes::user_reset_password(user_id),
+                &json!({"password": "short"}),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+
+        let res = app
+            .post_with_token(
+                &routes::user_reset_password(user_id),
+                &json!({}),
+                &user_token,
+            )
+            .await;
+        assert_eq!(res.status, 403);
+    }
+}
+
+mod account_status {
+    use super::*;
+
+    #[tokio::test]
+    async fn deactivation_blocks_login_and_existing_tokens() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_status", "securepass", "admin")
+            .await;
+        let victim_token = app.create_authenticated_user("dormant", "securepass").await;
+        let victim_id = app.get_with_token(routes::ME, &victim_token).await.id();
+
+        let res = app
+            .patch_with_token(
+                &routes::user_status(victim_id),
+                &json!({"is_active": false}),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 200, "{}", res.text);
+        assert_eq!(res.body["is_active"], false);
+
+        let me = app.get_with_token(routes::ME, &victim_token).await;
+        assert_eq!(me.status, 403);
+        assert_eq!(me.body["code"], "ACCOUNT_DEACTIVATED");
+
+        // The victim's refresh cookie was the last one stored.
+        let refresh_res = app.post_without_token(routes::REFRESH, &json!({})).await;
+        assert_eq!(refresh_res.status, 401);
+
+        let login_res = app
+            .post_without_token(
+                routes::LOGIN,
+                &json!({"username": "dormant", "password": "securepass"}),
+            )
+            .await;
+        assert_eq!(login_res.status, 403);
+        assert_eq!(login_res.body["code"], "ACCOUNT_DEACTIVATED");
+    }
+
+    #[tokio::test]
+    async fn reactivation_restores_access() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_status2", "securepass", "admin")
+            .await;
+        let victim_token = app
+            .create_authenticated_user("dormant2", "securepass")
+            .await;
+        let victim_id = app.get_with_token(routes::ME, &victim_token).await.id();
+
+        for is_active in [false, true] {
+            let res = app
+                .patch_with_token(
+                    &routes::user_status(victim_id),
+                    &json!({"is_active": is_active}),
+                    &admin_token,
+                )
+                .await;
+            assert_eq!(res.status, 200, "{}", res.text);
+            assert_eq!(res.body["is_active"], is_active);
+        }
+
+        let me = app.get_with_token(routes::ME, &victim_token).await;
+        assert_eq!(me.status, 200, "{}", me.text);
+
+        let login_res = app
+            .post_without_token(
+                routes::LOGIN,
+                &json!({"username": "dormant2", "password": "securepass"}),
+            )
+            .await;
+        assert_eq!(login_res.status, 200, "{}", login_res.text);
+    }
+
+    #[tokio::test]
+    async fn requires_permission_and_rejects_self_deactivation() {
+        let app = TestApp::spawn().await;
+        let admin_token = app
+            .create_user_with_role("admin_status3", "securepass", "admin")
+            .await;
+        let admin_id = app.get_with_token(routes::ME, &admin_token).await.id();
+        let user_token = app
+            .create_authenticated_user("plain_status", "securepass")
+            .await;
+
+        let res = app
+            .patch_with_token(
+                &routes::user_status(admin_id),
+                &json!({"is_active": false}),
+                &admin_token,
+            )
+            .await;
+        assert_eq!(res.status, 400);
+        assert_eq!(res.body["code"], "VALIDATION_ERROR");
+
+        let res = app
+            .patch_with_token(
+                &routes::user_status(admin_id),
+                &json!({"is_active": false}),
+                &user_token,
+            )
+            .await;
+        assert_eq!(res.status, 403);
+        assert_eq!(res.body["code"], "PERMISSION_DENIED");
+    }
+}
+
 mod role_management {
     use super::*;
 
@@ -0,0 +1,227 @@
+use std::time::{Duration, Instant};
+
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde_json::json;
+use server::config::WebhookConfig;
+use server::entity::dead_letter_message;
+use server::webhook::{
+    EVENT_HEADER, JUDGE_RESULT_EVENT, JudgeResultPayload, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+    deliver_judge_result, sign,
+};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::common::{TestApp, routes};
+
+const SECRET: &str = "webhook-test-secret";
+
+fn payload(submission_id: i32) -> JudgeResultPayload {
+    JudgeResultPayload {
+        event: JUDGE_RESULT_EVENT.to_string(),
+        submission_id,
+        user_id: 7,
+        problem_id: 3,
+        contest_id: Some(2),
+        status: "Judged".to_string(),
+        verdict: Some("Accepted".to_string()),
+        score: Some(100.0),
+        time_used: Some(12),
+        memory_used: Some(2048),
+        judge_epoch: 1,
+        judged_at: None,
+    }
+}
+
+fn config(server: &MockServer, max_attempts: u8) -> WebhookConfig {
+    WebhookConfig {
+        urls: vec![format!("{}/hook", server.uri())],
+        secret: SECRET.to_string(),
+        max_attempts,
+        retry_delay_ms: 10,
+        timeout_secs: 5,
+    }
+}
+
+async fn dlq_entries(app: &TestApp, submission_id: i32) -> Vec<dead_letter_message::Model> {
+    dead_letter_message::Entity::find()
+        .filter(dead_letter_message::Column::SubmissionId.eq(submission_id))
+        .all(&app.db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn delivers_signed_payload() {
+    let app = TestApp::spawn().await;
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hook"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    deliver_judge_result(&app.db, &config(&server, 3), &payload(101)).await;
+
+    let requests = server.received_requests().await.unwrap();
+    let request = &requests[0];
+    let body: JudgeResultPayload = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body, payload(101));
+    let timestamp: i64 = request.headers[TIMESTAMP_HEADER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((chrono::Utc::now().timestamp() - timestamp).abs() < 60);
+    assert_eq!(
+        request.headers[SIGNATURE_HEADER].to_str().unwrap(),
+        sign(SECRET, timestamp, &request.body)
+    );
+    assert_eq!(
+        request.headers[EVENT_HEADER].to_str().unwrap(),
+        JUDGE_RESULT_EVENT
+    );
+    assert!(dlq_entries(&app, 101).await.is_empty());
+}
+
+#[tokio::test]
+async fn retries_after_server_error() {
+    let app = TestApp::spawn().await;
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    deliver_judge_result(&app.db, &config(&server, 5), &payload(102)).await;
+
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    assert!(dlq_entries(&app, 102).await.is_empty());
+}
+
+#[tokio::test]
+async fn dead_letters_after_exhausting_retries() {
+    let app = TestApp::spawn().await;
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    deliver_judge_result(&app.db, &config(&server, 3), &payload(103)).await;
+
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    let entries = dlq_entries(&app, 103).await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].message_type, "webhook_delivery");
+    assert_eq!(entries[0].error_code, "MAX_RETRIES_EXCEEDED");
+    assert_eq!(entries[0].retry_count, 3);
+    assert_eq!(entries[0].payload["body"]["submission_id"], 103);
+}
+
+#[tokio::test]
+async fn client_error_is_dead_lettered_without_retrying() {
+    let app = TestApp::spawn().await;
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(410))
+        .mount(&server)
+        .await;
+
+    deliver_judge_result(&app.db, &config(&server, 5), &payload(104)).await;
+
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    let entries = dlq_entries(&app, 104).await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].error_code, "DELIVERY_REJECTED");
+}
+
+#[tokio::test]
+async fn delivers_to_every_url_concurrently() {
+    let app = TestApp::spawn().await;
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(800)))
+        .expect(3)
+        .mount(&server)
+        .await;
+    let config = WebhookConfig {
+        urls: (0..3)
+            .map(|i| format!("{}/hook/{i}", server.uri()))
+            .collect(),
+        ..config(&server, 1)
+    };
+
+    let started = Instant::now();
+    deliver_judge_result(&app.db, &config, &payload(105)).await;
+
+    assert!(
+        started.elapsed() < Duration::from_millis(2000),
+        "deliveries ran one after another: {:?}",
+        started.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn refuses_to_send_without_a_secret() {
+    let app = TestApp::spawn().await;
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+    let config = WebhookConfig {
+        secret: String::new(),
+        ..config(&server, 3)
+    };
+
+    deliver_judge_result(&app.db, &config, &payload(106)).await;
+
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn dead_lettered_delivery_can_be_retried() {
+    let app = TestApp::spawn().await;
+    let token = app
+        .create_user_with_role("webhook_admin", "securepass", "admin")
+        .await;
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(410))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    deliver_judge_result(&app.db, &config(&server, 3), &payload(107)).await;
+    let entries = dlq_entries(&app, 107).await;
+    assert_eq!(entries.len(), 1);
+
+    let res = app
+        .post_with_token(&routes::dlq_retry(entries[0].id), &json!({}), &token)
+        .await;
+    assert_eq!(res.status, 200, "unexpected body: {}", res.body);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while server.received_requests().await.unwrap().len() < 2 && Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2, "retry should send the payload again");
+    let body: JudgeResultPayload = serde_json::from_slice(&requests[1].body).unwrap();
+    assert_eq!(body, payload(107));
+
+    let entries = dlq_entries(&app, 107).await;
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].resolved);
+}
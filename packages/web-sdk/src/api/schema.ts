@@ -2618,7 +2618,11 @@ export interface components {
       memory_limit: number;
       /** @example batch */
       problem_type?: string;
-      /** @example false */
+      /**
+       * @description Legacy boolean form of `test_details_visibility`: `true` is `Full`,
+       *     `false` is `SampleOnly`. Ignored when `test_details_visibility` is set.
+       * @example false
+       */
       show_test_details?: boolean | null;
       /**
        * @example {
@@ -2633,6 +2637,13 @@ export interface components {
       submission_format?: {
         [key: string]: string[];
       } | null;
+      /**
+       * @description Test case I/O shown to users who cannot view all submissions.
+       *     Defaults to `SampleOnly`.
+       */
+      test_details_visibility?:
+        | null
+        | components['schemas']['TestDetailsVisibility'];
       /**
        * Format: int32
        * @example 1000
@@ -3085,8 +3096,7 @@ export interface components {
       memory_limit: number;
       /** @example batch */
       problem_type: string;
-      /** @example false */
-      show_test_details: boolean;
+      test_details_visibility: components['schemas']['TestDetailsVisibility'];
       /**
        * Format: int32
        * @example 1000
@@ -3130,7 +3140,10 @@ export interface components {
       /** @example batch */
       problem_type: string;
       samples: components['schemas']['SampleTestCaseMeta'][];
-      /** @example false */
+      /**
+       * @description Whether `test_details_visibility` is `Full`. Kept for older clients.
+       * @example false
+       */
       show_test_details: boolean;
       /**
        * @example {
@@ -3145,6 +3158,7 @@ export interface components {
       submission_format?: {
         [key: string]: string[];
       } | null;
+      test_details_visibility: components['schemas']['TestDetailsVisibility'];
       /**
        * Format: int32
        * @example 1000
@@ -3609,6 +3623,12 @@ export interface components {
       /** @example Accepted */
       verdict: string;
     };
+    /**
+     * @description Test case I/O shown to users without `submission:view_all` on
+     *     submissions for a problem.
+     * @enum {string}
+     */
+    TestDetailsVisibility: 'None' | 'SampleOnly' | 'Full';
     /**
      * @example {
      *       "sidebar.plugins": "插件",
@@ -3677,7 +3697,12 @@ export interface components {
       memory_limit?: number | null;
       /** @example batch */
       problem_type?: string | null;
-      /** @example true */
+      /**
+       * @description Legacy boolean form of `test_details_visibility`. Ignored when
+       *     `test_details_visibility` is set or when it matches the current
+       *     visibility's `show_test_details`.
+       * @example true
+       */
       show_test_details?: boolean | null;
       /**
        * @example {
@@ -3692,6 +3717,9 @@ export interface components {
       submission_format?: {
         [key: string]: string[];
       } | null;
+      test_details_visibility?:
+        | null
+        | components['schemas']['TestDetailsVisibility'];
       /**
        * Format: int32
        * @example 2000
@@ -11,6 +11,7 @@ export const USER_PERMISSIONS = [
   'problem:create',
   'problem:edit',
   'problem:delete',
+  'problem:manage',
   'contest:create',
   'contest:manage',
   'contest:delete',
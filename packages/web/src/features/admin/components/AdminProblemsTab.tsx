@@ -43,6 +43,7 @@ import { CheckerSourceDialog } from '@/features/admin/components/CheckerSourceDi
 import {
   ProblemForm,
   type ProblemFormData,
+  type TestDetailsVisibility,
 } from '@/features/admin/components/ProblemForm';
 import { TestCasesDialog } from '@/features/admin/components/TestCasesDialog';
 import { fetchContestProblems } from '@/features/contest/api/fetch-contest-problems';
@@ -76,7 +77,8 @@ export function ProblemFormDialog({
   const [problemType, setProblemType] = useState('standard');
   const [checkerFormat, setCheckerFormat] = useState('exact');
   const [defaultContestType, setDefaultContestType] = useState('standard');
-  const [showTestDetails, setShowTestDetails] = useState(false);
+  const [testDetailsVisibility, setTestDetailsVisibility] =
+    useState<TestDetailsVisibility>('SampleOnly');
   const [submissionFormat, setSubmissionFormat] = useState<
     Record<string, string[]>
   >({});
@@ -99,7 +101,7 @@ export function ProblemFormDialog({
     problemType,
     checkerFormat,
     defaultContestType,
-    showTestDetails,
+    testDetailsVisibility,
     submissionFormat,
   };
 
@@ -111,7 +113,7 @@ export function ProblemFormDialog({
     setProblemType(data.problemType);
     setCheckerFormat(data.checkerFormat);
     setDefaultContestType(data.defaultContestType);
-    setShowTestDetails(data.showTestDetails);
+    setTestDetailsVisibility(data.testDetailsVisibility);
     setSubmissionFormat(data.submissionFormat);
   };
 
@@ -131,7 +133,7 @@ export function ProblemFormDialog({
           setProblemType(data.problem_type);
           setCheckerFormat(data.checker_format);
           setDefaultContestType(data.default_contest_type);
-          setShowTestDetails(data.show_test_details);
+          setTestDetailsVisibility(data.test_details_visibility);
           setSubmissionFormat(data.submission_format ?? {});
         });
     } else {
@@ -142,7 +144,7 @@ export function ProblemFormDialog({
       setProblemType('standard');
       setCheckerFormat('exact');
       setDefaultContestType('standard');
-      setShowTestDetails(false);
+      setTestDetailsVisibility('SampleOnly');
       setSubmissionFormat({});
     }
   }, [apiClient, open, problem]);
@@ -169,7 +171,7 @@ export function ProblemFormDialog({
       problem_type: problemType,
       checker_format: checkerFormat,
       default_contest_type: defaultContestType,
-      show_test_details: showTestDetails,
+      test_details_visibility: testDetailsVisibility,
       submission_format:
         Object.keys(submissionFormat).length > 0 ? submissionFormat : null,
     };
@@ -22,13 +22,20 @@ import { useEffect, useMemo, useState } from 'react';
 
 import { MarkdownEditor } from '@/components/MarkdownEditor';
 import { MarkdownEditorWithAttachments } from '@/features/admin/components/MarkdownEditorWithAttachments';
-import { SwitchField } from '@/features/admin/components/SwitchField';
 import type { Attachment } from '@/features/problem/api/attachments';
 import {
   fetchSupportedLanguages,
   type SupportedLanguage,
 } from '@/features/problem/api/fetch-supported-languages';
 
+export type TestDetailsVisibility = 'None' | 'SampleOnly' | 'Full';
+
+const TEST_DETAILS_VISIBILITIES: TestDetailsVisibility[] = [
+  'None',
+  'SampleOnly',
+  'Full',
+];
+
 export interface ProblemFormData {
   title: string;
   content: string;
@@ -37,7 +44,7 @@ export interface ProblemFormData {
   problemType: string;
   checkerFormat: string;
   defaultContestType: string;
-  showTestDetails: boolean;
+  testDetailsVisibility: TestDetailsVisibility;
   submissionFormat: Record<string, string[]>;
 }
 
@@ -149,8 +156,13 @@ export function ProblemForm({
     onChange({ ...data, defaultContestType });
   };
 
-  const handleShowTestDetailsChange = (showTestDetails: boolean) => {
-    onChange({ ...data, showTestDetails });
+  const handleTestDetailsVisibilityChange = (
+    testDetailsVisibility: string,
+  ) => {
+    onChange({
+      ...data,
+      testDetailsVisibility: testDetailsVisibility as TestDetailsVisibility,
+    });
   };
 
   const configuredLanguages = useMemo(
@@ -364,16 +376,25 @@ export function ProblemForm({
 
       <Separator />
 
-      <div className="space-y-3">
-        <Label className="text-sm text-muted-foreground">
-          {t('admin.field.options')}
+      <div className="space-y-2">
+        <Label htmlFor="problem-test-details">
+          {t('admin.field.testDetailsVisibility')}
         </Label>
-        <SwitchField
-          id="problem-test-details"
-          label={t('admin.field.showTestDetails')}
-          checked={data.showTestDetails}
-          onCheckedChange={handleShowTestDetailsChange}
-        />
+        <Select
+          value={data.testDetailsVisibility}
+          onValueChange={handleTestDetailsVisibilityChange}
+        >
+          <SelectTrigger id="problem-test-details">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            {TEST_DETAILS_VISIBILITIES.map((opt) => (
+              <SelectItem key={opt} value={opt}>
+                {t(`admin.field.testDetailsVisibility${opt}`)}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
       </div>
 
       <Separator />
@@ -13,9 +13,15 @@ export interface DlqMessage {
   resolved_by: number | null;
 }
 
+export interface DlqRetryAttempt {
+  attempt: number;
+  error: string;
+  timestamp: string;
+}
+
 export interface DlqMessageDetail extends DlqMessage {
   payload: unknown;
-  retry_history: unknown;
+  retry_history: DlqRetryAttempt[];
 }
 
 export interface DlqListResponse {
@@ -39,7 +39,7 @@ export function ProblemEditForm({ problemId }: ProblemEditFormProps) {
     problemType: 'standard',
     checkerFormat: 'exact',
     defaultContestType: 'standard',
-    showTestDetails: false,
+    testDetailsVisibility: 'SampleOnly',
     submissionFormat: {},
   });
   const [loading, setLoading] = useState(false);
@@ -76,7 +76,7 @@ export function ProblemEditForm({ problemId }: ProblemEditFormProps) {
         problemType: data.problem_type,
         checkerFormat: data.checker_format,
         defaultContestType: data.default_contest_type,
-        showTestDetails: data.show_test_details,
+        testDetailsVisibility: data.test_details_visibility,
         submissionFormat: data.submission_format ?? {},
       });
       setLoadingData(false);
@@ -155,7 +155,7 @@ export function ProblemEditForm({ problemId }: ProblemEditFormProps) {
       problem_type: formData.problemType,
       checker_format: formData.checkerFormat,
       default_contest_type: formData.defaultContestType,
-      show_test_details: formData.showTestDetails,
+      test_details_visibility: formData.testDetailsVisibility,
       submission_format:
         Object.keys(formData.submissionFormat).length > 0
           ? formData.submissionFormat
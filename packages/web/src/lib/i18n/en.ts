@@ -514,7 +514,10 @@ export const en: Record<string, string> = {
   'admin.field.contestType': 'Contest Type',
   'admin.field.contestTypeNone': 'None (default)',
   'admin.field.submissionFormat': 'Submission Format',
-  'admin.field.showTestDetails': 'Show Test Details',
+  'admin.field.testDetailsVisibility': 'Test Details Visibility',
+  'admin.field.testDetailsVisibilityNone': 'Hidden',
+  'admin.field.testDetailsVisibilitySampleOnly': 'Samples only',
+  'admin.field.testDetailsVisibilityFull': 'All test cases',
   'admin.field.options': 'Options',
   'admin.field.createdAt': 'Created',
   'admin.submissionFormat.language': 'Select language',
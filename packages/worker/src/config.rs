@@ -38,6 +38,18 @@ pub struct WorkerConfig {
     pub enable_cgroups: bool,
     #[serde(default = "default_sandbox_backend")]
     pub sandbox_backend: String,
+    /// Initialized sandboxes kept around and reset between jobs instead of
+    /// being created and destroyed each time. 0 disables reuse.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// Jobs of a single contest allowed to run at once across all workers,
+    /// so one busy contest cannot starve the others. 0 means unlimited.
+    #[serde(default)]
+    pub max_concurrent_per_contest: usize,
+    /// Base under which each worker process keeps its own locked directory
+    /// for sandbox and channel files. Defaults to the system temp dir.
+    #[serde(default)]
+    pub temp_dir: Option<String>,
 }
 
 fn default_worker_id() -> String {
@@ -52,6 +64,9 @@ fn default_enable_cgroups() -> bool {
 fn default_sandbox_backend() -> String {
     "isolate".into()
 }
+fn default_batch_size() -> usize {
+    4
+}
 
 impl Default for WorkerConfig {
     fn default() -> Self {
@@ -60,10 +75,22 @@ impl Default for WorkerConfig {
             isolate_bin: default_isolate_bin(),
             enable_cgroups: default_enable_cgroups(),
             sandbox_backend: default_sandbox_backend(),
+            batch_size: default_batch_size(),
+            max_concurrent_per_contest: 0,
+            temp_dir: None,
         }
     }
 }
 
+impl WorkerConfig {
+    /// Base directory for the worker's temporary sandbox files.
+    pub fn temp_dir(&self) -> std::path::PathBuf {
+        self.temp_dir
+            .as_ref()
+            .map_or_else(std::env::temp_dir, std::path::PathBuf::from)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct StorageConfig {
     #[serde(flatten)]
@@ -115,12 +142,20 @@ impl WorkerAppConfig {
             .set_default("worker.isolate_bin", "isolate")?
             .set_default("worker.enable_cgroups", true)?
             .set_default("worker.sandbox_backend", "isolate")?
+            .set_default("worker.batch_size", 4_i64)?
+            .set_default("worker.max_concurrent_per_contest", 0_i64)?
             .set_default("mq.enabled", true)?
             .set_default("mq.url", "redis://localhost:6379")?
             .set_default("mq.pool_size", 5_i64)?
             .set_default("mq.operation_queue_name", "operation_tasks")?
             .set_default("mq.operation_result_queue_name", "operation_results")?
             .set_default("mq.operation_dlq_queue_name", "operation_tasks_dlq")?
+            .set_default("mq.operation_concurrency", 1_i64)?
+            .set_default("mq.operation_result_concurrency", 8_i64)?
+            .set_default("mq.operation_dlq_concurrency", 1_i64)?
+            .set_default("mq.operation_result_dedup_window", 10_000_i64)?
+            .set_default("mq.reconnect_base_delay_ms", 1000_i64)?
+            .set_default("mq.reconnect_max_delay_ms", 30_000_i64)?
             .set_default("observability.log_format", "pretty")?
             .set_default("observability.log_filter", "info")?
             .set_default("observability.otlp.service_name", "broccoli-worker")?
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::worker::Task;
+use mq::config::PublishConfig;
+use mq::{BroccoliError, Envelope};
+use redis::aio::MultiplexedConnection;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// How long a consumer pauses after handing a capped task back.
+const REQUEUE_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Lua script for an atomic bounded slot claim.
+///
+/// Every running job holds its own member of a sorted set scored by when it
+/// lapses, so a crashed worker's slot expires on its own without touching
+/// the slots of jobs that are still running.
+///
+/// KEYS[1] = slot set key (e.g. `broccoli:contest_slots:<contest-id>`)
+/// ARGV[1] = maximum concurrent jobs for the contest
+/// ARGV[2] = TTL seconds of the slot, renewed while the job runs
+/// ARGV[3] = slot token
+///
+/// Returns 1 if a slot was taken, 0 if the contest is at its cap.
+const ACQUIRE_SCRIPT: &str = r#"
+local now = tonumber(redis.call('TIME')[1])
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now)
+if redis.call('ZCARD', KEYS[1]) >= tonumber(ARGV[1]) then
+  return 0
+end
+redis.call('ZADD', KEYS[1], now + tonumber(ARGV[2]), ARGV[3])
+redis.call('EXPIRE', KEYS[1], ARGV[2])
+return 1
+"#;
+
+/// Lua script that pushes a held slot's lapse time out by another TTL.
+///
+/// KEYS[1] = slot set key
+/// ARGV[1] = TTL seconds
+/// ARGV[2] = slot token
+const RENEW_SCRIPT: &str = r#"
+local now = tonumber(redis.call('TIME')[1])
+redis.call('ZADD', KEYS[1], now + tonumber(ARGV[1]), ARGV[2])
+redis.call('EXPIRE', KEYS[1], ARGV[1])
+return 1
+"#;
+
+/// Caps how many jobs of one contest run at once.
+///
+/// With Redis the slots are shared by every worker. The in-process counter
+/// only limits this worker; it is used without Redis and whenever a Redis
+/// call fails, so an outage degrades the cap instead of lifting it.
+pub struct ContestLimiter {
+    max_per_contest: usize,
+    local: std::sync::Mutex<HashMap<i32, usize>>,
+    redis: Option<Box<RedisSlots>>,
+}
+
+struct RedisSlots {
+    client: redis::Client,
+    conn: Mutex<Option<MultiplexedConnection>>,
+    ttl_secs: u64,
+    prefix: String,
+}
+
+/// Whether a task may run now.
+pub enum Admission {
+    /// Run the task, holding the slot (if its contest is capped) until done.
+    Run(Option<ContestSlot>),
+    /// The task's contest is at its cap; hand the task back to the queue.
+    AtCap,
+}
+
+/// A running slot for `contest_id`, given back when dropped.
+pub struct ContestSlot {
+    limiter: Arc<ContestLimiter>,
+    contest_id: i32,
+    held: Held,
+}
+
+enum Held {
+    Local,
+    Shared {
+        token: String,
+        renewal: JoinHandle<()>,
+    },
+}
+
+impl Drop for ContestSlot {
+    fn drop(&mut self) {
+        match &self.held {
+            Held::Local => self.limiter.release_local(self.contest_id),
+            Held::Shared { token, renewal } => {
+                renewal.abort();
+                let limiter = Arc::clone(&self.limiter);
+                let contest_id = self.contest_id;
+                let token = token.clone();
+                tokio::spawn(async move {
+                    if let Some(redis) = &limiter.redis {
+                        redis.release(contest_id, &token).await;
+                    }
+                });
+            }
+        }
+    }
+}
+
+impl ContestLimiter {
+    /// Limiter shared across workers through Redis at `redis_url`.
+    pub fn redis(
+        redis_url: &str,
+        max_per_contest: usize,
+        ttl_secs: u64,
+    ) -> Result<Self, redis::RedisError> {
+        let mut limiter = Self::local(max_per_contest);
+        limiter.redis = Some(Box::new(RedisSlots {
+            client: redis::Client::open(redis_url)?,
+            conn: Mutex::new(None),
+            ttl_secs: ttl_secs.max(1),
+            prefix: "broccoli:contest_slots:".to_string(),
+        }));
+        Ok(limiter)
+    }
+
+    /// Limiter that only counts this worker's jobs.
+    pub fn local(max_per_contest: usize) -> Self {
+        Self {
+            max_per_contest,
+            local: std::sync::Mutex::new(HashMap::new()),
+            redis: None,
+        }
+    }
+
+    /// Takes a slot for `task`'s contest if fewer than the cap of its jobs
+    /// are running. Tasks outside a contest, or with no cap, always run.
+    pub async fn admit(self: &Arc<Self>, task: &Task) -> Admission {
+        let Some(contest_id) = task_contest_id(task) else {
+            return Admission::Run(None);
+        };
+        if self.max_per_contest == 0 {
+            return Admission::Run(None);
+        }
+
+        let held = match &self.redis {
+            Some(redis) => match redis.try_acquire(contest_id, self.max_per_contest).await {
+                Ok(Some(token)) => Some(Held::Shared {
+                    renewal: redis.spawn_renewal(Arc::clone(self), contest_id, token.clone()),
+                    token,
+                }),
+                Ok(None) => None,
+                Err(e) => {
+                    warn!(
+                        contest_id,
+                        error = %e,
+                        "Redis contest slot acquire failed, capping per worker only"
+                    );
+                    self.try_acquire_local(contest_id).then_some(Held::Local)
+                }
+            },
+            None => self.try_acquire_local(contest_id).then_some(Held::Local),
+        };
+
+        match held {
+            Some(held) => Admission::Run(Some(ContestSlot {
+                limiter: Arc::clone(self),
+                contest_id,
+                held,
+            })),
+            None => Admission::AtCap,
+        }
+    }
+
+    /// Publishes `message` again at the tail of `queue`, so a task whose
+    /// contest is at its cap waits behind other contests' work instead of
+    /// holding a consumer. Pauses briefly afterwards so consumers do not spin
+    /// when only capped contests have work queued.
+    pub async fn requeue(
+        &self,
+        mq: &mq::Mq,
+        queue: &str,
+        message: &Envelope<Task>,
+    ) -> Result<(), BroccoliError> {
+        let options = message
+            .payload
+            .priority
+            .map(|p| PublishConfig::builder().priority(p).build());
+        mq.publish(queue, None, message, options).await?;
+        tokio::time::sleep(REQUEUE_BACKOFF).await;
+        Ok(())
+    }
+
+    fn try_acquire_local(&self, contest_id: i32) -> bool {
+        let mut counts = self.local.lock().unwrap_or_else(|e| e.into_inner());
+        let count = counts.entry(contest_id).or_default();
+        if *count >= self.max_per_contest {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
+    fn release_local(&self, contest_id: i32) {
+        let mut counts = self.local.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = counts.get_mut(&contest_id) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&contest_id);
+            }
+        }
+    }
+}
+
+/// Contest an operation task belongs to, if any.
+fn task_contest_id(task: &Task) -> Option<i32> {
+    if task.task_type != "operation" {
+        return None;
+    }
+    task.payload
+        .get("contest_id")
+        .and_then(|v| v.as_i64())
+        .and_then(|id| i32::try_from(id).ok())
+}
+
+impl RedisSlots {
+    async fn get_conn(&self) -> Result<MultiplexedConnection, redis::RedisError> {
+        let mut guard = self.conn.lock().await;
+        if let Some(ref conn) = *guard {
+            return Ok(conn.clone());
+        }
+        let conn = self.client.get_multiplexed_async_connection().await?;
+        *guard = Some(conn.clone());
+        Ok(conn)
+    }
+
+    async fn invalidate_conn(&self) {
+        *self.conn.lock().await = None;
+    }
+
+    fn key(&self, contest_id: i32) -> String {
+        format!("{}{}", self.prefix, contest_id)
+    }
+
+    /// Claims a slot, returning its token, or `None` at the cap.
+    async fn try_acquire(
+        &self,
+        contest_id: i32,
+        max_per_contest: usize,
+    ) -> Result<Option<String>, redis::RedisError> {
+        let token = uuid::Uuid::new_v4().to_string();
+        let mut conn = self.get_conn().await?;
+        let result: Result<i64, _> = redis::cmd("EVAL")
+            .arg(ACQUIRE_SCRIPT)
+            .arg(1)
+            .arg(self.key(contest_id))
+            .arg(max_per_contest)
+            .arg(self.ttl_secs)
+            .arg(&token)
+            .query_async(&mut conn)
+            .await;
+        match result {
+            Ok(acquired) => Ok((acquired == 1).then_some(token)),
+            Err(e) => {
+                self.invalidate_conn().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Keeps the slot from lapsing while its job runs longer than the TTL.
+    fn spawn_renewal(
+        &self,
+        limiter: Arc<ContestLimiter>,
+        contest_id: i32,
+        token: String,
+    ) -> JoinHandle<()> {
+        let interval = Duration::from_secs(self.ttl_secs).div_f64(3.0);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if let Some(redis) = &limiter.redis {
+                    redis.renew(contest_id, &token).await;
+                }
+            }
+        })
+    }
+
+    async fn renew(&self, contest_id: i32, token: &str) {
+        let mut conn = match self.get_conn().await {
+            Ok(c) => c,
+            Err(e) => {
+                warn!(contest_id, error = %e, "Redis contest slot connection failed on renewal");
+                return;
+            }
+        };
+        let result: Result<i64, _> = redis::cmd("EVAL")
+            .arg(RENEW_SCRIPT)
+            .arg(1)
+            .arg(self.key(contest_id))
+            .arg(self.ttl_secs)
+            .arg(token)
+            .query_async(&mut conn)
+            .await;
+        if let Err(e) = result {
+            warn!(contest_id, error = %e, "Redis contest slot renewal failed");
+            self.invalidate_conn().await;
+        }
+    }
+
+    async fn release(&self, contest_id: i32, token: &str) {
+        let mut conn = match self.get_conn().await {
+            Ok(c) => c,
+            Err(e) => {
+                warn!(contest_id, error = %e, "Redis contest slot connection failed on release");
+                return;
+            }
+        };
+        let result: Result<i64, _> = redis::cmd("ZREM")
+            .arg(self.key(contest_id))
+            .arg(token)
+            .query_async(&mut conn)
+            .await;
+        if let Err(e) = result {
+            warn!(contest_id, error = %e, "Redis contest slot release failed");
+            self.invalidate_conn().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contest_task(contest_id: i32) -> Task {
+        Task {
+            id: uuid::Uuid::new_v4().to_string(),
+            task_type: "operation".into(),
+            executor_name: "operation".into(),
+            payload: serde_json::json!({ "contest_id": contest_id }),
+            result_queue: "results".into(),
+            reply_queue: None,
+            priority: None,
+            trace_context: None,
+            schema_version: None,
+        }
+    }
+
+    fn slot(admission: Admission) -> Option<ContestSlot> {
+        match admission {
+            Admission::Run(slot) => slot,
+            Admission::AtCap => panic!("expected the task to be admitted"),
+        }
+    }
+
+    #[tokio::test]
+    async fn cap_of_one_defers_a_contest_but_not_others() {
+        let limiter = Arc::new(ContestLimiter::local(1));
+
+        let first = slot(limiter.admit(&contest_task(1)).await);
+        assert!(first.is_some());
+        assert!(matches!(
+            limiter.admit(&contest_task(1)).await,
+            Admission::AtCap
+        ));
+        assert!(slot(limiter.admit(&contest_task(2)).await).is_some());
+
+        drop(first);
+        assert!(slot(limiter.admit(&contest_task(1)).await).is_some());
+    }
+
+    #[tokio::test]
+    async fn zero_cap_never_defers() {
+        let limiter = Arc::new(ContestLimiter::local(0));
+        assert!(slot(limiter.admit(&contest_task(1)).await).is_none());
+        assert!(slot(limiter.admit(&contest_task(1)).await).is_none());
+    }
+
+    #[tokio::test]
+    async fn tasks_outside_a_contest_are_not_capped() {
+        let limiter = Arc::new(ContestLimiter::local(1));
+        let mut task = contest_task(1);
+        task.payload = serde_json::json!({});
+        assert!(slot(limiter.admit(&task).await).is_none());
+        assert!(slot(limiter.admit(&task).await).is_none());
+    }
+
+    #[tokio::test]
+    async fn unreachable_redis_falls_back_to_the_local_cap() {
+        let limiter = Arc::new(ContestLimiter::redis("redis://127.0.0.1:1", 1, 60).unwrap());
+
+        let first = slot(limiter.admit(&contest_task(1)).await);
+        assert!(first.is_some());
+        assert!(matches!(
+            limiter.admit(&contest_task(1)).await,
+            Admission::AtCap
+        ));
+
+        drop(first);
+        assert!(slot(limiter.admit(&contest_task(1)).await).is_some());
+    }
+}
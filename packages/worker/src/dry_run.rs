@@ -0,0 +1,298 @@
+//! Language dry-runs: compile and run a hello-world program through the same
+//! sandbox path as judged operations, so operators can check a worker's
+//! toolchain and sandbox setup before real submissions reach it.
+
+use anyhow::{Context, Result, anyhow};
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use serde::{Deserialize, Serialize};
+
+use crate::config::WorkerAppConfig;
+use crate::models::operation::executor::OperationTaskExecutor;
+use crate::models::operation::file_cacher::NoopFileCacher;
+use crate::models::operation::handler::OperationHandler;
+use crate::models::operation::models::{
+    Environment, IOConfig, IOTarget, OperationTask, SessionFile, Step,
+};
+use crate::models::operation::sandbox::{ExecutionResult, ResourceLimits, RunOptions};
+use crate::models::operation::task_cache::NoopTaskCacheStore;
+
+/// What every built-in hello-world program prints.
+pub const EXPECTED_OUTPUT: &str = "Hello, world!";
+
+/// Language ids accepted by [`DryRunLanguage::from_compilation_config`].
+pub const BUILTIN_LANGUAGES: &[&str] = &["c", "cpp", "java", "python3"];
+
+/// Plugin whose global config holds the compilers and flags submissions are
+/// judged with.
+const LANGUAGES_PLUGIN_ID: &str = "standard-languages";
+/// Config namespace of that plugin's compilers and flags.
+const COMPILATION_NAMESPACE: &str = "compilation";
+
+const ENV_ID: &str = "dry-run";
+const COMPILE_STEP: &str = "compile";
+const RUN_STEP: &str = "run";
+
+/// One language's entry in the standard-languages `compilation` config.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LanguageCompilationConfig {
+    pub compiler: Option<String>,
+    pub interpreter: Option<String>,
+    pub runner: Option<String>,
+    pub flags: Option<Vec<String>>,
+}
+
+impl LanguageCompilationConfig {
+    /// The plugin's schema defaults for `id`, which apply until an admin
+    /// saves the config.
+    fn schema_default(id: &str) -> Self {
+        let owned = |args: &[&str]| args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        match id {
+            "c" => Self {
+                compiler: Some("/usr/bin/gcc".to_string()),
+                flags: Some(owned(&["-O2", "-std=c17"])),
+                ..Default::default()
+            },
+            "cpp" => Self {
+                compiler: Some("/usr/bin/g++".to_string()),
+                flags: Some(owned(&["-O2", "-std=c++17"])),
+                ..Default::default()
+            },
+            "java" => Self {
+                compiler: Some("javac".to_string()),
+                runner: Some("java".to_string()),
+                flags: Some(Vec::new()),
+                ..Default::default()
+            },
+            "python3" => Self {
+                interpreter: Some("/usr/bin/python3".to_string()),
+                ..Default::default()
+            },
+            _ => Self::default(),
+        }
+    }
+}
+
+/// Reads the global `compilation` config saved for the standard-languages
+/// plugin, or `None` while it still uses its schema defaults.
+pub async fn load_saved_compilation_config(
+    db: &impl ConnectionTrait,
+) -> Result<Option<serde_json::Value>> {
+    let row = db
+        .query_one_raw(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT config FROM plugin_config WHERE scope = 'plugin' AND ref_id = $1 AND namespace = $2",
+            [LANGUAGES_PLUGIN_ID.into(), COMPILATION_NAMESPACE.into()],
+        ))
+        .await
+        .context("Failed to read the standard-languages compilation config")?;
+    row.map(|row| row.try_get::<serde_json::Value>("", "config"))
+        .transpose()
+        .context("Failed to decode the standard-languages compilation config")
+}
+
+/// A hello-world program and the commands that build and run it.
+#[derive(Debug, Clone)]
+pub struct DryRunLanguage {
+    pub id: String,
+    pub source_filename: String,
+    pub source: String,
+    /// `None` for interpreted languages.
+    pub compile: Option<Vec<String>>,
+    pub run: Vec<String>,
+}
+
+impl DryRunLanguage {
+    /// The program for `id`, built with the commands the standard-languages
+    /// plugin resolves for a judged submission. `saved` is the result of
+    /// [`load_saved_compilation_config`]; like the plugin, a language left
+    /// out of a saved config has no compiler at all.
+    pub fn from_compilation_config(id: &str, saved: Option<&serde_json::Value>) -> Option<Self> {
+        if !BUILTIN_LANGUAGES.contains(&id) {
+            return None;
+        }
+        let config = match saved {
+            Some(saved) => saved
+                .get(id)
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or_default(),
+            None => LanguageCompilationConfig::schema_default(id),
+        };
+        let compiler = config.compiler.unwrap_or_default();
+        let flags = config.flags.unwrap_or_default();
+        let compile = |source: &str, output: &[&str]| {
+            let mut argv = vec![compiler.clone()];
+            argv.extend(flags.iter().cloned());
+            argv.push(source.to_string());
+            argv.extend(output.iter().map(|s| s.to_string()));
+            argv
+        };
+
+        let (source_filename, source, compile, run) = match id {
+            "c" => (
+                "main.c",
+                "#include <stdio.h>\nint main(void) { puts(\"Hello, world!\"); return 0; }\n",
+                Some(compile("main.c", &["-o", "main", "-lm"])),
+                vec!["./main".to_string()],
+            ),
+            "cpp" => (
+                "main.cpp",
+                "#include <iostream>\nint main() { std::cout << \"Hello, world!\" << std::endl; }\n",
+                Some(compile("main.cpp", &["-o", "main"])),
+                vec!["./main".to_string()],
+            ),
+            "java" => (
+                "Main.java",
+                "public class Main {\n    public static void main(String[] args) {\n        System.out.println(\"Hello, world!\");\n    }\n}\n",
+                Some(compile("Main.java", &[])),
+                vec![
+                    config.runner.unwrap_or_default(),
+                    "-cp".to_string(),
+                    ".".to_string(),
+                    "Main".to_string(),
+                ],
+            ),
+            _ => (
+                "main.py",
+                "print(\"Hello, world!\")\n",
+                None,
+                vec![
+                    config.interpreter.unwrap_or_default(),
+                    "main.py".to_string(),
+                ],
+            ),
+        };
+        Some(Self {
+            id: id.to_string(),
+            source_filename: source_filename.to_string(),
+            source: source.to_string(),
+            compile,
+            run,
+        })
+    }
+}
+
+/// Sandbox results of a dry-run. `run` is `None` when compilation failed.
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunReport {
+    pub language: String,
+    pub compile: Option<ExecutionResult>,
+    pub run: Option<ExecutionResult>,
+}
+
+impl DryRunReport {
+    /// Whether the program ran cleanly and printed [`EXPECTED_OUTPUT`].
+    pub fn succeeded(&self) -> bool {
+        let clean = |r: &ExecutionResult| r.exit_code == Some(0) && r.status == "OK";
+        self.compile.as_ref().is_none_or(clean)
+            && self
+                .run
+                .as_ref()
+                .is_some_and(|r| clean(r) && r.stdout.trim() == EXPECTED_OUTPUT)
+    }
+}
+
+/// An operation handler using the configured sandbox backend, without the
+/// blob store or task cache, which a dry-run never touches.
+pub fn handler_from_config(
+    config: &WorkerAppConfig,
+    metrics: common::metrics::Metrics,
+) -> OperationHandler {
+    OperationHandler::new(
+        OperationTaskExecutor::sandbox_manager_from_config(Some(config)),
+        Box::new(NoopFileCacher),
+        Box::new(NoopTaskCacheStore),
+        String::new(),
+        metrics,
+    )
+}
+
+/// Compiles and runs `language`'s program on `handler`.
+///
+/// Errors only when the operation itself could not be carried out; a
+/// misconfigured toolchain shows up in the returned report instead.
+pub async fn dry_run(
+    handler: &OperationHandler,
+    language: &DryRunLanguage,
+) -> Result<DryRunReport> {
+    let mut tasks = Vec::new();
+    if let Some(compile) = &language.compile {
+        tasks.push(Step {
+            id: COMPILE_STEP.to_string(),
+            env_ref: ENV_ID.to_string(),
+            argv: compile.clone(),
+            conf: RunOptions {
+                resource_limits: limits(30.0),
+                ..Default::default()
+            },
+            io: IOConfig {
+                stdin: IOTarget::Null,
+                ..Default::default()
+            },
+            collect: vec![],
+            depends_on: vec![],
+            cache: None,
+        });
+    }
+    tasks.push(Step {
+        id: RUN_STEP.to_string(),
+        env_ref: ENV_ID.to_string(),
+        argv: language.run.clone(),
+        conf: RunOptions {
+            resource_limits: limits(10.0),
+            ..Default::default()
+        },
+        io: IOConfig {
+            stdin: IOTarget::Null,
+            ..Default::default()
+        },
+        collect: vec![],
+        depends_on: tasks.iter().map(|t| t.id.clone()).collect(),
+        cache: None,
+    });
+
+    let operation = OperationTask {
+        environments: vec![Environment {
+            id: ENV_ID.to_string(),
+            files_in: vec![(
+                language.source_filename.clone(),
+                SessionFile::Content {
+                    content: language.source.clone(),
+                },
+            )],
+        }],
+        tasks,
+        channels: vec![],
+        priority: None,
+        target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
+    };
+
+    let mut result = handler
+        .execute(&operation)
+        .await
+        .map_err(|e| anyhow!("Dry-run for {} failed: {e:#}", language.id))?;
+    let compile = result.task_results.remove(COMPILE_STEP);
+    // A run skipped after a failed compile carries no sandbox result.
+    let run = match &compile {
+        Some(compile) if !compile.success => None,
+        _ => result.task_results.remove(RUN_STEP),
+    };
+    Ok(DryRunReport {
+        language: language.id.clone(),
+        compile: compile.map(|task| task.sandbox_result),
+        run: run.map(|task| task.sandbox_result),
+    })
+}
+
+/// Generous limits: a dry-run checks the toolchain, not performance, and
+/// compilers and JVMs need more room than judged programs.
+fn limits(time_limit_s: f64) -> ResourceLimits {
+    ResourceLimits {
+        time_limit: Some(time_limit_s),
+        wall_time_limit: Some(time_limit_s * 2.0),
+        memory_limit: Some(1_048_576),
+        process_limit: Some(64),
+        ..Default::default()
+    }
+}
@@ -19,6 +19,9 @@ pub enum WorkerError {
 
     #[error("Task panicked: {0}")]
     TaskPanic(String),
+
+    #[error("Task timed out: {0}")]
+    Timeout(String),
 }
 
 impl From<mq::error::MqError> for WorkerError {
@@ -0,0 +1,109 @@
+use broccoli_server_sdk::types::JudgeJob;
+use redis::AsyncCommands;
+use redis::aio::MultiplexedConnection;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// Must match the server's `JUDGE_EPOCH_KEY_PREFIX`.
+const JUDGE_EPOCH_PREFIX: &str = "broccoli:judge_epoch:";
+
+/// Reads the judge epoch the server last applied to each submission.
+///
+/// A rejudge raises the recorded epoch before dispatching its own jobs, so
+/// jobs still queued for an older epoch can be dropped without running.
+pub struct JudgeEpochs {
+    client: redis::Client,
+    conn: Mutex<Option<MultiplexedConnection>>,
+}
+
+impl JudgeEpochs {
+    pub fn new(redis_url: &str) -> Result<Self, redis::RedisError> {
+        let client = redis::Client::open(redis_url)?;
+        Ok(Self {
+            client,
+            conn: Mutex::new(None),
+        })
+    }
+
+    async fn get_conn(&self) -> Result<MultiplexedConnection, redis::RedisError> {
+        let mut guard = self.conn.lock().await;
+        if let Some(ref conn) = *guard {
+            return Ok(conn.clone());
+        }
+        let conn = self.client.get_multiplexed_async_connection().await?;
+        *guard = Some(conn.clone());
+        Ok(conn)
+    }
+
+    async fn invalidate_conn(&self) {
+        let mut guard = self.conn.lock().await;
+        *guard = None;
+    }
+
+    /// Whether a newer epoch than `job`'s has been applied to its submission.
+    /// Fails open: on any Redis error the job runs.
+    pub async fn is_superseded(&self, job: JudgeJob) -> bool {
+        let key = format!("{JUDGE_EPOCH_PREFIX}{}", job.submission_id);
+        let mut conn = match self.get_conn().await {
+            Ok(c) => c,
+            Err(e) => {
+                warn!(
+                    submission_id = job.submission_id,
+                    error = %e,
+                    "Redis judge epoch connection failed, running job (fail-open)"
+                );
+                return false;
+            }
+        };
+
+        let current: Result<Option<i32>, _> = conn.get(&key).await;
+        match current {
+            Ok(Some(epoch)) => epoch > job.judge_epoch,
+            Ok(None) => false,
+            Err(e) => {
+                warn!(
+                    submission_id = job.submission_id,
+                    error = %e,
+                    "Redis judge epoch lookup failed, running job (fail-open)"
+                );
+                self.invalidate_conn().await;
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "requires a live local Redis instance on redis://localhost:6379"]
+    async fn older_epoch_is_superseded() {
+        let epochs = JudgeEpochs::new("redis://localhost:6379").unwrap();
+        let submission_id = i32::MAX - (uuid::Uuid::new_v4().as_u128() % 1_000_000) as i32;
+        let key = format!("{JUDGE_EPOCH_PREFIX}{submission_id}");
+        let mut conn = epochs.get_conn().await.unwrap();
+        let _: () = conn.set_ex(&key, 3, 60).await.unwrap();
+
+        let job = |judge_epoch| JudgeJob {
+            submission_id,
+            judge_epoch,
+        };
+        assert!(epochs.is_superseded(job(2)).await);
+        assert!(!epochs.is_superseded(job(3)).await);
+        assert!(!epochs.is_superseded(job(4)).await);
+
+        let _: () = conn.del(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_open_on_bad_url() {
+        let epochs = JudgeEpochs::new("redis://nonexistent:9999").unwrap();
+        let job = JudgeJob {
+            submission_id: 1,
+            judge_epoch: 1,
+        };
+        assert!(!epochs.is_superseded(job).await);
+    }
+}
@@ -1,7 +1,12 @@
 pub mod config;
+pub mod contest_limit;
 pub mod dedup;
+pub mod dry_run;
 pub mod error;
+pub mod judge_epoch;
 pub mod models;
+pub mod task_runner;
+pub mod temp_dir;
 
 pub use config::{DatabaseConfig, MqAppConfig, StorageConfig, WorkerAppConfig, WorkerConfig};
 pub use error::WorkerError;
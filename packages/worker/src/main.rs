@@ -1,30 +1,40 @@
 mod config;
+mod contest_limit;
 mod dedup;
+mod dry_run;
 mod error;
 mod heartbeat;
+mod judge_epoch;
 mod models;
 mod system_info;
+mod task_runner;
+mod temp_dir;
 
 use anyhow::Context;
+use common::DlqConfig;
 use common::metrics::Metrics;
-use common::retry::{
-    RetryCleanupGuard, RetryDecision, RetryTracker, calculate_backoff, spawn_cleanup_task,
-};
+use common::retry::{RetryTracker, spawn_cleanup_task};
 use common::worker::Task;
-use common::{DlqConfig, DlqEnvelope, DlqErrorCode, DlqMessageType};
-use mq::{BroccoliError, BrokerMessage, MqConfig, init_mq};
-use opentelemetry::KeyValue;
+use mq::{
+    BroccoliError, BrokerMessage, ConsumeConfig, Envelope, MqConfig, ReconnectPolicy, init_mq,
+};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tracing::{error, info, warn};
 
+use crate::contest_limit::{Admission, ContestLimiter};
 use crate::dedup::RedisTaskDedup;
-use crate::error::WorkerError;
+use crate::dry_run::DryRunLanguage;
 use crate::heartbeat::{HeartbeatConfig, InFlightCounter};
+use crate::judge_epoch::JudgeEpochs;
+use crate::models::operation::executor::OperationTaskExecutor;
 use crate::models::worker::Worker;
 use crate::system_info::SystemInfo;
+use crate::task_runner::{
+    TaskOutcome, dead_letter_unsupported_schema, drop_if_superseded, run_task,
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -39,7 +49,11 @@ async fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
-    let config = config::WorkerAppConfig::load().context("Failed to load config")?;
+    if let Some(language) = std::env::args().skip_while(|a| a != "--dry-run").nth(1) {
+        return run_dry_run(&language).await;
+    }
+
+    let mut config = config::WorkerAppConfig::load().context("Failed to load config")?;
 
     let _telemetry_guard = common::observability::init_tracing(&config.observability);
     info!("Worker starting: {}", config.worker.id);
@@ -49,6 +63,13 @@ async fn main() -> anyhow::Result<()> {
 
     spawn_metrics_server(prometheus_registry);
 
+    let temp_base = config.worker.temp_dir();
+    let cleanup_sandbox = OperationTaskExecutor::sandbox_manager_from_config(Some(&config));
+    temp_dir::clean_stale_dirs(&temp_base, cleanup_sandbox.as_ref()).await;
+    let worker_temp_dir =
+        temp_dir::WorkerTempDir::claim(&temp_base).context("Failed to claim worker temp dir")?;
+    config.worker.temp_dir = Some(worker_temp_dir.path().to_string_lossy().into_owned());
+
     let mq = Arc::new(
         init_mq(MqConfig {
             url: config.mq.url.clone(),
@@ -83,6 +104,37 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    let judge_epochs = match JudgeEpochs::new(&config.mq.url) {
+        Ok(epochs) => Some(Arc::new(epochs)),
+        Err(e) => {
+            warn!(error = %e, "Failed to initialize judge epoch checks, running superseded jobs");
+            None
+        }
+    };
+
+    let max_per_contest = config.worker.max_concurrent_per_contest;
+    let contest_limiter = Arc::new(if max_per_contest == 0 {
+        ContestLimiter::local(0)
+    } else {
+        match ContestLimiter::redis(
+            &config.mq.url,
+            max_per_contest,
+            config.mq.dlq.stuck_job_timeout_secs,
+        ) {
+            Ok(limiter) => {
+                info!(max_per_contest, "Per-contest concurrency cap enabled");
+                limiter
+            }
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    "Failed to initialize shared contest slots, capping per worker only"
+                );
+                ContestLimiter::local(max_per_contest)
+            }
+        }
+    });
+
     let op_dlq_queue = config.mq.operation_dlq_queue_name.clone();
     let dlq_config = config.mq.dlq.clone();
     let mq_for_handler = Arc::clone(&mq);
@@ -114,7 +166,9 @@ async fn main() -> anyhow::Result<()> {
         in_flight.clone(),
     );
 
-    let retry_tracker = Arc::new(Mutex::new(RetryTracker::new(dlq_config.max_retries)));
+    let retry_tracker = Arc::new(Mutex::new(
+        RetryTracker::new(dlq_config.max_retries).with_max_history(dlq_config.max_retry_history),
+    ));
 
     let _cleanup_handle = spawn_cleanup_task(
         retry_tracker.clone(),
@@ -138,9 +192,12 @@ async fn main() -> anyhow::Result<()> {
         "{}:worker:{}",
         config.mq.operation_queue_name, config.worker.id
     );
+    let reconnect = ReconnectPolicy::from(&config.mq);
+    let consume_config = ConsumeConfig::with_concurrency(config.mq.operation_concurrency);
     info!(
         shared_queue = %shared_queue,
         private_queue = %private_queue,
+        concurrency = consume_config.concurrency,
         "Subscribing to operation queues"
     );
 
@@ -150,32 +207,38 @@ async fn main() -> anyhow::Result<()> {
         Reconnect(&'static str, BroccoliError),
     }
 
+    let mut reconnect_attempt = 0;
     loop {
         if shutdown.load(Ordering::Relaxed) {
             break;
         }
 
-        let handler = {
+        let handler = |queue: String| {
             let mq_for_handler = Arc::clone(&mq_for_handler);
             let worker = Arc::clone(&worker);
             let op_dlq_queue = op_dlq_queue.clone();
             let dlq_config_handler = dlq_config.clone();
             let retry_tracker = Arc::clone(&retry_tracker);
             let dedup = dedup.clone();
+            let judge_epochs = judge_epochs.clone();
+            let contest_limiter = Arc::clone(&contest_limiter);
             let metrics = metrics.clone();
             let in_flight_for_handler = in_flight.clone();
             let shutdown_for_handler = shutdown.clone();
 
-            move |message: BrokerMessage<Task>| {
+            move |message: BrokerMessage<Envelope<Task>>| {
                 let mq = Arc::clone(&mq_for_handler);
                 let worker = Arc::clone(&worker);
                 let dlq_queue = op_dlq_queue.clone();
                 let dlq_config = dlq_config_handler.clone();
                 let retry_tracker = Arc::clone(&retry_tracker);
                 let dedup = dedup.clone();
+                let judge_epochs = judge_epochs.clone();
+                let contest_limiter = Arc::clone(&contest_limiter);
                 let metrics = metrics.clone();
                 let in_flight = in_flight_for_handler.clone();
                 let shutdown = shutdown_for_handler.clone();
+                let queue = queue.clone();
                 async move {
                     if shutdown.load(Ordering::Relaxed) {
                         // Refuse the message so broccoli_queue rejects (requeues) it
@@ -187,12 +250,15 @@ async fn main() -> anyhow::Result<()> {
                     let _guard = in_flight.guard();
                     process_message(
                         message,
+                        &queue,
                         &worker,
                         &mq,
                         &dlq_queue,
                         &dlq_config,
                         &retry_tracker,
                         dedup.as_deref(),
+                        judge_epochs.as_deref(),
+                        &contest_limiter,
                         &metrics,
                     )
                     .await
@@ -200,11 +266,23 @@ async fn main() -> anyhow::Result<()> {
             }
         };
 
-        let shared_fut = mq.process_messages(&shared_queue, None, None, handler.clone());
-        let private_fut = mq.process_messages(&private_queue, None, None, handler);
+        let shared_fut = mq::consume(
+            &mq,
+            &shared_queue,
+            &consume_config,
+            handler(shared_queue.clone()),
+        );
+        let private_fut = mq::consume(
+            &mq,
+            &private_queue,
+            &consume_config,
+            handler(private_queue.clone()),
+        );
 
         tokio::pin!(shared_fut, private_fut);
 
+        let connected_at = Instant::now();
+
         let outcome = tokio::select! {
             biased;
             _ = wait_for_shutdown(&shutdown) => OpOutcome::Shutdown,
@@ -228,9 +306,22 @@ async fn main() -> anyhow::Result<()> {
                 break;
             }
             OpOutcome::Reconnect(which, e) => {
-                error!(consumer = which, error = %e, "Operation MQ error, reconnecting in 5s...");
+                // A consumer that stayed up past the longest backoff was
+                // healthy, so this drop starts a fresh backoff sequence.
+                if connected_at.elapsed() > reconnect.max_delay {
+                    reconnect_attempt = 0;
+                }
+                reconnect_attempt += 1;
+                let delay = reconnect.delay(reconnect_attempt);
+                error!(
+                    consumer = which,
+                    error = %e,
+                    attempt = reconnect_attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    "Operation MQ error, reconnecting"
+                );
                 tokio::select! {
-                    _ = tokio::time::sleep(Duration::from_secs(5)) => {}
+                    _ = tokio::time::sleep(delay) => {}
                     _ = wait_for_shutdown(&shutdown) => {
                         drain_in_flight(&in_flight, drain_timeout).await;
                         break;
@@ -245,6 +336,42 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Compiles and runs the built-in hello-world program for `language_id` on
+/// the configured sandbox, with the compilers and flags submissions are
+/// judged with, and prints the sandbox results as JSON.
+async fn run_dry_run(language_id: &str) -> anyhow::Result<()> {
+    let config = config::WorkerAppConfig::load().context("Failed to load config")?;
+    let mut db_options = sea_orm::ConnectOptions::new(config.database.url.clone());
+    db_options
+        .max_connections(1)
+        .connect_timeout(Duration::from_secs(5))
+        .sqlx_logging(false);
+    let db = sea_orm::Database::connect(db_options)
+        .await
+        .context("Failed to connect to database")?;
+    let saved = dry_run::load_saved_compilation_config(&db).await?;
+    let language = DryRunLanguage::from_compilation_config(language_id, saved.as_ref())
+        .with_context(|| {
+            format!(
+                "Unknown dry-run language '{language_id}', expected one of: {}",
+                dry_run::BUILTIN_LANGUAGES.join(", ")
+            )
+        })?;
+    let (metrics, _registry) =
+        common::observability::init_metrics(&config.observability.otlp.service_name);
+
+    let handler = dry_run::handler_from_config(&config, metrics);
+    let report = dry_run::dry_run(&handler, &language).await?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if !report.succeeded() {
+        anyhow::bail!(
+            "Dry-run for {language_id} did not print \"{}\"; check the results above",
+            dry_run::EXPECTED_OUTPUT
+        );
+    }
+    Ok(())
+}
+
 async fn run_healthcheck() -> anyhow::Result<()> {
     use sea_orm::{ConnectionTrait, Database, DbBackend, Statement};
 
@@ -344,18 +471,29 @@ async fn drain_in_flight(in_flight: &InFlightCounter, timeout: Duration) {
 
 #[allow(clippy::too_many_arguments)]
 async fn process_message(
-    message: BrokerMessage<Task>,
+    message: BrokerMessage<Envelope<Task>>,
+    queue: &str,
     worker: &Arc<Worker>,
     mq: &Arc<mq::Mq>,
     dlq_queue: &str,
     dlq_config: &DlqConfig,
     retry_tracker: &Arc<Mutex<RetryTracker>>,
     dedup: Option<&RedisTaskDedup>,
+    judge_epochs: Option<&JudgeEpochs>,
+    contest_limiter: &Arc<ContestLimiter>,
     metrics: &Metrics,
 ) -> Result<(), BroccoliError> {
-    let task = message.payload;
+    let Envelope {
+        headers,
+        payload: task,
+    } = message.payload;
     let task_id = task.id.clone();
 
+    let schema_version = task.schema_version.or(headers.schema_version);
+    if dead_letter_unsupported_schema(&task, schema_version, mq, dlq_queue, metrics).await {
+        return Ok(());
+    }
+
     if let Some(dedup) = dedup {
         match dedup.try_claim(&task_id).await {
             crate::dedup::ClaimOutcome::Claimed => {}
@@ -376,6 +514,8 @@ async fn process_message(
         job_id = %task_id,
         task_type = %task.task_type,
         executor_name = %task.executor_name,
+        schema_version = ?schema_version,
+        trace_id = ?headers.trace_id,
         "Received task"
     );
 
@@ -387,145 +527,45 @@ async fn process_message(
         tracing::Span::current().add_link(remote_cx.span().span_context().clone());
     }
 
-    let task_attrs = [KeyValue::new("task_type", task.task_type.clone())];
-    let task_start = std::time::Instant::now();
-    let mut cleanup_guard = RetryCleanupGuard::new(retry_tracker, &task_id);
-
-    loop {
-        match process_task(&task, worker, mq).await {
-            Ok(()) => {
-                metrics
-                    .task_process_duration
-                    .record(task_start.elapsed().as_secs_f64(), &task_attrs);
-
-                retry_tracker.lock().await.clear(&task_id);
-                cleanup_guard.defuse();
-                return Ok(());
-            }
-            Err(e) => {
-                let error_str = e.to_string();
-                let decision = retry_tracker
-                    .lock()
-                    .await
-                    .record_failure(&task_id, &error_str);
-
-                match decision {
-                    RetryDecision::Retry { attempt, .. } => {
-                        metrics.task_retries_total.add(1, &task_attrs);
-
-                        let delay = calculate_backoff(
-                            attempt,
-                            dlq_config.base_delay_ms,
-                            dlq_config.max_delay_ms,
-                        );
-                        warn!(
-                            job_id = %task_id,
-                            attempt,
-                            delay_ms = delay.as_millis() as u64,
-                            error = %e,
-                            "Task failed, retrying"
-                        );
-                        tokio::time::sleep(delay).await;
-                    }
-                    RetryDecision::Exhausted { history } => {
-                        metrics.task_retries_total.add(1, &task_attrs);
-                        metrics.dlq_messages_total.add(1, &task_attrs);
-
-                        error!(
-                            job_id = %task_id,
-                            retry_count = history.len(),
-                            error = %e,
-                            "Max retries exhausted, sending to DLQ"
-                        );
-
-                        let error_result = common::worker::TaskResult {
-                            task_id: task_id.clone(),
-                            success: false,
-                            output: serde_json::json!({}),
-                            error: Some(format!(
-                                "Operation failed after {} retries: {}",
-                                history.len(),
-                                error_str
-                            )),
-                        };
-                        if let Err(e) = mq
-                            .publish(task.reply_queue_name(), None, &error_result, None)
-                            .await
-                        {
-                            error!(job_id = %task_id, error = %e, "Failed to publish error result for operation task");
-                        }
-
-                        let payload = serde_json::to_value(&task).unwrap_or_else(|ser_err| {
-                            error!(error = %ser_err, "Failed to serialize task for DLQ");
-                            serde_json::json!({ "task_id": task_id })
-                        });
-
-                        let envelope = DlqEnvelope {
-                            message_id: task_id.clone(),
-                            message_type: DlqMessageType::OperationTask,
-                            submission_id: None,
-                            payload,
-                            error_code: DlqErrorCode::MaxRetriesExceeded,
-                            error_message: error_str,
-                            retry_history: history,
-                        };
-
-                        if let Err(dlq_err) = mq.publish(dlq_queue, None, &envelope, None).await {
-                            error!(
-                                job_id = %task_id,
-                                error = %dlq_err,
-                                "CRITICAL: Failed to publish to DLQ, message may be lost"
-                            );
-                        }
-
-                        if let Some(dedup) = dedup {
-                            dedup.release(&task_id).await;
-                        }
-
-                        cleanup_guard.defuse();
-                        return Ok(());
-                    }
-                }
-            }
+    if let Some(judge_epochs) = judge_epochs
+        && drop_if_superseded(&task, judge_epochs, mq).await
+    {
+        if let Some(dedup) = dedup {
+            dedup.release(&task_id).await;
         }
+        return Ok(());
     }
-}
 
-async fn process_task(
-    task: &Task,
-    worker: &Arc<Worker>,
-    mq: &Arc<mq::Mq>,
-) -> Result<(), WorkerError> {
-    info!(
-        job_id = %task.id,
-        task_type = %task.task_type,
-        "Processing task"
-    );
-
-    let worker = Arc::clone(worker);
-    let task_clone = task.clone();
-    let result = tokio::spawn(async move { worker.execute_task(task_clone).await })
-        .await
-        .map_err(|e| {
-            if e.is_panic() {
-                WorkerError::TaskPanic(format!("{e}"))
-            } else {
-                WorkerError::Internal(format!("Task join error: {e}"))
+    // Held until this function returns, across retries.
+    let _contest_slot = match contest_limiter.admit(&task).await {
+        Admission::Run(slot) => slot,
+        Admission::AtCap => {
+            info!(job_id = %task_id, "Contest at its concurrency cap, requeuing task");
+            if let Some(dedup) = dedup {
+                dedup.release(&task_id).await;
             }
-        })??;
-
-    mq.publish(task.reply_queue_name(), None, &result, None)
-        .await
-        .map_err(|e| WorkerError::Mq(e.to_string()))?;
-
-    info!(
-        job_id = %task.id,
-        task_result_id = %result.task_id,
-        success = result.success,
-        result_queue = %task.reply_queue_name(),
-        "Task finished"
-    );
+            contest_limiter
+                .requeue(mq, queue, &Envelope::new(headers, task))
+                .await?;
+            return Ok(());
+        }
+    };
 
+    let outcome = run_task(
+        &task,
+        worker,
+        mq,
+        dlq_queue,
+        dlq_config,
+        retry_tracker,
+        metrics,
+    )
+    .await;
+    if outcome == TaskOutcome::DeadLettered
+        && let Some(dedup) = dedup
+    {
+        dedup.release(&task_id).await;
+    }
     Ok(())
 }
 
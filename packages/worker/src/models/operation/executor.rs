@@ -8,7 +8,7 @@ use super::sandbox::isolate::IsolateSandboxManager;
 use super::sandbox::mock::MockSandboxManager;
 use super::task_cache::{DatabaseTaskCacheStore, NoopTaskCacheStore, TaskCacheStore};
 use crate::config::WorkerAppConfig;
-use crate::models::operation::handler::OperationHandler;
+use crate::models::operation::handler::{OperationHandler, WatchdogTimeout};
 use anyhow::Result;
 use async_trait::async_trait;
 use common::storage::config::create_blob_store;
@@ -35,7 +35,9 @@ impl OperationTaskExecutor {
                 task_cache,
                 fingerprint,
                 metrics,
-            ),
+            )
+            .with_sandbox_pool_size(config.worker.batch_size)
+            .with_temp_dir(config.worker.temp_dir()),
         })
     }
 
@@ -55,7 +57,21 @@ impl OperationTaskExecutor {
         }
     }
 
-    fn sandbox_manager_from_config(
+    /// Overrides the slack the watchdog adds on top of the summed step limits.
+    #[allow(dead_code)]
+    pub fn with_watchdog_margin(mut self, margin: Duration) -> Self {
+        self.operation_executor = self.operation_executor.with_watchdog_margin(margin);
+        self
+    }
+
+    /// Sets how many sandboxes are kept for reuse between operations.
+    #[allow(dead_code)]
+    pub fn with_sandbox_pool_size(mut self, size: usize) -> Self {
+        self.operation_executor = self.operation_executor.with_sandbox_pool_size(size);
+        self
+    }
+
+    pub(crate) fn sandbox_manager_from_config(
         config: Option<&WorkerAppConfig>,
     ) -> Box<dyn SandboxManager + Send + Sync> {
         let backend = config
@@ -67,7 +83,9 @@ impl OperationTaskExecutor {
 
         if backend.eq_ignore_ascii_case("mock") {
             info!(sandbox_backend = "mock", "Using operation sandbox backend");
-            Box::new(MockSandboxManager::default())
+            Box::new(config.map_or_else(MockSandboxManager::default, |c| {
+                MockSandboxManager::in_temp_dir(&c.worker.temp_dir())
+            }))
         } else {
             if !backend.eq_ignore_ascii_case("isolate") {
                 warn!(sandbox_backend = %backend, "Unknown sandbox backend, fallback to isolate");
@@ -80,7 +98,10 @@ impl OperationTaskExecutor {
                 .map(|c| c.worker.isolate_bin.clone())
                 .unwrap_or_else(|| "isolate".to_string());
             let enable_cgroups = config.map(|c| c.worker.enable_cgroups).unwrap_or(false);
-            Box::new(IsolateSandboxManager::new(isolate_bin, enable_cgroups))
+            let temp_dir = config.map_or_else(std::env::temp_dir, |c| c.worker.temp_dir());
+            Box::new(
+                IsolateSandboxManager::new(isolate_bin, enable_cgroups).with_temp_dir(temp_dir),
+            )
         }
     }
 
@@ -188,12 +209,15 @@ impl Executor for OperationTaskExecutor {
                             .unwrap_or_else(|| "Operation failed".into()),
                     )
                 },
+                worker_id: None,
             }),
+            Err(e) if e.is::<WatchdogTimeout>() => Err(e),
             Err(e) => Ok(TaskResult {
                 task_id: task.id,
                 success: false,
                 output: serde_json::json!({ "error": format!("{e:#}") }),
                 error: Some(format!("{e:#}")),
+                worker_id: None,
             }),
         }
     }
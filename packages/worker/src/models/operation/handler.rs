@@ -1,16 +1,21 @@
 use super::file_cacher::FileCacher;
 use super::models::*;
+use super::sandbox::guard::SandboxCleanupGuard;
+use super::sandbox::pool::SandboxPool;
 use super::sandbox::{
-    DirectoryOptions, DirectoryRule, ExecutionResult, RunOptions, SandboxManager,
+    DirectoryOptions, DirectoryRule, ExecutionResult, RunOptions, SandboxManager, StderrCapture,
 };
 use super::task_cache::{TaskCacheStore, compute_cache_key};
+use crate::temp_dir::CHANNELS_PREFIX;
 use anyhow::{Context, Result, anyhow};
 use futures::future::join_all;
 use std::collections::{HashMap, HashSet};
 #[cfg(unix)]
 use std::os::unix::fs::FileTypeExt;
 use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
 use tracing::{debug, error, info, instrument, warn};
 
 fn safe_join(base: &Path, relative: &str) -> Result<PathBuf> {
@@ -31,7 +36,41 @@ fn safe_join(base: &Path, relative: &str) -> Result<PathBuf> {
     Ok(resolved)
 }
 
-static NEXT_BOX_ID: AtomicU32 = AtomicU32::new(0);
+static NEXT_CHANNELS_DIR_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Slack added on top of the summed step wall limits before the watchdog
+/// aborts an operation. Covers sandbox setup, file transfer and cleanup.
+pub const DEFAULT_WATCHDOG_MARGIN: Duration = Duration::from_secs(30);
+
+/// Wall-clock allowance for a step that declares neither `wall_time_limit`
+/// nor `time_limit` (e.g. compilation steps without explicit limits).
+const UNLIMITED_STEP_ALLOWANCE_SECS: f64 = 60.0;
+
+/// Returned when an operation outlives its watchdog deadline. The executor
+/// propagates it as a hard error so the retry/DLQ path takes over instead of
+/// reporting an ordinary failed result.
+#[derive(Debug, thiserror::Error)]
+#[error("Operation exceeded watchdog deadline of {}ms", .0.as_millis())]
+pub struct WatchdogTimeout(pub Duration);
+
+/// Upper bound on how long an operation may run: the sum of every step's
+/// wall limit (steps in a layer run concurrently, so this over-approximates)
+/// plus `margin`.
+pub fn watchdog_deadline(operation: &OperationTask, margin: Duration) -> Duration {
+    let steps_secs: f64 = operation
+        .tasks
+        .iter()
+        .map(|step| {
+            let limits = &step.conf.resource_limits;
+            let base = limits
+                .wall_time_limit
+                .or(limits.time_limit)
+                .unwrap_or(UNLIMITED_STEP_ALLOWANCE_SECS);
+            base.max(0.0) + limits.extra_time.unwrap_or(0.0).max(0.0)
+        })
+        .sum();
+    Duration::from_secs_f64(steps_secs) + margin
+}
 
 fn validate_pipe_name(name: &str) -> Result<()> {
     if name.is_empty() {
@@ -53,21 +92,14 @@ fn validate_pipe_name(name: &str) -> Result<()> {
     Ok(())
 }
 
-fn allocate_box_id() -> String {
-    let id = NEXT_BOX_ID.fetch_add(1, Ordering::Relaxed) % 1000;
-    id.to_string()
-}
-
 struct EnvironmentList {
-    id: String,
     box_id: String,
     working_dir: PathBuf,
 }
 
 impl EnvironmentList {
-    fn new(id: String, box_id: String, working_dir: PathBuf) -> Self {
+    fn new(box_id: String, working_dir: PathBuf) -> Self {
         Self {
-            id,
             box_id,
             working_dir,
         }
@@ -75,11 +107,14 @@ impl EnvironmentList {
 }
 
 pub struct OperationHandler {
-    sandbox_manager: Box<dyn SandboxManager + Send + Sync>,
+    sandbox_manager: Arc<dyn SandboxManager + Send + Sync>,
+    sandbox_pool: SandboxPool,
     file_cacher: Box<dyn FileCacher>,
     task_cache: Box<dyn TaskCacheStore>,
     toolchain_fingerprint: String,
     metrics: common::metrics::Metrics,
+    watchdog_margin: Duration,
+    temp_dir: PathBuf,
 }
 
 impl OperationHandler {
@@ -90,15 +125,37 @@ impl OperationHandler {
         toolchain_fingerprint: String,
         metrics: common::metrics::Metrics,
     ) -> Self {
+        let sandbox_manager: Arc<dyn SandboxManager + Send + Sync> = Arc::from(sandbox_manager);
         Self {
+            sandbox_pool: SandboxPool::new(Arc::clone(&sandbox_manager), 0),
             sandbox_manager,
             file_cacher,
             task_cache,
             toolchain_fingerprint,
             metrics,
+            watchdog_margin: DEFAULT_WATCHDOG_MARGIN,
+            temp_dir: std::env::temp_dir(),
         }
     }
 
+    pub fn with_watchdog_margin(mut self, margin: Duration) -> Self {
+        self.watchdog_margin = margin;
+        self
+    }
+
+    /// Keeps up to `size` sandboxes initialized between operations instead of
+    /// creating and removing one per environment.
+    pub fn with_sandbox_pool_size(mut self, size: usize) -> Self {
+        self.sandbox_pool = SandboxPool::new(Arc::clone(&self.sandbox_manager), size);
+        self
+    }
+
+    /// Creates shared channel directories under `temp_dir`.
+    pub fn with_temp_dir(mut self, temp_dir: PathBuf) -> Self {
+        self.temp_dir = temp_dir;
+        self
+    }
+
     #[instrument(skip(self, operation))]
     pub async fn execute(&self, operation: &OperationTask) -> Result<OperationResult> {
         info!(
@@ -107,33 +164,30 @@ impl OperationHandler {
             operation.tasks.len()
         );
 
+        let mut sandbox_guard = SandboxCleanupGuard::new(Arc::clone(&self.sandbox_manager));
         let mut environments = HashMap::new();
         for env_config in operation.environments.iter() {
-            let box_id = allocate_box_id();
-            debug!(env_id = %env_config.id, box_id = %box_id, "Initializing environment");
-
-            let working_dir = match self.create_sandbox(&box_id).await {
-                Ok(dir) => dir,
+            let (box_id, working_dir) = match self.sandbox_pool.acquire().await {
+                Ok(sandbox) => sandbox,
                 Err(e) => {
-                    self.cleanup_environments(&environments).await.ok();
-                    return Err(e.context("Failed to create sandbox"));
+                    sandbox_guard.cleanup().await;
+                    return Err(anyhow::Error::new(e).context("Failed to create sandbox"));
                 }
             };
+            debug!(env_id = %env_config.id, box_id = %box_id, "Initialized environment");
+            sandbox_guard.track(box_id.clone());
 
             if let Err(e) = self
                 .load_environment_files(&working_dir, &env_config.files_in)
                 .await
             {
-                if let Err(cleanup_err) = self.sandbox_manager.remove_sandbox(&box_id).await {
-                    error!(box_id = %box_id, error = %cleanup_err, "Failed to clean up sandbox after file loading failure");
-                }
-                self.cleanup_environments(&environments).await?;
+                sandbox_guard.cleanup().await;
                 return Err(e.context("Failed to load environment files"));
             }
 
             environments.insert(
                 env_config.id.clone(),
-                EnvironmentList::new(env_config.id.clone(), box_id, working_dir),
+                EnvironmentList::new(box_id, working_dir),
             );
         }
 
@@ -141,22 +195,22 @@ impl OperationHandler {
             operation.channels.iter().map(|c| c.name.clone()).collect();
         for name in &channel_names {
             if let Err(e) = validate_pipe_name(name) {
-                self.cleanup_environments(&environments).await.ok();
+                sandbox_guard.cleanup().await;
                 return Err(e);
             }
         }
         let shared_channels_dir = if !channel_names.is_empty() {
-            let dir = std::env::temp_dir().join(format!(
-                "broccoli-channels-{}-{}-{}",
+            let dir = self.temp_dir.join(format!(
+                "{CHANNELS_PREFIX}{}-{}-{}",
                 std::process::id(),
-                NEXT_BOX_ID.fetch_add(1, Ordering::Relaxed),
+                NEXT_CHANNELS_DIR_ID.fetch_add(1, Ordering::Relaxed),
                 std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .map(|d| d.as_nanos())
                     .unwrap_or(0),
             ));
             if let Err(e) = tokio::fs::create_dir_all(&dir).await {
-                self.cleanup_environments(&environments).await.ok();
+                sandbox_guard.cleanup().await;
                 return Err(
                     anyhow::Error::new(e).context("Failed to create shared channels directory")
                 );
@@ -173,7 +227,7 @@ impl OperationHandler {
                     if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
                         warn!(error = %e, "Failed to clean up shared channels directory after mkfifo failure");
                     }
-                    self.cleanup_environments(&environments).await.ok();
+                    sandbox_guard.cleanup().await;
                     return Err(anyhow!(
                         "mkfifo failed for channel {}: {}",
                         channel.name,
@@ -212,60 +266,82 @@ impl OperationHandler {
                 if let Some(ref dir) = shared_channels_dir {
                     let _ = tokio::fs::remove_dir_all(dir).await;
                 }
-                self.cleanup_environments(&environments).await.ok();
+                sandbox_guard.cleanup().await;
                 return Err(e);
             }
         };
         debug!(layers = ?execution_layers, "Task execution layers determined");
 
+        let deadline = watchdog_deadline(operation, self.watchdog_margin);
         let mut task_results = HashMap::new();
         let mut global_success = true;
 
-        for layer in execution_layers {
-            let mut futures = Vec::new();
-            for task_id in &layer {
-                let task = operation
-                    .tasks
-                    .iter()
-                    .find(|t| t.id == *task_id)
-                    .ok_or_else(|| {
-                        anyhow!(
-                            "Task '{}' not found — dependency graph inconsistency",
-                            task_id
-                        )
-                    })?;
+        let run_layers = async {
+            for layer in execution_layers {
+                let mut futures = Vec::new();
+                for task_id in &layer {
+                    let task = operation
+                        .tasks
+                        .iter()
+                        .find(|t| t.id == *task_id)
+                        .ok_or_else(|| {
+                            anyhow!(
+                                "Task '{}' not found — dependency graph inconsistency",
+                                task_id
+                            )
+                        })?;
+
+                    let deps_ok = task.depends_on.iter().all(|dep_id| {
+                        task_results
+                            .get(dep_id)
+                            .map(|r: &TaskExecutionResult| r.success)
+                            .unwrap_or(false)
+                    });
+
+                    futures.push(self.execute_step_with_deps(
+                        task,
+                        &environments,
+                        deps_ok,
+                        shared_channels_dir.as_deref(),
+                        &channel_names,
+                    ));
+                }
 
-                let deps_ok = task.depends_on.iter().all(|dep_id| {
-                    task_results
-                        .get(dep_id)
-                        .map(|r: &TaskExecutionResult| r.success)
-                        .unwrap_or(false)
-                });
-
-                futures.push(self.execute_step_with_deps(
-                    task,
-                    &environments,
-                    deps_ok,
-                    shared_channels_dir.as_deref(),
-                    &channel_names,
-                ));
+                let results = join_all(futures).await;
+                for result in results {
+                    if !result.success {
+                        global_success = false;
+                    }
+                    task_results.insert(result.task_id.clone(), result);
+                }
             }
+            Ok::<(), anyhow::Error>(())
+        };
 
-            let results = join_all(futures).await;
-            for result in results {
-                if !result.success {
-                    global_success = false;
-                }
-                task_results.insert(result.task_id.clone(), result);
+        let layers_outcome = match tokio::time::timeout(deadline, run_layers).await {
+            Ok(outcome) => outcome,
+            Err(_) => {
+                error!(
+                    deadline_ms = deadline.as_millis() as u64,
+                    "Operation exceeded watchdog deadline, forcing sandbox cleanup"
+                );
+                Err(anyhow::Error::new(WatchdogTimeout(deadline)))
             }
-        }
+        };
 
         if let Some(dir) = &shared_channels_dir
             && let Err(e) = tokio::fs::remove_dir_all(dir).await
         {
             error!(error = %e, "Failed to clean up shared channels directory");
         }
-        self.cleanup_environments(&environments).await.ok();
+        // Boxes are only reused after a clean run; after a watchdog abort or
+        // internal error something may still be running inside them.
+        if layers_outcome.is_ok() {
+            sandbox_guard.release_to(&self.sandbox_pool).await;
+        } else {
+            sandbox_guard.cleanup().await;
+        }
+        layers_outcome?;
 
         info!(
             success = global_success,
@@ -277,18 +353,10 @@ impl OperationHandler {
             success: global_success,
             task_results,
             error: None,
+            worker_id: None,
         })
     }
 
-    async fn create_sandbox(&self, box_id: &str) -> Result<PathBuf> {
-        let sandbox_path = self
-            .sandbox_manager
-            .create_sandbox(Some(box_id))
-            .await
-            .context("Sandbox creation failed")?;
-        Ok(sandbox_path)
-    }
-
     async fn load_environment_files(
         &self,
         working_dir: &Path,
@@ -657,6 +725,15 @@ impl OperationHandler {
             });
         }
 
+        let stderr_capture = step.conf.stderr_capture;
+        let stderr_path = if stderr_capture == StderrCapture::Never
+            && !matches!(step.io.stderr, IOTarget::Pipe { .. })
+        {
+            Some(PathBuf::from("/dev/null"))
+        } else {
+            stderr_path
+        };
+
         let run_opts = RunOptions {
             resource_limits: step.conf.resource_limits.clone(),
             wait: true,
@@ -667,9 +744,10 @@ impl OperationHandler {
             stderr: stderr_path,
             env_rules: step.conf.env_rules.clone(),
             directory_rules,
+            stderr_capture,
         };
 
-        let exec_result = self
+        let mut exec_result = self
             .sandbox_manager
             .execute(&env.box_id, step.argv.clone(), &run_opts)
             .await
@@ -679,7 +757,17 @@ impl OperationHandler {
             })?;
 
         let success = exec_result.exit_code == Some(0);
-        let collected_outputs = self.collect_output(&env.working_dir, &step.collect).await?;
+        let collect: Vec<String> = if stderr_capture == StderrCapture::Never {
+            exec_result.stderr.clear();
+            step.collect
+                .iter()
+                .filter(|name| !matches!(&step.io.stderr, IOTarget::File { path } if path == *name))
+                .cloned()
+                .collect()
+        } else {
+            step.collect.clone()
+        };
+        let collected_outputs = self.collect_output(&env.working_dir, &collect).await?;
 
         Ok(TaskExecutionResult {
             task_id: step.id.clone(),
@@ -817,16 +905,4 @@ impl OperationHandler {
         }
         Ok(collected)
     }
-
-    async fn cleanup_environments(
-        &self,
-        environments: &HashMap<String, EnvironmentList>,
-    ) -> Result<()> {
-        for env in environments.values() {
-            if let Err(e) = self.sandbox_manager.remove_sandbox(&env.box_id).await {
-                error!(env_id = %env.id, error = %e, "Failed to cleanup sandbox");
-            }
-        }
-        Ok(())
-    }
 }
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use tracing::{debug, error, warn};
+
+use super::SandboxManager;
+use super::pool::SandboxPool;
+
+/// Removes every tracked sandbox when dropped unless [`cleanup`] already
+/// removed them all.
+///
+/// `Drop` cannot await, so leftover sandboxes are removed on a spawned task.
+/// This covers early returns, `?` propagation, panics and cancelled futures
+/// (e.g. the watchdog or a dropped task) without each call site having to
+/// remember to clean up.
+///
+/// [`cleanup`]: SandboxCleanupGuard::cleanup
+pub struct SandboxCleanupGuard {
+    sandbox_manager: Arc<dyn SandboxManager + Send + Sync>,
+    box_ids: Vec<String>,
+    defused: bool,
+}
+
+impl SandboxCleanupGuard {
+    pub fn new(sandbox_manager: Arc<dyn SandboxManager + Send + Sync>) -> Self {
+        Self {
+            sandbox_manager,
+            box_ids: Vec::new(),
+            defused: false,
+        }
+    }
+
+    /// Registers a freshly created sandbox for removal.
+    pub fn track(&mut self, box_id: impl Into<String>) {
+        self.box_ids.push(box_id.into());
+    }
+
+    /// Removes all tracked sandboxes. The guard is defused only when every
+    /// removal succeeded; failed ones stay tracked and are retried on drop.
+    pub async fn cleanup(&mut self) {
+        let mut remaining = Vec::new();
+        for box_id in std::mem::take(&mut self.box_ids) {
+            if let Err(e) = self.sandbox_manager.remove_sandbox(&box_id).await {
+                error!(box_id = %box_id, error = %e, "Failed to cleanup sandbox");
+                remaining.push(box_id);
+            }
+        }
+        self.box_ids = remaining;
+        if self.box_ids.is_empty() {
+            self.defuse();
+        }
+    }
+
+    /// Hands all tracked sandboxes back to `pool` for reuse. Only call this
+    /// once nothing is running in them; sandboxes the pool fails to take stay
+    /// tracked and are removed on drop.
+    pub async fn release_to(&mut self, pool: &SandboxPool) {
+        let mut remaining = Vec::new();
+        for box_id in std::mem::take(&mut self.box_ids) {
+            if let Err(e) = pool.release(box_id.clone()).await {
+                error!(box_id = %box_id, error = %e, "Failed to release sandbox");
+                remaining.push(box_id);
+            }
+        }
+        self.box_ids = remaining;
+        if self.box_ids.is_empty() {
+            self.defuse();
+        }
+    }
+
+    pub fn defuse(&mut self) {
+        self.defused = true;
+    }
+}
+
+impl Drop for SandboxCleanupGuard {
+    fn drop(&mut self) {
+        if self.defused || self.box_ids.is_empty() {
+            return;
+        }
+
+        let box_ids = std::mem::take(&mut self.box_ids);
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            warn!(
+                ?box_ids,
+                "No Tokio runtime available, sandboxes leaked on drop"
+            );
+            return;
+        };
+
+        debug!(?box_ids, "Removing sandboxes from drop guard");
+        let sandbox_manager = Arc::clone(&self.sandbox_manager);
+        handle.spawn(async move {
+            for box_id in box_ids {
+                if let Err(e) = sandbox_manager.remove_sandbox(&box_id).await {
+                    error!(box_id = %box_id, error = %e, "Failed to cleanup sandbox from drop guard");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::sandbox::error::SandboxError;
+    use crate::models::operation::sandbox::{ExecutionResult, RunOptions};
+    use async_trait::async_trait;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingSandbox {
+        removed: Mutex<Vec<String>>,
+        failures_left: Mutex<u32>,
+    }
+
+    #[async_trait]
+    impl SandboxManager for RecordingSandbox {
+        async fn create_sandbox(&self, id: Option<&str>) -> Result<PathBuf, SandboxError> {
+            Ok(PathBuf::from(id.unwrap_or("0")))
+        }
+
+        async fn remove_sandbox(&self, id: &str) -> Result<(), SandboxError> {
+            {
+                let mut failures_left = self.failures_left.lock().unwrap();
+                if *failures_left > 0 {
+                    *failures_left -= 1;
+                    return Err(SandboxError::Execution("busy".into()));
+                }
+            }
+            self.removed.lock().unwrap().push(id.to_string());
+            Ok(())
+        }
+
+        async fn execute(
+            &self,
+            _box_id: &str,
+            _argv: Vec<String>,
+            _run_options: &RunOptions,
+        ) -> Result<ExecutionResult, SandboxError> {
+            Ok(ExecutionResult::default())
+        }
+    }
+
+    async fn settle() {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn drop_without_cleanup_removes_tracked_sandboxes() {
+        let sandbox = Arc::new(RecordingSandbox::default());
+        {
+            let mut guard = SandboxCleanupGuard::new(sandbox.clone());
+            guard.track("1");
+            guard.track("2");
+        }
+        settle().await;
+        assert_eq!(*sandbox.removed.lock().unwrap(), vec!["1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn successful_cleanup_defuses_guard() {
+        let sandbox = Arc::new(RecordingSandbox::default());
+        {
+            let mut guard = SandboxCleanupGuard::new(sandbox.clone());
+            guard.track("1");
+            guard.cleanup().await;
+            assert!(guard.defused);
+        }
+        settle().await;
+        assert_eq!(*sandbox.removed.lock().unwrap(), vec!["1"]);
+    }
+
+    #[tokio::test]
+    async fn failed_cleanup_keeps_guard_armed_and_retries_on_drop() {
+        let sandbox = Arc::new(RecordingSandbox {
+            failures_left: Mutex::new(1),
+            ..Default::default()
+        });
+        {
+            let mut guard = SandboxCleanupGuard::new(sandbox.clone());
+            guard.track("1");
+            guard.cleanup().await;
+            assert!(!guard.defused);
+        }
+        settle().await;
+        assert_eq!(*sandbox.removed.lock().unwrap(), vec!["1"]);
+    }
+}
@@ -1,6 +1,10 @@
 use super::error::SandboxError;
-use super::{DirectoryRule, EnvRule, ExecutionResult, ResourceLimits, RunOptions, SandboxManager};
+use super::{
+    DirectoryRule, EnvRule, ExecutionResult, INLINE_OUTPUT_PREVIEW_BYTES, ResourceLimits,
+    RunOptions, SandboxManager, read_text_preview, text_preview_from_bytes,
+};
 use crate::config::WorkerAppConfig;
+use crate::temp_dir::BoxLock;
 use async_trait::async_trait;
 use std::{
     collections::HashMap,
@@ -13,13 +17,14 @@ use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Command;
 use tokio::sync::RwLock;
 
-const INLINE_OUTPUT_PREVIEW_BYTES: usize = 64 * 1024;
-
 #[derive(Debug)]
 pub struct IsolateSandboxManager {
     isolate_bin: String,
     enable_cgroups: bool,
+    temp_dir: PathBuf,
     sandboxes: Arc<RwLock<HashMap<String, PathBuf>>>,
+    /// Locks on the boxes this manager has initialized, released on cleanup.
+    box_locks: Arc<std::sync::Mutex<HashMap<String, BoxLock>>>,
 }
 
 impl IsolateSandboxManager {
@@ -27,9 +32,18 @@ impl IsolateSandboxManager {
         Self {
             isolate_bin,
             enable_cgroups,
+            temp_dir: std::env::temp_dir(),
             sandboxes: Arc::new(RwLock::new(HashMap::new())),
+            box_locks: Arc::default(),
         }
     }
+
+    /// Writes isolate's meta files, and a record of each initialized box,
+    /// under `temp_dir`.
+    pub fn with_temp_dir(mut self, temp_dir: PathBuf) -> Self {
+        self.temp_dir = temp_dir;
+        self
+    }
 }
 
 impl Default for IsolateSandboxManager {
@@ -40,8 +54,10 @@ impl Default for IsolateSandboxManager {
                 .as_ref()
                 .map(|c| c.worker.isolate_bin.clone())
                 .unwrap_or_else(|| "isolate".to_string()),
-            enable_cgroups: cfg.map(|c| c.worker.enable_cgroups).unwrap_or(false),
+            enable_cgroups: cfg.as_ref().is_some_and(|c| c.worker.enable_cgroups),
+            temp_dir: cfg.map_or_else(std::env::temp_dir, |c| c.worker.temp_dir()),
             sandboxes: Arc::new(RwLock::new(HashMap::new())),
+            box_locks: Arc::default(),
         }
     }
 }
@@ -61,26 +77,48 @@ fn is_fifo(path: &Path) -> bool {
     }
 }
 
-fn text_preview_from_bytes(mut bytes: Vec<u8>, truncated: bool) -> String {
-    let was_truncated = truncated || bytes.len() > INLINE_OUTPUT_PREVIEW_BYTES;
-    if bytes.len() > INLINE_OUTPUT_PREVIEW_BYTES {
-        bytes.truncate(INLINE_OUTPUT_PREVIEW_BYTES);
+/// Removes everything inside `dir`, keeping `dir` itself.
+async fn clear_dir(dir: &Path) -> std::io::Result<()> {
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if entry.file_type().await?.is_dir() {
+            fs::remove_dir_all(&path).await?;
+        } else {
+            fs::remove_file(&path).await?;
+        }
     }
+    Ok(())
+}
 
-    let mut text = String::from_utf8_lossy(&bytes).into_owned();
-    if was_truncated {
-        text.push_str("\n... (truncated)");
+/// Empties an isolate box's working directory and its `/tmp`, leaving the
+/// box initialized.
+async fn clear_box(working_dir: &Path) -> std::io::Result<()> {
+    clear_dir(working_dir).await?;
+    if let Some(tmp) = working_dir.parent().map(|root| root.join("tmp"))
+        && fs::try_exists(&tmp).await?
+    {
+        clear_dir(&tmp).await?;
     }
-    text
+    Ok(())
 }
 
-async fn read_text_preview(path: &Path) -> Result<String, std::io::Error> {
-    let file = tokio::fs::File::open(path).await?;
-    let mut bytes = Vec::with_capacity(INLINE_OUTPUT_PREVIEW_BYTES + 1);
-    let mut limited = file.take((INLINE_OUTPUT_PREVIEW_BYTES + 1) as u64);
-    limited.read_to_end(&mut bytes).await?;
-    let truncated = bytes.len() > INLINE_OUTPUT_PREVIEW_BYTES;
-    Ok(text_preview_from_bytes(bytes, truncated))
+/// Stops a running `isolate --run`. SIGTERM lets isolate kill the sandboxed
+/// program and tear down the box itself; killing isolate outright is the
+/// fallback when the signal cannot be sent.
+async fn interrupt_isolate(child: &mut tokio::process::Child) {
+    let terminated = match child.id() {
+        Some(pid) => Command::new("kill")
+            .arg("-TERM")
+            .arg(pid.to_string())
+            .status()
+            .await
+            .is_ok_and(|status| status.success()),
+        None => false,
+    };
+    if !terminated {
+        let _ = child.start_kill();
+    }
 }
 
 async fn read_capped_child_pipe<R>(mut reader: R) -> Result<Vec<u8>, std::io::Error>
@@ -269,6 +307,13 @@ async fn parse_meta_file(meta_path: &Path) -> Result<ExecutionResult, SandboxErr
 #[async_trait]
 impl SandboxManager for IsolateSandboxManager {
     async fn create_sandbox(&self, id: Option<&str>) -> Result<PathBuf, SandboxError> {
+        let box_id = id.unwrap_or("0").to_string();
+        let box_lock = crate::temp_dir::lock_box(&self.temp_dir, &box_id)
+            .await
+            .map_err(|e| {
+                SandboxError::Initialization(format!("failed to lock isolate box {box_id}: {e}"))
+            })?;
+
         let mut command = Command::new(&self.isolate_bin);
         if let Some(box_id) = id {
             command.arg(format!("--box-id={box_id}"));
@@ -297,7 +342,13 @@ impl SandboxManager for IsolateSandboxManager {
         }
 
         let working_dir = PathBuf::from(&path_text).join("box");
-        let box_id = id.unwrap_or("0").to_string();
+        if let Err(e) = crate::temp_dir::record_box(&self.temp_dir, &box_id).await {
+            tracing::warn!(box_id = %box_id, error = %e, "Failed to record isolate box");
+        }
+        self.box_locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(box_id.clone(), box_lock);
         self.sandboxes
             .write()
             .await
@@ -327,10 +378,34 @@ impl SandboxManager for IsolateSandboxManager {
                 String::from_utf8_lossy(&output.stderr).trim()
             )));
         }
+        if let Err(e) = crate::temp_dir::forget_box(&self.temp_dir, &box_id).await {
+            tracing::warn!(box_id = %box_id, error = %e, "Failed to forget isolate box");
+        }
+        self.box_locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&box_id);
 
         Ok(())
     }
 
+    /// Clears the box in place, falling back to `--cleanup` and `--init`
+    /// only when that fails or the box is unknown to this manager.
+    async fn reset_sandbox(&self, id: &str) -> Result<PathBuf, SandboxError> {
+        let box_id = parse_box_id(Some(id))?;
+        let working_dir = self.sandboxes.read().await.get(&box_id).cloned();
+        if let Some(working_dir) = working_dir {
+            match clear_box(&working_dir).await {
+                Ok(()) => return Ok(working_dir),
+                Err(e) => {
+                    tracing::warn!(box_id = %box_id, error = %e, "Failed to clear isolate box, reinitializing");
+                }
+            }
+        }
+        self.remove_sandbox(id).await?;
+        self.create_sandbox(Some(id)).await
+    }
+
     async fn execute(
         &self,
         box_id: &str,
@@ -379,7 +454,7 @@ impl IsolateSandboxManager {
         run_options: &RunOptions,
     ) -> Result<ExecutionResult, SandboxError> {
         let box_id = parse_box_id(Some(box_id))?;
-        let meta_path = std::env::temp_dir().join(format!(
+        let meta_path = self.temp_dir.join(format!(
             "broccoli-isolate-{box_id}-{}.meta",
             uuid::Uuid::new_v4()
         ));
@@ -444,6 +519,26 @@ impl IsolateSandboxManager {
 
         command.arg("--run").arg("--").args(&rewritten_argv);
 
+        let box_dir = self
+            .sandboxes
+            .read()
+            .await
+            .get(&box_id)
+            .cloned()
+            .ok_or_else(|| {
+                SandboxError::Execution(format!(
+                    "sandbox working directory not found for box id: {box_id}"
+                ))
+            })?;
+        // isolate's own `--quota` is fixed at `--init` and needs a quota
+        // enabled filesystem, so the run is polled for what it added to the
+        // box and stopped once over quota.
+        let disk_quota = run_options.resource_limits.disk_quota;
+        let baseline_usage = match disk_quota {
+            Some(_) => super::directory_usage(&box_dir).await,
+            None => 0,
+        };
+
         command.stdout(Stdio::piped()).stderr(Stdio::piped());
         let mut child = command.spawn().map_err(|err| {
             SandboxError::Execution(format!("failed to spawn isolate --run: {err}"))
@@ -456,27 +551,33 @@ impl IsolateSandboxManager {
         })?;
         let stdout_task = tokio::spawn(read_capped_child_pipe(stdout));
         let stderr_task = tokio::spawn(read_capped_child_pipe(stderr));
-        let status = child.wait().await.map_err(|err| {
+        let over_quota = super::wait_for_disk_quota(&box_dir, baseline_usage, disk_quota);
+        let (status, quota_exceeded) = tokio::select! {
+            status = child.wait() => (status, false),
+            _ = over_quota => {
+                interrupt_isolate(&mut child).await;
+                (child.wait().await, true)
+            }
+        };
+        let status = status.map_err(|err| {
             SandboxError::Execution(format!("failed to wait for isolate --run: {err}"))
         })?;
         let output_stdout = join_pipe_capture(stdout_task, "stdout").await?;
         let output_stderr = join_pipe_capture(stderr_task, "stderr").await?;
 
+        if let (true, Some(quota)) = (quota_exceeded, disk_quota) {
+            // An interrupted isolate may exit before writing its meta file.
+            let mut result = parse_meta_file(&meta_path).await.unwrap_or_default();
+            let _ = fs::remove_file(&meta_path).await;
+            result.stderr = text_preview_from_bytes(output_stderr, false);
+            super::mark_disk_quota_exceeded(&mut result, quota);
+            return Ok(result);
+        }
+
         match status.code() {
             Some(0) | Some(1) => {
                 let mut result = parse_meta_file(&meta_path).await?;
                 let _ = fs::remove_file(&meta_path).await;
-                let box_dir = self
-                    .sandboxes
-                    .read()
-                    .await
-                    .get(&box_id)
-                    .cloned()
-                    .ok_or_else(|| {
-                        SandboxError::Execution(format!(
-                            "sandbox working directory not found for box id: {box_id}"
-                        ))
-                    })?;
                 result.stdout = if let Some(stdout_path) = &run_options.stdout {
                     let resolved = box_dir.join(stdout_path);
                     if is_fifo(&resolved) {
@@ -517,6 +618,9 @@ impl IsolateSandboxManager {
                 } else {
                     text_preview_from_bytes(output_stderr, false)
                 };
+                if let Some(quota) = disk_quota {
+                    super::apply_disk_quota(&mut result, &box_dir, baseline_usage, quota).await;
+                }
                 Ok(result)
             }
             _ => {
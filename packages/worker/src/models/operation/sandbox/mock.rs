@@ -1,5 +1,9 @@
 use super::error::SandboxError;
-use super::{DirectoryRule, ExecutionResult, RunOptions, SandboxManager};
+use super::{
+    DirectoryRule, EnvRule, ExecutionResult, INLINE_OUTPUT_PREVIEW_BYTES, RunOptions,
+    SandboxManager, read_text_preview, text_preview_from_bytes,
+};
+use crate::temp_dir::MOCK_SANDBOX_PREFIX;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::fs::OpenOptions;
@@ -18,8 +22,6 @@ use tokio::sync::RwLock;
 use tokio::time::Instant;
 use tracing::{debug, warn};
 
-const INLINE_OUTPUT_PREVIEW_BYTES: usize = 64 * 1024;
-
 /// Drain a child pipe to EOF while keeping only the capped preview. Reading to
 /// the end (rather than stopping at the cap) keeps the child from taking SIGPIPE
 /// when it writes more than the preview limit.
@@ -43,28 +45,6 @@ where
     preview
 }
 
-fn text_preview_from_bytes(mut bytes: Vec<u8>, truncated: bool) -> String {
-    let was_truncated = truncated || bytes.len() > INLINE_OUTPUT_PREVIEW_BYTES;
-    if bytes.len() > INLINE_OUTPUT_PREVIEW_BYTES {
-        bytes.truncate(INLINE_OUTPUT_PREVIEW_BYTES);
-    }
-
-    let mut text = String::from_utf8_lossy(&bytes).into_owned();
-    if was_truncated {
-        text.push_str("\n... (truncated)");
-    }
-    text
-}
-
-async fn read_text_preview(path: &Path) -> Result<String, std::io::Error> {
-    let file = tokio::fs::File::open(path).await?;
-    let mut bytes = Vec::with_capacity(INLINE_OUTPUT_PREVIEW_BYTES + 1);
-    let mut limited = file.take((INLINE_OUTPUT_PREVIEW_BYTES + 1) as u64);
-    limited.read_to_end(&mut bytes).await?;
-    let truncated = bytes.len() > INLINE_OUTPUT_PREVIEW_BYTES;
-    Ok(text_preview_from_bytes(bytes, truncated))
-}
-
 #[derive(Debug, Clone)]
 pub struct MockSandboxManager {
     base_dir: PathBuf,
@@ -75,12 +55,17 @@ impl Default for MockSandboxManager {
     fn default() -> Self {
         // Scope the base directory by PID so concurrent worker processes don't
         // race on the same `box_id` directory under a shared temp root.
-        let pid = std::process::id();
-        Self::new(std::env::temp_dir().join(format!("broccoli-mock-sandbox-{pid}")))
+        Self::in_temp_dir(&std::env::temp_dir())
     }
 }
 
 impl MockSandboxManager {
+    /// Manager rooted in a per-process directory under `temp_dir`.
+    pub fn in_temp_dir(temp_dir: &Path) -> Self {
+        let pid = std::process::id();
+        Self::new(temp_dir.join(format!("{MOCK_SANDBOX_PREFIX}{pid}")))
+    }
+
     pub fn new(base_dir: PathBuf) -> Self {
         Self {
             base_dir,
@@ -301,6 +286,34 @@ impl MockSandboxManager {
         Ok(())
     }
 
+    /// Mirrors isolate: an empty list or `FullEnv` inherits the worker's
+    /// environment, otherwise only the listed variables are passed through.
+    fn apply_env_rules(command: &mut Command, rules: &[EnvRule]) {
+        if rules.is_empty() || rules.iter().any(|r| matches!(r, EnvRule::FullEnv)) {
+            for rule in rules {
+                if let EnvRule::Set(var, value) = rule {
+                    command.env(var, value);
+                }
+            }
+            return;
+        }
+
+        command.env_clear();
+        for rule in rules {
+            match rule {
+                EnvRule::Inherit(var) => {
+                    if let Some(value) = std::env::var_os(var) {
+                        command.env(var, value);
+                    }
+                }
+                EnvRule::Set(var, value) => {
+                    command.env(var, value);
+                }
+                EnvRule::FullEnv => {}
+            }
+        }
+    }
+
     async fn apply_directory_rules(
         sandbox_path: &Path,
         directory_rules: &[DirectoryRule],
@@ -371,6 +384,16 @@ impl SandboxManager for MockSandboxManager {
         Ok(())
     }
 
+    async fn reset_sandbox(&self, id: &str) -> Result<PathBuf, SandboxError> {
+        let known_path = self.sandboxes.read().await.get(id).cloned();
+        let Some(sandbox_path) = known_path else {
+            return self.create_sandbox(Some(id)).await;
+        };
+        debug!(box_id = %id, path = %sandbox_path.display(), "Resetting mock sandbox");
+        super::clear_directory(&sandbox_path).await?;
+        Ok(sandbox_path)
+    }
+
     async fn execute(
         &self,
         box_id: &str,
@@ -402,6 +425,7 @@ impl SandboxManager for MockSandboxManager {
         let mut command = Command::new(&rewritten_argv[0]);
         command.args(rewritten_argv.iter().skip(1));
         command.current_dir(&sandbox_path);
+        Self::apply_env_rules(&mut command, &run_options.env_rules);
 
         Self::apply_directory_rules(&sandbox_path, &run_options.directory_rules).await?;
 
@@ -443,6 +467,12 @@ impl SandboxManager for MockSandboxManager {
             command.stderr(Stdio::piped());
         }
 
+        let disk_quota = run_options.resource_limits.disk_quota;
+        let baseline_usage = match disk_quota {
+            Some(_) => super::directory_usage(&sandbox_path).await,
+            None => 0,
+        };
+
         let start = Instant::now();
         let mut child = command.spawn().map_err(|err| {
             SandboxError::Execution(format!("failed to spawn mock sandbox process: {err}"))
@@ -461,26 +491,32 @@ impl SandboxManager for MockSandboxManager {
                 .map(|t| (t * 1.5).max(t + 5.0))
         });
 
-        let (timed_out, exit_status) = if let Some(limit) = time_limit_secs {
-            let timeout_dur = Duration::from_secs_f64(limit);
-            tokio::select! {
-                result = child.wait() => {
-                    let status = result.map_err(|err| {
-                        SandboxError::Execution(format!("failed to wait mock sandbox process: {err}"))
-                    })?;
-                    (false, status)
-                }
-                _ = tokio::time::sleep(timeout_dur) => {
-                    let _ = child.kill().await;
-                    let _ = child.wait().await;
-                    (true, std::process::ExitStatus::default())
-                }
+        let timeout = async {
+            match time_limit_secs {
+                Some(limit) => tokio::time::sleep(Duration::from_secs_f64(limit)).await,
+                None => std::future::pending().await,
+            }
+        };
+        // Poll what the run has added to the box, killing it once over quota.
+        let over_quota = super::wait_for_disk_quota(&sandbox_path, baseline_usage, disk_quota);
+
+        let (timed_out, exit_status) = tokio::select! {
+            result = child.wait() => {
+                let status = result.map_err(|err| {
+                    SandboxError::Execution(format!("failed to wait mock sandbox process: {err}"))
+                })?;
+                (false, status)
+            }
+            _ = timeout => {
+                let _ = child.kill().await;
+                let _ = child.wait().await;
+                (true, std::process::ExitStatus::default())
+            }
+            _ = over_quota => {
+                let _ = child.kill().await;
+                let status = child.wait().await.unwrap_or_default();
+                (false, status)
             }
-        } else {
-            let status = child.wait().await.map_err(|err| {
-                SandboxError::Execution(format!("failed to wait mock sandbox process: {err}"))
-            })?;
-            (false, status)
         };
 
         let elapsed = start.elapsed().as_secs_f64();
@@ -541,7 +577,7 @@ impl SandboxManager for MockSandboxManager {
             warn!(box_id = %box_id, exit_code = ?exit_code, signal = ?signal, time_used = elapsed, "Mock sandbox command failed");
         }
 
-        Ok(ExecutionResult {
+        let mut result = ExecutionResult {
             exit_code,
             signal,
             time_used: elapsed,
@@ -567,6 +603,10 @@ impl SandboxManager for MockSandboxManager {
             },
             stdout,
             stderr,
-        })
+        };
+        if let Some(quota) = disk_quota {
+            super::apply_disk_quota(&mut result, &sandbox_path, baseline_usage, quota).await;
+        }
+        Ok(result)
     }
 }
@@ -1,12 +1,15 @@
 #![allow(unused_imports)]
 
 pub mod error;
+pub mod guard;
 pub mod isolate;
 pub mod mock;
+pub mod pool;
 
 use async_trait::async_trait;
 pub use broccoli_server_sdk::types::{
-    DirectoryOptions, DirectoryRule, EnvRule, ExecutionResult, ResourceLimits, RunOptions,
+    DISK_QUOTA_EXCEEDED_STATUS, DirectoryOptions, DirectoryRule, EnvRule, ExecutionResult,
+    ResourceLimits, RunOptions, StderrCapture,
 };
 use error::SandboxError;
 use std::path::PathBuf;
@@ -15,6 +18,14 @@ use std::path::PathBuf;
 pub trait SandboxManager {
     async fn create_sandbox(&self, id: Option<&str>) -> Result<PathBuf, SandboxError>;
     async fn remove_sandbox(&self, id: &str) -> Result<(), SandboxError>;
+    /// Returns an existing sandbox to the state `create_sandbox` leaves it
+    /// in, so it can be reused for another job. Defaults to removing and
+    /// recreating it, which also discards state outside the working
+    /// directory such as the box's own `/tmp`.
+    async fn reset_sandbox(&self, id: &str) -> Result<PathBuf, SandboxError> {
+        self.remove_sandbox(id).await?;
+        self.create_sandbox(Some(id)).await
+    }
     async fn execute(
         &self,
         box_id: &str,
@@ -22,3 +33,131 @@ pub trait SandboxManager {
         run_options: &RunOptions,
     ) -> Result<ExecutionResult, SandboxError>;
 }
+
+/// Most bytes of stdout/stderr returned inline in an [`ExecutionResult`].
+const INLINE_OUTPUT_PREVIEW_BYTES: usize = 64 * 1024;
+
+/// How often a run under a disk quota has its working directory measured.
+const DISK_QUOTA_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// Turns captured program output into the text stored in an
+/// [`ExecutionResult`]. Compilers and crashing programs may emit arbitrary
+/// bytes, so invalid UTF-8 becomes U+FFFD and NUL (which Postgres text
+/// columns reject) is dropped; the result is always safe to serialize.
+fn text_preview_from_bytes(mut bytes: Vec<u8>, truncated: bool) -> String {
+    let was_truncated = truncated || bytes.len() > INLINE_OUTPUT_PREVIEW_BYTES;
+    if bytes.len() > INLINE_OUTPUT_PREVIEW_BYTES {
+        bytes.truncate(INLINE_OUTPUT_PREVIEW_BYTES);
+    }
+    bytes.retain(|&b| b != 0);
+
+    let mut text = String::from_utf8_lossy(&bytes).into_owned();
+    if was_truncated {
+        text.push_str("\n... (truncated)");
+    }
+    text
+}
+
+async fn read_text_preview(path: &std::path::Path) -> Result<String, std::io::Error> {
+    use tokio::io::AsyncReadExt;
+
+    let file = tokio::fs::File::open(path).await?;
+    let mut bytes = Vec::with_capacity(INLINE_OUTPUT_PREVIEW_BYTES + 1);
+    let mut limited = file.take((INLINE_OUTPUT_PREVIEW_BYTES + 1) as u64);
+    limited.read_to_end(&mut bytes).await?;
+    let truncated = bytes.len() > INLINE_OUTPUT_PREVIEW_BYTES;
+    Ok(text_preview_from_bytes(bytes, truncated))
+}
+
+/// Bytes stored under `dir`, not following symlinks (mapped directories are
+/// symlinks in the mock sandbox and must not count against the box).
+pub(crate) async fn directory_usage(dir: &std::path::Path) -> u64 {
+    let mut total = 0;
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(mut entries) = tokio::fs::read_dir(&current).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let Ok(metadata) = tokio::fs::symlink_metadata(entry.path()).await else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else if metadata.is_file() {
+                total += metadata.len();
+            }
+        }
+    }
+    total
+}
+
+/// Whether the run grew `working_dir` past `quota_kib` beyond the
+/// `baseline` bytes it held before the run (staged inputs, compiled binaries).
+pub(crate) async fn exceeds_disk_quota(
+    working_dir: &std::path::Path,
+    baseline: u64,
+    quota_kib: u32,
+) -> bool {
+    directory_usage(working_dir).await.saturating_sub(baseline) > u64::from(quota_kib) * 1024
+}
+
+/// Resolves once the run has grown `working_dir` past `quota_kib`, polling
+/// its usage; never resolves without a quota.
+pub(crate) async fn wait_for_disk_quota(
+    working_dir: &std::path::Path,
+    baseline: u64,
+    quota_kib: Option<u32>,
+) {
+    let Some(quota_kib) = quota_kib else {
+        return std::future::pending().await;
+    };
+    while !exceeds_disk_quota(working_dir, baseline, quota_kib).await {
+        tokio::time::sleep(DISK_QUOTA_POLL_INTERVAL).await;
+    }
+}
+
+/// Rewrites `result` as a disk quota failure.
+pub(crate) fn mark_disk_quota_exceeded(result: &mut ExecutionResult, quota_kib: u32) {
+    result.status = DISK_QUOTA_EXCEEDED_STATUS.to_string();
+    result.message = format!("disk quota exceeded ({quota_kib} KiB)");
+    result.killed = true;
+}
+
+/// Rewrites `result` as a disk quota failure when the run wrote more than
+/// `quota_kib` into `working_dir` on top of its `baseline` bytes.
+pub(crate) async fn apply_disk_quota(
+    result: &mut ExecutionResult,
+    working_dir: &std::path::Path,
+    baseline: u64,
+    quota_kib: u32,
+) {
+    if exceeds_disk_quota(working_dir, baseline, quota_kib).await {
+        mark_disk_quota_exceeded(result, quota_kib);
+    }
+}
+
+/// Removes everything inside `dir` while keeping `dir` itself.
+pub(crate) async fn clear_directory(dir: &std::path::Path) -> Result<(), SandboxError> {
+    let mut entries = tokio::fs::read_dir(dir).await.map_err(|err| {
+        SandboxError::Initialization(format!("failed to read sandbox directory: {err}"))
+    })?;
+    while let Some(entry) = entries.next_entry().await.map_err(|err| {
+        SandboxError::Initialization(format!("failed to read sandbox directory: {err}"))
+    })? {
+        let path = entry.path();
+        let is_dir = entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false);
+        let removed = if is_dir {
+            tokio::fs::remove_dir_all(&path).await
+        } else {
+            tokio::fs::remove_file(&path).await
+        };
+        removed.map_err(|err| {
+            SandboxError::Initialization(format!(
+                "failed to remove {} from sandbox: {err}",
+                path.display()
+            ))
+        })?;
+    }
+    Ok(())
+}
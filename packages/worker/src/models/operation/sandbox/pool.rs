@@ -0,0 +1,223 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use tracing::{debug, warn};
+
+use super::SandboxManager;
+use super::error::SandboxError;
+
+/// Box ids handed out, matching isolate's default `num_boxes`.
+const MAX_BOX_IDS: u32 = 1000;
+
+/// Ids of the boxes pools in this process have created and not yet
+/// removed, whether idle or in use.
+static ALLOCATED_BOX_IDS: Mutex<BTreeSet<u32>> = Mutex::new(BTreeSet::new());
+
+/// Reserves the lowest box id no pool currently holds.
+fn allocate_box_id() -> Result<String, SandboxError> {
+    let mut allocated = ALLOCATED_BOX_IDS.lock().unwrap_or_else(|e| e.into_inner());
+    let id = (0..MAX_BOX_IDS)
+        .find(|id| !allocated.contains(id))
+        .ok_or_else(|| {
+            SandboxError::Initialization(format!("all {MAX_BOX_IDS} sandbox box ids are in use"))
+        })?;
+    allocated.insert(id);
+    Ok(id.to_string())
+}
+
+/// Returns a box id reserved by [`allocate_box_id`] once its box is gone.
+fn free_box_id(box_id: &str) {
+    if let Ok(id) = box_id.parse::<u32>() {
+        ALLOCATED_BOX_IDS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&id);
+    }
+}
+
+/// Keeps up to `capacity` initialized sandboxes around between jobs.
+///
+/// Released boxes are parked idle and reset on their next [`acquire`], so a
+/// job always starts from an empty box no matter how the previous one left
+/// it. With a capacity of 0 every box is created and removed per job.
+///
+/// [`acquire`]: SandboxPool::acquire
+pub struct SandboxPool {
+    sandbox_manager: Arc<dyn SandboxManager + Send + Sync>,
+    capacity: usize,
+    idle: Mutex<Vec<String>>,
+}
+
+impl SandboxPool {
+    pub fn new(sandbox_manager: Arc<dyn SandboxManager + Send + Sync>, capacity: usize) -> Self {
+        Self {
+            sandbox_manager,
+            capacity,
+            idle: Mutex::new(Vec::with_capacity(capacity)),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn idle_count(&self) -> usize {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Returns a clean sandbox as `(box_id, working_dir)`, resetting an idle
+    /// box when one is available and creating a new one otherwise. Idle boxes
+    /// that fail to reset are removed and skipped.
+    pub async fn acquire(&self) -> Result<(String, PathBuf), SandboxError> {
+        while let Some(box_id) = self.pop_idle() {
+            match self.sandbox_manager.reset_sandbox(&box_id).await {
+                Ok(working_dir) => {
+                    debug!(box_id = %box_id, "Reusing pooled sandbox");
+                    return Ok((box_id, working_dir));
+                }
+                Err(e) => {
+                    warn!(box_id = %box_id, error = %e, "Failed to reset pooled sandbox, discarding");
+                    if let Err(e) = self.remove(&box_id).await {
+                        warn!(box_id = %box_id, error = %e, "Failed to remove unusable sandbox");
+                    }
+                }
+            }
+        }
+
+        let box_id = allocate_box_id()?;
+        match self.sandbox_manager.create_sandbox(Some(&box_id)).await {
+            Ok(working_dir) => Ok((box_id, working_dir)),
+            Err(e) => {
+                free_box_id(&box_id);
+                Err(e)
+            }
+        }
+    }
+
+    /// Parks a sandbox for reuse, or removes it when the pool is full.
+    pub async fn release(&self, box_id: String) -> Result<(), SandboxError> {
+        {
+            let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+            if idle.len() < self.capacity {
+                idle.push(box_id);
+                return Ok(());
+            }
+        }
+        self.remove(&box_id).await
+    }
+
+    /// Removes a box and frees its id for the next [`acquire`].
+    ///
+    /// [`acquire`]: SandboxPool::acquire
+    async fn remove(&self, box_id: &str) -> Result<(), SandboxError> {
+        let removed = self.sandbox_manager.remove_sandbox(box_id).await;
+        free_box_id(box_id);
+        removed
+    }
+
+    fn pop_idle(&self) -> Option<String> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner()).pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::sandbox::{ExecutionResult, RunOptions};
+    use async_trait::async_trait;
+
+    #[derive(Default)]
+    struct CountingSandbox {
+        created: Mutex<Vec<String>>,
+        reset: Mutex<Vec<String>>,
+        removed: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl SandboxManager for CountingSandbox {
+        async fn create_sandbox(&self, id: Option<&str>) -> Result<PathBuf, SandboxError> {
+            let id = id.unwrap_or("0").to_string();
+            self.created.lock().unwrap().push(id.clone());
+            Ok(PathBuf::from(id))
+        }
+
+        async fn remove_sandbox(&self, id: &str) -> Result<(), SandboxError> {
+            self.removed.lock().unwrap().push(id.to_string());
+            Ok(())
+        }
+
+        async fn reset_sandbox(&self, id: &str) -> Result<PathBuf, SandboxError> {
+            self.reset.lock().unwrap().push(id.to_string());
+            Ok(PathBuf::from(id))
+        }
+
+        async fn execute(
+            &self,
+            _box_id: &str,
+            _argv: Vec<String>,
+            _run_options: &RunOptions,
+        ) -> Result<ExecutionResult, SandboxError> {
+            Ok(ExecutionResult::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn released_box_is_reset_and_reused() {
+        let sandbox = Arc::new(CountingSandbox::default());
+        let pool = SandboxPool::new(sandbox.clone(), 1);
+
+        let (first, _) = pool.acquire().await.unwrap();
+        pool.release(first.clone()).await.unwrap();
+        assert_eq!(pool.idle_count(), 1);
+        let (second, _) = pool.acquire().await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(*sandbox.created.lock().unwrap(), vec![first.clone()]);
+        assert_eq!(*sandbox.reset.lock().unwrap(), vec![first]);
+        assert!(sandbox.removed.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn release_beyond_capacity_removes_box() {
+        let sandbox = Arc::new(CountingSandbox::default());
+        let pool = SandboxPool::new(sandbox.clone(), 1);
+
+        let (a, _) = pool.acquire().await.unwrap();
+        let (b, _) = pool.acquire().await.unwrap();
+        pool.release(a).await.unwrap();
+        pool.release(b.clone()).await.unwrap();
+
+        assert_eq!(pool.idle_count(), 1);
+        assert_eq!(*sandbox.removed.lock().unwrap(), vec![b]);
+    }
+
+    #[tokio::test]
+    async fn zero_capacity_never_keeps_boxes() {
+        let sandbox = Arc::new(CountingSandbox::default());
+        let pool = SandboxPool::new(sandbox.clone(), 0);
+
+        let (a, _) = pool.acquire().await.unwrap();
+        pool.release(a.clone()).await.unwrap();
+
+        assert_eq!(pool.idle_count(), 0);
+        assert_eq!(*sandbox.removed.lock().unwrap(), vec![a]);
+        assert!(sandbox.reset.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn box_ids_in_use_are_never_reissued() {
+        let sandbox = Arc::new(CountingSandbox::default());
+        let pool = SandboxPool::new(sandbox.clone(), 1);
+
+        let (held, _) = pool.acquire().await.unwrap();
+        let (parked, _) = pool.acquire().await.unwrap();
+        pool.release(parked.clone()).await.unwrap();
+
+        let churn = SandboxPool::new(sandbox.clone(), 0);
+        for _ in 0..MAX_BOX_IDS + 10 {
+            let (id, _) = churn.acquire().await.unwrap();
+            assert_ne!(id, held);
+            assert_ne!(id, parked);
+            churn.release(id).await.unwrap();
+        }
+        pool.release(held).await.unwrap();
+    }
+}
@@ -6,10 +6,13 @@ use std::sync::{Arc, Mutex};
 use crate::config::WorkerAppConfig;
 use crate::error::WorkerError;
 use crate::models::operation::OperationTaskExecutor;
+use crate::models::operation::handler::WatchdogTimeout;
 
 pub struct Worker {
     executors: Arc<Mutex<HashMap<String, Arc<dyn Executor>>>>,
     hook_registry: Arc<Mutex<HookRegistry>>,
+    /// Stamped on every result as [`TaskResult::worker_id`].
+    id: Option<String>,
 }
 
 impl Worker {
@@ -17,14 +20,21 @@ impl Worker {
         Self {
             executors: Arc::new(Mutex::new(HashMap::new())),
             hook_registry: Arc::new(Mutex::new(HookRegistry::new(()))),
+            id: None,
         }
     }
 
+    /// Sets the id reported in the results this worker produces.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
     pub async fn from_config(
         config: &WorkerAppConfig,
         metrics: common::metrics::Metrics,
     ) -> anyhow::Result<Self> {
-        let worker = Self::with_no_executors();
+        let worker = Self::with_no_executors().with_id(config.worker.id.clone());
 
         worker.register_executor(
             "operation",
@@ -53,6 +63,12 @@ impl Worker {
     }
 
     pub async fn execute_task(&self, task: Task) -> Result<TaskResult, WorkerError> {
+        let mut result = self.run_task(task).await?;
+        result.worker_id = self.id.clone();
+        Ok(result)
+    }
+
+    async fn run_task(&self, task: Task) -> Result<TaskResult, WorkerError> {
         let hook_manager = { self.hook_registry.lock().unwrap().clone() };
 
         let _ = hook_manager
@@ -90,11 +106,17 @@ impl Worker {
                             error: error_msg.clone(),
                         })
                         .await;
+                    // A watchdog abort says nothing about the submission itself,
+                    // so surface it as an error and let the caller retry.
+                    if e.is::<WatchdogTimeout>() {
+                        return Err(WorkerError::Timeout(error_msg));
+                    }
                     TaskResult {
                         task_id: task.id,
                         success: false,
                         output: serde_json::json!({ "error": error_msg }),
                         error: Some(error_msg),
+                        worker_id: None,
                     }
                 }
             }
@@ -105,6 +127,7 @@ impl Worker {
                 success: false,
                 output: serde_json::json!({ "error": &error_msg }),
                 error: Some(error_msg),
+                worker_id: None,
             });
         };
 
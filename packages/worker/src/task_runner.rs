@@ -0,0 +1,280 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use broccoli_server_sdk::types::JudgeJob;
+use common::metrics::Metrics;
+use common::retry::{
+    RetryAttempt, RetryCleanupGuard, RetryDecision, RetryTracker, calculate_backoff,
+};
+use common::worker::{TASK_SCHEMA_VERSION, Task, is_supported_task_schema};
+use common::{DlqConfig, DlqEnvelope, DlqErrorCode, DlqMessageType};
+use opentelemetry::KeyValue;
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+use crate::error::WorkerError;
+use crate::judge_epoch::JudgeEpochs;
+use crate::models::worker::Worker;
+
+/// How [`run_task`] left a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskOutcome {
+    /// The task ran and its result was published.
+    Completed,
+    /// Every retry failed; the task was failed back and parked in the DLQ.
+    DeadLettered,
+}
+
+/// Runs `task` on `worker` and publishes its result, retrying failures (a
+/// watchdog timeout included) with backoff until `retry_tracker` gives up,
+/// then dead-letters it to `dlq_queue`.
+pub async fn run_task(
+    task: &Task,
+    worker: &Arc<Worker>,
+    mq: &Arc<mq::Mq>,
+    dlq_queue: &str,
+    dlq_config: &DlqConfig,
+    retry_tracker: &Arc<Mutex<RetryTracker>>,
+    metrics: &Metrics,
+) -> TaskOutcome {
+    let task_id = task.id.clone();
+    let task_attrs = [KeyValue::new("task_type", task.task_type.clone())];
+    let task_start = Instant::now();
+    let mut cleanup_guard = RetryCleanupGuard::new(retry_tracker, &task_id);
+
+    loop {
+        match process_task(task, worker, mq).await {
+            Ok(()) => {
+                metrics
+                    .task_process_duration
+                    .record(task_start.elapsed().as_secs_f64(), &task_attrs);
+
+                retry_tracker.lock().await.clear(&task_id);
+                cleanup_guard.defuse();
+                return TaskOutcome::Completed;
+            }
+            Err(e) => {
+                let error_str = e.to_string();
+                let decision = retry_tracker
+                    .lock()
+                    .await
+                    .record_failure(&task_id, &error_str);
+
+                match decision {
+                    RetryDecision::Retry { attempt, .. } => {
+                        metrics.task_retries_total.add(1, &task_attrs);
+
+                        let delay = calculate_backoff(
+                            attempt,
+                            dlq_config.base_delay_ms,
+                            dlq_config.max_delay_ms,
+                            dlq_config.backoff_jitter,
+                        );
+                        warn!(
+                            job_id = %task_id,
+                            attempt,
+                            delay_ms = delay.as_millis() as u64,
+                            error = %e,
+                            "Task failed, retrying"
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    RetryDecision::Exhausted { history } => {
+                        metrics.task_retries_total.add(1, &task_attrs);
+                        metrics.dlq_messages_total.add(1, &task_attrs);
+
+                        error!(
+                            job_id = %task_id,
+                            retry_count = history.len(),
+                            error = %e,
+                            "Max retries exhausted, sending to DLQ"
+                        );
+
+                        dead_letter_task(
+                            mq,
+                            dlq_queue,
+                            task,
+                            format!(
+                                "Operation failed after {} retries: {}",
+                                history.len(),
+                                error_str
+                            ),
+                            DlqErrorCode::MaxRetriesExceeded,
+                            error_str,
+                            history,
+                        )
+                        .await;
+
+                        cleanup_guard.defuse();
+                        return TaskOutcome::DeadLettered;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Dead-letters `task` if it was published with a `schema_version` newer
+/// than this worker understands. Returns whether it did; any other task may
+/// go on to [`run_task`].
+pub async fn dead_letter_unsupported_schema(
+    task: &Task,
+    schema_version: Option<u32>,
+    mq: &Arc<mq::Mq>,
+    dlq_queue: &str,
+    metrics: &Metrics,
+) -> bool {
+    if is_supported_task_schema(schema_version) {
+        return false;
+    }
+
+    let error_str = format!(
+        "Task schema version {} is newer than supported version {}",
+        schema_version.unwrap_or_default(),
+        TASK_SCHEMA_VERSION
+    );
+    error!(
+        job_id = %task.id,
+        schema_version = ?schema_version,
+        supported = TASK_SCHEMA_VERSION,
+        "Rejecting task with unsupported schema version, sending to DLQ"
+    );
+    metrics
+        .dlq_messages_total
+        .add(1, &[KeyValue::new("task_type", task.task_type.clone())]);
+    dead_letter_task(
+        mq,
+        dlq_queue,
+        task,
+        error_str.clone(),
+        DlqErrorCode::DeserializationError,
+        error_str,
+        Vec::new(),
+    )
+    .await;
+    true
+}
+
+/// Fails `task` back without running it if it judges an epoch of its
+/// submission that a newer rejudge has superseded. Returns whether it did.
+pub async fn drop_if_superseded(task: &Task, judge_epochs: &JudgeEpochs, mq: &Arc<mq::Mq>) -> bool {
+    let Some(job) = task_judge_job(task) else {
+        return false;
+    };
+    if !judge_epochs.is_superseded(job).await {
+        return false;
+    }
+
+    info!(
+        job_id = %task.id,
+        submission_id = job.submission_id,
+        judge_epoch = job.judge_epoch,
+        "Dropping job superseded by a newer rejudge"
+    );
+    publish_error_result(mq, task, "Superseded by a newer rejudge".into()).await;
+    true
+}
+
+/// Submission and epoch an operation task judges, if it carries them.
+fn task_judge_job(task: &Task) -> Option<JudgeJob> {
+    if task.task_type != "operation" {
+        return None;
+    }
+    task.payload
+        .get("judge_job")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Fails `task` back to its reply queue with `result_error`.
+pub async fn publish_error_result(mq: &Arc<mq::Mq>, task: &Task, result_error: String) {
+    let error_result = common::worker::TaskResult {
+        task_id: task.id.clone(),
+        success: false,
+        output: serde_json::json!({}),
+        error: Some(result_error),
+        worker_id: None,
+    };
+    if let Err(e) = mq
+        .publish(task.reply_queue_name(), None, &error_result, None)
+        .await
+    {
+        error!(job_id = %task.id, error = %e, "Failed to publish error result for operation task");
+    }
+}
+
+/// Fails `task` back to its reply queue with `result_error` and parks it in
+/// the DLQ.
+pub async fn dead_letter_task(
+    mq: &Arc<mq::Mq>,
+    dlq_queue: &str,
+    task: &Task,
+    result_error: String,
+    error_code: DlqErrorCode,
+    error_message: String,
+    retry_history: Vec<RetryAttempt>,
+) {
+    let task_id = task.id.clone();
+
+    publish_error_result(mq, task, result_error).await;
+
+    let payload = serde_json::to_value(task).unwrap_or_else(|ser_err| {
+        error!(error = %ser_err, "Failed to serialize task for DLQ");
+        serde_json::json!({ "task_id": task_id })
+    });
+
+    let envelope = DlqEnvelope {
+        message_id: task_id.clone(),
+        message_type: DlqMessageType::OperationTask,
+        submission_id: None,
+        payload,
+        error_code,
+        error_message,
+        retry_history,
+    };
+
+    if let Err(dlq_err) = mq.publish(dlq_queue, None, &envelope, None).await {
+        error!(
+            job_id = %task_id,
+            error = %dlq_err,
+            "CRITICAL: Failed to publish to DLQ, message may be lost"
+        );
+    }
+}
+
+async fn process_task(
+    task: &Task,
+    worker: &Arc<Worker>,
+    mq: &Arc<mq::Mq>,
+) -> Result<(), WorkerError> {
+    info!(
+        job_id = %task.id,
+        task_type = %task.task_type,
+        "Processing task"
+    );
+
+    let worker = Arc::clone(worker);
+    let task_clone = task.clone();
+    let result = tokio::spawn(async move { worker.execute_task(task_clone).await })
+        .await
+        .map_err(|e| {
+            if e.is_panic() {
+                WorkerError::TaskPanic(format!("{e}"))
+            } else {
+                WorkerError::Internal(format!("Task join error: {e}"))
+            }
+        })??;
+
+    mq.publish(task.reply_queue_name(), None, &result, None)
+        .await
+        .map_err(|e| WorkerError::Mq(e.to_string()))?;
+
+    info!(
+        job_id = %task.id,
+        task_result_id = %result.task_id,
+        success = result.success,
+        result_queue = %task.reply_queue_name(),
+        "Task finished"
+    );
+
+    Ok(())
+}
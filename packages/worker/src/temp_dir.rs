@@ -0,0 +1,310 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use tracing::{info, warn};
+
+use crate::models::operation::sandbox::SandboxManager;
+
+/// Prefix of the per-process mock sandbox root.
+pub const MOCK_SANDBOX_PREFIX: &str = "broccoli-mock-sandbox-";
+/// Prefix of the FIFO directories shared between the environments of one
+/// operation.
+pub const CHANNELS_PREFIX: &str = "broccoli-channels-";
+/// Prefix of the directory each worker process keeps its temp files in.
+pub const WORKER_DIR_PREFIX: &str = "broccoli-worker-";
+/// Suffix of a worker directory that is still being claimed.
+const CLAIMING_SUFFIX: &str = ".claiming";
+/// Lock file inside a worker directory, held for the worker's lifetime.
+const LOCK_FILE: &str = "lock";
+/// Directory inside a worker directory with one empty file per isolate box
+/// the worker has initialized.
+pub const BOXES_DIR: &str = "broccoli-isolate-boxes";
+/// Directory beside the worker directories with one lock file per isolate
+/// box id, shared by every worker on the host.
+pub const BOX_LOCKS_DIR: &str = "broccoli-isolate-box-locks";
+
+/// Directory owned by this worker process for sandbox roots, channel
+/// directories and isolate meta files. Ownership is an exclusive `flock` on
+/// its lock file, which the kernel releases when the process dies, so
+/// another worker can tell a crashed owner from a live one regardless of
+/// PID namespaces.
+pub struct WorkerTempDir {
+    path: PathBuf,
+    _lock: File,
+}
+
+impl WorkerTempDir {
+    /// Creates and locks a fresh worker directory under `base`.
+    pub fn claim(base: &Path) -> std::io::Result<Self> {
+        let name = format!("{WORKER_DIR_PREFIX}{}", uuid::Uuid::new_v4());
+        // Locked under a name the cleaner skips, then renamed, so a worker
+        // starting up alongside never sees the directory unlocked.
+        let claiming = base.join(format!("{name}{CLAIMING_SUFFIX}"));
+        std::fs::create_dir_all(&claiming)?;
+        let lock = File::create(claiming.join(LOCK_FILE))?;
+        lock.try_lock()
+            .map_err(|e| std::io::Error::other(format!("failed to lock worker dir: {e}")))?;
+        let path = base.join(name);
+        std::fs::rename(&claiming, &path)?;
+        Ok(Self { path, _lock: lock })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Exclusive `flock` on one isolate box id. Workers hold it from `--init`
+/// until `--cleanup`, and the stale-dir cleaner only removes a box while
+/// holding it, so a box in use is never cleaned up from under its worker.
+#[derive(Debug)]
+pub struct BoxLock {
+    _file: File,
+}
+
+/// The directory holding box locks for a worker that keeps its files in
+/// `dir`: beside the worker directories when `dir` is one, `dir` otherwise.
+fn box_locks_dir(dir: &Path) -> PathBuf {
+    let is_worker_dir = dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with(WORKER_DIR_PREFIX));
+    match dir.parent() {
+        Some(base) if is_worker_dir => base.join(BOX_LOCKS_DIR),
+        _ => dir.join(BOX_LOCKS_DIR),
+    }
+}
+
+fn open_box_lock(locks_dir: &Path, box_id: &str) -> std::io::Result<File> {
+    std::fs::create_dir_all(locks_dir)?;
+    std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(locks_dir.join(box_id))
+}
+
+/// Locks isolate box `box_id` for the worker keeping its files in `dir`,
+/// waiting while a cleaner holds it.
+pub async fn lock_box(dir: &Path, box_id: &str) -> std::io::Result<BoxLock> {
+    let locks_dir = box_locks_dir(dir);
+    let box_id = box_id.to_string();
+    tokio::task::spawn_blocking(move || {
+        let file = open_box_lock(&locks_dir, &box_id)?;
+        file.lock()?;
+        Ok(BoxLock { _file: file })
+    })
+    .await
+    .map_err(std::io::Error::other)?
+}
+
+/// Locks isolate box `box_id` under `base` unless another worker holds it.
+fn try_lock_box(base: &Path, box_id: &str) -> std::io::Result<Option<BoxLock>> {
+    let file = open_box_lock(&base.join(BOX_LOCKS_DIR), box_id)?;
+    match file.try_lock() {
+        Ok(()) => Ok(Some(BoxLock { _file: file })),
+        Err(std::fs::TryLockError::WouldBlock) => Ok(None),
+        Err(std::fs::TryLockError::Error(e)) => Err(e),
+    }
+}
+
+/// Records that isolate box `box_id` was initialized by the worker owning
+/// `dir`.
+pub async fn record_box(dir: &Path, box_id: &str) -> std::io::Result<()> {
+    let boxes = dir.join(BOXES_DIR);
+    tokio::fs::create_dir_all(&boxes).await?;
+    tokio::fs::write(boxes.join(box_id), b"").await
+}
+
+/// Drops the record written by [`record_box`].
+pub async fn forget_box(dir: &Path, box_id: &str) -> std::io::Result<()> {
+    match tokio::fs::remove_file(dir.join(BOXES_DIR).join(box_id)).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Removes the worker directories under `base` whose owner is gone, along
+/// with the isolate boxes those workers left initialized. A directory is
+/// stale when its lock can be taken. Each box is only removed while holding
+/// its [`BoxLock`], so a box another worker has since taken over is left
+/// alone; box ids are only unique within one process. Returns how many
+/// directories were removed.
+pub async fn clean_stale_dirs(base: &Path, sandbox_manager: &(dyn SandboxManager + Sync)) -> usize {
+    let mut entries = match tokio::fs::read_dir(base).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!(dir = %base.display(), error = %e, "Failed to scan temp dir for stale sandboxes");
+            return 0;
+        }
+    };
+
+    let mut stale = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        if !name.starts_with(WORKER_DIR_PREFIX) || name.ends_with(CLAIMING_SUFFIX) {
+            continue;
+        }
+        let path = entry.path();
+        match File::open(path.join(LOCK_FILE)).map(|f| f.try_lock().map(|()| f)) {
+            // Keep the lock until the directory is gone.
+            Ok(Ok(lock)) => stale.push((path, lock)),
+            Ok(Err(_)) => {}
+            Err(e) => warn!(path = %path.display(), error = %e, "Worker dir has no lock file"),
+        }
+    }
+
+    let mut removed = 0;
+    for (path, _lock) in stale {
+        for box_id in recorded_boxes(&path).await {
+            let _box_lock = match try_lock_box(base, &box_id) {
+                Ok(Some(lock)) => lock,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!(box_id = %box_id, error = %e, "Failed to lock stale sandbox box");
+                    continue;
+                }
+            };
+            if let Err(e) = sandbox_manager.remove_sandbox(&box_id).await {
+                warn!(box_id = %box_id, error = %e, "Failed to clean up stale sandbox box");
+            }
+        }
+        match tokio::fs::remove_dir_all(&path).await {
+            Ok(()) => removed += 1,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Failed to remove stale worker dir")
+            }
+        }
+    }
+    if removed > 0 {
+        info!(dir = %base.display(), removed, "Removed stale worker dirs");
+    }
+    removed
+}
+
+async fn recorded_boxes(dir: &Path) -> Vec<String> {
+    let Ok(mut entries) = tokio::fs::read_dir(dir.join(BOXES_DIR)).await else {
+        return Vec::new();
+    };
+    let mut boxes = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if let Some(name) = entry.file_name().to_str() {
+            boxes.push(name.to_string());
+        }
+    }
+    boxes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::sandbox::error::SandboxError;
+    use crate::models::operation::sandbox::{ExecutionResult, RunOptions};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RemovalLog {
+        removed: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl SandboxManager for RemovalLog {
+        async fn create_sandbox(&self, id: Option<&str>) -> Result<PathBuf, SandboxError> {
+            Ok(PathBuf::from(id.unwrap_or("0")))
+        }
+
+        async fn remove_sandbox(&self, id: &str) -> Result<(), SandboxError> {
+            self.removed.lock().unwrap().push(id.to_string());
+            Ok(())
+        }
+
+        async fn execute(
+            &self,
+            _box_id: &str,
+            _argv: Vec<String>,
+            _run_options: &RunOptions,
+        ) -> Result<ExecutionResult, SandboxError> {
+            Ok(ExecutionResult::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn removes_unlocked_worker_dirs_only() {
+        let base = tempfile::tempdir().unwrap();
+        let live = WorkerTempDir::claim(base.path()).unwrap();
+        let stale = WorkerTempDir::claim(base.path()).unwrap();
+        let stale_path = stale.path().to_path_buf();
+        drop(stale);
+        std::fs::create_dir_all(stale_path.join("broccoli-mock-sandbox-1/box")).unwrap();
+        std::fs::write(stale_path.join("broccoli-isolate-3-x.meta"), "").unwrap();
+        let unrelated = base.path().join("unrelated");
+        std::fs::create_dir_all(&unrelated).unwrap();
+
+        assert_eq!(
+            clean_stale_dirs(base.path(), &RemovalLog::default()).await,
+            1
+        );
+        assert!(!stale_path.exists());
+        assert!(live.path().exists());
+        assert!(unrelated.exists());
+    }
+
+    #[tokio::test]
+    async fn cleans_up_boxes_of_dead_workers_not_in_use_elsewhere() {
+        let base = tempfile::tempdir().unwrap();
+        let live = WorkerTempDir::claim(base.path()).unwrap();
+        let _in_use = lock_box(live.path(), "8").await.unwrap();
+        record_box(live.path(), "8").await.unwrap();
+        let stale = WorkerTempDir::claim(base.path()).unwrap();
+        record_box(stale.path(), "7").await.unwrap();
+        record_box(stale.path(), "8").await.unwrap();
+        drop(stale);
+
+        let log = RemovalLog::default();
+        assert_eq!(clean_stale_dirs(base.path(), &log).await, 1);
+        assert_eq!(*log.removed.lock().unwrap(), vec!["7".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn forgotten_box_is_not_cleaned_up() {
+        let base = tempfile::tempdir().unwrap();
+        let stale = WorkerTempDir::claim(base.path()).unwrap();
+        record_box(stale.path(), "4").await.unwrap();
+        forget_box(stale.path(), "4").await.unwrap();
+        drop(stale);
+
+        let log = RemovalLog::default();
+        assert_eq!(clean_stale_dirs(base.path(), &log).await, 1);
+        assert!(log.removed.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn box_taken_over_after_the_scan_is_not_cleaned_up() {
+        let base = tempfile::tempdir().unwrap();
+        let stale = WorkerTempDir::claim(base.path()).unwrap();
+        record_box(stale.path(), "5").await.unwrap();
+        drop(stale);
+        // A live worker that has not recorded the box yet still holds its lock.
+        let live = WorkerTempDir::claim(base.path()).unwrap();
+        let _in_use = lock_box(live.path(), "5").await.unwrap();
+
+        let log = RemovalLog::default();
+        assert_eq!(clean_stale_dirs(base.path(), &log).await, 1);
+        assert!(log.removed.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn box_lock_is_shared_between_worker_dirs() {
+        let base = tempfile::tempdir().unwrap();
+        let first = WorkerTempDir::claim(base.path()).unwrap();
+        let lock = lock_box(first.path(), "2").await.unwrap();
+
+        assert!(try_lock_box(base.path(), "2").unwrap().is_none());
+        drop(lock);
+        assert!(try_lock_box(base.path(), "2").unwrap().is_some());
+    }
+}
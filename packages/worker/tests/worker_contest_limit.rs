@@ -0,0 +1,211 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use common::worker::Task;
+use mq::{BrokerMessage, ConsumeConfig, Envelope, MessageHeaders, MqConfig, init_mq};
+use testcontainers::ContainerAsync;
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+use tokio::time::timeout;
+use worker::contest_limit::{Admission, ContestLimiter};
+
+async fn start_redis() -> (ContainerAsync<Redis>, String) {
+    let redis = Redis::default()
+        .start()
+        .await
+        .expect("failed to start Redis container");
+    let port = redis
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("failed to get Redis port");
+    (redis, format!("redis://127.0.0.1:{port}"))
+}
+
+fn contest_task(id: &str, contest_id: i32) -> Task {
+    Task {
+        id: id.to_string(),
+        task_type: "operation".into(),
+        executor_name: "operation".into(),
+        payload: serde_json::json!({ "contest_id": contest_id }),
+        result_queue: "results".into(),
+        reply_queue: None,
+        priority: None,
+        trace_context: None,
+        schema_version: None,
+    }
+}
+
+fn is_admitted(admission: &Admission) -> bool {
+    matches!(admission, Admission::Run(Some(_)))
+}
+
+#[tokio::test]
+async fn redis_slots_are_shared_across_workers() {
+    let (_redis, redis_url) = start_redis().await;
+    let worker_a = Arc::new(ContestLimiter::redis(&redis_url, 1, 60).unwrap());
+    let worker_b = Arc::new(ContestLimiter::redis(&redis_url, 1, 60).unwrap());
+
+    let held = worker_a.admit(&contest_task("a", 1)).await;
+    assert!(is_admitted(&held));
+    assert!(matches!(
+        worker_b.admit(&contest_task("b", 1)).await,
+        Admission::AtCap
+    ));
+    assert!(is_admitted(&worker_b.admit(&contest_task("c", 2)).await));
+
+    // Slots are given back asynchronously once dropped.
+    drop(held);
+    let admitted = timeout(Duration::from_secs(5), async {
+        loop {
+            let admission = worker_b.admit(&contest_task("b", 1)).await;
+            if is_admitted(&admission) {
+                return admission;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await;
+    assert!(admitted.is_ok(), "released slot was never freed");
+}
+
+#[tokio::test]
+async fn running_slot_is_renewed_past_its_ttl_and_dead_slot_lapses() {
+    let (_redis, redis_url) = start_redis().await;
+    let worker_a = Arc::new(ContestLimiter::redis(&redis_url, 1, 1).unwrap());
+    let worker_b = Arc::new(ContestLimiter::redis(&redis_url, 1, 1).unwrap());
+
+    let held = worker_a.admit(&contest_task("a", 1)).await;
+    assert!(is_admitted(&held));
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    assert!(
+        matches!(
+            worker_b.admit(&contest_task("b", 1)).await,
+            Admission::AtCap
+        ),
+        "a running job's slot lapsed"
+    );
+    drop(held);
+
+    // A slot left behind by a crashed worker is never renewed.
+    let client = redis::Client::open(redis_url.as_str()).unwrap();
+    let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+    let lapses_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        + 1;
+    let _: i64 = redis::cmd("ZADD")
+        .arg("broccoli:contest_slots:2")
+        .arg(lapses_at)
+        .arg("crashed-worker-slot")
+        .query_async(&mut conn)
+        .await
+        .unwrap();
+    assert!(matches!(
+        worker_b.admit(&contest_task("c", 2)).await,
+        Admission::AtCap
+    ));
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    assert!(is_admitted(&worker_b.admit(&contest_task("c", 2)).await));
+}
+
+#[tokio::test]
+async fn capped_task_is_requeued_without_holding_a_consumer() {
+    let (_redis, redis_url) = start_redis().await;
+    let mq = Arc::new(
+        init_mq(MqConfig {
+            url: redis_url.clone(),
+            pool_size: 4,
+        })
+        .await
+        .expect("failed to create MQ client"),
+    );
+    let limiter = Arc::new(ContestLimiter::redis(&redis_url, 1, 60).unwrap());
+
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let queue = format!("operations.contest_limit_{suffix}");
+    for (id, contest_id) in [("a1", 1), ("a2", 1), ("b", 2)] {
+        mq.publish(
+            &queue,
+            None,
+            &Envelope::new(MessageHeaders::json(), contest_task(id, contest_id)),
+            None,
+        )
+        .await
+        .expect("failed to publish task");
+    }
+
+    // Two consumers, as `process_message` would run them: one holds the
+    // first contest 1 job, the other must move on to contest 2 rather than
+    // wait out the cap on the second contest 1 job.
+    let origin = Instant::now();
+    let runs: Arc<Mutex<Vec<(String, Duration, Duration)>>> = Arc::default();
+    let handler = {
+        let mq = Arc::clone(&mq);
+        let limiter = Arc::clone(&limiter);
+        let queue = queue.clone();
+        let runs = Arc::clone(&runs);
+        move |message: BrokerMessage<Envelope<Task>>| {
+            let mq = Arc::clone(&mq);
+            let limiter = Arc::clone(&limiter);
+            let queue = queue.clone();
+            let runs = Arc::clone(&runs);
+            async move {
+                let envelope = message.payload;
+                let _slot = match limiter.admit(&envelope.payload).await {
+                    Admission::Run(slot) => slot,
+                    Admission::AtCap => return limiter.requeue(&mq, &queue, &envelope).await,
+                };
+                let start = origin.elapsed();
+                tokio::time::sleep(Duration::from_millis(800)).await;
+                let end = origin.elapsed();
+                runs.lock()
+                    .unwrap()
+                    .push((envelope.payload.id.clone(), start, end));
+                Ok(())
+            }
+        }
+    };
+    let consumer = tokio::spawn({
+        let mq = Arc::clone(&mq);
+        let queue = queue.clone();
+        async move {
+            let _ = mq::consume(&mq, &queue, &ConsumeConfig::with_concurrency(2), handler).await;
+        }
+    });
+
+    let finished = timeout(Duration::from_secs(15), async {
+        while runs.lock().unwrap().len() < 3 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await;
+    consumer.abort();
+    assert!(
+        finished.is_ok(),
+        "tasks did not all run: {:?}",
+        runs.lock().unwrap()
+    );
+
+    let runs = runs.lock().unwrap().clone();
+    let run = |id: &str| {
+        runs.iter()
+            .find(|(run_id, _, _)| run_id == id)
+            .map(|(_, start, end)| (*start, *end))
+            .unwrap()
+    };
+    let (a1, a2) = (run("a1"), run("a2"));
+    let (first, second) = if a1.0 <= a2.0 { (a1, a2) } else { (a2, a1) };
+    let b = run("b");
+    assert!(
+        second.0 >= first.1,
+        "contest 1 jobs overlapped: {first:?} and {second:?}"
+    );
+    assert!(
+        b.0 < first.1,
+        "contest 2 job waited for contest 1: {b:?} vs {first:?}"
+    );
+}
@@ -8,13 +8,16 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 use worker::WorkerAppConfig;
 use worker::models::operation::executor::OperationTaskExecutor;
+use worker::models::operation::file_cacher::UnavailableFileCacher;
+use worker::models::operation::handler::OperationHandler;
 use worker::models::operation::models::{
     Environment, IOConfig, IOTarget, OperationResult, OperationTask, Step,
 };
 use worker::models::operation::sandbox::isolate::IsolateSandboxManager;
 use worker::models::operation::sandbox::{
-    DirectoryOptions, DirectoryRule, ResourceLimits, RunOptions,
+    DISK_QUOTA_EXCEEDED_STATUS, DirectoryOptions, DirectoryRule, ResourceLimits, RunOptions,
 };
+use worker::models::operation::task_cache::NoopTaskCacheStore;
 use worker::models::worker::Worker;
 
 static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -84,6 +87,8 @@ fn build_operation_task(command: &str) -> OperationTask {
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     }
 }
 
@@ -114,6 +119,7 @@ async fn execute_operation_with_isolate(
         reply_queue: None,
         priority: None,
         trace_context: None,
+        schema_version: None,
     };
 
     let result = worker.execute_task(task).await.unwrap();
@@ -142,6 +148,27 @@ async fn execute_operation_task_successfully_with_isolate_sandbox() {
     assert_eq!(step_result.sandbox_result.exit_code, Some(0));
 }
 
+#[tokio::test]
+#[ignore = "requires Linux isolate sandbox installed and configured"]
+#[serial]
+async fn program_filling_the_box_is_killed_for_disk_quota_with_isolate_sandbox() {
+    assert!(isolate_available(), "isolate is not available");
+
+    let mut operation =
+        build_operation_task("while true; do head -c 65536 /dev/zero >> big.bin; done");
+    operation.tasks[0].conf.resource_limits.disk_quota = Some(512);
+    operation.tasks[0].conf.resource_limits.wall_time_limit = Some(10.0);
+
+    let (result, operation_result) =
+        execute_operation_with_isolate("task-disk-quota", operation).await;
+    assert!(!result.success);
+
+    let sandbox = &operation_result.task_results["step-1"].sandbox_result;
+    assert_eq!(sandbox.status, DISK_QUOTA_EXCEEDED_STATUS);
+    assert!(sandbox.killed);
+    assert!(sandbox.message.contains("disk quota exceeded"));
+}
+
 #[tokio::test]
 #[ignore = "requires Linux isolate sandbox installed and configured"]
 #[serial]
@@ -271,6 +298,8 @@ printf '2 40\n' > input.txt
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) =
@@ -361,6 +390,8 @@ CPP
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) =
@@ -416,6 +447,8 @@ async fn execute_operation_task_with_empty_pipe_name_should_fail_isolate() {
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) =
@@ -504,6 +537,8 @@ async fn execute_operation_task_with_two_envs_shared_directory_mapping_isolate()
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) =
@@ -520,3 +555,37 @@ async fn execute_operation_task_with_two_envs_shared_directory_mapping_isolate()
     assert!(consumer.success);
     assert_eq!(consumer.sandbox_result.exit_code, Some(0));
 }
+
+#[tokio::test]
+#[ignore = "requires Linux isolate sandbox installed and configured"]
+#[serial]
+async fn pooled_isolate_box_does_not_leak_tmp_between_jobs() {
+    let (metrics, _registry) = common::observability::init_metrics("broccoli-worker-test");
+    let handler = OperationHandler::new(
+        Box::new(IsolateSandboxManager::new(isolate_bin(), false)),
+        Box::new(UnavailableFileCacher::new("test storage unavailable")),
+        Box::new(NoopTaskCacheStore),
+        String::new(),
+        metrics,
+    )
+    .with_sandbox_pool_size(1);
+
+    let first = handler
+        .execute(&build_operation_task("echo secret > /tmp/leak.txt"))
+        .await
+        .unwrap();
+    assert!(first.success, "first job failed: {first:?}");
+
+    let second = handler
+        .execute(&build_operation_task(
+            "if [ -e /tmp/leak.txt ]; then echo dirty; else echo clean; fi",
+        ))
+        .await
+        .unwrap();
+    assert!(second.success, "second job failed: {second:?}");
+    assert_eq!(
+        second.task_results["step-1"].sandbox_result.stdout.trim(),
+        "clean",
+        "/tmp from the first job must not be visible to the second"
+    );
+}
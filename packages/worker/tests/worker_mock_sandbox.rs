@@ -1,20 +1,39 @@
+use broccoli_server_sdk::evaluator::interpret_compile_result;
+use broccoli_server_sdk::types::{CompileSpec, JudgeJob, Verdict, sanitize_untrusted_env_rules};
+use common::retry::RetryTracker;
 use common::storage::BlobStore;
 use common::storage::object_storage::{ObjectStorageBlobStore, ObjectStorageConfig};
-use common::worker::Task;
+use common::worker::{TASK_SCHEMA_VERSION, Task};
+use common::{DlqConfig, DlqEnvelope, DlqErrorCode};
+use mq::{BrokerMessage, ConsumeConfig, MqConfig, init_mq};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+use worker::WorkerError;
+use worker::dry_run::{self, DryRunLanguage};
+use worker::judge_epoch::JudgeEpochs;
 use worker::models::operation::executor::OperationTaskExecutor;
-use worker::models::operation::file_cacher::{BlobStoreFileCacher, UnavailableFileCacher};
-use worker::models::operation::handler::OperationHandler;
+use worker::models::operation::file_cacher::{
+    BlobStoreFileCacher, NoopFileCacher, UnavailableFileCacher,
+};
+use worker::models::operation::handler::{OperationHandler, watchdog_deadline};
 use worker::models::operation::models::{
     Channel, Environment, IOConfig, IOTarget, OperationResult, OperationTask, SessionFile, Step,
 };
+use worker::models::operation::sandbox::error::SandboxError;
 use worker::models::operation::sandbox::mock::MockSandboxManager;
-use worker::models::operation::sandbox::{DirectoryOptions, DirectoryRule, RunOptions};
+use worker::models::operation::sandbox::{
+    DISK_QUOTA_EXCEEDED_STATUS, DirectoryOptions, DirectoryRule, EnvRule, ExecutionResult,
+    ResourceLimits, RunOptions, SandboxManager, StderrCapture,
+};
 use worker::models::operation::task_cache::NoopTaskCacheStore;
 use worker::models::worker::Worker;
+use worker::task_runner::{
+    TaskOutcome, dead_letter_unsupported_schema, drop_if_superseded, run_task,
+};
 
 static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -61,6 +80,8 @@ fn build_operation_task(command: &str) -> OperationTask {
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     }
 }
 
@@ -91,6 +112,7 @@ async fn execute_operation_with_mock(
         reply_queue: None,
         priority: None,
         trace_context: None,
+        schema_version: None,
     };
 
     let result = worker.execute_task(task).await.unwrap();
@@ -140,6 +162,65 @@ async fn execute_operation_task_successfully_with_mock_sandbox() {
     assert!(step_result.sandbox_result.stdout.contains("mock-ok"));
 }
 
+#[tokio::test]
+async fn result_carries_configured_worker_id() {
+    let worker = build_worker_with_mock_sandbox()
+        .await
+        .with_id("judge-node-7");
+    let task = Task {
+        id: "task-worker-id".to_string(),
+        task_type: "operation".to_string(),
+        executor_name: "operation".to_string(),
+        payload: serde_json::to_value(build_operation_task("echo ok")).unwrap(),
+        result_queue: "test_results".into(),
+        reply_queue: None,
+        priority: None,
+        trace_context: None,
+        schema_version: None,
+    };
+
+    let result = worker.execute_task(task).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.worker_id.as_deref(), Some("judge-node-7"));
+}
+
+#[tokio::test]
+async fn compile_exceeding_compile_time_limit_is_killed_with_timeout_status() {
+    let spec = CompileSpec {
+        command: vec!["/bin/sh".into(), "-c".into(), "exec sleep 5".into()],
+        cache_inputs: vec![],
+        outputs: vec![],
+        resource_limits: None,
+        time_limit: Some(0.2),
+        env_rules: vec![],
+    };
+    let mut operation = build_operation_task("");
+    let step = &mut operation.tasks[0];
+    step.id = "compile".to_string();
+    step.argv = spec.command.clone();
+    step.conf.resource_limits = spec.limits_or(|| ResourceLimits {
+        time_limit: Some(30.0),
+        wall_time_limit: Some(60.0),
+        ..Default::default()
+    });
+
+    let (result, operation_result) =
+        execute_operation_with_mock("task-compile-timeout", operation).await;
+
+    let compile = operation_result.task_results.get("compile").unwrap();
+    assert!(!compile.success);
+    assert_eq!(compile.sandbox_result.status, "TO");
+    assert!(compile.sandbox_result.wall_time_used < 5.0);
+
+    let verdict = interpret_compile_result(0, &serde_json::from_value(result.output).unwrap());
+    assert_eq!(verdict.verdict, Verdict::CompileError);
+    let message = verdict.message.unwrap();
+    assert!(
+        message.starts_with("Compilation time limit exceeded"),
+        "{message}"
+    );
+}
+
 #[tokio::test]
 async fn large_stdout_is_returned_as_capped_preview() {
     let command = "awk 'BEGIN { for (i = 0; i < 70000; i++) printf \"a\" }'";
@@ -157,6 +238,31 @@ async fn large_stdout_is_returned_as_capped_preview() {
     );
 }
 
+#[test]
+fn sub_millisecond_time_rounds_up_to_one_millisecond() {
+    let result = ExecutionResult {
+        time_used: 0.0005,
+        memory_used: Some(2048),
+        ..Default::default()
+    };
+    assert_eq!(result.time_used_ms(), Some(1));
+    assert_eq!(result.memory_used_kb(), Some(2048));
+}
+
+#[tokio::test]
+async fn reported_time_converts_to_whole_milliseconds_rounded_up() {
+    let (_, operation_result) =
+        execute_operation_with_mock("task-time-units", build_operation_task("true")).await;
+    let sandbox = &operation_result.task_results["step-1"].sandbox_result;
+
+    // Seconds on the wire from the worker, milliseconds in judge results.
+    assert!(sandbox.time_used > 0.0 && sandbox.time_used < 5.0);
+    let ms = sandbox.time_used_ms().unwrap();
+    assert!(ms >= 1);
+    assert!(ms as f64 >= sandbox.time_used * 1000.0 - 1e-3);
+    assert!(((ms - 1) as f64) < sandbox.time_used * 1000.0);
+}
+
 #[tokio::test]
 async fn blob_input_fails_when_blob_storage_is_unavailable() {
     let (metrics, _registry) = common::observability::init_metrics("broccoli-worker-test");
@@ -183,6 +289,103 @@ async fn blob_input_fails_when_blob_storage_is_unavailable() {
     assert!(format!("{err:?}").contains("blob storage is unavailable"));
 }
 
+fn mock_handler() -> OperationHandler {
+    let (metrics, _registry) = common::observability::init_metrics("broccoli-worker-test");
+    OperationHandler::new(
+        Box::new(MockSandboxManager::new(unique_mock_base_dir())),
+        Box::new(NoopFileCacher),
+        Box::new(NoopTaskCacheStore),
+        String::new(),
+        metrics,
+    )
+}
+
+fn shell_language(compile: &str) -> DryRunLanguage {
+    DryRunLanguage {
+        id: "sh".to_string(),
+        source_filename: "hello.sh".to_string(),
+        source: format!("echo '{}'\n", dry_run::EXPECTED_OUTPUT),
+        compile: Some(vec![
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            compile.to_string(),
+        ]),
+        run: vec!["/bin/sh".to_string(), "main.sh".to_string()],
+    }
+}
+
+#[tokio::test]
+async fn dry_run_of_configured_language_succeeds() {
+    let report = dry_run::dry_run(&mock_handler(), &shell_language("cp hello.sh main.sh"))
+        .await
+        .unwrap();
+
+    assert_eq!(report.compile.as_ref().unwrap().exit_code, Some(0));
+    let run = report.run.as_ref().expect("run result");
+    assert_eq!(run.exit_code, Some(0));
+    assert_eq!(run.status, "OK");
+    assert_eq!(run.stdout.trim(), dry_run::EXPECTED_OUTPUT);
+    assert!(report.succeeded());
+}
+
+#[tokio::test]
+async fn dry_run_reports_compile_failure_without_running() {
+    let report = dry_run::dry_run(
+        &mock_handler(),
+        &shell_language("echo 'compiler not found' >&2; exit 127"),
+    )
+    .await
+    .unwrap();
+
+    let compile = report.compile.as_ref().unwrap();
+    assert_eq!(compile.exit_code, Some(127));
+    assert!(compile.stderr.contains("compiler not found"));
+    assert!(report.run.is_none());
+    assert!(!report.succeeded());
+}
+
+#[test]
+fn dry_run_language_uses_the_judge_compilation_config() {
+    let default_c = DryRunLanguage::from_compilation_config("c", None).unwrap();
+    assert_eq!(
+        default_c.compile.unwrap(),
+        [
+            "/usr/bin/gcc",
+            "-O2",
+            "-std=c17",
+            "main.c",
+            "-o",
+            "main",
+            "-lm"
+        ]
+    );
+
+    let saved = serde_json::json!({
+        "cpp": { "compiler": "/opt/gcc/bin/g++", "flags": ["-O0"] },
+        "java": { "compiler": "javac", "runner": "/opt/jdk/bin/java", "flags": [] },
+    });
+    let cpp = DryRunLanguage::from_compilation_config("cpp", Some(&saved)).unwrap();
+    assert_eq!(
+        cpp.compile.unwrap(),
+        ["/opt/gcc/bin/g++", "-O0", "main.cpp", "-o", "main"]
+    );
+    let java = DryRunLanguage::from_compilation_config("java", Some(&saved)).unwrap();
+    assert_eq!(java.run, ["/opt/jdk/bin/java", "-cp", ".", "Main"]);
+    // Saved configs replace the schema defaults wholesale, as in the plugin.
+    let python = DryRunLanguage::from_compilation_config("python3", Some(&saved)).unwrap();
+    assert_eq!(python.run, ["", "main.py"]);
+
+    assert!(DryRunLanguage::from_compilation_config("rust", None).is_none());
+}
+
+#[tokio::test]
+#[ignore = "requires /usr/bin/python3"]
+async fn dry_run_of_builtin_python3_succeeds() {
+    let language = DryRunLanguage::from_compilation_config("python3", None).unwrap();
+    let report = dry_run::dry_run(&mock_handler(), &language).await.unwrap();
+    assert!(report.succeeded(), "{report:?}");
+}
+
 #[tokio::test]
 async fn execute_operation_task_failure_with_mock_sandbox() {
     let (result, operation_result) =
@@ -195,6 +398,130 @@ async fn execute_operation_task_failure_with_mock_sandbox() {
     assert_eq!(step_result.sandbox_result.exit_code, Some(17));
 }
 
+fn stderr_capture_task(command: &str, capture: StderrCapture) -> OperationTask {
+    let mut operation = build_operation_task(command);
+    let step = &mut operation.tasks[0];
+    step.conf.stderr_capture = capture;
+    step.io.stderr = IOTarget::File {
+        path: "stderr.txt".to_string(),
+    };
+    step.collect = vec!["stderr.txt".to_string()];
+    operation
+}
+
+#[tokio::test]
+async fn stderr_capture_on_failure_leaves_stderr_for_the_evaluator() {
+    let (_, clean) = execute_operation_with_mock(
+        "task-stderr-ok",
+        stderr_capture_task("echo noise >&2", StderrCapture::OnFailure),
+    )
+    .await;
+    let step = &clean.task_results["step-1"];
+    assert!(step.success);
+    assert_eq!(step.sandbox_result.stderr.trim(), "noise");
+    assert!(step.collected_outputs.contains_key("stderr.txt"));
+
+    let (_, failed) = execute_operation_with_mock(
+        "task-stderr-fail",
+        stderr_capture_task("echo crashed >&2; exit 3", StderrCapture::OnFailure),
+    )
+    .await;
+    let step = &failed.task_results["step-1"];
+    assert!(!step.success);
+    assert_eq!(step.sandbox_result.stderr.trim(), "crashed");
+    assert!(step.collected_outputs.contains_key("stderr.txt"));
+}
+
+#[tokio::test]
+async fn stderr_capture_never_discards_stderr_of_failed_runs() {
+    let (_, operation_result) = execute_operation_with_mock(
+        "task-stderr-never",
+        stderr_capture_task("echo crashed >&2; exit 3", StderrCapture::Never),
+    )
+    .await;
+    let step = &operation_result.task_results["step-1"];
+    assert!(!step.success);
+    assert!(step.sandbox_result.stderr.is_empty());
+    assert!(step.collected_outputs.is_empty());
+}
+
+#[tokio::test]
+async fn invalid_utf8_stderr_is_stored_as_valid_utf8() {
+    let (_, operation_result) = execute_operation_with_mock(
+        "task-stderr-binary",
+        stderr_capture_task(
+            "printf 'bad \\377\\376 byte\\000here' >&2; exit 1",
+            StderrCapture::Always,
+        ),
+    )
+    .await;
+    let step = &operation_result.task_results["step-1"];
+    assert!(!step.success);
+    assert_eq!(step.sandbox_result.stderr, "bad \u{FFFD}\u{FFFD} bytehere");
+
+    let json = serde_json::to_string(&operation_result).expect("serialize result");
+    let round_trip: OperationResult = serde_json::from_str(&json).expect("deserialize result");
+    assert_eq!(
+        round_trip.task_results["step-1"].sandbox_result.stderr,
+        step.sandbox_result.stderr
+    );
+}
+
+#[tokio::test]
+async fn program_filling_the_box_is_killed_for_disk_quota() {
+    let mut operation =
+        build_operation_task("while true; do head -c 65536 /dev/zero >> big.bin; done");
+    operation.tasks[0].conf.resource_limits.disk_quota = Some(512);
+    operation.tasks[0].conf.resource_limits.wall_time_limit = Some(10.0);
+
+    let (result, operation_result) =
+        execute_operation_with_mock("task-disk-quota", operation).await;
+    assert!(!result.success);
+
+    let sandbox = &operation_result.task_results["step-1"].sandbox_result;
+    assert_eq!(sandbox.status, DISK_QUOTA_EXCEEDED_STATUS);
+    assert!(sandbox.killed);
+    assert!(sandbox.message.contains("disk quota exceeded"));
+}
+
+#[tokio::test]
+async fn staged_inputs_do_not_count_against_disk_quota() {
+    let mut operation = build_operation_task("head -c 65536 /dev/zero > small.bin");
+    operation.environments[0].files_in = vec![(
+        "input.txt".to_string(),
+        SessionFile::Content {
+            content: "x".repeat(1024 * 1024),
+        },
+    )];
+    operation.tasks[0].conf.resource_limits.disk_quota = Some(512);
+
+    let (result, operation_result) =
+        execute_operation_with_mock("task-disk-quota-inputs", operation).await;
+    assert!(result.success);
+    assert_eq!(
+        operation_result.task_results["step-1"]
+            .sandbox_result
+            .status,
+        "OK"
+    );
+}
+
+#[tokio::test]
+async fn program_within_disk_quota_succeeds() {
+    let mut operation = build_operation_task("head -c 65536 /dev/zero > small.bin");
+    operation.tasks[0].conf.resource_limits.disk_quota = Some(512);
+
+    let (result, operation_result) =
+        execute_operation_with_mock("task-disk-quota-ok", operation).await;
+    assert!(result.success);
+    assert_eq!(
+        operation_result.task_results["step-1"]
+            .sandbox_result
+            .status,
+        "OK"
+    );
+}
+
 #[tokio::test]
 #[ignore = "requires a C++17 compiler available on PATH"]
 async fn execute_cpp_oi_pipeline_with_io_redirection() {
@@ -293,6 +620,8 @@ printf '2 40\n' > input.txt
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) = execute_operation_with_mock("task-cpp-oi", operation).await;
@@ -389,6 +718,8 @@ CPP
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) =
@@ -410,6 +741,66 @@ CPP
     assert_eq!(skipped_result.sandbox_result.status, "UNKNOWN");
 }
 
+#[tokio::test]
+async fn compile_only_operation_returns_compile_output_without_exec() {
+    // Shaped like a `judge_mode=compile_only` evaluation: a lone compile
+    // step whose diagnostics go to a file, and no exec step.
+    let operation = OperationTask {
+        environments: vec![Environment {
+            id: "sandbox".to_string(),
+            files_in: vec![(
+                "main.c".to_string(),
+                SessionFile::Content {
+                    content: "int main() { int unused; }".to_string(),
+                },
+            )],
+        }],
+        tasks: vec![Step {
+            id: "compile".to_string(),
+            env_ref: "sandbox".to_string(),
+            argv: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "echo 'main.c:1: warning: unused variable' >&2 && cp main.c main".to_string(),
+            ],
+            conf: RunOptions::default(),
+            io: IOConfig {
+                stdin: IOTarget::Null,
+                stdout: IOTarget::Null,
+                stderr: IOTarget::File {
+                    path: "compile_stderr.txt".to_string(),
+                },
+            },
+            collect: vec!["main".to_string(), "compile_stderr.txt".to_string()],
+            depends_on: vec![],
+            cache: None,
+        }],
+        channels: vec![],
+        priority: None,
+        target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
+    };
+
+    let (result, operation_result) =
+        execute_operation_with_mock("task-compile-only", operation).await;
+
+    assert!(result.success, "operation failed: {:?}", operation_result);
+    assert_eq!(operation_result.task_results.len(), 1);
+    assert!(!operation_result.task_results.contains_key("exec"));
+
+    let compile = operation_result.task_results.get("compile").unwrap();
+    assert_eq!(compile.sandbox_result.exit_code, Some(0));
+    assert!(
+        compile
+            .sandbox_result
+            .stderr
+            .contains("warning: unused variable"),
+        "compile output missing: {:?}",
+        compile.sandbox_result
+    );
+}
+
 #[tokio::test]
 async fn execute_operation_task_with_empty_pipe_name_should_fail() {
     let operation = OperationTask {
@@ -440,6 +831,8 @@ async fn execute_operation_task_with_empty_pipe_name_should_fail() {
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) =
@@ -517,6 +910,8 @@ async fn execute_operation_task_with_two_envs_shared_directory_mapping() {
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) =
@@ -645,6 +1040,8 @@ async fn execute_operation_with_file_pulled_from_object_storage() {
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let result = handler
@@ -714,6 +1111,8 @@ async fn shared_channel_fifo_between_two_environments() {
         }],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) =
@@ -795,6 +1194,8 @@ async fn channel_pipe_io_redirect_between_environments() {
         }],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) =
@@ -867,6 +1268,8 @@ async fn non_channel_pipe_still_works_with_channels_present() {
         }],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     };
 
     let (result, operation_result) =
@@ -883,3 +1286,640 @@ async fn non_channel_pipe_still_works_with_channels_present() {
         reader.sandbox_result.stdout
     );
 }
+
+/// Sandbox whose `execute` never returns, standing in for a hung isolate run.
+/// Records every removed box id so tests can assert cleanup happened.
+struct StallingSandboxManager {
+    base_dir: PathBuf,
+    removed: Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl SandboxManager for StallingSandboxManager {
+    async fn create_sandbox(&self, id: Option<&str>) -> Result<PathBuf, SandboxError> {
+        let path = self.base_dir.join(id.unwrap_or("0"));
+        tokio::fs::create_dir_all(&path)
+            .await
+            .map_err(|e| SandboxError::Execution(e.to_string()))?;
+        Ok(path)
+    }
+
+    async fn remove_sandbox(&self, id: &str) -> Result<(), SandboxError> {
+        self.removed.lock().unwrap().push(id.to_string());
+        let _ = tokio::fs::remove_dir_all(self.base_dir.join(id)).await;
+        Ok(())
+    }
+
+    async fn execute(
+        &self,
+        _box_id: &str,
+        _argv: Vec<String>,
+        _run_options: &RunOptions,
+    ) -> Result<ExecutionResult, SandboxError> {
+        std::future::pending().await
+    }
+}
+
+/// Worker whose operations all stall, with a watchdog that gives up quickly.
+fn stalling_worker(removed: &Arc<std::sync::Mutex<Vec<String>>>) -> Worker {
+    let (metrics, _registry) = common::observability::init_metrics("broccoli-worker-test");
+    let worker = Worker::with_no_executors();
+    worker.register_executor(
+        "operation",
+        Arc::new(
+            OperationTaskExecutor::new_with_sandbox_manager(
+                Box::new(StallingSandboxManager {
+                    base_dir: unique_mock_base_dir(),
+                    removed: Arc::clone(removed),
+                }),
+                metrics,
+            )
+            .with_watchdog_margin(Duration::from_millis(100)),
+        ),
+    );
+    worker
+}
+
+fn stalled_task(id: &str, result_queue: &str) -> Task {
+    let mut operation = build_operation_task("sleep 3600");
+    operation.tasks[0].conf.resource_limits.wall_time_limit = Some(0.2);
+    Task {
+        id: id.to_string(),
+        task_type: "operation".to_string(),
+        executor_name: "operation".to_string(),
+        payload: serde_json::to_value(operation).unwrap(),
+        result_queue: result_queue.into(),
+        reply_queue: None,
+        priority: None,
+        trace_context: None,
+        schema_version: None,
+    }
+}
+
+#[tokio::test]
+async fn stalled_sandbox_is_aborted_by_watchdog_and_surfaces_retryable_error() {
+    let removed = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let worker = stalling_worker(&removed);
+    let task = stalled_task("task-stalled", "test_results");
+
+    let outcome = tokio::time::timeout(Duration::from_secs(10), worker.execute_task(task))
+        .await
+        .expect("watchdog must abort the stalled operation instead of hanging");
+
+    // An `Err` (rather than a failed `TaskResult`) is what drives the worker's
+    // retry/DLQ loop in `process_message`.
+    match outcome {
+        Err(WorkerError::Timeout(msg)) => assert!(msg.contains("watchdog"), "{msg}"),
+        other => panic!("expected WorkerError::Timeout, got {other:?}"),
+    }
+    assert_eq!(
+        removed.lock().unwrap().len(),
+        1,
+        "sandbox must be cleaned up"
+    );
+}
+
+#[tokio::test]
+async fn stalled_operation_is_retried_then_dead_lettered() {
+    let redis = Redis::default()
+        .start()
+        .await
+        .expect("failed to start Redis container");
+    let port = redis
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("failed to get Redis port");
+    let mq = Arc::new(
+        init_mq(MqConfig {
+            url: format!("redis://127.0.0.1:{port}"),
+            pool_size: 2,
+        })
+        .await
+        .expect("failed to create MQ client"),
+    );
+
+    let removed = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let worker = Arc::new(stalling_worker(&removed));
+    let task = stalled_task("task-stalled-dlq", "test_results");
+    let dlq_config = DlqConfig {
+        max_retries: 2,
+        base_delay_ms: 10,
+        max_delay_ms: 10,
+        ..DlqConfig::default()
+    };
+    let retry_tracker = Arc::new(tokio::sync::Mutex::new(RetryTracker::new(
+        dlq_config.max_retries,
+    )));
+    let (metrics, _registry) = common::observability::init_metrics("broccoli-worker-test");
+
+    let outcome = tokio::time::timeout(
+        Duration::from_secs(30),
+        run_task(
+            &task,
+            &worker,
+            &mq,
+            "test_operation_dlq",
+            &dlq_config,
+            &retry_tracker,
+            &metrics,
+        ),
+    )
+    .await
+    .expect("a stalled operation must not hang the retry loop");
+    assert_eq!(outcome, TaskOutcome::DeadLettered);
+    // The first attempt plus both retries, each aborted and cleaned up.
+    assert_eq!(removed.lock().unwrap().len(), 3);
+    assert_eq!(retry_tracker.lock().await.get_attempt(&task.id), 0);
+
+    let (dlq_tx, mut dlq_rx) = tokio::sync::mpsc::unbounded_channel();
+    let dlq_consumer = tokio::spawn({
+        let mq = Arc::clone(&mq);
+        async move {
+            let _ = mq::consume(
+                &mq,
+                "test_operation_dlq",
+                &ConsumeConfig::default(),
+                move |message: BrokerMessage<DlqEnvelope>| {
+                    let dlq_tx = dlq_tx.clone();
+                    async move {
+                        let _ = dlq_tx.send(message.payload);
+                        Ok(())
+                    }
+                },
+            )
+            .await;
+        }
+    });
+    let (result_tx, mut result_rx) = tokio::sync::mpsc::unbounded_channel();
+    let result_consumer = tokio::spawn({
+        let mq = Arc::clone(&mq);
+        async move {
+            let _ = mq::consume(
+                &mq,
+                "test_results",
+                &ConsumeConfig::default(),
+                move |message: BrokerMessage<common::worker::TaskResult>| {
+                    let result_tx = result_tx.clone();
+                    async move {
+                        let _ = result_tx.send(message.payload);
+                        Ok(())
+                    }
+                },
+            )
+            .await;
+        }
+    });
+
+    let envelope = tokio::time::timeout(Duration::from_secs(5), dlq_rx.recv())
+        .await
+        .expect("task was not dead-lettered")
+        .unwrap();
+    assert_eq!(envelope.message_id, "task-stalled-dlq");
+    assert_eq!(envelope.error_code, DlqErrorCode::MaxRetriesExceeded);
+    assert_eq!(envelope.retry_history.len(), 3);
+    assert!(envelope.error_message.contains("watchdog"));
+
+    let result = tokio::time::timeout(Duration::from_secs(5), result_rx.recv())
+        .await
+        .expect("failure was not reported on the result queue")
+        .unwrap();
+    assert_eq!(result.task_id, "task-stalled-dlq");
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("after 3 retries"));
+
+    dlq_consumer.abort();
+    result_consumer.abort();
+}
+
+async fn start_redis_mq() -> (testcontainers::ContainerAsync<Redis>, Arc<mq::Mq>, String) {
+    let redis = Redis::default()
+        .start()
+        .await
+        .expect("failed to start Redis container");
+    let port = redis
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("failed to get Redis port");
+    let url = format!("redis://127.0.0.1:{port}");
+    let mq = Arc::new(
+        init_mq(MqConfig {
+            url: url.clone(),
+            pool_size: 2,
+        })
+        .await
+        .expect("failed to create MQ client"),
+    );
+    (redis, mq, url)
+}
+
+/// Forwards every message published to `queue` into the returned channel.
+fn collect_queue<T>(
+    mq: &Arc<mq::Mq>,
+    queue: &'static str,
+) -> (
+    tokio::task::JoinHandle<()>,
+    tokio::sync::mpsc::UnboundedReceiver<T>,
+)
+where
+    T: serde::de::DeserializeOwned + serde::Serialize + Clone + Send + 'static,
+{
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let mq = Arc::clone(mq);
+    let consumer = tokio::spawn(async move {
+        let _ = mq::consume(
+            &mq,
+            queue,
+            &ConsumeConfig::default(),
+            move |message: BrokerMessage<T>| {
+                let tx = tx.clone();
+                async move {
+                    let _ = tx.send(message.payload);
+                    Ok(())
+                }
+            },
+        )
+        .await;
+    });
+    (consumer, rx)
+}
+
+fn versioned_task(id: &str, schema_version: Option<u32>) -> Task {
+    Task {
+        id: id.to_string(),
+        task_type: "operation".to_string(),
+        executor_name: "operation".to_string(),
+        payload: serde_json::to_value(build_operation_task("echo ok")).unwrap(),
+        result_queue: "test_schema_results".into(),
+        reply_queue: None,
+        priority: None,
+        trace_context: None,
+        schema_version,
+    }
+}
+
+#[tokio::test]
+async fn future_schema_task_is_dead_lettered_and_current_one_runs() {
+    let (_redis, mq, _url) = start_redis_mq().await;
+    let (metrics, _registry) = common::observability::init_metrics("broccoli-worker-test");
+    let (dlq_consumer, mut dlq_rx) = collect_queue::<DlqEnvelope>(&mq, "test_schema_dlq");
+    let (result_consumer, mut result_rx) =
+        collect_queue::<common::worker::TaskResult>(&mq, "test_schema_results");
+
+    let future = versioned_task("task-schema-future", Some(TASK_SCHEMA_VERSION + 1));
+    assert!(
+        dead_letter_unsupported_schema(
+            &future,
+            future.schema_version,
+            &mq,
+            "test_schema_dlq",
+            &metrics
+        )
+        .await
+    );
+
+    let current = versioned_task("task-schema-current", Some(TASK_SCHEMA_VERSION));
+    assert!(
+        !dead_letter_unsupported_schema(
+            &current,
+            current.schema_version,
+            &mq,
+            "test_schema_dlq",
+            &metrics
+        )
+        .await
+    );
+    let worker = Arc::new(build_worker_with_mock_sandbox().await);
+    let dlq_config = DlqConfig::default();
+    let retry_tracker = Arc::new(tokio::sync::Mutex::new(RetryTracker::new(
+        dlq_config.max_retries,
+    )));
+    let outcome = run_task(
+        &current,
+        &worker,
+        &mq,
+        "test_schema_dlq",
+        &dlq_config,
+        &retry_tracker,
+        &metrics,
+    )
+    .await;
+    assert_eq!(outcome, TaskOutcome::Completed);
+
+    let envelope = tokio::time::timeout(Duration::from_secs(5), dlq_rx.recv())
+        .await
+        .expect("future-schema task was not dead-lettered")
+        .unwrap();
+    assert_eq!(envelope.message_id, "task-schema-future");
+    assert_eq!(envelope.error_code, DlqErrorCode::DeserializationError);
+    assert!(envelope.error_message.contains("newer than supported"));
+
+    let mut results = std::collections::HashMap::new();
+    for _ in 0..2 {
+        let result = tokio::time::timeout(Duration::from_secs(5), result_rx.recv())
+            .await
+            .expect("missing result on the result queue")
+            .unwrap();
+        results.insert(result.task_id.clone(), result);
+    }
+    assert!(!results["task-schema-future"].success);
+    assert!(results["task-schema-current"].success);
+    assert!(dlq_rx.try_recv().is_err());
+
+    dlq_consumer.abort();
+    result_consumer.abort();
+}
+
+#[tokio::test]
+async fn job_for_superseded_judge_epoch_is_skipped() {
+    let (_redis, mq, url) = start_redis_mq().await;
+    let (result_consumer, mut result_rx) =
+        collect_queue::<common::worker::TaskResult>(&mq, "test_epoch_results");
+    let mut conn = redis::Client::open(url.as_str())
+        .unwrap()
+        .get_multiplexed_async_connection()
+        .await
+        .unwrap();
+    // The server records each submission's current epoch under this key.
+    let _: () = redis::AsyncCommands::set(&mut conn, "broccoli:judge_epoch:77", 3)
+        .await
+        .unwrap();
+    let judge_epochs = JudgeEpochs::new(&url).unwrap();
+
+    let epoch_task = |id: &str, judge_epoch| {
+        let mut operation = build_operation_task("echo ok");
+        operation.judge_job = Some(JudgeJob {
+            submission_id: 77,
+            judge_epoch,
+        });
+        Task {
+            id: id.to_string(),
+            task_type: "operation".to_string(),
+            executor_name: "operation".to_string(),
+            payload: serde_json::to_value(operation).unwrap(),
+            result_queue: "test_epoch_results".into(),
+            reply_queue: None,
+            priority: None,
+            trace_context: None,
+            schema_version: None,
+        }
+    };
+
+    assert!(drop_if_superseded(&epoch_task("task-epoch-stale", 2), &judge_epochs, &mq).await);
+    assert!(!drop_if_superseded(&epoch_task("task-epoch-current", 3), &judge_epochs, &mq).await);
+
+    let result = tokio::time::timeout(Duration::from_secs(5), result_rx.recv())
+        .await
+        .expect("the skipped job was not failed back")
+        .unwrap();
+    assert_eq!(result.task_id, "task-epoch-stale");
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("Superseded"));
+
+    result_consumer.abort();
+}
+
+#[test]
+fn watchdog_deadline_sums_step_limits_plus_margin() {
+    let mut operation = build_operation_task("true");
+    operation.tasks[0].conf.resource_limits.wall_time_limit = Some(2.0);
+    operation.tasks[0].conf.resource_limits.extra_time = Some(0.5);
+    let mut second = operation.tasks[0].clone();
+    second.id = "step-2".to_string();
+    second.conf.resource_limits.wall_time_limit = None;
+    second.conf.resource_limits.extra_time = None;
+    second.conf.resource_limits.time_limit = Some(1.0);
+    operation.tasks.push(second);
+
+    assert_eq!(
+        watchdog_deadline(&operation, Duration::from_secs(5)),
+        Duration::from_millis(8_500)
+    );
+}
+
+#[tokio::test]
+async fn sandbox_is_removed_when_environment_setup_fails() {
+    let (metrics, _registry) = common::observability::init_metrics("broccoli-worker-test");
+    let removed = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let handler = OperationHandler::new(
+        Box::new(StallingSandboxManager {
+            base_dir: unique_mock_base_dir(),
+            removed: Arc::clone(&removed),
+        }),
+        Box::new(UnavailableFileCacher::new("test storage unavailable")),
+        Box::new(NoopTaskCacheStore),
+        String::new(),
+        metrics,
+    );
+
+    let mut operation = build_operation_task("cat input.txt");
+    operation.environments[0].files_in.push((
+        "input.txt".to_string(),
+        SessionFile::Blob {
+            hash: "b".repeat(64),
+        },
+    ));
+
+    handler
+        .execute(&operation)
+        .await
+        .expect_err("operation must fail when its input blob cannot be fetched");
+    assert_eq!(
+        removed.lock().unwrap().len(),
+        1,
+        "sandbox created before the failure must be removed"
+    );
+}
+
+/// Mock sandbox that records which box ids were created and reset.
+struct RecordingSandboxManager {
+    inner: MockSandboxManager,
+    created: Arc<std::sync::Mutex<Vec<String>>>,
+    reset: Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl SandboxManager for RecordingSandboxManager {
+    async fn create_sandbox(&self, id: Option<&str>) -> Result<PathBuf, SandboxError> {
+        self.created
+            .lock()
+            .unwrap()
+            .push(id.unwrap_or("0").to_string());
+        self.inner.create_sandbox(id).await
+    }
+
+    async fn remove_sandbox(&self, id: &str) -> Result<(), SandboxError> {
+        self.inner.remove_sandbox(id).await
+    }
+
+    async fn reset_sandbox(&self, id: &str) -> Result<PathBuf, SandboxError> {
+        self.reset.lock().unwrap().push(id.to_string());
+        self.inner.reset_sandbox(id).await
+    }
+
+    async fn execute(
+        &self,
+        box_id: &str,
+        argv: Vec<String>,
+        run_options: &RunOptions,
+    ) -> Result<ExecutionResult, SandboxError> {
+        self.inner.execute(box_id, argv, run_options).await
+    }
+}
+
+#[tokio::test]
+async fn pooled_sandbox_is_reused_without_leaking_files() {
+    let (metrics, _registry) = common::observability::init_metrics("broccoli-worker-test");
+    let created = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let reset = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let handler = OperationHandler::new(
+        Box::new(RecordingSandboxManager {
+            inner: MockSandboxManager::new(unique_mock_base_dir()),
+            created: Arc::clone(&created),
+            reset: Arc::clone(&reset),
+        }),
+        Box::new(UnavailableFileCacher::new("test storage unavailable")),
+        Box::new(NoopTaskCacheStore),
+        String::new(),
+        metrics,
+    )
+    .with_sandbox_pool_size(1);
+
+    let first = handler
+        .execute(&build_operation_task(
+            "mkdir -p nested && echo secret > leak.txt && echo secret > nested/leak.txt",
+        ))
+        .await
+        .unwrap();
+    assert!(first.success, "first job failed: {first:?}");
+
+    let second = handler
+        .execute(&build_operation_task(
+            "if [ -e leak.txt ] || [ -e nested ]; then echo dirty; else echo clean; fi",
+        ))
+        .await
+        .unwrap();
+    assert!(second.success, "second job failed: {second:?}");
+    assert_eq!(
+        second.task_results["step-1"].sandbox_result.stdout.trim(),
+        "clean",
+        "files from the first job must not be visible to the second"
+    );
+
+    let created = created.lock().unwrap().clone();
+    assert_eq!(created.len(), 1, "second job must not create a new box");
+    assert_eq!(
+        *reset.lock().unwrap(),
+        created,
+        "second job must reuse the first job's box"
+    );
+}
+
+#[tokio::test]
+async fn untrusted_env_rules_set_allowlisted_vars_and_drop_other_inherits() {
+    // SAFETY: the variable name is unique to this test and nothing else in
+    // the process reads it concurrently.
+    unsafe { std::env::set_var("BROCCOLI_TEST_WORKER_SECRET", "leaked") };
+
+    let mut operation = build_operation_task(
+        "echo \"seed=$PYTHONHASHSEED\"; echo \"secret=${BROCCOLI_TEST_WORKER_SECRET:-unset}\"",
+    );
+    operation.tasks[0].conf.env_rules = sanitize_untrusted_env_rules(&[
+        EnvRule::Set("PYTHONHASHSEED".into(), "0".into()),
+        EnvRule::Inherit("BROCCOLI_TEST_WORKER_SECRET".into()),
+    ]);
+
+    let (_result, operation_result) =
+        execute_operation_with_mock("task-env-rules", operation).await;
+    let stdout = &operation_result.task_results["step-1"]
+        .sandbox_result
+        .stdout;
+    assert!(stdout.contains("seed=0"), "stdout: {stdout}");
+    assert!(stdout.contains("secret=unset"), "stdout: {stdout}");
+}
+
+#[tokio::test]
+#[ignore = "requires a C++17 compiler available on PATH"]
+async fn execute_cpp_multi_file_compile_with_explicit_entrypoint() {
+    let compiler = cpp_compiler().expect("no C++ compiler found");
+
+    let main_cpp = "#include <iostream>\n#include \"helper.h\"\nint main() { std::cout << add(2, 40) << std::endl; return 0; }\n";
+    let helper_h = "#pragma once\nint add(int a, int b);\n";
+    let helper_cpp = "#include \"helper.h\"\nint add(int a, int b) { return a + b; }\n";
+
+    let operation = OperationTask {
+        environments: vec![Environment {
+            id: "env-1".to_string(),
+            files_in: vec![
+                (
+                    "main.cpp".to_string(),
+                    SessionFile::Content {
+                        content: main_cpp.to_string(),
+                    },
+                ),
+                (
+                    "helper.h".to_string(),
+                    SessionFile::Content {
+                        content: helper_h.to_string(),
+                    },
+                ),
+                (
+                    "helper.cpp".to_string(),
+                    SessionFile::Content {
+                        content: helper_cpp.to_string(),
+                    },
+                ),
+            ],
+        }],
+        tasks: vec![
+            Step {
+                id: "compile".to_string(),
+                env_ref: "env-1".to_string(),
+                // Same shape as the standard-languages resolver: entry point
+                // first, then every other source.
+                argv: vec![
+                    compiler,
+                    "-std=c++17".to_string(),
+                    "-O2".to_string(),
+                    "main.cpp".to_string(),
+                    "helper.cpp".to_string(),
+                    "-o".to_string(),
+                    "main".to_string(),
+                ],
+                conf: RunOptions::default(),
+                io: IOConfig::default(),
+                collect: vec![],
+                depends_on: vec![],
+                cache: None,
+            },
+            Step {
+                id: "run".to_string(),
+                env_ref: "env-1".to_string(),
+                argv: vec!["./main".to_string()],
+                conf: RunOptions::default(),
+                io: IOConfig::default(),
+                collect: vec![],
+                depends_on: vec!["compile".to_string()],
+                cache: None,
+            },
+        ],
+        channels: vec![],
+        priority: None,
+        target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
+    };
+
+    let (result, operation_result) =
+        execute_operation_with_mock("task-cpp-multi-file", operation).await;
+
+    assert!(result.success, "task result failed: {:?}", operation_result);
+    let compile_result = operation_result.task_results.get("compile").unwrap();
+    assert!(
+        compile_result.success,
+        "compile failed: {}",
+        compile_result.sandbox_result.stderr
+    );
+    let run_result = operation_result.task_results.get("run").unwrap();
+    assert_eq!(run_result.sandbox_result.exit_code, Some(0));
+    assert_eq!(run_result.sandbox_result.stdout.trim(), "42");
+}
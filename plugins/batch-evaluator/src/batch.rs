@@ -1,7 +1,7 @@
 use broccoli_server_sdk::types::{
     BuildEvalOpsInput, Environment, EvaluationTimeoutBudget, IOConfig, IOTarget, JudgeFile,
     OperationTask, OutputSpec, ResolveLanguageOutput, ResourceLimits, RunOptions, SessionFile,
-    Step, StepCacheConfig, seconds_from_ms,
+    StderrCapture, Step, StepCacheConfig, sanitize_untrusted_env_rules, seconds_from_ms,
 };
 use serde::Deserialize;
 use std::collections::HashSet;
@@ -24,8 +24,12 @@ pub struct SandboxConfig {
     pub exec_process_limit: u32,
     pub exec_open_files_limit: u32,
     pub exec_file_size_limit_kb: u32,
+    /// Total KB a run may write into the sandbox; 0 means no quota.
+    pub exec_disk_quota_kb: u32,
     pub exec_wall_time_multiplier: f64,
     pub result_timeout_ms: u64,
+    /// Which test cases keep the contestant program's stderr.
+    pub stderr_capture: StderrCapture,
 }
 
 impl Default for SandboxConfig {
@@ -44,14 +48,23 @@ impl Default for SandboxConfig {
             exec_process_limit: 1,
             exec_open_files_limit: 64,
             exec_file_size_limit_kb: 65_536, // 64 MB
+            exec_disk_quota_kb: 0,
             exec_wall_time_multiplier: 3.0,
             result_timeout_ms: EvaluationTimeoutBudget::default_for_time_limit_ms(0)
                 .minimum_timeout_ms,
+            stderr_capture: StderrCapture::Always,
         }
     }
 }
 
 impl SandboxConfig {
+    /// Applies the problem's own wall-time multiplier, if it set one.
+    pub fn apply_wall_time_multiplier(&mut self, multiplier: Option<f64>) {
+        if let Some(multiplier) = multiplier.filter(|m| m.is_finite() && *m >= 1.0) {
+            self.exec_wall_time_multiplier = multiplier;
+        }
+    }
+
     /// Build ResourceLimits for the compilation step.
     pub fn compile_limits(&self) -> ResourceLimits {
         ResourceLimits {
@@ -86,6 +99,7 @@ impl SandboxConfig {
             process_limit: Some(self.exec_process_limit),
             open_files_limit: Some(self.exec_open_files_limit),
             file_size_limit: Some(self.exec_file_size_limit_kb),
+            disk_quota: limit_if_positive(self.exec_disk_quota_kb),
             ..Default::default()
         }
     }
@@ -120,6 +134,8 @@ fn limit_if_positive(value: u32) -> Option<u32> {
 /// Build a sandbox OperationTask from enriched evaluator input.
 ///
 /// Returns `Vec<OperationTask>` ready for `host.operations.start_batch()`.
+/// In compile-only mode the operation holds just the compile step, and no
+/// operation is built at all for languages without one.
 pub fn build_operation(
     req: &BuildEvalOpsInput,
     lang: &ResolveLanguageOutput,
@@ -128,6 +144,10 @@ pub fn build_operation(
     if req.solution_source.is_empty() {
         return Err("No source file provided".into());
     }
+    let compile_only = req.judge_mode.is_compile_only();
+    if compile_only && lang.compile.is_none() {
+        return Ok(vec![]);
+    }
 
     let mut files_in = Vec::new();
     let mut seen_filenames = HashSet::new();
@@ -154,10 +174,12 @@ pub fn build_operation(
         }
     }
 
-    files_in.push((
-        "input.txt".to_string(),
-        session_file_from_judge_file(&req.test_input),
-    ));
+    if !compile_only {
+        files_in.push((
+            "input.txt".to_string(),
+            session_file_from_judge_file(&req.test_input),
+        ));
+    }
 
     let env = Environment {
         id: "sandbox".to_string(),
@@ -191,12 +213,9 @@ pub fn build_operation(
             env_ref: "sandbox".to_string(),
             argv: compile.command.clone(),
             conf: RunOptions {
-                resource_limits: compile
-                    .resource_limits
-                    .clone()
-                    .unwrap_or_else(|| config.compile_limits()),
+                resource_limits: compile.limits_or(|| config.compile_limits()),
                 wait: true,
-                env_rules: vec![],
+                env_rules: sanitize_untrusted_env_rules(&compile.env_rules),
                 ..Default::default()
             },
             io: IOConfig {
@@ -216,6 +235,18 @@ pub fn build_operation(
         steps.push(compile_step);
     }
 
+    if compile_only {
+        return Ok(vec![OperationTask {
+            environments: vec![env],
+            tasks: steps,
+            channels: vec![],
+            priority: None,
+            target_worker_id: req.target_worker_id.clone(),
+            contest_id: req.contest_id,
+            judge_job: req.judge_job,
+        }]);
+    }
+
     // Exec step
     let exec_depends = if lang.compile.is_some() {
         vec!["compile".to_string()]
@@ -230,7 +261,8 @@ pub fn build_operation(
         conf: RunOptions {
             resource_limits: config.exec_limits(time_limit_s, memory_limit_kb),
             wait: true,
-            env_rules: vec![],
+            env_rules: sanitize_untrusted_env_rules(&lang.run.env_rules),
+            stderr_capture: config.stderr_capture,
             ..Default::default()
         },
         io: IOConfig {
@@ -256,6 +288,8 @@ pub fn build_operation(
         channels: vec![],
         priority: None,
         target_worker_id: req.target_worker_id.clone(),
+        contest_id: req.contest_id,
+        judge_job: req.judge_job,
     };
 
     Ok(vec![op])
@@ -278,12 +312,15 @@ fn session_file_from_judge_file(file: &JudgeFile) -> SessionFile {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use broccoli_server_sdk::types::{CompileSpec, FileRef, JudgeFile, RunSpec, SourceFile};
+    use broccoli_server_sdk::types::{
+        CompileSpec, FileRef, JudgeFile, JudgeMode, RunSpec, SourceFile,
+    };
 
     fn make_req() -> BuildEvalOpsInput {
         BuildEvalOpsInput {
             problem_id: 1,
             test_case_id: 42,
+            test_case_label: None,
             solution_source: vec![SourceFile {
                 filename: "main.cpp".to_string(),
                 content: "int main() {}".to_string(),
@@ -291,6 +328,8 @@ mod tests {
             solution_language: "cpp".to_string(),
             time_limit_ms: 1000,
             memory_limit_kb: 262144,
+            wall_time_multiplier: None,
+            require_zero_exit: true,
             contest_id: None,
             test_input: JudgeFile::inline("hello\n"),
             expected_output: JudgeFile::inline("world\n"),
@@ -299,6 +338,8 @@ mod tests {
             checker_source: None,
             additional_file_refs: vec![],
             target_worker_id: None,
+            judge_mode: JudgeMode::Full,
+            judge_job: None,
         }
     }
 
@@ -315,10 +356,13 @@ mod tests {
                 cache_inputs: vec!["main.cpp".to_string(), "solution.cpp".to_string()],
                 outputs: vec![OutputSpec::File("solution".to_string())],
                 resource_limits: None,
+                time_limit: None,
+                env_rules: vec![],
             }),
             run: RunSpec {
                 command: vec!["./solution".to_string()],
                 extra_files: vec![],
+                env_rules: vec![],
             },
         }
     }
@@ -329,6 +373,7 @@ mod tests {
             run: RunSpec {
                 command: vec!["/usr/bin/python3".to_string(), "solution.py".to_string()],
                 extra_files: vec!["solution.py".to_string()],
+                env_rules: vec![],
             },
         }
     }
@@ -360,6 +405,37 @@ mod tests {
         assert!(tasks[0].depends_on.is_empty());
     }
 
+    #[test]
+    fn compile_only_produces_only_compile_step() {
+        let req = BuildEvalOpsInput {
+            judge_mode: JudgeMode::CompileOnly,
+            ..make_req()
+        };
+        let ops = build_operation(&req, &compiled_lang(), &default_config()).unwrap();
+
+        assert_eq!(ops.len(), 1);
+        let tasks = &ops[0].tasks;
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, "compile");
+        assert!(
+            !ops[0].environments[0]
+                .files_in
+                .iter()
+                .any(|(name, _)| name == "input.txt")
+        );
+    }
+
+    #[test]
+    fn compile_only_interpreted_language_builds_no_operation() {
+        let req = BuildEvalOpsInput {
+            judge_mode: JudgeMode::CompileOnly,
+            ..make_req()
+        };
+        let ops = build_operation(&req, &interpreted_lang(), &default_config()).unwrap();
+
+        assert!(ops.is_empty());
+    }
+
     #[test]
     fn test_input_wired_to_environment_files() {
         let ops = build_operation(&make_req(), &compiled_lang(), &default_config()).unwrap();
@@ -481,6 +557,21 @@ mod tests {
         assert!(exec.collect.contains(&"stderr.txt".to_string()));
     }
 
+    #[test]
+    fn exec_step_uses_configured_stderr_capture() {
+        let config = SandboxConfig {
+            stderr_capture: StderrCapture::OnFailure,
+            ..SandboxConfig::default()
+        };
+        let ops = build_operation(&make_req(), &compiled_lang(), &config).unwrap();
+
+        assert_eq!(ops[0].tasks[0].conf.stderr_capture, StderrCapture::Always);
+        assert_eq!(
+            ops[0].tasks[1].conf.stderr_capture,
+            StderrCapture::OnFailure
+        );
+    }
+
     #[test]
     fn negative_memory_limit_returns_error() {
         let mut req = make_req();
@@ -518,6 +609,20 @@ mod tests {
         assert_eq!(exec.conf.resource_limits.wall_time_limit, Some(5.0));
     }
 
+    #[test]
+    fn problem_wall_time_multiplier_overrides_config() {
+        let mut config = SandboxConfig::default();
+        config.apply_wall_time_multiplier(Some(4.0));
+        let ops = build_operation(&make_req(), &compiled_lang(), &config).unwrap();
+
+        let exec = &ops[0].tasks[1];
+        assert_eq!(exec.conf.resource_limits.time_limit, Some(1.0));
+        assert_eq!(exec.conf.resource_limits.wall_time_limit, Some(4.0));
+
+        config.apply_wall_time_multiplier(None);
+        assert_eq!(config.exec_wall_time_multiplier, 4.0);
+    }
+
     #[test]
     fn compile_limits_use_configured_stack_limit() {
         let config = SandboxConfig {
@@ -530,6 +635,17 @@ mod tests {
         assert_eq!(compile.conf.resource_limits.stack_limit, Some(262_144));
     }
 
+    #[test]
+    fn language_compile_time_limit_overrides_default() {
+        let mut lang = compiled_lang();
+        lang.compile.as_mut().unwrap().time_limit = Some(5.0);
+        let ops = build_operation(&make_req(), &lang, &SandboxConfig::default()).unwrap();
+
+        let limits = &ops[0].tasks[0].conf.resource_limits;
+        assert_eq!(limits.time_limit, Some(5.0));
+        assert_eq!(limits.wall_time_limit, Some(10.0));
+    }
+
     #[test]
     fn exec_limits_use_configured_stack_limit() {
         let config = SandboxConfig {
@@ -542,6 +658,24 @@ mod tests {
         assert_eq!(exec.conf.resource_limits.stack_limit, Some(262_144));
     }
 
+    #[test]
+    fn exec_limits_use_configured_disk_quota() {
+        let ops = build_operation(&make_req(), &compiled_lang(), &default_config()).unwrap();
+        assert_eq!(ops[0].tasks[1].conf.resource_limits.disk_quota, None);
+
+        let config = SandboxConfig {
+            exec_disk_quota_kb: 131_072,
+            ..SandboxConfig::default()
+        };
+        let ops = build_operation(&make_req(), &compiled_lang(), &config).unwrap();
+
+        assert_eq!(ops[0].tasks[0].conf.resource_limits.disk_quota, None);
+        assert_eq!(
+            ops[0].tasks[1].conf.resource_limits.disk_quota,
+            Some(131_072)
+        );
+    }
+
     #[test]
     fn compile_limits_use_configured_wall_time_multiplier_and_extra_time() {
         let config = SandboxConfig {
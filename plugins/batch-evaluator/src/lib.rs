@@ -1,7 +1,7 @@
 #[cfg(target_arch = "wasm32")]
 use broccoli_server_sdk::prelude::*;
 #[cfg(target_arch = "wasm32")]
-use broccoli_server_sdk::types::ResolveLanguageInput;
+use broccoli_server_sdk::types::{ConfigSource, ResolveLanguageInput};
 #[cfg(target_arch = "wasm32")]
 use extism_pdk::{FnResult, plugin_fn};
 
@@ -15,12 +15,48 @@ fn load_sandbox_config(host: &Host) -> batch::SandboxConfig {
     }
 }
 
+/// Applies the problem's `output.stderr_capture` override, if it set one.
+#[cfg(target_arch = "wasm32")]
+fn apply_problem_output_config(
+    host: &Host,
+    config: &mut batch::SandboxConfig,
+    problem_id: i32,
+    contest_id: Option<i32>,
+) {
+    let Ok(effective) = host.config.get_effective("output", problem_id, contest_id) else {
+        return;
+    };
+    if matches!(
+        effective.source,
+        ConfigSource::Default | ConfigSource::Disabled
+    ) {
+        return;
+    }
+    if let Some(capture) = effective
+        .config
+        .get("stderr_capture")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+    {
+        config.stderr_capture = capture;
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 #[plugin_fn]
 pub fn init() -> FnResult<String> {
     let host = Host::new();
     host.registry
         .register_evaluator("batch", "evaluate_batch")?;
+    host.registry
+        .register_evaluator("output_only", "evaluate_output_only")?;
+    host.registry.register_language_resolver_for_problem_types(
+        "output",
+        "resolve_output_language",
+        "Output files",
+        "output.txt",
+        &["out", "txt"],
+        &["output_only"],
+    )?;
     host.log.info("Batch evaluator registered")?;
     Ok("ok".to_string())
 }
@@ -32,7 +68,9 @@ pub fn evaluate_batch(input: String) -> FnResult<String> {
     let req: BuildEvalOpsInput = serde_json::from_str(&input)?;
     let tc_id = req.test_case_id;
 
-    let sandbox_config = load_sandbox_config(&host);
+    let mut sandbox_config = load_sandbox_config(&host);
+    apply_problem_output_config(&host, &mut sandbox_config, req.problem_id, req.contest_id);
+    sandbox_config.apply_wall_time_multiplier(req.wall_time_multiplier);
 
     let additional_filenames: std::collections::HashSet<&str> = req
         .additional_file_refs
@@ -60,6 +98,17 @@ pub fn evaluate_batch(input: String) -> FnResult<String> {
     let operations = batch::build_operation(&req, &resolved, &sandbox_config)
         .map_err(|e| extism_pdk::Error::msg(format!("{e}")))?;
 
+    if req.judge_mode.is_compile_only() && operations.is_empty() {
+        let verdict = evaluator::interpret_compile_result(
+            tc_id,
+            &OperationResult {
+                success: true,
+                ..Default::default()
+            },
+        );
+        return Ok(serde_json::to_string(&verdict)?);
+    }
+
     let result_timeout_ms = sandbox_config
         .result_timeout_ms_for(req.time_limit_ms, u32::from(resolved.compile.is_some()));
 
@@ -73,6 +122,11 @@ pub fn evaluate_batch(input: String) -> FnResult<String> {
         .next_result(&batch_id, result_timeout_ms)
         .map_err(|e| extism_pdk::Error::msg(format!("{e}")))?;
 
+    if req.judge_mode.is_compile_only() {
+        let verdict = evaluator::interpret_compile_result(tc_id, &result);
+        return Ok(serde_json::to_string(&verdict)?);
+    }
+
     let checker_format = req.checker_format.as_deref().unwrap_or("exact");
     let checker_input = CheckerParseInput {
         stdout: JudgeFile::Missing,
@@ -89,8 +143,32 @@ pub fn evaluate_batch(input: String) -> FnResult<String> {
         &result,
         checker_format,
         &checker_input,
+        req.require_zero_exit,
+        sandbox_config.stderr_capture,
     )
     .map_err(|e| extism_pdk::Error::msg(format!("{e}")))?;
 
     Ok(serde_json::to_string(&verdict)?)
 }
+
+/// Output-only problems: the submitted file is checked against the expected
+/// output directly, with no compile or run step.
+#[cfg(target_arch = "wasm32")]
+#[plugin_fn]
+pub fn evaluate_output_only(input: String) -> FnResult<String> {
+    let host = Host::new();
+    let req: BuildEvalOpsInput = serde_json::from_str(&input)?;
+    let verdict = evaluator::evaluate_output_only(&host.checker, &req);
+    Ok(serde_json::to_string(&verdict)?)
+}
+
+/// The `output` language only exists so output files can be submitted;
+/// `evaluate_output_only` never resolves it into a command.
+#[cfg(target_arch = "wasm32")]
+#[plugin_fn]
+pub fn resolve_output_language(_input: String) -> FnResult<String> {
+    Err(
+        extism_pdk::Error::msg("Output files are checked directly and cannot be compiled or run")
+            .into(),
+    )
+}
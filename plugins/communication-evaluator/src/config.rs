@@ -122,6 +122,8 @@ pub struct SandboxConfig {
     pub exec_process_limit: u32,
     pub exec_open_files_limit: u32,
     pub exec_file_size_limit_kb: u32,
+    /// Total KB a contestant run may write into the sandbox; 0 means no quota.
+    pub exec_disk_quota_kb: u32,
     pub result_timeout_ms: u64,
 }
 
@@ -140,6 +142,7 @@ impl Default for SandboxConfig {
             exec_process_limit: 1,
             exec_open_files_limit: 64,
             exec_file_size_limit_kb: 65_536,
+            exec_disk_quota_kb: 0,
             result_timeout_ms: EvaluationTimeoutBudget::default_for_time_limit_ms(0)
                 .minimum_timeout_ms,
         }
@@ -147,6 +150,13 @@ impl Default for SandboxConfig {
 }
 
 impl SandboxConfig {
+    /// Applies the problem's own wall-time multiplier, if it set one.
+    pub fn apply_wall_time_multiplier(&mut self, multiplier: Option<f64>) {
+        if let Some(multiplier) = multiplier.filter(|m| m.is_finite() && *m >= 1.0) {
+            self.exec_wall_time_multiplier = multiplier;
+        }
+    }
+
     pub fn compile_limits(&self) -> ResourceLimits {
         ResourceLimits {
             time_limit: Some(self.compile_time_limit_s),
@@ -177,6 +187,7 @@ impl SandboxConfig {
             process_limit: Some(self.exec_process_limit),
             open_files_limit: Some(self.exec_open_files_limit),
             file_size_limit: Some(self.exec_file_size_limit_kb),
+            disk_quota: (self.exec_disk_quota_kb > 0).then_some(self.exec_disk_quota_kb),
             ..Default::default()
         }
     }
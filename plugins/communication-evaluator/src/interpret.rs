@@ -1,4 +1,4 @@
-use broccoli_server_sdk::types::{OperationResult, TestCaseVerdict, Verdict};
+use broccoli_server_sdk::types::{OperationResult, TestCaseVerdict, Verdict, ms_from_seconds};
 
 /// Interpret the communication operation result into a TestCaseVerdict.
 pub fn interpret_result(
@@ -18,6 +18,7 @@ pub fn interpret_result(
             message: result.error.clone().or(Some("Operation failed".into())),
             stdout: None,
             stderr: None,
+            worker_id: None,
         };
     }
 
@@ -35,6 +36,7 @@ pub fn interpret_result(
                 )),
                 stdout: None,
                 stderr: None,
+                worker_id: None,
             };
         }
     }
@@ -55,6 +57,7 @@ pub fn interpret_result(
                     )),
                     stdout: None,
                     stderr: None,
+                    worker_id: None,
                 };
             }
         }
@@ -74,6 +77,7 @@ pub fn interpret_result(
                 )),
                 stdout: None,
                 stderr: None,
+                worker_id: None,
             };
         }
     }
@@ -100,7 +104,7 @@ pub fn interpret_result(
                         test_case_id,
                         verdict: Verdict::MemoryLimitExceeded,
                         score: 0.0,
-                        time_used_ms: time_to_ms(total_time_s),
+                        time_used_ms: ms_from_seconds(total_time_s),
                         memory_used_kb: max_memory_kb.map(|m| m as i64),
                         message: Some(format!(
                             "Memory limit exceeded (contestant {i}, {}KB)",
@@ -108,6 +112,7 @@ pub fn interpret_result(
                         )),
                         stdout: None,
                         stderr: opt_nonempty(&sandbox.stderr),
+                        worker_id: None,
                     };
                 }
 
@@ -117,11 +122,12 @@ pub fn interpret_result(
                             test_case_id,
                             verdict: Verdict::TimeLimitExceeded,
                             score: 0.0,
-                            time_used_ms: time_to_ms(total_time_s),
+                            time_used_ms: ms_from_seconds(total_time_s),
                             memory_used_kb: max_memory_kb.map(|m| m as i64),
                             message: Some(format!("Time limit exceeded (contestant {i})")),
                             stdout: None,
                             stderr: opt_nonempty(&sandbox.stderr),
+                            worker_id: None,
                         };
                     }
                     "SG" => {
@@ -129,7 +135,7 @@ pub fn interpret_result(
                             test_case_id,
                             verdict: Verdict::RuntimeError,
                             score: 0.0,
-                            time_used_ms: time_to_ms(total_time_s),
+                            time_used_ms: ms_from_seconds(total_time_s),
                             memory_used_kb: max_memory_kb.map(|m| m as i64),
                             message: Some(format!(
                                 "Signal received (contestant {i}): {}",
@@ -137,6 +143,7 @@ pub fn interpret_result(
                             )),
                             stdout: None,
                             stderr: opt_nonempty(&sandbox.stderr),
+                            worker_id: None,
                         };
                     }
                     _ => {
@@ -145,7 +152,7 @@ pub fn interpret_result(
                             test_case_id,
                             verdict: Verdict::RuntimeError,
                             score: 0.0,
-                            time_used_ms: time_to_ms(total_time_s),
+                            time_used_ms: ms_from_seconds(total_time_s),
                             memory_used_kb: max_memory_kb.map(|m| m as i64),
                             message: Some(format!(
                                 "Runtime error (contestant {i}, exit code: {})",
@@ -153,6 +160,7 @@ pub fn interpret_result(
                             )),
                             stdout: None,
                             stderr: opt_nonempty(&sandbox.stderr),
+                            worker_id: None,
                         };
                     }
                 }
@@ -167,6 +175,7 @@ pub fn interpret_result(
                 message: Some(format!("Contestant {i} run step was skipped")),
                 stdout: None,
                 stderr: None,
+                worker_id: None,
             };
         }
     }
@@ -178,11 +187,12 @@ pub fn interpret_result(
                 test_case_id,
                 verdict: Verdict::SystemError,
                 score: 0.0,
-                time_used_ms: time_to_ms(total_time_s),
+                time_used_ms: ms_from_seconds(total_time_s),
                 memory_used_kb: max_memory_kb.map(|m| m as i64),
                 message: Some("Manager run step missing".into()),
                 stdout: None,
                 stderr: None,
+                worker_id: None,
             };
         }
     };
@@ -194,7 +204,7 @@ pub fn interpret_result(
             test_case_id,
             verdict: Verdict::SystemError,
             score: 0.0,
-            time_used_ms: time_to_ms(total_time_s),
+            time_used_ms: ms_from_seconds(total_time_s),
             memory_used_kb: max_memory_kb.map(|m| m as i64),
             message: Some(format!(
                 "Manager exited with code {} — {}",
@@ -203,6 +213,7 @@ pub fn interpret_result(
             )),
             stdout: None,
             stderr: opt_nonempty(&mgr_sandbox.stderr),
+            worker_id: None,
         };
     }
 
@@ -214,7 +225,7 @@ pub fn interpret_result(
                 test_case_id,
                 verdict: Verdict::SystemError,
                 score: 0.0,
-                time_used_ms: time_to_ms(total_time_s),
+                time_used_ms: ms_from_seconds(total_time_s),
                 memory_used_kb: max_memory_kb.map(|m| m as i64),
                 message: Some(format!(
                     "Manager stdout is not a valid score: '{}'",
@@ -222,6 +233,7 @@ pub fn interpret_result(
                 )),
                 stdout: opt_nonempty(&mgr_sandbox.stdout),
                 stderr: opt_nonempty(&mgr_sandbox.stderr),
+                worker_id: None,
             };
         }
     };
@@ -239,19 +251,12 @@ pub fn interpret_result(
         test_case_id,
         verdict,
         score: capped_score,
-        time_used_ms: time_to_ms(total_time_s),
+        time_used_ms: ms_from_seconds(total_time_s),
         memory_used_kb: max_memory_kb.map(|m| m as i64),
         message,
         stdout: opt_nonempty(&mgr_sandbox.stdout),
         stderr: opt_nonempty(&mgr_sandbox.stderr),
-    }
-}
-
-fn time_to_ms(secs: f64) -> Option<i64> {
-    if secs >= 0.0 && secs.is_finite() && secs < (i64::MAX as f64 / 1000.0) {
-        Some((secs * 1000.0) as i64)
-    } else {
-        None
+        worker_id: None,
     }
 }
 
@@ -339,6 +344,7 @@ mod tests {
                 task_result("run_contestant_0", true, ok_sandbox(0, 0.5, 4096)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -356,6 +362,7 @@ mod tests {
                 task_result("run_contestant_0", true, ok_sandbox(0, 0.3, 2048)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -372,6 +379,7 @@ mod tests {
                 task_result("run_contestant_0", true, ok_sandbox(0, 0.2, 1024)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -393,6 +401,7 @@ mod tests {
                 task_result("run_contestant_0", false, tle_sandbox),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -414,6 +423,7 @@ mod tests {
                 task_result("run_contestant_0", false, mle_sandbox),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -429,6 +439,7 @@ mod tests {
                 task_result("run_contestant_0", false, ok_sandbox(11, 0.1, 2048)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -447,6 +458,7 @@ mod tests {
                 task_result("compile_contestant_0", false, ce),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -463,6 +475,7 @@ mod tests {
             success: false,
             task_results: HashMap::from([task_result("compile_manager", false, ce)]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -482,6 +495,7 @@ mod tests {
                 task_result("run_contestant_0", true, ok_sandbox(0, 0.3, 2048)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -497,6 +511,7 @@ mod tests {
                 task_result("run_contestant_0", true, ok_sandbox(0, 0.3, 2048)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -513,6 +528,7 @@ mod tests {
                 task_result("run_contestant_0", true, ok_sandbox(0, 0.3, 2048)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -529,6 +545,7 @@ mod tests {
                 task_result("run_contestant_0", true, ok_sandbox(0, 0.3, 2048)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -545,6 +562,7 @@ mod tests {
                 task_result("run_contestant_1", true, ok_sandbox(0, 0.7, 4096)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 2, MEM_LIMIT);
@@ -564,6 +582,7 @@ mod tests {
                 task_result("run_contestant_0", true, ok_sandbox(0, 0.1, 1024)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -580,6 +599,7 @@ mod tests {
                 task_result("run_contestant_0", true, ok_sandbox(0, 0.1, 1024)),
             ]),
             error: None,
+            worker_id: None,
         };
 
         let verdict = interpret_result(42, &result, 1, MEM_LIMIT);
@@ -43,7 +43,8 @@ pub fn evaluate_communication(input: String) -> FnResult<String> {
     let tc_id = req.test_case_id;
     let problem_id = req.problem_id;
 
-    let sandbox_config = load_sandbox_config(&host);
+    let mut sandbox_config = load_sandbox_config(&host);
+    sandbox_config.apply_wall_time_multiplier(req.wall_time_multiplier);
     let comm_config = load_comm_config(&host, problem_id);
 
     let additional_filenames: std::collections::HashSet<&str> = req
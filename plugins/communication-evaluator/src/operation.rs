@@ -1,6 +1,7 @@
 use broccoli_server_sdk::types::{
     BuildEvalOpsInput, Channel, Environment, IOConfig, IOTarget, JudgeFile, OperationTask,
     OutputSpec, ResolveLanguageOutput, RunOptions, SessionFile, Step, StepCacheConfig,
+    sanitize_untrusted_env_rules,
 };
 
 use crate::config::{CommConfig, CommunicationMode, ManagerSourceEntry, SandboxConfig};
@@ -122,10 +123,7 @@ pub fn build_operation(
             env_ref: "manager_env".to_string(),
             argv: compile.command.clone(),
             conf: RunOptions {
-                resource_limits: compile
-                    .resource_limits
-                    .clone()
-                    .unwrap_or_else(|| sandbox_config.compile_limits()),
+                resource_limits: compile.limits_or(|| sandbox_config.compile_limits()),
                 wait: true,
                 env_rules: vec![],
                 ..Default::default()
@@ -164,12 +162,9 @@ pub fn build_operation(
                 env_ref: format!("contestant_{i}"),
                 argv: compile.command.clone(),
                 conf: RunOptions {
-                    resource_limits: compile
-                        .resource_limits
-                        .clone()
-                        .unwrap_or_else(|| sandbox_config.compile_limits()),
+                    resource_limits: compile.limits_or(|| sandbox_config.compile_limits()),
                     wait: true,
-                    env_rules: vec![],
+                    env_rules: sanitize_untrusted_env_rules(&compile.env_rules),
                     ..Default::default()
                 },
                 io: IOConfig {
@@ -271,7 +266,7 @@ pub fn build_operation(
             conf: RunOptions {
                 resource_limits: sandbox_config.exec_limits(time_limit_s, memory_limit_kb),
                 wait: true,
-                env_rules: vec![],
+                env_rules: sanitize_untrusted_env_rules(&contestant_lang.run.env_rules),
                 ..Default::default()
             },
             io,
@@ -287,6 +282,8 @@ pub fn build_operation(
         channels,
         priority: None,
         target_worker_id: req.target_worker_id.clone(),
+        contest_id: req.contest_id,
+        judge_job: req.judge_job,
     }])
 }
 
@@ -307,12 +304,13 @@ fn session_file_from_judge_file(file: &JudgeFile) -> SessionFile {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use broccoli_server_sdk::types::{CompileSpec, RunSpec, SourceFile};
+    use broccoli_server_sdk::types::{CompileSpec, JudgeMode, RunSpec, SourceFile};
 
     fn make_req() -> BuildEvalOpsInput {
         BuildEvalOpsInput {
             problem_id: 1,
             test_case_id: 42,
+            test_case_label: None,
             solution_source: vec![SourceFile {
                 filename: "main.cpp".to_string(),
                 content: "int main() {}".to_string(),
@@ -320,6 +318,8 @@ mod tests {
             solution_language: "cpp".to_string(),
             time_limit_ms: 2000,
             memory_limit_kb: 262144,
+            wall_time_multiplier: None,
+            require_zero_exit: true,
             contest_id: None,
             test_input: JudgeFile::inline("5\n1 2 3 4 5\n"),
             expected_output: JudgeFile::Missing,
@@ -328,6 +328,8 @@ mod tests {
             checker_source: None,
             additional_file_refs: vec![],
             target_worker_id: None,
+            judge_mode: JudgeMode::Full,
+            judge_job: None,
         }
     }
 
@@ -344,10 +346,13 @@ mod tests {
                 cache_inputs: vec!["main.cpp".to_string()],
                 outputs: vec![OutputSpec::File("solution".to_string())],
                 resource_limits: None,
+                time_limit: None,
+                env_rules: vec![],
             }),
             run: RunSpec {
                 command: vec!["./solution".to_string()],
                 extra_files: vec![],
+                env_rules: vec![],
             },
         }
     }
@@ -365,10 +370,13 @@ mod tests {
                 cache_inputs: vec!["manager.cpp".to_string()],
                 outputs: vec![OutputSpec::File("manager".to_string())],
                 resource_limits: None,
+                time_limit: None,
+                env_rules: vec![],
             }),
             run: RunSpec {
                 command: vec!["./manager".to_string()],
                 extra_files: vec![],
+                env_rules: vec![],
             },
         }
     }
@@ -1,3 +1,4 @@
+use broccoli_server_sdk::types::Verdict;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -19,8 +20,9 @@ fn default_penalty_minutes() -> i32 {
     20
 }
 
-/// Per-user per-problem penalty tracking state, stored in plugin storage.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Per-user per-problem penalty state, replayed from the participant's
+/// judged submissions to the problem.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ProblemState {
     /// Number of penalty-eligible wrong submissions before AC.
     pub attempts: i32,
@@ -31,12 +33,25 @@ pub struct ProblemState {
     pub solve_time_ms: Option<i64>,
 }
 
-/// Build the plugin-storage key for a user's per-problem penalty state.
-pub fn standings_key(contest_id: i32, user_id: i32, problem_id: i32) -> String {
-    format!("standings:{contest_id}:{user_id}:{problem_id}")
-}
-
 impl ProblemState {
+    /// Folds in the next judged submission, made `elapsed_ms` after contest
+    /// start. System errors, compile errors unless `count_compile_error`,
+    /// and everything after the first accepted submission are not attempts.
+    pub fn record(&mut self, verdict: &Verdict, elapsed_ms: i64, count_compile_error: bool) {
+        if self.solved {
+            return;
+        }
+        match verdict {
+            Verdict::Accepted => {
+                self.solved = true;
+                self.solve_time_ms = Some(elapsed_ms.max(0));
+            }
+            Verdict::SystemError | Verdict::Skipped => {}
+            Verdict::CompileError if !count_compile_error => {}
+            _ => self.attempts += 1,
+        }
+    }
+
     /// Penalty time in minutes for this problem. 0 if unsolved.
     pub fn penalty_minutes(&self, penalty_per_attempt: i32) -> i32 {
         if !self.solved {
@@ -98,4 +113,44 @@ mod tests {
         // 0 minutes (truncated) + 0 penalties = 0
         assert_eq!(state.penalty_minutes(20), 0);
     }
+
+    fn replay(verdicts: &[Verdict], count_compile_error: bool) -> ProblemState {
+        let mut state = ProblemState::default();
+        for (i, verdict) in verdicts.iter().enumerate() {
+            state.record(verdict, (i as i64 + 1) * 60_000, count_compile_error);
+        }
+        state
+    }
+
+    #[test]
+    fn accepted_records_solve_time_after_wrong_attempts() {
+        let state = replay(&[Verdict::WrongAnswer, Verdict::Accepted], false);
+        assert!(state.solved);
+        assert_eq!(state.attempts, 1);
+        assert_eq!(state.solve_time_ms, Some(120_000));
+    }
+
+    #[test]
+    fn compile_errors_only_count_when_configured() {
+        assert_eq!(replay(&[Verdict::CompileError], false).attempts, 0);
+        assert_eq!(replay(&[Verdict::CompileError], true).attempts, 1);
+    }
+
+    #[test]
+    fn system_error_is_not_an_attempt() {
+        assert_eq!(
+            replay(&[Verdict::SystemError], false),
+            ProblemState::default()
+        );
+    }
+
+    #[test]
+    fn submissions_after_accept_are_ignored() {
+        let state = replay(
+            &[Verdict::Accepted, Verdict::WrongAnswer, Verdict::Accepted],
+            false,
+        );
+        assert_eq!(state.attempts, 0);
+        assert_eq!(state.solve_time_ms, Some(60_000));
+    }
 }
@@ -14,6 +14,7 @@ pub struct EvalOutcome {
     pub message: Option<String>,
     pub stdout: Option<String>,
     pub stderr: Option<String>,
+    pub worker_id: Option<String>,
 }
 
 /// Result of the evaluation phase.
@@ -56,6 +57,8 @@ pub fn evaluate_short_circuit(
                 expected_output: tc.expected_output.clone(),
                 is_custom: tc.is_custom,
                 target_worker_id: req.target_worker_id.clone(),
+                judge_mode: req.judge_mode,
+                judge_job: Some(req.judge_job()),
             })
             .collect(),
     };
@@ -81,6 +84,7 @@ pub fn evaluate_short_circuit(
                     message: Some(format!("BATCH_START_FAILED: {e:?}")),
                     stdout: None,
                     stderr: None,
+                    worker_id: None,
                 };
                 insert_tc_result(host, submission_id, req.judgement_id, &outcome, &tc_map)?;
                 outcomes.push(outcome);
@@ -132,6 +136,7 @@ pub fn evaluate_short_circuit(
                     message: verdict.message,
                     stdout: verdict.stdout,
                     stderr: verdict.stderr,
+                    worker_id: verdict.worker_id,
                 };
 
                 if outcome.verdict == Verdict::CompileError {
@@ -219,6 +224,7 @@ pub fn evaluate_short_circuit(
                     message: Some(fill_message.into()),
                     stdout: None,
                     stderr: None,
+                    worker_id: None,
                 });
             }
         }
@@ -262,6 +268,7 @@ fn test_submission(test_cases: Vec<TestCaseRow>) -> OnSubmissionInput {
         test_cases,
         judge_epoch: 1,
         target_worker_id: None,
+        judge_mode: JudgeMode::Full,
     }
 }
 
@@ -398,6 +405,7 @@ mod tests {
             message: Some("bad\0message".into()),
             stdout: Some("out\0put".into()),
             stderr: Some("err\0or".into()),
+            worker_id: None,
         });
 
         evaluate_short_circuit(&host, &req, &tcs, 1).unwrap();
@@ -13,11 +13,11 @@ use extism_pdk::{FnResult, plugin_fn};
 use serde::{Deserialize, Serialize};
 
 #[cfg(target_arch = "wasm32")]
-use crate::config::{ContestConfig, ProblemState, standings_key};
+use crate::config::{ContestConfig, ProblemState};
 #[cfg(target_arch = "wasm32")]
 use crate::evaluate::evaluate_short_circuit;
 #[cfg(target_arch = "wasm32")]
-use crate::persist::persist_and_track;
+use crate::persist::persist_result;
 
 // ── Plugin entry points ─────────────────────────────────────────────────
 
@@ -40,6 +40,21 @@ pub fn handle_icpc_submission(input: String) -> FnResult<String> {
     let host = Host::new();
     let req: OnSubmissionInput = serde_json::from_str(&input)?;
 
+    if req.judge_mode.is_compile_only() {
+        let output = match evaluator::compile_submission(&host, &req) {
+            Ok(out) => out,
+            Err(SdkError::StaleEpoch) => OnSubmissionOutput {
+                success: true,
+                error_message: None,
+            },
+            Err(e) => OnSubmissionOutput {
+                success: false,
+                error_message: Some(format!("{e:?}")),
+            },
+        };
+        return Ok(serde_json::to_string(&output)?);
+    }
+
     let output = match req.contest_id {
         None => run_standalone_judge(&host, &req),
         Some(contest_id) => {
@@ -47,7 +62,7 @@ pub fn handle_icpc_submission(input: String) -> FnResult<String> {
                 "ICPC: Judging submission {} for problem {} in contest {}",
                 req.submission_id, req.problem_id, contest_id
             ))?;
-            match run_judge(&host, &req, contest_id) {
+            match run_judge(&host, &req) {
                 Ok(out) => out,
                 Err(SdkError::StaleEpoch) => OnSubmissionOutput {
                     success: true,
@@ -75,13 +90,7 @@ pub fn handle_icpc_code_run(input: String) -> FnResult<String> {
 // ── Core judging logic ──────────────────────────────────────────────────
 
 #[cfg(target_arch = "wasm32")]
-fn run_judge(
-    host: &Host,
-    req: &OnSubmissionInput,
-    contest_id: i32,
-) -> Result<OnSubmissionOutput, SdkError> {
-    let contest_config: ContestConfig = contest::load_config(host, contest_id)?;
-
+fn run_judge(host: &Host, req: &OnSubmissionInput) -> Result<OnSubmissionOutput, SdkError> {
     let test_cases = req.test_cases.clone();
 
     if test_cases.is_empty() {
@@ -100,6 +109,8 @@ fn run_judge(
             compile_output: None,
             error_code: None,
             error_message: None,
+            judged_by: None,
+            subtasks: None,
         })?;
         if affected == 0 {
             return Err(SdkError::StaleEpoch);
@@ -125,16 +136,12 @@ fn run_judge(
         Err(e) => return Err(e),
     };
 
-    persist_and_track(
+    persist_result(
         host,
         req.submission_id,
         req.judgement_id,
         req.judge_epoch,
-        contest_id,
-        req.user_id,
-        req.problem_id,
         &eval,
-        contest_config.count_compile_error,
     )
 }
 
@@ -200,6 +207,8 @@ fn persist_empty_standalone(
         compile_output: None,
         error_code: None,
         error_message: None,
+        judged_by: None,
+        subtasks: None,
     })?;
 
     if affected == 0 {
@@ -261,6 +270,8 @@ fn persist_standalone(
         compile_output: Some(compile_output),
         error_code: None,
         error_message: None,
+        judged_by: None,
+        subtasks: None,
     })?;
 
     if affected == 0 {
@@ -321,6 +332,7 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
     let info = contest::check_access(host, req, contest_id)?;
     info.require_type("icpc")?;
     let config: ContestConfig = contest::load_config(host, contest_id)?;
+    let policy = standings::load_policy(host, req, contest_id)?;
 
     // Fetch contest problems in order
     #[derive(Deserialize)]
@@ -371,6 +383,17 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
     let user_filter = if is_restricted {
         match req.user_id() {
             Some(uid) => format!(" AND cu.user_id = {}", p.bind(uid)),
+            None if standings::wants_table(req) => {
+                let table = standings::StandingsTable {
+                    labels: problem_labels,
+                    rows: Vec::new(),
+                };
+                return Ok(PluginHttpResponse {
+                    status: 200,
+                    headers: None,
+                    body: Some(serde_json::to_value(table)?),
+                });
+            }
             None => {
                 return Ok(PluginHttpResponse {
                     status: 200,
@@ -392,22 +415,64 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
          FROM contest_user cu \
          JOIN \"user\" u ON u.id = cu.user_id \
          WHERE cu.contest_id = {}{user_filter} \
-         ORDER BY cu.registered_at ASC",
+         ORDER BY cu.registered_at ASC, cu.user_id ASC",
         p.bind(contest_id)
     );
     let participants: Vec<Participant> = host.db.query_with_args(&sql, &p.into_args())?;
 
-    // Bulk-fetch all standings keys
-    let all_keys: Vec<String> = participants
-        .iter()
-        .flat_map(|p| {
-            problem_ids
+    // Replay judged submissions into per-user, per-problem penalty state
+    #[derive(Deserialize)]
+    struct JudgedSubmission {
+        user_id: i32,
+        problem_id: i32,
+        verdict: Option<Verdict>,
+        elapsed_ms: i64,
+    }
+    let mut p = Params::new();
+    let sql = format!(
+        "SELECT s.user_id, s.problem_id, \
+                CASE WHEN s.status = 'CompilationError' THEN 'CompileError' \
+                     ELSE s.verdict END AS verdict, \
+                (EXTRACT(EPOCH FROM (s.created_at - c.start_time)) * 1000)::bigint \
+                  AS elapsed_ms \
+         FROM submission s \
+         JOIN contest c ON c.id = s.contest_id \
+         WHERE s.contest_id = {} \
+           AND s.status IN ('Judged', 'CompilationError') \
+           AND s.user_id IN (SELECT user_id FROM contest_user WHERE contest_id = s.contest_id){} \
+         ORDER BY s.created_at ASC, s.id ASC",
+        p.bind(contest_id),
+        policy.submission_filter("s"),
+    );
+    let submissions: Vec<JudgedSubmission> = host.db.query_with_args(&sql, &p.into_args())?;
+    let mut all_states: HashMap<(i32, i32), ProblemState> = HashMap::new();
+    for submission in submissions {
+        if let Some(verdict) = &submission.verdict {
+            all_states
+                .entry((submission.user_id, submission.problem_id))
+                .or_default()
+                .record(verdict, submission.elapsed_ms, config.count_compile_error);
+        }
+    }
+
+    // Solve order over every participant, so dynamic points do not depend
+    // on which rows the viewer gets
+    let dynamic_points = if policy.dynamic_scoring {
+        standings::dynamic_solve_points(
+            all_states
                 .iter()
-                .map(move |&pid| standings_key(contest_id, p.user_id, pid))
-        })
-        .collect();
-    let key_refs: Vec<&str> = all_keys.iter().map(|s| s.as_str()).collect();
-    let all_states = host.storage.get(&key_refs)?;
+                .filter_map(|(&(user_id, pid), state)| {
+                    state.solve_time_ms.map(|ms| (user_id, pid, ms))
+                })
+                .collect(),
+        )
+    } else {
+        HashMap::new()
+    };
+
+    // Solved problems earn points under dynamic scoring or once any problem
+    // sets max_points; a problem without max_points is worth 1
+    let scored = policy.dynamic_scoring || !policy.max_points.is_empty();
 
     // Track first solve per problem for highlighting
     let mut first_solve_time: HashMap<i32, (i32, i64)> = HashMap::new(); // problem_id -> (user_id, solve_time_ms)
@@ -423,6 +488,10 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
         penalty: Option<i32>,
         #[serde(skip_serializing_if = "Option::is_none")]
         first_solve: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        points: Option<f64>,
+        #[serde(skip)]
+        solve_time_ms: Option<i64>,
     }
 
     #[derive(Serialize)]
@@ -432,21 +501,22 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
         username: String,
         solved: i32,
         penalty: i32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        points: Option<f64>,
         problems: HashMap<String, ProblemCell>,
     }
 
     let mut entries: Vec<StandingsEntry> = Vec::new();
 
-    for participant in &participants {
+    for (position, participant) in participants.iter().enumerate() {
         let mut solved = 0;
         let mut total_penalty = 0;
+        let mut total_points = scored.then_some(0.0);
         let mut problem_cells = HashMap::new();
 
         for (i, &pid) in problem_ids.iter().enumerate() {
-            let key = standings_key(contest_id, participant.user_id, pid);
-            let state: ProblemState = all_states
-                .get(&key)
-                .and_then(|s| serde_json::from_str(s).ok())
+            let state = all_states
+                .remove(&(participant.user_id, pid))
                 .unwrap_or_default();
 
             let label = &problem_labels[i];
@@ -466,6 +536,14 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
                     *entry = (participant.user_id, solve_ms);
                 }
 
+                let points = if policy.dynamic_scoring {
+                    dynamic_points.get(&(participant.user_id, pid)).copied()
+                } else {
+                    scored.then(|| policy.scale_score(pid, 1.0, 1.0))
+                };
+                if let (Some(total), Some(points)) = (total_points.as_mut(), points) {
+                    *total += points;
+                }
                 problem_cells.insert(
                     label.clone(),
                     ProblemCell {
@@ -474,6 +552,8 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
                         time: Some(time_min),
                         penalty: Some(pen),
                         first_solve: None, // filled in second pass
+                        points,
+                        solve_time_ms: state.solve_time_ms,
                     },
                 );
             } else if state.attempts > 0 {
@@ -485,6 +565,8 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
                         time: None,
                         penalty: None,
                         first_solve: None,
+                        points: total_points.map(|_| 0.0),
+                        solve_time_ms: None,
                     },
                 );
             }
@@ -494,9 +576,10 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
         entries.push(StandingsEntry {
             rank: 0,
             user_id: participant.user_id,
-            username: participant.username.clone(),
+            username: policy.display_name(participant.user_id, &participant.username, position + 1),
             solved,
             penalty: total_penalty,
+            points: total_points,
             problems: problem_cells,
         });
     }
@@ -517,10 +600,12 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
         }
     }
 
-    // Sort: solved DESC, penalty ASC, username ASC
+    // Sort: points DESC (when scored), solved DESC, penalty ASC, username ASC
     entries.sort_by(|a, b| {
-        b.solved
-            .cmp(&a.solved)
+        b.points
+            .unwrap_or(0.0)
+            .total_cmp(&a.points.unwrap_or(0.0))
+            .then_with(|| b.solved.cmp(&a.solved))
             .then_with(|| a.penalty.cmp(&b.penalty))
             .then_with(|| a.username.cmp(&b.username))
     });
@@ -528,6 +613,7 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
     // Assign ranks (ties get same rank)
     for i in 0..entries.len() {
         if i > 0
+            && entries[i].points == entries[i - 1].points
             && entries[i].solved == entries[i - 1].solved
             && entries[i].penalty == entries[i - 1].penalty
         {
@@ -536,6 +622,42 @@ fn handle_standings(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpRe
             entries[i].rank = i + 1;
         }
     }
+    policy.truncate(&mut entries);
+
+    if standings::wants_table(req) {
+        let rows = entries
+            .iter()
+            .map(|entry| standings::StandingsTableRow {
+                rank: entry.rank,
+                user_id: entry.user_id,
+                username: entry.username.clone(),
+                cells: problem_labels
+                    .iter()
+                    .map(|label| match entry.problems.get(label) {
+                        Some(cell) => standings::StandingsTableCell {
+                            score: Some(cell.points.unwrap_or(if cell.solved { 1.0 } else { 0.0 })),
+                            attempts: u32::try_from(cell.attempts).unwrap_or(0),
+                            solve_time_ms: cell.solve_time_ms,
+                            penalty: cell.penalty.unwrap_or(0).into(),
+                        },
+                        None => standings::StandingsTableCell::default(),
+                    })
+                    .collect(),
+                total: entry.points.unwrap_or(f64::from(entry.solved)),
+                solved: usize::try_from(entry.solved).unwrap_or(0),
+                penalty: entry.penalty.into(),
+            })
+            .collect();
+        let table = standings::StandingsTable {
+            labels: problem_labels,
+            rows,
+        };
+        return Ok(PluginHttpResponse {
+            status: 200,
+            headers: None,
+            body: Some(serde_json::to_value(table)?),
+        });
+    }
 
     Ok(PluginHttpResponse {
         status: 200,
@@ -1,19 +1,15 @@
 use broccoli_server_sdk::prelude::*;
 
-use crate::config::{ProblemState, standings_key};
 use crate::evaluate::EvalResult;
 
-/// Persist the terminal submission update and update penalty tracking.
-pub fn persist_and_track(
+/// Persist the terminal submission update. The standings replay judged
+/// submissions, so nothing else needs tracking here.
+pub fn persist_result(
     host: &Host,
     submission_id: i32,
     judgement_id: i32,
     judge_epoch: i32,
-    contest_id: i32,
-    user_id: i32,
-    problem_id: i32,
     eval: &EvalResult,
-    count_compile_error: bool,
 ) -> Result<OnSubmissionOutput, SdkError> {
     let non_skipped: Vec<_> = eval
         .outcomes
@@ -31,7 +27,6 @@ pub fn persist_and_track(
     let max_memory = non_skipped.iter().filter_map(|o| o.memory_used).max();
 
     let is_ce = verdict == Verdict::CompileError;
-    let is_system_error = verdict == Verdict::SystemError;
     let status = if is_ce {
         SubmissionStatus::CompilationError
     } else {
@@ -51,6 +46,8 @@ pub fn persist_and_track(
     // ICPC: 1.0 for AC, 0.0 otherwise
     let score = if eval.is_accepted { 1.0 } else { 0.0 };
 
+    let judged_by = eval.outcomes.iter().find_map(|o| o.worker_id.clone());
+
     let affected = host.submission.update(&SubmissionUpdate {
         submission_id,
         judgement_id,
@@ -63,26 +60,14 @@ pub fn persist_and_track(
         compile_output: Some(compile_output),
         error_code: None,
         error_message: None,
+        judged_by: Some(judged_by),
+        subtasks: None,
     })?;
 
     if affected == 0 {
         return Err(SdkError::StaleEpoch);
     }
 
-    if is_system_error {
-        let _ = host.log.info(&format!(
-            "ICPC: Submission {} SystemError — not counted as attempt",
-            submission_id
-        ));
-    } else if is_ce && !count_compile_error {
-        let _ = host.log.info(&format!(
-            "ICPC: Submission {} CE — not counted as attempt",
-            submission_id
-        ));
-    } else {
-        update_penalty_state(host, contest_id, user_id, problem_id, eval.is_accepted)?;
-    }
-
     let _ = host.log.info(&format!(
         "ICPC: Submission {} judged: {:?}, accepted={}",
         submission_id, verdict, eval.is_accepted
@@ -113,6 +98,7 @@ fn eval_result(
                 message: None,
                 stdout: None,
                 stderr: None,
+                worker_id: None,
             })
             .collect(),
         is_compile_error,
@@ -120,246 +106,60 @@ fn eval_result(
     }
 }
 
-/// Atomically update the penalty state for a user-problem pair.
-fn update_penalty_state(
-    host: &Host,
-    contest_id: i32,
-    user_id: i32,
-    problem_id: i32,
-    is_accepted: bool,
-) -> Result<(), SdkError> {
-    let key = standings_key(contest_id, user_id, problem_id);
-
-    if is_accepted {
-        // Query elapsed time from contest start to now
-        let mut p = Params::new();
-        let sql = format!(
-            "SELECT EXTRACT(EPOCH FROM (NOW() - start_time)) * 1000 as elapsed_ms \
-             FROM contest WHERE id = {}",
-            p.bind(contest_id)
-        );
-        #[derive(serde::Deserialize)]
-        struct ElapsedMs {
-            elapsed_ms: Option<f64>,
-        }
-        let elapsed_ms = host
-            .db
-            .query_one_with_args::<ElapsedMs>(&sql, &p.into_args())?
-            .and_then(|r| r.elapsed_ms)
-            .unwrap_or(0.0)
-            .max(0.0) as i64;
-
-        host.storage.modify::<ProblemState, _>(&key, |state| {
-            if !state.solved {
-                state.solved = true;
-                state.solve_time_ms = Some(elapsed_ms);
-            }
-            // If already solved, don't update
-            Ok(())
-        })?;
-    } else {
-        host.storage.modify::<ProblemState, _>(&key, |state| {
-            if !state.solved {
-                state.attempts += 1;
-            }
-            // If already solved, ignore further wrong submissions
-            Ok(())
-        })?;
-    }
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::standings_key;
-    use serde_json::json;
 
-    const CONTEST_ID: i32 = 1;
-    const USER_ID: i32 = 10;
-    const PROBLEM_ID: i32 = 100;
     const SUBMISSION_ID: i32 = 1;
     const JUDGEMENT_ID: i32 = 1;
     const JUDGE_EPOCH: i32 = 1;
 
-    fn key() -> String {
-        standings_key(CONTEST_ID, USER_ID, PROBLEM_ID)
-    }
-
-    fn read_state(host: &Host) -> ProblemState {
-        host.storage
-            .get_one(&key())
-            .unwrap()
-            .map(|s| serde_json::from_str(&s).unwrap())
-            .unwrap_or_default()
-    }
-
-    /// Seed the DB mock to return a given elapsed_ms for the contest start-time query.
-    fn seed_elapsed_ms(host: &Host, ms: f64) {
-        host.db.queue_query_result(json!([{ "elapsed_ms": ms }]));
+    fn persist(host: &Host, eval: &EvalResult) -> OnSubmissionOutput {
+        persist_result(host, SUBMISSION_ID, JUDGEMENT_ID, JUDGE_EPOCH, eval).unwrap()
     }
 
     #[test]
-    fn accepted_sets_score_1_and_records_solve_time() {
+    fn accepted_sets_score_1() {
         let host = Host::mock();
-        seed_elapsed_ms(&host, 120_000.0); // 2 minutes into contest
         let eval = eval_result(vec![(1, Verdict::Accepted)], false, true);
 
-        let out = persist_and_track(
-            &host,
-            SUBMISSION_ID,
-            JUDGEMENT_ID,
-            JUDGE_EPOCH,
-            CONTEST_ID,
-            USER_ID,
-            PROBLEM_ID,
-            &eval,
-            false,
-        )
-        .unwrap();
-
-        assert!(out.success);
-        // Submission update should set score=1.0
+        assert!(persist(&host, &eval).success);
         let update = host.submission.last_update();
         assert_eq!(update.score, Some(1.0));
+        assert_eq!(update.status, Some(SubmissionStatus::Judged));
         assert_eq!(update.verdict, Some(Some(Verdict::Accepted)));
-        // Penalty state should record solved + solve_time
-        let state = read_state(&host);
-        assert!(state.solved);
-        assert_eq!(state.solve_time_ms, Some(120_000));
-        assert_eq!(state.attempts, 0);
     }
 
     #[test]
-    fn wrong_answer_sets_score_0_and_increments_attempts() {
+    fn wrong_answer_sets_score_0() {
         let host = Host::mock();
         let eval = eval_result(vec![(1, Verdict::WrongAnswer)], false, false);
 
-        let out = persist_and_track(
-            &host,
-            SUBMISSION_ID,
-            JUDGEMENT_ID,
-            JUDGE_EPOCH,
-            CONTEST_ID,
-            USER_ID,
-            PROBLEM_ID,
-            &eval,
-            false,
-        )
-        .unwrap();
-
-        assert!(out.success);
+        assert!(persist(&host, &eval).success);
         let update = host.submission.last_update();
         assert_eq!(update.score, Some(0.0));
-        let state = read_state(&host);
-        assert!(!state.solved);
-        assert_eq!(state.attempts, 1);
+        assert_eq!(update.verdict, Some(Some(Verdict::WrongAnswer)));
     }
 
     #[test]
-    fn ce_without_counting_does_not_track_attempt() {
+    fn compile_error_is_stored_without_a_verdict() {
         let host = Host::mock();
         let eval = eval_result(vec![(1, Verdict::CompileError)], true, false);
 
-        persist_and_track(
-            &host,
-            SUBMISSION_ID,
-            JUDGEMENT_ID,
-            JUDGE_EPOCH,
-            CONTEST_ID,
-            USER_ID,
-            PROBLEM_ID,
-            &eval,
-            false, // count_compile_error = false
-        )
-        .unwrap();
-
-        // Penalty state should be untouched (no attempt recorded)
-        let state = read_state(&host);
-        assert_eq!(state.attempts, 0);
-        assert!(!state.solved);
-    }
-
-    #[test]
-    fn ce_with_counting_tracks_attempt() {
-        let host = Host::mock();
-        let eval = eval_result(vec![(1, Verdict::CompileError)], true, false);
-
-        persist_and_track(
-            &host,
-            SUBMISSION_ID,
-            JUDGEMENT_ID,
-            JUDGE_EPOCH,
-            CONTEST_ID,
-            USER_ID,
-            PROBLEM_ID,
-            &eval,
-            true, // count_compile_error = true
-        )
-        .unwrap();
-
-        let state = read_state(&host);
-        assert_eq!(state.attempts, 1);
-        assert!(!state.solved);
-    }
-
-    #[test]
-    fn already_solved_ignores_further_submissions() {
-        let host = Host::mock();
-        // Pre-seed: problem already solved at 60s with 2 prior wrong attempts
-        let prior = ProblemState {
-            attempts: 2,
-            solved: true,
-            solve_time_ms: Some(60_000),
-        };
-        host.storage
-            .set(&[(&key(), &serde_json::to_string(&prior).unwrap())])
-            .unwrap();
-
-        // Submit another WA
-        let eval = eval_result(vec![(1, Verdict::WrongAnswer)], false, false);
-        persist_and_track(
-            &host,
-            SUBMISSION_ID,
-            JUDGEMENT_ID,
-            JUDGE_EPOCH,
-            CONTEST_ID,
-            USER_ID,
-            PROBLEM_ID,
-            &eval,
-            false,
-        )
-        .unwrap();
-
-        // State should be unchanged. attempts still 2, solve_time still 60s
-        let state = read_state(&host);
-        assert!(state.solved);
-        assert_eq!(state.attempts, 2);
-        assert_eq!(state.solve_time_ms, Some(60_000));
+        persist(&host, &eval);
+        let update = host.submission.last_update();
+        assert_eq!(update.status, Some(SubmissionStatus::CompilationError));
+        assert_eq!(update.verdict, Some(None));
     }
 
     #[test]
-    fn system_error_does_not_track_attempt() {
+    fn system_error_keeps_its_verdict() {
         let host = Host::mock();
         let eval = eval_result(vec![(1, Verdict::SystemError)], false, false);
 
-        persist_and_track(
-            &host,
-            SUBMISSION_ID,
-            JUDGEMENT_ID,
-            JUDGE_EPOCH,
-            CONTEST_ID,
-            USER_ID,
-            PROBLEM_ID,
-            &eval,
-            false,
-        )
-        .unwrap();
-
-        // SystemError is a judge failure, should not penalize the contestant
-        let state = read_state(&host);
-        assert_eq!(state.attempts, 0);
-        assert!(!state.solved);
+        persist(&host, &eval);
+        let update = host.submission.last_update();
+        assert_eq!(update.status, Some(SubmissionStatus::Judged));
+        assert_eq!(update.verdict, Some(Some(Verdict::SystemError)));
     }
 }
@@ -264,7 +264,7 @@ function RankRow({
         className="py-1.5 px-3 border-b border-border text-center font-bold font-mono tabular-nums text-[14px]"
         style={{ width: 60 }}
       >
-        {entry.solved}
+        {entry.points ?? entry.solved}
       </td>
       <td
         className="py-1.5 px-3 border-b border-border text-center font-mono tabular-nums text-[13px] text-muted-foreground"
@@ -10,6 +10,7 @@ export interface ProblemCell {
   time?: number;
   penalty?: number;
   first_solve?: boolean;
+  points?: number;
 }
 
 export interface StandingsEntry {
@@ -18,6 +19,7 @@ export interface StandingsEntry {
   username: string;
   solved: number;
   penalty: number;
+  points?: number;
   problems: Record<string, ProblemCell>;
 }
 
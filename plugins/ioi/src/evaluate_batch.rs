@@ -15,6 +15,7 @@ pub struct EvalOutcome {
     pub message: Option<String>,
     pub stdout: Option<String>,
     pub stderr: Option<String>,
+    pub worker_id: Option<String>,
 }
 
 pub fn evaluate_all(
@@ -47,6 +48,8 @@ pub fn evaluate_all(
                 expected_output: tc.expected_output.clone(),
                 is_custom: tc.is_custom,
                 target_worker_id: req.target_worker_id.clone(),
+                judge_mode: req.judge_mode,
+                judge_job: Some(req.judge_job()),
             })
             .collect(),
     };
@@ -72,6 +75,7 @@ pub fn evaluate_all(
                     message: Some(format!("BATCH_START_FAILED: {e:?}")),
                     stdout: None,
                     stderr: None,
+                    worker_id: None,
                 };
                 insert_tc_result(
                     host,
@@ -131,6 +135,7 @@ pub fn evaluate_all(
                     message: verdict.message,
                     stdout: verdict.stdout,
                     stderr: verdict.stderr,
+                    worker_id: verdict.worker_id,
                 };
 
                 if outcome.verdict == Verdict::CompileError {
@@ -188,6 +193,7 @@ pub fn evaluate_all(
                     message: Some("EVALUATION_TIMEOUT".into()),
                     stdout: None,
                     stderr: None,
+                    worker_id: None,
                 };
                 insert_tc_result(
                     host,
@@ -206,6 +212,27 @@ pub fn evaluate_all(
     Ok(outcomes)
 }
 
+/// Replaces the stored rows for this judgement with one row per outcome.
+///
+/// Used after subtask scoring to rewrite the awarded scores while keeping
+/// every executed case's verdict.
+pub fn rewrite_results(
+    host: &Host,
+    submission_id: i32,
+    judgement_id: i32,
+    outcomes: &[EvalOutcome],
+    test_cases: &[TestCaseRow],
+    scale_score: impl Fn(f64, &TestCaseRow) -> f64,
+) -> Result<(), SdkError> {
+    let tc_map: HashMap<i32, &TestCaseRow> = test_cases.iter().map(|tc| (tc.id, tc)).collect();
+    let rows: Vec<TestCaseResultRow> = outcomes
+        .iter()
+        .map(|outcome| result_row(submission_id, judgement_id, outcome, &tc_map, &scale_score))
+        .collect();
+    host.submission
+        .replace_results(submission_id, judgement_id, &rows)
+}
+
 fn insert_tc_result(
     host: &Host,
     submission_id: i32,
@@ -214,6 +241,22 @@ fn insert_tc_result(
     tc_map: &HashMap<i32, &TestCaseRow>,
     scale_score: &impl Fn(f64, &TestCaseRow) -> f64,
 ) -> Result<(), SdkError> {
+    host.submission.insert_results(&[result_row(
+        submission_id,
+        judgement_id,
+        outcome,
+        tc_map,
+        scale_score,
+    )])
+}
+
+fn result_row(
+    submission_id: i32,
+    judgement_id: i32,
+    outcome: &EvalOutcome,
+    tc_map: &HashMap<i32, &TestCaseRow>,
+    scale_score: &impl Fn(f64, &TestCaseRow) -> f64,
+) -> TestCaseResultRow {
     let tc = tc_map.get(&outcome.test_case_id);
     let score = match tc {
         Some(tc) => scale_score(outcome.raw_score, tc),
@@ -225,7 +268,7 @@ fn insert_tc_result(
     } else {
         (Some(outcome.test_case_id), None)
     };
-    host.submission.insert_results(&[TestCaseResultRow {
+    TestCaseResultRow {
         submission_id,
         judgement_id,
         test_case_id: tc_id,
@@ -237,7 +280,7 @@ fn insert_tc_result(
         message: sanitize_optional_text(outcome.message.as_deref()),
         stdout: sanitize_optional_text(outcome.stdout.as_deref()),
         stderr: sanitize_optional_text(outcome.stderr.as_deref()),
-    }])
+    }
 }
 
 fn sanitize_optional_text(value: Option<&str>) -> Option<String> {
@@ -3,9 +3,9 @@ use std::collections::HashMap;
 use broccoli_server_sdk::prelude::*;
 
 use crate::config::{ContestConfig, SubtaskDef, TaskConfig, resolve_tc_label, round_score};
-use crate::evaluate_batch::evaluate_all;
+use crate::evaluate_batch::{evaluate_all, rewrite_results};
 use crate::persist::persist_results;
-use crate::subtasks::score_all_subtasks;
+use crate::subtasks::{score_all_subtasks, unawarded_labels};
 
 /// Context gathered from host functions, passed to pure judge logic.
 pub struct JudgeContext {
@@ -46,6 +46,8 @@ pub fn judge_with_context(
             compile_output: None,
             error_code: None,
             error_message: None,
+            judged_by: None,
+            subtasks: Some(vec![]),
         })?;
         if affected == 0 {
             return Err(SdkError::StaleEpoch);
@@ -60,28 +62,28 @@ pub fn judge_with_context(
         });
     }
 
-    let outcomes = match evaluate_all(host, req, &ctx.test_cases, ctx.submission_id, |raw, tc| {
-        round_score(raw * tc.score)
-    }) {
-        Ok(outcomes) => outcomes,
-        Err(SdkError::StaleEpoch) => {
-            // Submission was rejudged. This execution is stale. Stop gracefully
-            // without persisting anything (the new epoch's plugin will handle it).
-            let _ = host.log.info(&format!(
-                "Submission {} epoch {} is stale, stopping",
-                ctx.submission_id, req.judge_epoch
-            ));
-            return Ok(JudgeResult {
-                output: OnSubmissionOutput {
-                    success: true,
-                    error_message: None,
-                },
-                submission_score: None,
-                subtask_scores: None,
-            });
-        }
-        Err(e) => return Err(e),
-    };
+    let scale_score = |raw: f64, tc: &TestCaseRow| round_score(raw * tc.score);
+    let mut outcomes =
+        match evaluate_all(host, req, &ctx.test_cases, ctx.submission_id, scale_score) {
+            Ok(outcomes) => outcomes,
+            Err(SdkError::StaleEpoch) => {
+                // Submission was rejudged. This execution is stale. Stop gracefully
+                // without persisting anything (the new epoch's plugin will handle it).
+                let _ = host.log.info(&format!(
+                    "Submission {} epoch {} is stale, stopping",
+                    ctx.submission_id, req.judge_epoch
+                ));
+                return Ok(JudgeResult {
+                    output: OnSubmissionOutput {
+                        success: true,
+                        error_message: None,
+                    },
+                    submission_score: None,
+                    subtask_scores: None,
+                });
+            }
+            Err(e) => return Err(e),
+        };
 
     let id_to_label: HashMap<i32, String> = ctx
         .test_cases
@@ -101,8 +103,58 @@ pub fn judge_with_context(
     let subtask_results = score_all_subtasks(&ctx.subtask_defs, &ctx.test_cases, &tc_scores);
     let subtask_scores: Vec<f64> = subtask_results.iter().map(|r| r.score).collect();
 
+    // Cases in a failed all-or-nothing subtask keep their verdict but award
+    // no points, so a per-case row only carries points its subtask awarded.
+    let unawarded = unawarded_labels(&ctx.subtask_defs, &subtask_results);
+    let mut zeroed = false;
+    for outcome in &mut outcomes {
+        let is_unawarded = id_to_label
+            .get(&outcome.test_case_id)
+            .is_some_and(|label| unawarded.contains(label));
+        if is_unawarded && outcome.raw_score > 0.0 {
+            outcome.raw_score = 0.0;
+            zeroed = true;
+        }
+    }
+    if zeroed {
+        rewrite_results(
+            host,
+            ctx.submission_id,
+            req.judgement_id,
+            &outcomes,
+            &ctx.test_cases,
+            scale_score,
+        )?;
+    }
+
     let submission_score = round_score(subtask_scores.iter().sum());
 
+    let tc_verdicts: HashMap<&str, &Verdict> = outcomes
+        .iter()
+        .filter(|o| !o.verdict.is_skipped())
+        .filter_map(|o| {
+            id_to_label
+                .get(&o.test_case_id)
+                .map(|label| (label.as_str(), &o.verdict))
+        })
+        .collect();
+    let subtasks = ctx
+        .subtask_defs
+        .iter()
+        .zip(subtask_results)
+        .map(|(def, result)| SubtaskScore {
+            name: result.name,
+            score: result.score,
+            max_score: result.max_score,
+            verdict: def
+                .test_cases
+                .iter()
+                .filter_map(|label| tc_verdicts.get(label.as_str()))
+                .max_by_key(|v| v.severity())
+                .map(|v| (*v).clone()),
+        })
+        .collect();
+
     let output = persist_results(
         host,
         ctx.submission_id,
@@ -110,6 +162,7 @@ pub fn judge_with_context(
         req.judge_epoch,
         &outcomes,
         submission_score,
+        subtasks,
     )?;
 
     Ok(JudgeResult {
@@ -142,6 +195,7 @@ mod tests {
             test_cases: vec![],
             judge_epoch: 0,
             target_worker_id: None,
+            judge_mode: JudgeMode::Full,
         }
     }
 
@@ -423,6 +477,7 @@ mod tests {
             message: Some("bad\0message".into()),
             stdout: Some("out\0put".into()),
             stderr: Some("err\0or".into()),
+            worker_id: None,
         });
 
         let tcs = vec![TestCaseRow {
@@ -519,6 +574,7 @@ mod tests {
             message: None,
             stdout: None,
             stderr: None,
+            worker_id: None,
         });
 
         let tcs = vec![
@@ -560,6 +616,146 @@ mod tests {
         assert_eq!(result.submission_score, Some(0.0));
     }
 
+    #[test]
+    fn failed_group_min_subtask_records_verdicts_with_zero_scores() {
+        let host = Host::mock();
+        for id in 1..=3 {
+            host.submission.add_test_case(id, 20.0);
+        }
+        host.eval.queue_result(TestCaseVerdict::accepted(1));
+        host.eval.queue_result(TestCaseVerdict {
+            test_case_id: 2,
+            verdict: Verdict::WrongAnswer,
+            score: 0.0,
+            time_used_ms: Some(15),
+            memory_used_kb: None,
+            message: Some("expected 3, found 4".into()),
+            stdout: None,
+            stderr: None,
+            worker_id: None,
+        });
+        host.eval.queue_result(TestCaseVerdict::accepted(3));
+
+        let tcs: Vec<TestCaseRow> = (1..=3)
+            .map(|id| TestCaseRow {
+                id,
+                score: 20.0,
+                is_sample: false,
+                position: id - 1,
+                description: None,
+                label: Some(id.to_string()),
+                input: TestCaseBodyRef::Missing,
+                expected_output: TestCaseBodyRef::Missing,
+                is_custom: false,
+            })
+            .collect();
+        let ctx = explicit_subtask_ctx(
+            tcs,
+            vec![
+                SubtaskDef {
+                    name: "Small".into(),
+                    scoring_method: SubtaskScoringMethod::GroupMin,
+                    max_score: 40.0,
+                    test_cases: vec!["1".into(), "2".into()],
+                },
+                SubtaskDef {
+                    name: "Large".into(),
+                    scoring_method: SubtaskScoringMethod::GroupMin,
+                    max_score: 60.0,
+                    test_cases: vec!["3".into()],
+                },
+            ],
+        );
+
+        let result = judge_with_context(&host, &sample_input(), &ctx).unwrap();
+        assert_eq!(result.subtask_scores, Some(vec![0.0, 60.0]));
+        assert_eq!(result.submission_score, Some(60.0));
+
+        let mut rows = host.submission.results();
+        rows.sort_by_key(|r| r.test_case_id);
+        let summary: Vec<(Option<i32>, Verdict, f64)> = rows
+            .iter()
+            .map(|r| (r.test_case_id, r.verdict.clone(), r.score))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some(1), Verdict::Accepted, 0.0),
+                (Some(2), Verdict::WrongAnswer, 0.0),
+                (Some(3), Verdict::Accepted, 20.0),
+            ]
+        );
+        assert_eq!(rows[1].message.as_deref(), Some("expected 3, found 4"));
+        assert_eq!(rows[1].time_used, Some(15));
+    }
+
+    #[test]
+    fn three_subtasks_persist_per_subtask_breakdown() {
+        let host = Host::mock();
+        for id in 1..=5 {
+            host.submission.add_test_case(id, 20.0);
+        }
+        host.eval.queue_result(TestCaseVerdict::accepted(1));
+        host.eval.queue_result(TestCaseVerdict::accepted(2));
+        host.eval.queue_result(TestCaseVerdict::accepted(3));
+        host.eval.queue_result(TestCaseVerdict::wrong_answer(4));
+        host.eval.queue_result(TestCaseVerdict::accepted(5));
+
+        let tcs: Vec<TestCaseRow> = (1..=5)
+            .map(|id| TestCaseRow {
+                id,
+                score: 20.0,
+                is_sample: false,
+                position: id - 1,
+                description: None,
+                label: Some(id.to_string()),
+                input: TestCaseBodyRef::Missing,
+                expected_output: TestCaseBodyRef::Missing,
+                is_custom: false,
+            })
+            .collect();
+        let ctx = explicit_subtask_ctx(
+            tcs,
+            vec![
+                SubtaskDef {
+                    name: "Small".into(),
+                    scoring_method: SubtaskScoringMethod::GroupMin,
+                    max_score: 20.0,
+                    test_cases: vec!["1".into()],
+                },
+                SubtaskDef {
+                    name: "Medium".into(),
+                    scoring_method: SubtaskScoringMethod::GroupMin,
+                    max_score: 30.0,
+                    test_cases: vec!["2".into(), "3".into()],
+                },
+                SubtaskDef {
+                    name: "Large".into(),
+                    scoring_method: SubtaskScoringMethod::Sum,
+                    max_score: 50.0,
+                    test_cases: vec!["4".into(), "5".into()],
+                },
+            ],
+        );
+
+        let result = judge_with_context(&host, &sample_input(), &ctx).unwrap();
+        assert_eq!(result.submission_score, Some(75.0));
+
+        let subtasks = host.submission.last_update().subtasks.unwrap();
+        let summary: Vec<(&str, f64, f64, Option<Verdict>)> = subtasks
+            .iter()
+            .map(|s| (s.name.as_str(), s.score, s.max_score, s.verdict.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Small", 20.0, 20.0, Some(Verdict::Accepted)),
+                ("Medium", 30.0, 30.0, Some(Verdict::Accepted)),
+                ("Large", 25.0, 50.0, Some(Verdict::WrongAnswer)),
+            ]
+        );
+    }
+
     #[test]
     fn group_mul_subtask() {
         let host = Host::mock();
@@ -574,6 +770,7 @@ mod tests {
             message: None,
             stdout: None,
             stderr: None,
+            worker_id: None,
         });
         host.eval.queue_result(TestCaseVerdict {
             test_case_id: 2,
@@ -584,6 +781,7 @@ mod tests {
             message: None,
             stdout: None,
             stderr: None,
+            worker_id: None,
         });
 
         let tcs = vec![
@@ -718,6 +916,7 @@ mod tests {
             message: None,
             stdout: None,
             stderr: None,
+            worker_id: None,
         });
         host.eval.queue_result(TestCaseVerdict::accepted(2));
 
@@ -345,6 +345,21 @@ pub fn handle_ioi_submission(input: String) -> FnResult<String> {
     let host = Host::new();
     let req: OnSubmissionInput = serde_json::from_str(&input)?;
 
+    if req.judge_mode.is_compile_only() {
+        let output = match evaluator::compile_submission(&host, &req) {
+            Ok(out) => out,
+            Err(SdkError::StaleEpoch) => OnSubmissionOutput {
+                success: true,
+                error_message: None,
+            },
+            Err(e) => OnSubmissionOutput {
+                success: false,
+                error_message: Some(format!("{e:?}")),
+            },
+        };
+        return Ok(serde_json::to_string(&output)?);
+    }
+
     let output = match req.contest_id {
         None => OnSubmissionOutput {
             success: false,
@@ -481,6 +496,11 @@ fn redact_submission_for_level(submission: &mut serde_json::Value, level: Feedba
                     }
                 }
             }
+            if level == FeedbackLevel::TotalOnly
+                && let Some(obj) = submission.get_mut("result").and_then(|r| r.as_object_mut())
+            {
+                obj.insert("subtasks".into(), Value::Array(vec![]));
+            }
         }
         FeedbackLevel::None => {
             if in_list {
@@ -501,6 +521,7 @@ fn redact_submission_for_level(submission: &mut serde_json::Value, level: Feedba
                 obj.insert("compile_output".into(), Value::Null);
                 obj.insert("error_message".into(), Value::Null);
                 obj.insert("test_case_results".into(), Value::Array(vec![]));
+                obj.insert("subtasks".into(), Value::Array(vec![]));
             }
         }
     }
@@ -717,6 +738,7 @@ fn load_token_state(host: &Host, contest_id: i32, user_id: i32) -> Result<TokenS
 #[cfg(target_arch = "wasm32")]
 fn load_max_submission_scoreboard_cells(
     host: &Host,
+    policy: &standings::StandingsPolicy,
     contest_id: i32,
     user_ids: &[i32],
     problem_ids: &[i32],
@@ -730,9 +752,22 @@ fn load_max_submission_scoreboard_cells(
     let user_placeholders: Vec<String> = user_ids.iter().map(|id| p.bind(*id)).collect();
     let problem_placeholders: Vec<String> = problem_ids.iter().map(|id| p.bind(*id)).collect();
     let score_epsilon_placeholder = p.bind(SCORE_EPSILON);
+    let selected = match policy.score_selection {
+        standings::ScoreSelection::Best => {
+            "SELECT user_id, problem_id, MAX(score) as score \
+             FROM scored \
+             GROUP BY user_id, problem_id"
+        }
+        standings::ScoreSelection::Last => {
+            "SELECT DISTINCT ON (user_id, problem_id) user_id, problem_id, score \
+             FROM scored \
+             ORDER BY user_id, problem_id, created_at DESC, id DESC"
+        }
+    };
     let sql = format!(
         "WITH scored AS ( \
-             SELECT s.user_id, s.problem_id, COALESCE(sj.score, s.score) as score, \
+             SELECT s.id, s.created_at, s.user_id, s.problem_id, \
+                    COALESCE(sj.score, s.score) as score, \
                     GREATEST(EXTRACT(EPOCH FROM (s.created_at - c.start_time))::bigint, 0) \
                       as elapsed_seconds \
              FROM submission s \
@@ -742,22 +777,19 @@ fn load_max_submission_scoreboard_cells(
              WHERE s.contest_id = {} \
                AND s.user_id IN ({}) \
                AND s.problem_id IN ({}) \
-               AND COALESCE(sj.score, s.score) IS NOT NULL \
-         ), maxes AS ( \
-             SELECT user_id, problem_id, MAX(score) as score \
-             FROM scored \
-             GROUP BY user_id, problem_id \
-         ) \
+               AND COALESCE(sj.score, s.score) IS NOT NULL{} \
+         ), selected AS ({selected}) \
          SELECT m.user_id, m.problem_id, m.score, \
                 COALESCE(MIN(s.elapsed_seconds) FILTER \
                     (WHERE m.score > 0.0 AND s.score >= m.score - {}), 0) \
                     as score_time_seconds \
-         FROM maxes m \
+         FROM selected m \
          JOIN scored s ON s.user_id = m.user_id AND s.problem_id = m.problem_id \
          GROUP BY m.user_id, m.problem_id, m.score",
         contest_placeholder,
         user_placeholders.join(","),
         problem_placeholders.join(","),
+        policy.submission_filter("s"),
         score_epsilon_placeholder,
     );
     let rows: Vec<MaxSubmissionScoreboardRow> = host.db.query_with_args(&sql, &p.into_args())?;
@@ -778,6 +810,7 @@ fn load_max_submission_scoreboard_cells(
 #[cfg(target_arch = "wasm32")]
 fn load_best_tokened_or_last_scoreboard_cells(
     host: &Host,
+    policy: &standings::StandingsPolicy,
     contest_id: i32,
     user_ids: &[i32],
     problem_ids: &[i32],
@@ -818,11 +851,12 @@ fn load_best_tokened_or_last_scoreboard_cells(
            ON sj.submission_id = s.id AND sj.is_current = TRUE \
          WHERE s.contest_id = {} \
            AND s.user_id IN ({}) \
-           AND s.problem_id IN ({}) \
+           AND s.problem_id IN ({}){} \
          ORDER BY s.user_id, s.problem_id, s.created_at DESC",
         contest_placeholder,
         user_placeholders.join(","),
         problem_placeholders.join(","),
+        policy.submission_filter("s"),
     );
     let last_rows: Vec<ScoreboardSubmissionRow> = host.db.query_with_args(&sql, &p.into_args())?;
 
@@ -849,11 +883,12 @@ fn load_best_tokened_or_last_scoreboard_cells(
              WHERE s.contest_id = {} \
                AND s.user_id IN ({}) \
                AND s.problem_id IN ({}) \
-               AND s.id IN ({})",
+               AND s.id IN ({}){}",
             contest_placeholder,
             user_placeholders.join(","),
             problem_placeholders.join(","),
             tokened_placeholders.join(","),
+            policy.submission_filter("s"),
         );
         host.db
             .query_with_args::<ScoreboardSubmissionRow>(&sql, &p.into_args())?
@@ -915,6 +950,7 @@ fn load_best_tokened_or_last_scoreboard_cells(
 #[cfg(target_arch = "wasm32")]
 fn load_sum_best_subtask_scoreboard_cells(
     host: &Host,
+    policy: &standings::StandingsPolicy,
     contest_id: i32,
     user_ids: &[i32],
     problem_ids: &[i32],
@@ -954,11 +990,12 @@ fn load_sum_best_subtask_scoreboard_cells(
            AND s.user_id IN ({}) \
            AND s.problem_id IN ({}) \
            AND tcr.test_case_id IS NOT NULL \
-           AND (tcr.judgement_id IS NULL OR (sj.is_current = TRUE AND sj.is_finalized = TRUE)) \
+           AND (tcr.judgement_id IS NULL OR (sj.is_current = TRUE AND sj.is_finalized = TRUE)){} \
          ORDER BY s.created_at ASC",
         contest_placeholder,
         user_placeholders.join(","),
         problem_placeholders.join(","),
+        policy.submission_filter("s"),
     );
     let rows: Vec<ScoreboardTcScoreRow> = host.db.query_with_args(&sql, &p.into_args())?;
 
@@ -1052,21 +1089,124 @@ fn load_sum_best_subtask_scoreboard_cells(
 fn load_scoreboard_cells(
     host: &Host,
     config: &ContestConfig,
+    policy: &standings::StandingsPolicy,
     contest_id: i32,
     user_ids: &[i32],
     problem_ids: &[i32],
 ) -> Result<HashMap<(i32, i32), ScoreboardCell>, SdkError> {
     match config.scoring_mode {
         ScoringMode::MaxSubmission => {
-            load_max_submission_scoreboard_cells(host, contest_id, user_ids, problem_ids)
+            load_max_submission_scoreboard_cells(host, policy, contest_id, user_ids, problem_ids)
         }
-        ScoringMode::SumBestSubtask => {
-            load_sum_best_subtask_scoreboard_cells(host, contest_id, user_ids, problem_ids)
+        // With `score_selection: Last`, the most recent submission's total
+        // counts, as in max-submission mode.
+        ScoringMode::SumBestSubtask
+            if policy.score_selection == standings::ScoreSelection::Last =>
+        {
+            load_max_submission_scoreboard_cells(host, policy, contest_id, user_ids, problem_ids)
         }
-        ScoringMode::BestTokenedOrLast => {
-            load_best_tokened_or_last_scoreboard_cells(host, contest_id, user_ids, problem_ids)
+        ScoringMode::SumBestSubtask => {
+            load_sum_best_subtask_scoreboard_cells(host, policy, contest_id, user_ids, problem_ids)
         }
+        ScoringMode::BestTokenedOrLast => load_best_tokened_or_last_scoreboard_cells(
+            host,
+            policy,
+            contest_id,
+            user_ids,
+            problem_ids,
+        ),
+    }
+}
+
+/// Dynamic points per `(user_id, problem_id)` for every participant's first
+/// accepted submission to a problem that the viewer may see.
+#[cfg(target_arch = "wasm32")]
+fn load_dynamic_points(
+    host: &Host,
+    policy: &standings::StandingsPolicy,
+    contest_id: i32,
+) -> Result<HashMap<(i32, i32), f64>, SdkError> {
+    #[derive(Deserialize)]
+    struct Solve {
+        user_id: i32,
+        problem_id: i32,
+        solved_at_ms: i64,
     }
+    let mut p = Params::new();
+    let sql = format!(
+        "SELECT s.user_id, s.problem_id, \
+                (EXTRACT(EPOCH FROM MIN(s.created_at)) * 1000)::bigint AS solved_at_ms \
+         FROM submission s \
+         WHERE s.contest_id = {} \
+           AND s.status = 'Judged' AND s.verdict = 'Accepted' \
+           AND s.user_id IN (SELECT user_id FROM contest_user WHERE contest_id = s.contest_id){} \
+         GROUP BY s.user_id, s.problem_id",
+        p.bind(contest_id),
+        policy.submission_filter("s"),
+    );
+    let solves: Vec<Solve> = host.db.query_with_args(&sql, &p.into_args())?;
+    Ok(standings::dynamic_solve_points(
+        solves
+            .into_iter()
+            .map(|s| (s.user_id, s.problem_id, s.solved_at_ms))
+            .collect(),
+    ))
+}
+
+/// Per `(user_id, problem_id)`: judged attempts up to and including the
+/// first accepted one, and milliseconds from contest start to that one.
+#[cfg(target_arch = "wasm32")]
+fn load_attempt_stats(
+    host: &Host,
+    policy: &standings::StandingsPolicy,
+    contest_id: i32,
+    user_ids: &[i32],
+) -> Result<HashMap<(i32, i32), (u32, Option<i64>)>, SdkError> {
+    if user_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    #[derive(Deserialize)]
+    struct Stats {
+        user_id: i32,
+        problem_id: i32,
+        attempts: i32,
+        solve_time_ms: Option<i64>,
+    }
+    let mut p = Params::new();
+    let contest_placeholder = p.bind(contest_id);
+    let user_placeholders: Vec<String> = user_ids.iter().map(|id| p.bind(*id)).collect();
+    let sql = format!(
+        "WITH judged AS ( \
+             SELECT s.user_id, s.problem_id, s.created_at, s.verdict, c.start_time \
+             FROM submission s \
+             JOIN contest c ON c.id = s.contest_id \
+             WHERE s.contest_id = {} AND s.status = 'Judged' \
+               AND s.user_id IN ({}){} \
+         ), firsts AS ( \
+             SELECT user_id, problem_id, \
+                    MIN(created_at) FILTER (WHERE verdict = 'Accepted') AS accepted_at \
+             FROM judged GROUP BY user_id, problem_id \
+         ) \
+         SELECT j.user_id, j.problem_id, \
+                COUNT(*) FILTER (WHERE f.accepted_at IS NULL OR j.created_at <= f.accepted_at)::int \
+                    AS attempts, \
+                (EXTRACT(EPOCH FROM (f.accepted_at - j.start_time)) * 1000)::bigint AS solve_time_ms \
+         FROM judged j \
+         JOIN firsts f ON f.user_id = j.user_id AND f.problem_id = j.problem_id \
+         GROUP BY j.user_id, j.problem_id, f.accepted_at, j.start_time",
+        contest_placeholder,
+        user_placeholders.join(", "),
+        policy.submission_filter("s"),
+    );
+    let rows: Vec<Stats> = host.db.query_with_args(&sql, &p.into_args())?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let attempts = u32::try_from(row.attempts).unwrap_or(0);
+            ((row.user_id, row.problem_id), (attempts, row.solve_time_ms))
+        })
+        .collect())
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -1347,26 +1487,35 @@ fn handle_submission_status(
         id: i32,
         verdict: Option<String>,
         score: Option<f64>,
+        withheld: bool,
     }
     let mut p = Params::new();
     let sql = format!(
-        "SELECT id, verdict, score FROM submission \
-         WHERE user_id = {} AND problem_id = {} AND contest_id = {} \
-         AND status = 'Judged' AND verdict IS NOT NULL \
-         ORDER BY created_at DESC LIMIT 1",
+        "SELECT s.id, s.verdict, s.score, {} AS withheld FROM submission s \
+         WHERE s.user_id = {} AND s.problem_id = {} AND s.contest_id = {} \
+         AND s.status = 'Judged' AND s.verdict IS NOT NULL \
+         ORDER BY s.created_at DESC LIMIT 1",
+        contest::results_withheld("s"),
         p.bind(user_id),
         p.bind(problem_id),
         p.bind(contest_id)
     );
+    let privileged = can_view_privileged_submission_feedback(req);
     let (last_submission_id, last_verdict, last_score) = host
         .db
         .query_one_with_args::<LastVerdict>(&sql, &p.into_args())?
-        .map(|r| (Some(r.id), r.verdict, r.score))
+        .map(|r| {
+            if r.withheld && !privileged {
+                (Some(r.id), None, None)
+            } else {
+                (Some(r.id), r.verdict, r.score)
+            }
+        })
         .unwrap_or((None, None, None));
 
     let contest_config: ContestConfig = contest::load_config(host, contest_id)?;
 
-    let can_view_full_feedback = can_view_privileged_submission_feedback(&req)
+    let can_view_full_feedback = privileged
         || match last_submission_id {
             Some(sid) => {
                 tokens_enabled(&contest_config)
@@ -1481,20 +1630,22 @@ fn handle_scoreboard(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpR
 
     let info = contest::check_access(host, req, contest_id)?;
     let phase = &info.phase;
+    let policy = standings::load_policy(host, req, contest_id)?;
 
     #[derive(Deserialize)]
     struct ContestProblem {
         problem_id: i32,
+        label: String,
     }
     let mut p = Params::new();
     let sql = format!(
-        "SELECT problem_id FROM contest_problem WHERE contest_id = {} ORDER BY position",
+        "SELECT problem_id, label FROM contest_problem WHERE contest_id = {} ORDER BY position",
         p.bind(contest_id)
     );
     let problems: Vec<ContestProblem> = host.db.query_with_args(&sql, &p.into_args())?;
     let problem_ids: Vec<i32> = problems.iter().map(|p| p.problem_id).collect();
 
-    let mut max_scores: HashMap<i32, f64> = HashMap::new();
+    let mut raw_max_scores: HashMap<i32, f64> = HashMap::new();
     for &pid in &problem_ids {
         let task_config: TaskConfig = serde_json::from_value(
             host.config
@@ -1515,8 +1666,24 @@ fn handle_scoreboard(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpR
         } else {
             task_config.subtasks.iter().map(|s| s.max_score).sum()
         };
-        max_scores.insert(pid, max);
+        raw_max_scores.insert(pid, max);
     }
+    let max_scores: HashMap<i32, f64> = raw_max_scores
+        .iter()
+        .map(|(&pid, &max)| {
+            let max = if policy.dynamic_scoring {
+                standings::dynamic_points(1)
+            } else {
+                policy.scale_score(pid, max, max)
+            };
+            (pid, max)
+        })
+        .collect();
+    let dynamic_points = if policy.dynamic_scoring {
+        load_dynamic_points(host, &policy, contest_id)?
+    } else {
+        HashMap::new()
+    };
 
     #[derive(Deserialize)]
     struct Participant {
@@ -1529,7 +1696,7 @@ fn handle_scoreboard(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpR
          FROM contest_user cu \
          JOIN \"user\" u ON u.id = cu.user_id \
          WHERE cu.contest_id = {} \
-         ORDER BY cu.registered_at ASC",
+         ORDER BY cu.registered_at ASC, cu.user_id ASC",
         p.bind(contest_id)
     );
     let participants: Vec<Participant> = host.db.query_with_args(&sql, &p.into_args())?;
@@ -1539,6 +1706,8 @@ fn handle_scoreboard(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpR
     struct ProblemScore {
         problem_id: i32,
         score: f64,
+        #[serde(skip)]
+        has_score: bool,
     }
 
     #[derive(Serialize)]
@@ -1562,20 +1731,25 @@ fn handle_scoreboard(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpR
         can_view_all,
         contest_config.scoreboard_visibility,
     );
-    let visible_participants: Vec<&Participant> = participants
+    let visible_participants: Vec<(usize, &Participant)> = participants
         .iter()
-        .filter(|p| full_scoreboard_visible || req.user_id() == Some(p.user_id))
+        .enumerate()
+        .filter(|(_, p)| full_scoreboard_visible || req.user_id() == Some(p.user_id))
+        .collect();
+    let visible_user_ids: Vec<i32> = visible_participants
+        .iter()
+        .map(|(_, p)| p.user_id)
         .collect();
-    let visible_user_ids: Vec<i32> = visible_participants.iter().map(|p| p.user_id).collect();
     let scoreboard_cells = load_scoreboard_cells(
         host,
         &contest_config,
+        &policy,
         contest_id,
         &visible_user_ids,
         &problem_ids,
     )?;
 
-    for participant in &visible_participants {
+    for &(position, participant) in &visible_participants {
         let mut total = 0.0;
         let mut problem_score_times = Vec::with_capacity(problem_ids.len());
         let mut prob_scores = Vec::new();
@@ -1585,13 +1759,23 @@ fn handle_scoreboard(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpR
                 .get(&(participant.user_id, pid))
                 .copied()
                 .unwrap_or_default();
-            let score = cell.score;
+            let score = if policy.dynamic_scoring {
+                dynamic_points
+                    .get(&(participant.user_id, pid))
+                    .copied()
+                    .unwrap_or(0.0)
+            } else {
+                let raw_total = raw_max_scores.get(&pid).copied().unwrap_or(0.0);
+                policy.scale_score(pid, cell.score, raw_total)
+            };
             let score_time_seconds = cell.score_time_seconds;
             total += score;
             problem_score_times.push(score_time_seconds);
+            let key = (participant.user_id, pid);
             prob_scores.push(ProblemScore {
                 problem_id: pid,
                 score: round_score(score),
+                has_score: scoreboard_cells.contains_key(&key) || dynamic_points.contains_key(&key),
             });
         }
         let total_time_seconds =
@@ -1605,7 +1789,7 @@ fn handle_scoreboard(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpR
         entries.push(RankEntry {
             rank: 0,
             user_id: participant.user_id,
-            username: participant.username.clone(),
+            username: policy.display_name(participant.user_id, &participant.username, position + 1),
             total_score: round_score(total),
             total_time_seconds,
             problems,
@@ -1640,6 +1824,53 @@ fn handle_scoreboard(host: &Host, req: &PluginHttpRequest) -> Result<PluginHttpR
             entries[i].rank = i + 1;
         }
     }
+    policy.truncate(&mut entries);
+
+    if standings::wants_table(req) {
+        let attempt_stats = load_attempt_stats(host, &policy, contest_id, &visible_user_ids)?;
+        let rows = entries
+            .iter()
+            .map(|entry| {
+                let cells: Vec<standings::StandingsTableCell> = match &entry.problems {
+                    Some(scores) => scores
+                        .iter()
+                        .map(|ps| {
+                            let (attempts, solve_time_ms) = attempt_stats
+                                .get(&(entry.user_id, ps.problem_id))
+                                .copied()
+                                .unwrap_or_default();
+                            standings::StandingsTableCell {
+                                score: ps.has_score.then_some(ps.score),
+                                attempts,
+                                solve_time_ms,
+                                penalty: 0,
+                            }
+                        })
+                        .collect(),
+                    None => vec![Default::default(); problems.len()],
+                };
+                let solved = cells.iter().filter(|c| c.solve_time_ms.is_some()).count();
+                standings::StandingsTableRow {
+                    rank: entry.rank,
+                    user_id: entry.user_id,
+                    username: entry.username.clone(),
+                    cells,
+                    total: entry.total_score,
+                    solved,
+                    penalty: 0,
+                }
+            })
+            .collect();
+        let table = standings::StandingsTable {
+            labels: problems.into_iter().map(|p| p.label).collect(),
+            rows,
+        };
+        return Ok(PluginHttpResponse {
+            status: 200,
+            headers: None,
+            body: Some(serde_json::to_value(table)?),
+        });
+    }
 
     Ok(PluginHttpResponse {
         status: 200,
@@ -1679,10 +1910,13 @@ fn handle_submission_subtask_scores(
     struct SubInfo {
         problem_id: i32,
         user_id: i32,
+        withheld: bool,
     }
     let mut p = Params::new();
     let sql = format!(
-        "SELECT problem_id, user_id FROM submission WHERE id = {} AND contest_id = {}",
+        "SELECT s.problem_id, s.user_id, {} AS withheld FROM submission s \
+         WHERE s.id = {} AND s.contest_id = {}",
+        contest::results_withheld("s"),
         p.bind(submission_id),
         p.bind(contest_id)
     );
@@ -1715,11 +1949,12 @@ fn handle_submission_subtask_scores(
         || (tokens_enabled(&contest_config)
             && viewer_has_token_feedback_for_submission(host, &req, contest_id, submission_id)?);
 
-    let can_view_subtask_scores = can_view_full_feedback
-        || matches!(
-            contest_config.feedback_level,
-            FeedbackLevel::Full | FeedbackLevel::SubtaskScores
-        );
+    let can_view_subtask_scores = (can_view_all_submissions || !sub_info.withheld)
+        && (can_view_full_feedback
+            || matches!(
+                contest_config.feedback_level,
+                FeedbackLevel::Full | FeedbackLevel::SubtaskScores
+            ));
 
     let subtasks = if can_view_subtask_scores {
         let task_config = load_task_config(host, contest_id, problem_id)?;
@@ -1951,4 +2186,25 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn subtask_breakdown_is_hidden_below_subtask_scores_feedback() {
+        let submission = serde_json::json!({
+            "result": {
+                "score": 75.0,
+                "test_case_results": [],
+                "subtasks": [{"name": "Small", "score": 20.0, "max_score": 20.0, "verdict": "Accepted"}]
+            }
+        });
+
+        let mut shown = submission.clone();
+        redact_submission_for_level(&mut shown, FeedbackLevel::SubtaskScores);
+        assert_eq!(shown["result"]["subtasks"][0]["score"], 20.0);
+
+        for level in [FeedbackLevel::TotalOnly, FeedbackLevel::None] {
+            let mut hidden = submission.clone();
+            redact_submission_for_level(&mut hidden, level);
+            assert_eq!(hidden["result"]["subtasks"], serde_json::json!([]));
+        }
+    }
 }
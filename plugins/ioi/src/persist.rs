@@ -11,6 +11,7 @@ pub fn persist_results(
     judge_epoch: i32,
     outcomes: &[EvalOutcome],
     submission_score: f64,
+    subtasks: Vec<SubtaskScore>,
 ) -> Result<OnSubmissionOutput, SdkError> {
     let non_skipped: Vec<_> = outcomes
         .iter()
@@ -43,6 +44,8 @@ pub fn persist_results(
         None
     };
 
+    let judged_by = outcomes.iter().find_map(|o| o.worker_id.clone());
+
     let affected = host.submission.update(&SubmissionUpdate {
         submission_id,
         judgement_id,
@@ -55,6 +58,8 @@ pub fn persist_results(
         compile_output: Some(compile_output),
         error_code: None,
         error_message: None,
+        judged_by: Some(judged_by),
+        subtasks: Some(subtasks),
     })?;
 
     if affected == 0 {
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(test)]
 use broccoli_server_sdk::types::TestCaseBodyRef;
@@ -93,6 +93,23 @@ pub fn score_all_subtasks(
         .collect()
 }
 
+/// Labels of cases that earn nothing because every subtask containing them
+/// is all-or-nothing (`GroupMin`) and failed.
+pub fn unawarded_labels(defs: &[SubtaskDef], results: &[SubtaskResult]) -> HashSet<String> {
+    let mut failed = HashSet::new();
+    let mut awarded = HashSet::new();
+    for (def, result) in defs.iter().zip(results) {
+        let labels = def.test_cases.iter().cloned();
+        if def.scoring_method == SubtaskScoringMethod::GroupMin && result.score <= 0.0 {
+            failed.extend(labels);
+        } else {
+            awarded.extend(labels);
+        }
+    }
+    failed.retain(|label| !awarded.contains(label));
+    failed
+}
+
 /// Build a single default subtask containing all test cases with Sum scoring.
 ///
 /// Used when no subtask definitions are configured.
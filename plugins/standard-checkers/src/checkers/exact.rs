@@ -1,13 +1,15 @@
 use broccoli_server_sdk::types::*;
 
-use crate::util::diff_preview;
+use crate::util::{accepted_outputs, diff_preview};
 
-/// True byte-exact comparison.
+/// True byte-exact comparison against the expected output or any of the
+/// configured `accepted_outputs`.
 pub fn check(req: &CheckerParseInput) -> Result<CheckerVerdict, String> {
     let actual = req.stdout.inline_text();
     let expected = req.expected_output.inline_text();
+    let alternatives = accepted_outputs(req.config.as_ref())?;
 
-    if actual == expected {
+    if actual == expected || alternatives.iter().any(|a| a == actual) {
         Ok(CheckerVerdict {
             verdict: Verdict::Accepted,
             score: 1.0,
@@ -25,7 +27,8 @@ pub fn check(req: &CheckerParseInput) -> Result<CheckerVerdict, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::checkers::input;
+    use crate::checkers::{input, input_with_config};
+    use serde_json::json;
 
     #[test]
     fn identical_accepted() {
@@ -49,6 +52,23 @@ mod tests {
         assert_eq!(v.verdict, Verdict::Accepted);
     }
 
+    #[test]
+    fn any_accepted_output_accepted() {
+        let config = json!({ "accepted_outputs": ["1 0\n", "NO\n"] });
+        let v = check(&input_with_config("1 0\n", "0 1\n", config.clone())).unwrap();
+        assert_eq!(v.verdict, Verdict::Accepted);
+        let v = check(&input_with_config("0 1\n", "0 1\n", config.clone())).unwrap();
+        assert_eq!(v.verdict, Verdict::Accepted);
+        let v = check(&input_with_config("YES\n", "0 1\n", config)).unwrap();
+        assert_eq!(v.verdict, Verdict::WrongAnswer);
+    }
+
+    #[test]
+    fn malformed_accepted_outputs_rejected() {
+        let config = json!({ "accepted_outputs": "1 0" });
+        assert!(check(&input_with_config("1 0", "0 1", config)).is_err());
+    }
+
     #[test]
     fn empty_vs_nonempty() {
         let req = input("", "hello");
@@ -1,13 +1,19 @@
 use broccoli_server_sdk::types::*;
 
-use crate::util::{line_count_msg, line_mismatch_msg, split_lines_trimmed};
+use crate::util::{ignore_blank_lines, line_count_msg, line_mismatch_msg, split_lines_trimmed};
 
 /// Per-line comparison with trailing whitespace normalization.
 ///
-/// Trailing empty lines are ignored from both sides.
+/// Trailing empty lines are ignored from both sides. With
+/// `{"ignore_blank_lines": true}` in the config, all empty lines are.
 pub fn check(req: &CheckerParseInput) -> Result<CheckerVerdict, String> {
-    let expected = split_lines_trimmed(req.expected_output.inline_text());
-    let actual = split_lines_trimmed(req.stdout.inline_text());
+    let skip_blank = ignore_blank_lines(req.config.as_ref())?;
+    let mut expected = split_lines_trimmed(req.expected_output.inline_text());
+    let mut actual = split_lines_trimmed(req.stdout.inline_text());
+    if skip_blank {
+        expected.retain(|l| !l.is_empty());
+        actual.retain(|l| !l.is_empty());
+    }
 
     if expected.len() != actual.len() {
         return Ok(CheckerVerdict {
@@ -37,7 +43,7 @@ pub fn check(req: &CheckerParseInput) -> Result<CheckerVerdict, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::checkers::input;
+    use crate::checkers::{input, input_with_config};
 
     #[test]
     fn trailing_whitespace_per_line_ignored() {
@@ -68,6 +74,44 @@ mod tests {
         assert!(v.message.unwrap().contains("Line count mismatch"));
     }
 
+    #[test]
+    fn interspersed_blank_lines_differ_by_default() {
+        let req = input("a\n\nb\n\n\nc\n", "a\nb\nc\n");
+        let v = check(&req).unwrap();
+        assert_eq!(v.verdict, Verdict::WrongAnswer);
+    }
+
+    #[test]
+    fn interspersed_blank_lines_ignored_when_configured() {
+        let req = input_with_config(
+            "\na\n\nb\n  \n\nc\n",
+            "a\nb\nc\n",
+            serde_json::json!({"ignore_blank_lines": true}),
+        );
+        let v = check(&req).unwrap();
+        assert_eq!(v.verdict, Verdict::Accepted);
+
+        let req = input_with_config(
+            "a\n\nx\n",
+            "a\nb\n",
+            serde_json::json!({"ignore_blank_lines": true}),
+        );
+        let v = check(&req).unwrap();
+        assert_eq!(v.verdict, Verdict::WrongAnswer);
+        assert!(v.message.unwrap().contains("Line 2"));
+    }
+
+    #[test]
+    fn non_boolean_ignore_blank_lines_rejected() {
+        let req = input_with_config(
+            "a\n",
+            "a\n",
+            serde_json::json!({"ignore_blank_lines": "yes"}),
+        );
+        let err = check(&req).unwrap_err();
+        assert!(err.contains("Invalid checker config"));
+    }
+
     #[test]
     fn first_diff_line_reported() {
         let req = input("a\nX\nc\n", "a\nb\nc\n");
@@ -383,6 +383,8 @@ pub fn dispatch_testlib_checker(host: &Host, req: &CheckerParseInput) -> Checker
         channels: vec![],
         priority: None,
         target_worker_id: None,
+        contest_id: None,
+        judge_job: None,
     }];
 
     let batch_id = match host.operations.start_batch(&operations) {
@@ -82,12 +82,45 @@ pub fn check_exact(input: String) -> FnResult<String> {
     let host = Host::new();
     let req: CheckerParseInput = serde_json::from_str(&input)?;
     let verdict = match check_streaming_if_blob(&host, &req, StreamingFormat::Exact)? {
+        Some(verdict) if verdict.verdict != Verdict::Accepted => {
+            check_accepted_outputs(&host, &req, verdict)?
+        }
         Some(verdict) => verdict,
         None => checkers::exact::check(&req).map_err(extism_pdk::Error::msg)?,
     };
     Ok(serde_json::to_string(&verdict)?)
 }
 
+/// Retries a rejected blob-backed exact comparison against each of the
+/// test case's `accepted_outputs`, keeping `rejected` if none matches.
+#[cfg(target_arch = "wasm32")]
+fn check_accepted_outputs(
+    host: &Host,
+    req: &CheckerParseInput,
+    rejected: CheckerVerdict,
+) -> Result<CheckerVerdict, extism_pdk::Error> {
+    let alternatives =
+        util::accepted_outputs(req.config.as_ref()).map_err(extism_pdk::Error::msg)?;
+    for alternative in alternatives {
+        let expected = Box::new(MemoryByteSource::new(
+            alternative.into_bytes(),
+            STREAMING_CHECKER_CHUNK_BYTES,
+        ));
+        let actual = streaming_source(host, &req.stdout);
+        let verdict = streaming::check_streaming(
+            StreamingFormat::Exact,
+            expected,
+            actual,
+            req.config.as_ref(),
+        )
+        .map_err(extism_pdk::Error::msg)?;
+        if verdict.verdict == Verdict::Accepted {
+            return Ok(verdict);
+        }
+    }
+    Ok(rejected)
+}
+
 #[cfg(target_arch = "wasm32")]
 #[plugin_fn]
 pub fn check_lines(input: String) -> FnResult<String> {
@@ -1,10 +1,12 @@
 use std::collections::VecDeque;
+use std::hash::{DefaultHasher, Hasher};
 
 use broccoli_server_sdk::types::{CheckerVerdict, Verdict};
 use serde_json::Value;
 
 use crate::util::{
-    line_count_msg, line_mismatch_msg, token_count_msg, token_mismatch_msg, truncate,
+    ignore_blank_lines, line_count_msg, line_mismatch_msg, token_count_msg, token_mismatch_msg,
+    truncate,
 };
 
 #[derive(serde::Deserialize)]
@@ -126,15 +128,62 @@ pub enum StreamingFormat {
     TokensFloat,
 }
 
-pub fn check_streaming(
+/// Checker config key opting a problem into hash comparison: outputs larger
+/// than this many bytes are compared by hashing their normalized form
+/// instead of token by token. Unset or `0` leaves every output streamed
+/// with mismatch diagnostics.
+const MAX_COMPARE_BYTES_KEY: &str = "max_compare_bytes";
+
+fn max_compare_bytes(config: Option<&Value>) -> Result<Option<usize>, String> {
+    let Some(value) = config.and_then(|v| v.get(MAX_COMPARE_BYTES_KEY)) else {
+        return Ok(None);
+    };
+    match value.as_u64() {
+        Some(0) => Ok(None),
+        Some(n) => Ok(Some(usize::try_from(n).unwrap_or(usize::MAX))),
+        None => Err(format!(
+            "Invalid checker config: {MAX_COMPARE_BYTES_KEY} must be a non-negative integer"
+        )),
+    }
+}
+
+pub fn check_streaming<'a>(
     format: StreamingFormat,
-    expected: Box<dyn ByteSource + '_>,
-    actual: Box<dyn ByteSource + '_>,
+    expected: Box<dyn ByteSource + 'a>,
+    actual: Box<dyn ByteSource + 'a>,
     config: Option<&Value>,
 ) -> Result<CheckerVerdict, String> {
+    // Float tolerances cannot be checked through a hash, so tokens-float
+    // always compares token by token.
+    let cap = match format {
+        StreamingFormat::TokensFloat => None,
+        _ => max_compare_bytes(config)?,
+    };
+    let skip_blank = match format {
+        StreamingFormat::Lines => ignore_blank_lines(config)?,
+        _ => false,
+    };
+    let (expected, actual): (Box<dyn ByteSource + 'a>, Box<dyn ByteSource + 'a>) = match cap {
+        Some(cap) => {
+            let expected = PrefetchedSource::new(expected, cap)?;
+            let actual = PrefetchedSource::new(actual, cap)?;
+            if expected.exceeds(cap) || actual.exceeds(cap) {
+                return compare_hashed(
+                    &format,
+                    Box::new(expected),
+                    Box::new(actual),
+                    cap,
+                    skip_blank,
+                );
+            }
+            (Box::new(expected), Box::new(actual))
+        }
+        None => (expected, actual),
+    };
+
     match format {
         StreamingFormat::Exact => compare_exact(expected, actual),
-        StreamingFormat::Lines => compare_lines(expected, actual),
+        StreamingFormat::Lines => compare_lines(expected, actual, skip_blank),
         StreamingFormat::Tokens => compare_tokens(expected, actual, TokenMode::Exact),
         StreamingFormat::TokensCaseInsensitive => {
             compare_tokens(expected, actual, TokenMode::CaseInsensitive)
@@ -157,6 +206,113 @@ pub fn check_streaming(
     }
 }
 
+/// A source whose first bytes were read ahead to learn whether it is
+/// larger than the comparison cap.
+struct PrefetchedSource<'a> {
+    prefix: Vec<u8>,
+    rest: Box<dyn ByteSource + 'a>,
+}
+
+impl<'a> PrefetchedSource<'a> {
+    /// Reads until more than `cap` bytes are buffered or the source ends.
+    fn new(mut rest: Box<dyn ByteSource + 'a>, cap: usize) -> Result<Self, String> {
+        let mut prefix = Vec::new();
+        while prefix.len() <= cap {
+            match rest.next_chunk()? {
+                Some(chunk) => prefix.extend(chunk),
+                None => break,
+            }
+        }
+        Ok(Self { prefix, rest })
+    }
+
+    fn exceeds(&self, cap: usize) -> bool {
+        self.prefix.len() > cap
+    }
+}
+
+impl ByteSource for PrefetchedSource<'_> {
+    fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, String> {
+        if !self.prefix.is_empty() {
+            return Ok(Some(std::mem::take(&mut self.prefix)));
+        }
+        self.rest.next_chunk()
+    }
+}
+
+fn compare_hashed(
+    format: &StreamingFormat,
+    expected: Box<dyn ByteSource + '_>,
+    actual: Box<dyn ByteSource + '_>,
+    cap: usize,
+    skip_blank: bool,
+) -> Result<CheckerVerdict, String> {
+    if normalized_hash(format, expected, skip_blank)?
+        == normalized_hash(format, actual, skip_blank)?
+    {
+        Ok(accepted())
+    } else {
+        Ok(wrong(format!(
+            "Output differs from expected (compared by hash, output exceeds {cap} bytes)"
+        )))
+    }
+}
+
+/// Hashes what the format's comparator would compare: raw bytes for
+/// `Exact`, trailing-whitespace-trimmed lines for `Lines`, and
+/// whitespace-separated tokens for the token formats. `skip_blank` drops
+/// empty lines for `Lines`.
+fn normalized_hash(
+    format: &StreamingFormat,
+    source: Box<dyn ByteSource + '_>,
+    skip_blank: bool,
+) -> Result<u64, String> {
+    let mut hasher = DefaultHasher::new();
+    match format {
+        StreamingFormat::Exact => {
+            let mut source = source;
+            while let Some(chunk) = source.next_chunk()? {
+                hasher.write(&chunk);
+            }
+        }
+        StreamingFormat::Lines => {
+            let mut lines = LineStream::new(source, skip_blank);
+            while let Some(line) = lines.next_line()? {
+                hasher.write(line.as_bytes());
+                hasher.write_u8(b'\n');
+            }
+        }
+        StreamingFormat::Tokens | StreamingFormat::TokensCaseInsensitive => {
+            let lowercase = matches!(format, StreamingFormat::TokensCaseInsensitive);
+            let mut chars = CharStream::new(source);
+            let mut in_token = false;
+            let mut seen_token = false;
+            while let Some(ch) = chars.next_char()? {
+                if ch.is_whitespace() {
+                    in_token = false;
+                    continue;
+                }
+                if !in_token && seen_token {
+                    hasher.write_u8(b' ');
+                }
+                in_token = true;
+                seen_token = true;
+                if lowercase {
+                    for lower in ch.to_lowercase() {
+                        hasher.write_u32(lower as u32);
+                    }
+                } else {
+                    hasher.write_u32(ch as u32);
+                }
+            }
+        }
+        StreamingFormat::TokensFloat => {
+            return Err("tokens-float outputs cannot be compared by hash".to_string());
+        }
+    }
+    Ok(hasher.finish())
+}
+
 fn accepted() -> CheckerVerdict {
     CheckerVerdict {
         verdict: Verdict::Accepted,
@@ -420,15 +576,19 @@ struct LineStream<'a> {
     current: String,
     pending_empty: usize,
     queued: VecDeque<String>,
+    skip_blank: bool,
 }
 
 impl<'a> LineStream<'a> {
-    fn new(source: Box<dyn ByteSource + 'a>) -> Self {
+    /// Yields trimmed lines without trailing empty ones, or without any
+    /// empty ones when `skip_blank` is set.
+    fn new(source: Box<dyn ByteSource + 'a>, skip_blank: bool) -> Self {
         Self {
             chars: CharStream::new(source),
             current: String::new(),
             pending_empty: 0,
             queued: VecDeque::new(),
+            skip_blank,
         }
     }
 
@@ -441,7 +601,9 @@ impl<'a> LineStream<'a> {
                 Some(line) => {
                     let line = line.trim_end().to_string();
                     if line.is_empty() {
-                        self.pending_empty += 1;
+                        if !self.skip_blank {
+                            self.pending_empty += 1;
+                        }
                         continue;
                     }
                     for _ in 0..self.pending_empty {
@@ -479,9 +641,10 @@ impl<'a> LineStream<'a> {
 fn compare_lines(
     expected: Box<dyn ByteSource + '_>,
     actual: Box<dyn ByteSource + '_>,
+    skip_blank: bool,
 ) -> Result<CheckerVerdict, String> {
-    let mut expected = LineStream::new(expected);
-    let mut actual = LineStream::new(actual);
+    let mut expected = LineStream::new(expected, skip_blank);
+    let mut actual = LineStream::new(actual, skip_blank);
     let mut line_no = 1usize;
 
     loop {
@@ -577,6 +740,48 @@ mod tests {
         assert_eq!(verdict.verdict, Verdict::Accepted);
     }
 
+    #[test]
+    fn lines_checker_ignores_interspersed_blank_lines_only_when_configured() {
+        let run = |config: Option<&Value>| {
+            check_streaming(
+                StreamingFormat::Lines,
+                Box::new(MemoryByteSource::new(b"a\n\n\nb\n \nc\n".to_vec(), 2)),
+                Box::new(MemoryByteSource::new(b"a\nb\nc".to_vec(), 3)),
+                config,
+            )
+            .unwrap()
+            .verdict
+        };
+
+        assert_eq!(run(None), Verdict::WrongAnswer);
+        assert_eq!(
+            run(Some(&serde_json::json!({ "ignore_blank_lines": true }))),
+            Verdict::Accepted
+        );
+    }
+
+    #[test]
+    fn hashed_lines_comparison_ignores_blank_lines_when_configured() {
+        let expected = b"line\n".repeat(4096);
+        let actual = b"line\n\n".repeat(4096);
+        let run = |ignore: bool| {
+            check_streaming(
+                StreamingFormat::Lines,
+                Box::new(MemoryByteSource::new(expected.clone(), 1024)),
+                Box::new(MemoryByteSource::new(actual.clone(), 1024)),
+                Some(&serde_json::json!({
+                    "max_compare_bytes": 1024,
+                    "ignore_blank_lines": ignore,
+                })),
+            )
+            .unwrap()
+            .verdict
+        };
+
+        assert_eq!(run(false), Verdict::WrongAnswer);
+        assert_eq!(run(true), Verdict::Accepted);
+    }
+
     #[test]
     fn float_checker_uses_tolerance_config() {
         let verdict = check_streaming(
@@ -602,4 +807,87 @@ mod tests {
 
         assert!(err.contains("Invalid checker config"));
     }
+
+    fn large_output(size: usize) -> Vec<u8> {
+        b"1234567 ".iter().copied().cycle().take(size).collect()
+    }
+
+    #[test]
+    fn large_identical_output_compares_equal_by_hash() {
+        let output = large_output(4 * 1024 * 1024);
+
+        for format in [
+            StreamingFormat::Exact,
+            StreamingFormat::Lines,
+            StreamingFormat::Tokens,
+            StreamingFormat::TokensCaseInsensitive,
+        ] {
+            let verdict = check_streaming(
+                format,
+                Box::new(MemoryByteSource::new(output.clone(), 64 * 1024)),
+                Box::new(MemoryByteSource::new(output.clone(), 48 * 1024)),
+                Some(&serde_json::json!({ "max_compare_bytes": 1024 * 1024 })),
+            )
+            .unwrap();
+
+            assert_eq!(verdict.verdict, Verdict::Accepted);
+        }
+    }
+
+    #[test]
+    fn large_output_differing_by_one_byte_compares_unequal_by_hash() {
+        let expected = large_output(4 * 1024 * 1024);
+        let mut actual = expected.clone();
+        let last = actual.len() - 2;
+        actual[last] = b'8';
+
+        let verdict = check_streaming(
+            StreamingFormat::Tokens,
+            Box::new(MemoryByteSource::new(expected, 64 * 1024)),
+            Box::new(MemoryByteSource::new(actual, 64 * 1024)),
+            Some(&serde_json::json!({ "max_compare_bytes": 1024 * 1024 })),
+        )
+        .unwrap();
+
+        assert_eq!(verdict.verdict, Verdict::WrongAnswer);
+        assert!(verdict.message.unwrap().contains("compared by hash"));
+    }
+
+    #[test]
+    fn large_output_is_streamed_with_diagnostics_without_a_cap() {
+        let expected = large_output(4 * 1024 * 1024);
+        let mut actual = expected.clone();
+        let last = actual.len() - 2;
+        actual[last] = b'8';
+
+        let verdict = check_streaming(
+            StreamingFormat::Tokens,
+            Box::new(MemoryByteSource::new(expected, 64 * 1024)),
+            Box::new(MemoryByteSource::new(actual, 64 * 1024)),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(verdict.verdict, Verdict::WrongAnswer);
+        assert!(!verdict.message.unwrap().contains("compared by hash"));
+    }
+
+    #[test]
+    fn hashed_token_comparison_ignores_whitespace_layout() {
+        let expected = large_output(2 * 1024 * 1024);
+        let actual: Vec<u8> = expected
+            .iter()
+            .map(|&b| if b == b' ' { b'\n' } else { b })
+            .collect();
+
+        let verdict = check_streaming(
+            StreamingFormat::Tokens,
+            Box::new(MemoryByteSource::new(expected, 64 * 1024)),
+            Box::new(MemoryByteSource::new(actual, 64 * 1024)),
+            Some(&serde_json::json!({ "max_compare_bytes": 1024 })),
+        )
+        .unwrap();
+
+        assert_eq!(verdict.verdict, Verdict::Accepted);
+    }
 }
@@ -22,6 +22,33 @@ pub fn split_lines_trimmed(s: &str) -> Vec<&str> {
     lines
 }
 
+const IGNORE_BLANK_LINES_KEY: &str = "ignore_blank_lines";
+
+/// The `ignore_blank_lines` checker config key: when true, the lines
+/// checker drops every empty (or whitespace-only) line before comparing.
+pub fn ignore_blank_lines(config: Option<&serde_json::Value>) -> Result<bool, String> {
+    match config.and_then(|v| v.get(IGNORE_BLANK_LINES_KEY)) {
+        None => Ok(false),
+        Some(value) => value.as_bool().ok_or_else(|| {
+            format!("Invalid checker config: {IGNORE_BLANK_LINES_KEY} must be a boolean")
+        }),
+    }
+}
+
+const ACCEPTED_OUTPUTS_KEY: &str = "accepted_outputs";
+
+/// The `accepted_outputs` checker config key: outputs the exact checker
+/// accepts besides the expected output. The host fills it in from the test
+/// case.
+pub fn accepted_outputs(config: Option<&serde_json::Value>) -> Result<Vec<String>, String> {
+    match config.and_then(|v| v.get(ACCEPTED_OUTPUTS_KEY)) {
+        None | Some(serde_json::Value::Null) => Ok(Vec::new()),
+        Some(value) => serde_json::from_value(value.clone()).map_err(|_| {
+            format!("Invalid checker config: {ACCEPTED_OUTPUTS_KEY} must be an array of strings")
+        }),
+    }
+}
+
 pub fn token_count_msg(expected: usize, actual: usize) -> String {
     format!("Token count mismatch: expected {expected} tokens, got {actual}")
 }
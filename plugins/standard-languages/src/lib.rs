@@ -68,7 +68,7 @@ pub fn resolve_standard_language(input: String) -> FnResult<String> {
         .or(lang_config.flags.as_deref())
         .unwrap_or(&[]);
 
-    let result = match req.language_id.as_str() {
+    let mut result = match req.language_id.as_str() {
         "c" => resolve::resolve_c(
             &req,
             entry_point_config.as_ref(),
@@ -105,9 +105,82 @@ pub fn resolve_standard_language(input: String) -> FnResult<String> {
         }
     };
 
+    let env_rules = resolve::env_rules(lang_config.env.as_deref().unwrap_or(&[]));
+    if let Some(compile) = result.compile.as_mut() {
+        compile.env_rules = env_rules.clone();
+        compile.time_limit = lang_config.compile_time_limit;
+    }
+    result.run.env_rules = env_rules;
+
     Ok(serde_json::to_string(&result)?)
 }
 
+#[cfg(target_arch = "wasm32")]
+#[derive(Deserialize)]
+struct AdditionalFilename {
+    filename: String,
+}
+
+/// `before_submission` hook: rejects a submission when the problem declares
+/// an entry point for its language that is neither submitted nor provided as
+/// an additional file, which would otherwise only surface as a compile error.
+#[cfg(target_arch = "wasm32")]
+#[plugin_fn]
+pub fn check_entry_point(input: String) -> FnResult<String> {
+    let host = Host::new();
+    let event: BeforeSubmissionEvent = serde_json::from_str(&input)?;
+
+    // Older servers do not send filenames; nothing to validate against.
+    if event.filenames.is_empty() {
+        return Ok(serde_json::to_string(
+            &serde_json::json!({"action": "pass"}),
+        )?);
+    }
+
+    let entry_point_config =
+        load_entry_point_config(&host, Some(event.problem_id), &event.language);
+    let Some(entry_point) = entry_point_config
+        .as_ref()
+        .and_then(|c| c.entry_point.as_deref())
+        .filter(|ep| !ep.trim().is_empty())
+    else {
+        return Ok(serde_json::to_string(
+            &serde_json::json!({"action": "pass"}),
+        )?);
+    };
+
+    let mut p = Params::new();
+    let sql = format!(
+        "SELECT filename FROM additional_file WHERE problem_id = {} AND language = {}",
+        p.bind(event.problem_id),
+        p.bind(event.language.as_str()),
+    );
+    let additional: Vec<String> = host
+        .db
+        .query_with_args::<AdditionalFilename>(&sql, &p.into_args())?
+        .into_iter()
+        .map(|r| r.filename)
+        .collect();
+
+    if resolve::entry_point_present(entry_point, &event.filenames, &additional) {
+        return Ok(serde_json::to_string(
+            &serde_json::json!({"action": "pass"}),
+        )?);
+    }
+
+    let resp = serde_json::json!({
+        "action": "reject",
+        "code": "ENTRY_POINT_MISSING",
+        "message": format!("Submission must include the entry point file '{entry_point}'"),
+        "status_code": 400,
+        "details": {
+            "entry_point": entry_point,
+            "language": event.language,
+        }
+    });
+    Ok(serde_json::to_string(&resp)?)
+}
+
 /// Load per-language compilation config from the cascade.
 #[cfg(target_arch = "wasm32")]
 fn load_lang_config(
@@ -163,6 +236,11 @@ struct LanguageCompilationConfig {
     interpreter: Option<String>,
     runner: Option<String>,
     flags: Option<Vec<String>>,
+    /// Compile step CPU time limit in seconds.
+    compile_time_limit: Option<f64>,
+    /// `NAME=value` pairs set in the sandbox. Only allowlisted names survive
+    /// evaluation; see `sanitize_untrusted_env_rules`.
+    env: Option<Vec<String>>,
 }
 
 /// Per-problem, per-language entry point config.
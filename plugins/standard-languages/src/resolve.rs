@@ -1,5 +1,5 @@
 use broccoli_server_sdk::types::{
-    CompileSpec, OutputSpec, ResolveLanguageInput, ResolveLanguageOutput, RunSpec,
+    CompileSpec, EnvRule, OutputSpec, ResolveLanguageInput, ResolveLanguageOutput, RunSpec,
 };
 use std::path::Path;
 
@@ -93,6 +93,15 @@ fn resolve_primary<'a>(
     (primary, basename)
 }
 
+/// Whether a declared entry point is among the submitted or additional files.
+pub fn entry_point_present(entry_point: &str, submitted: &[String], additional: &[String]) -> bool {
+    let entry_point = entry_point.trim();
+    submitted
+        .iter()
+        .chain(additional.iter())
+        .any(|f| f.trim() == entry_point)
+}
+
 fn collect_files<'a>(req: &'a ResolveLanguageInput) -> Vec<&'a str> {
     req.submitted_files
         .iter()
@@ -138,14 +147,30 @@ fn resolve_compiled(
             cache_inputs,
             outputs: vec![OutputSpec::File(basename.clone())],
             resource_limits: None,
+            time_limit: None,
+            env_rules: vec![],
         }),
         run: RunSpec {
             command: vec![format!("./{basename}")],
             extra_files: vec![],
+            env_rules: vec![],
         },
     }
 }
 
+/// Parses `NAME=value` entries from the language `env` config into
+/// `Set` rules. Entries without `=` or with an empty name are ignored.
+pub fn env_rules(entries: &[String]) -> Vec<EnvRule> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let (name, value) = entry.split_once('=')?;
+            let name = name.trim();
+            (!name.is_empty()).then(|| EnvRule::Set(name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
 pub fn resolve_c(
     req: &ResolveLanguageInput,
     entry_point_config: Option<&EntryPointConfig>,
@@ -196,6 +221,7 @@ pub fn resolve_python3(
         run: RunSpec {
             command: vec![interpreter.to_string(), primary.to_string()],
             extra_files: all_files.iter().map(|s| s.to_string()).collect(),
+            env_rules: vec![],
         },
     }
 }
@@ -231,10 +257,13 @@ pub fn resolve_java(
             // javac may produce multiple .class files (inner classes)
             outputs: vec![OutputSpec::Glob("*.class".into())],
             resource_limits: None,
+            time_limit: None,
+            env_rules: vec![],
         }),
         run: RunSpec {
             command: vec![runner.to_string(), "-cp".into(), ".".into(), basename],
             extra_files: vec![],
+            env_rules: vec![],
         },
     }
 }
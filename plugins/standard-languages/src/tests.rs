@@ -1,4 +1,4 @@
-use broccoli_server_sdk::types::{FileRef, OutputSpec, ResolveLanguageInput};
+use broccoli_server_sdk::types::{EnvRule, FileRef, OutputSpec, ResolveLanguageInput};
 
 use crate::EntryPointConfig;
 use crate::resolve;
@@ -221,3 +221,41 @@ fn cpp_with_additional_file_refs() {
         vec!["solution.cpp", "grader.cpp", "grader.h"]
     );
 }
+
+#[test]
+fn env_entries_parse_into_set_rules() {
+    let rules = resolve::env_rules(&[
+        "PYTHONHASHSEED=0".into(),
+        "LANG=C.UTF-8".into(),
+        "MALFORMED".into(),
+        "=missing-name".into(),
+    ]);
+    assert_eq!(
+        rules,
+        vec![
+            EnvRule::Set("PYTHONHASHSEED".into(), "0".into()),
+            EnvRule::Set("LANG".into(), "C.UTF-8".into()),
+        ]
+    );
+}
+
+#[test]
+fn entry_point_present_checks_submitted_and_additional_files() {
+    let submitted = vec!["main.cpp".to_string(), "helper.cpp".to_string()];
+    let additional = vec!["grader.cpp".to_string()];
+    assert!(resolve::entry_point_present(
+        "main.cpp",
+        &submitted,
+        &additional
+    ));
+    assert!(resolve::entry_point_present(
+        "grader.cpp",
+        &submitted,
+        &additional
+    ));
+    assert!(!resolve::entry_point_present(
+        "solution.cpp",
+        &submitted,
+        &additional
+    ));
+}